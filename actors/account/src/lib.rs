@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
-use fvm_shared::address::Protocol;
+use fvm_shared::address::{Address, Protocol};
 use fvm_shared::crypto::signature::SignatureType::{BLS, Secp256k1};
 use fvm_shared::crypto::signature::{Signature, SignatureType};
 use fvm_shared::error::ExitCode;
@@ -12,7 +12,9 @@ use num_derive::FromPrimitive;
 use fil_actors_runtime::builtin::singletons::SYSTEM_ACTOR_ADDR;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{ActorDowncast, FIRST_EXPORTED_METHOD_NUMBER, actor_dispatch};
-use fil_actors_runtime::{ActorError, actor_error};
+use fil_actors_runtime::{
+    ActorError, ValidateSponsorParams, ValidateSponsorReturn, actor_error, sponsor,
+};
 use types::{AuthenticateMessageReturn, ConstructorParams, PubkeyAddressReturn};
 
 use crate::types::AuthenticateMessageParams;
@@ -35,6 +37,7 @@ pub enum Method {
     // Deprecated in v10
     // AuthenticateMessage = 3,
     AuthenticateMessageExported = frc42_dispatch::method_hash!("AuthenticateMessage"),
+    ValidateSponsorExported = frc42_dispatch::method_hash!("ValidateSponsor"),
 }
 
 /// Account Actor
@@ -73,15 +76,7 @@ impl Actor {
         rt.validate_immediate_caller_accept_any()?;
         let st: State = rt.state()?;
         let address = st.address;
-        let sig_type: SignatureType = match address.protocol() {
-            Protocol::Secp256k1 => Secp256k1,
-            Protocol::BLS => BLS,
-            protocol => {
-                return Err(actor_error!(illegal_state;
-                    "account address must use BLS or SECP protocol, got {}", protocol));
-            }
-        };
-        let sig = Signature { sig_type, bytes: params.signature };
+        let sig = Signature { sig_type: signature_type(&address)?, bytes: params.signature };
         rt.verify_signature(&sig, &address, &params.message).map_err(|e| {
             e.downcast_default(
                 ExitCode::USR_ILLEGAL_ARGUMENT,
@@ -92,6 +87,25 @@ impl Actor {
         Ok(AuthenticateMessageReturn { authenticated: true })
     }
 
+    /// Authorizes this account to pay for the currently executing message sent by another account,
+    /// if the signature over the sponsorship payload is valid for this account's key.
+    /// Returns false, rather than aborting, if the sponsorship is for a different message
+    /// or the signature is invalid.
+    pub fn validate_sponsor(
+        rt: &impl Runtime,
+        params: ValidateSponsorParams,
+    ) -> Result<ValidateSponsorReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let Some(payload) = sponsor::validate_sponsor(rt, &params)? else {
+            return Ok(ValidateSponsorReturn { authorized: false });
+        };
+        let st: State = rt.state()?;
+        let sig = Signature { sig_type: signature_type(&st.address)?, bytes: params.signature };
+        let authorized = rt.verify_signature(&sig, &st.address, &payload).is_ok();
+
+        Ok(ValidateSponsorReturn { authorized })
+    }
+
     /// Fallback method for unimplemented method numbers.
     pub fn fallback(
         rt: &impl Runtime,
@@ -107,6 +121,15 @@ impl Actor {
     }
 }
 
+fn signature_type(address: &Address) -> Result<SignatureType, ActorError> {
+    match address.protocol() {
+        Protocol::Secp256k1 => Ok(Secp256k1),
+        Protocol::BLS => Ok(BLS),
+        protocol => Err(actor_error!(illegal_state;
+            "account address must use BLS or SECP protocol, got {}", protocol)),
+    }
+}
+
 impl ActorCode for Actor {
     type Methods = Method;
 
@@ -118,6 +141,7 @@ impl ActorCode for Actor {
         Constructor => constructor,
         PubkeyAddress => pubkey_address,
         AuthenticateMessageExported => authenticate_message,
+        ValidateSponsorExported => validate_sponsor,
         _ => fallback,
     }
 }
@@ -12,9 +12,11 @@ use fvm_shared::error::ExitCode;
 
 use fil_actor_account::types::AuthenticateMessageParams;
 use fil_actor_account::{Actor as AccountActor, Method, State, testing::check_state_invariants};
-use fil_actors_runtime::FIRST_EXPORTED_METHOD_NUMBER;
 use fil_actors_runtime::builtin::SYSTEM_ACTOR_ADDR;
 use fil_actors_runtime::test_utils::*;
+use fil_actors_runtime::{
+    FIRST_EXPORTED_METHOD_NUMBER, ValidateSponsorParams, ValidateSponsorReturn,
+};
 
 #[test]
 fn construction() {
@@ -161,6 +163,66 @@ fn authenticate_message() {
     );
 }
 
+#[test]
+fn validate_sponsor() {
+    let rt = MockRuntime { receiver: Address::new_id(100), ..Default::default() };
+    rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+    let addr = Address::new_bls(&[1; fvm_shared::address::BLS_PUB_LEN]).unwrap();
+    rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+    rt.call::<AccountActor>(
+        Method::Constructor as MethodNum,
+        IpldBlock::serialize_cbor(&addr).unwrap(),
+    )
+    .unwrap();
+
+    let mut params = ValidateSponsorParams {
+        sponsored: Address::new_id(1234),
+        nonce: 7,
+        signature: vec![4, 5, 6],
+    };
+    rt.set_origin(params.sponsored);
+    rt.set_nonce(params.nonce);
+    let call = |params: &ValidateSponsorParams| -> bool {
+        let ret: ValidateSponsorReturn = rt
+            .call::<AccountActor>(
+                Method::ValidateSponsorExported as MethodNum,
+                IpldBlock::serialize_cbor(params).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret.authorized
+    };
+    let validate = |params: &ValidateSponsorParams, result: anyhow::Result<()>| -> bool {
+        rt.expect_validate_caller_any();
+        rt.expect_verify_signature(ExpectedVerifySig {
+            sig: Signature::new_bls(params.signature.clone()),
+            signer: addr,
+            plaintext: params.signing_payload().unwrap(),
+            result,
+        });
+        call(params)
+    };
+
+    assert!(validate(&params, Ok(())));
+    // An invalid signature declines rather than aborting.
+    assert!(!validate(&params, Err(anyhow!("bad signature"))));
+
+    // A sponsorship for another of the sender's messages is declined without checking the signature.
+    rt.expect_validate_caller_any();
+    params.nonce = 8;
+    assert!(!call(&params));
+
+    // A sponsorship for a message from another sender is declined.
+    rt.expect_validate_caller_any();
+    params.nonce = 7;
+    rt.set_origin(Address::new_id(1235));
+    assert!(!call(&params));
+    check_state(&rt);
+}
+
 #[test]
 fn test_fallback() {
     let rt = MockRuntime { receiver: Address::new_id(100), ..Default::default() };
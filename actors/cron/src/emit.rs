@@ -0,0 +1,14 @@
+// A namespace for helpers that build and emit cron actor events.
+
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+
+/// Summarises an epoch tick: the number of entries invoked and how many of those calls failed.
+/// Each entry is invoked with exactly one call.
+pub fn epoch_tick(rt: &impl Runtime, invoked: u64, failed: u64) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("cron-tick")
+        .field("invoked", &invoked)
+        .field("failed", &failed)
+        .emit(rt)
+}
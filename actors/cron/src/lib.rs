@@ -15,6 +15,7 @@ use num_traits::Zero;
 
 pub use self::state::{Entry, State};
 
+mod emit;
 mod state;
 pub mod testing;
 
@@ -56,6 +57,8 @@ impl Actor {
         rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
 
         let st: State = rt.state()?;
+        let invoked = st.entries.len() as u64;
+        let mut failed = 0;
         for entry in st.entries {
             // Intentionally ignore any error when calling cron methods
             let res = extract_send_result(rt.send_simple(
@@ -65,6 +68,7 @@ impl Actor {
                 TokenAmount::zero(),
             ));
             if let Err(e) = res {
+                failed += 1;
                 log::error!(
                     "cron failed to send entry to {}, send error code {}",
                     entry.receiver,
@@ -72,7 +76,7 @@ impl Actor {
                 );
             }
         }
-        Ok(())
+        emit::epoch_tick(rt, invoked, failed)
     }
 }
 
@@ -5,8 +5,8 @@ use std::cell::RefCell;
 
 use fil_actor_cron::testing::check_state_invariants;
 use fil_actor_cron::{Actor as CronActor, ConstructorParams, Entry, State};
-use fil_actors_runtime::SYSTEM_ACTOR_ADDR;
 use fil_actors_runtime::test_utils::*;
+use fil_actors_runtime::{EventBuilder, SYSTEM_ACTOR_ADDR};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
@@ -62,7 +62,7 @@ fn epoch_tick_with_empty_entries() {
     let rt = construct_runtime();
 
     construct_and_verify(&rt, &ConstructorParams { entries: vec![] });
-    epoch_tick_and_verify(&rt);
+    epoch_tick_and_verify(&rt, 0, 0);
 }
 
 #[test]
@@ -114,7 +114,7 @@ fn epoch_tick_with_entries() {
         ExitCode::OK,
     );
 
-    epoch_tick_and_verify(&rt);
+    epoch_tick_and_verify(&rt, 4, 1);
 }
 
 fn construct_and_verify(rt: &MockRuntime, params: &ConstructorParams) {
@@ -125,8 +125,16 @@ fn construct_and_verify(rt: &MockRuntime, params: &ConstructorParams) {
     rt.verify();
 }
 
-fn epoch_tick_and_verify(rt: &MockRuntime) {
+fn epoch_tick_and_verify(rt: &MockRuntime, invoked: u64, failed: u64) {
     rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("cron-tick")
+            .field("invoked", &invoked)
+            .field("failed", &failed)
+            .build()
+            .unwrap(),
+    );
     let ret = rt.call::<CronActor>(2, None).unwrap();
     assert!(ret.is_none());
     rt.verify();
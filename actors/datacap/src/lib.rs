@@ -18,11 +18,12 @@ use fvm_shared::{ActorID, METHOD_CONSTRUCTOR, MethodNum};
 use lazy_static::lazy_static;
 use log::info;
 use num_derive::FromPrimitive;
+use num_traits::Signed;
 
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    ActorContext, ActorError, AsActorError, SYSTEM_ACTOR_ADDR, actor_dispatch, actor_error,
-    extract_send_result,
+    ActorContext, ActorError, AsActorError, SYSTEM_ACTOR_ADDR, VersionedOperatorData,
+    actor_dispatch, actor_error, extract_send_result,
 };
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 
@@ -82,6 +83,8 @@ pub enum Method {
     BurnExported = frc42_dispatch::method_hash!("Burn"),
     BurnFromExported = frc42_dispatch::method_hash!("BurnFrom"),
     AllowanceExported = frc42_dispatch::method_hash!("Allowance"),
+    SetMintLimitsExported = frc42_dispatch::method_hash!("SetMintLimits"),
+    SetTransferReceiversExported = frc42_dispatch::method_hash!("SetTransferReceivers"),
 }
 
 pub struct Actor;
@@ -161,6 +164,25 @@ impl Actor {
                 rt.validate_immediate_caller_is(std::iter::once(&st.governor))?;
                 let operator = st.governor;
 
+                let limits = st.effective_mint_limits(rt.policy());
+                if params.amount > limits.max_per_call {
+                    return Err(actor_error!(
+                        forbidden,
+                        "mint amount {} exceeds per-call limit {}",
+                        params.amount,
+                        limits.max_per_call
+                    ));
+                }
+                if &st.token.supply + &params.amount > limits.supply_cap {
+                    return Err(actor_error!(
+                        forbidden,
+                        "mint amount {} would take supply {} over cap {}",
+                        params.amount,
+                        st.token.supply,
+                        limits.supply_cap
+                    ));
+                }
+
                 let syscalls = SyscallProvider { rt };
                 let runtime = ActorRuntime::new(&syscalls, syscalls.rt.store());
                 let mut token = as_token(st, &runtime);
@@ -193,6 +215,55 @@ impl Actor {
         as_token(&mut st, &runtime).mint_return(intermediate).actor_result()
     }
 
+    /// Sets the limits enforced on minting, overriding those of the policy,
+    /// or restores the policy limits if none are given.
+    /// Only the system actor can call this method, so that the limits can't be lifted by the
+    /// governor they guard against. No message can be sent from the system actor, so the limits
+    /// change only when a network upgrade invokes this method.
+    /// This method is not part of the fungible token standard.
+    pub fn set_mint_limits(
+        rt: &impl Runtime,
+        params: SetMintLimitsParams,
+    ) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
+        if let Some(limits) = &params.limits {
+            if limits.supply_cap.is_negative() || limits.max_per_call.is_negative() {
+                return Err(actor_error!(illegal_argument, "negative mint limits {:?}", limits));
+            }
+        }
+        rt.transaction(|st: &mut State, _| {
+            st.mint_limits = params.limits;
+            Ok(())
+        })
+    }
+
+    /// Sets the receivers, in addition to the governor, to which tokens may be transferred.
+    /// Receivers must be actors which handle the tokens they receive, else those tokens are lost.
+    /// Only the system actor can call this method, so the receivers change only when a network
+    /// upgrade invokes it.
+    /// This method is not part of the fungible token standard.
+    pub fn set_transfer_receivers(
+        rt: &impl Runtime,
+        params: SetTransferReceiversParams,
+    ) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
+        let mut receivers = params
+            .receivers
+            .iter()
+            .map(|receiver| {
+                rt.resolve_address(receiver).map(Address::new_id).ok_or_else(|| {
+                    actor_error!(illegal_argument, "failed to resolve receiver {}", receiver)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        receivers.sort_by_key(|receiver| receiver.id().unwrap());
+        receivers.dedup();
+        rt.transaction(|st: &mut State, _| {
+            st.transfer_receivers = receivers;
+            Ok(())
+        })
+    }
+
     /// Destroys data cap tokens for an address (a verified client).
     /// Only the governor can call this method.
     /// This method is not part of the fungible token standard, and is named distinctly from
@@ -214,7 +285,8 @@ impl Actor {
 
     /// Transfers data cap tokens to an address.
     /// Data cap tokens are not generally transferable.
-    /// Succeeds if the to or from address is the governor, otherwise always fails.
+    /// Succeeds if the from address is the governor or the to address is the governor or
+    /// another allowed receiver, otherwise always fails.
     pub fn transfer(
         rt: &impl Runtime,
         params: TransferParams,
@@ -227,17 +299,17 @@ impl Actor {
             .resolve_address(&params.to)
             .context_code(ExitCode::USR_ILLEGAL_ARGUMENT, "to must be ID address")?;
         let to_address = Address::new_id(to);
+        VersionedOperatorData::decode(&params.operator_data)?;
 
         let mut hook = rt
             .transaction(|st: &mut State, rt| {
-                let allowed = to_address == st.governor || *from == st.governor;
+                let allowed = st.is_allowed_receiver(&to_address) || *from == st.governor;
                 if !allowed {
                     return Err(actor_error!(
                         forbidden,
-                        "transfer not allowed from {} to {} (governor is {})",
+                        "transfer not allowed from {} to {}: not an allowed receiver",
                         from,
-                        to_address,
-                        st.governor
+                        to_address
                     ));
                 }
 
@@ -265,7 +337,8 @@ impl Actor {
 
     /// Transfers data cap tokens between addresses.
     /// Data cap tokens are not generally transferable between addresses.
-    /// Succeeds if the to address is the governor, otherwise always fails.
+    /// Succeeds if the to address is the governor or another allowed receiver,
+    /// otherwise always fails.
     pub fn transfer_from(
         rt: &impl Runtime,
         params: TransferFromParams,
@@ -278,17 +351,16 @@ impl Actor {
             .resolve_address(&params.to)
             .context_code(ExitCode::USR_ILLEGAL_ARGUMENT, "to must be an ID address")?;
         let to_address = Address::new_id(to);
+        VersionedOperatorData::decode(&params.operator_data)?;
 
         let mut hook = rt
             .transaction(|st: &mut State, rt| {
-                let allowed = to_address == st.governor;
-                if !allowed {
+                if !st.is_allowed_receiver(&to_address) {
                     return Err(actor_error!(
                         forbidden,
-                        "transfer not allowed from {} to {} (governor is {})",
+                        "transfer not allowed from {} to {}: not an allowed receiver",
                         from,
-                        to_address,
-                        st.governor
+                        to_address
                     ));
                 }
 
@@ -521,5 +593,7 @@ impl ActorCode for Actor {
         BurnExported => burn,
         BurnFromExported => burn_from,
         AllowanceExported => allowance,
+        SetMintLimitsExported => set_mint_limits,
+        SetTransferReceiversExported => set_transfer_receivers,
     }
 }
@@ -6,19 +6,48 @@ use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 
+use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::{ActorError, AsActorError};
 
+use crate::{DATACAP_GRANULARITY, MintLimits};
+
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct State {
     pub governor: Address,
     pub token: token::state::TokenState,
+    // Mint limits set at a network upgrade, overriding those of the policy.
+    // Absent from state written before mint limits, which decodes with none set.
+    #[serde(default)]
+    pub mint_limits: Option<MintLimits>,
+    // Receivers other than the governor to which tokens may be transferred, set at a network
+    // upgrade.
+    // Tokens sent to an actor which doesn't expect them are effectively burnt, so transfers are
+    // restricted to actors known to handle them.
+    // Absent from state written before the allowlist, which decodes with it empty.
+    #[serde(default)]
+    pub transfer_receivers: Vec<Address>,
 }
 
 impl State {
     pub fn new<BS: Blockstore>(store: &BS, governor: Address) -> Result<State, ActorError> {
         let token_state = token::state::TokenState::new(store)
             .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to create token state")?;
-        Ok(State { governor, token: token_state })
+        Ok(State { governor, token: token_state, mint_limits: None, transfer_receivers: vec![] })
+    }
+
+    /// Whether tokens may be transferred to an address (which must be an ID address).
+    pub fn is_allowed_receiver(&self, to: &Address) -> bool {
+        *to == self.governor || self.transfer_receivers.contains(to)
+    }
+
+    /// The mint limits in force: those set by governance, else those of the policy.
+    pub fn effective_mint_limits(&self, policy: &Policy) -> MintLimits {
+        self.mint_limits.clone().unwrap_or_else(|| MintLimits {
+            supply_cap: TokenAmount::from_atto(policy.datacap_supply_cap.clone())
+                * DATACAP_GRANULARITY,
+            max_per_call: TokenAmount::from_atto(policy.datacap_max_mint_per_call.clone())
+                * DATACAP_GRANULARITY,
+        })
     }
 
     // Visible for testing
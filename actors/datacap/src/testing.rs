@@ -1,6 +1,7 @@
 use frc46_token::token::state::StateSummary;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_shared::address::Protocol;
+use num_traits::Signed;
 
 use fil_actors_runtime::MessageAccumulator;
 
@@ -13,6 +14,18 @@ pub fn check_state_invariants<BS: Blockstore>(
 ) -> (StateSummary, MessageAccumulator) {
     let acc = MessageAccumulator::default();
     acc.require(state.governor.protocol() == Protocol::ID, "governor must be ID address");
+    if let Some(limits) = &state.mint_limits {
+        acc.require(
+            !limits.supply_cap.is_negative() && !limits.max_per_call.is_negative(),
+            format!("negative mint limits {:?}", limits),
+        );
+    }
+    for receiver in &state.transfer_receivers {
+        acc.require(
+            receiver.protocol() == Protocol::ID,
+            format!("transfer receiver {} must be ID address", receiver),
+        );
+    }
     let (summary, msgs) = state.token.check_invariants(store, DATACAP_GRANULARITY);
     for e in msgs {
         acc.add(e.to_string());
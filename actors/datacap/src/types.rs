@@ -72,6 +72,30 @@ pub struct MintParams {
     pub operators: Vec<Address>,
 }
 
+/// Limits on the minting of datacap tokens.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct MintLimits {
+    // Maximum total supply of tokens.
+    pub supply_cap: TokenAmount,
+    // Maximum amount of tokens minted by a single call.
+    pub max_per_call: TokenAmount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct SetMintLimitsParams {
+    // Limits to enforce in place of those of the policy, or None to restore the policy limits.
+    pub limits: Option<MintLimits>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct SetTransferReceiversParams {
+    // Receivers other than the governor to which tokens may be transferred.
+    // An empty list restricts transfers to the governor.
+    pub receivers: Vec<Address>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct DestroyParams {
     pub owner: Address,
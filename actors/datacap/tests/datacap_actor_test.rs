@@ -16,7 +16,7 @@ lazy_static! {
 
 mod construction {
     use crate::*;
-    use fil_actor_datacap::{Actor, DATACAP_GRANULARITY, GranularityReturn, Method};
+    use fil_actor_datacap::{Actor, DATACAP_GRANULARITY, GranularityReturn, Method, State};
     use fil_actors_runtime::VERIFIED_REGISTRY_ACTOR_ADDR;
     use fvm_shared::MethodNum;
 
@@ -57,6 +57,22 @@ mod construction {
         rt.verify();
         assert_eq!(ret, "DCAP")
     }
+
+    #[test]
+    fn decode_state_without_governance_settings() {
+        let rt = new_runtime();
+        let h = Harness { governor: VERIFIED_REGISTRY_ACTOR_ADDR };
+        h.construct_and_verify(&rt, &h.governor);
+        let st: State = rt.get_state();
+
+        // State written before mint limits and transfer receivers were added.
+        let prior = fvm_ipld_encoding::to_vec(&(&st.governor, &st.token)).unwrap();
+        let decoded: State = fvm_ipld_encoding::from_slice(&prior).unwrap();
+        assert_eq!(st.governor, decoded.governor);
+        assert_eq!(st.token, decoded.token);
+        assert_eq!(None, decoded.mint_limits);
+        assert!(decoded.transfer_receivers.is_empty());
+    }
 }
 
 mod mint {
@@ -64,11 +80,19 @@ mod mint {
     use fvm_shared::econ::TokenAmount;
     use fvm_shared::error::ExitCode;
 
-    use fil_actor_datacap::{Actor, INFINITE_ALLOWANCE, Method, MintParams};
-    use fil_actors_runtime::test_utils::{MARKET_ACTOR_CODE_ID, expect_abort_contains_message};
-    use fil_actors_runtime::{STORAGE_MARKET_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR};
+    use fil_actor_datacap::{
+        Actor, INFINITE_ALLOWANCE, Method, MintLimits, MintParams, SetMintLimitsParams, State,
+    };
+    use fil_actors_runtime::test_utils::{
+        MARKET_ACTOR_CODE_ID, SYSTEM_ACTOR_CODE_ID, VERIFREG_ACTOR_CODE_ID,
+        expect_abort_contains_message,
+    };
+    use fil_actors_runtime::{
+        ActorError, STORAGE_MARKET_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    };
     use fvm_ipld_encoding::RawBytes;
     use fvm_ipld_encoding::ipld_block::IpldBlock;
+    use num_traits::Zero;
     use std::ops::Sub;
 
     use crate::*;
@@ -126,6 +150,87 @@ mod mint {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn enforces_policy_mint_limits() {
+        let (mut rt, h) = make_harness();
+        rt.policy.datacap_max_mint_per_call = 2.into();
+        rt.policy.datacap_supply_cap = 3.into();
+
+        expect_abort_contains_message(
+            ExitCode::USR_FORBIDDEN,
+            "exceeds per-call limit",
+            h.mint(&rt, &ALICE, &TokenAmount::from_whole(3), vec![]),
+        );
+        rt.reset();
+
+        h.mint(&rt, &ALICE, &TokenAmount::from_whole(2), vec![]).unwrap();
+        expect_abort_contains_message(
+            ExitCode::USR_FORBIDDEN,
+            "over cap",
+            h.mint(&rt, &BOB, &TokenAmount::from_whole(2), vec![]),
+        );
+        rt.reset();
+        h.mint(&rt, &BOB, &TokenAmount::from_whole(1), vec![]).unwrap();
+        assert_eq!(TokenAmount::from_whole(3), h.get_supply(&rt));
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn governance_overrides_mint_limits() {
+        let (rt, h) = make_harness();
+        let limits =
+            MintLimits { supply_cap: TokenAmount::zero(), max_per_call: TokenAmount::zero() };
+
+        // Only the system actor may set the limits, not the governor.
+        rt.set_caller(*VERIFREG_ACTOR_CODE_ID, VERIFIED_REGISTRY_ACTOR_ADDR);
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        expect_abort_contains_message(
+            ExitCode::USR_FORBIDDEN,
+            "caller address",
+            set_mint_limits(&rt, Some(limits.clone())),
+        );
+        rt.reset();
+
+        rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        expect_abort_contains_message(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            "negative mint limits",
+            set_mint_limits(
+                &rt,
+                Some(MintLimits { max_per_call: TokenAmount::from_atto(-1), ..limits.clone() }),
+            ),
+        );
+        rt.reset();
+
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        set_mint_limits(&rt, Some(limits.clone())).unwrap();
+        assert_eq!(Some(limits), rt.get_state::<State>().mint_limits);
+        expect_abort_contains_message(
+            ExitCode::USR_FORBIDDEN,
+            "exceeds per-call limit",
+            h.mint(&rt, &ALICE, &TokenAmount::from_whole(1), vec![]),
+        );
+        rt.reset();
+
+        // Restore the policy limits.
+        rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        set_mint_limits(&rt, None).unwrap();
+        h.mint(&rt, &ALICE, &TokenAmount::from_whole(1), vec![]).unwrap();
+        h.check_state(&rt);
+    }
+
+    fn set_mint_limits(rt: &MockRuntime, limits: Option<MintLimits>) -> Result<(), ActorError> {
+        let ret = rt.call::<Actor>(
+            Method::SetMintLimitsExported as MethodNum,
+            IpldBlock::serialize_cbor(&SetMintLimitsParams { limits }).unwrap(),
+        )?;
+        assert!(ret.is_none());
+        rt.verify();
+        Ok(())
+    }
+
     #[test]
     fn auto_allowance_on_mint() {
         let (rt, h) = make_harness();
@@ -158,8 +263,17 @@ mod transfer {
     // Tests for the specific transfer restrictions of the datacap token.
 
     use crate::{ALICE, BOB, CARLA, make_harness};
-    use fil_actors_runtime::test_utils::expect_abort_contains_message;
+    use fil_actor_datacap::{Actor, Method, SetTransferReceiversParams, State};
+    use fil_actors_runtime::test_utils::{
+        ACCOUNT_ACTOR_CODE_ID, expect_abort, expect_abort_contains_message,
+    };
+    use fil_actors_runtime::{
+        OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS, SYSTEM_ACTOR_ADDR, VersionedOperatorData,
+    };
     use fvm_ipld_encoding::RawBytes;
+    use fvm_ipld_encoding::ipld_block::IpldBlock;
+    use fvm_shared::MethodNum;
+    use fvm_shared::address::Address;
     use fvm_shared::econ::TokenAmount;
     use fvm_shared::error::ExitCode;
 
@@ -218,6 +332,94 @@ mod transfer {
         );
         rt.reset();
     }
+
+    #[test]
+    fn governance_allows_receivers() {
+        let (rt, h) = make_harness();
+        let operator_data = RawBytes::new(vec![1, 2, 3, 4]);
+        let receiver = Address::new_id(1000);
+
+        let amt = TokenAmount::from_whole(1);
+        h.mint(&rt, &ALICE, &amt, vec![*BOB]).unwrap();
+        expect_abort_contains_message(
+            ExitCode::USR_FORBIDDEN,
+            "not an allowed receiver",
+            h.transfer(&rt, &ALICE, &receiver, &amt, operator_data.clone()),
+        );
+        rt.reset();
+
+        // Only the system actor can set the receivers, which must resolve.
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, *ALICE);
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        expect_abort(
+            ExitCode::USR_FORBIDDEN,
+            rt.call::<Actor>(
+                Method::SetTransferReceiversExported as MethodNum,
+                IpldBlock::serialize_cbor(&SetTransferReceiversParams {
+                    receivers: vec![receiver],
+                })
+                .unwrap(),
+            ),
+        );
+        rt.reset();
+        expect_abort_contains_message(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            "failed to resolve receiver",
+            h.set_transfer_receivers(&rt, vec![Address::new_bls(&[1; 48]).unwrap()]),
+        );
+        rt.reset();
+
+        h.set_transfer_receivers(&rt, vec![receiver, receiver]).unwrap();
+        assert_eq!(vec![receiver], rt.get_state::<State>().transfer_receivers);
+        h.transfer(&rt, &ALICE, &receiver, &amt, operator_data.clone()).unwrap();
+        h.mint(&rt, &ALICE, &amt, vec![*BOB]).unwrap();
+        h.transfer_from(&rt, &BOB, &ALICE, &receiver, &amt, operator_data.clone()).unwrap();
+
+        // Clearing the receivers restores the restriction to the governor.
+        h.set_transfer_receivers(&rt, vec![]).unwrap();
+        h.mint(&rt, &ALICE, &amt, vec![]).unwrap();
+        expect_abort_contains_message(
+            ExitCode::USR_FORBIDDEN,
+            "not an allowed receiver",
+            h.transfer(&rt, &ALICE, &receiver, &amt, operator_data.clone()),
+        );
+        rt.reset();
+        h.transfer(&rt, &ALICE, &h.governor, &amt, operator_data).unwrap();
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn operator_data_version_checked() {
+        let (rt, h) = make_harness();
+        let amt = TokenAmount::from_whole(1);
+        h.mint(&rt, &ALICE, &amt, vec![*BOB]).unwrap();
+
+        let unsupported = RawBytes::serialize(VersionedOperatorData {
+            version: 99,
+            payload: RawBytes::default(),
+        })
+        .unwrap();
+        expect_abort_contains_message(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            "unsupported operator data version 99",
+            h.transfer(&rt, &ALICE, &h.governor, &amt, unsupported.clone()),
+        );
+        rt.reset();
+        expect_abort_contains_message(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            "unsupported operator data version 99",
+            h.transfer_from(&rt, &BOB, &ALICE, &h.governor, &amt, unsupported),
+        );
+        rt.reset();
+
+        // A supported version is forwarded to the receiver as is.
+        let supported = RawBytes::serialize(VersionedOperatorData {
+            version: OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS,
+            payload: RawBytes::new(vec![1, 2, 3, 4]),
+        })
+        .unwrap();
+        h.transfer(&rt, &ALICE, &h.governor, &amt, supported).unwrap();
+    }
 }
 
 mod destroy {
@@ -1,20 +1,18 @@
 use std::cell::RefCell;
 
-use frc46_token::receiver::{FRC46_TOKEN_TYPE, FRC46TokenReceived};
 use frc46_token::token::types::{
     BurnReturn, MintReturn, TransferFromParams, TransferFromReturn, TransferParams, TransferReturn,
 };
-use fvm_actor_utils::receiver::UniversalReceiverParams;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::MethodNum;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
-use num_traits::Zero;
 
 use fil_actor_datacap::testing::check_state_invariants;
-use fil_actor_datacap::{Actor as DataCapActor, DestroyParams, Method, MintParams, State};
-use fil_actors_runtime::cbor::serialize;
+use fil_actor_datacap::{
+    Actor as DataCapActor, DestroyParams, Method, MintParams, SetTransferReceiversParams, State,
+};
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
@@ -71,27 +69,14 @@ impl Harness {
         rt.expect_validate_caller_addr(vec![VERIFIED_REGISTRY_ACTOR_ADDR]);
 
         // Expect the token receiver hook to be called.
-        let hook_params = UniversalReceiverParams {
-            type_: FRC46_TOKEN_TYPE,
-            payload: serialize(
-                &FRC46TokenReceived {
-                    from: DATACAP_TOKEN_ACTOR_ADDR.id().unwrap(),
-                    to: to.id().unwrap(),
-                    operator: VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap(),
-                    amount: amount.clone(),
-                    operator_data: Default::default(),
-                    token_data: Default::default(),
-                },
-                "hook payload",
-            )?,
-        };
-        // UniversalReceiverParams
-        rt.expect_send_simple(
-            *to,
-            frc42_dispatch::method_hash!("Receive"),
-            IpldBlock::serialize_cbor(&hook_params).unwrap(),
-            TokenAmount::zero(),
-            None,
+        rt.expect_frc46_receiver_hook(
+            &make_frc46_token_received(
+                DATACAP_TOKEN_ACTOR_ADDR.id().unwrap(),
+                to.id().unwrap(),
+                VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap(),
+                amount.clone(),
+                RawBytes::default(),
+            ),
             ExitCode::OK,
         );
 
@@ -138,27 +123,14 @@ impl Harness {
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, *from);
 
         // Expect the token receiver hook to be called.
-        let hook_params = UniversalReceiverParams {
-            type_: FRC46_TOKEN_TYPE,
-            payload: serialize(
-                &FRC46TokenReceived {
-                    from: from.id().unwrap(),
-                    to: to.id().unwrap(),
-                    operator: from.id().unwrap(),
-                    amount: amount.clone(),
-                    operator_data: operator_data.clone(),
-                    token_data: Default::default(),
-                },
-                "hook payload",
-            )?,
-        };
-        // UniversalReceiverParams
-        rt.expect_send_simple(
-            *to,
-            frc42_dispatch::method_hash!("Receive"),
-            IpldBlock::serialize_cbor(&hook_params).unwrap(),
-            TokenAmount::zero(),
-            None,
+        rt.expect_frc46_receiver_hook(
+            &make_frc46_token_received(
+                from.id().unwrap(),
+                to.id().unwrap(),
+                from.id().unwrap(),
+                amount.clone(),
+                operator_data.clone(),
+            ),
             ExitCode::OK,
         );
 
@@ -185,27 +157,14 @@ impl Harness {
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, *operator);
 
         // Expect the token receiver hook to be called.
-        let hook_params = UniversalReceiverParams {
-            type_: FRC46_TOKEN_TYPE,
-            payload: serialize(
-                &FRC46TokenReceived {
-                    from: from.id().unwrap(),
-                    to: to.id().unwrap(),
-                    operator: operator.id().unwrap(),
-                    amount: amount.clone(),
-                    operator_data: operator_data.clone(),
-                    token_data: Default::default(),
-                },
-                "hook payload",
-            )?,
-        };
-        // UniversalReceiverParams
-        rt.expect_send_simple(
-            *to,
-            frc42_dispatch::method_hash!("Receive"),
-            IpldBlock::serialize_cbor(&hook_params).unwrap(),
-            TokenAmount::zero(),
-            None,
+        rt.expect_frc46_receiver_hook(
+            &make_frc46_token_received(
+                from.id().unwrap(),
+                to.id().unwrap(),
+                operator.id().unwrap(),
+                amount.clone(),
+                operator_data.clone(),
+            ),
             ExitCode::OK,
         );
 
@@ -220,6 +179,22 @@ impl Harness {
         Ok(ret.unwrap().deserialize().unwrap())
     }
 
+    pub fn set_transfer_receivers(
+        &self,
+        rt: &MockRuntime,
+        receivers: Vec<Address>,
+    ) -> Result<(), ActorError> {
+        rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        let ret = rt.call::<DataCapActor>(
+            Method::SetTransferReceiversExported as MethodNum,
+            IpldBlock::serialize_cbor(&SetTransferReceiversParams { receivers }).unwrap(),
+        )?;
+        assert!(ret.is_none());
+        rt.verify();
+        Ok(())
+    }
+
     // Reads the total supply from state directly.
     pub fn get_supply(&self, rt: &MockRuntime) -> TokenAmount {
         rt.get_state::<State>().token.supply
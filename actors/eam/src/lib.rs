@@ -129,9 +129,11 @@ fn create_actor(
 
     let f4_addr = Address::new_delegated(EAM_ACTOR_ID, &new_addr.0).unwrap();
 
-    if let Some(id) = rt.resolve_address(&f4_addr) {
+    // If the actor at the f4 address was deleted, the init actor re-maps the address to a new actor.
+    let existing =
+        rt.resolve_address(&f4_addr).and_then(|id| Some((id, rt.get_actor_code_cid(&id)?)));
+    if let Some((id, caller_code_cid)) = existing {
         // Try to resurrect it if it is already an EVM actor (must be "dead")
-        let caller_code_cid = rt.get_actor_code_cid(&id).expect("failed to lookup actor code");
         match rt.resolve_builtin_actor_type(&caller_code_cid) {
             // If it's an EVM actor, resurrect it.
             Some(Type::EVM) => {
@@ -154,7 +156,7 @@ fn create_actor(
         }
     }
 
-    // If the f4 address wasn't resolved, or resolved to something other than an EVM actor, we try
+    // If the f4 address wasn't resolved, or resolved to a placeholder or deleted actor, we try
     // to construct it "normally".
     let init_params = Exec4Params {
         code_cid: rt.get_code_cid_for_type(Type::EVM),
@@ -2,13 +2,15 @@ pub mod types;
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Payload;
+use fvm_shared::crypto::hash::SupportedHashes;
+use fvm_shared::crypto::signature::{SECP_SIG_LEN, SECP_SIG_MESSAGE_HASH_SIZE};
 use fvm_shared::{METHOD_CONSTRUCTOR, MethodNum};
 use num_derive::FromPrimitive;
 
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    ActorError, EAM_ACTOR_ID, FIRST_EXPORTED_METHOD_NUMBER, SYSTEM_ACTOR_ADDR, actor_dispatch,
-    actor_error,
+    ActorError, EAM_ACTOR_ID, FIRST_EXPORTED_METHOD_NUMBER, SYSTEM_ACTOR_ADDR,
+    ValidateSponsorParams, ValidateSponsorReturn, actor_dispatch, actor_error, sponsor,
 };
 
 #[cfg(feature = "fil-actor")]
@@ -19,6 +21,7 @@ fil_actors_runtime::wasm_trampoline!(EthAccountActor);
 #[repr(u64)]
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
+    ValidateSponsorExported = frc42_dispatch::method_hash!("ValidateSponsor"),
 }
 
 /// Ethereum Account actor.
@@ -50,6 +53,43 @@ impl EthAccountActor {
         Ok(())
     }
 
+    /// Authorizes this account to pay for the currently executing message sent by another account.
+    /// The signature must be a 65-byte secp256k1 signature (r, s, v with v in {0, 1}) over the
+    /// Keccak-256 hash of the sponsorship payload, by the key owning this account's Ethereum address.
+    /// Returns false, rather than aborting, if the sponsorship is for a different message
+    /// or the signature does not match.
+    pub fn validate_sponsor(
+        rt: &impl Runtime,
+        params: ValidateSponsorParams,
+    ) -> Result<ValidateSponsorReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let Some(payload) = sponsor::validate_sponsor(rt, &params)? else {
+            return Ok(ValidateSponsorReturn { authorized: false });
+        };
+
+        let eth_address = match rt
+            .lookup_delegated_address(rt.message().receiver().id().unwrap())
+            .map(|a| *a.payload())
+        {
+            Some(Payload::Delegated(da)) if da.namespace() == EAM_ACTOR_ID => {
+                da.subaddress().to_vec()
+            }
+            _ => return Err(actor_error!(illegal_state; "account has no Ethereum address")),
+        };
+        let signature: [u8; SECP_SIG_LEN] =
+            params.signature.as_slice().try_into().map_err(|_| {
+                actor_error!(illegal_argument; "invalid signature length {}", params.signature.len())
+            })?;
+        let mut hash = [0u8; SECP_SIG_MESSAGE_HASH_SIZE];
+        hash.copy_from_slice(&rt.hash(SupportedHashes::Keccak256, &payload));
+
+        let authorized = match rt.recover_secp_public_key(&hash, &signature) {
+            Ok(pubkey) => rt.hash(SupportedHashes::Keccak256, &pubkey[1..])[12..] == eth_address,
+            Err(_) => false,
+        };
+        Ok(ValidateSponsorReturn { authorized })
+    }
+
     // Always succeeds, accepting any transfers.
     pub fn fallback(
         rt: &impl Runtime,
@@ -74,6 +114,7 @@ impl ActorCode for EthAccountActor {
 
     actor_dispatch! {
         Constructor => constructor,
+        ValidateSponsorExported => validate_sponsor,
         _ => fallback,
     }
 }
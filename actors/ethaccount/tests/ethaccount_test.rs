@@ -10,10 +10,13 @@ use fil_actor_ethaccount::{EthAccountActor, Method};
 use fvm_shared::MethodNum;
 use fvm_shared::error::ExitCode;
 
-use fil_actors_runtime::SYSTEM_ACTOR_ADDR;
 use fil_actors_runtime::test_utils::{
-    ACCOUNT_ACTOR_CODE_ID, SYSTEM_ACTOR_CODE_ID, expect_abort_contains_message,
+    ACCOUNT_ACTOR_CODE_ID, MockRuntime, SYSTEM_ACTOR_CODE_ID, expect_abort_contains_message, hash,
 };
+use fil_actors_runtime::{
+    ActorError, SYSTEM_ACTOR_ADDR, ValidateSponsorParams, ValidateSponsorReturn,
+};
+use fvm_shared::crypto::hash::SupportedHashes;
 
 #[test]
 fn no_delegated_cant_deploy() {
@@ -46,3 +49,66 @@ fn token_receiver() {
         .unwrap();
     assert!(ret.is_none());
 }
+
+#[test]
+fn validate_sponsor() {
+    // The mock recovers a fixed public key from any signature.
+    let pubkey = [4u8; 65];
+    let mut rt = new_runtime();
+    rt.recover_secp_pubkey_fn = Box::new(move |_, _| Ok(pubkey));
+    let (digest, _) = hash(SupportedHashes::Keccak256, &pubkey[1..]);
+    construct(&rt, &digest[12..32]);
+
+    let mut params = ValidateSponsorParams {
+        sponsored: Address::new_id(1234),
+        nonce: 7,
+        signature: vec![7; 65],
+    };
+    rt.set_origin(params.sponsored);
+    rt.set_nonce(params.nonce);
+    let ret: ValidateSponsorReturn =
+        call_validate_sponsor(&rt, &params).unwrap().unwrap().deserialize().unwrap();
+    assert!(ret.authorized);
+
+    // A key that doesn't own the account's address is not authorized.
+    rt.recover_secp_pubkey_fn = Box::new(|_, _| Ok([5u8; 65]));
+    let ret: ValidateSponsorReturn =
+        call_validate_sponsor(&rt, &params).unwrap().unwrap().deserialize().unwrap();
+    assert!(!ret.authorized);
+    rt.recover_secp_pubkey_fn = Box::new(move |_, _| Ok(pubkey));
+
+    // A sponsorship for another of the sender's messages is not authorized.
+    params.nonce = 8;
+    let ret: ValidateSponsorReturn =
+        call_validate_sponsor(&rt, &params).unwrap().unwrap().deserialize().unwrap();
+    assert!(!ret.authorized);
+    params.nonce = 7;
+
+    // Nor is one for a message from another sender.
+    rt.set_origin(Address::new_id(1235));
+    let ret: ValidateSponsorReturn =
+        call_validate_sponsor(&rt, &params).unwrap().unwrap().deserialize().unwrap();
+    assert!(!ret.authorized);
+    rt.set_origin(params.sponsored);
+
+    params.signature = vec![7; 64];
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "invalid signature length",
+        call_validate_sponsor(&rt, &params),
+    );
+}
+
+fn call_validate_sponsor(
+    rt: &MockRuntime,
+    params: &ValidateSponsorParams,
+) -> Result<Option<IpldBlock>, ActorError> {
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, params.sponsored);
+    rt.expect_validate_caller_any();
+    let ret = rt.call::<EthAccountActor>(
+        Method::ValidateSponsorExported as MethodNum,
+        IpldBlock::serialize_cbor(params).unwrap(),
+    );
+    rt.verify();
+    ret
+}
@@ -21,17 +21,19 @@ pub fn new_runtime() -> MockRuntime {
 #[allow(dead_code)]
 pub fn setup() -> MockRuntime {
     let rt = new_runtime();
+    construct(&rt, &hex_literal::hex!("FEEDFACECAFEBEEF000000000000000000000000"));
+    rt
+}
+
+/// Constructs the account with the given Ethereum address.
+#[allow(dead_code)]
+pub fn construct(rt: &MockRuntime, eth_address: &[u8]) {
     rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
     rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
     rt.set_delegated_address(
         EOA.id().unwrap(),
-        Address::new_delegated(
-            EAM_ACTOR_ID,
-            &hex_literal::hex!("FEEDFACECAFEBEEF000000000000000000000000"),
-        )
-        .unwrap(),
+        Address::new_delegated(EAM_ACTOR_ID, eth_address).unwrap(),
     );
     rt.call::<EthAccountActor>(Method::Constructor as MethodNum, None).unwrap();
     rt.verify();
-    rt
 }
@@ -274,7 +274,19 @@ impl<'r, 'a, RT: Runtime + 'r> Machine<'r, 'a, RT> {
     // Note: pub only for unit test steps.
     pub(crate) fn step(&mut self) -> Result<(), ActorError> {
         let op = self.bytecode[self.pc];
-        unsafe { Self::JMPTABLE[op as usize](self) }
+        #[cfg(feature = "instrumentation")]
+        {
+            let probe = super::instrumentation::Probe::start(self.system.rt, op);
+            let result = unsafe { Self::JMPTABLE[op as usize](self) };
+            if let Some(probe) = probe {
+                probe.finish(self.system.rt);
+            }
+            result
+        }
+        #[cfg(not(feature = "instrumentation"))]
+        unsafe {
+            Self::JMPTABLE[op as usize](self)
+        }
     }
 
     const JMPTABLE: [opcodes::Instruction<'r, 'a, RT>; 256] = opcodes::jumptable::<'r, 'a, RT>();
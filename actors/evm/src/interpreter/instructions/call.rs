@@ -159,7 +159,6 @@ pub fn call_generic<RT: Runtime>(
         // non-zero sends are side-effects and hence a static mode violation
         return Err(ActorError::read_only("cannot transfer value when read-only".into()));
     }
-
     let input_region = get_memory_region(memory, input_offset, input_size)?;
 
     let (call_result, return_data) = {
@@ -215,7 +214,7 @@ pub fn call_generic<RT: Runtime>(
                     //
                     // 1. If the outer result fails, it means we failed to flush/restore state and
                     // there is a bug. We exit with an actor error and abort.
-                    match system.send_raw(
+                    match system.call_raw(
                         &dst_addr,
                         Method::InvokeContract as MethodNum,
                         params,
@@ -268,7 +267,7 @@ pub fn call_generic<RT: Runtime>(
                                 value: state.value_received.clone(),
                             };
                             system
-                                .send(
+                                .call(
                                     &system.rt.message().receiver(),
                                     Method::InvokeContractDelegate as u64,
                                     IpldBlock::serialize_dag_cbor(&params)?,
@@ -83,8 +83,7 @@ pub fn gas_price(
     _state: &mut ExecutionState,
     system: &System<impl Runtime>,
 ) -> Result<U256, ActorError> {
-    let effective_price = system.rt.base_fee() + system.rt.message().gas_premium();
-    Ok(U256::from(&effective_price))
+    Ok(U256::from(&system.rt.effective_gas_price()))
 }
 
 #[inline]
@@ -121,7 +121,7 @@ fn create_common(
     let gas_limit = (63 * system.rt.gas_available()) / 64;
 
     // send bytecode & params to EAM to generate the address and contract
-    let ret = system.send(
+    let ret = system.call(
         &EAM_ACTOR_ADDR,
         method,
         params,
@@ -1,19 +1,11 @@
 use crate::interpreter::instructions::memory::get_memory_region;
 use fil_actors_evm_shared::uints::U256;
-use fil_actors_runtime::ActorError;
-use fvm_ipld_encoding::IPLD_RAW;
-use fvm_shared::event::{Entry, Flags};
+use fil_actors_runtime::{ActorError, EthLog};
 use {
     crate::interpreter::{ExecutionState, System},
     fil_actors_runtime::runtime::Runtime,
 };
 
-/// The event key for the Ethereum log data.
-const EVENT_DATA_KEY: &str = "d";
-
-/// The event keys for the Ethereum log topics.
-const EVENT_TOPIC_KEYS: &[&str] = &["t1", "t2", "t3", "t4"];
-
 #[inline]
 pub fn log(
     state: &mut ExecutionState,
@@ -32,35 +24,15 @@ pub fn log(
     // LOG0 + a zero-sized memory region emits an event with no entries whatsoever. In this case,
     // the FVM will record a hollow event carrying only the emitter actor ID.
     let region = get_memory_region(&mut state.memory, mem_index, size)?;
+    let data = match region {
+        Some(r) => state.memory[r.offset..r.offset + r.size.get()].to_vec(),
+        None => Vec::new(),
+    };
 
-    // Extract the topics. Prefer to allocate an extra item than to incur in the cost of a
-    // decision based on the size of the data.
-    let mut entries: Vec<Entry> = Vec::with_capacity(num_topics + 1);
-    for i in 0..num_topics {
-        let key = EVENT_TOPIC_KEYS[i];
-        let topic = topics[i];
-        let entry = Entry {
-            flags: Flags::FLAG_INDEXED_ALL,
-            key: (*key).to_owned(),
-            codec: IPLD_RAW,
-            value: topic.to_bytes().into(), // U256 serializes as a byte string.
-        };
-        entries.push(entry);
-    }
-
-    // Skip adding the data if it's zero-sized.
-    if let Some(r) = region {
-        let data = state.memory[r.offset..r.offset + r.size.get()].to_vec();
-        let entry = Entry {
-            flags: Flags::FLAG_INDEXED_ALL,
-            key: EVENT_DATA_KEY.to_owned(),
-            codec: IPLD_RAW,
-            value: data,
-        };
-        entries.push(entry);
-    }
-
-    system.rt.emit_event(&entries.into())?;
+    // Each topic is encoded as an indexed entry holding the 32-byte big-endian word, so the
+    // log can be recovered from the event alone with `EthLog::from_event`.
+    let log = EthLog { topics: topics[..num_topics].iter().map(U256::to_bytes).collect(), data };
+    system.rt.emit_event(&log.to_event()?)?;
 
     Ok(())
 }
@@ -71,8 +43,8 @@ mod tests {
     use fvm_ipld_encoding::IPLD_RAW;
     use fvm_shared::event::{ActorEvent, Entry, Flags};
 
-    use super::{EVENT_DATA_KEY, EVENT_TOPIC_KEYS};
     use crate::evm_unit_test;
+    use fil_actors_runtime::{EVM_LOG_DATA_KEY, EVM_LOG_TOPIC_KEYS};
 
     #[test]
     fn test_log0() {
@@ -86,7 +58,7 @@ mod tests {
                 rt.expect_emitted_event(
                     ActorEvent::from(vec![Entry{
                         flags: Flags::FLAG_INDEXED_ALL,
-                        key: EVENT_DATA_KEY.to_owned(),
+                        key: EVM_LOG_DATA_KEY.to_owned(),
                         codec: IPLD_RAW,
                         value: data.into(),
                     }])
@@ -120,13 +92,13 @@ mod tests {
                     ActorEvent::from(vec![
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[0].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[0].to_owned(),
                             codec: IPLD_RAW,
                             value: t1.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key: EVENT_DATA_KEY.to_owned(),
+                            key: EVM_LOG_DATA_KEY.to_owned(),
                             codec: IPLD_RAW,
                             value: data.into(),
                         }
@@ -163,19 +135,19 @@ mod tests {
                     ActorEvent::from(vec![
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[0].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[0].to_owned(),
                             codec: IPLD_RAW,
                             value: t1.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[1].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[1].to_owned(),
                             codec: IPLD_RAW,
                             value: t2.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key: EVENT_DATA_KEY.to_owned(),
+                            key: EVM_LOG_DATA_KEY.to_owned(),
                             codec: IPLD_RAW,
                             value: data.into(),
                         }
@@ -214,25 +186,25 @@ mod tests {
                     ActorEvent::from(vec![
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[0].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[0].to_owned(),
                             codec: IPLD_RAW,
                             value: t1.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[1].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[1].to_owned(),
                             codec: IPLD_RAW,
                             value: t2.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[2].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[2].to_owned(),
                             codec: IPLD_RAW,
                             value: t3.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key: EVENT_DATA_KEY.to_owned(),
+                            key: EVM_LOG_DATA_KEY.to_owned(),
                             codec: IPLD_RAW,
                             value: data.into(),
                         }
@@ -273,31 +245,31 @@ mod tests {
                     ActorEvent::from(vec![
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[0].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[0].to_owned(),
                             codec: IPLD_RAW,
                             value: t1.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[1].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[1].to_owned(),
                             codec: IPLD_RAW,
                             value: t2.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[2].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[2].to_owned(),
                             codec: IPLD_RAW,
                             value: t3.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key:  EVENT_TOPIC_KEYS[3].to_owned(),
+                            key:  EVM_LOG_TOPIC_KEYS[3].to_owned(),
                             codec: IPLD_RAW,
                             value: t4.to_bytes().into(),
                         },
                         Entry{
                             flags: Flags::FLAG_INDEXED_ALL,
-                            key: EVENT_DATA_KEY.to_owned(),
+                            key: EVM_LOG_DATA_KEY.to_owned(),
                             codec: IPLD_RAW,
                             value: data.into(),
                         }
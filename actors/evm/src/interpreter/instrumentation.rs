@@ -0,0 +1,93 @@
+//! Opt-in interpreter instrumentation, enabled with the `instrumentation` feature.
+//!
+//! While recording, every executed instruction is tallied per opcode into a thread-local buffer,
+//! which a test harness can take once execution completes. When gas measurement is requested, the
+//! gas consumed by each instruction is measured as the difference in `gas_available` before and
+//! after it executes. The gas attributed to call and create instructions therefore includes the
+//! gas consumed by the callee.
+//!
+//! This is intended for benchmarks and regression tests of interpreter performance and must not be
+//! enabled in production builds.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use fil_actors_runtime::runtime::Runtime;
+
+/// Count and total gas of the executions of a single opcode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpcodeStats {
+    pub count: u64,
+    pub gas: u64,
+}
+
+/// Per-opcode statistics recorded while instrumentation was enabled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InstrumentationReport {
+    /// Statistics keyed by opcode, for opcodes executed at least once.
+    pub opcodes: BTreeMap<u8, OpcodeStats>,
+}
+
+impl InstrumentationReport {
+    /// Statistics for an opcode, zero if it was never executed.
+    pub fn get(&self, opcode: u8) -> OpcodeStats {
+        self.opcodes.get(&opcode).copied().unwrap_or_default()
+    }
+
+    /// Total number of instructions executed.
+    pub fn total_count(&self) -> u64 {
+        self.opcodes.values().map(|s| s.count).sum()
+    }
+
+    /// Total gas consumed by all executed instructions.
+    pub fn total_gas(&self) -> u64 {
+        self.opcodes.values().map(|s| s.gas).sum()
+    }
+}
+
+struct Recorder {
+    measure_gas: bool,
+    report: InstrumentationReport,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Starts recording instructions executed on this thread, discarding any previous recording.
+/// If `measure_gas` is set, the runtime is queried for available gas before and after each
+/// instruction.
+pub fn enable(measure_gas: bool) {
+    RECORDER.with_borrow_mut(|r| {
+        *r = Some(Recorder { measure_gas, report: InstrumentationReport::default() })
+    });
+}
+
+/// Stops recording and returns the report, or None if recording was not enabled.
+pub fn take() -> Option<InstrumentationReport> {
+    RECORDER.with_borrow_mut(|r| r.take()).map(|r| r.report)
+}
+
+/// An instruction in flight, started before it executes and finished after.
+pub(crate) struct Probe {
+    opcode: u8,
+    gas_before: Option<u64>,
+}
+
+impl Probe {
+    pub(crate) fn start(rt: &impl Runtime, opcode: u8) -> Option<Probe> {
+        let measure_gas = RECORDER.with_borrow(|r| r.as_ref().map(|r| r.measure_gas))?;
+        Some(Probe { opcode, gas_before: measure_gas.then(|| rt.gas_available()) })
+    }
+
+    pub(crate) fn finish(self, rt: &impl Runtime) {
+        let gas = self.gas_before.map(|before| before.saturating_sub(rt.gas_available()));
+        RECORDER.with_borrow_mut(|r| {
+            if let Some(r) = r {
+                let stats = r.report.opcodes.entry(self.opcode).or_default();
+                stats.count += 1;
+                stats.gas += gas.unwrap_or_default();
+            }
+        });
+    }
+}
@@ -1,6 +1,8 @@
 mod bytecode;
 mod execution;
 mod instructions;
+#[cfg(feature = "instrumentation")]
+pub mod instrumentation;
 mod memory;
 mod output;
 mod precompiles;
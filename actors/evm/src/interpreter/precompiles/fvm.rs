@@ -1,8 +1,12 @@
 use crate::{EVM_MAX_RESERVED_METHOD, EVM_WORD_SIZE};
 use fil_actors_evm_shared::uints::U256;
+use fil_actors_runtime::REWARD_ACTOR_ADDR;
 use fil_actors_runtime::runtime::Runtime;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::{BytesDe, BytesSer, from_slice, to_vec};
+use fvm_shared::clock::ChainEpoch;
 use fvm_shared::{METHOD_SEND, address::Address, econ::TokenAmount, sys::SendFlags};
+use num_traits::Zero;
 
 use crate::interpreter::{CallKind, System};
 
@@ -53,6 +57,67 @@ pub(super) fn resolve_address<RT: Runtime>(
         .unwrap_or_default())
 }
 
+/// The position of the params helper mode in the call actor flags.
+const PARAMS_HELPER_SHIFT: u32 = 32;
+/// The position of the return helper mode in the call actor flags.
+const RETURN_HELPER_SHIFT: u32 = 40;
+/// Flag bits above the helper modes, which must be zero.
+const RESERVED_FLAGS_MASK: u64 = !((1 << 48) - 1);
+
+/// A CBOR scalar that the call actor precompiles can encode params as, or decode a return value
+/// from, so that contracts can make simple calls to builtin actors without a CBOR library.
+///
+/// Helper modes are selected in the flags, with 0 for no helper.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum CborScalar {
+    /// Mode 1: an unsigned integer, passed to and from the contract as a u256 word.
+    U64,
+    /// Mode 2: a byte string, passed as raw bytes.
+    Bytes,
+    /// Mode 3: an address, passed in its Filecoin byte representation.
+    Address,
+}
+
+impl CborScalar {
+    fn from_mode(mode: u64) -> Result<Option<Self>, PrecompileError> {
+        match mode {
+            0 => Ok(None),
+            1 => Ok(Some(Self::U64)),
+            2 => Ok(Some(Self::Bytes)),
+            3 => Ok(Some(Self::Address)),
+            _ => Err(PrecompileError::InvalidInput),
+        }
+    }
+
+    /// Encodes a value passed by the contract as CBOR.
+    fn encode(self, data: &[u8]) -> Result<Vec<u8>, PrecompileError> {
+        let encoded = match self {
+            Self::U64 => {
+                if data.len() != EVM_WORD_SIZE {
+                    return Err(PrecompileError::InvalidInput);
+                }
+                let value: u64 = ValueReader::new(data).read_value()?;
+                to_vec(&value)
+            }
+            Self::Bytes => to_vec(&BytesSer(data)),
+            Self::Address => {
+                let addr = Address::from_bytes(data).map_err(|_| PrecompileError::InvalidInput)?;
+                to_vec(&addr)
+            }
+        };
+        encoded.map_err(|_| PrecompileError::InvalidInput)
+    }
+
+    /// Decodes CBOR into the value to pass to the contract, or None if it isn't such a scalar.
+    fn decode(self, data: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            Self::U64 => from_slice::<u64>(data).ok().map(|v| U256::from(v).to_bytes().to_vec()),
+            Self::Bytes => from_slice::<BytesDe>(data).ok().map(|BytesDe(b)| b),
+            Self::Address => from_slice::<Address>(data).ok().map(|a| a.to_bytes()),
+        }
+    }
+}
+
 /// Calls an actor by address.
 ///
 /// Parameters are encoded according to the solidity ABI, with no function selector:
@@ -60,9 +125,9 @@ pub(super) fn resolve_address<RT: Runtime>(
 /// ```text
 /// u64   method
 /// u256  value
-/// u64   flags (1 for read-only, 0 otherwise)
+/// u64   flags (see below)
 /// u64   codec (0x71 for "dag-cbor", or `0` for "nothing")
-/// bytes params (must be empty if the codec is 0x0)
+/// bytes params (must be empty if the codec is 0x0, unless a params helper is selected)
 /// bytes address
 /// ```
 ///
@@ -78,6 +143,10 @@ pub(super) fn resolve_address<RT: Runtime>(
 /// - negative values are system errors
 /// - positive are user errors (from the called actor)
 /// - 0 is success
+///
+/// The low 32 bits of flags are send flags (1 for read-only). Bits 32-39 select a helper that
+/// encodes the params as a CBOR scalar, and bits 40-47 a helper that decodes the return value
+/// from one (see [`CborScalar`]). With a params helper, the codec must be 0.
 pub(super) fn call_actor<RT: Runtime>(
     system: &mut System<RT>,
     input: &[u8],
@@ -93,9 +162,9 @@ pub(super) fn call_actor<RT: Runtime>(
 /// ```text
 /// u64   method
 /// u256  value
-/// u64   flags (1 for read-only, 0 otherwise)
+/// u64   flags (see below)
 /// u64   codec (0x71 for "dag-cbor", or `0` for "nothing")
-/// bytes params (must be empty if the codec is 0x0)
+/// bytes params (must be empty if the codec is 0x0, unless a params helper is selected)
 /// u64   actor_id
 /// ```
 ///
@@ -111,6 +180,8 @@ pub(super) fn call_actor<RT: Runtime>(
 /// - negative values are system errors
 /// - positive are user errors (from the called actor)
 /// - 0 is success
+///
+/// Flags are as for [`call_actor`].
 pub(super) fn call_actor_id<RT: Runtime>(
     system: &mut System<RT>,
     input: &[u8],
@@ -138,7 +209,13 @@ pub(super) fn call_actor_shared<RT: Runtime>(
     let value: U256 = input_params.read_value()?;
 
     let flags: u64 = input_params.read_value()?;
-    let flags = SendFlags::from_bits(flags).ok_or(PrecompileError::InvalidInput)?;
+    if flags & RESERVED_FLAGS_MASK != 0 {
+        return Err(PrecompileError::InvalidInput);
+    }
+    let params_helper = CborScalar::from_mode((flags >> PARAMS_HELPER_SHIFT) & 0xff)?;
+    let return_helper = CborScalar::from_mode((flags >> RETURN_HELPER_SHIFT) & 0xff)?;
+    let flags =
+        SendFlags::from_bits(flags & u32::MAX as u64).ok_or(PrecompileError::InvalidInput)?;
 
     let codec: u64 = input_params.read_value()?;
 
@@ -167,16 +244,19 @@ pub(super) fn call_actor_shared<RT: Runtime>(
 
     let result = {
         // TODO only CBOR or "nothing" for now. We should support RAW and DAG_CBOR in the future.
-        let params = match codec {
-            fvm_ipld_encoding::CBOR => Some(IpldBlock { codec, data: params.into() }),
-            0 if params.is_empty() => None,
+        let params = match (codec, params_helper) {
+            (0, Some(helper)) => {
+                Some(IpldBlock { codec: fvm_ipld_encoding::CBOR, data: helper.encode(&params)? })
+            }
+            (fvm_ipld_encoding::CBOR, None) => Some(IpldBlock { codec, data: params.into() }),
+            (0, None) if params.is_empty() => None,
             _ => return Err(PrecompileError::InvalidInput),
         };
         // This method returns two results. If the outer result is an error, we consider the
         // precompile to have completely failed.
         //
         // If get `Ok(anything)`, we expose `anything` to the user.
-        system.send_raw(
+        system.call_raw(
             &address,
             method,
             params,
@@ -202,7 +282,16 @@ pub(super) fn call_actor_shared<RT: Runtime>(
             Ok(resp) => (U256::from(resp.exit_code.value()), resp.return_data),
         };
 
-        let ret_blk = data.unwrap_or(IpldBlock { codec: 0, data: vec![] });
+        let mut ret_blk = data.unwrap_or(IpldBlock { codec: 0, data: vec![] });
+        // A successful CBOR return value is decoded with the return helper, if any, and returned
+        // with codec 0. Any other return value is passed through unchanged.
+        if let Some(helper) = return_helper {
+            if exit_code.is_zero() && ret_blk.codec == fvm_ipld_encoding::CBOR {
+                if let Some(decoded) = helper.decode(&ret_blk.data) {
+                    ret_blk = IpldBlock { codec: 0, data: decoded };
+                }
+            }
+        }
 
         let mut output = Vec::with_capacity(4 * EVM_WORD_SIZE + ret_blk.data.len());
         output.extend_from_slice(&exit_code.to_bytes());
@@ -238,3 +327,60 @@ pub(super) fn get_randomness<RT: Runtime>(
     let randomness = system.rt.get_beacon_randomness(randomness_epoch);
     randomness.map(|r| r.to_vec()).map_err(|_| PrecompileError::InvalidInput)
 }
+
+/// The reward actor method returning the base fees of recent epochs.
+const BASE_FEE_HISTORY_METHOD: u64 = frc42_dispatch::method_hash!("BaseFeeHistory");
+
+/// Returns the base fees of recent non-null epochs, as recorded by the reward actor.
+///
+/// Params:
+///
+/// | Param            | Value                     |
+/// |------------------|---------------------------|
+/// | limit            | U256 - low u64            |
+///
+/// Returns up to `limit` of the most recent epochs (bounded by the history retained by the
+/// reward actor), most recent first, each as two words:
+///
+/// ```text
+/// u256  epoch
+/// u256  base_fee (attoFIL)
+/// ```
+///
+/// Errors if the history can't be fetched.
+pub(super) fn get_base_fee_history<RT: Runtime>(
+    system: &mut System<RT>,
+    input: &[u8],
+    _: PrecompileContext,
+) -> PrecompileResult {
+    let mut input_params = ValueReader::new(input);
+    let limit: u64 = input_params.read_value()?;
+
+    let params = IpldBlock::serialize_cbor(&limit).map_err(|_| PrecompileError::InvalidInput)?;
+    let resp = system
+        .rt
+        .send(
+            &REWARD_ACTOR_ADDR,
+            BASE_FEE_HISTORY_METHOD,
+            params,
+            TokenAmount::zero(),
+            None,
+            SendFlags::READ_ONLY,
+        )
+        .map_err(|_| PrecompileError::InvalidInput)?;
+    if !resp.exit_code.is_success() {
+        return Err(PrecompileError::InvalidInput);
+    }
+    let history: Vec<(ChainEpoch, TokenAmount)> = resp
+        .return_data
+        .ok_or(PrecompileError::InvalidInput)?
+        .deserialize()
+        .map_err(|_| PrecompileError::InvalidInput)?;
+
+    let mut output = Vec::with_capacity(history.len() * 2 * EVM_WORD_SIZE);
+    for (epoch, base_fee) in history {
+        output.extend_from_slice(&U256::from(u64::try_from(epoch)?).to_bytes());
+        output.extend_from_slice(&U256::from(&base_fee).to_bytes());
+    }
+    Ok(output)
+}
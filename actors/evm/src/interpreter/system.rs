@@ -16,8 +16,8 @@ use fvm_shared::sys::SendFlags;
 use fvm_shared::{IPLD_RAW, METHOD_SEND, MethodNum, Response};
 use multihash_codetable::Code;
 
-use crate::BytecodeHash;
 use crate::state::{State, Tombstone, TransientData, TransientDataLifespan};
+use crate::{BytecodeHash, EVM_CONTRACT_CALL_DEPTH_EXCEEDED, EVM_CONTRACT_SUBCALL_LIMIT_EXCEEDED};
 
 use cid::Cid;
 use fil_actors_runtime::{ActorError, runtime::Runtime};
@@ -93,6 +93,10 @@ pub struct System<'r, RT: Runtime> {
     /// The contract's EVM transient storage slots.
     transient_slots: StateKamt<RT::Blockstore>,
     current_transient_data_lifespan: TransientDataLifespan,
+    /// The number of calls made by invocations of this contract that are still in progress,
+    /// kept with the transient data so that it's scoped to the current message and visible
+    /// to nested invocations of the same contract.
+    call_depth: u64,
 
     /// The contracts "nonce" (incremented when creating new actors).
     pub(crate) nonce: u64,
@@ -106,6 +110,9 @@ pub struct System<'r, RT: Runtime> {
     /// This is "some" if the actor is currently a "zombie". I.e., it has selfdestructed, but the
     /// current message is still executing. `System` cannot load a contracts state with a
     pub(crate) tombstone: Option<Tombstone>,
+
+    /// The number of calls and contract creations made by the current invocation.
+    subcalls: u64,
 }
 
 impl<'r, RT: Runtime> System<'r, RT> {
@@ -121,12 +128,14 @@ impl<'r, RT: Runtime> System<'r, RT> {
             slots: StateKamt::new_with_config(store, KAMT_CONFIG.clone()),
             transient_slots: StateKamt::new_with_config(transient_store, KAMT_CONFIG.clone()),
             current_transient_data_lifespan,
+            call_depth: 0,
             nonce: 1,
             saved_state_root: None,
             bytecode: None,
             readonly,
             randomness: None,
             tombstone: None,
+            subcalls: 0,
         }
     }
 
@@ -188,21 +197,22 @@ impl<'r, RT: Runtime> System<'r, RT> {
         let current_transient_data_lifespan = get_current_transient_data_lifespan(rt);
 
         // Handle transient storage based on the presence and lifespan of `transient_data`
-        let transient_slots = match state.transient_data {
+        let (transient_slots, call_depth) = match state.transient_data {
             Some(transient_data)
                 if current_transient_data_lifespan == transient_data.transient_data_lifespan =>
             {
                 // Lifespans match, load the transient storage
-                StateKamt::load_with_config(
+                let slots = StateKamt::load_with_config(
                     &transient_data.transient_data_state,
                     transient_store,
                     KAMT_CONFIG.clone(),
                 )
-                .context_code(ExitCode::USR_ILLEGAL_STATE, "transient_state not in blockstore")?
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "transient_state not in blockstore")?;
+                (slots, transient_data.call_depth)
             }
             _ => {
                 // Lifespan mismatch or `transient_data` is `None`, initialize an empty transient storage KAMT
-                StateKamt::new_with_config(transient_store, KAMT_CONFIG.clone())
+                (StateKamt::new_with_config(transient_store, KAMT_CONFIG.clone()), 0)
             }
         };
 
@@ -212,15 +222,57 @@ impl<'r, RT: Runtime> System<'r, RT> {
                 .context_code(ExitCode::USR_ILLEGAL_STATE, "state not in blockstore")?,
             transient_slots,
             current_transient_data_lifespan,
+            call_depth,
             nonce: state.nonce,
             saved_state_root: Some(state_root),
             bytecode: Some(EvmBytecode::new(state.bytecode, state.bytecode_hash)),
             readonly: read_only,
             randomness: None,
             tombstone: state.tombstone,
+            subcalls: 0,
         })
     }
 
+    /// Counts a call or contract creation made by the current invocation, aborting the
+    /// invocation once the policy limit is exceeded.
+    fn count_subcall(&mut self) -> Result<(), ActorError> {
+        let max = self.rt.policy().evm_max_subcalls;
+        if self.subcalls >= max {
+            return Err(ActorError::unchecked(
+                EVM_CONTRACT_SUBCALL_LIMIT_EXCEEDED,
+                format!("contract invocation exceeded the limit of {max} calls"),
+            ));
+        }
+        self.subcalls += 1;
+        Ok(())
+    }
+
+    /// Records that this invocation is about to call out, aborting the invocation if the contract
+    /// is already nested the maximum number of calls deep. Read-only calls count like any other.
+    /// Returns the depth to restore once the call returns.
+    ///
+    /// An invocation which is itself read-only can't record its calls, so calls nested within a
+    /// read-only call are limited by the depth recorded when it was made.
+    fn enter_call(&mut self) -> Result<u64, ActorError> {
+        let max = self.rt.policy().evm_max_call_depth;
+        let depth = self.call_depth;
+        if depth >= max {
+            return Err(ActorError::unchecked(
+                EVM_CONTRACT_CALL_DEPTH_EXCEEDED,
+                format!("contract exceeded the nested call depth limit of {max}"),
+            ));
+        }
+        self.set_call_depth(depth + 1);
+        Ok(depth)
+    }
+
+    fn set_call_depth(&mut self, depth: u64) {
+        if !self.readonly && depth != self.call_depth {
+            self.call_depth = depth;
+            self.saved_state_root = None; // Mark state as dirty
+        }
+    }
+
     pub fn increment_nonce(&mut self) {
         self.saved_state_root = None;
         self.nonce = self.nonce.checked_add(1).unwrap();
@@ -242,7 +294,31 @@ impl<'r, RT: Runtime> System<'r, RT> {
         gas_limit: Option<u64>,
         send_flags: SendFlags,
     ) -> Result<Option<IpldBlock>, ActorError> {
-        let result = self.send_raw(to, method, params, value, gas_limit, send_flags)?.map_err(|err| {
+        let result = self.send_raw(to, method, params, value, gas_limit, send_flags)?;
+        Self::return_data(to, method, result)
+    }
+
+    /// Like [`System::send`], but for sends that may run other code (calls and contract
+    /// creations): the send counts against the subcall limit and the nested call depth limit.
+    pub fn call(
+        &mut self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        send_flags: SendFlags,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        let result = self.call_raw(to, method, params, value, gas_limit, send_flags)?;
+        Self::return_data(to, method, result)
+    }
+
+    fn return_data(
+        to: &Address,
+        method: MethodNum,
+        result: Result<Response, ErrorNumber>,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        let result = result.map_err(|err| {
             actor_error!(unspecified; "send syscall to {to} on method {method} failed: {}", err)
         })?;
 
@@ -285,6 +361,24 @@ impl<'r, RT: Runtime> System<'r, RT> {
         Ok(result.map_err(|e| e.0))
     }
 
+    /// Like [`System::send_raw`], but for sends that may run other code (calls and contract
+    /// creations): the send counts against the subcall limit and the nested call depth limit.
+    pub fn call_raw(
+        &mut self,
+        to: &Address,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        value: TokenAmount,
+        gas_limit: Option<u64>,
+        send_flags: SendFlags,
+    ) -> Result<Result<Response, ErrorNumber>, ActorError> {
+        self.count_subcall()?;
+        let depth = self.enter_call()?;
+        let result = self.send_raw(to, method, params, value, gas_limit, send_flags)?;
+        self.set_call_depth(depth);
+        Ok(result)
+    }
+
     /// Flush the actor state (bytecode, nonce, transient data and slots).
     pub fn flush(&mut self) -> Result<(), ActorError> {
         if self.saved_state_root.is_some() {
@@ -301,7 +395,7 @@ impl<'r, RT: Runtime> System<'r, RT> {
             None => self.set_bytecode(&[])?,
         };
 
-        let transient_data = if self.transient_slots.is_empty() {
+        let transient_data = if self.transient_slots.is_empty() && self.call_depth == 0 {
             None
         } else {
             Some(TransientData {
@@ -310,6 +404,7 @@ impl<'r, RT: Runtime> System<'r, RT> {
                     "failed to flush transient storage state",
                 )?,
                 transient_data_lifespan: self.current_transient_data_lifespan,
+                call_depth: self.call_depth,
             })
         };
 
@@ -356,15 +451,19 @@ impl<'r, RT: Runtime> System<'r, RT> {
             .context_code(ExitCode::USR_ILLEGAL_STATE, "state not in blockstore")?;
 
         match &state.transient_data {
-            Some(TransientData { transient_data_state, transient_data_lifespan })
+            Some(TransientData { transient_data_state, transient_data_lifespan, call_depth })
                 if transient_data_lifespan == &self.current_transient_data_lifespan =>
             {
                 self.transient_slots.set_root(transient_data_state).context_code(
                     ExitCode::USR_ILLEGAL_STATE,
                     "transient_state not in blockstore",
-                )?
+                )?;
+                self.call_depth = *call_depth;
+            }
+            _ => {
+                self.transient_slots.clear();
+                self.call_depth = 0;
             }
-            _ => self.transient_slots.clear(),
         }
 
         self.slots
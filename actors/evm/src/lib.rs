@@ -13,6 +13,7 @@ use fvm_shared::error::ExitCode;
 use crate::interpreter::Outcome;
 use crate::interpreter::{Bytecode, ExecutionState, System, execute};
 use crate::reader::ValueReader;
+use crate::revert::RevertReason;
 use cid::Cid;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fvm_shared::METHOD_CONSTRUCTOR;
@@ -24,6 +25,7 @@ pub use types::*;
 pub mod ext;
 pub mod interpreter;
 pub(crate) mod reader;
+pub mod revert;
 mod state;
 mod types;
 
@@ -40,6 +42,8 @@ pub const EVM_CONTRACT_STACK_OVERFLOW: ExitCode = ExitCode::new(37);
 pub const EVM_CONTRACT_ILLEGAL_MEMORY_ACCESS: ExitCode = ExitCode::new(38);
 pub const EVM_CONTRACT_BAD_JUMPDEST: ExitCode = ExitCode::new(39);
 pub const EVM_CONTRACT_SELFDESTRUCT_FAILED: ExitCode = ExitCode::new(40);
+pub const EVM_CONTRACT_SUBCALL_LIMIT_EXCEEDED: ExitCode = ExitCode::new(41);
+pub const EVM_CONTRACT_CALL_DEPTH_EXCEEDED: ExitCode = ExitCode::new(42);
 
 const EVM_MAX_RESERVED_METHOD: u64 = 1023;
 pub const NATIVE_METHOD_SIGNATURE: &str = "handle_filecoin_method(uint64,uint64,bytes)";
@@ -136,11 +140,7 @@ fn initialize_evm_contract(
             system.set_bytecode(&output.return_data)?;
             system.flush()
         }
-        Outcome::Revert => Err(ActorError::unchecked_with_data(
-            EVM_CONTRACT_REVERTED,
-            "constructor reverted".to_string(),
-            IpldBlock::serialize_cbor(&BytesSer(&output.return_data)).unwrap(),
-        )),
+        Outcome::Revert => Err(revert_error("constructor reverted", &output.return_data)),
     }
 }
 
@@ -175,14 +175,22 @@ where
             system.flush()?;
             Ok(output.return_data.to_vec())
         }
-        Outcome::Revert => Err(ActorError::unchecked_with_data(
-            EVM_CONTRACT_REVERTED,
-            format!("contract reverted at {0}", output.pc),
-            IpldBlock::serialize_cbor(&BytesSer(&output.return_data)).unwrap(),
-        )),
+        Outcome::Revert => {
+            Err(revert_error(&format!("contract reverted at {0}", output.pc), &output.return_data))
+        }
     }
 }
 
+// The error for a reverted contract, describing the decoded revert reason.
+// The raw revert payload is carried as the error's data, and so returned to the caller.
+fn revert_error(context: &str, payload: &[u8]) -> ActorError {
+    ActorError::unchecked_with_data(
+        EVM_CONTRACT_REVERTED,
+        format!("{}: {}", context, RevertReason::decode(payload)),
+        IpldBlock::serialize_cbor(&BytesSer(payload)).unwrap(),
+    )
+}
+
 impl EvmContractActor {
     pub fn constructor<RT>(rt: &RT, params: ConstructorParams) -> Result<(), ActorError>
     where
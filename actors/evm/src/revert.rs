@@ -0,0 +1,82 @@
+use std::fmt;
+
+use fil_actors_evm_shared::uints::U256;
+use fvm_ipld_encoding::BytesDe;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+
+/// The selector of Solidity's `Error(string)`, raised by `revert("...")` and `require`.
+pub const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// The selector of Solidity's `Panic(uint256)`, raised by failed assertions, arithmetic
+/// overflow and similar.
+pub const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// The reason a contract reverted, decoded from the revert payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RevertReason {
+    /// The contract reverted without data.
+    Empty,
+    /// An `Error(string)` with its message.
+    Error(String),
+    /// A `Panic(uint256)` with its code.
+    Panic(U256),
+    /// A custom error, or any other payload, which is not decoded.
+    Custom(Vec<u8>),
+}
+
+impl RevertReason {
+    /// Decodes a revert payload.
+    /// A payload which is not a well-formed `Error(string)` or `Panic(uint256)` is custom.
+    pub fn decode(payload: &[u8]) -> Self {
+        if payload.is_empty() {
+            return RevertReason::Empty;
+        }
+        let decoded = match payload.split_at_checked(4) {
+            Some((selector, args)) if selector == ERROR_SELECTOR => {
+                decode_string(args).map(RevertReason::Error)
+            }
+            Some((selector, args)) if selector == PANIC_SELECTOR && args.len() == 32 => {
+                Some(RevertReason::Panic(U256::from_big_endian(args)))
+            }
+            _ => None,
+        };
+        decoded.unwrap_or_else(|| RevertReason::Custom(payload.to_vec()))
+    }
+
+    /// Decodes the revert payload carried as the data of an EVM actor's revert error,
+    /// or returns None if the data is not a payload.
+    pub fn from_error_data(data: Option<&IpldBlock>) -> Option<Self> {
+        let BytesDe(payload) = data?.deserialize().ok()?;
+        Some(Self::decode(&payload))
+    }
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevertReason::Empty => write!(f, "no reason"),
+            RevertReason::Error(msg) => write!(f, "{:?}", msg),
+            RevertReason::Panic(code) => write!(f, "panic 0x{:x}", code),
+            RevertReason::Custom(payload) => write!(f, "custom error 0x{}", hex::encode(payload)),
+        }
+    }
+}
+
+// Decodes an ABI-encoded string argument: a word holding the offset of the string,
+// then at that offset a word holding its length, followed by its bytes.
+fn decode_string(args: &[u8]) -> Option<String> {
+    let offset = read_len(args, 0)?;
+    let len = read_len(args, offset)?;
+    let start = offset.checked_add(32)?;
+    let bytes = args.get(start..start.checked_add(len)?)?;
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+// Reads a word at an offset as a length, if it is in bounds and fits in a usize.
+fn read_len(args: &[u8], offset: usize) -> Option<usize> {
+    let word = args.get(offset..offset.checked_add(32)?)?;
+    let value = U256::from_big_endian(word);
+    if value.bits() > usize::BITS as usize {
+        return None;
+    }
+    Some(value.as_usize())
+}
@@ -26,6 +26,13 @@ pub struct TransientData {
     pub transient_data_state: Cid,
     /// The data representing the transient data lifespan
     pub transient_data_lifespan: TransientDataLifespan,
+    /// The number of calls made by invocations of this contract that are still in progress.
+    /// Kept apart from the transient storage slots so that contract code can't alter it.
+    ///
+    /// This field is not included in the serialised form of transient data recorded before
+    /// it was introduced, and is zero for that data.
+    #[serde(default)]
+    pub call_depth: u64,
 }
 
 /// A structure representing the transient data lifespan.
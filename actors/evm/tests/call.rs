@@ -204,6 +204,109 @@ fn test_call() {
 
     let result = util::invoke_contract(&rt, &contract_params);
     assert_eq!(U256::from_big_endian(&result), U256::from(0x42));
+
+    // The call depth recorded for the duration of the call is cleared once it returns.
+    let state: evm::State = rt.get_state();
+    assert!(state.transient_data.is_none());
+}
+
+#[test]
+fn test_call_subcall_limit() {
+    let contract = call_proxy_contract();
+    let mut rt = util::construct_and_verify(contract);
+    rt.policy.evm_max_subcalls = 0;
+
+    let evm_target = EthAddress(hex_literal::hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let mut contract_params = vec![0u8; 36];
+    evm_target.as_evm_word().write_as_big_endian(&mut contract_params[..32]);
+
+    // The call is refused before anything is sent.
+    rt.expect_validate_caller_any();
+    rt.expect_gas_available(10_000_000_000u64);
+    let err = rt
+        .call::<evm::EvmContractActor>(
+            evm::Method::InvokeContract as u64,
+            IpldBlock::serialize_cbor(&BytesSer(&contract_params)).unwrap(),
+        )
+        .unwrap_err();
+    assert_eq!(evm::EVM_CONTRACT_SUBCALL_LIMIT_EXCEEDED, err.exit_code());
+    rt.verify();
+}
+
+#[test]
+fn test_call_precompile_not_counted_as_subcall() {
+    let contract = call_proxy_contract();
+    let mut rt = util::construct_and_verify(contract);
+    rt.policy.evm_max_subcalls = 0;
+
+    // The identity precompile echoes its input without calling out.
+    let identity = EthAddress(hex_literal::hex!("0000000000000000000000000000000000000004"));
+    let mut contract_params = vec![0u8; 64];
+    identity.as_evm_word().write_as_big_endian(&mut contract_params[..32]);
+    contract_params[63] = 0x42;
+
+    let result = util::invoke_contract(&rt, &contract_params);
+    assert_eq!(U256::from_big_endian(&result), U256::from(0x42));
+}
+
+#[test]
+fn test_call_depth_limit() {
+    let contract = call_proxy_contract();
+    let mut rt = util::construct_and_verify(contract);
+    rt.policy.evm_max_call_depth = 0;
+
+    let evm_target = EthAddress(hex_literal::hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let mut contract_params = vec![0u8; 36];
+    evm_target.as_evm_word().write_as_big_endian(&mut contract_params[..32]);
+
+    // The call is refused before anything is sent.
+    rt.expect_validate_caller_any();
+    rt.expect_gas_available(10_000_000_000u64);
+    let err = rt
+        .call::<evm::EvmContractActor>(
+            evm::Method::InvokeContract as u64,
+            IpldBlock::serialize_cbor(&BytesSer(&contract_params)).unwrap(),
+        )
+        .unwrap_err();
+    assert_eq!(evm::EVM_CONTRACT_CALL_DEPTH_EXCEEDED, err.exit_code());
+    rt.verify();
+}
+
+#[test]
+fn test_call_depth_recorded_apart_from_transient_storage() {
+    let contract = call_proxy_contract();
+    let mut rt = util::construct_and_verify(contract);
+    rt.policy.evm_max_call_depth = 1;
+
+    // Record that the contract is already nested the maximum depth in the current message.
+    // The contract has no storage, so its storage root is also an empty transient storage root.
+    let mut state: evm::State = rt.get_state();
+    state.transient_data = Some(evm::TransientData {
+        transient_data_state: state.contract_state,
+        transient_data_lifespan: evm::TransientDataLifespan {
+            origin: rt.origin.borrow().id().unwrap(),
+            nonce: *rt.nonce.borrow(),
+        },
+        call_depth: 1,
+    });
+    rt.replace_state(&state);
+
+    let evm_target = EthAddress(hex_literal::hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+    let mut contract_params = vec![0u8; 36];
+    evm_target.as_evm_word().write_as_big_endian(&mut contract_params[..32]);
+
+    // The call is refused, including from a read-only invocation.
+    rt.set_read_only(true);
+    rt.expect_validate_caller_any();
+    rt.expect_gas_available(10_000_000_000u64);
+    let err = rt
+        .call::<evm::EvmContractActor>(
+            evm::Method::InvokeContract as u64,
+            IpldBlock::serialize_cbor(&BytesSer(&contract_params)).unwrap(),
+        )
+        .unwrap_err();
+    assert_eq!(evm::EVM_CONTRACT_CALL_DEPTH_EXCEEDED, err.exit_code());
+    rt.verify();
 }
 
 const TRANSFER_GAS_VALUE: u64 = 10_000_000;
@@ -753,6 +856,87 @@ fn call_actor_syscall_error() {
     test.run_test_expecting(&rt, expect, util::PrecompileExit::Success);
 }
 
+#[test]
+fn call_actor_cbor_helpers() {
+    let contract = {
+        let (init, body) = util::PrecompileTest::test_runner_assembly();
+        asm::new_contract("call_actor-precompile-test", &init, &body).unwrap()
+    };
+    let rt = util::construct_and_verify(contract);
+    let addr = Address::new_delegated(1234, b"foobarboxy").unwrap();
+
+    // Wrap a u64 param, and unwrap a bytes return value.
+    let mut call_params = CallActorParams::default();
+    call_params.flags = U256::from((1u64 << 32) | (2u64 << 40));
+    call_params.set_params(
+        CallActorParams::FIRST_DYNAMIC_OFFSET,
+        Some(U256::from(42).to_bytes().to_vec()),
+    );
+    call_params.set_addr(CallActorParams::EMPTY_PARAM_ADDR_OFFSET + 32, addr.to_bytes());
+
+    let mut test = util::PrecompileTest {
+        precompile_address: util::NativePrecompile::CallActor.eth_address(),
+        output_size: 32,
+        call_op: util::PrecompileCallOpcode::DelegateCall,
+        // overwritten in tests
+        expected_return: vec![],
+        expected_exit_code: util::PrecompileExit::Success,
+        input: call_params.clone().into(),
+    };
+
+    let ret_bytes = vec![0xca, 0xfe];
+    rt.expect_gas_available(10_000_000_000);
+    rt.expect_send(
+        addr,
+        0,
+        IpldBlock::serialize_cbor(&42u64).unwrap(),
+        TokenAmount::zero(),
+        Some(0),
+        SendFlags::empty(),
+        IpldBlock::serialize_cbor(&BytesSer(&ret_bytes)).unwrap(),
+        ExitCode::OK,
+        None,
+    );
+
+    let mut data = vec![0; 32];
+    data[..ret_bytes.len()].copy_from_slice(&ret_bytes);
+    let expect =
+        CallActorReturn { codec: 0, data_size: ret_bytes.len() as u32, data, ..Default::default() };
+    test.run_test_expecting(&rt, expect, util::PrecompileExit::Success);
+
+    // A return value that doesn't decode is passed through unchanged.
+    rt.expect_gas_available(10_000_000_000);
+    let not_bytes = IpldBlock::serialize_cbor(&7u64).unwrap().unwrap();
+    rt.expect_send(
+        addr,
+        0,
+        IpldBlock::serialize_cbor(&42u64).unwrap(),
+        TokenAmount::zero(),
+        Some(0),
+        SendFlags::empty(),
+        Some(not_bytes.clone()),
+        ExitCode::OK,
+        None,
+    );
+    let mut data = vec![0; 32];
+    data[..not_bytes.data.len()].copy_from_slice(&not_bytes.data);
+    let expect = CallActorReturn {
+        codec: CBOR,
+        data_size: not_bytes.data.len() as u32,
+        data,
+        ..Default::default()
+    };
+    test.run_test_expecting(&rt, expect, util::PrecompileExit::Success);
+
+    // A params helper can't be combined with a codec, and unknown modes are rejected.
+    for (flags, codec) in [(1u64 << 32, CBOR), (4u64 << 32, 0), (4u64 << 40, 0), (1u64 << 48, 0)] {
+        call_params.flags = U256::from(flags);
+        call_params.codec(U256::from(codec));
+        test.input = call_params.clone().into();
+        test.run_test_expecting(&rt, vec![], util::PrecompileExit::Reverted);
+    }
+}
+
 #[cfg(test)]
 mod call_actor_invalid {
     use super::*;
@@ -18,7 +18,7 @@ pub struct TestEnv {
 
 impl TestEnv {
     pub fn take_store_stats(&mut self) -> BSStats {
-        self.runtime.store.stats.take()
+        self.runtime.store.reset_stats()
     }
 
     pub fn clear_store_stats(&mut self) {
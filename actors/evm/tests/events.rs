@@ -1,6 +1,7 @@
 mod asm;
 
 use fil_actors_evm_shared::uints::U256;
+use fil_actors_runtime::EthLog;
 use fvm_ipld_encoding::IPLD_RAW;
 use fvm_shared::event::{ActorEvent, Entry, Flags};
 
@@ -92,7 +93,7 @@ fn test_events() {
 
     // log four with data
     contract_params[3] = 0x02;
-    rt.expect_emitted_event(ActorEvent {
+    let four_topic_event = ActorEvent {
         entries: vec![
             Entry {
                 flags: Flags::FLAG_INDEXED_ALL,
@@ -125,7 +126,16 @@ fn test_events() {
                 value: vec![0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88],
             },
         ],
-    });
+    };
+    // The emitted event decodes back to the Ethereum log.
+    assert_eq!(
+        EthLog {
+            topics: [0x1111, 0x2222, 0x3333, 0x4444].map(|t| U256::from(t).to_bytes()).to_vec(),
+            data: vec![0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88],
+        },
+        EthLog::from_event(&four_topic_event).unwrap()
+    );
+    rt.expect_emitted_event(four_topic_event);
     util::invoke_contract(&rt, &contract_params);
 
     rt.verify();
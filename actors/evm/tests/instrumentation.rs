@@ -0,0 +1,74 @@
+#![cfg(feature = "instrumentation")]
+
+mod asm;
+mod util;
+
+use evm::interpreter::instrumentation::{self, OpcodeStats};
+use evm::interpreter::opcodes;
+use fil_actor_evm as evm;
+use fil_actors_evm_shared::uints::U256;
+
+fn add_contract() -> Vec<u8> {
+    let init = "";
+    let body = r#"
+push1 0x02
+push1 0x03
+add
+%return_stack_word()
+"#;
+    asm::new_contract("add", init, body).unwrap()
+}
+
+#[test]
+fn records_opcode_counts() {
+    let rt = util::construct_and_verify(add_contract());
+
+    instrumentation::enable(false);
+    let result = util::invoke_contract(&rt, &[]);
+    let report = instrumentation::take().unwrap();
+    assert_eq!(U256::from(5), U256::from_big_endian(&result));
+
+    assert_eq!(OpcodeStats { count: 5, gas: 0 }, report.get(opcodes::PUSH1));
+    assert_eq!(OpcodeStats { count: 1, gas: 0 }, report.get(opcodes::ADD));
+    assert_eq!(OpcodeStats { count: 1, gas: 0 }, report.get(opcodes::MSTORE));
+    assert_eq!(OpcodeStats { count: 1, gas: 0 }, report.get(opcodes::RETURN));
+    assert_eq!(OpcodeStats::default(), report.get(opcodes::SUB));
+    assert_eq!(8, report.total_count());
+
+    // Nothing is recorded once the report has been taken.
+    util::invoke_contract(&rt, &[]);
+    assert!(instrumentation::take().is_none());
+}
+
+#[test]
+fn records_opcode_gas() {
+    let rt = util::construct_and_verify(add_contract());
+
+    // Gas consumed by each executed instruction, in order.
+    let executed = [
+        (opcodes::PUSH1, 3),
+        (opcodes::PUSH1, 3),
+        (opcodes::ADD, 5),
+        (opcodes::PUSH1, 3),
+        (opcodes::MSTORE, 7),
+        (opcodes::PUSH1, 3),
+        (opcodes::PUSH1, 3),
+        (opcodes::RETURN, 2),
+    ];
+    let mut available = 1_000_000;
+    for (_, gas) in executed {
+        rt.expect_gas_available(available);
+        available -= gas;
+        rt.expect_gas_available(available);
+    }
+
+    instrumentation::enable(true);
+    util::invoke_contract(&rt, &[]);
+    let report = instrumentation::take().unwrap();
+
+    assert_eq!(OpcodeStats { count: 5, gas: 15 }, report.get(opcodes::PUSH1));
+    assert_eq!(OpcodeStats { count: 1, gas: 5 }, report.get(opcodes::ADD));
+    assert_eq!(OpcodeStats { count: 1, gas: 7 }, report.get(opcodes::MSTORE));
+    assert_eq!(OpcodeStats { count: 1, gas: 2 }, report.get(opcodes::RETURN));
+    assert_eq!(executed.iter().map(|(_, gas)| gas).sum::<u64>(), report.total_gas());
+}
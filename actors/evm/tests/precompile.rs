@@ -2,9 +2,12 @@ mod asm;
 
 use fil_actors_evm_shared::{address::EthAddress, uints::U256};
 use fil_actors_runtime::{
-    EAM_ACTOR_ID,
+    EAM_ACTOR_ID, REWARD_ACTOR_ADDR,
     test_utils::{MockRuntime, new_bls_addr},
 };
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::sys::SendFlags;
 use fvm_shared::{METHOD_SEND, address::Address as FILAddress, econ::TokenAmount, error::ExitCode};
 
 mod util;
@@ -254,6 +257,70 @@ fn test_precompile_randomness() {
     }
 }
 
+#[test]
+fn test_precompile_base_fee_history() {
+    let (init, body) = PrecompileTest::test_runner_assembly();
+    let rt =
+        util::construct_and_verify(asm::new_contract("precompile-tester", &init, &body).unwrap());
+    let method = frc42_dispatch::method_hash!("BaseFeeHistory");
+    let limit = 2u64;
+    let params = IpldBlock::serialize_cbor(&limit).unwrap();
+    {
+        // The reward actor's history is returned as (epoch, base fee) word pairs.
+        let history: Vec<(ChainEpoch, TokenAmount)> =
+            vec![(101, TokenAmount::from_atto(1_000)), (99, TokenAmount::from_atto(2_000))];
+        let test = PrecompileTest {
+            precompile_address: NativePrecompile::GetBaseFeeHistory.eth_address(),
+            output_size: 128,
+            expected_exit_code: PrecompileExit::Success,
+            call_op: util::PrecompileCallOpcode::StaticCall,
+            input: U256::from(limit).to_bytes().to_vec(),
+            expected_return: [
+                U256::from(101).to_bytes(),
+                U256::from(1_000).to_bytes(),
+                U256::from(99).to_bytes(),
+                U256::from(2_000).to_bytes(),
+            ]
+            .concat(),
+        };
+        rt.expect_send(
+            REWARD_ACTOR_ADDR,
+            method,
+            params.clone(),
+            TokenAmount::default(),
+            None,
+            SendFlags::READ_ONLY,
+            IpldBlock::serialize_cbor(&history).unwrap(),
+            ExitCode::OK,
+            None,
+        );
+        test.run_test(&rt);
+    }
+    {
+        // The call to the reward actor fails.
+        let test = PrecompileTest {
+            precompile_address: NativePrecompile::GetBaseFeeHistory.eth_address(),
+            output_size: 128,
+            expected_exit_code: PrecompileExit::Reverted,
+            call_op: util::PrecompileCallOpcode::StaticCall,
+            input: U256::from(limit).to_bytes().to_vec(),
+            expected_return: vec![],
+        };
+        rt.expect_send(
+            REWARD_ACTOR_ADDR,
+            method,
+            params,
+            TokenAmount::default(),
+            None,
+            SendFlags::READ_ONLY,
+            None,
+            ExitCode::USR_ILLEGAL_STATE,
+            None,
+        );
+        test.run_test(&rt);
+    }
+}
+
 #[test]
 fn test_precompile_transfer() {
     let (init, body) = util::PrecompileTest::test_runner_assembly();
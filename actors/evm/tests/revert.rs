@@ -1,4 +1,7 @@
 use fil_actor_evm as evm;
+use fil_actor_evm::revert::{ERROR_SELECTOR, PANIC_SELECTOR, RevertReason};
+use fil_actors_evm_shared::uints::U256;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::{BytesSer, RawBytes};
 
 mod asm;
@@ -27,8 +30,65 @@ revert
     assert!(result.is_err());
     let mut e = result.unwrap_err();
     assert_eq!(e.exit_code(), evm::EVM_CONTRACT_REVERTED);
+    assert!(e.msg().ends_with("custom error 0xdeadbeef"), "unexpected message: {}", e.msg());
+    let data = e.take_data().unwrap();
     assert_eq!(
-        e.take_data().unwrap().data,
+        data.data,
         RawBytes::serialize(BytesSer(&[0xde, 0xad, 0xbe, 0xef])).unwrap().bytes()
     );
+    assert_eq!(
+        Some(RevertReason::Custom(vec![0xde, 0xad, 0xbe, 0xef])),
+        RevertReason::from_error_data(Some(&data))
+    );
+}
+
+fn word(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+fn error_payload(msg: &str) -> Vec<u8> {
+    let mut payload = ERROR_SELECTOR.to_vec();
+    payload.extend_from_slice(&word(32));
+    payload.extend_from_slice(&word(msg.len()));
+    payload.extend_from_slice(msg.as_bytes());
+    payload.resize((payload.len() - 4).next_multiple_of(32) + 4, 0);
+    payload
+}
+
+#[test]
+fn test_revert_reason_decoding() {
+    assert_eq!(RevertReason::Empty, RevertReason::decode(&[]));
+    assert_eq!(
+        RevertReason::Error("insufficient balance".to_string()),
+        RevertReason::decode(&error_payload("insufficient balance"))
+    );
+    assert_eq!(RevertReason::Error(String::new()), RevertReason::decode(&error_payload("")));
+
+    let mut panic = PANIC_SELECTOR.to_vec();
+    panic.extend_from_slice(&word(0x11));
+    assert_eq!(RevertReason::Panic(U256::from_u64(0x11)), RevertReason::decode(&panic));
+    assert_eq!("panic 0x11", RevertReason::decode(&panic).to_string());
+
+    // Malformed payloads are not decoded.
+    for payload in [
+        // Truncated string.
+        error_payload("insufficient balance")[..4 + 64 + 8].to_vec(),
+        // String offset out of bounds.
+        [&ERROR_SELECTOR[..], &word(1024)[..]].concat(),
+        // Truncated panic code.
+        panic[..20].to_vec(),
+        // Unknown selector.
+        vec![1, 2, 3, 4, 5],
+        // Too short for a selector.
+        vec![1, 2],
+    ] {
+        assert_eq!(RevertReason::Custom(payload.clone()), RevertReason::decode(&payload));
+    }
+
+    // Only a byte payload is decoded from error data.
+    assert_eq!(None, RevertReason::from_error_data(None));
+    let not_bytes = IpldBlock::serialize_cbor(&42u64).unwrap();
+    assert_eq!(None, RevertReason::from_error_data(not_bytes.as_ref()));
 }
@@ -140,6 +140,7 @@ pub enum NativePrecompile {
     GetActorTypeDISABLED = 4,
     CallActorId = 5,
     GetRandomness = 6,
+    GetBaseFeeHistory = 7,
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,40 @@
+use cid::Cid;
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::ActorID;
+use fvm_shared::address::Address;
+
+/// Indicates a new actor has been created and constructed.
+/// The delegated address is only present for actors created through `Exec4`.
+pub fn actor_created(
+    rt: &impl Runtime,
+    id: ActorID,
+    code: &Cid,
+    robust_address: &Address,
+    delegated_address: Option<&Address>,
+    creator: ActorID,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("actor-created")
+        .field_indexed("id", &id)
+        .field_indexed("code", code)
+        .field("robust", robust_address)
+        .field("delegated", &delegated_address)
+        .field_indexed("creator", &creator)
+        .emit(rt)
+}
+
+/// Indicates a delegated address has been re-mapped from a deleted actor to a newly created one.
+pub fn delegated_address_remapped(
+    rt: &impl Runtime,
+    delegated_address: &Address,
+    previous_id: ActorID,
+    id: ActorID,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("delegated-address-remapped")
+        .field_indexed("delegated", delegated_address)
+        .field_indexed("previous-id", &previous_id)
+        .field_indexed("id", &id)
+        .emit(rt)
+}
@@ -17,6 +17,7 @@ use num_derive::FromPrimitive;
 pub use self::state::State;
 pub use self::types::*;
 
+mod emit;
 mod state;
 pub mod testing;
 mod types;
@@ -104,6 +105,15 @@ impl Actor {
         ))
         .context("constructor failed")?;
 
+        emit::actor_created(
+            rt,
+            id_address,
+            &params.code_cid,
+            &robust_address,
+            None,
+            rt.message().caller().id().unwrap(),
+        )?;
+
         Ok(ExecReturn { id_address: Address::new_id(id_address), robust_address })
     }
 
@@ -129,13 +139,34 @@ impl Actor {
 
         // Allocate an ID for this actor.
         // Store mapping of actor addresses to the actor ID.
-        let (id_address, existing): (ActorID, bool) = rt.transaction(|s: &mut State, rt| {
-            s.map_addresses_to_id(rt.store(), &robust_address, Some(&delegated_address))
-                .context("failed to map addresses to ID")
-        })?;
+        // If the f4 address was assigned to an actor that has since been deleted (e.g. it
+        // self-destructed), the address manager is recreating it: the f4 address is re-mapped to a
+        // newly allocated ID rather than left pointing at the deleted actor.
+        let (id_address, existing, replaced): (ActorID, bool, Option<ActorID>) =
+            rt.transaction(|s: &mut State, rt| {
+                let prev_id = s
+                    .resolve_address(rt.store(), &delegated_address)?
+                    .and_then(|addr| addr.id().ok());
+                if let Some(prev_id) = prev_id {
+                    if rt.get_actor_code_cid(&prev_id).is_none() {
+                        let (id, prev_id) = s
+                            .remap_delegated_address(
+                                rt.store(),
+                                &robust_address,
+                                &delegated_address,
+                            )
+                            .context("failed to re-map delegated address")?;
+                        return Ok((id, false, Some(prev_id)));
+                    }
+                }
+                let (id, existing) = s
+                    .map_addresses_to_id(rt.store(), &robust_address, Some(&delegated_address))
+                    .context("failed to map addresses to ID")?;
+                Ok((id, existing, None))
+            })?;
 
-        // If the f4 address was already assigned, make sure we're deploying over a placeholder and not
-        // some other existing actor (and make sure the target actor wasn't deleted either).
+        // If the f4 address was already assigned to a live actor, make sure we're deploying over a
+        // placeholder and not some other existing actor.
         if existing {
             let code_cid = rt
                 .get_actor_code_cid(&id_address)
@@ -160,6 +191,18 @@ impl Actor {
         ))
         .context("constructor failed")?;
 
+        if let Some(prev_id) = replaced {
+            emit::delegated_address_remapped(rt, &delegated_address, prev_id, id_address)?;
+        }
+        emit::actor_created(
+            rt,
+            id_address,
+            &params.code_cid,
+            &robust_address,
+            Some(&delegated_address),
+            caller_id,
+        )?;
+
         Ok(Exec4Return { id_address: Address::new_id(id_address), robust_address })
     }
 }
@@ -31,7 +31,8 @@ impl State {
     /// With no delegated address, or if the delegated address is not already mapped,
     /// allocates a new ID address and maps both to it.
     /// If the delegated address is already present, maps the robust address to that actor ID.
-    /// Fails if the robust address is already mapped. The assignment of an ID to an address is one-time-only, even if the actor at that ID is deleted.
+    /// Fails if the robust address is already mapped. The assignment of an ID to an address is one-time-only, even if the actor at that ID is deleted,
+    /// except that a delegated address may be re-mapped with `remap_delegated_address`.
     /// Returns the actor ID and a boolean indicating whether or not the actor already exists.
     pub fn map_addresses_to_id<BS: Blockstore>(
         &mut self,
@@ -71,6 +72,38 @@ impl State {
         Ok((id, existing))
     }
 
+    /// Re-maps an already-mapped delegated address to a newly allocated actor ID, and maps the
+    /// robust address to that ID too.
+    /// This is for an actor being recreated at its delegated address by the address manager after
+    /// the actor previously assigned that address was deleted. The caller must check the deletion.
+    /// Fails if the delegated address is not mapped, or the robust address is already mapped.
+    /// Returns the new actor ID and the ID the delegated address was previously mapped to.
+    pub fn remap_delegated_address<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        robust_addr: &Address,
+        delegated_addr: &Address,
+    ) -> Result<(ActorID, ActorID), ActorError> {
+        let mut map = AddressMap::load(store, &self.address_map, DEFAULT_HAMT_CONFIG, "addresses")?;
+        let prev_id = *map.get(delegated_addr)?.ok_or_else(|| {
+            actor_error!(not_found, "delegated address {} is not mapped", delegated_addr)
+        })?;
+        let new_id = self.next_id;
+        self.next_id += 1;
+        map.set(delegated_addr, new_id)?;
+
+        let is_new = map.set_if_absent(robust_addr, new_id)?;
+        if !is_new {
+            return Err(actor_error!(
+                forbidden,
+                "robust address {} is already allocated in the address map",
+                robust_addr
+            ));
+        }
+        self.address_map = map.flush()?;
+        Ok((new_id, prev_id))
+    }
+
     /// ResolveAddress resolves an address to an ID-address, if possible.
     /// If the provided address is an ID address, it is returned as-is.
     /// This means that mapped ID-addresses (which should only appear as values, not keys) and
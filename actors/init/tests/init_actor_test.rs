@@ -11,7 +11,8 @@ use fil_actor_init::{
 };
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::{
-    ActorError, FIRST_NON_SINGLETON_ADDR, Multimap, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    ActorError, EventBuilder, FIRST_NON_SINGLETON_ADDR, Multimap, STORAGE_POWER_ACTOR_ADDR,
+    SYSTEM_ACTOR_ADDR,
 };
 use fil_actors_runtime::{EAM_ACTOR_ADDR, EAM_ACTOR_ID, test_utils::*};
 use fvm_ipld_encoding::RawBytes;
@@ -81,6 +82,15 @@ fn repeated_robust_address() {
             ExitCode::OK,
         );
 
+        expect_actor_created(
+            &rt,
+            expected_id,
+            *MULTISIG_ACTOR_CODE_ID,
+            unique_address,
+            None,
+            some_acc_actor.id().unwrap(),
+        );
+
         // Return should have been successful. Check the returned addresses
         let exec_ret = exec_and_verify(&rt, *MULTISIG_ACTOR_CODE_ID, &fake_params).unwrap();
         assert_eq!(unique_address, exec_ret.robust_address, "Robust address does not macth");
@@ -145,6 +155,15 @@ fn create_2_payment_channels() {
             ExitCode::OK,
         );
 
+        expect_actor_created(
+            &rt,
+            expected_id,
+            *PAYCH_ACTOR_CODE_ID,
+            unique_address,
+            None,
+            anne.id().unwrap(),
+        );
+
         let exec_ret = exec_and_verify(&rt, *PAYCH_ACTOR_CODE_ID, &fake_params).unwrap();
         assert_eq!(unique_address, exec_ret.robust_address, "Robust Address does not match");
         assert_eq!(expected_id_addr, exec_ret.id_address, "Id address does not match");
@@ -186,6 +205,15 @@ fn create_storage_miner() {
         ExitCode::OK,
     );
 
+    expect_actor_created(
+        &rt,
+        expected_id,
+        *MINER_ACTOR_CODE_ID,
+        unique_address,
+        None,
+        STORAGE_POWER_ACTOR_ADDR.id().unwrap(),
+    );
+
     let exec_ret = exec_and_verify(&rt, *MINER_ACTOR_CODE_ID, &fake_params).unwrap();
     assert_eq!(unique_address, exec_ret.robust_address);
     assert_eq!(expected_id_addr, exec_ret.id_address);
@@ -235,6 +263,15 @@ fn create_multisig_actor() {
         ExitCode::OK,
     );
 
+    expect_actor_created(
+        &rt,
+        expected_id,
+        *MULTISIG_ACTOR_CODE_ID,
+        unique_address,
+        None,
+        some_acc_actor.id().unwrap(),
+    );
+
     // Return should have been successful. Check the returned addresses
     let exec_ret = exec_and_verify(&rt, *MULTISIG_ACTOR_CODE_ID, &fake_params).unwrap();
     assert_eq!(unique_address, exec_ret.robust_address, "Robust address does not macth");
@@ -317,6 +354,15 @@ fn call_exec4() {
         ExitCode::OK,
     );
 
+    expect_actor_created(
+        &rt,
+        expected_id,
+        *MULTISIG_ACTOR_CODE_ID,
+        unique_address,
+        Some(f4_addr),
+        namespace,
+    );
+
     // Return should have been successful. Check the returned addresses
     let exec_ret =
         exec4_and_verify(&rt, namespace, subaddr, *MULTISIG_ACTOR_CODE_ID, &fake_params).unwrap();
@@ -341,13 +387,53 @@ fn call_exec4() {
 
     assert_eq!(exec_err.exit_code(), ExitCode::USR_FORBIDDEN);
 
-    // Delete and try again, it should still fail.
+    // Delete and try again, the f4 address should be re-mapped to a new actor.
     rt.actor_code_cids.borrow_mut().remove(&resolved_id);
     let unique_address = Address::new_actor(b"test2");
     rt.new_actor_addr.replace(Some(unique_address));
+    let new_id = expected_id + 1;
+    let new_id_addr = Address::new_id(new_id);
+    rt.expect_create_actor(*MULTISIG_ACTOR_CODE_ID, new_id, Some(f4_addr));
+    rt.expect_send_simple(
+        new_id_addr,
+        METHOD_CONSTRUCTOR,
+        IpldBlock::serialize_cbor(&fake_params).unwrap(),
+        TokenAmount::zero(),
+        None,
+        ExitCode::OK,
+    );
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("delegated-address-remapped")
+            .field_indexed("delegated", &f4_addr)
+            .field_indexed("previous-id", &expected_id)
+            .field_indexed("id", &new_id)
+            .build()
+            .unwrap(),
+    );
+    expect_actor_created(
+        &rt,
+        new_id,
+        *MULTISIG_ACTOR_CODE_ID,
+        unique_address,
+        Some(f4_addr),
+        namespace,
+    );
+    let exec_ret =
+        exec4_and_verify(&rt, namespace, subaddr, *MULTISIG_ACTOR_CODE_ID, &fake_params).unwrap();
+    assert_eq!(new_id_addr, exec_ret.id_address);
+
+    let init_state: State = rt.get_state();
+    assert_eq!(Some(new_id_addr), init_state.resolve_address(rt.store(), &f4_addr).unwrap());
+    // The deleted actor's robust address still resolves to its own ID.
+    let first_robust = Address::new_actor(b"test");
+    assert_eq!(Some(resolved_id), init_state.resolve_address(rt.store(), &first_robust).unwrap());
+
+    // A live re-mapped actor can't be replaced.
+    let unique_address = Address::new_actor(b"test3");
+    rt.new_actor_addr.replace(Some(unique_address));
     let exec_err = exec4_and_verify(&rt, namespace, subaddr, *MULTISIG_ACTOR_CODE_ID, &fake_params)
         .unwrap_err();
-
     assert_eq!(exec_err.exit_code(), ExitCode::USR_FORBIDDEN);
 }
 
@@ -394,6 +480,15 @@ fn call_exec4_placeholder() {
         ExitCode::OK,
     );
 
+    expect_actor_created(
+        &rt,
+        expected_id,
+        *MULTISIG_ACTOR_CODE_ID,
+        unique_address,
+        Some(f4_addr),
+        namespace,
+    );
+
     // Return should have been successful. Check the returned addresses
     let exec_ret =
         exec4_and_verify(&rt, namespace, subaddr, *MULTISIG_ACTOR_CODE_ID, &fake_params).unwrap();
@@ -435,6 +530,27 @@ fn construct_and_verify(rt: &MockRuntime) {
     check_state(rt);
 }
 
+fn expect_actor_created(
+    rt: &MockRuntime,
+    id: ActorID,
+    code: Cid,
+    robust_address: Address,
+    delegated_address: Option<Address>,
+    creator: ActorID,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("actor-created")
+            .field_indexed("id", &id)
+            .field_indexed("code", &code)
+            .field("robust", &robust_address)
+            .field("delegated", &delegated_address)
+            .field_indexed("creator", &creator)
+            .build()
+            .unwrap(),
+    );
+}
+
 fn exec_and_verify<S>(rt: &MockRuntime, code_id: Cid, params: &S) -> Result<ExecReturn, ActorError>
 where
     S: Serialize,
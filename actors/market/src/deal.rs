@@ -1,9 +1,11 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+use std::collections::BTreeMap;
+
 use cid::{Cid, Version};
-use fvm_ipld_encoding::BytesSer;
 use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::{BytesSer, RawBytes};
 use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::commcid::{FIL_COMMITMENT_UNSEALED, SHA2_256_TRUNC254_PADDED};
@@ -134,6 +136,108 @@ pub struct ClientDealProposal {
     pub client_signature: Signature,
 }
 
+/// Payment terms of a deal.
+/// The storage price and collateral are denominated in FIL and held in escrow.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct DealPayment {
+    pub storage_price_per_epoch: TokenAmount,
+    pub provider_collateral: TokenAmount,
+    pub client_collateral: TokenAmount,
+    /// Payment in a token other than FIL, made in full when the deal is published.
+    pub token: Option<TokenPayment>,
+}
+
+/// An up-front payment from the client to the provider in an FRC-46 token.
+/// The market transfers the amount from the client's allowance for the market to the
+/// provider's owner address when the deal is published; a deal is dropped if the transfer fails.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct TokenPayment {
+    /// Address of the token actor.
+    pub token: Address,
+    pub amount: TokenAmount,
+}
+
+/// Terms of the verified registry allocation to be made for a deal's piece.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct DealAllocationTerms {
+    pub term_min: ChainEpoch,
+    pub term_max: ChainEpoch,
+    pub expiration: ChainEpoch,
+}
+
+/// Name of the extension field in which a client bounds the publish fee it agrees to pay
+/// to the provider, as a CBOR-encoded token amount.
+/// A proposal without the field agrees to no publish fee.
+pub const MAX_PUBLISH_FEE_EXTENSION: &str = "max_publish_fee";
+
+/// DealProposal2 is the second, extensible format of a deal proposal.
+/// Optional fields introduced after this format are carried in the extensions map,
+/// so the serialized shape of the proposal remains stable as the format evolves.
+/// A published proposal is recorded in state in the DealProposal format.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct DealProposal2 {
+    pub piece_cid: Cid,
+    pub piece_size: PaddedPieceSize,
+    pub client: Address,
+    pub provider: Address,
+
+    /// Arbitrary client chosen label to apply to the deal
+    pub label: Label,
+
+    pub start_epoch: ChainEpoch,
+    pub end_epoch: ChainEpoch,
+    pub payment: DealPayment,
+
+    /// Terms of the verified registry allocation to be made for the piece.
+    /// A proposal with allocation terms is a verified deal.
+    pub allocation: Option<DealAllocationTerms>,
+
+    /// CID of off-chain extended metadata for the deal, by which the deal is indexed.
+    pub metadata: Option<Cid>,
+
+    /// Optional fields keyed by name. Fields not recognised by the market are ignored.
+    pub extensions: BTreeMap<String, RawBytes>,
+}
+
+impl DealProposal2 {
+    /// Returns the proposal in the format recorded in state.
+    pub fn to_proposal(&self) -> DealProposal {
+        DealProposal {
+            piece_cid: self.piece_cid,
+            piece_size: self.piece_size,
+            verified_deal: self.allocation.is_some(),
+            client: self.client,
+            provider: self.provider,
+            label: self.label.clone(),
+            start_epoch: self.start_epoch,
+            end_epoch: self.end_epoch,
+            storage_price_per_epoch: self.payment.storage_price_per_epoch.clone(),
+            provider_collateral: self.payment.provider_collateral.clone(),
+            client_collateral: self.payment.client_collateral.clone(),
+        }
+    }
+
+    /// Returns the maximum publish fee the client agrees to pay, zero if unspecified.
+    pub fn max_publish_fee(&self) -> Result<TokenAmount, fvm_ipld_encoding::Error> {
+        match self.extensions.get(MAX_PUBLISH_FEE_EXTENSION) {
+            Some(fee) => fee.deserialize(),
+            None => Ok(TokenAmount::default()),
+        }
+    }
+
+    /// Total size of the names and values of the extension fields.
+    pub fn extensions_size(&self) -> usize {
+        self.extensions.iter().map(|(k, v)| k.len() + v.len()).sum()
+    }
+}
+
+/// ClientDealProposal2 is a DealProposal2 signed by a client
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ClientDealProposal2 {
+    pub proposal: DealProposal2,
+    pub client_signature: Signature,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize_tuple, Deserialize_tuple)]
 pub struct DealState {
     // 0 if not yet included in proven sector (0 is also a valid sector number)
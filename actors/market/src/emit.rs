@@ -1,7 +1,9 @@
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::{ActorError, EventBuilder};
 use fvm_shared::ActorID;
+use fvm_shared::clock::ChainEpoch;
 use fvm_shared::deal::DealID;
+use fvm_shared::econ::TokenAmount;
 
 /// Indicates a deal has been published.
 pub fn deal_published(
@@ -10,12 +12,7 @@ pub fn deal_published(
     provider: ActorID,
     deal_id: DealID,
 ) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("deal-published")
-            .with_parties(deal_id, client, provider)
-            .build()?,
-    )
+    EventBuilder::new().typ("deal-published").with_parties(deal_id, client, provider).emit(rt)
 }
 
 /// Indicates a deal has been activated.
@@ -25,12 +22,7 @@ pub fn deal_activated(
     client: ActorID,
     provider: ActorID,
 ) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("deal-activated")
-            .with_parties(deal_id, client, provider)
-            .build()?,
-    )
+    EventBuilder::new().typ("deal-activated").with_parties(deal_id, client, provider).emit(rt)
 }
 
 /// Indicates a deal has been terminated.
@@ -40,12 +32,7 @@ pub fn deal_terminated(
     client: ActorID,
     provider: ActorID,
 ) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("deal-terminated")
-            .with_parties(deal_id, client, provider)
-            .build()?,
-    )
+    EventBuilder::new().typ("deal-terminated").with_parties(deal_id, client, provider).emit(rt)
 }
 
 /// Indicates a deal has been completed successfully.
@@ -55,12 +42,27 @@ pub fn deal_completed(
     client: ActorID,
     provider: ActorID,
 ) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("deal-completed")
-            .with_parties(deal_id, client, provider)
-            .build()?,
-    )
+    EventBuilder::new().typ("deal-completed").with_parties(deal_id, client, provider).emit(rt)
+}
+
+/// Indicates a deal's payment has been settled for the epochs from `from_epoch` (inclusive)
+/// to `to_epoch` (exclusive), transferring `amount` from the client to the provider.
+pub fn deal_settled(
+    rt: &impl Runtime,
+    deal_id: DealID,
+    client: ActorID,
+    provider: ActorID,
+    amount: &TokenAmount,
+    from_epoch: ChainEpoch,
+    to_epoch: ChainEpoch,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("deal-settled")
+        .with_parties(deal_id, client, provider)
+        .field("amount", amount)
+        .field("from-epoch", &from_epoch)
+        .field("to-epoch", &to_epoch)
+        .emit(rt)
 }
 
 trait WithParties {
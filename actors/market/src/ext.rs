@@ -129,6 +129,15 @@ pub mod verifreg {
         pub extensions: Vec<ClaimExtensionRequest>,
     }
 
+    pub const VALIDATE_ALLOCATION_REQUESTS_METHOD: u64 =
+        frc42_dispatch::method_hash!("ValidateAllocationRequests");
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+    #[serde(transparent)]
+    pub struct ValidateAllocationRequestsParams {
+        pub requests: Vec<AllocationRequest>,
+    }
+
     #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
     pub struct AllocationsResponse {
         // Result for each allocation request.
@@ -145,6 +154,11 @@ pub mod datacap {
     pub const TRANSFER_FROM_METHOD: u64 = frc42_dispatch::method_hash!("TransferFrom");
 }
 
+// Any FRC-46 token actor.
+pub mod token {
+    pub const TRANSFER_FROM_METHOD: u64 = frc42_dispatch::method_hash!("TransferFrom");
+}
+
 pub mod reward {
     pub const THIS_EPOCH_REWARD_METHOD: u64 = 3;
 }
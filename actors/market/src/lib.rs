@@ -3,6 +3,7 @@
 
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::Range;
 
 use cid::Cid;
 use cid::multihash::Multihash;
@@ -17,6 +18,7 @@ use fvm_shared::address::Address;
 use fvm_shared::bigint::BigInt;
 use fvm_shared::clock::{ChainEpoch, EPOCH_UNDEFINED};
 use fvm_shared::crypto::hash::SupportedHashes;
+use fvm_shared::crypto::signature::Signature;
 use fvm_shared::deal::DealID;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
@@ -37,7 +39,10 @@ use fil_actors_runtime::{
     DATACAP_TOKEN_ACTOR_ADDR, REWARD_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
     VERIFIED_REGISTRY_ACTOR_ADDR, actor_dispatch, actor_error, deserialize_block,
 };
-use fil_actors_runtime::{BatchReturnGen, FIRST_ACTOR_SPECIFIC_EXIT_CODE, extract_send_result};
+use fil_actors_runtime::{
+    BatchReturn, BatchReturnGen, Cursor, FIRST_ACTOR_SPECIFIC_EXIT_CODE, extract_send_result,
+    state_getters,
+};
 
 use crate::balance_table::BalanceTable;
 use crate::ext::verifreg::{AllocationID, AllocationRequest};
@@ -68,6 +73,8 @@ pub const NO_ALLOCATION_ID: u64 = 0;
 pub const EX_DEAL_EXPIRED: ExitCode = ExitCode::new(FIRST_ACTOR_SPECIFIC_EXIT_CODE);
 // Indicates that information about a deal's activation is not yet available.
 pub const EX_DEAL_NOT_ACTIVATED: ExitCode = ExitCode::new(FIRST_ACTOR_SPECIFIC_EXIT_CODE + 1);
+// Indicates that publishing of new deals is paused by governance.
+pub const EX_DEAL_PUBLISHING_PAUSED: ExitCode = ExitCode::new(FIRST_ACTOR_SPECIFIC_EXIT_CODE + 2);
 
 /// Market actor methods available
 #[derive(FromPrimitive)]
@@ -82,6 +89,7 @@ pub enum Method {
     OnMinerSectorsTerminate = 7,
     // ComputeDataCommitment = 8, // Deprecated
     CronTick = 9,
+    SetPublishingPaused = 10,
     // Method numbers derived from FRC-0042 standards
     AddBalanceExported = frc42_dispatch::method_hash!("AddBalance"),
     WithdrawBalanceExported = frc42_dispatch::method_hash!("WithdrawBalance"),
@@ -100,6 +108,15 @@ pub enum Method {
     GetDealSectorExported = frc42_dispatch::method_hash!("GetDealSector"),
     SettleDealPaymentsExported = frc42_dispatch::method_hash!("SettleDealPayments"),
     SectorContentChangedExported = ext::miner::SECTOR_CONTENT_CHANGED,
+    ListClientDealsExported = frc42_dispatch::method_hash!("ListClientDeals"),
+    PublishStorageDeals2Exported = frc42_dispatch::method_hash!("PublishStorageDeals2"),
+    LockedFundsExported = frc42_dispatch::method_hash!("LockedFunds"),
+    SetDealPolicyExported = frc42_dispatch::method_hash!("SetDealPolicy"),
+    GetDealPolicyExported = frc42_dispatch::method_hash!("GetDealPolicy"),
+    GetDealsForPieceExported = frc42_dispatch::method_hash!("GetDealsForPiece"),
+    GetDealsForMetadataExported = frc42_dispatch::method_hash!("GetDealsForMetadata"),
+    RecomputeProviderCollateralExported =
+        frc42_dispatch::method_hash!("RecomputeProviderCollateral"),
 }
 
 /// Market Actor
@@ -191,59 +208,102 @@ impl Actor {
         Ok(GetBalanceReturn { balance, locked })
     }
 
+    state_getters! {
+        State;
+        /// Returns the funds locked in escrow across all accounts, by purpose.
+        fn locked_funds() -> LockedFundsReturn {
+            client_collateral: total_client_locked_collateral,
+            provider_collateral: total_provider_locked_collateral,
+            client_storage_fees: total_client_storage_fee,
+        }
+    }
+
+    /// Declares the policy a provider places on deals published for it.
+    /// May only be invoked by a controlling address of the provider.
+    fn set_deal_policy(rt: &impl Runtime, params: SetDealPolicyParams) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.policy.publish_fee.is_negative() {
+            return Err(actor_error!(
+                illegal_argument,
+                "negative publish fee {}",
+                params.policy.publish_fee
+            ));
+        }
+        let provider_id = resolve_provider_for_caller(rt, &params.provider)?;
+        rt.transaction(|st: &mut State, rt| {
+            st.set_deal_policy(rt.store(), provider_id, params.policy)
+        })
+    }
+
+    /// Returns the policy a provider places on deals published for it.
+    fn get_deal_policy(
+        rt: &impl Runtime,
+        params: GetDealPolicyParams,
+    ) -> Result<GetDealPolicyReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let provider_id = rt.resolve_address(&params.provider).ok_or_else(|| {
+            actor_error!(not_found, "failed to resolve provider address {}", params.provider)
+        })?;
+        let st: State = rt.state()?;
+        st.get_deal_policy(rt.store(), provider_id)
+    }
+
     /// Publish a new set of storage deals (not yet included in a sector).
     fn publish_storage_deals(
         rt: &impl Runtime,
         params: PublishStorageDealsParams,
     ) -> Result<PublishStorageDealsReturn, ActorError> {
         rt.validate_immediate_caller_accept_any()?;
-        if params.deals.is_empty() {
-            return Err(actor_error!(illegal_argument, "Empty deals parameter"));
-        }
-
-        // All deals should have the same provider so get worker once
-        let provider_raw = params.deals[0].proposal.provider;
-        let provider_id = rt.resolve_address(&provider_raw).ok_or_else(|| {
-            actor_error!(not_found, "failed to resolve provider address {}", provider_raw)
-        })?;
+        let deals = params
+            .deals
+            .into_iter()
+            .map(ProposalToPublish::from_v1)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::publish_deals(rt, deals)
+    }
 
-        let code_id = rt
-            .get_actor_code_cid(&provider_id)
-            .ok_or_else(|| actor_error!(not_found, "no code ID for address {}", provider_id))?;
+    /// Publish a new set of storage deals proposed in the DealProposal2 format
+    /// (not yet included in a sector).
+    fn publish_storage_deals2(
+        rt: &impl Runtime,
+        params: PublishStorageDeals2Params,
+    ) -> Result<PublishStorageDealsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let deals = params
+            .deals
+            .into_iter()
+            .map(ProposalToPublish::from_v2)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::publish_deals(rt, deals)
+    }
 
-        if rt.resolve_builtin_actor_type(&code_id) != Some(Type::Miner) {
-            return Err(actor_error!(
-                illegal_argument,
-                "deal provider is not a storage miner actor"
-            ));
+    fn publish_deals(
+        rt: &impl Runtime,
+        deals: Vec<ProposalToPublish>,
+    ) -> Result<PublishStorageDealsReturn, ActorError> {
+        if deals.is_empty() {
+            return Err(actor_error!(illegal_argument, "Empty deals parameter"));
         }
 
-        let caller = rt.message().caller();
-        let caller_status: ext::miner::IsControllingAddressReturn =
-            deserialize_block(extract_send_result(rt.send_simple(
-                &Address::new_id(provider_id),
-                ext::miner::IS_CONTROLLING_ADDRESS_EXPORTED,
-                IpldBlock::serialize_cbor(&ext::miner::IsControllingAddressParam {
-                    address: caller,
-                })?,
-                TokenAmount::zero(),
-            ))?)?;
-        if !caller_status.is_controlling {
-            return Err(actor_error!(
-                forbidden,
-                "caller {} is not worker or control address of provider {}",
-                caller,
-                provider_id
+        let st: State = rt.state()?;
+        if st.publishing_paused {
+            return Err(ActorError::unchecked(
+                EX_DEAL_PUBLISHING_PAUSED,
+                "deal publishing is paused".to_string(),
             ));
         }
+
+        // All deals should have the same provider so get worker once
+        let provider_raw = deals[0].proposal.provider;
+        let provider_id = resolve_provider_for_caller(rt, &provider_raw)?;
         // Deals that passed `AuthenticateMessage` and other state-less checks.
-        let mut validity_index: Vec<bool> = Vec::with_capacity(params.deals.len());
+        let mut validity_index: Vec<bool> = Vec::with_capacity(deals.len());
 
         let baseline_power = request_current_baseline_power(rt)?;
         let (network_raw_power, _) = request_current_network_power(rt)?;
 
         // We perform these checks before loading state since the call to `AuthenticateMessage` could recurse
-        for (di, deal) in params.deals.iter().enumerate() {
+        for (di, deal) in deals.iter().enumerate() {
             let valid = if let Err(e) = validate_deal(rt, deal, &network_raw_power, &baseline_power)
             {
                 info!("invalid deal {}: {}", di, e);
@@ -255,14 +315,61 @@ impl Actor {
             validity_index.push(valid);
         }
 
+        let curr_epoch = rt.curr_epoch();
+
+        // Allocation terms explicitly proposed by clients are checked by the verified registry,
+        // so that a deal with unacceptable terms is dropped rather than failing the datacap
+        // transfer for all of its client's deals.
+        let deals_with_terms: Vec<usize> = (0..deals.len())
+            .filter(|di| validity_index[*di] && deals[*di].allocation_terms.is_some())
+            .collect();
+        if !deals_with_terms.is_empty() {
+            let requests = deals_with_terms
+                .iter()
+                .map(|di| {
+                    let deal = &deals[*di];
+                    alloc_request_for_deal(
+                        &deal.proposal,
+                        provider_id,
+                        deal.allocation_terms.as_ref(),
+                        rt.policy(),
+                        curr_epoch,
+                    )
+                })
+                .collect();
+            let results = validate_allocation_requests(rt, requests)?;
+            for (di, code) in deals_with_terms.iter().zip(results.codes()) {
+                if !code.is_success() {
+                    info!("invalid deal {}: allocation terms rejected with {}", di, code);
+                    validity_index[*di] = false;
+                }
+            }
+        }
+
+        // Token payments are made to the provider's owner.
+        let token_payment_recipient = if deals
+            .iter()
+            .enumerate()
+            .any(|(di, deal)| validity_index[di] && deal.token_payment.is_some())
+        {
+            let (owner, _, _) = request_miner_control_addrs(rt, provider_id)?;
+            Some(owner)
+        } else {
+            None
+        };
+
         struct ValidDeal {
             proposal: DealProposal,
             serialized_proposal: RawBytes,
             cid: Cid,
+            metadata: Option<Cid>,
+            index: usize,
+            client_id: ActorID,
+            token_payment: Option<TokenPayment>,
         }
 
         // Deals that passed validation.
-        let mut valid_deals: Vec<ValidDeal> = Vec::with_capacity(params.deals.len());
+        let mut valid_deals: Vec<ValidDeal> = Vec::with_capacity(deals.len());
         // CIDs of valid proposals.
         let mut proposal_cid_lookup = BTreeSet::new();
         let mut total_client_lockup: BTreeMap<ActorID, TokenAmount> = BTreeMap::new();
@@ -274,11 +381,11 @@ impl Actor {
         let mut total_provider_lockup = TokenAmount::zero();
 
         let mut valid_input_bf = BitField::default();
-        let curr_epoch = rt.curr_epoch();
 
         let state: State = rt.state()?;
+        let publish_fee = state.get_deal_policy(rt.store(), provider_id)?.publish_fee;
 
-        for (di, mut deal) in params.deals.into_iter().enumerate() {
+        for (di, mut deal) in deals.into_iter().enumerate() {
             if !*validity_index.get(di).context_code(
                 ExitCode::USR_ASSERTION_FAILED,
                 "validity index has incorrect length",
@@ -306,16 +413,26 @@ impl Actor {
                 }
             };
 
+            // drop deals whose client hasn't agreed to pay the provider's publish fee
+            if !deal.max_publish_fee.as_ref().is_ok_and(|max| *max >= publish_fee) {
+                info!("invalid deal: {}: publish fee exceeds the client's maximum", di);
+                continue;
+            }
+
             // drop deals with insufficient lock up to cover costs
             let mut client_lockup =
                 total_client_lockup.get(&client_id).cloned().unwrap_or_default();
             client_lockup += deal.proposal.client_balance_requirement();
+            client_lockup += &publish_fee;
 
             let client_balance_ok =
                 state.balance_covered(rt.store(), Address::new_id(client_id), &client_lockup)?;
 
             if !client_balance_ok {
-                info!("invalid deal: {}: insufficient client funds to cover proposal cost", di);
+                info!(
+                    "invalid deal: {}: insufficient client funds to cover proposal cost and publish fee",
+                    di
+                );
                 continue;
             }
 
@@ -357,6 +474,7 @@ impl Actor {
             // Fetch each client's datacap balance and calculate the amount of datacap required for
             // each client's verified deals.
             // Drop any verified deals for which the client has insufficient datacap.
+            let mut datacap = None;
             if deal.proposal.verified_deal {
                 let remaining_datacap = match client_datacap_remaining.get(&client_id).cloned() {
                     None => balance_of(rt, &Address::new_id(client_id))
@@ -371,21 +489,62 @@ impl Actor {
                     client_datacap_remaining.insert(client_id, remaining_datacap);
                     continue; // Drop the deal
                 }
+                datacap = Some((remaining_datacap, piece_datacap_required));
+            }
+
+            if let Some((remaining_datacap, piece_datacap_required)) = datacap {
                 client_datacap_remaining
                     .insert(client_id, remaining_datacap - piece_datacap_required);
-                client_alloc_reqs
-                    .entry(client_id)
-                    .or_default()
-                    .push((pcid, alloc_request_for_deal(&deal.proposal, rt.policy(), curr_epoch)));
+                client_alloc_reqs.entry(client_id).or_default().push((
+                    pcid,
+                    alloc_request_for_deal(
+                        &deal.proposal,
+                        provider_id,
+                        deal.allocation_terms.as_ref(),
+                        rt.policy(),
+                        curr_epoch,
+                    ),
+                ));
             }
 
             total_provider_lockup = provider_lockup;
             total_client_lockup.insert(client_id, client_lockup);
             proposal_cid_lookup.insert(pcid);
-            valid_deals.push(ValidDeal { proposal: deal.proposal, serialized_proposal, cid: pcid });
+            valid_deals.push(ValidDeal {
+                proposal: deal.proposal,
+                serialized_proposal,
+                cid: pcid,
+                metadata: deal.metadata,
+                index: di,
+                client_id,
+                token_payment: deal.token_payment,
+            });
             valid_input_bf.set(di as u64)
         }
 
+        // Take token payments last, once the deals are otherwise known to be valid and the state
+        // read above is no longer relied on, since a token may call back into this actor.
+        // Drop any deal for which the payment cannot be made.
+        if let Some(recipient) = &token_payment_recipient {
+            valid_deals.retain(|deal| {
+                let Some(payment) = &deal.token_payment else {
+                    return true;
+                };
+                match transfer_token_payment(rt, deal.client_id, recipient, payment) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        info!("invalid deal {}: failed to take token payment: {}", deal.index, e);
+                        valid_input_bf.unset(deal.index as u64);
+                        if let Some(reqs) = client_alloc_reqs.get_mut(&deal.client_id) {
+                            reqs.retain(|(cid, _)| *cid != deal.cid);
+                        }
+                        false
+                    }
+                }
+            });
+            client_alloc_reqs.retain(|_, reqs| !reqs.is_empty());
+        }
+
         // Make datacap allocation requests by transferring datacap tokens, once per client.
         // Record the allocation ID for each deal proposal CID.
         let mut deal_allocation_ids: BTreeMap<Cid, AllocationID> = BTreeMap::new();
@@ -429,11 +588,20 @@ impl Actor {
             let mut deal_proposals: Vec<(DealID, DealProposal)> = vec![];
             let mut deals_by_epoch: Vec<(ChainEpoch, DealID)> = vec![];
             let mut pending_deal_allocation_ids: Vec<(DealID, AllocationID)> = vec![];
+            let mut deal_metadata: Vec<(DealID, Cid)> = vec![];
 
             // All storage dealProposals will be added in an atomic transaction; this operation will be unrolled if any of them fails.
             // This should only fail on programmer error because all expected invalid conditions should be filtered in the first set of checks.
             for valid_deal in valid_deals.iter() {
                 st.lock_client_and_provider_balances(rt.store(), &valid_deal.proposal)?;
+                if publish_fee.is_positive() {
+                    st.pay_publish_fee(
+                        rt.store(),
+                        &valid_deal.proposal.client,
+                        &valid_deal.proposal.provider,
+                        &publish_fee,
+                    )?;
+                }
 
                 // Store the proposal CID in pending deals set.
                 pending_deals.push(valid_deal.cid);
@@ -447,6 +615,9 @@ impl Actor {
                 if let Some(alloc_id) = deal_allocation_ids.get(&valid_deal.cid) {
                     pending_deal_allocation_ids.push((deal_id, *alloc_id));
                 }
+                if let Some(metadata_cid) = valid_deal.metadata {
+                    deal_metadata.push((deal_id, metadata_cid));
+                }
 
                 // Randomize the first epoch for when the deal will be processed so an attacker isn't able to
                 // schedule too many deals for the same tick.
@@ -465,6 +636,7 @@ impl Actor {
             st.put_pending_deals(rt.store(), &pending_deals)?;
             st.put_deal_proposals(rt.store(), &deal_proposals)?;
             st.put_pending_deal_allocation_ids(rt.store(), &pending_deal_allocation_ids)?;
+            st.put_deal_metadata(rt.store(), &deal_metadata)?;
             st.put_deals_by_epoch(rt.store(), &deals_by_epoch)?;
             Ok(())
         })?;
@@ -787,6 +959,8 @@ impl Actor {
     /// Terminate a set of deals in response to their containing sector being terminated.
     /// Slash provider collateral, refund client collateral, and refund partial unpaid escrow
     /// amount to client.
+    /// Deals that have already expired are settled and removed instead, since their sector
+    /// mapping is removed here and nothing would otherwise prompt their final settlement.
     fn on_miner_sectors_terminate(
         rt: &impl Runtime,
         params: OnMinerSectorsTerminateParams,
@@ -800,9 +974,9 @@ impl Actor {
             let states = st.load_deal_states(rt.store())?;
 
             // The sector deals mapping is removed all at once.
-            // Note there may be some deal states that are not removed here,
-            // despite deletion of this mapping, e.g. for expired but not-yet-settled deals.
-            // The sector->deal mapping is no longer needed (the deal state has sector number too).
+            // Every deal state referenced by this mapping is also removed below, either by
+            // termination or by the final settlement of an expired deal, except for a deal
+            // which cannot yet be settled to its end, which is indexed again.
             let all_deal_ids = st.pop_sector_deal_ids(
                 rt.store(),
                 miner_addr.id().unwrap(),
@@ -810,6 +984,7 @@ impl Actor {
             )?;
 
             let mut total_slashed = TokenAmount::zero();
+            let mut unsettled_deals = Vec::new();
             for id in all_deal_ids {
                 let deal = proposals
                     .get(id)
@@ -835,12 +1010,6 @@ impl Actor {
                     ));
                 }
 
-                // do not slash expired deals
-                if deal.end_epoch <= params.epoch {
-                    info!("deal {} expired, not slashing", id);
-                    continue;
-                }
-
                 let mut state: DealState = states
                     .get(id)
                     .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
@@ -849,6 +1018,33 @@ impl Actor {
                     .cloned()
                     .ok_or_else(|| actor_error!(illegal_argument, "no state for deal {}", id))?;
 
+                // do not slash expired deals, but settle their final payment and remove them
+                if deal.end_epoch <= params.epoch {
+                    info!("deal {} expired, settling instead of slashing", id);
+                    let dcid = deal_cid(rt, &deal)?;
+                    let (slash_amount, _, payment_epochs, completed, remove) =
+                        st.process_deal_update(rt.store(), &state, &deal, &dcid, rt.curr_epoch())?;
+                    total_slashed += slash_amount;
+                    emit_deal_settled(rt, id, &deal, payment_epochs)?;
+                    if remove {
+                        st.remove_completed_deal(rt.store(), id)?;
+                    } else {
+                        // The deal was settled only up to the current epoch.
+                        state.last_updated_epoch = rt.curr_epoch();
+                        st.put_deal_states(rt.store(), &[(id, state)])?;
+                        unsettled_deals.push((state.sector_number, vec![id]));
+                    }
+                    if completed {
+                        emit::deal_completed(
+                            rt,
+                            id,
+                            deal.client.id().unwrap(),
+                            deal.provider.id().unwrap(),
+                        )?;
+                    }
+                    continue;
+                }
+
                 // If a deal is already slashed, there should be no existing state for it
                 // but we process it here for deletion anyway
                 if state.slash_epoch != EPOCH_UNDEFINED {
@@ -872,6 +1068,9 @@ impl Actor {
                     deal.provider.id().unwrap(),
                 )?;
             }
+            if !unsettled_deals.is_empty() {
+                st.put_sector_deal_ids(rt.store(), miner_addr.id().unwrap(), &unsettled_deals)?;
+            }
 
             Ok(total_slashed)
         })?;
@@ -887,6 +1086,98 @@ impl Actor {
         Ok(())
     }
 
+    /// Suspends or resumes the publishing of new deals.
+    /// Only the system actor may change the flag. No message can be sent from it, so the flag
+    /// changes only when a network upgrade invokes this method, not by any on-chain vote.
+    /// Deals already published continue to be activated, settled and terminated while paused.
+    fn set_publishing_paused(
+        rt: &impl Runtime,
+        params: SetPublishingPausedParams,
+    ) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
+        rt.transaction(|st: &mut State, _| {
+            st.publishing_paused = params.paused;
+            Ok(())
+        })
+    }
+
+    /// Recomputes the minimum provider collateral for a batch of a provider's deals under the
+    /// current network power and circulating supply, by the same formula that bounds the
+    /// collateral of newly published deals.
+    /// Returns the deals whose locked collateral is now below their minimum, and the total
+    /// collateral the provider would need to add to cover them all.
+    /// Deals which are not found, are not the provider's, or have ended, been terminated,
+    /// or missed activation fail.
+    /// This is a query: any caller may request it, and it neither changes state nor emits events.
+    fn recompute_provider_collateral(
+        rt: &impl Runtime,
+        params: RecomputeProviderCollateralParams,
+    ) -> Result<RecomputeProviderCollateralReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.deal_ids.len() > MAX_COLLATERAL_RECOMPUTATION_DEALS {
+            return Err(actor_error!(
+                illegal_argument,
+                "too many deals {}, max {}",
+                params.deal_ids.len(),
+                MAX_COLLATERAL_RECOMPUTATION_DEALS
+            ));
+        }
+
+        let baseline_power = request_current_baseline_power(rt)?;
+        let (network_raw_power, _) = request_current_network_power(rt)?;
+        let circulating_supply = rt.total_fil_circ_supply();
+
+        let st: State = rt.state()?;
+        let proposals = st.load_proposals(rt.store())?;
+        let deal_states = st.load_deal_states(rt.store())?;
+        let provider = Address::new_id(params.provider);
+
+        let mut batch = BatchReturnGen::new(params.deal_ids.len());
+        let mut under_collateralized = Vec::new();
+        let mut top_up_required = TokenAmount::zero();
+        for deal_id in params.deal_ids {
+            let proposal = match find_proposal(&proposals, deal_id)? {
+                Some(proposal) => proposal,
+                None => {
+                    batch.add_fail(ExitCode::USR_NOT_FOUND);
+                    continue;
+                }
+            };
+            if proposal.provider != provider {
+                batch.add_fail(ExitCode::USR_ILLEGAL_ARGUMENT);
+                continue;
+            }
+            // A deal not activated by its start epoch will be timed out,
+            // forfeiting the collateral already locked.
+            let state = find_deal_state(&deal_states, deal_id)?;
+            let terminated = state.is_some_and(|state| state.slash_epoch != EPOCH_UNDEFINED);
+            let timed_out = state.is_none() && rt.curr_epoch() > proposal.start_epoch;
+            if terminated || timed_out || proposal.end_epoch <= rt.curr_epoch() {
+                batch.add_fail(EX_DEAL_EXPIRED);
+                continue;
+            }
+
+            let (required, _) = deal_provider_collateral_bounds(
+                rt.policy(),
+                proposal.piece_size,
+                &network_raw_power,
+                &baseline_power,
+                &circulating_supply,
+            );
+            if proposal.provider_collateral < required {
+                top_up_required += &required - &proposal.provider_collateral;
+                under_collateralized.push(deal_id);
+            }
+            batch.add_success();
+        }
+
+        Ok(RecomputeProviderCollateralReturn {
+            results: batch.generate(),
+            under_collateralized,
+            top_up_required,
+        })
+    }
+
     fn cron_tick(rt: &impl Runtime) -> Result<(), ActorError> {
         rt.validate_immediate_caller_is(std::iter::once(&CRON_ACTOR_ADDR))?;
 
@@ -952,14 +1243,15 @@ impl Actor {
                     // https://github.com/filecoin-project/builtin-actors/issues/1389
                     // handling of legacy deals is still done in cron. we handle such deals here and continue to
                     // reschedule them. eventually, all legacy deals will expire and the below code can be removed.
-                    let (slash_amount, _payment_amount, completed, remove_deal) = st
-                        .process_deal_update(
+                    let (slash_amount, _payment_amount, payment_epochs, completed, remove_deal) =
+                        st.process_deal_update(
                             rt.store(),
                             &state,
                             &deal_proposal,
                             &dcid,
                             curr_epoch,
                         )?;
+                    emit_deal_settled(rt, deal_id, &deal_proposal, payment_epochs)?;
 
                     if remove_deal {
                         // TODO: remove handling for terminated-deal slashing when marked-for-termination deals are all processed
@@ -1217,6 +1509,71 @@ impl Actor {
         }
     }
 
+    /// Lists the IDs of deals for which the given address is the client, in no particular order.
+    /// Only deals with a proposal still in state are listed.
+    /// Results are paginated; a call returns at most LIST_CLIENT_DEALS_PAGE_SIZE deal IDs.
+    fn list_client_deals(
+        rt: &impl Runtime,
+        params: ListClientDealsParams,
+    ) -> Result<ListClientDealsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let client = rt.resolve_address(&params.client).ok_or_else(|| {
+            actor_error!(illegal_argument, "failed to resolve address {}", params.client)
+        })?;
+
+        // The cursor's position is the first deal ID to list.
+        let start: Option<DealID> = params.cursor.decode()?;
+        let st: State = rt.state()?;
+        let (deals, next) =
+            st.list_client_deals(rt.store(), client, start, LIST_CLIENT_DEALS_PAGE_SIZE)?;
+        let next_cursor = next.map(|id| Cursor::encode(&id)).transpose()?;
+        Ok(ListClientDealsReturn { deals, next_cursor })
+    }
+
+    /// Lists the IDs of deals for the given piece CID, in no particular order.
+    /// Only deals with a proposal still in state are listed.
+    /// Results are paginated; a call returns at most GET_DEALS_FOR_PIECE_PAGE_SIZE deal IDs.
+    fn get_deals_for_piece(
+        rt: &impl Runtime,
+        params: GetDealsForPieceParams,
+    ) -> Result<GetDealsForPieceReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        // The cursor's position is the first deal ID to list.
+        let start: Option<DealID> = params.cursor.decode()?;
+        let st: State = rt.state()?;
+        let (deals, next) = st.list_piece_deals(
+            rt.store(),
+            &params.piece_cid,
+            start,
+            GET_DEALS_FOR_PIECE_PAGE_SIZE,
+        )?;
+        let next_cursor = next.map(|id| Cursor::encode(&id)).transpose()?;
+        Ok(GetDealsForPieceReturn { deals, next_cursor })
+    }
+
+    /// Lists the IDs of deals that declared the given metadata CID, in no particular order.
+    /// Only deals with a proposal still in state are listed.
+    /// Results are paginated; a call returns at most GET_DEALS_FOR_METADATA_PAGE_SIZE deal IDs.
+    fn get_deals_for_metadata(
+        rt: &impl Runtime,
+        params: GetDealsForMetadataParams,
+    ) -> Result<GetDealsForMetadataReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        // The cursor's position is the first deal ID to list.
+        let start: Option<DealID> = params.cursor.decode()?;
+        let st: State = rt.state()?;
+        let (deals, next) = st.list_metadata_deals(
+            rt.store(),
+            &params.metadata_cid,
+            start,
+            GET_DEALS_FOR_METADATA_PAGE_SIZE,
+        )?;
+        let next_cursor = next.map(|id| Cursor::encode(&id)).transpose()?;
+        Ok(GetDealsForMetadataReturn { deals, next_cursor })
+    }
+
     fn settle_deal_payments(
         rt: &impl Runtime,
         params: SettleDealPaymentsParams,
@@ -1292,19 +1649,16 @@ impl Actor {
                     ));
                 }
 
-                let (_, payment_amount, completed, remove_deal) = match st.process_deal_update(
-                    rt.store(),
-                    &deal_state,
-                    &deal_proposal,
-                    &dcid,
-                    curr_epoch,
-                ) {
+                let (_, payment_amount, payment_epochs, completed, remove_deal) = match st
+                    .process_deal_update(rt.store(), &deal_state, &deal_proposal, &dcid, curr_epoch)
+                {
                     Ok(res) => res,
                     Err(e) => {
                         batch_gen.add_fail(e.exit_code());
                         continue;
                     }
                 };
+                emit_deal_settled(rt, deal_id, &deal_proposal, payment_epochs)?;
 
                 if remove_deal {
                     st.remove_completed_deal(rt.store(), deal_id)?;
@@ -1362,6 +1716,28 @@ impl Actor {
     }
 }
 
+// Emits an event for the payment made to a deal's provider over a range of epochs, if any.
+fn emit_deal_settled(
+    rt: &impl Runtime,
+    deal_id: DealID,
+    proposal: &DealProposal,
+    payment_epochs: Range<ChainEpoch>,
+) -> Result<(), ActorError> {
+    if payment_epochs.is_empty() {
+        return Ok(());
+    }
+    let amount = &proposal.storage_price_per_epoch * (payment_epochs.end - payment_epochs.start);
+    emit::deal_settled(
+        rt,
+        deal_id,
+        proposal.client.id().unwrap(),
+        proposal.provider.id().unwrap(),
+        &amount,
+        payment_epochs.start,
+        payment_epochs.end,
+    )
+}
+
 fn get_proposals<BS: Blockstore>(
     proposal_array: &DealArray<BS>,
     deal_ids: &[DealID],
@@ -1482,12 +1858,25 @@ fn preactivate_deal<BS: Blockstore>(
     Ok(Ok(proposal))
 }
 
+// Allocation terms explicitly proposed by the client take precedence over terms derived
+// from the deal.
 fn alloc_request_for_deal(
-    // Deal proposal must have ID addresses
     deal: &DealProposal,
+    provider: ActorID,
+    terms: Option<&DealAllocationTerms>,
     policy: &Policy,
     curr_epoch: ChainEpoch,
 ) -> ext::verifreg::AllocationRequest {
+    if let Some(terms) = terms {
+        return ext::verifreg::AllocationRequest {
+            provider,
+            data: deal.piece_cid,
+            size: deal.piece_size,
+            term_min: terms.term_min,
+            term_max: terms.term_max,
+            expiration: terms.expiration,
+        };
+    }
     let alloc_term_min = deal.end_epoch - deal.start_epoch;
     let alloc_term_max = min(
         alloc_term_min + policy.market_default_allocation_term_buffer,
@@ -1496,7 +1885,7 @@ fn alloc_request_for_deal(
     let alloc_expiration =
         min(deal.start_epoch, curr_epoch + policy.maximum_verified_allocation_expiration);
     ext::verifreg::AllocationRequest {
-        provider: deal.provider.id().unwrap(),
+        provider,
         data: deal.piece_cid,
         size: deal.piece_size,
         term_min: alloc_term_min,
@@ -1542,6 +1931,46 @@ fn transfer_from(
     Ok(allocs.new_allocations)
 }
 
+// Requests the verified registry's validation of allocation requests, returning the result for each.
+fn validate_allocation_requests(
+    rt: &impl Runtime,
+    requests: Vec<AllocationRequest>,
+) -> Result<BatchReturn, ActorError> {
+    let ret = extract_send_result(rt.send(
+        &VERIFIED_REGISTRY_ACTOR_ADDR,
+        ext::verifreg::VALIDATE_ALLOCATION_REQUESTS_METHOD,
+        IpldBlock::serialize_cbor(&ext::verifreg::ValidateAllocationRequestsParams { requests })?,
+        TokenAmount::zero(),
+        None,
+        SendFlags::READ_ONLY,
+    ))
+    .context("failed to validate allocation requests")?;
+    deserialize_block(ret)
+}
+
+// Transfers a deal's token payment from the client's allowance for the market to the recipient.
+fn transfer_token_payment(
+    rt: &impl Runtime,
+    client: ActorID,
+    recipient: &Address,
+    payment: &TokenPayment,
+) -> Result<(), ActorError> {
+    let params = TransferFromParams {
+        from: Address::new_id(client),
+        to: *recipient,
+        amount: payment.amount.clone(),
+        operator_data: RawBytes::default(),
+    };
+    extract_send_result(rt.send_simple(
+        &payment.token,
+        ext::token::TRANSFER_FROM_METHOD,
+        IpldBlock::serialize_cbor(&params)?,
+        TokenAmount::zero(),
+    ))
+    .with_context(|| format!("failed to transfer {} of token {}", payment.amount, payment.token))?;
+    Ok(())
+}
+
 // Invokes BalanceOf on the data cap token actor.
 fn balance_of(rt: &impl Runtime, owner: &Address) -> Result<TokenAmount, ActorError> {
     let params = IpldBlock::serialize_cbor(owner)?;
@@ -1613,9 +2042,60 @@ fn validate_deal_can_activate(
     Ok(())
 }
 
+// A deal proposal to be published, normalised from any of the supported proposal formats.
+struct ProposalToPublish {
+    // The proposal in the format recorded in state.
+    proposal: DealProposal,
+    // The serialized proposal, in the format signed by the client.
+    signed_proposal: RawBytes,
+    client_signature: Signature,
+    // Allocation terms explicitly proposed by the client, if any.
+    allocation_terms: Option<DealAllocationTerms>,
+    // Payment in a token other than FIL, if any.
+    token_payment: Option<TokenPayment>,
+    // CID of the deal's extended metadata, if any.
+    metadata: Option<Cid>,
+    // Total size of the proposal's extension fields.
+    extensions_size: usize,
+    // The maximum publish fee the client agrees to pay, or the error decoding it.
+    max_publish_fee: Result<TokenAmount, ActorError>,
+}
+
+impl ProposalToPublish {
+    fn from_v1(deal: ClientDealProposal) -> Result<Self, ActorError> {
+        let signed_proposal = serialize(&deal.proposal, "deal proposal")?;
+        Ok(Self {
+            proposal: deal.proposal,
+            signed_proposal,
+            client_signature: deal.client_signature,
+            allocation_terms: None,
+            token_payment: None,
+            metadata: None,
+            extensions_size: 0,
+            max_publish_fee: Ok(TokenAmount::zero()),
+        })
+    }
+
+    fn from_v2(deal: ClientDealProposal2) -> Result<Self, ActorError> {
+        let signed_proposal = serialize(&deal.proposal, "deal proposal")?;
+        Ok(Self {
+            proposal: deal.proposal.to_proposal(),
+            signed_proposal,
+            client_signature: deal.client_signature,
+            extensions_size: deal.proposal.extensions_size(),
+            max_publish_fee: deal.proposal.max_publish_fee().map_err(
+                |e| actor_error!(illegal_argument; "failed to decode max publish fee: {}", e),
+            ),
+            allocation_terms: deal.proposal.allocation,
+            token_payment: deal.proposal.payment.token,
+            metadata: deal.proposal.metadata,
+        })
+    }
+}
+
 fn validate_deal(
     rt: &impl Runtime,
-    deal: &ClientDealProposal,
+    deal: &ProposalToPublish,
     network_raw_power: &StoragePower,
     baseline_power: &StoragePower,
 ) -> Result<(), ActorError> {
@@ -1632,6 +2112,40 @@ fn validate_deal(
         ));
     }
 
+    if let Some(cid) = &deal.metadata {
+        if cid.encoded_len() > detail::DEAL_MAX_METADATA_CID_SIZE {
+            return Err(actor_error!(
+                illegal_argument,
+                "deal metadata CID can be at most {} bytes, is {}",
+                detail::DEAL_MAX_METADATA_CID_SIZE,
+                cid.encoded_len()
+            ));
+        }
+    }
+
+    if let Err(e) = &deal.max_publish_fee {
+        return Err(e.clone());
+    }
+
+    if deal.extensions_size > detail::DEAL_MAX_EXTENSIONS_SIZE {
+        return Err(actor_error!(
+            illegal_argument,
+            "deal extensions can be at most {} bytes, are {}",
+            detail::DEAL_MAX_EXTENSIONS_SIZE,
+            deal.extensions_size
+        ));
+    }
+
+    if let Some(payment) = &deal.token_payment {
+        if !payment.amount.is_positive() {
+            return Err(actor_error!(
+                illegal_argument,
+                "token payment must be positive, was {}",
+                payment.amount
+            ));
+        }
+    }
+
     proposal
         .piece_size
         .validate()
@@ -1689,18 +2203,14 @@ fn validate_deal(
 
 fn deal_proposal_is_internally_valid(
     rt: &impl Runtime,
-    proposal: &ClientDealProposal,
+    deal: &ProposalToPublish,
 ) -> Result<(), ActorError> {
-    let signature_bytes = proposal.client_signature.bytes.clone();
-    // Generate unsigned bytes
-    let proposal_bytes = serialize(&proposal.proposal, "deal proposal")?;
-
     if !extract_send_result(rt.send(
-        &proposal.proposal.client,
+        &deal.proposal.client,
         ext::account::AUTHENTICATE_MESSAGE_METHOD,
         IpldBlock::serialize_cbor(&ext::account::AuthenticateMessageParams {
-            signature: signature_bytes,
-            message: proposal_bytes.to_vec(),
+            signature: deal.client_signature.bytes.clone(),
+            message: deal.signed_proposal.to_vec(),
         })?,
         TokenAmount::zero(),
         None,
@@ -1745,6 +2255,43 @@ fn request_miner_control_addrs(
     Ok((addrs.owner, addrs.worker, addrs.control_addresses))
 }
 
+/// Resolves the address of a storage provider, checking that the caller is one of its
+/// controlling addresses.
+fn resolve_provider_for_caller(
+    rt: &impl Runtime,
+    provider: &Address,
+) -> Result<ActorID, ActorError> {
+    let provider_id = rt.resolve_address(provider).ok_or_else(|| {
+        actor_error!(not_found, "failed to resolve provider address {}", provider)
+    })?;
+
+    let code_id = rt
+        .get_actor_code_cid(&provider_id)
+        .ok_or_else(|| actor_error!(not_found, "no code ID for address {}", provider_id))?;
+
+    if rt.resolve_builtin_actor_type(&code_id) != Some(Type::Miner) {
+        return Err(actor_error!(illegal_argument, "deal provider is not a storage miner actor"));
+    }
+
+    let caller = rt.message().caller();
+    let caller_status: ext::miner::IsControllingAddressReturn =
+        deserialize_block(extract_send_result(rt.send_simple(
+            &Address::new_id(provider_id),
+            ext::miner::IS_CONTROLLING_ADDRESS_EXPORTED,
+            IpldBlock::serialize_cbor(&ext::miner::IsControllingAddressParam { address: caller })?,
+            TokenAmount::zero(),
+        ))?)?;
+    if !caller_status.is_controlling {
+        return Err(actor_error!(
+            forbidden,
+            "caller {} is not worker or control address of provider {}",
+            caller,
+            provider_id
+        ));
+    }
+    Ok(provider_id)
+}
+
 /// Resolves a provider or client address to the canonical form against which a balance should be held, and
 /// the designated recipient address of withdrawals (which is the same, for simple account parties).
 fn escrow_address(
@@ -1819,10 +2366,12 @@ impl ActorCode for Actor {
         AddBalance|AddBalanceExported => add_balance,
         WithdrawBalance|WithdrawBalanceExported => withdraw_balance,
         PublishStorageDeals|PublishStorageDealsExported => publish_storage_deals,
+        PublishStorageDeals2Exported => publish_storage_deals2,
         VerifyDealsForActivation => verify_deals_for_activation,
         BatchActivateDeals => batch_activate_deals,
         OnMinerSectorsTerminate => on_miner_sectors_terminate,
         CronTick => cron_tick,
+        SetPublishingPaused => set_publishing_paused,
         GetBalanceExported => get_balance,
         GetDealDataCommitmentExported => get_deal_data_commitment,
         GetDealClientExported => get_deal_client,
@@ -1837,5 +2386,12 @@ impl ActorCode for Actor {
         GetDealSectorExported => get_deal_sector,
         SettleDealPaymentsExported => settle_deal_payments,
         SectorContentChangedExported => sector_content_changed,
+        ListClientDealsExported => list_client_deals,
+        GetDealsForPieceExported => get_deals_for_piece,
+        GetDealsForMetadataExported => get_deals_for_metadata,
+        RecomputeProviderCollateralExported => recompute_provider_collateral,
+        LockedFundsExported => locked_funds,
+        SetDealPolicyExported => set_deal_policy,
+        GetDealPolicyExported => get_deal_policy,
     }
 }
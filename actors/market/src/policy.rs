@@ -16,6 +16,13 @@ use num_traits::Zero;
 pub mod detail {
     /// Maximum length of a deal label.
     pub const DEAL_MAX_LABEL_SIZE: usize = 256;
+
+    /// Maximum encoded length of the metadata CID declared by a deal proposal.
+    /// This comfortably fits a CID with a 512-bit digest.
+    pub const DEAL_MAX_METADATA_CID_SIZE: usize = 100;
+
+    /// Maximum total size of the names and values of a deal proposal's extension fields.
+    pub const DEAL_MAX_EXTENSIONS_SIZE: usize = 256;
 }
 
 lazy_static! {
@@ -24,6 +31,18 @@ lazy_static! {
     pub static ref TOTAL_FILECOIN: TokenAmount = TokenAmount::from_whole(2_000_000_000);
 }
 
+/// Maximum number of deal IDs returned by a single call to list a client's deals.
+pub const LIST_CLIENT_DEALS_PAGE_SIZE: usize = 1000;
+
+/// Maximum number of deal IDs returned by a single call to list the deals for a piece.
+pub const GET_DEALS_FOR_PIECE_PAGE_SIZE: usize = 1000;
+
+/// Maximum number of deal IDs returned by a single call to list the deals for a metadata CID.
+pub const GET_DEALS_FOR_METADATA_PAGE_SIZE: usize = 1000;
+
+/// Maximum number of deals for which provider collateral is recomputed in a single call.
+pub const MAX_COLLATERAL_RECOMPUTATION_DEALS: usize = 1000;
+
 /// Bounds (inclusive) on deal duration.
 pub(super) fn deal_duration_bounds(_size: PaddedPieceSize) -> (ChainEpoch, ChainEpoch) {
     (180 * EPOCHS_IN_DAY, 1278 * EPOCHS_IN_DAY)
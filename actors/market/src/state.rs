@@ -3,6 +3,7 @@
 
 use std::cmp::{max, min};
 use std::collections::BTreeMap;
+use std::ops::Range;
 
 use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
@@ -17,7 +18,7 @@ use fvm_shared::{ActorID, HAMT_BIT_WIDTH};
 use num_traits::Zero;
 
 use fil_actors_runtime::{
-    ActorContext, ActorError, Array, AsActorError, Config, DEFAULT_HAMT_CONFIG, Map2, Set,
+    ActorContext, ActorError, Array, AsActorError, Config, DEFAULT_HAMT_CONFIG, Map2, MapKey, Set,
     SetMultimap, SetMultimapConfig, actor_error,
 };
 
@@ -86,6 +87,34 @@ pub struct State {
     /// of multiple sectors all belonging to the same provider.
     /// HAMT[ActorID]HAMT[SectorNumber][]DealID
     pub provider_sectors: Cid,
+
+    /// Maps clients to the IDs of their deals that have a proposal in state.
+    /// Entries are added when a deal is published and removed with the proposal,
+    /// when a deal is terminated, expires, or times out before activation.
+    /// SetMultimap<ActorID, DealID>
+    pub client_deals: Cid,
+
+    /// Whether publishing of new deals is suspended, as set only at a network upgrade.
+    /// Settlement, activation and termination of existing deals are unaffected.
+    pub publishing_paused: bool,
+
+    /// Deal policies declared by providers. Providers without an entry have the default policy.
+    /// HAMT[ActorID]DealPolicy
+    pub deal_policies: Cid,
+
+    /// Maps piece CIDs to the IDs of deals for that piece that have a proposal in state.
+    /// Entries are maintained alongside those of client_deals.
+    /// SetMultimap<Cid, DealID>
+    pub piece_deals: Cid,
+
+    /// Maps the metadata CIDs declared by deal proposals to the IDs of deals with a proposal
+    /// in state that declared them. Entries are maintained alongside those of deal_metadata.
+    /// SetMultimap<Cid, DealID>
+    pub metadata_deals: Cid,
+
+    /// The metadata CID declared by each deal with a proposal in state that declared one.
+    /// HAMT[DealID]Cid
+    pub deal_metadata: Cid,
 }
 
 pub type PendingProposalsSet<BS> = Set<BS, Cid>;
@@ -103,6 +132,26 @@ pub type ProviderSectorsMap<BS> = Map2<BS, ActorID, Cid>;
 pub const PROVIDER_SECTORS_CONFIG: Config =
     Config { bit_width: HAMT_BIT_WIDTH, ..DEFAULT_HAMT_CONFIG };
 
+pub type DealPoliciesMap<BS> = Map2<BS, ActorID, DealPolicy>;
+pub const DEAL_POLICIES_CONFIG: Config =
+    Config { bit_width: HAMT_BIT_WIDTH, ..DEFAULT_HAMT_CONFIG };
+
+pub type ClientDealsMap<BS> = SetMultimap<BS, ActorID, DealID>;
+pub const CLIENT_DEALS_CONFIG: SetMultimapConfig =
+    SetMultimapConfig { outer: DEFAULT_HAMT_CONFIG, inner: DEFAULT_HAMT_CONFIG };
+
+pub type PieceDealsMap<BS> = SetMultimap<BS, Cid, DealID>;
+pub const PIECE_DEALS_CONFIG: SetMultimapConfig =
+    SetMultimapConfig { outer: DEFAULT_HAMT_CONFIG, inner: DEFAULT_HAMT_CONFIG };
+
+pub type MetadataDealsMap<BS> = SetMultimap<BS, Cid, DealID>;
+pub const METADATA_DEALS_CONFIG: SetMultimapConfig =
+    SetMultimapConfig { outer: DEFAULT_HAMT_CONFIG, inner: DEFAULT_HAMT_CONFIG };
+
+pub type DealMetadataMap<BS> = Map2<BS, DealID, Cid>;
+pub const DEAL_METADATA_CONFIG: Config =
+    Config { bit_width: HAMT_BIT_WIDTH, ..DEFAULT_HAMT_CONFIG };
+
 pub type SectorDealsMap<BS> = Map2<BS, SectorNumber, Vec<DealID>>;
 pub const SECTOR_DEALS_CONFIG: Config = Config { bit_width: HAMT_BIT_WIDTH, ..DEFAULT_HAMT_CONFIG };
 
@@ -136,6 +185,16 @@ impl State {
 
         let empty_sector_deals_hamt =
             ProviderSectorsMap::empty(store, PROVIDER_SECTORS_CONFIG, "sector deals").flush()?;
+        let empty_client_deals =
+            ClientDealsMap::empty(store, CLIENT_DEALS_CONFIG, "client deals").flush()?;
+        let empty_deal_policies =
+            DealPoliciesMap::empty(store, DEAL_POLICIES_CONFIG, "deal policies").flush()?;
+        let empty_piece_deals =
+            PieceDealsMap::empty(store, PIECE_DEALS_CONFIG, "piece deals").flush()?;
+        let empty_metadata_deals =
+            MetadataDealsMap::empty(store, METADATA_DEALS_CONFIG, "metadata deals").flush()?;
+        let empty_deal_metadata =
+            DealMetadataMap::empty(store, DEAL_METADATA_CONFIG, "deal metadata").flush()?;
 
         Ok(Self {
             proposals: empty_proposals_array,
@@ -152,6 +211,12 @@ impl State {
             total_client_storage_fee: TokenAmount::default(),
             pending_deal_allocation_ids: empty_pending_deal_allocation_map,
             provider_sectors: empty_sector_deals_hamt,
+            client_deals: empty_client_deals,
+            publishing_paused: false,
+            deal_policies: empty_deal_policies,
+            piece_deals: empty_piece_deals,
+            metadata_deals: empty_metadata_deals,
+            deal_metadata: empty_deal_metadata,
         })
     }
 
@@ -276,6 +341,28 @@ impl State {
             .flush()
             .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush deal proposals")?;
 
+        if let Some(proposal) = &proposal {
+            let client = proposal.client.id().context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "deal proposal client is not an ID address",
+            )?;
+            let mut client_deals = self.load_client_deals(store)?;
+            client_deals.remove(&client, deal_id)?;
+            self.client_deals = client_deals.flush()?;
+
+            let mut piece_deals = self.load_piece_deals(store)?;
+            piece_deals.remove(&proposal.piece_cid, deal_id)?;
+            self.piece_deals = piece_deals.flush()?;
+
+            let mut deal_metadata = self.load_deal_metadata(store)?;
+            if let Some(metadata_cid) = deal_metadata.delete(&deal_id)? {
+                self.deal_metadata = deal_metadata.flush()?;
+                let mut metadata_deals = self.load_metadata_deals(store)?;
+                metadata_deals.remove(&metadata_cid, deal_id)?;
+                self.metadata_deals = metadata_deals.flush()?;
+            }
+        }
+
         Ok(proposal)
     }
 
@@ -290,20 +377,166 @@ impl State {
         let mut deal_proposals = DealArray::load(&self.proposals, store)
             .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load deal proposal array")?;
 
+        let mut client_deals = self.load_client_deals(store)?;
+        let mut piece_deals = self.load_piece_deals(store)?;
         new_deal_proposals.iter().try_for_each(|(id, proposal)| -> Result<(), ActorError> {
             deal_proposals
                 .set(*id, proposal.clone())
                 .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to set deal proposal")?;
+            let client = proposal.client.id().context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "deal proposal client is not an ID address",
+            )?;
+            client_deals.put(&client, *id)?;
+            piece_deals.put(&proposal.piece_cid, *id)?;
             Ok(())
         })?;
 
         self.proposals = deal_proposals
             .flush()
             .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush deal proposals")?;
+        self.client_deals = client_deals.flush()?;
+        self.piece_deals = piece_deals.flush()?;
+
+        Ok(())
+    }
+
+    /// Records the metadata CIDs declared by newly published deals.
+    pub fn put_deal_metadata<BS>(
+        &mut self,
+        store: &BS,
+        new_deal_metadata: &[(DealID, Cid)],
+    ) -> Result<(), ActorError>
+    where
+        BS: Blockstore,
+    {
+        let mut deal_metadata = self.load_deal_metadata(store)?;
+        let mut metadata_deals = self.load_metadata_deals(store)?;
+        new_deal_metadata.iter().try_for_each(|(id, metadata_cid)| -> Result<(), ActorError> {
+            deal_metadata.set(id, *metadata_cid)?;
+            metadata_deals.put(metadata_cid, *id)?;
+            Ok(())
+        })?;
+        self.deal_metadata = deal_metadata.flush()?;
+        self.metadata_deals = metadata_deals.flush()?;
+        Ok(())
+    }
+
+    /// Rebuilds the client, piece and metadata deal indexes from the deal proposals and the
+    /// metadata they declared, replacing any existing entries.
+    /// This populates the indexes of state from before they were maintained.
+    pub fn rebuild_deal_indexes<BS>(&mut self, store: &BS) -> Result<(), ActorError>
+    where
+        BS: Blockstore,
+    {
+        let deal_proposals = DealArray::load(&self.proposals, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load deal proposal array")?;
+        let mut proposals = Vec::new();
+        deal_proposals
+            .for_each(|deal_id, proposal| {
+                proposals.push((deal_id, proposal.client, proposal.piece_cid));
+                Ok(())
+            })
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to iterate deal proposals")?;
+
+        let mut client_deals = ClientDealsMap::empty(store, CLIENT_DEALS_CONFIG, "client deals");
+        let mut piece_deals = PieceDealsMap::empty(store, PIECE_DEALS_CONFIG, "piece deals");
+        for (deal_id, client, piece_cid) in proposals {
+            let client = client.id().context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "deal proposal client is not an ID address",
+            )?;
+            client_deals.put(&client, deal_id)?;
+            piece_deals.put(&piece_cid, deal_id)?;
+        }
 
+        let mut metadata_deals =
+            MetadataDealsMap::empty(store, METADATA_DEALS_CONFIG, "metadata deals");
+        self.load_deal_metadata(store)?
+            .for_each(|deal_id, metadata_cid| metadata_deals.put(metadata_cid, deal_id))?;
+
+        self.client_deals = client_deals.flush()?;
+        self.piece_deals = piece_deals.flush()?;
+        self.metadata_deals = metadata_deals.flush()?;
         Ok(())
     }
 
+    pub fn load_client_deals<BS>(&self, store: BS) -> Result<ClientDealsMap<BS>, ActorError>
+    where
+        BS: Blockstore,
+    {
+        ClientDealsMap::load(store, &self.client_deals, CLIENT_DEALS_CONFIG, "client deals")
+    }
+
+    /// Lists a client's deal IDs in the order of the index, starting from the cursor (inclusive),
+    /// or from the beginning if none is given.
+    /// Returns at most `limit` IDs, and the cursor from which to resume listing if more remain.
+    pub fn list_client_deals<BS>(
+        &self,
+        store: &BS,
+        client: ActorID,
+        cursor: Option<DealID>,
+        limit: usize,
+    ) -> Result<(Vec<DealID>, Option<DealID>), ActorError>
+    where
+        BS: Blockstore,
+    {
+        list_deal_ids(&self.load_client_deals(store)?, &client, cursor, limit)
+    }
+
+    pub fn load_piece_deals<BS>(&self, store: BS) -> Result<PieceDealsMap<BS>, ActorError>
+    where
+        BS: Blockstore,
+    {
+        PieceDealsMap::load(store, &self.piece_deals, PIECE_DEALS_CONFIG, "piece deals")
+    }
+
+    /// Lists the IDs of deals for a piece in the order of the index, starting from the cursor
+    /// (inclusive), or from the beginning if none is given.
+    /// Returns at most `limit` IDs, and the cursor from which to resume listing if more remain.
+    pub fn list_piece_deals<BS>(
+        &self,
+        store: &BS,
+        piece_cid: &Cid,
+        cursor: Option<DealID>,
+        limit: usize,
+    ) -> Result<(Vec<DealID>, Option<DealID>), ActorError>
+    where
+        BS: Blockstore,
+    {
+        list_deal_ids(&self.load_piece_deals(store)?, piece_cid, cursor, limit)
+    }
+
+    pub fn load_metadata_deals<BS>(&self, store: BS) -> Result<MetadataDealsMap<BS>, ActorError>
+    where
+        BS: Blockstore,
+    {
+        MetadataDealsMap::load(store, &self.metadata_deals, METADATA_DEALS_CONFIG, "metadata deals")
+    }
+
+    pub fn load_deal_metadata<BS>(&self, store: BS) -> Result<DealMetadataMap<BS>, ActorError>
+    where
+        BS: Blockstore,
+    {
+        DealMetadataMap::load(store, &self.deal_metadata, DEAL_METADATA_CONFIG, "deal metadata")
+    }
+
+    /// Lists the IDs of deals declaring a metadata CID in the order of the index, starting from
+    /// the cursor (inclusive), or from the beginning if none is given.
+    /// Returns at most `limit` IDs, and the cursor from which to resume listing if more remain.
+    pub fn list_metadata_deals<BS>(
+        &self,
+        store: &BS,
+        metadata_cid: &Cid,
+        cursor: Option<DealID>,
+        limit: usize,
+    ) -> Result<(Vec<DealID>, Option<DealID>), ActorError>
+    where
+        BS: Blockstore,
+    {
+        list_deal_ids(&self.load_metadata_deals(store)?, metadata_cid, cursor, limit)
+    }
+
     pub fn load_pending_deal_allocation_ids<BS>(
         &mut self,
         store: BS,
@@ -813,6 +1046,7 @@ impl State {
         (
             /* slash_amount */ TokenAmount,
             /* payment_amount */ TokenAmount,
+            /* payment_epochs */ Range<ChainEpoch>,
             /* is_deal_completed */ bool,
             /* remove */ bool,
         ),
@@ -846,7 +1080,7 @@ impl State {
 
         // this is a safe no-op but can happen if a storage provider calls settle_deal_payments too early
         if deal.start_epoch > epoch {
-            return Ok((TokenAmount::zero(), TokenAmount::zero(), false, false));
+            return Ok((TokenAmount::zero(), TokenAmount::zero(), epoch..epoch, false, false));
         }
 
         let payment_end_epoch = if ever_slashed {
@@ -908,15 +1142,22 @@ impl State {
             self.slash_balance(store, &deal.provider, &slashed, Reason::ProviderCollateral)
                 .context("slashing balance")?;
 
-            return Ok((slashed, payment_remaining + elapsed_payment, false, true));
+            return Ok((
+                slashed,
+                payment_remaining + elapsed_payment,
+                payment_start_epoch..payment_end_epoch,
+                false,
+                true,
+            ));
         }
 
+        let payment_epochs = payment_start_epoch..payment_end_epoch;
         if epoch >= deal.end_epoch {
             self.process_deal_expired(store, deal, state)?;
-            return Ok((TokenAmount::zero(), elapsed_payment, true, true));
+            return Ok((TokenAmount::zero(), elapsed_payment, payment_epochs, true, true));
         }
 
-        Ok((TokenAmount::zero(), elapsed_payment, false, false))
+        Ok((TokenAmount::zero(), elapsed_payment, payment_epochs, false, false))
     }
 
     pub fn process_slashed_deal<BS>(
@@ -1024,6 +1265,79 @@ impl State {
         Ok(())
     }
 
+    /// Returns the deal policy declared by a provider, or the default policy if none.
+    pub fn get_deal_policy<BS>(
+        &self,
+        store: &BS,
+        provider: ActorID,
+    ) -> Result<DealPolicy, ActorError>
+    where
+        BS: Blockstore,
+    {
+        let policies = DealPoliciesMap::load(
+            store,
+            &self.deal_policies,
+            DEAL_POLICIES_CONFIG,
+            "deal policies",
+        )?;
+        Ok(policies.get(&provider)?.cloned().unwrap_or_default())
+    }
+
+    /// Records the deal policy declared by a provider. The default policy is not stored.
+    pub fn set_deal_policy<BS>(
+        &mut self,
+        store: &BS,
+        provider: ActorID,
+        policy: DealPolicy,
+    ) -> Result<(), ActorError>
+    where
+        BS: Blockstore,
+    {
+        let mut policies = DealPoliciesMap::load(
+            store,
+            &self.deal_policies,
+            DEAL_POLICIES_CONFIG,
+            "deal policies",
+        )?;
+        if policy == DealPolicy::default() {
+            policies.delete(&provider)?;
+        } else {
+            policies.set(&provider, policy)?;
+        }
+        self.deal_policies = policies.flush()?;
+        Ok(())
+    }
+
+    /// Pays a provider's publish fee for a deal from the client's unlocked escrow
+    /// to the provider's escrow.
+    pub fn pay_publish_fee<BS>(
+        &mut self,
+        store: &BS,
+        client: &Address,
+        provider: &Address,
+        fee: &TokenAmount,
+    ) -> Result<(), ActorError>
+    where
+        BS: Blockstore,
+    {
+        if fee.is_negative() {
+            return Err(actor_error!(illegal_state, "negative publish fee {}", fee));
+        }
+        if !self.balance_covered(store, *client, fee)? {
+            return Err(actor_error!(
+                insufficient_funds,
+                "not enough unlocked balance for {} to pay publish fee {}",
+                client,
+                fee
+            ));
+        }
+        let mut escrow_table = BalanceTable::from_root(store, &self.escrow_table, "escrow table")?;
+        escrow_table.must_subtract(client, fee)?;
+        escrow_table.add(provider, fee)?;
+        self.escrow_table = escrow_table.root()?;
+        Ok(())
+    }
+
     pub fn generate_storage_deal_id(&mut self) -> DealID {
         let ret = self.next_id;
         self.next_id += 1;
@@ -1258,6 +1572,26 @@ where
     Ok(state.cloned())
 }
 
+// Lists the deal IDs under a key of a deal index in the order of the index, from the cursor
+// (inclusive), or from the beginning if none is given.
+fn list_deal_ids<BS, K>(
+    index: &SetMultimap<BS, K, DealID>,
+    key: &K,
+    cursor: Option<DealID>,
+    limit: usize,
+) -> Result<(Vec<DealID>, Option<DealID>), ActorError>
+where
+    BS: Blockstore,
+    K: MapKey,
+{
+    let mut deal_ids = Vec::new();
+    let next_cursor = index.for_each_in_from(key, cursor.as_ref(), limit, |deal_id| {
+        deal_ids.push(deal_id);
+        Ok(())
+    })?;
+    Ok((deal_ids, next_cursor))
+}
+
 pub fn load_provider_sector_deals<BS>(
     store: BS,
     provider_sectors: &ProviderSectorsMap<BS>,
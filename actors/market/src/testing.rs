@@ -28,13 +28,16 @@ use fil_actors_runtime::{
 
 use crate::ext::verifreg::AllocationID;
 use crate::{
-    DEAL_OPS_BY_EPOCH_CONFIG, DealArray, DealMetaArray, DealOpsByEpoch, DealProposal,
-    PENDING_PROPOSALS_CONFIG, PROVIDER_SECTORS_CONFIG, PendingProposalsSet, ProviderSectorsMap,
-    SECTOR_DEALS_CONFIG, SectorDealsMap, State, balance_table::BalanceTable,
+    CLIENT_DEALS_CONFIG, ClientDealsMap, DEAL_METADATA_CONFIG, DEAL_OPS_BY_EPOCH_CONFIG, DealArray,
+    DealMetaArray, DealMetadataMap, DealOpsByEpoch, DealProposal, METADATA_DEALS_CONFIG,
+    MetadataDealsMap, PENDING_PROPOSALS_CONFIG, PIECE_DEALS_CONFIG, PROVIDER_SECTORS_CONFIG,
+    PendingProposalsSet, PieceDealsMap, ProviderSectorsMap, SECTOR_DEALS_CONFIG, SectorDealsMap,
+    State, balance_table::BalanceTable,
 };
 
 #[derive(Clone)]
 pub struct DealSummary {
+    pub client: Address,
     pub provider: Address,
     pub start_epoch: ChainEpoch,
     pub end_epoch: ChainEpoch,
@@ -43,11 +46,13 @@ pub struct DealSummary {
     pub last_update_epoch: ChainEpoch,
     pub slash_epoch: ChainEpoch,
     pub piece_cid: Option<Cid>,
+    pub metadata_cid: Option<Cid>,
 }
 
 impl Default for DealSummary {
     fn default() -> Self {
         Self {
+            client: Address::new_id(0),
             provider: Address::new_id(0),
             start_epoch: 0,
             end_epoch: 0,
@@ -56,6 +61,7 @@ impl Default for DealSummary {
             last_update_epoch: -1,
             slash_epoch: -1,
             piece_cid: None,
+            metadata_cid: None,
         }
     }
 }
@@ -123,6 +129,7 @@ pub fn check_state_invariants<BS: Blockstore>(
                 proposal_stats.insert(
                     deal_id,
                     DealSummary {
+                        client: proposal.client,
                         provider: proposal.provider,
                         start_epoch: proposal.start_epoch,
                         end_epoch: proposal.end_epoch,
@@ -392,6 +399,126 @@ pub fn check_state_invariants<BS: Blockstore>(
         format!("missing deal ops for proposals: {expected_deal_ops:?}"),
     );
 
+    // client deals
+    // Every proposal should be indexed under its client, and every indexed deal should have a proposal.
+    let mut expected_client_deals: BTreeSet<DealID> = proposal_stats.keys().copied().collect();
+    match ClientDealsMap::load(store, &state.client_deals, CLIENT_DEALS_CONFIG, "client deals") {
+        Ok(client_deals) => {
+            let ret = client_deals.for_each(|client: ActorID, _| {
+                client_deals.for_each_in(&client, |deal_id: DealID| {
+                    match proposal_stats.get(&deal_id) {
+                        Some(stats) => acc.require(
+                            stats.client == Address::new_id(client),
+                            format!(
+                                "client deal {deal_id} client {client} does not match proposal client {}",
+                                stats.client
+                            ),
+                        ),
+                        None => acc.add(format!("client deal {deal_id} not found in proposals")),
+                    }
+                    expected_client_deals.remove(&deal_id);
+                    Ok(())
+                })
+            });
+            acc.require_no_error(ret, "error iterating client deals");
+        }
+        Err(e) => acc.add(format!("error loading client deals: {e}")),
+    };
+
+    acc.require(
+        expected_client_deals.is_empty(),
+        format!("missing client deals for proposals: {expected_client_deals:?}"),
+    );
+
+    // piece deals
+    // Every proposal should be indexed under its piece, and every indexed deal should have a proposal.
+    let mut expected_piece_deals: BTreeSet<DealID> = proposal_stats.keys().copied().collect();
+    match PieceDealsMap::load(store, &state.piece_deals, PIECE_DEALS_CONFIG, "piece deals") {
+        Ok(piece_deals) => {
+            let ret = piece_deals.for_each(|piece_cid: Cid, _| {
+                piece_deals.for_each_in(&piece_cid, |deal_id: DealID| {
+                    match proposal_stats.get(&deal_id) {
+                        Some(stats) => acc.require(
+                            stats.piece_cid == Some(piece_cid),
+                            format!(
+                                "piece deal {deal_id} piece {piece_cid} does not match proposal piece {:?}",
+                                stats.piece_cid
+                            ),
+                        ),
+                        None => acc.add(format!("piece deal {deal_id} not found in proposals")),
+                    }
+                    expected_piece_deals.remove(&deal_id);
+                    Ok(())
+                })
+            });
+            acc.require_no_error(ret, "error iterating piece deals");
+        }
+        Err(e) => acc.add(format!("error loading piece deals: {e}")),
+    };
+
+    acc.require(
+        expected_piece_deals.is_empty(),
+        format!("missing piece deals for proposals: {expected_piece_deals:?}"),
+    );
+
+    // deal metadata
+    // Every deal with recorded metadata should have a proposal.
+    match DealMetadataMap::load(store, &state.deal_metadata, DEAL_METADATA_CONFIG, "deal metadata")
+    {
+        Ok(deal_metadata) => {
+            let ret = deal_metadata.for_each(|deal_id: DealID, metadata_cid: &Cid| {
+                match proposal_stats.get_mut(&deal_id) {
+                    Some(stats) => stats.metadata_cid = Some(*metadata_cid),
+                    None => acc.add(format!("deal metadata {deal_id} not found in proposals")),
+                }
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating deal metadata");
+        }
+        Err(e) => acc.add(format!("error loading deal metadata: {e}")),
+    };
+
+    // metadata deals
+    // Every deal with recorded metadata should be indexed under its metadata CID,
+    // and every indexed deal should have a proposal.
+    let mut expected_metadata_deals: BTreeSet<DealID> = proposal_stats
+        .iter()
+        .filter(|(_, stats)| stats.metadata_cid.is_some())
+        .map(|(id, _)| *id)
+        .collect();
+    match MetadataDealsMap::load(
+        store,
+        &state.metadata_deals,
+        METADATA_DEALS_CONFIG,
+        "metadata deals",
+    ) {
+        Ok(metadata_deals) => {
+            let ret = metadata_deals.for_each(|metadata_cid: Cid, _| {
+                metadata_deals.for_each_in(&metadata_cid, |deal_id: DealID| {
+                    match proposal_stats.get(&deal_id) {
+                        Some(stats) => acc.require(
+                            stats.metadata_cid == Some(metadata_cid),
+                            format!(
+                                "metadata deal {deal_id} metadata {metadata_cid} does not match proposal metadata {:?}",
+                                stats.metadata_cid
+                            ),
+                        ),
+                        None => acc.add(format!("metadata deal {deal_id} not found in proposals")),
+                    }
+                    expected_metadata_deals.remove(&deal_id);
+                    Ok(())
+                })
+            });
+            acc.require_no_error(ret, "error iterating metadata deals");
+        }
+        Err(e) => acc.add(format!("error loading metadata deals: {e}")),
+    };
+
+    acc.require(
+        expected_metadata_deals.is_empty(),
+        format!("missing metadata deals for proposals: {expected_metadata_deals:?}"),
+    );
+
     (
         StateSummary {
             deals: proposal_stats,
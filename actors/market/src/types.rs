@@ -5,6 +5,7 @@ use super::ext::verifreg::AllocationID;
 use cid::Cid;
 use fil_actors_runtime::Array;
 use fil_actors_runtime::BatchReturn;
+use fil_actors_runtime::Cursor;
 use fvm_ipld_bitfield::BitField;
 use fvm_ipld_encoding::strict_bytes;
 use fvm_ipld_encoding::tuple::*;
@@ -19,7 +20,7 @@ use fvm_shared::piece::PaddedPieceSize;
 use crate::Label;
 use fvm_shared::sector::{RegisteredSealProof, SectorNumber};
 
-use super::deal::{ClientDealProposal, DealProposal, DealState};
+use super::deal::{ClientDealProposal, ClientDealProposal2, DealProposal, DealState};
 
 pub const PROPOSALS_AMT_BITWIDTH: u32 = 5;
 pub const STATES_AMT_BITWIDTH: u32 = 6;
@@ -54,6 +55,41 @@ pub struct GetBalanceReturn {
     pub locked: TokenAmount,
 }
 
+/// The funds locked in escrow across all accounts.
+/// The three amounts together make up the total of the locked table.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct LockedFundsReturn {
+    /// Collateral locked by clients for deals that have not yet completed.
+    pub client_collateral: TokenAmount,
+    /// Collateral locked by providers for deals that have not yet completed.
+    pub provider_collateral: TokenAmount,
+    /// Storage fees locked by clients that have not yet been paid to providers.
+    pub client_storage_fees: TokenAmount,
+}
+
+/// Terms a provider places on the deals published for it.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Default, Eq, PartialEq)]
+pub struct DealPolicy {
+    /// Fee paid by the client to the provider, from escrow, for each deal published.
+    /// Only deals whose client agreed to a maximum publish fee of at least this amount,
+    /// in the proposal it signed, are published.
+    pub publish_fee: TokenAmount,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct SetDealPolicyParams {
+    pub provider: Address,
+    pub policy: DealPolicy,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetDealPolicyParams {
+    pub provider: Address,
+}
+
+pub type GetDealPolicyReturn = DealPolicy;
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq)] // Add Eq when BitField does
 pub struct OnMinerSectorsTerminateParams {
     pub epoch: ChainEpoch,
@@ -65,6 +101,11 @@ pub struct PublishStorageDealsParams {
     pub deals: Vec<ClientDealProposal>,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct PublishStorageDeals2Params {
+    pub deals: Vec<ClientDealProposal2>,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq)] // Add Eq when BitField does
 pub struct PublishStorageDealsReturn {
     pub ids: Vec<DealID>,
@@ -248,6 +289,75 @@ pub struct GetDealSectorReturn {
     pub sector: SectorNumber,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ListClientDealsParams {
+    pub client: Address,
+    /// The start cursor to list from the beginning; otherwise the next_cursor of a previous call.
+    pub cursor: Cursor,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ListClientDealsReturn {
+    /// IDs of the client's deals with a proposal in state.
+    pub deals: Vec<DealID>,
+    /// Cursor from which to continue the listing, if more deals remain.
+    pub next_cursor: Option<Cursor>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetDealsForPieceParams {
+    pub piece_cid: Cid,
+    /// The start cursor to list from the beginning; otherwise the next_cursor of a previous call.
+    pub cursor: Cursor,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetDealsForPieceReturn {
+    /// IDs of the deals for the piece with a proposal in state.
+    pub deals: Vec<DealID>,
+    /// Cursor from which to continue the listing, if more deals remain.
+    pub next_cursor: Option<Cursor>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetDealsForMetadataParams {
+    pub metadata_cid: Cid,
+    /// The start cursor to list from the beginning; otherwise the next_cursor of a previous call.
+    pub cursor: Cursor,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetDealsForMetadataReturn {
+    /// IDs of the deals declaring the metadata with a proposal in state.
+    pub deals: Vec<DealID>,
+    /// Cursor from which to continue the listing, if more deals remain.
+    pub next_cursor: Option<Cursor>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct SetPublishingPausedParams {
+    pub paused: bool,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct RecomputeProviderCollateralParams {
+    pub provider: ActorID,
+    /// Deals of the provider for which to recompute the minimum collateral.
+    pub deal_ids: Vec<DealID>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct RecomputeProviderCollateralReturn {
+    /// Result for each deal, which fails if the deal is not found, is not the provider's,
+    /// or has ended, been terminated, or missed activation.
+    pub results: BatchReturn,
+    /// Deals whose collateral is below the current minimum, in the order given.
+    pub under_collateralized: Vec<DealID>,
+    /// Total collateral needed to bring the under-collateralized deals up to their minimum.
+    pub top_up_required: TokenAmount,
+}
+
 // Interface market clients can implement to receive notifications from builtin market
 pub const MARKET_NOTIFY_DEAL_METHOD: u64 = frc42_dispatch::method_hash!("MarketNotifyDeal");
 
@@ -181,6 +181,7 @@ fn expired_deal_should_unlock_the_remaining_client_and_provider_locked_balance_a
     let p_escrow = get_balance(&rt, &PROVIDER_ADDR).balance;
 
     // move the current epoch so that deal is expired
+    expect_deal_settled(&rt, deal_id, START_EPOCH, END_EPOCH);
     expect_emitted(
         &rt,
         "deal-completed",
@@ -224,6 +225,7 @@ fn all_payments_are_made_for_a_deal_client_withdraws_collateral_and_client_accou
 
     // move the current epoch so that deal is expired
     rt.set_epoch(END_EPOCH + 100);
+    expect_deal_settled(&rt, _deal_id, START_EPOCH, END_EPOCH);
     expect_emitted(
         &rt,
         "deal-completed",
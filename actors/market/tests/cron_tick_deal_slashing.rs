@@ -129,21 +129,21 @@ fn deal_is_slashed_at_the_end_epoch_should_not_be_slashed_and_should_be_consider
     );
 
     // set current epoch to deal end epoch and attempt to slash it -> should not be slashed
-    // as deal is considered to be expired.
+    // as deal is considered to be expired, and is settled instead.
     rt.set_epoch(END_EPOCH);
-    terminate_deals_and_assert_balances(&rt, CLIENT_ADDR, PROVIDER_ADDR, &[SECTOR_NUMBER], &[]);
-    let duration = END_EPOCH - START_EPOCH;
-
-    let current = END_EPOCH + 300;
-    rt.set_epoch(current);
     let (pay, slashed) =
-        cron_tick_and_assert_balances(&rt, CLIENT_ADDR, PROVIDER_ADDR, current, deal_id);
+        terminate_deals_and_assert_balances(&rt, CLIENT_ADDR, PROVIDER_ADDR, &[SECTOR_NUMBER], &[]);
+    let duration = END_EPOCH - START_EPOCH;
     assert_eq!(duration * &deal_proposal.storage_price_per_epoch, pay);
     assert!(slashed.is_zero());
 
     // deal should be deleted as it should have expired
     assert_deal_deleted(&rt, deal_id, &deal_proposal, SECTOR_NUMBER, true);
 
+    // a later cron tick has nothing left to do for the deal
+    rt.set_epoch(END_EPOCH + 300);
+    cron_tick(&rt);
+
     check_state(&rt);
 }
 
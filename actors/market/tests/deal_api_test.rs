@@ -1,4 +1,6 @@
+use cid::Cid;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::Address;
 use fvm_shared::clock::{ChainEpoch, EPOCH_UNDEFINED};
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
@@ -6,17 +8,19 @@ use num_traits::Zero;
 use serde::de::DeserializeOwned;
 
 use fil_actor_market::{
-    Actor as MarketActor, DealQueryParams, EX_DEAL_EXPIRED, EX_DEAL_NOT_ACTIVATED,
+    Actor as MarketActor, DealProposal2, DealQueryParams, EX_DEAL_EXPIRED, EX_DEAL_NOT_ACTIVATED,
     GetDealActivationReturn, GetDealClientCollateralReturn, GetDealClientReturn,
     GetDealDataCommitmentReturn, GetDealLabelReturn, GetDealProviderCollateralReturn,
     GetDealProviderReturn, GetDealSectorReturn, GetDealTermReturn, GetDealTotalPriceReturn,
-    GetDealVerifiedReturn, Method,
+    GetDealVerifiedReturn, GetDealsForMetadataParams, GetDealsForMetadataReturn,
+    GetDealsForPieceParams, GetDealsForPieceReturn, Label, ListClientDealsParams,
+    ListClientDealsReturn, LockedFundsReturn, Method, State,
 };
-use fil_actors_runtime::ActorError;
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::test_utils::{
-    ACCOUNT_ACTOR_CODE_ID, MockRuntime, expect_abort, expect_abort_contains_message,
+    ACCOUNT_ACTOR_CODE_ID, MockRuntime, expect_abort, expect_abort_contains_message, make_piece_cid,
 };
+use fil_actors_runtime::{ActorError, Cursor};
 use harness::*;
 
 mod harness;
@@ -145,6 +149,430 @@ fn activation() {
     query_deal_fails(&rt, Method::GetDealSectorExported, id + 1, ExitCode::USR_NOT_FOUND);
 }
 
+#[test]
+fn list_client_deals() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 180 * EPOCHS_IN_DAY;
+    let publish_epoch = ChainEpoch::from(1);
+
+    let rt = setup();
+    rt.set_epoch(publish_epoch);
+    let next_allocation_id = 1;
+
+    // No deals yet.
+    assert_eq!(
+        ListClientDealsReturn { deals: vec![], next_cursor: None },
+        list_client_deals(&rt, CLIENT_ADDR, Cursor::start())
+    );
+
+    let proposal1 = generate_deal_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch,
+    );
+    let proposal2 = generate_deal_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch + 1,
+    );
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    let ids = publish_deals(
+        &rt,
+        &MinerAddresses::default(),
+        &[proposal1, proposal2],
+        TokenAmount::zero(),
+        next_allocation_id,
+    );
+
+    let listed = list_client_deals(&rt, CLIENT_ADDR, Cursor::start());
+    assert_eq!(None, listed.next_cursor);
+    let mut deals = listed.deals.clone();
+    deals.sort_unstable();
+    assert_eq!(ids, deals);
+    // Listing from a cursor resumes at that deal.
+    assert_eq!(
+        ListClientDealsReturn { deals: listed.deals[1..].to_vec(), next_cursor: None },
+        list_client_deals(&rt, CLIENT_ADDR, Cursor::encode(&listed.deals[1]).unwrap())
+    );
+    // The provider is not the client of any deal.
+    assert_eq!(
+        ListClientDealsReturn { deals: vec![], next_cursor: None },
+        list_client_deals(&rt, PROVIDER_ADDR, Cursor::start())
+    );
+
+    // Terminated deals are removed from the listing.
+    let activate_epoch = start_epoch - 2;
+    rt.set_epoch(activate_epoch);
+    let sector_number = 7;
+    activate_deals(&rt, end_epoch + 1, PROVIDER_ADDR, activate_epoch, sector_number, &[ids[0]]);
+    rt.set_epoch(activate_epoch + 100);
+    terminate_deals(&rt, PROVIDER_ADDR, &[sector_number], &[ids[0]]);
+    assert_eq!(
+        ListClientDealsReturn { deals: vec![ids[1]], next_cursor: None },
+        list_client_deals(&rt, CLIENT_ADDR, Cursor::start())
+    );
+    // A cursor at a removed deal still resumes the listing.
+    assert_eq!(
+        ListClientDealsReturn { deals: vec![ids[1]], next_cursor: None },
+        list_client_deals(&rt, CLIENT_ADDR, Cursor::encode(&ids[0]).unwrap())
+    );
+
+    // Unresolvable client address, and a malformed cursor.
+    for params in [
+        ListClientDealsParams { client: Address::new_actor(b"unknown"), cursor: Cursor::start() },
+        ListClientDealsParams { client: CLIENT_ADDR, cursor: Cursor::encode(&"x").unwrap() },
+    ] {
+        rt.expect_validate_caller_any();
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            rt.call::<MarketActor>(
+                Method::ListClientDealsExported as u64,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            ),
+        );
+    }
+
+    check_state(&rt);
+}
+
+#[test]
+fn get_deals_for_piece() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 180 * EPOCHS_IN_DAY;
+    let publish_epoch = ChainEpoch::from(1);
+
+    let rt = setup();
+    rt.set_epoch(publish_epoch);
+    let next_allocation_id = 1;
+
+    let proposal1 = generate_deal_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch,
+    );
+    let proposal2 = generate_deal_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch + 1,
+    );
+    // Both deals are for the same piece.
+    let piece_cid = proposal1.piece_cid;
+    assert_eq!(piece_cid, proposal2.piece_cid);
+
+    // No deals yet.
+    assert_eq!(
+        GetDealsForPieceReturn { deals: vec![], next_cursor: None },
+        get_deals_for_piece(&rt, piece_cid, Cursor::start())
+    );
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    let ids = publish_deals(
+        &rt,
+        &MinerAddresses::default(),
+        &[proposal1, proposal2],
+        TokenAmount::zero(),
+        next_allocation_id,
+    );
+
+    let listed = get_deals_for_piece(&rt, piece_cid, Cursor::start());
+    assert_eq!(None, listed.next_cursor);
+    let mut deals = listed.deals.clone();
+    deals.sort_unstable();
+    assert_eq!(ids, deals);
+    // Listing from a cursor resumes at that deal.
+    assert_eq!(
+        GetDealsForPieceReturn { deals: listed.deals[1..].to_vec(), next_cursor: None },
+        get_deals_for_piece(&rt, piece_cid, Cursor::encode(&listed.deals[1]).unwrap())
+    );
+    // No deals for some other piece.
+    assert_eq!(
+        GetDealsForPieceReturn { deals: vec![], next_cursor: None },
+        get_deals_for_piece(&rt, make_piece_cid(b"other"), Cursor::start())
+    );
+
+    // Terminated deals are removed from the index.
+    let activate_epoch = start_epoch - 2;
+    rt.set_epoch(activate_epoch);
+    let sector_number = 7;
+    activate_deals(&rt, end_epoch + 1, PROVIDER_ADDR, activate_epoch, sector_number, &[ids[0]]);
+    rt.set_epoch(activate_epoch + 100);
+    terminate_deals(&rt, PROVIDER_ADDR, &[sector_number], &[ids[0]]);
+    assert_eq!(
+        GetDealsForPieceReturn { deals: vec![ids[1]], next_cursor: None },
+        get_deals_for_piece(&rt, piece_cid, Cursor::start())
+    );
+
+    // Malformed cursor.
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        rt.call::<MarketActor>(
+            Method::GetDealsForPieceExported as u64,
+            IpldBlock::serialize_cbor(&GetDealsForPieceParams {
+                piece_cid,
+                cursor: Cursor::encode(&"x").unwrap(),
+            })
+            .unwrap(),
+        ),
+    );
+
+    check_state(&rt);
+}
+
+#[test]
+fn get_deals_for_metadata() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 180 * EPOCHS_IN_DAY;
+    let publish_epoch = ChainEpoch::from(1);
+
+    let rt = setup();
+    rt.set_epoch(publish_epoch);
+    let next_allocation_id = 1;
+    let addrs = MinerAddresses::default();
+
+    let metadata_cid = make_piece_cid(b"metadata");
+    let proposal1 = DealProposal2 {
+        metadata: Some(metadata_cid),
+        ..deal_proposal2(&generate_deal_and_add_funds(
+            &rt,
+            CLIENT_ADDR,
+            &addrs,
+            start_epoch,
+            end_epoch,
+        ))
+    };
+    let proposal2 = DealProposal2 {
+        metadata: Some(metadata_cid),
+        ..deal_proposal2(&generate_deal_and_add_funds(
+            &rt,
+            CLIENT_ADDR,
+            &addrs,
+            start_epoch,
+            end_epoch + 1,
+        ))
+    };
+    // A deal without metadata isn't indexed.
+    let proposal3 = deal_proposal2(&generate_deal_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &addrs,
+        start_epoch,
+        end_epoch + 2,
+    ));
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    let ids = publish_deals2(
+        &rt,
+        &addrs,
+        &[proposal1, proposal2, proposal3],
+        TokenAmount::zero(),
+        next_allocation_id,
+        PublishDeals2Config::default(),
+    );
+
+    // Nor is a deal whose label happens to hold the metadata CID.
+    let mut proposal4 =
+        generate_deal_and_add_funds(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch + 3);
+    proposal4.label = Label::Bytes([vec![0], metadata_cid.to_bytes()].concat());
+    publish_deals(&rt, &addrs, &[proposal4], TokenAmount::zero(), next_allocation_id);
+
+    let listed = get_deals_for_metadata(&rt, metadata_cid, Cursor::start());
+    assert_eq!(None, listed.next_cursor);
+    let mut deals = listed.deals;
+    deals.sort_unstable();
+    assert_eq!(ids[..2].to_vec(), deals);
+    assert_eq!(
+        GetDealsForMetadataReturn { deals: vec![], next_cursor: None },
+        get_deals_for_metadata(&rt, make_piece_cid(b"other"), Cursor::start())
+    );
+
+    // Terminated deals are removed from the index.
+    let activate_epoch = start_epoch - 2;
+    rt.set_epoch(activate_epoch);
+    let sector_number = 7;
+    activate_deals(&rt, end_epoch + 1, PROVIDER_ADDR, activate_epoch, sector_number, &[ids[0]]);
+    rt.set_epoch(activate_epoch + 100);
+    terminate_deals(&rt, PROVIDER_ADDR, &[sector_number], &[ids[0]]);
+    assert_eq!(
+        GetDealsForMetadataReturn { deals: vec![ids[1]], next_cursor: None },
+        get_deals_for_metadata(&rt, metadata_cid, Cursor::start())
+    );
+
+    check_state(&rt);
+}
+
+#[test]
+fn rebuild_deal_indexes() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 180 * EPOCHS_IN_DAY;
+    let publish_epoch = ChainEpoch::from(1);
+
+    let rt = setup();
+    rt.set_epoch(publish_epoch);
+    let next_allocation_id = 1;
+    let addrs = MinerAddresses::default();
+    let unindexed: State = rt.get_state();
+
+    let metadata_cid = make_piece_cid(b"metadata");
+    let proposal = generate_deal_and_add_funds(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch);
+    let piece_cid = proposal.piece_cid;
+    let proposal = DealProposal2 { metadata: Some(metadata_cid), ..deal_proposal2(&proposal) };
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    let ids = publish_deals2(
+        &rt,
+        &addrs,
+        &[proposal],
+        TokenAmount::zero(),
+        next_allocation_id,
+        PublishDeals2Config::default(),
+    );
+
+    // Drop the index entries, as for state from before the indexes were maintained.
+    let mut st: State = rt.get_state();
+    st.client_deals = unindexed.client_deals;
+    st.piece_deals = unindexed.piece_deals;
+    st.metadata_deals = unindexed.metadata_deals;
+    st.rebuild_deal_indexes(&rt.store).unwrap();
+    rt.replace_state(&st);
+
+    assert_eq!(
+        ListClientDealsReturn { deals: ids.clone(), next_cursor: None },
+        list_client_deals(&rt, CLIENT_ADDR, Cursor::start())
+    );
+    assert_eq!(
+        GetDealsForPieceReturn { deals: ids.clone(), next_cursor: None },
+        get_deals_for_piece(&rt, piece_cid, Cursor::start())
+    );
+    assert_eq!(
+        GetDealsForMetadataReturn { deals: ids, next_cursor: None },
+        get_deals_for_metadata(&rt, metadata_cid, Cursor::start())
+    );
+    check_state(&rt);
+}
+
+#[test]
+fn locked_funds() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 180 * EPOCHS_IN_DAY;
+    let publish_epoch = ChainEpoch::from(1);
+
+    let rt = setup();
+    rt.set_epoch(publish_epoch);
+    let next_allocation_id = 1;
+
+    let nothing_locked = LockedFundsReturn {
+        client_collateral: TokenAmount::zero(),
+        provider_collateral: TokenAmount::zero(),
+        client_storage_fees: TokenAmount::zero(),
+    };
+    assert_eq!(nothing_locked, query_locked_funds(&rt));
+
+    let proposal = generate_deal_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch,
+    );
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    publish_deals(
+        &rt,
+        &MinerAddresses::default(),
+        &[proposal.clone()],
+        TokenAmount::zero(),
+        next_allocation_id,
+    );
+
+    let locked = query_locked_funds(&rt);
+    assert_eq!(
+        LockedFundsReturn {
+            client_collateral: proposal.client_collateral.clone(),
+            provider_collateral: proposal.provider_collateral.clone(),
+            client_storage_fees: proposal.total_storage_fee(),
+        },
+        locked
+    );
+    // The totals match the locked balances of the accounts.
+    assert_eq!(
+        get_balance(&rt, &CLIENT_ADDR).locked + get_balance(&rt, &PROVIDER_ADDR).locked,
+        locked.client_collateral + locked.provider_collateral + locked.client_storage_fees
+    );
+
+    check_state(&rt);
+}
+
+fn query_locked_funds(rt: &MockRuntime) -> LockedFundsReturn {
+    rt.expect_validate_caller_any();
+    let ret = rt
+        .call::<MarketActor>(Method::LockedFundsExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    ret
+}
+
+fn list_client_deals(rt: &MockRuntime, client: Address, cursor: Cursor) -> ListClientDealsReturn {
+    let params = ListClientDealsParams { client, cursor };
+    rt.expect_validate_caller_any();
+    let ret = rt
+        .call::<MarketActor>(
+            Method::ListClientDealsExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    ret
+}
+
+fn get_deals_for_piece(rt: &MockRuntime, piece_cid: Cid, cursor: Cursor) -> GetDealsForPieceReturn {
+    let params = GetDealsForPieceParams { piece_cid, cursor };
+    rt.expect_validate_caller_any();
+    let ret = rt
+        .call::<MarketActor>(
+            Method::GetDealsForPieceExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    ret
+}
+
+fn get_deals_for_metadata(
+    rt: &MockRuntime,
+    metadata_cid: Cid,
+    cursor: Cursor,
+) -> GetDealsForMetadataReturn {
+    let params = GetDealsForMetadataParams { metadata_cid, cursor };
+    rt.expect_validate_caller_any();
+    let ret = rt
+        .call::<MarketActor>(
+            Method::GetDealsForMetadataExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    ret
+}
+
 fn query_deal<T: DeserializeOwned>(rt: &MockRuntime, method: Method, id: u64) -> T {
     query_deal_raw(rt, method, id).unwrap().unwrap().deserialize().unwrap()
 }
@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
 use std::cmp::{max, min};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::{cell::RefCell, collections::HashMap, collections::HashSet};
 
 use cid::Cid;
@@ -29,27 +29,29 @@ use fil_actor_market::ext::miner::{
 };
 use fil_actor_market::ext::verifreg::{AllocationID, AllocationRequest, AllocationsResponse};
 use fil_actor_market::{
-    Actor as MarketActor, ClientDealProposal, DealArray, DealMetaArray, DealProposal, DealState,
-    GetBalanceReturn, Label, MARKET_NOTIFY_DEAL_METHOD, MarketNotifyDealParams, Method,
-    NO_ALLOCATION_ID, OnMinerSectorsTerminateParams, PublishStorageDealsParams,
+    Actor as MarketActor, ClientDealProposal, ClientDealProposal2, DealArray, DealMetaArray,
+    DealPayment, DealProposal, DealProposal2, DealState, GetBalanceReturn, Label,
+    MARKET_NOTIFY_DEAL_METHOD, MarketNotifyDealParams, Method, NO_ALLOCATION_ID,
+    OnMinerSectorsTerminateParams, PublishStorageDeals2Params, PublishStorageDealsParams,
     PublishStorageDealsReturn, SectorDeals, State, VerifyDealsForActivationParams,
     VerifyDealsForActivationReturn, WithdrawBalanceParams, WithdrawBalanceReturn, ext,
     ext::miner::GetControlAddressesReturnParams, next_update_epoch,
     testing::check_state_invariants,
 };
 use fil_actor_market::{
-    BatchActivateDealsParams, BatchActivateDealsResult, DealOpsByEpoch, PENDING_ALLOCATIONS_CONFIG,
-    PENDING_PROPOSALS_CONFIG, PROVIDER_SECTORS_CONFIG, PendingDealAllocationsMap,
-    PendingProposalsSet, ProviderSectorsMap, SECTOR_DEALS_CONFIG, SectorDealsMap,
+    BatchActivateDealsParams, BatchActivateDealsResult, DealOpsByEpoch, DealPolicy,
+    GetDealPolicyParams, PENDING_ALLOCATIONS_CONFIG, PENDING_PROPOSALS_CONFIG,
+    PROVIDER_SECTORS_CONFIG, PendingDealAllocationsMap, PendingProposalsSet, ProviderSectorsMap,
+    SECTOR_DEALS_CONFIG, SectorDealsMap, SetDealPolicyParams, SetPublishingPausedParams,
     SettleDealPaymentsParams, SettleDealPaymentsReturn, deal_cid, deal_get_payment_remaining,
 };
 use fil_actor_power::{CurrentTotalPowerReturn, Method as PowerMethod};
 use fil_actor_reward::Method as RewardMethod;
 use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::{
-    ActorError, BURNT_FUNDS_ACTOR_ADDR, BatchReturn, CRON_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ADDR,
-    EventBuilder, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
-    SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    ActorError, BURNT_FUNDS_ACTOR_ADDR, BatchReturn, CRON_ACTOR_ADDR, EventBuilder,
+    REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
     network::EPOCHS_IN_DAY,
     runtime::{Policy, Runtime, builtins::Type},
     test_utils::*,
@@ -668,6 +670,9 @@ pub fn cron_tick_and_assert_balances(
         updated_provider_locked = TokenAmount::zero();
     }
 
+    if payment_end > payment_start {
+        expect_deal_settled(rt, deal_id, payment_start, payment_end);
+    }
     if is_deal_expired {
         expect_emitted(
             rt,
@@ -775,14 +780,7 @@ pub fn publish_deals(
             // Expect query for the client's datacap balance, just once per client.
             let client_id = deal.client.id().unwrap();
             if !client_verified_deals.contains_key(&client_id) {
-                rt.expect_send_simple(
-                    DATACAP_TOKEN_ACTOR_ADDR,
-                    ext::datacap::BALANCE_OF_METHOD,
-                    IpldBlock::serialize_cbor(&deal.client).unwrap(),
-                    TokenAmount::zero(),
-                    IpldBlock::serialize_cbor(&clients_datacap_balance).unwrap(),
-                    ExitCode::OK,
-                );
+                rt.expect_datacap_balance(deal.client, clients_datacap_balance.clone());
             }
 
             let cvd = client_verified_deals.entry(client_id).or_insert(ClientVerifiedDeals {
@@ -837,19 +835,14 @@ pub fn publish_deals(
             extension_results: BatchReturn::empty(),
             new_allocations: (alloc_id..alloc_id + alloc_req.allocations.len() as u64).collect(),
         };
-        rt.expect_send_simple(
-            DATACAP_TOKEN_ACTOR_ADDR,
-            ext::datacap::TRANSFER_FROM_METHOD,
-            IpldBlock::serialize_cbor(&params).unwrap(),
-            TokenAmount::zero(),
-            IpldBlock::serialize_cbor(&TransferFromReturn {
+        rt.expect_datacap_transfer_from(
+            params,
+            TransferFromReturn {
                 from_balance: TokenAmount::zero(),
                 to_balance: cvd.datacap_consumed,
                 allowance: TokenAmount::zero(),
                 recipient_data: serialize(&alloc_ids, "allocation response").unwrap(),
-            })
-            .unwrap(),
-            ExitCode::OK,
+            },
         );
         alloc_id += alloc_req.allocations.len() as AllocationID;
     }
@@ -904,6 +897,209 @@ pub fn publish_deals(
     ret.ids
 }
 
+// Publishes deals in the DealProposal2 format, expecting all of them to be valid
+// and the clients to hold sufficient datacap for the verified deals.
+/// Outcomes of the checks other actors make of deals published by publish_deals2.
+#[derive(Default)]
+pub struct PublishDeals2Config {
+    // Indices of deals whose allocation terms the verified registry rejects.
+    pub rejected_allocations: Vec<usize>,
+    // Indices of deals whose token payment fails.
+    pub failed_token_payments: Vec<usize>,
+}
+
+pub fn publish_deals2(
+    rt: &MockRuntime,
+    addrs: &MinerAddresses,
+    publish_deals: &[DealProposal2],
+    clients_datacap_balance: TokenAmount,
+    next_allocation_id: AllocationID,
+    cfg: PublishDeals2Config,
+) -> Vec<DealID> {
+    let st: State = rt.get_state();
+    let next_deal_id = st.next_id;
+    rt.expect_validate_caller_any();
+    expect_provider_is_control_address(rt, addrs.provider, *rt.caller.borrow(), true);
+    expect_query_network_info(rt);
+
+    let mut params = PublishStorageDeals2Params { deals: vec![] };
+    for deal in publish_deals {
+        // The client signs the proposal in the DealProposal2 format.
+        let buf = RawBytes::serialize(deal.clone()).expect("failed to marshal deal proposal");
+        let sig = Signature::new_bls("does not matter".as_bytes().to_vec());
+        params.deals.push(ClientDealProposal2 { proposal: deal.clone(), client_signature: sig });
+        rt.expect_send(
+            deal.client,
+            ext::account::AUTHENTICATE_MESSAGE_METHOD,
+            IpldBlock::serialize_cbor(&AuthenticateMessageParams {
+                signature: "does not matter".as_bytes().to_vec(),
+                message: buf.to_vec(),
+            })
+            .unwrap(),
+            TokenAmount::zero(),
+            None,
+            SendFlags::READ_ONLY,
+            AUTHENTICATE_MESSAGE_RESPONSE.clone(),
+            ExitCode::OK,
+            None,
+        );
+    }
+
+    // Expect the verified registry to validate the proposed allocation terms.
+    let with_terms: Vec<usize> =
+        (0..publish_deals.len()).filter(|di| publish_deals[*di].allocation.is_some()).collect();
+    if !with_terms.is_empty() {
+        let requests = with_terms
+            .iter()
+            .map(|di| alloc_request_for_terms(&publish_deals[*di], addrs.provider))
+            .collect();
+        let codes: Vec<_> = with_terms
+            .iter()
+            .map(|di| match cfg.rejected_allocations.contains(di) {
+                true => ExitCode::USR_ILLEGAL_ARGUMENT,
+                false => ExitCode::OK,
+            })
+            .collect();
+        rt.expect_send(
+            VERIFIED_REGISTRY_ACTOR_ADDR,
+            ext::verifreg::VALIDATE_ALLOCATION_REQUESTS_METHOD,
+            IpldBlock::serialize_cbor(&ext::verifreg::ValidateAllocationRequestsParams {
+                requests,
+            })
+            .unwrap(),
+            TokenAmount::zero(),
+            None,
+            SendFlags::READ_ONLY,
+            IpldBlock::serialize_cbor(&BatchReturn::of(&codes)).unwrap(),
+            ExitCode::OK,
+            None,
+        );
+    }
+    let accepted: Vec<usize> =
+        (0..publish_deals.len()).filter(|di| !cfg.rejected_allocations.contains(di)).collect();
+    if accepted.iter().any(|di| publish_deals[*di].payment.token.is_some()) {
+        expect_provider_control_address(rt, addrs.provider, addrs.owner, addrs.worker);
+    }
+
+    // Expect one datacap balance query per client with verified deals, then each token payment
+    // to be taken from the client for the provider's owner.
+    let mut datacap_queried = BTreeSet::new();
+    for di in &accepted {
+        let deal = &publish_deals[*di];
+        if deal.allocation.is_some() && datacap_queried.insert(deal.client.id().unwrap()) {
+            rt.expect_datacap_balance(deal.client, clients_datacap_balance.clone());
+        }
+    }
+    let mut published = Vec::new();
+    let mut client_allocations: BTreeMap<ActorID, Vec<AllocationRequest>> = BTreeMap::new();
+    for di in accepted {
+        let deal = &publish_deals[di];
+        let client = deal.client.id().unwrap();
+        if let Some(payment) = &deal.payment.token {
+            let failed = cfg.failed_token_payments.contains(&di);
+            rt.expect_send_simple(
+                payment.token,
+                ext::token::TRANSFER_FROM_METHOD,
+                IpldBlock::serialize_cbor(&TransferFromParams {
+                    from: deal.client,
+                    to: addrs.owner,
+                    amount: payment.amount.clone(),
+                    operator_data: RawBytes::default(),
+                })
+                .unwrap(),
+                TokenAmount::zero(),
+                None,
+                if failed { ExitCode::USR_INSUFFICIENT_FUNDS } else { ExitCode::OK },
+            );
+            if failed {
+                continue;
+            }
+        }
+        if deal.allocation.is_some() {
+            client_allocations
+                .entry(client)
+                .or_default()
+                .push(alloc_request_for_terms(deal, addrs.provider));
+        }
+        published.push(deal);
+    }
+    let mut alloc_id = next_allocation_id;
+    for (client, allocations) in client_allocations {
+        let datacap_consumed =
+            TokenAmount::from_whole(allocations.iter().map(|a| a.size.0).sum::<u64>());
+        let alloc_req = ext::verifreg::AllocationRequests { allocations, extensions: vec![] };
+        let params = TransferFromParams {
+            from: Address::new_id(client),
+            to: VERIFIED_REGISTRY_ACTOR_ADDR,
+            amount: datacap_consumed.clone(),
+            operator_data: serialize(&alloc_req, "allocation requests").unwrap(),
+        };
+        let alloc_ids = AllocationsResponse {
+            allocation_results: BatchReturn::ok(alloc_req.allocations.len() as u32),
+            extension_results: BatchReturn::empty(),
+            new_allocations: (alloc_id..alloc_id + alloc_req.allocations.len() as u64).collect(),
+        };
+        rt.expect_datacap_transfer_from(
+            params,
+            TransferFromReturn {
+                from_balance: TokenAmount::zero(),
+                to_balance: datacap_consumed,
+                allowance: TokenAmount::zero(),
+                recipient_data: serialize(&alloc_ids, "allocation response").unwrap(),
+            },
+        );
+        alloc_id += alloc_req.allocations.len() as AllocationID;
+    }
+
+    // Clients are notified with the proposal in the format recorded in state.
+    let mut deal_id = next_deal_id;
+    for deal in published {
+        let proposal = deal.to_proposal();
+        let buf = RawBytes::serialize(proposal.clone()).expect("failed to marshal deal proposal");
+        rt.expect_send_simple(
+            proposal.client,
+            MARKET_NOTIFY_DEAL_METHOD,
+            IpldBlock::serialize_cbor(&MarketNotifyDealParams { proposal: buf.to_vec(), deal_id })
+                .unwrap(),
+            TokenAmount::zero(),
+            None,
+            ExitCode::OK,
+        );
+        expect_emitted(
+            rt,
+            "deal-published",
+            deal_id,
+            proposal.client.id().unwrap(),
+            proposal.provider.id().unwrap(),
+        );
+        deal_id += 1;
+    }
+
+    let ret: PublishStorageDealsReturn = rt
+        .call::<MarketActor>(
+            Method::PublishStorageDeals2Exported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    ret.ids
+}
+
+fn alloc_request_for_terms(deal: &DealProposal2, provider: Address) -> AllocationRequest {
+    let terms = deal.allocation.as_ref().unwrap();
+    AllocationRequest {
+        provider: provider.id().unwrap(),
+        data: deal.piece_cid,
+        size: deal.piece_size,
+        term_min: terms.term_min,
+        term_max: terms.term_max,
+        expiration: terms.expiration,
+    }
+}
+
 pub fn publish_deals_expect_abort(
     rt: &MockRuntime,
     miner_addresses: &MinerAddresses,
@@ -968,26 +1164,33 @@ pub fn settle_deal_payments(
     rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, caller);
     rt.expect_validate_caller_any();
 
-    for deal_id in completed_deals {
-        let deal = get_deal_proposal(rt, *deal_id);
-        expect_emitted(
-            rt,
-            "deal-completed",
-            *deal_id,
-            deal.client.id().unwrap(),
-            deal.provider.id().unwrap(),
-        );
-    }
-
-    for deal_id in terminated_deals {
-        let deal = get_deal_proposal(rt, *deal_id);
-        expect_emitted(
-            rt,
-            "deal-terminated",
-            *deal_id,
-            deal.client.id().unwrap(),
-            deal.provider.id().unwrap(),
-        );
+    // Deals are settled in order of deal ID.
+    let mut sorted_deal_ids = deal_ids.to_vec();
+    sorted_deal_ids.sort();
+    for deal_id in sorted_deal_ids {
+        if let Some((from_epoch, to_epoch)) = expected_settlement_epochs(rt, deal_id) {
+            expect_deal_settled(rt, deal_id, from_epoch, to_epoch);
+        }
+        if terminated_deals.contains(&deal_id) {
+            let deal = get_deal_proposal(rt, deal_id);
+            expect_emitted(
+                rt,
+                "deal-terminated",
+                deal_id,
+                deal.client.id().unwrap(),
+                deal.provider.id().unwrap(),
+            );
+        }
+        if completed_deals.contains(&deal_id) {
+            let deal = get_deal_proposal(rt, deal_id);
+            expect_emitted(
+                rt,
+                "deal-completed",
+                deal_id,
+                deal.client.id().unwrap(),
+                deal.provider.id().unwrap(),
+            );
+        }
     }
 
     let res =
@@ -1440,6 +1643,57 @@ pub fn generate_and_publish_deal_for_piece(
     deal_ids[0]
 }
 
+pub fn set_publishing_paused(rt: &MockRuntime, paused: bool) {
+    rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+    rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+    let ret = rt
+        .call::<MarketActor>(
+            Method::SetPublishingPaused as u64,
+            IpldBlock::serialize_cbor(&SetPublishingPausedParams { paused }).unwrap(),
+        )
+        .unwrap();
+    assert!(ret.is_none());
+    rt.verify();
+    let st: State = rt.get_state();
+    assert_eq!(paused, st.publishing_paused);
+}
+
+pub fn set_deal_policy(
+    rt: &MockRuntime,
+    addrs: &MinerAddresses,
+    caller: Address,
+    policy: DealPolicy,
+) -> Result<(), ActorError> {
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, caller);
+    rt.expect_validate_caller_any();
+    let is_controlling =
+        caller == addrs.owner || caller == addrs.worker || addrs.control.contains(&caller);
+    expect_provider_is_control_address(rt, addrs.provider, caller, is_controlling);
+    let ret = rt.call::<MarketActor>(
+        Method::SetDealPolicyExported as u64,
+        IpldBlock::serialize_cbor(&SetDealPolicyParams { provider: addrs.provider, policy })
+            .unwrap(),
+    )?;
+    assert!(ret.is_none());
+    rt.verify();
+    Ok(())
+}
+
+pub fn get_deal_policy(rt: &MockRuntime, provider: Address) -> DealPolicy {
+    rt.expect_validate_caller_any();
+    let ret = rt
+        .call::<MarketActor>(
+            Method::GetDealPolicyExported as u64,
+            IpldBlock::serialize_cbor(&GetDealPolicyParams { provider }).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    ret
+}
+
 pub fn generate_deal_and_add_funds(
     rt: &MockRuntime,
     client: Address,
@@ -1453,6 +1707,28 @@ pub fn generate_deal_and_add_funds(
     deal
 }
 
+// Returns the proposal in the DealProposal2 format, without allocation terms or metadata.
+pub fn deal_proposal2(deal: &DealProposal) -> DealProposal2 {
+    DealProposal2 {
+        piece_cid: deal.piece_cid,
+        piece_size: deal.piece_size,
+        client: deal.client,
+        provider: deal.provider,
+        label: deal.label.clone(),
+        start_epoch: deal.start_epoch,
+        end_epoch: deal.end_epoch,
+        payment: DealPayment {
+            storage_price_per_epoch: deal.storage_price_per_epoch.clone(),
+            provider_collateral: deal.provider_collateral.clone(),
+            client_collateral: deal.client_collateral.clone(),
+            token: None,
+        },
+        allocation: None,
+        metadata: None,
+        extensions: BTreeMap::new(),
+    }
+}
+
 pub fn generate_deal_with_collateral_and_add_funds(
     rt: &MockRuntime,
     client: Address,
@@ -1550,21 +1826,24 @@ pub fn terminate_deals_and_assert_balances(
     // payment to be refunded
     let mut payment_remaining = TokenAmount::zero();
     let mut client_unlocked = TokenAmount::zero();
+    // collateral returned to the provider
+    let mut provider_unlocked = TokenAmount::zero();
 
     let curr_epoch = *rt.epoch.borrow();
     for (s, d) in &deal_infos {
-        // terminate is a no-op if deal is already expired/expiring
+        let mut payment_start = d.start_epoch;
+        if s.last_updated_epoch != EPOCH_UNDEFINED {
+            payment_start = max(s.last_updated_epoch, d.start_epoch);
+        }
+        let duration = max(0, min(curr_epoch, d.end_epoch) - payment_start);
+        total_payment += duration * &d.storage_price_per_epoch;
+        client_unlocked += &d.client_collateral;
         if curr_epoch < d.end_epoch {
-            let mut payment_start = d.start_epoch;
-            if s.last_updated_epoch != EPOCH_UNDEFINED {
-                payment_start = max(s.last_updated_epoch, d.start_epoch);
-            }
-            let duration = max(0, curr_epoch - payment_start);
-            let payment = duration * &d.storage_price_per_epoch;
-            total_payment += payment;
             payment_remaining += deal_get_payment_remaining(d, curr_epoch).unwrap();
-            client_unlocked += &d.client_collateral;
             total_slashed += &d.provider_collateral;
+        } else {
+            // expired deals are settled rather than slashed
+            provider_unlocked += &d.provider_collateral;
         }
     }
 
@@ -1576,7 +1855,7 @@ pub fn terminate_deals_and_assert_balances(
     let updated_provider_escrow = &provider_before.balance + &total_payment - &total_slashed;
     let updated_client_locked =
         &client_before.locked - &total_payment - &payment_remaining - &client_unlocked;
-    let updated_provider_locked = &provider_before.locked - &total_slashed;
+    let updated_provider_locked = &provider_before.locked - &total_slashed - &provider_unlocked;
 
     terminate_deals(rt, provider_addr, sectors, expected_terminations);
 
@@ -1687,15 +1966,35 @@ pub fn terminate_deals_raw(
     let bf = BitField::try_from_bits(sector_numbers.iter().copied()).unwrap();
     let params = OnMinerSectorsTerminateParams { epoch: *rt.epoch.borrow(), sectors: bf };
 
-    for deal_id in terminated_deals {
-        let d = get_deal_proposal(rt, *deal_id);
-        expect_emitted(
-            rt,
-            "deal-terminated",
-            *deal_id,
-            d.client.id().unwrap(),
-            d.provider.id().unwrap(),
-        )
+    // Deals are processed in sector order. Terminated deals are expected to be listed in that
+    // order too, while expired deals are settled and completed.
+    let sorted_sectors: Vec<SectorNumber> = params.sectors.iter().collect();
+    let sector_deals =
+        get_sector_deal_ids(rt, miner_addr.id().unwrap(), &sorted_sectors).unwrap_or_default();
+    for deal_id in sector_deals {
+        let Some(d) = find_deal_proposal(rt, deal_id) else {
+            continue;
+        };
+        if terminated_deals.contains(&deal_id) {
+            expect_emitted(
+                rt,
+                "deal-terminated",
+                deal_id,
+                d.client.id().unwrap(),
+                d.provider.id().unwrap(),
+            );
+        } else if d.end_epoch <= params.epoch {
+            if let Some((from, to)) = expected_settlement_epochs(rt, deal_id) {
+                expect_deal_settled(rt, deal_id, from, to);
+            }
+            expect_emitted(
+                rt,
+                "deal-completed",
+                deal_id,
+                d.client.id().unwrap(),
+                d.provider.id().unwrap(),
+            );
+        }
     }
 
     rt.call::<MarketActor>(
@@ -1782,6 +2081,41 @@ pub fn piece_info_from_deal(id: DealID, deal: &DealProposal) -> PieceChange {
     }
 }
 
+/// Returns the epochs for which an activated deal's payment will be settled at the current epoch,
+/// if there are any.
+fn expected_settlement_epochs(
+    rt: &MockRuntime,
+    deal_id: DealID,
+) -> Option<(ChainEpoch, ChainEpoch)> {
+    let st: State = rt.get_state();
+    let deal = st.find_proposal(rt.store(), deal_id).unwrap()?;
+    let state = st.find_deal_state(rt.store(), deal_id).unwrap()?;
+    let from_epoch = max(deal.start_epoch, state.last_updated_epoch);
+    let to_epoch = min(deal.end_epoch, *rt.epoch.borrow());
+    (from_epoch < to_epoch).then_some((from_epoch, to_epoch))
+}
+
+pub fn expect_deal_settled(
+    rt: &MockRuntime,
+    deal_id: DealID,
+    from_epoch: ChainEpoch,
+    to_epoch: ChainEpoch,
+) {
+    let deal = get_deal_proposal(rt, deal_id);
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("deal-settled")
+            .field_indexed("id", &deal_id)
+            .field_indexed("client", &deal.client.id().unwrap())
+            .field_indexed("provider", &deal.provider.id().unwrap())
+            .field("amount", &(&deal.storage_price_per_epoch * (to_epoch - from_epoch)))
+            .field("from-epoch", &from_epoch)
+            .field("to-epoch", &to_epoch)
+            .build()
+            .unwrap(),
+    );
+}
+
 pub fn expect_emitted(rt: &MockRuntime, typ: &str, id: DealID, client: ActorID, provider: ActorID) {
     rt.expect_emitted_event(
         EventBuilder::new()
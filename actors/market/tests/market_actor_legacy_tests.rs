@@ -57,6 +57,7 @@ fn slash_a_deal_and_make_payment_for_another_deal_in_the_same_epoch() {
     let slash_epoch = process_epoch(start_epoch, deal_id2) + ChainEpoch::from(100);
     rt.set_epoch(slash_epoch);
     terminate_deals(&rt, PROVIDER_ADDR, &[sector_1], &[deal_id1]);
+    expect_deal_settled(&rt, deal_id2, start_epoch + 1, slash_epoch);
     cron_tick(&rt);
 
     assert_deal_deleted(&rt, deal_id1, &d1, sector_1, true);
@@ -124,7 +125,8 @@ fn settling_deal_fails_when_deal_update_epoch_is_in_the_future() {
 
     // move the current epoch such that the deal's last updated field is set to the start epoch of the deal
     // and the next tick for it is scheduled at the endepoch.
-    rt.set_epoch(process_epoch(start_epoch, deal_id));
+    let current = rt.set_epoch(process_epoch(start_epoch, deal_id));
+    expect_deal_settled(&rt, deal_id, start_epoch, current);
     cron_tick(&rt);
 
     // update last updated to some time in the future (breaks state invariants)
@@ -173,6 +175,7 @@ fn cron_reschedules_update_to_new_period() {
     rt.replace_state(&st);
 
     let curr_epoch = rt.set_epoch(misscheduled_epoch);
+    expect_deal_settled(&rt, deal_id, start_epoch, curr_epoch);
     cron_tick(&rt);
 
     let st: State = rt.get_state();
@@ -215,6 +218,7 @@ fn cron_reschedules_update_to_new_period_boundary() {
     rt.replace_state(&st);
 
     let curr_epoch = rt.set_epoch(misscheduled_epoch);
+    expect_deal_settled(&rt, deal_id, start_epoch, curr_epoch);
     cron_tick(&rt);
 
     let st: State = rt.get_state();
@@ -273,6 +277,10 @@ fn cron_reschedules_many_updates() {
 
     for epoch in (start_epoch + 1)..(start_epoch + update_interval) {
         rt.set_epoch(epoch);
+        let st: State = rt.get_state();
+        for deal_id in st.get_deals_for_epoch(rt.store(), epoch).unwrap() {
+            expect_deal_settled(&rt, deal_id, start_epoch, epoch);
+        }
         cron_tick(&rt);
         let st: State = rt.get_state();
         assert_eq!(2, st.get_deals_for_epoch(rt.store(), epoch + update_interval).unwrap().len());
@@ -356,6 +364,8 @@ fn locked_fund_tracking_states() {
         None,
         ExitCode::OK,
     );
+    expect_deal_settled(&rt, deal_id1, start_epoch, curr);
+    expect_deal_settled(&rt, deal_id2, start_epoch, curr);
     cron_tick(&rt);
     let duration = curr - start_epoch;
     let payment: TokenAmount = 2 * &d1.storage_price_per_epoch * duration;
@@ -374,6 +384,8 @@ fn locked_fund_tracking_states() {
     let duration = curr - last_payment_epoch;
     let payment = 2 * d1.storage_price_per_epoch * duration;
     csf -= payment;
+    expect_deal_settled(&rt, deal_id1, last_payment_epoch, curr);
+    expect_deal_settled(&rt, deal_id2, last_payment_epoch, curr);
     cron_tick(&rt);
     assert_locked_fund_states(&rt, csf.clone(), plc.clone(), clc.clone());
 
@@ -387,6 +399,7 @@ fn locked_fund_tracking_states() {
     clc = TokenAmount::zero();
     plc = TokenAmount::zero();
 
+    expect_deal_settled(&rt, deal_id2, curr, end_epoch);
     expect_emitted(&rt, "deal-completed", deal_id2, d2.client.id().unwrap(), p2.id().unwrap());
 
     cron_tick(&rt);
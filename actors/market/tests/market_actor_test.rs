@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::ops::Add;
 
 use frc46_token::token::types::{TransferFromParams, TransferFromReturn};
@@ -27,10 +28,12 @@ use fil_actor_market::ext::verifreg::{AllocationRequest, AllocationsResponse};
 use fil_actor_market::policy::detail::DEAL_MAX_LABEL_SIZE;
 use fil_actor_market::{
     Actor as MarketActor, BatchActivateDealsResult, ClientDealProposal, DEAL_OPS_BY_EPOCH_CONFIG,
-    DealArray, DealMetaArray, DealOpsByEpoch, EX_DEAL_EXPIRED, Label, MARKET_NOTIFY_DEAL_METHOD,
-    MarketNotifyDealParams, Method, PENDING_ALLOCATIONS_CONFIG, PENDING_PROPOSALS_CONFIG,
-    PROPOSALS_AMT_BITWIDTH, PendingDealAllocationsMap, PendingProposalsSet,
-    PublishStorageDealsParams, PublishStorageDealsReturn, STATES_AMT_BITWIDTH, SectorDeals, State,
+    DealAllocationTerms, DealArray, DealMetaArray, DealOpsByEpoch, DealPayment, DealPolicy,
+    DealProposal, DealProposal2, EX_DEAL_EXPIRED, Label, MARKET_NOTIFY_DEAL_METHOD,
+    MAX_PUBLISH_FEE_EXTENSION, MarketNotifyDealParams, Method, NO_ALLOCATION_ID,
+    PENDING_ALLOCATIONS_CONFIG, PENDING_PROPOSALS_CONFIG, PROPOSALS_AMT_BITWIDTH,
+    PendingDealAllocationsMap, PendingProposalsSet, PublishStorageDealsParams,
+    PublishStorageDealsReturn, STATES_AMT_BITWIDTH, SectorDeals, State, TokenPayment,
     WithdrawBalanceParams, ext,
 };
 use fil_actors_runtime::cbor::{deserialize, serialize};
@@ -38,8 +41,8 @@ use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::runtime::{Policy, Runtime};
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
-    ActorError, BURNT_FUNDS_ACTOR_ADDR, BatchReturn, DATACAP_TOKEN_ACTOR_ADDR, DEFAULT_HAMT_CONFIG,
-    SYSTEM_ACTOR_ADDR, SetMultimap, SetMultimapConfig, VERIFIED_REGISTRY_ACTOR_ADDR,
+    ActorError, BURNT_FUNDS_ACTOR_ADDR, BatchReturn, DEFAULT_HAMT_CONFIG, SYSTEM_ACTOR_ADDR,
+    SetMultimap, SetMultimapConfig, VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use harness::*;
 
@@ -777,6 +780,106 @@ fn deal_expires() {
     check_state(&rt);
 }
 
+#[test]
+fn publish_deals2_with_explicit_allocation_terms() {
+    let start_epoch = 50;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let rt = setup();
+    rt.set_epoch(start_epoch - 10);
+    let next_allocation_id = 1;
+
+    let addrs = MinerAddresses::default();
+    let deal1 = generate_deal_and_add_funds(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch);
+    let deal2 = generate_deal_and_add_funds(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch - 1);
+
+    // A verified deal with allocation terms other than the market's defaults.
+    let terms = DealAllocationTerms {
+        term_min: deal1.duration(),
+        term_max: deal1.duration() + 100 * EPOCHS_IN_DAY,
+        expiration: start_epoch - 1,
+    };
+    let verified = DealProposal2 { allocation: Some(terms), ..deal_proposal2(&deal1) };
+    // An unverified deal with an extension field unknown to the market.
+    let unverified = DealProposal2 {
+        extensions: BTreeMap::from([("future-field".to_string(), RawBytes::new(vec![1, 2, 3]))]),
+        ..deal_proposal2(&deal2)
+    };
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    let ids = publish_deals2(
+        &rt,
+        &addrs,
+        &[verified.clone(), unverified.clone()],
+        TokenAmount::from_whole(verified.piece_size.0),
+        next_allocation_id,
+        PublishDeals2Config::default(),
+    );
+
+    // Proposals are recorded in state in the original format.
+    let proposal1 = get_deal_proposal(&rt, ids[0]);
+    assert_eq!(DealProposal { verified_deal: true, ..deal1 }, proposal1);
+    assert_eq!(next_allocation_id, get_pending_deal_allocation(&rt, ids[0]));
+    let proposal2 = get_deal_proposal(&rt, ids[1]);
+    assert_eq!(deal2, proposal2);
+    check_state(&rt);
+}
+
+#[test]
+fn publish_deals2_drops_deals_with_rejected_terms_or_failed_token_payment() {
+    let start_epoch = 50;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let rt = setup();
+    rt.set_epoch(start_epoch - 10);
+
+    let addrs = MinerAddresses::default();
+    let token = Address::new_id(5000);
+    let mut proposals = Vec::new();
+    for i in 0..3 {
+        let deal =
+            generate_deal_and_add_funds(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch - i);
+        proposals.push(DealProposal2 {
+            piece_cid: deal.piece_cid,
+            piece_size: deal.piece_size,
+            client: deal.client,
+            provider: deal.provider,
+            label: deal.label.clone(),
+            start_epoch: deal.start_epoch,
+            end_epoch: deal.end_epoch,
+            payment: DealPayment {
+                storage_price_per_epoch: deal.storage_price_per_epoch.clone(),
+                provider_collateral: deal.provider_collateral.clone(),
+                client_collateral: deal.client_collateral.clone(),
+                token: Some(TokenPayment { token, amount: TokenAmount::from_atto(100 + i) }),
+            },
+            allocation: None,
+            metadata: None,
+            extensions: BTreeMap::new(),
+        });
+    }
+    // The verified registry rejects the first deal's allocation terms,
+    // whose allocation would expire before it is made.
+    proposals[0].allocation = Some(DealAllocationTerms {
+        term_min: proposals[0].end_epoch - start_epoch,
+        term_max: proposals[0].end_epoch - start_epoch,
+        expiration: start_epoch - 20,
+    });
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    let ids = publish_deals2(
+        &rt,
+        &addrs,
+        &proposals,
+        TokenAmount::zero(),
+        1,
+        PublishDeals2Config { rejected_allocations: vec![0], failed_token_payments: vec![1] },
+    );
+
+    // Only the deal whose token payment was made is published.
+    assert_eq!(1, ids.len());
+    assert_eq!(proposals[2].to_proposal(), get_deal_proposal(&rt, ids[0]));
+    check_state(&rt);
+}
+
 #[test]
 fn provider_and_client_addresses_are_resolved_before_persisting_state_and_sent_to_verigreg_actor_for_a_verified_deal()
  {
@@ -889,16 +992,7 @@ fn provider_and_client_addresses_are_resolved_before_persisting_state_and_sent_t
         }],
         extensions: vec![],
     };
-    let balance_of_params = client_resolved;
-    let balance_of_return = TokenAmount::from_whole(2048);
-    rt.expect_send_simple(
-        DATACAP_TOKEN_ACTOR_ADDR,
-        ext::datacap::BALANCE_OF_METHOD,
-        IpldBlock::serialize_cbor(&balance_of_params).unwrap(),
-        TokenAmount::zero(),
-        IpldBlock::serialize_cbor(&balance_of_return).unwrap(),
-        ExitCode::OK,
-    );
+    rt.expect_datacap_balance(client_resolved, TokenAmount::from_whole(2048));
 
     let datacap_amount = TokenAmount::from_whole(deal.piece_size.0 as i64);
     let transfer_params = TransferFromParams {
@@ -921,14 +1015,7 @@ fn provider_and_client_addresses_are_resolved_before_persisting_state_and_sent_t
         )
         .unwrap(),
     };
-    rt.expect_send_simple(
-        DATACAP_TOKEN_ACTOR_ADDR,
-        ext::datacap::TRANSFER_FROM_METHOD,
-        IpldBlock::serialize_cbor(&transfer_params).unwrap(),
-        TokenAmount::zero(),
-        IpldBlock::serialize_cbor(&transfer_return).unwrap(),
-        ExitCode::OK,
-    );
+    rt.expect_datacap_transfer_from(transfer_params, transfer_return);
     let mut normalized_deal = deal;
     normalized_deal.provider = provider_resolved;
     normalized_deal.client = client_resolved;
@@ -2286,3 +2373,82 @@ fn psd_restricted_correctly() {
     rt.verify();
     check_state(&rt);
 }
+
+#[test]
+fn publish_deal_pays_provider_publish_fee_up_to_client_maximum() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let rt = setup();
+    rt.set_epoch(1);
+    let addrs = MinerAddresses::default();
+    assert_eq!(DealPolicy::default(), get_deal_policy(&rt, addrs.provider));
+
+    let policy = DealPolicy { publish_fee: TokenAmount::from_atto(1234) };
+    set_deal_policy(&rt, &addrs, addrs.worker, policy.clone()).unwrap();
+    assert_eq!(policy, get_deal_policy(&rt, addrs.provider));
+
+    // A deal whose client didn't agree to a publish fee is dropped.
+    let deal = generate_deal_and_add_funds(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch);
+    add_participant_funds(&rt, CLIENT_ADDR, policy.publish_fee.clone());
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, addrs.worker);
+    publish_deals_expect_abort(&rt, &addrs, deal.clone(), ExitCode::USR_ILLEGAL_ARGUMENT);
+
+    // The client signs the maximum fee it agrees to pay.
+    let mut deal2 = deal_proposal2(&deal);
+    deal2.extensions.insert(
+        MAX_PUBLISH_FEE_EXTENSION.to_string(),
+        RawBytes::serialize(&policy.publish_fee).unwrap(),
+    );
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, addrs.worker);
+    publish_deals2(
+        &rt,
+        &addrs,
+        &[deal2],
+        TokenAmount::zero(),
+        NO_ALLOCATION_ID,
+        PublishDeals2Config::default(),
+    );
+
+    // The fee moved from the client's escrow to the provider's.
+    let client = get_balance(&rt, &CLIENT_ADDR);
+    assert_eq!(deal.client_balance_requirement(), client.balance);
+    assert_eq!(deal.client_balance_requirement(), client.locked);
+    let provider = get_balance(&rt, &addrs.provider);
+    assert_eq!(&deal.provider_collateral + &policy.publish_fee, provider.balance);
+    assert_eq!(deal.provider_collateral, provider.locked);
+
+    // Restoring the default policy removes the fee.
+    set_deal_policy(&rt, &addrs, addrs.owner, DealPolicy::default()).unwrap();
+    assert_eq!(DealPolicy::default(), get_deal_policy(&rt, addrs.provider));
+    check_state(&rt);
+}
+
+#[test]
+fn set_deal_policy_restricted_correctly() {
+    let rt = setup();
+    let addrs = MinerAddresses::default();
+
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        set_deal_policy(
+            &rt,
+            &addrs,
+            CLIENT_ADDR,
+            DealPolicy { publish_fee: TokenAmount::from_atto(1) },
+        ),
+    );
+    rt.reset();
+
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        set_deal_policy(
+            &rt,
+            &addrs,
+            addrs.worker,
+            DealPolicy { publish_fee: TokenAmount::from_atto(-1) },
+        ),
+    );
+    rt.reset();
+    assert_eq!(DealPolicy::default(), get_deal_policy(&rt, addrs.provider));
+    check_state(&rt);
+}
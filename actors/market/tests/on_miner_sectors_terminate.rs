@@ -160,7 +160,7 @@ fn terminate_valid_deals_along_with_just_expired_deal() {
         start_epoch,
         end_epoch + 1,
     );
-    let (id2, _deal2) = generate_and_publish_deal(
+    let (id2, deal2) = generate_and_publish_deal(
         &rt,
         CLIENT_ADDR,
         &MinerAddresses::default(),
@@ -186,13 +186,12 @@ fn terminate_valid_deals_along_with_just_expired_deal() {
         CLIENT_ADDR,
         PROVIDER_ADDR,
         &[sector_number],
-        // Deal2 isn't terminated (or cleaned up) because it expired and is waiting for settlement.
+        // Deal2 isn't terminated because it expired, but is settled and cleaned up.
         &[id0, id1],
     );
     assert_deal_deleted(&rt, id0, &deal0, sector_number, true);
     assert_deal_deleted(&rt, id1, &deal1, sector_number, true);
-    // Deal2 state still exists, and isn't terminated.
-    assert_deals_not_marked_terminated(&rt, &[id2]);
+    assert_deal_deleted(&rt, id2, &deal2, sector_number, true);
     // All deals are removed from sector deals mapping at once.
     assert!(get_sector_deal_ids(&rt, PROVIDER_ID, &[sector_number]).is_none());
     check_state(&rt);
@@ -244,6 +243,8 @@ fn terminate_valid_deals_along_with_expired_and_cleaned_up_deal() {
 
     let new_epoch = end_epoch - 1;
     rt.set_epoch(new_epoch);
+    expect_deal_settled(&rt, deal_ids[0], start_epoch, new_epoch);
+    expect_deal_settled(&rt, deal_ids[1], start_epoch, deal2.end_epoch);
     expect_emitted(
         &rt,
         "deal-completed",
@@ -283,7 +284,7 @@ fn do_not_terminate_deal_if_end_epoch_is_equal_to_or_less_than_current_epoch() {
     rt.set_epoch(current_epoch);
 
     // deal1 has endepoch equal to current epoch when terminate is called
-    let (deal1, _) = generate_and_publish_deal(
+    let (deal1, proposal1) = generate_and_publish_deal(
         &rt,
         CLIENT_ADDR,
         &MinerAddresses::default(),
@@ -302,11 +303,11 @@ fn do_not_terminate_deal_if_end_epoch_is_equal_to_or_less_than_current_epoch() {
     assert!(ret.activation_results.all_ok());
     rt.set_epoch(end_epoch);
     terminate_deals_and_assert_balances(&rt, CLIENT_ADDR, PROVIDER_ADDR, &[sector_number], &[]);
-    assert_deals_not_marked_terminated(&rt, &[deal1]);
+    assert_deal_deleted(&rt, deal1, &proposal1, sector_number, true);
 
     // deal2 has end epoch less than current epoch when terminate is called
     rt.set_epoch(current_epoch);
-    let (deal2, _) = generate_and_publish_deal(
+    let (deal2, proposal2) = generate_and_publish_deal(
         &rt,
         CLIENT_ADDR,
         &MinerAddresses::default(),
@@ -325,8 +326,61 @@ fn do_not_terminate_deal_if_end_epoch_is_equal_to_or_less_than_current_epoch() {
     assert!(ret.activation_results.all_ok());
     rt.set_epoch(end_epoch + 1);
     terminate_deals_and_assert_balances(&rt, CLIENT_ADDR, PROVIDER_ADDR, &[sector_number], &[]);
-    assert_deals_not_marked_terminated(&rt, &[deal2]);
+    assert_deal_deleted(&rt, deal2, &proposal2, sector_number, true);
+
+    check_state(&rt);
+}
+
+#[test]
+fn persist_expired_deal_settled_only_to_current_epoch() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let sector_expiry = end_epoch + 100;
+    let current_epoch = 5;
+
+    let rt = setup();
+    rt.set_epoch(current_epoch);
 
+    let (deal_id, _) = generate_and_publish_deal(
+        &rt,
+        CLIENT_ADDR,
+        &MinerAddresses::default(),
+        start_epoch,
+        end_epoch,
+    );
+    let sector_number = 7;
+    let ret = activate_deals_legacy(
+        &rt,
+        sector_expiry,
+        PROVIDER_ADDR,
+        current_epoch,
+        sector_number,
+        &[deal_id],
+    );
+    assert!(ret.activation_results.all_ok());
+
+    // The termination is effective at the deal's end, after the current epoch, so the deal
+    // can be settled only up to the current epoch.
+    let settle_epoch = end_epoch - 100;
+    rt.set_epoch(settle_epoch);
+    rt.set_caller(*MINER_ACTOR_CODE_ID, PROVIDER_ADDR);
+    rt.expect_validate_caller_type(vec![Type::Miner]);
+    expect_deal_settled(&rt, deal_id, start_epoch, settle_epoch);
+    let params = OnMinerSectorsTerminateParams {
+        epoch: end_epoch,
+        sectors: BitField::try_from_bits([sector_number]).unwrap(),
+    };
+    rt.call::<MarketActor>(
+        Method::OnMinerSectorsTerminate as u64,
+        IpldBlock::serialize_cbor(&params).unwrap(),
+    )
+    .unwrap();
+    rt.verify();
+
+    // The deal records its settlement, so the epochs settled are not paid again,
+    // and remains indexed by its sector until settled to its end.
+    assert_eq!(settle_epoch, get_deal_state(&rt, deal_id).last_updated_epoch);
+    assert_eq!(vec![deal_id], get_sector_deal_ids(&rt, PROVIDER_ID, &[sector_number]).unwrap());
     check_state(&rt);
 }
 
@@ -3,10 +3,11 @@
 
 use fil_actor_market::policy::deal_provider_collateral_bounds;
 use fil_actor_market::{
-    Actor as MarketActor, ClientDealProposal, DealProposal, MARKET_NOTIFY_DEAL_METHOD,
-    MarketNotifyDealParams, Method, PublishStorageDealsParams, PublishStorageDealsReturn, State,
-    ext,
+    Actor as MarketActor, ClientDealProposal, DealProposal, EX_DEAL_PUBLISHING_PAUSED,
+    MARKET_NOTIFY_DEAL_METHOD, MarketNotifyDealParams, Method, NO_ALLOCATION_ID,
+    PublishStorageDealsParams, PublishStorageDealsReturn, SetPublishingPausedParams, State, ext,
 };
+use fil_actors_runtime::SYSTEM_ACTOR_ADDR;
 use fil_actors_runtime::network::EPOCHS_IN_DAY;
 use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::test_utils::*;
@@ -569,3 +570,67 @@ fn fails_if_notify_deal_fails() {
     rt.verify();
     check_state(&rt);
 }
+
+#[test]
+fn fail_when_publishing_paused() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let sector_expiry = end_epoch + 100;
+    let addrs = MinerAddresses::default();
+
+    let rt = setup();
+    let (activated_id, _) = publish_and_activate_deal(
+        &rt,
+        CLIENT_ADDR,
+        &addrs,
+        1,
+        start_epoch,
+        end_epoch,
+        0,
+        sector_expiry,
+    );
+
+    // only governance may pause publishing
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        rt.call::<MarketActor>(
+            Method::SetPublishingPaused as u64,
+            IpldBlock::serialize_cbor(&SetPublishingPausedParams { paused: true }).unwrap(),
+        ),
+    );
+    rt.verify();
+
+    set_publishing_paused(&rt, true);
+
+    let later_start = start_epoch + 2 * EPOCHS_IN_DAY;
+    let deal = generate_deal_and_add_funds(&rt, CLIENT_ADDR, &addrs, later_start, end_epoch);
+    let buf = RawBytes::serialize(deal.clone()).expect("failed to marshal deal proposal");
+    let params = PublishStorageDealsParams {
+        deals: vec![ClientDealProposal {
+            proposal: deal.clone(),
+            client_signature: Signature::new_bls(buf.to_vec()),
+        }],
+    };
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    rt.expect_validate_caller_any();
+    expect_abort(
+        EX_DEAL_PUBLISHING_PAUSED,
+        rt.call::<MarketActor>(
+            Method::PublishStorageDeals as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        ),
+    );
+    rt.verify();
+
+    // existing deals continue to settle while publishing is paused
+    rt.set_epoch(start_epoch + EPOCHS_IN_DAY);
+    settle_deal_payments(&rt, addrs.provider, &[activated_id], &[], &[]);
+
+    // publishing resumes once unpaused
+    set_publishing_paused(&rt, false);
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, WORKER_ADDR);
+    publish_deals(&rt, &addrs, &[deal], TokenAmount::zero(), NO_ALLOCATION_ID);
+    check_state(&rt);
+}
@@ -0,0 +1,127 @@
+use fil_actor_market::policy::{
+    MAX_COLLATERAL_RECOMPUTATION_DEALS, deal_provider_collateral_bounds,
+};
+use fil_actor_market::{
+    Actor as MarketActor, EX_DEAL_EXPIRED, Method, NO_ALLOCATION_ID,
+    RecomputeProviderCollateralParams, RecomputeProviderCollateralReturn,
+};
+use fil_actors_runtime::network::EPOCHS_IN_DAY;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_utils::*;
+use fil_actors_runtime::{ActorError, BatchReturn};
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::deal::DealID;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::sector::StoragePower;
+use num_traits::Zero;
+
+mod harness;
+
+use harness::*;
+
+fn recompute_provider_collateral(
+    rt: &MockRuntime,
+    provider: u64,
+    deal_ids: Vec<DealID>,
+) -> Result<RecomputeProviderCollateralReturn, ActorError> {
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, CLIENT_ADDR);
+    rt.expect_validate_caller_any();
+    let params = RecomputeProviderCollateralParams { provider, deal_ids };
+    let ret = rt.call::<MarketActor>(
+        Method::RecomputeProviderCollateralExported as u64,
+        IpldBlock::serialize_cbor(&params).unwrap(),
+    );
+    rt.verify();
+    ret.map(|r| r.unwrap().deserialize().unwrap())
+}
+
+#[test]
+fn reports_deals_below_current_minimum_collateral() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let rt = setup();
+    rt.set_epoch(5);
+    let addrs = MinerAddresses::default();
+
+    // Deals are published while the circulating supply, and so the minimum collateral, is zero.
+    let (low, low_proposal) =
+        generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch);
+    let high_proposal = generate_deal_with_collateral_and_add_funds(
+        &rt,
+        CLIENT_ADDR,
+        &addrs,
+        TokenAmount::from_whole(1),
+        TokenAmount::zero(),
+        start_epoch,
+        end_epoch + 1,
+    );
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, addrs.worker);
+    let high =
+        publish_deals(&rt, &addrs, &[high_proposal], TokenAmount::zero(), NO_ALLOCATION_ID)[0];
+
+    let circulating_supply = TokenAmount::from_whole(1_000_000_000);
+    rt.set_circulating_supply(circulating_supply.clone());
+    // As reported by expect_query_network_info.
+    let (required, _) = deal_provider_collateral_bounds(
+        &Policy::default(),
+        low_proposal.piece_size,
+        &StoragePower::zero(),
+        &StoragePower::from(1i64 << 50),
+        &circulating_supply,
+    );
+    assert!(low_proposal.provider_collateral < required);
+    assert!(TokenAmount::from_whole(1) > required);
+
+    expect_query_network_info(&rt);
+    let ret = recompute_provider_collateral(&rt, PROVIDER_ID, vec![low, high]).unwrap();
+    assert_eq!(BatchReturn::of(&[ExitCode::OK, ExitCode::OK]), ret.results);
+    assert_eq!(vec![low], ret.under_collateralized);
+    assert_eq!(&required - &low_proposal.provider_collateral, ret.top_up_required);
+    check_state(&rt);
+}
+
+#[test]
+fn skips_unknown_foreign_and_ended_deals() {
+    let start_epoch = 10;
+    let end_epoch = start_epoch + 200 * EPOCHS_IN_DAY;
+    let sector_expiry = end_epoch + 100;
+    let current_epoch = 5;
+    let rt = setup();
+    rt.set_epoch(current_epoch);
+    let addrs = MinerAddresses::default();
+
+    let (ending, _) = generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch);
+    let (ongoing, _) =
+        generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch + 1);
+    let (unactivated, _) =
+        generate_and_publish_deal(&rt, CLIENT_ADDR, &addrs, start_epoch, end_epoch + 2);
+    activate_deals(&rt, sector_expiry, PROVIDER_ADDR, current_epoch, 1, &[ending, ongoing]);
+    rt.set_epoch(end_epoch);
+
+    expect_query_network_info(&rt);
+    let ret =
+        recompute_provider_collateral(&rt, PROVIDER_ID, vec![ongoing, ending, unactivated, 999])
+            .unwrap();
+    assert_eq!(
+        vec![ExitCode::OK, EX_DEAL_EXPIRED, EX_DEAL_EXPIRED, ExitCode::USR_NOT_FOUND],
+        ret.results.codes()
+    );
+    assert!(ret.under_collateralized.is_empty());
+    assert!(ret.top_up_required.is_zero());
+
+    expect_query_network_info(&rt);
+    let ret = recompute_provider_collateral(&rt, CLIENT_ID, vec![ongoing]).unwrap();
+    assert_eq!(vec![ExitCode::USR_ILLEGAL_ARGUMENT], ret.results.codes());
+
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        recompute_provider_collateral(
+            &rt,
+            PROVIDER_ID,
+            vec![ongoing; MAX_COLLATERAL_RECOMPUTATION_DEALS + 1],
+        ),
+    );
+    rt.reset();
+    check_state(&rt);
+}
@@ -89,6 +89,8 @@ fn can_manually_settle_deals_in_the_cron_queue() {
     assert_eq!(&client_updated.balance, &incremental_client_escrow);
     assert_eq!(&provider_updated.balance, &incremental_provider_escrow);
 
+    // cron settles the remaining payment, from the epoch of the manual settlement
+    expect_deal_settled(&rt, deal_id, START_EPOCH + 100, END_EPOCH);
     expect_emitted(
         &rt,
         "deal-completed",
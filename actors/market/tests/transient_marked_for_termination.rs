@@ -92,6 +92,8 @@ fn deal_scheduled_for_termination_cannot_be_settled_manually() {
         None,
         ExitCode::OK,
     );
+    // payment is made up to the slash epoch
+    expect_deal_settled(&rt, slashed_deal, START_EPOCH, slashed_epoch);
     expect_emitted(
         &rt,
         "deal-terminated",
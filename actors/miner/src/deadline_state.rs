@@ -10,6 +10,7 @@ use fvm_ipld_bitfield::BitField;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::CborStore;
 use fvm_ipld_encoding::tuple::*;
+use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
@@ -175,7 +176,7 @@ pub struct Deadline {
     pub daily_fee: TokenAmount,
 }
 
-#[derive(Serialize_tuple, Deserialize_tuple, Clone)]
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq)]
 pub struct WindowedPoSt {
     // Partitions proved by this WindowedPoSt.
     pub partitions: BitField,
@@ -185,6 +186,17 @@ pub struct WindowedPoSt {
     // this array will always have a single element (independent of number
     // of partitions).
     pub proofs: Vec<PoStProof>,
+
+    // The address (ID) of the account that submitted the proofs.
+    //
+    // This field and the next are not included in the serialised form of proofs recorded before
+    // they were introduced, and are absent for those proofs.
+    #[serde(default)]
+    pub prover: Option<Address>,
+
+    // The epoch at which the proofs were optimistically accepted.
+    #[serde(default)]
+    pub epoch: Option<ChainEpoch>,
 }
 
 #[derive(Serialize_tuple, Deserialize_tuple)]
@@ -668,6 +680,45 @@ impl Deadline {
         Ok(power_lost)
     }
 
+    /// Selects partitions whose live sectors may be merged by compaction: those with no faulty
+    /// or unproven sectors and fewer live sectors than a full partition.
+    /// Returns an empty set if compacting the candidates would neither reduce the number of
+    /// partitions nor remove any terminated sectors.
+    /// At most `max_partitions` partitions are selected.
+    pub fn underfilled_partitions<BS: Blockstore>(
+        &self,
+        store: &BS,
+        partition_size: u64,
+        max_partitions: u64,
+    ) -> anyhow::Result<BitField> {
+        let partitions = self.partitions_amt(store)?;
+
+        let mut selected = Vec::<u64>::new();
+        let mut live_count = 0u64;
+        let mut has_dead = false;
+        partitions.for_each_while(|partition_idx, partition| {
+            if selected.len() as u64 >= max_partitions {
+                return Ok(false);
+            }
+            if !partition.faults.is_empty() || !partition.unproven.is_empty() {
+                return Ok(true);
+            }
+            let live = partition.live_sectors().len();
+            if live < partition_size {
+                selected.push(partition_idx);
+                live_count += live;
+                has_dead |= !partition.terminated.is_empty();
+            }
+            Ok(true)
+        })?;
+
+        let merged_count = live_count.div_ceil(partition_size);
+        if !has_dead && merged_count >= selected.len() as u64 {
+            return Ok(BitField::new());
+        }
+        Ok(BitField::try_from_bits(selected)?)
+    }
+
     /// RemovePartitions removes the specified partitions, shifting the remaining
     /// ones to the left, and returning the dead sectors they contained.
     ///
@@ -1328,12 +1379,15 @@ impl Deadline {
     }
 
     // RecordPoStProofs records a set of optimistically accepted PoSt proofs
-    // (usually one), associating them with the given partitions.
+    // (usually one), associating them with the given partitions, along with
+    // the prover and epoch of acceptance.
     pub fn record_post_proofs<BS: Blockstore>(
         &mut self,
         store: &BS,
         partitions: &BitField,
         proofs: &[PoStProof],
+        prover: Address,
+        epoch: ChainEpoch,
     ) -> anyhow::Result<()> {
         let mut proof_arr = self
             .optimistic_proofs_amt(store)
@@ -1342,7 +1396,12 @@ impl Deadline {
             .set(
                 proof_arr.count(),
                 // TODO: Can we do this with out cloning?
-                WindowedPoSt { partitions: partitions.clone(), proofs: proofs.to_vec() },
+                WindowedPoSt {
+                    partitions: partitions.clone(),
+                    proofs: proofs.to_vec(),
+                    prover: Some(prover),
+                    epoch: Some(epoch),
+                },
             )
             .map_err(|e| e.downcast_wrap("failed to store proof"))?;
         let root = proof_arr.flush().map_err(|e| e.downcast_wrap("failed to save proofs"))?;
@@ -1373,6 +1432,25 @@ impl Deadline {
         Ok((post.partitions, post.proofs))
     }
 
+    /// Returns the proofs remaining in the PoSt submissions snapshot, which may be disputed,
+    /// with their indexes in the snapshot.
+    pub fn disputable_post_proofs<BS: Blockstore>(
+        &self,
+        store: &BS,
+    ) -> anyhow::Result<Vec<(u64, WindowedPoSt)>> {
+        let proof_arr = self
+            .optimistic_proofs_snapshot_amt(store)
+            .map_err(|e| e.downcast_wrap("failed to load post proofs snapshot amt"))?;
+        let mut proofs = Vec::new();
+        proof_arr
+            .for_each(|idx, post| {
+                proofs.push((idx, post.clone()));
+                Ok(())
+            })
+            .map_err(|e| e.downcast_wrap("failed to iterate post proofs snapshot"))?;
+        Ok(proofs)
+    }
+
     /// RescheduleSectorExpirations reschedules the expirations of the given sectors
     /// to the target epoch, skipping any sectors it can't find.
     ///
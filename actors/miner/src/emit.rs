@@ -1,13 +1,27 @@
 use cid::Cid;
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::ActorID;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
 use fvm_shared::sector::SectorNumber;
 
 /// Indicates a sector has been pre-committed.
 pub fn sector_precommitted(rt: &impl Runtime, sector: SectorNumber) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new().typ("sector-precommitted").field_indexed("sector", &sector).build()?,
-    )
+    EventBuilder::new().typ("sector-precommitted").field_indexed("sector", &sector).emit(rt)
+}
+
+/// Indicates a pre-committed sector has expired without being proven, and its deposit burned.
+pub fn precommit_expired(
+    rt: &impl Runtime,
+    sector: SectorNumber,
+    deposit_burned: &TokenAmount,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("precommit-expired")
+        .field_indexed("sector", &sector)
+        .field("deposit-burned", deposit_burned)
+        .emit(rt)
 }
 
 /// Indicates a sector has been activated.
@@ -17,12 +31,10 @@ pub fn sector_activated(
     unsealed_cid: Option<Cid>,
     pieces: &[(Cid, u64)],
 ) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("sector-activated")
-            .with_sector_info(sector, unsealed_cid, pieces)
-            .build()?,
-    )
+    EventBuilder::new()
+        .typ("sector-activated")
+        .with_sector_info(sector, unsealed_cid, pieces)
+        .emit(rt)
 }
 
 /// Indicates a sector has been updated.
@@ -32,19 +44,77 @@ pub fn sector_updated(
     unsealed_cid: Option<Cid>,
     pieces: &[(Cid, u64)],
 ) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("sector-updated")
-            .with_sector_info(sector, unsealed_cid, pieces)
-            .build()?,
-    )
+    EventBuilder::new()
+        .typ("sector-updated")
+        .with_sector_info(sector, unsealed_cid, pieces)
+        .emit(rt)
+}
+
+/// Indicates a sector's replica update has been reverted, dropping its data.
+pub fn sector_update_reverted(rt: &impl Runtime, sector: SectorNumber) -> Result<(), ActorError> {
+    EventBuilder::new().typ("sector-update-reverted").field_indexed("sector", &sector).emit(rt)
 }
 
 /// Indicates a sector has been terminated.
 pub fn sector_terminated(rt: &impl Runtime, sector: SectorNumber) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new().typ("sector-terminated").field_indexed("sector", &sector).build()?,
-    )
+    EventBuilder::new().typ("sector-terminated").field_indexed("sector", &sector).emit(rt)
+}
+
+/// Indicates a sector's proof was not refreshed by the epoch it was scheduled to be.
+pub fn sector_proof_expired(
+    rt: &impl Runtime,
+    sector: SectorNumber,
+    refresh_epoch: ChainEpoch,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("sector-proof-expired")
+        .field_indexed("sector", &sector)
+        .field("refresh-epoch", &refresh_epoch)
+        .emit(rt)
+}
+
+/// Indicates the epoch by which a sector's proof must be refreshed has been extended.
+pub fn proof_refresh_extended(
+    rt: &impl Runtime,
+    sector: SectorNumber,
+    refresh_epoch: ChainEpoch,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("proof-refresh-extended")
+        .field_indexed("sector", &sector)
+        .field("refresh-epoch", &refresh_epoch)
+        .emit(rt)
+}
+
+/// Indicates fee debt has been repaid, with the amount of debt repaid.
+pub fn fee_debt_repaid(
+    rt: &impl Runtime,
+    payer: ActorID,
+    repaid: &TokenAmount,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("fee-debt-repaid")
+        .field_indexed("payer", &payer)
+        .field("repaid", repaid)
+        .emit(rt)
+}
+
+/// Indicates funds have been withdrawn to a beneficiary other than the owner,
+/// with the quota remaining and the expiration of the beneficiary's term.
+pub fn beneficiary_withdrawal(
+    rt: &impl Runtime,
+    beneficiary: ActorID,
+    amount: &TokenAmount,
+    remaining_quota: &TokenAmount,
+    expiration: ChainEpoch,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("beneficiary-withdrawal")
+        .field_indexed("beneficiary", &beneficiary)
+        .field("amount", amount)
+        .field("remaining-quota", remaining_quota)
+        .field("expiration", &expiration)
+        .emit(rt)
 }
 
 trait WithSectorInfo {
@@ -131,6 +131,7 @@ pub mod verifreg {
 
     pub const GET_CLAIMS_METHOD: u64 = 10;
     pub const CLAIM_ALLOCATIONS_METHOD: u64 = 9;
+    pub const REMOVE_SECTOR_CLAIMS_METHOD: u64 = 13;
 
     pub type ClaimID = u64;
     pub type AllocationID = u64;
@@ -188,10 +189,11 @@ pub mod verifreg {
     }
 
     #[derive(Clone, Debug, PartialEq, Eq, Default, Serialize_tuple, Deserialize_tuple)]
-    #[serde(transparent)]
     pub struct SectorClaimSummary {
         #[serde(with = "bigint_ser")]
         pub claimed_space: BigInt,
+        pub term_start: ChainEpoch,
+        pub sector_expiry: ChainEpoch,
     }
 
     #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
@@ -199,4 +201,14 @@ pub mod verifreg {
         pub sector_results: BatchReturn,
         pub sector_claims: Vec<SectorClaimSummary>,
     }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+    pub struct RemoveSectorClaimsParams {
+        pub sectors: Vec<SectorNumber>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+    pub struct RemoveSectorClaimsReturn {
+        pub removed: Vec<ClaimID>,
+    }
 }
@@ -53,7 +53,7 @@ use fil_actors_runtime::runtime::policy_constants::{
 use fil_actors_runtime::runtime::{ActorCode, DomainSeparationTag, Policy, Runtime};
 use fil_actors_runtime::{
     ActorContext, ActorDowncast, ActorError, AsActorError, BURNT_FUNDS_ACTOR_ADDR, BatchReturn,
-    BatchReturnGen, DealWeight, EPOCHS_IN_DAY, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR,
+    BatchReturnGen, Cursor, DealWeight, EPOCHS_IN_DAY, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR,
     STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
     VERIFIED_REGISTRY_ACTOR_ADDR, actor_dispatch, actor_error, deserialize_block,
     extract_send_result, util,
@@ -70,7 +70,9 @@ pub use types::*;
 pub use vesting_state::*;
 
 use crate::ext::market::NO_ALLOCATION_ID;
-use crate::notifications::{ActivationNotifications, notify_data_consumers};
+use crate::notifications::{
+    ActivationNotifications, notify_data_consumers, resend_failed_notifications,
+};
 
 // The following errors are particular cases of illegal state.
 // They're not expected to ever happen, but if they do, distinguished codes can help us
@@ -143,6 +145,11 @@ pub enum Method {
     ProveCommitSectors3 = 34,
     ProveReplicaUpdates3 = 35,
     ProveCommitSectorsNI = 36,
+    DisputeWindowedPoStPartitions = 37,
+    RevertReplicaUpdate = 38,
+    ProveCommitSectors3WithSummaries = 39,
+    ProveReplicaUpdates3WithSummaries = 40,
+    ProveCommitSectorsNIWithSummaries = 41,
     // Method numbers derived from FRC-0042 standards
     ChangeWorkerAddressExported = frc42_dispatch::method_hash!("ChangeWorkerAddress"),
     ChangePeerIDExported = frc42_dispatch::method_hash!("ChangePeerID"),
@@ -162,6 +169,23 @@ pub enum Method {
     GetMultiaddrsExported = frc42_dispatch::method_hash!("GetMultiaddrs"),
     MaxTerminationFeeExported = frc42_dispatch::method_hash!("MaxTerminationFee"),
     InitialPledgeExported = frc42_dispatch::method_hash!("InitialPledge"),
+    RetryNotificationsExported = frc42_dispatch::method_hash!("RetryNotifications"),
+    FindSectorExported = frc42_dispatch::method_hash!("FindSector"),
+    GetSectorClaimsExported = frc42_dispatch::method_hash!("GetSectorClaims"),
+    CheckWindowedPoStExported = frc42_dispatch::method_hash!("CheckWindowedPoSt"),
+    GetDisputableProofsExported = frc42_dispatch::method_hash!("GetDisputableProofs"),
+    GetMinerInfoExported = frc42_dispatch::method_hash!("GetMinerInfo"),
+    GetProofRefreshEpochExported = frc42_dispatch::method_hash!("GetProofRefreshEpoch"),
+    ExtendProofRefreshExported = frc42_dispatch::method_hash!("ExtendProofRefresh"),
+    GetPartitionReportExported = frc42_dispatch::method_hash!("GetPartitionReport"),
+    ExtendSectorExpirationsByClaimsExported =
+        frc42_dispatch::method_hash!("ExtendSectorExpirationsByClaims"),
+    GetSectorInfoExported = frc42_dispatch::method_hash!("GetSectorInfo"),
+    GetSectorsInfoExported = frc42_dispatch::method_hash!("GetSectorsInfo"),
+    GetSectorCountExported = frc42_dispatch::method_hash!("GetSectorCount"),
+    GetDeadlineInfoExported = frc42_dispatch::method_hash!("GetDeadlineInfo"),
+    GetPartitionSectorsExported = frc42_dispatch::method_hash!("GetPartitionSectors"),
+    GetLockedFundsExported = frc42_dispatch::method_hash!("GetLockedFunds"),
 }
 
 pub const SECTOR_CONTENT_CHANGED: MethodNum = frc42_dispatch::method_hash!("SectorContentChanged");
@@ -246,7 +270,7 @@ impl Actor {
             worker,
             control_addresses,
             params.peer_id,
-            params.multi_addresses,
+            params.multi_addresses.into_inner(),
             params.window_post_proof_type,
         )?;
         let info_cid = rt.store().put_cbor(&info, Blake2b256).map_err(|e| {
@@ -281,6 +305,24 @@ impl Actor {
         Ok(GetOwnerReturn { owner: info.owner, proposed: info.pending_owner_address })
     }
 
+    /// Returns the miner's addresses and parameters in a single query: the owner, worker,
+    /// any pending worker change, control addresses, peer info, PoSt proof type and sector size.
+    fn get_miner_info_exported(rt: &impl Runtime) -> Result<GetMinerInfoReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        let info = get_miner_info(rt.store(), &state)?;
+        Ok(GetMinerInfoReturn {
+            owner: info.owner,
+            worker: info.worker,
+            pending_worker_key: info.pending_worker_key,
+            control_addresses: info.control_addresses,
+            peer_id: info.peer_id,
+            multi_addrs: info.multi_address,
+            window_post_proof_type: info.window_post_proof_type,
+            sector_size: info.sector_size,
+        })
+    }
+
     /// Returns whether the provided address is "controlling".
     /// The "controlling" addresses are the Owner, the Worker, and all Control Addresses.
     fn is_controlling_address(
@@ -334,6 +376,14 @@ impl Actor {
         Ok(GetVestingFundsReturn { vesting_funds })
     }
 
+    /// Returns the total of the funds vesting in this miner, which is the sum of the amounts
+    /// returned by GetVestingFunds.
+    fn get_locked_funds(rt: &impl Runtime) -> Result<GetLockedFundsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        Ok(GetLockedFundsReturn { locked_funds: state.locked_funds })
+    }
+
     /// Will ALWAYS overwrite the existing control addresses with the control addresses passed in the params.
     /// If an empty addresses vector is passed, the control addresses will be cleared.
     /// A worker change will be scheduled if the worker passed in the params is different from the existing worker.
@@ -507,7 +557,7 @@ impl Actor {
                 info.control_addresses.iter().chain(&[info.worker, info.owner]),
             )?;
 
-            info.multi_address = params.new_multi_addrs;
+            info.multi_address = params.new_multi_addrs.into_inner();
             state.save_info(rt.store(), &info).map_err(|e| {
                 e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "could not save miner info")
             })?;
@@ -522,145 +572,15 @@ impl Actor {
         rt: &impl Runtime,
         mut params: SubmitWindowedPoStParams,
     ) -> Result<(), ActorError> {
-        let current_epoch = rt.curr_epoch();
-
-        {
-            let policy = rt.policy();
-            if params.proofs.len() != 1 {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "expected exactly one proof, got {}",
-                    params.proofs.len()
-                ));
-            }
-
-            if check_valid_post_proof_type(policy, params.proofs[0].post_proof).is_err() {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "proof type {:?} not allowed",
-                    params.proofs[0].post_proof
-                ));
-            }
-
-            if params.deadline >= policy.wpost_period_deadlines {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "invalid deadline {} of {}",
-                    params.deadline,
-                    policy.wpost_period_deadlines
-                ));
-            }
-
-            if params.chain_commit_rand.0.len() > RANDOMNESS_LENGTH {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "expected at most {} bytes of randomness, got {}",
-                    RANDOMNESS_LENGTH,
-                    params.chain_commit_rand.0.len()
-                ));
-            }
-        }
+        validate_windowed_post_params(rt.policy(), &params)?;
 
         let post_result = rt.transaction(|state: &mut State, rt| {
             let info = get_miner_info(rt.store(), state)?;
 
-            let max_proof_size = info.window_post_proof_type.proof_size().map_err(|e| {
-                actor_error!(illegal_state, "failed to determine max window post proof size: {}", e)
-            })?;
-
             rt.validate_immediate_caller_is(
                 info.control_addresses.iter().chain(&[info.worker, info.owner]),
             )?;
-
-            // Make sure the miner is using the correct proof type.
-            if params.proofs[0].post_proof != info.window_post_proof_type {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "expected proof of type {:?}, got {:?}",
-                    info.window_post_proof_type,
-                    params.proofs[0].post_proof
-                ));
-            }
-
-            // Make sure the proof size doesn't exceed the max. We could probably check for an exact match, but this is safer.
-            let max_size = max_proof_size * params.partitions.len();
-            if params.proofs[0].proof_bytes.len() > max_size {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "expected proof to be smaller than {} bytes",
-                    max_size
-                ));
-            }
-
-            // Validate that the miner didn't try to prove too many partitions at once.
-            let submission_partition_limit = cmp::min(
-                load_partitions_sectors_max(rt.policy(), info.window_post_partition_sectors),
-                rt.policy().posted_partitions_max,
-            );
-
-            if params.partitions.len() as u64 > submission_partition_limit {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "too many partitions {}, limit {}",
-                    params.partitions.len(),
-                    submission_partition_limit
-                ));
-            }
-            let current_deadline = state.deadline_info(rt.policy(), current_epoch);
-
-            // Check that the miner state indicates that the current proving deadline has started.
-            // This should only fail if the cron actor wasn't invoked, and matters only in case that it hasn't been
-            // invoked for a whole proving period, and hence the missed PoSt submissions from the prior occurrence
-            // of this deadline haven't been processed yet.
-            if !current_deadline.is_open() {
-                return Err(actor_error!(
-                    illegal_state,
-                    "proving period {} not yet open at {}",
-                    current_deadline.period_start,
-                    current_epoch
-                ));
-            }
-
-            // The miner may only submit a proof for the current deadline.
-            if params.deadline != current_deadline.index {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "invalid deadline {} at epoch {}, expected {}",
-                    params.deadline,
-                    current_epoch,
-                    current_deadline.index
-                ));
-            }
-
-            // Verify that the PoSt was committed to the chain at most
-            // WPoStChallengeLookback+WPoStChallengeWindow in the past.
-            if params.chain_commit_epoch < current_deadline.challenge {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "expected chain commit epoch {} to be after {}",
-                    params.chain_commit_epoch,
-                    current_deadline.challenge
-                ));
-            }
-
-            if params.chain_commit_epoch >= current_epoch {
-                return Err(actor_error!(
-                    illegal_argument,
-                    "chain commit epoch {} must be less than the current epoch {}",
-                    params.chain_commit_epoch,
-                    current_epoch
-                ));
-            }
-
-            // Verify the chain commit randomness
-            let comm_rand = rt.get_randomness_from_tickets(
-                DomainSeparationTag::PoStChainCommit,
-                params.chain_commit_epoch,
-                &[],
-            )?;
-            if Randomness(comm_rand.into()) != params.chain_commit_rand {
-                return Err(actor_error!(illegal_argument, "post commit randomness mismatched"));
-            }
+            let current_deadline = validate_windowed_post_state(rt, state, &info, &params)?;
 
             let sectors = Sectors::load(rt.store(), &state.sectors).map_err(|e| {
                 e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors")
@@ -716,7 +636,13 @@ impl Actor {
             // If we're not recovering power, record the proof for optimistic verification.
             if post_result.recovered_power.is_zero() {
                 deadline
-                    .record_post_proofs(rt.store(), &post_result.partitions, &params.proofs)
+                    .record_post_proofs(
+                        rt.store(),
+                        &post_result.partitions,
+                        &params.proofs,
+                        rt.message().caller(),
+                        rt.curr_epoch(),
+                    )
                     .map_err(|e| {
                         e.downcast_default(
                             ExitCode::USR_ILLEGAL_STATE,
@@ -776,10 +702,263 @@ impl Actor {
         Ok(())
     }
 
+    /// Checks a Window PoSt as if it were submitted with SubmitWindowedPoSt, without changing
+    /// any state. The submission's parameters are validated against the current chain and miner
+    /// state in the same way, and the proof is always verified.
+    /// Succeeds if the submission would be accepted, and otherwise fails with the error that
+    /// submission would fail with.
+    fn check_windowed_post(
+        rt: &impl Runtime,
+        mut params: SubmitWindowedPoStParams,
+    ) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        validate_windowed_post_params(rt.policy(), &params)?;
+
+        let state: State = rt.state()?;
+        let info = get_miner_info(rt.store(), &state)?;
+        let current_deadline = validate_windowed_post_state(rt, &state, &info, &params)?;
+
+        let sectors = Sectors::load(rt.store(), &state.sectors).map_err(|e| {
+            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors")
+        })?;
+        let deadlines =
+            state.load_deadlines(rt.store()).map_err(|e| e.wrap("failed to load deadlines"))?;
+        let mut deadline = deadlines.load_deadline(rt.store(), params.deadline)?;
+
+        // Process the submission against the deadline, which is then discarded, to determine the
+        // sectors to prove.
+        let fault_expiration = current_deadline.last() + rt.policy().fault_max_age;
+        let post_result = deadline
+            .record_proven_sectors(
+                rt.store(),
+                &sectors,
+                info.sector_size,
+                current_deadline.quant_spec(),
+                fault_expiration,
+                &mut params.partitions,
+            )
+            .map_err(|e| {
+                e.downcast_default(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    format!("failed to process post submission for deadline {}", params.deadline),
+                )
+            })?;
+
+        let proven_sectors = &post_result.sectors - &post_result.ignored_sectors;
+        if proven_sectors.is_empty() {
+            return Err(actor_error!(
+                illegal_argument,
+                "cannot prove partitions with no active sectors"
+            ));
+        }
+
+        let sector_infos = sectors
+            .load_for_proof(&post_result.sectors, &post_result.ignored_sectors)
+            .map_err(|e| {
+                e.downcast_default(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    "failed to load sectors for post verification",
+                )
+            })?;
+        if !verify_windowed_post(rt, current_deadline.challenge, &sector_infos, params.proofs)
+            .map_err(|e| e.wrap("window post failed"))?
+        {
+            return Err(actor_error!(illegal_argument, "invalid post was submitted"));
+        }
+        Ok(())
+    }
+
+    /// Returns the optimistically accepted Window PoSts of a deadline's last challenge window
+    /// that have not been disputed, along with the snapshots against which they are disputed.
+    fn get_disputable_proofs(
+        rt: &impl Runtime,
+        params: GetDisputableProofsParams,
+    ) -> Result<GetDisputableProofsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let policy = rt.policy();
+        if params.deadline >= policy.wpost_period_deadlines {
+            return Err(actor_error!(
+                illegal_argument,
+                "invalid deadline {} of {}",
+                params.deadline,
+                policy.wpost_period_deadlines
+            ));
+        }
+
+        let state: State = rt.state()?;
+        let current_epoch = rt.curr_epoch();
+        let dl_info = state.deadline_info(policy, current_epoch);
+        let dispute_open = deadline_available_for_optimistic_post_dispute(
+            policy,
+            dl_info.period_start,
+            params.deadline,
+            current_epoch,
+        );
+
+        let deadlines =
+            state.load_deadlines(rt.store()).map_err(|e| e.wrap("failed to load deadlines"))?;
+        let deadline = deadlines.load_deadline(rt.store(), params.deadline)?;
+        let proofs = deadline
+            .disputable_post_proofs(rt.store())
+            .map_err(|e| {
+                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load post proofs")
+            })?
+            .into_iter()
+            .map(|(post_index, post)| DisputableProof {
+                post_index,
+                partitions: post.partitions,
+                proofs: post.proofs,
+                prover: post.prover,
+                epoch: post.epoch,
+            })
+            .collect();
+
+        Ok(GetDisputableProofsReturn {
+            dispute_open,
+            sectors_snapshot: deadline.sectors_snapshot,
+            partitions_snapshot: deadline.partitions_snapshot,
+            proofs,
+        })
+    }
+
+    /// Summarizes the fill of each deadline's partitions and the partitions that compaction
+    /// would remove, so that a miner can decide when compaction is worthwhile.
+    fn get_partition_report(rt: &impl Runtime) -> Result<GetPartitionReportReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let policy = rt.policy();
+        let state: State = rt.state()?;
+        let info = get_miner_info(rt.store(), &state)?;
+        let partition_sectors = info.window_post_partition_sectors;
+        let proving_period_start = state.current_proving_period_start(policy, rt.curr_epoch());
+
+        let deadlines =
+            state.load_deadlines(rt.store()).map_err(|e| e.wrap("failed to load deadlines"))?;
+        let mut reports = Vec::new();
+        deadlines
+            .for_each(rt.store(), |dl_idx, deadline| {
+                let mut partition_live_sectors = Vec::new();
+                deadline.for_each(rt.store(), |_, partition| {
+                    partition_live_sectors.push(partition.live_sectors().len());
+                    Ok(())
+                })?;
+                // Compaction packs the live sectors into as few partitions as possible.
+                let compacted = deadline.live_sectors.div_ceil(partition_sectors);
+                let removable_partitions =
+                    (partition_live_sectors.len() as u64).saturating_sub(compacted);
+                reports.push(DeadlinePartitionReport {
+                    partition_live_sectors,
+                    removable_partitions,
+                    dead_sectors: deadline.total_sectors - deadline.live_sectors,
+                    compactable: deadline_available_for_compaction(
+                        policy,
+                        proving_period_start,
+                        dl_idx,
+                        rt.curr_epoch(),
+                    ),
+                });
+                Ok(())
+            })
+            .map_err(|e| {
+                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load partitions")
+            })?;
+
+        Ok(GetPartitionReportReturn { partition_sectors, deadlines: reports })
+    }
+
+    /// Returns a deadline's schedule for its current or next challenge window, with a summary
+    /// of its partitions, sectors, power and daily fee.
+    fn get_deadline_info(
+        rt: &impl Runtime,
+        params: GetDeadlineInfoParams,
+    ) -> Result<GetDeadlineInfoReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let policy = rt.policy();
+        if params.deadline >= policy.wpost_period_deadlines {
+            return Err(actor_error!(
+                illegal_argument,
+                "invalid deadline {} of {}",
+                params.deadline,
+                policy.wpost_period_deadlines
+            ));
+        }
+
+        let state: State = rt.state()?;
+        let current_epoch = rt.curr_epoch();
+        let dl_info = new_deadline_info(
+            policy,
+            state.current_proving_period_start(policy, current_epoch),
+            params.deadline,
+            current_epoch,
+        )
+        .next_not_elapsed();
+
+        let deadlines =
+            state.load_deadlines(rt.store()).map_err(|e| e.wrap("failed to load deadlines"))?;
+        let deadline = deadlines.load_deadline(rt.store(), params.deadline)?;
+        let partitions = deadline
+            .partitions_amt(rt.store())
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load partitions")?
+            .count();
+
+        Ok(GetDeadlineInfoReturn {
+            open: dl_info.open,
+            close: dl_info.close,
+            challenge: dl_info.challenge,
+            partitions,
+            partitions_posted: deadline.partitions_posted,
+            live_sectors: deadline.live_sectors,
+            total_sectors: deadline.total_sectors,
+            live_power: deadline.live_power,
+            faulty_power: deadline.faulty_power,
+            daily_fee: deadline.daily_fee,
+        })
+    }
+
+    /// Returns the sectors in a partition, by their status.
+    fn get_partition_sectors(
+        rt: &impl Runtime,
+        params: GetPartitionSectorsParams,
+    ) -> Result<GetPartitionSectorsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let policy = rt.policy();
+        if params.deadline >= policy.wpost_period_deadlines {
+            return Err(actor_error!(
+                illegal_argument,
+                "invalid deadline {} of {}",
+                params.deadline,
+                policy.wpost_period_deadlines
+            ));
+        }
+
+        let state: State = rt.state()?;
+        let deadlines =
+            state.load_deadlines(rt.store()).map_err(|e| e.wrap("failed to load deadlines"))?;
+        let deadline = deadlines.load_deadline(rt.store(), params.deadline)?;
+        let partition = deadline.load_partition(rt.store(), params.partition)?;
+
+        Ok(GetPartitionSectorsReturn {
+            live: partition.live_sectors(),
+            all: partition.sectors,
+            unproven: partition.unproven,
+            faulty: partition.faults,
+            recovering: partition.recoveries,
+            terminated: partition.terminated,
+        })
+    }
+
     fn prove_replica_updates3(
         rt: &impl Runtime,
         params: ProveReplicaUpdates3Params,
     ) -> Result<ProveReplicaUpdates3Return, ActorError> {
+        let ret = Self::prove_replica_updates3_with_summaries(rt, params)?;
+        Ok(ProveReplicaUpdates3Return { activation_results: ret.activation_results })
+    }
+
+    /// As for ProveReplicaUpdates3, additionally returning a summary of each updated sector.
+    fn prove_replica_updates3_with_summaries(
+        rt: &impl Runtime,
+        params: ProveReplicaUpdates3Params,
+    ) -> Result<ProveReplicaUpdates3WithSummariesReturn, ActorError> {
         let state: State = rt.state()?;
         let store = rt.store();
         let info = get_miner_info(store, &state)?;
@@ -954,6 +1133,7 @@ impl Actor {
                 seal_cid: update.new_sealed_cid,
                 unverified_space: data_activation.unverified_space.clone(),
                 verified_space: data_activation.verified_space.clone(),
+                claim_ids: data_activation.claim_ids,
             };
             state_updates_by_dline.entry(update.deadline).or_default().push(
                 ReplicaUpdateStateInputs {
@@ -965,7 +1145,7 @@ impl Actor {
             );
         }
 
-        let (power_delta, pledge_delta) = update_replica_states(
+        let (power_delta, pledge_delta, summaries) = update_replica_states(
             rt,
             &state_updates_by_dline,
             successful_manifests.len(),
@@ -976,6 +1156,14 @@ impl Actor {
         notify_pledge_changed(rt, &pledge_delta)?;
         request_update_power(rt, power_delta)?;
 
+        // Order the summaries as the successful updates, rather than by deadline.
+        let mut summaries: BTreeMap<SectorNumber, SectorActivationSummary> =
+            summaries.into_iter().map(|s| (s.sector, s)).collect();
+        let summaries: Vec<SectorActivationSummary> = successful_manifests
+            .iter()
+            .filter_map(|(update, _)| summaries.remove(&update.sector))
+            .collect();
+
         // Notify data consumers.
         let mut notifications: Vec<ActivationNotifications> = vec![];
         for (update, sector_info) in successful_manifests {
@@ -997,7 +1185,53 @@ impl Actor {
         notify_data_consumers(rt, &notifications, params.require_notification_success)?;
 
         let result = util::stack(&[validation_batch, proven_batch, data_batch]);
-        Ok(ProveReplicaUpdates3Return { activation_results: result })
+        Ok(ProveReplicaUpdates3WithSummariesReturn {
+            activation_results: result,
+            sectors: summaries,
+        })
+    }
+
+    /// Retries delivery of sector content change notifications which previously failed,
+    /// for up to max_sectors sectors.
+    /// Notifications for sectors which are no longer committed are discarded.
+    fn retry_notifications(
+        rt: &impl Runtime,
+        params: RetryNotificationsParams,
+    ) -> Result<RetryNotificationsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let max_sectors = rt.policy().addressed_sectors_max;
+        if params.max_sectors == 0 || params.max_sectors > max_sectors {
+            return Err(actor_error!(
+                illegal_argument,
+                "max sectors {} must be between 1 and {}",
+                params.max_sectors,
+                max_sectors
+            ));
+        }
+
+        let pending = rt.transaction(|st: &mut State, rt| {
+            st.take_failed_notifications(rt.store(), params.max_sectors)
+        })?;
+
+        let st: State = rt.state()?;
+        let mut retained = Vec::with_capacity(pending.len());
+        let mut dropped = 0;
+        for failure in pending {
+            if st.is_sector_committed(rt.store(), failure.changes.sector)? {
+                retained.push(failure);
+            } else {
+                dropped += 1;
+            }
+        }
+
+        let (delivered, failures) = resend_failed_notifications(rt, retained);
+        let failed = failures.len() as u64;
+        if !failures.is_empty() {
+            rt.transaction(|st: &mut State, rt| {
+                st.record_failed_notifications(rt.store(), failures)
+            })?;
+        }
+        Ok(RetryNotificationsReturn { delivered, failed, dropped })
     }
 
     fn dispute_windowed_post(
@@ -1031,32 +1265,12 @@ impl Actor {
         let (pledge_delta, mut to_burn, power_delta, to_reward) =
             rt.transaction(|st: &mut State, rt| {
                 let policy = rt.policy();
-                let dl_info = st.deadline_info(policy, current_epoch);
-
-                if !deadline_available_for_optimistic_post_dispute(
-                    policy,
-                    dl_info.period_start,
-                    params.deadline,
-                    current_epoch,
-                ) {
-                    return Err(actor_error!(
-                        forbidden,
-                        "can only dispute window posts during the dispute window \
-                    ({} epochs after the challenge window closes)",
-                        policy.wpost_dispute_window
-                    ));
-                }
-
+                let target_deadline =
+                    disputed_deadline_info(policy, st, params.deadline, current_epoch)?;
                 let info = get_miner_info(rt.store(), st)?;
+
                 // --- check proof ---
 
-                // Find the proving period start for the deadline in question.
-                let mut pp_start = dl_info.period_start;
-                if dl_info.index < params.deadline {
-                    pp_start -= policy.wpost_proving_period
-                }
-                let target_deadline =
-                    new_deadline_info(policy, pp_start, params.deadline, current_epoch);
                 // Load the target deadline
                 let mut deadlines_current = st
                     .load_deadlines(rt.store())
@@ -1077,78 +1291,26 @@ impl Actor {
                         )
                     })?;
 
-                // Load the partition info we need for the dispute.
-                let mut dispute_info = dl_current
-                    .load_partitions_for_dispute(rt.store(), partitions)
-                    .map_err(|e| {
-                        e.downcast_default(
-                            ExitCode::USR_ILLEGAL_STATE,
-                            "failed to load partition for dispute",
-                        )
-                    })?;
-
-                // This includes power that is no longer active (e.g., due to sector terminations).
-                // It must only be used for penalty calculations, not power adjustments.
-                let penalised_power = dispute_info.disputed_power.clone();
+                let (power_delta, penalised_power) =
+                    dispute_post(rt, &info, &mut dl_current, &target_deadline, partitions, proofs)?
+                        .ok_or_else(|| {
+                            actor_error!(illegal_argument, "failed to dispute valid post")
+                        })?;
 
-                // Load sectors for the dispute.
-                let sectors =
-                    Sectors::load(rt.store(), &dl_current.sectors_snapshot).map_err(|e| {
-                        e.downcast_default(
-                            ExitCode::USR_ILLEGAL_STATE,
-                            "failed to load sectors array",
-                        )
-                    })?;
-                let sector_infos = sectors
-                    .load_for_proof(&dispute_info.all_sector_nos, &dispute_info.ignored_sector_nos)
+                deadlines_current
+                    .update_deadline(policy, rt.store(), params.deadline, &dl_current)
                     .map_err(|e| {
                         e.downcast_default(
                             ExitCode::USR_ILLEGAL_STATE,
-                            "failed to load sectors to dispute window post",
+                            format!("failed to update deadline {}", params.deadline),
                         )
                     })?;
 
-                // Check proof, we fail if validation succeeds.
-                if verify_windowed_post(rt, target_deadline.challenge, &sector_infos, proofs)? {
-                    return Err(actor_error!(illegal_argument, "failed to dispute valid post"));
-                } else {
-                    info!("Successfully disputed post- window post was invalid");
-                }
+                st.save_deadlines(rt.store(), deadlines_current).map_err(|e| {
+                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
+                })?;
 
-                // Ok, now we record faults. This always works because
-                // we don't allow compaction/moving sectors during the
-                // challenge window.
-                //
-                // However, some of these sectors may have been
-                // terminated. That's fine, we'll skip them.
-                let fault_expiration_epoch = target_deadline.last() + policy.fault_max_age;
-                let power_delta = dl_current
-                    .record_faults(
-                        rt.store(),
-                        &sectors,
-                        info.sector_size,
-                        quant_spec_for_deadline(policy, &target_deadline),
-                        fault_expiration_epoch,
-                        &mut dispute_info.disputed_sectors,
-                    )
-                    .map_err(|e| {
-                        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to declare faults")
-                    })?;
-
-                deadlines_current
-                    .update_deadline(policy, rt.store(), params.deadline, &dl_current)
-                    .map_err(|e| {
-                        e.downcast_default(
-                            ExitCode::USR_ILLEGAL_STATE,
-                            format!("failed to update deadline {}", params.deadline),
-                        )
-                    })?;
-
-                st.save_deadlines(rt.store(), deadlines_current).map_err(|e| {
-                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
-                })?;
-
-                // --- penalties ---
+                // --- penalties ---
 
                 // Calculate the base penalty.
                 let penalty_base = pledge_penalty_for_invalid_windowpost(
@@ -1206,6 +1368,183 @@ impl Actor {
         Ok(())
     }
 
+    /// Disputes the window PoSts proving some partitions of a deadline.
+    /// Each proof covering any of the partitions is verified as a whole, so faults are recorded
+    /// for all the partitions proven by an invalid proof, including any not disputed.
+    /// Returns a result for each disputed partition, in order, which fails if the partition
+    /// was proven by a valid proof or has no proof open to dispute.
+    /// The reporter is rewarded for each invalid proof as if it were disputed alone.
+    fn dispute_windowed_post_partitions(
+        rt: &impl Runtime,
+        params: DisputeWindowedPoStPartitionsParams,
+    ) -> Result<BatchReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let reporter = rt.message().caller();
+
+        {
+            let policy = rt.policy();
+            if params.deadline >= policy.wpost_period_deadlines {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "invalid deadline {} of {}",
+                    params.deadline,
+                    policy.wpost_period_deadlines
+                ));
+            }
+            if params.partitions.is_empty() {
+                return Err(actor_error!(illegal_argument, "no partitions to dispute"));
+            }
+            if params.partitions.len() > policy.max_partitions_per_deadline {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "too many partitions {}, max {}",
+                    params.partitions.len(),
+                    policy.max_partitions_per_deadline
+                ));
+            }
+        }
+        let current_epoch = rt.curr_epoch();
+
+        // As for a single dispute, these are estimates of the conditions
+        // when the posts were submitted.
+        let epoch_reward = request_current_epoch_block_reward(rt)?;
+        let power_total = request_current_total_power(rt)?;
+
+        let (disputed, rejected, pledge_delta, mut to_burn, power_delta, to_reward) = rt
+            .transaction(|st: &mut State, rt| {
+                let policy = rt.policy();
+                let target_deadline =
+                    disputed_deadline_info(policy, st, params.deadline, current_epoch)?;
+                let info = get_miner_info(rt.store(), st)?;
+
+                let mut deadlines_current = st
+                    .load_deadlines(rt.store())
+                    .map_err(|e| e.wrap("failed to load deadlines"))?;
+                let mut dl_current =
+                    deadlines_current.load_deadline(rt.store(), params.deadline)?;
+
+                let posts = dl_current.disputable_post_proofs(rt.store()).map_err(|e| {
+                    e.downcast_default(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        "failed to load proofs for dispute",
+                    )
+                })?;
+
+                // Dispute each proof of the disputed partitions, taking invalid ones
+                // from the snapshot so they can't be disputed again.
+                let mut disputed = BitField::new();
+                let mut rejected = BitField::new();
+                let mut power_delta = PowerPair::zero();
+                let mut penalty_target = TokenAmount::zero();
+                let mut reward_target = TokenAmount::zero();
+                for (post_index, post) in posts {
+                    if (&post.partitions & &params.partitions).is_empty() {
+                        continue;
+                    }
+                    let Some((post_power_delta, penalised_power)) = dispute_post(
+                        rt,
+                        &info,
+                        &mut dl_current,
+                        &target_deadline,
+                        post.partitions.clone(),
+                        post.proofs,
+                    )?
+                    else {
+                        rejected |= &post.partitions;
+                        continue;
+                    };
+                    dl_current.take_post_proofs(rt.store(), post_index).map_err(|e| {
+                        e.downcast_default(
+                            ExitCode::USR_ILLEGAL_STATE,
+                            "failed to remove disputed proof",
+                        )
+                    })?;
+                    disputed |= &post.partitions;
+                    power_delta += &post_power_delta;
+
+                    let reward = reward_for_disputed_window_post(
+                        info.window_post_proof_type,
+                        penalised_power.clone(),
+                    );
+                    penalty_target += pledge_penalty_for_invalid_windowpost(
+                        &epoch_reward.this_epoch_reward_smoothed,
+                        &power_total.quality_adj_power_smoothed,
+                        &penalised_power.qa,
+                    ) + &reward;
+                    reward_target += reward;
+                }
+
+                if disputed.is_empty() {
+                    return Ok((
+                        disputed,
+                        rejected,
+                        TokenAmount::zero(),
+                        TokenAmount::zero(),
+                        power_delta,
+                        TokenAmount::zero(),
+                    ));
+                }
+
+                deadlines_current
+                    .update_deadline(policy, rt.store(), params.deadline, &dl_current)
+                    .map_err(|e| {
+                        e.downcast_default(
+                            ExitCode::USR_ILLEGAL_STATE,
+                            format!("failed to update deadline {}", params.deadline),
+                        )
+                    })?;
+                st.save_deadlines(rt.store(), deadlines_current).map_err(|e| {
+                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
+                })?;
+
+                // --- penalties ---
+
+                st.apply_penalty(&penalty_target)
+                    .map_err(|e| actor_error!(illegal_state, "failed to apply penalty {}", e))?;
+                let (to_burn, total_unlocked) = st
+                    .repay_partial_debt_in_priority_order(
+                        rt.store(),
+                        current_epoch,
+                        &rt.current_balance(),
+                    )
+                    .map_err(|e| {
+                        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to pay debt")
+                    })?;
+
+                let to_reward = std::cmp::min(&to_burn, &reward_target).clone();
+                let to_burn = &to_burn - &to_reward;
+                Ok((disputed, rejected, total_unlocked.neg(), to_burn, power_delta, to_reward))
+            })?;
+
+        request_update_power(rt, power_delta)?;
+        if !to_reward.is_zero() {
+            if let Err(e) =
+                extract_send_result(rt.send_simple(&reporter, METHOD_SEND, None, to_reward.clone()))
+            {
+                error!("failed to send reward: {}", e);
+                to_burn += to_reward;
+            }
+        }
+
+        burn_funds(rt, to_burn)?;
+        notify_pledge_changed(rt, &pledge_delta)?;
+
+        let st: State = rt.state()?;
+        st.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
+
+        let mut result = BatchReturnGen::new(params.partitions.len() as usize);
+        for partition in params.partitions.iter() {
+            if disputed.get(partition) {
+                result.add_success();
+            } else if rejected.get(partition) {
+                result.add_fail(ExitCode::USR_ILLEGAL_ARGUMENT);
+            } else {
+                result.add_fail(ExitCode::USR_NOT_FOUND);
+            }
+        }
+        Ok(result.generate())
+    }
+
     /// Pledges the miner to seal and commit some new sectors.
     /// The caller specifies sector numbers, sealed sector CIDs, unsealed sector CID, seal randomness epoch, expiration, and the IDs
     /// of any storage deals contained in the sector data. The storage deal proposals must be already submitted
@@ -1477,6 +1816,15 @@ impl Actor {
         rt: &impl Runtime,
         params: ProveCommitSectors3Params,
     ) -> Result<ProveCommitSectors3Return, ActorError> {
+        let ret = Self::prove_commit_sectors3_with_summaries(rt, params)?;
+        Ok(ProveCommitSectors3Return { activation_results: ret.activation_results })
+    }
+
+    /// As for ProveCommitSectors3, additionally returning a summary of each activated sector.
+    fn prove_commit_sectors3_with_summaries(
+        rt: &impl Runtime,
+        params: ProveCommitSectors3Params,
+    ) -> Result<ProveCommitSectors3WithSummariesReturn, ActorError> {
         let state: State = rt.state()?;
         let store = rt.store();
         let policy = rt.policy();
@@ -1649,7 +1997,7 @@ impl Actor {
             epochs_since_ramp_start: rt.curr_epoch() - pwr.ramp_start_epoch,
             ramp_duration_epochs: pwr.ramp_duration_epochs,
         };
-        activate_new_sector_infos(
+        let summaries = activate_new_sector_infos(
             rt,
             successful_precommits,
             data_activations,
@@ -1675,7 +2023,10 @@ impl Actor {
         notify_data_consumers(rt, &notifications, params.require_notification_success)?;
 
         let result = util::stack(&[validation_batch, proven_batch, data_batch]);
-        Ok(ProveCommitSectors3Return { activation_results: result })
+        Ok(ProveCommitSectors3WithSummariesReturn {
+            activation_results: result,
+            sectors: summaries,
+        })
     }
 
     fn internal_sector_setup_preseal(
@@ -1737,6 +2088,15 @@ impl Actor {
         rt: &impl Runtime,
         params: ProveCommitSectorsNIParams,
     ) -> Result<ProveCommitSectorsNIReturn, ActorError> {
+        let ret = Self::prove_commit_sectors_ni_with_summaries(rt, params)?;
+        Ok(ProveCommitSectorsNIReturn { activation_results: ret.activation_results })
+    }
+
+    /// As for ProveCommitSectorsNI, additionally returning a summary of each activated sector.
+    fn prove_commit_sectors_ni_with_summaries(
+        rt: &impl Runtime,
+        params: ProveCommitSectorsNIParams,
+    ) -> Result<ProveCommitSectorsNIWithSummariesReturn, ActorError> {
         let policy = rt.policy();
         let curr_epoch = rt.curr_epoch();
         let state: State = rt.state()?;
@@ -1869,6 +2229,9 @@ impl Actor {
             .collect::<Vec<SectorOnChainInfo>>();
 
         let sectors_len = sectors_to_add.len();
+        // Sectors committed with NI-PoRep have no data, and so no claims.
+        let summaries: Vec<SectorActivationSummary> =
+            sectors_to_add.iter().map(|s| summarize_sector(info.sector_size, s, 0)).collect();
 
         let total_pledge = BigInt::from(sectors_len) * sector_initial_pledge;
 
@@ -1940,7 +2303,10 @@ impl Actor {
             )?;
         }
 
-        Ok(ProveCommitSectorsNIReturn { activation_results: validation_batch })
+        Ok(ProveCommitSectorsNIWithSummariesReturn {
+            activation_results: validation_batch,
+            sectors: summaries,
+        })
     }
     /// Returns the maximum termination fee calculation for a given initial pledge and power amount
     fn max_termination_fee(
@@ -1974,6 +2340,185 @@ impl Actor {
         Ok(InitialPledgeReturn { initial_pledge: state.initial_pledge })
     }
 
+    /// Returns the deadline and partition to which a live sector is assigned.
+    fn find_sector(
+        rt: &impl Runtime,
+        params: FindSectorParams,
+    ) -> Result<FindSectorReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        if state.get_sector(rt.store(), params.sector_number)?.is_none() {
+            return Err(actor_error!(not_found, "sector {} not found", params.sector_number));
+        }
+        let (deadline, partition) = state
+            .find_sector(rt.store(), params.sector_number)
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to find sector {}", params.sector_number)
+            })?;
+        Ok(FindSectorReturn { deadline, partition })
+    }
+
+    /// Returns the IDs of the verified registry claims contributing to a sector's
+    /// verified deal weight, and whether they are known.
+    fn get_sector_claims(
+        rt: &impl Runtime,
+        params: GetSectorClaimsParams,
+    ) -> Result<GetSectorClaimsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        let sector = state
+            .get_sector(rt.store(), params.sector_number)?
+            .ok_or_else(|| actor_error!(not_found, "sector {} not found", params.sector_number))?;
+        let claim_ids = state.get_sector_claims(rt.store(), params.sector_number)?;
+        // Claims are recorded for every sector activated with verified data since the record
+        // was introduced, so verified deal weight with none recorded predates it.
+        let known = !claim_ids.is_empty() || sector.verified_deal_weight.is_zero();
+        Ok(GetSectorClaimsReturn { known, claim_ids })
+    }
+
+    /// Returns a summary of the on-chain information of a sector.
+    fn get_sector_info(
+        rt: &impl Runtime,
+        params: GetSectorInfoParams,
+    ) -> Result<GetSectorInfoReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        let sector = state
+            .get_sector(rt.store(), params.sector_number)?
+            .ok_or_else(|| actor_error!(not_found, "sector {} not found", params.sector_number))?;
+        Ok(GetSectorInfoReturn { sector: SectorSummary::from(&sector) })
+    }
+
+    /// Lists summaries of the on-chain information of sectors, in order of sector number.
+    /// Results are paginated; a call returns at most the requested number of sectors,
+    /// up to GET_SECTORS_INFO_MAX_PAGE_SIZE.
+    fn get_sectors_info(
+        rt: &impl Runtime,
+        params: GetSectorsInfoParams,
+    ) -> Result<GetSectorsInfoReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.limit == 0 {
+            return Err(actor_error!(illegal_argument, "limit must be positive"));
+        }
+        let limit = params.limit.min(GET_SECTORS_INFO_MAX_PAGE_SIZE) as usize;
+
+        // The cursor's position is the smallest sector number to list.
+        let start: Option<SectorNumber> = params.cursor.decode()?;
+        let state: State = rt.state()?;
+        let (sectors, next) = state.list_sectors(rt.store(), start, limit)?;
+        let next_cursor = next.map(|sno| Cursor::encode(&sno)).transpose()?;
+        Ok(GetSectorsInfoReturn {
+            sectors: sectors.iter().map(SectorSummary::from).collect(),
+            next_cursor,
+        })
+    }
+
+    /// Returns the number of sectors held by the miner, including faulty sectors.
+    fn get_sector_count(rt: &impl Runtime) -> Result<GetSectorCountReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        Ok(GetSectorCountReturn { count: state.sector_count(rt.store())? })
+    }
+
+    /// Returns the epoch by which a sector's proof is scheduled to be refreshed, if any.
+    fn get_proof_refresh_epoch(
+        rt: &impl Runtime,
+        params: GetProofRefreshEpochParams,
+    ) -> Result<GetProofRefreshEpochReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state: State = rt.state()?;
+        if state.get_sector(rt.store(), params.sector_number)?.is_none() {
+            return Err(actor_error!(not_found, "sector {} not found", params.sector_number));
+        }
+        let refresh_epoch = state
+            .get_proof_refresh(rt.store(), params.sector_number)?
+            .map(|refresh| refresh.refresh_epoch);
+        Ok(GetProofRefreshEpochReturn { refresh_epoch })
+    }
+
+    /// Extends the epoch by which sectors' proofs are scheduled to be refreshed.
+    /// The new epoch may be no further than the proof refresh period from the current epoch,
+    /// and must be later than each sector's currently scheduled epoch.
+    /// A refresh may be postponed by at most one further refresh period beyond that first
+    /// scheduled when the sector's proof was last refreshed, so that it can't be put off forever.
+    fn extend_proof_refresh(
+        rt: &impl Runtime,
+        params: ExtendProofRefreshParams,
+    ) -> Result<(), ActorError> {
+        let policy = rt.policy();
+        if !policy.proof_refresh_enabled {
+            return Err(actor_error!(forbidden, "proof refresh is not enabled"));
+        }
+        let max_epoch = rt.curr_epoch() + policy.proof_refresh_period;
+        if params.new_refresh_epoch > max_epoch {
+            return Err(actor_error!(
+                illegal_argument,
+                "new refresh epoch {} exceeds maximum {}",
+                params.new_refresh_epoch,
+                max_epoch
+            ));
+        }
+        params.sectors.validate().map_err(|e| {
+            actor_error!(illegal_argument, "failed to validate sectors bitfield: {}", e)
+        })?;
+        let sector_count = params.sectors.len();
+        if sector_count == 0 || sector_count > policy.addressed_sectors_max {
+            return Err(actor_error!(
+                illegal_argument,
+                "sector count {} must be between 1 and {}",
+                sector_count,
+                policy.addressed_sectors_max
+            ));
+        }
+
+        rt.transaction(|state: &mut State, rt| {
+            let info = get_miner_info(rt.store(), state)?;
+            rt.validate_immediate_caller_is(
+                info.control_addresses.iter().chain(&[info.worker, info.owner]),
+            )?;
+
+            let mut refreshes = Vec::new();
+            for sector in params.sectors.iter() {
+                if !state.is_sector_committed(rt.store(), sector)? {
+                    return Err(actor_error!(not_found, "sector {} is not committed", sector));
+                }
+                let scheduled = state.get_proof_refresh(rt.store(), sector)?.ok_or_else(|| {
+                    actor_error!(not_found, "no proof refresh scheduled for sector {}", sector)
+                })?;
+                if params.new_refresh_epoch <= scheduled.refresh_epoch {
+                    return Err(actor_error!(
+                        illegal_argument,
+                        "new refresh epoch {} must be after sector {}'s scheduled epoch {}",
+                        params.new_refresh_epoch,
+                        sector,
+                        scheduled.refresh_epoch
+                    ));
+                }
+                let latest_epoch = scheduled.proven_epoch + 2 * policy.proof_refresh_period;
+                if params.new_refresh_epoch > latest_epoch {
+                    return Err(actor_error!(
+                        illegal_argument,
+                        "new refresh epoch {} exceeds sector {}'s latest refresh epoch {}",
+                        params.new_refresh_epoch,
+                        sector,
+                        latest_epoch
+                    ));
+                }
+                refreshes.push((
+                    sector,
+                    ProofRefresh { refresh_epoch: params.new_refresh_epoch, ..scheduled },
+                ));
+            }
+            state.schedule_proof_refreshes(rt.policy(), rt.store(), refreshes)?;
+            Ok(())
+        })?;
+
+        for sector in params.sectors.iter() {
+            emit::proof_refresh_extended(rt, sector, params.new_refresh_epoch)?;
+        }
+        Ok(())
+    }
+
     fn check_sector_proven(
         rt: &impl Runtime,
         params: CheckSectorProvenParams,
@@ -2009,243 +2554,69 @@ impl Actor {
         Self::extend_sector_expiration_inner(rt, extend_expiration_inner)
     }
 
+    // Version of extend_sector_expiration2 that takes the claims of each sector from those
+    // recorded in state, so that only the claims to be dropped need to be declared.
+    // The claims of sectors without recorded claims are declared instead, and recorded.
+    // All claims of all declared sectors are fetched in a single batch.
+    fn extend_sector_expirations_by_claims(
+        rt: &impl Runtime,
+        params: ExtendSectorExpirationsByClaimsParams,
+    ) -> Result<(), ActorError> {
+        let extend_expiration_inner = validate_claim_extension_declarations(rt, params.extensions)?;
+        Self::extend_sector_expiration_inner(rt, extend_expiration_inner)
+    }
+
     fn extend_sector_expiration_inner(
         rt: &impl Runtime,
         inner: ExtendExpirationsInner,
     ) -> Result<(), ActorError> {
-        let curr_epoch = rt.curr_epoch();
-        let circulating_supply = rt.total_fil_circ_supply();
-
-        /* Loop over sectors and do extension */
         let (power_delta, pledge_delta) = rt.transaction(|state: &mut State, rt| {
             let info = get_miner_info(rt.store(), state)?;
             rt.validate_immediate_caller_is(
                 info.control_addresses.iter().chain(&[info.worker, info.owner]),
             )?;
+            apply_expiration_extensions(rt, state, &info, &inner)
+        })?;
 
-            let mut deadlines =
-                state.load_deadlines(rt.store()).map_err(|e| e.wrap("failed to load deadlines"))?;
+        // power_delta should be zero in most cases, but can be negative if claims are dropped in
+        // the process of extending sector expirations.
+        request_update_power(rt, power_delta)?;
 
-            // Group declarations by deadline, and remember iteration order.
-            //
-            let mut decls_by_deadline: Vec<_> = std::iter::repeat_with(Vec::new)
-                .take(rt.policy().wpost_period_deadlines as usize)
-                .collect();
-            let mut deadlines_to_load = Vec::<u64>::new();
-            for decl in &inner.extensions {
-                // the deadline indices are already checked.
-                let decls = &mut decls_by_deadline[decl.deadline as usize];
-                if decls.is_empty() {
-                    deadlines_to_load.push(decl.deadline);
-                }
-                decls.push(decl);
-            }
+        // Note: the pledge delta is expected to be zero, since pledge is not re-calculated for the extension.
+        // But in case that ever changes, we can do the right thing here.
+        notify_pledge_changed(rt, &pledge_delta)?;
+        Ok(())
+    }
 
-            let mut sectors = Sectors::load(rt.store(), &state.sectors).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors array")
-            })?;
+    /// Marks some sectors as terminated at the present epoch, earlier than their
+    /// scheduled termination, and adds these sectors to the early termination queue.
+    /// This method then processes up to AddressedSectorsMax sectors and
+    /// AddressedPartitionsMax partitions from the early termination queue,
+    /// terminating deals, paying fines, and returning pledge collateral. While
+    /// sectors remain in this queue:
+    ///
+    ///  1. The miner will be unable to withdraw funds.
+    ///  2. The chain will process up to AddressedSectorsMax sectors and
+    ///     AddressedPartitionsMax per epoch until the queue is empty.
+    ///
+    /// The sectors are immediately ignored for Window PoSt proofs, and should be
+    /// masked in the same way as faulty sectors. A miner may not terminate sectors in the
+    /// current deadline or the next deadline to be proven.
+    ///
+    /// This function may be invoked with no new sectors to explicitly process the
+    /// next batch of sectors.
+    fn terminate_sectors(
+        rt: &impl Runtime,
+        params: TerminateSectorsParams,
+    ) -> Result<TerminateSectorsReturn, ActorError> {
+        // Note: this cannot terminate pre-committed but un-proven sectors.
+        // They must be allowed to expire (and deposit burnt).
 
-            let mut power_delta = PowerPair::zero();
-            let mut pledge_delta = TokenAmount::zero();
+        let mut to_process = DeadlineSectorMap::new();
 
-            for deadline_idx in deadlines_to_load {
-                let policy = rt.policy();
-                let mut deadline = deadlines.load_deadline(rt.store(), deadline_idx)?;
-
-                let mut partitions = deadline.partitions_amt(rt.store()).map_err(|e| {
-                    e.downcast_default(
-                        ExitCode::USR_ILLEGAL_STATE,
-                        format!("failed to load partitions for deadline {}", deadline_idx),
-                    )
-                })?;
-
-                let quant = state.quant_spec_for_deadline(policy, deadline_idx);
-
-                let mut deadline_power_delta = PowerPair::zero();
-                let mut deadline_pledge_delta = TokenAmount::zero();
-                let mut deadline_daily_fee_delta = TokenAmount::zero();
-
-                // Group modified partitions by epoch to which they are extended. Duplicates are ok.
-                let mut partitions_by_new_epoch = BTreeMap::<ChainEpoch, Vec<u64>>::new();
-                let mut epochs_to_reschedule = Vec::<ChainEpoch>::new();
-
-                for decl in &mut decls_by_deadline[deadline_idx as usize] {
-                    let key = PartitionKey { deadline: deadline_idx, partition: decl.partition };
-
-                    let mut partition = partitions
-                        .get(decl.partition)
-                        .map_err(|e| {
-                            e.downcast_default(
-                                ExitCode::USR_ILLEGAL_STATE,
-                                format!("failed to load partition {:?}", key),
-                            )
-                        })?
-                        .cloned()
-                        .ok_or_else(|| actor_error!(not_found, "no such partition {:?}", key))?;
-
-                    let old_sectors = sectors
-                        .load_sectors(&decl.sectors)
-                        .map_err(|e| e.wrap("failed to load sectors"))?;
-                    let new_sectors: Vec<SectorOnChainInfo> = old_sectors
-                        .iter()
-                        .map(|sector| match &inner.claims {
-                            None => Err(actor_error!(
-                                unspecified,
-                                "extend2 always specifies (potentially empty) claim mapping"
-                            )),
-                            Some(claim_space_by_sector) => extend_sector_committment(
-                                rt.policy(),
-                                rt.network_version(),
-                                curr_epoch,
-                                &circulating_supply,
-                                decl.new_expiration,
-                                sector,
-                                info.sector_size,
-                                claim_space_by_sector,
-                            ),
-                        })
-                        .collect::<Result<_, _>>()?;
-
-                    // Overwrite sector infos.
-                    sectors.store(new_sectors.clone()).map_err(|e| {
-                        e.downcast_default(
-                            ExitCode::USR_ILLEGAL_STATE,
-                            format!("failed to update sectors {:?}", decl.sectors),
-                        )
-                    })?;
-
-                    // Remove old sectors from partition and assign new sectors.
-                    let (partition_power_delta, partition_pledge_delta, partition_daily_fee_delta) =
-                        partition
-                            .replace_sectors(
-                                rt.store(),
-                                &old_sectors,
-                                &new_sectors,
-                                info.sector_size,
-                                quant,
-                            )
-                            .map_err(|e| {
-                                e.downcast_default(
-                                    ExitCode::USR_ILLEGAL_STATE,
-                                    format!("failed to replace sector expirations at {:?}", key),
-                                )
-                            })?;
-
-                    deadline_power_delta += &partition_power_delta;
-                    // expected to be zero, see note below.
-                    deadline_pledge_delta += &partition_pledge_delta;
-                    // non-zero when extending sectors that previously paid no fees (e.g., because
-                    // they were sealed before we started charging fees).
-                    deadline_daily_fee_delta += &partition_daily_fee_delta;
-
-                    partitions.set(decl.partition, partition).map_err(|e| {
-                        e.downcast_default(
-                            ExitCode::USR_ILLEGAL_STATE,
-                            format!("failed to save partition {:?}", key),
-                        )
-                    })?;
-
-                    // Record the new partition expiration epoch for setting outside this loop
-                    // over declarations.
-                    let prev_epoch_partitions = partitions_by_new_epoch.entry(decl.new_expiration);
-                    let not_exists = matches!(prev_epoch_partitions, Entry::Vacant(_));
-
-                    // Add declaration partition
-                    prev_epoch_partitions.or_default().push(decl.partition);
-                    if not_exists {
-                        // reschedule epoch if the partition for new epoch didn't already exist
-                        epochs_to_reschedule.push(decl.new_expiration);
-                    }
-                }
-
-                deadline.live_power += &deadline_power_delta;
-                deadline.daily_fee += &deadline_daily_fee_delta;
-
-                power_delta += &deadline_power_delta;
-                pledge_delta += &deadline_pledge_delta;
-
-                deadline.partitions = partitions.flush().map_err(|e| {
-                    e.downcast_default(
-                        ExitCode::USR_ILLEGAL_STATE,
-                        format!("failed to save partitions for deadline {}", deadline_idx),
-                    )
-                })?;
-
-                // Record partitions in deadline expiration queue
-                for epoch in epochs_to_reschedule {
-                    let p_idxs = partitions_by_new_epoch.get(&epoch).unwrap();
-                    deadline.add_expiration_partitions(rt.store(), epoch, p_idxs, quant).map_err(
-                        |e| {
-                            e.downcast_default(
-                                ExitCode::USR_ILLEGAL_STATE,
-                                format!(
-                                    "failed to add expiration partitions to \\n                                        deadline {} epoch {}",
-                                    deadline_idx, epoch
-                                ),
-                            )
-                        },
-                    )?;
-                }
-
-                deadlines.update_deadline(policy, rt.store(), deadline_idx, &deadline).map_err(
-                    |e| {
-                        e.downcast_default(
-                            ExitCode::USR_ILLEGAL_STATE,
-                            format!("failed to save deadline {}", deadline_idx),
-                        )
-                    },
-                )?;
-            }
-
-            state.sectors = sectors.amt.flush().map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save sectors")
-            })?;
-            state.save_deadlines(rt.store(), deadlines).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
-            })?;
-
-            Ok((power_delta, pledge_delta))
-        })?;
-
-        // power_delta should be zero in most cases, but can be negative if claims are dropped in
-        // the process of extending sector expirations.
-        request_update_power(rt, power_delta)?;
-
-        // Note: the pledge delta is expected to be zero, since pledge is not re-calculated for the extension.
-        // But in case that ever changes, we can do the right thing here.
-        notify_pledge_changed(rt, &pledge_delta)?;
-        Ok(())
-    }
-
-    /// Marks some sectors as terminated at the present epoch, earlier than their
-    /// scheduled termination, and adds these sectors to the early termination queue.
-    /// This method then processes up to AddressedSectorsMax sectors and
-    /// AddressedPartitionsMax partitions from the early termination queue,
-    /// terminating deals, paying fines, and returning pledge collateral. While
-    /// sectors remain in this queue:
-    ///
-    ///  1. The miner will be unable to withdraw funds.
-    ///  2. The chain will process up to AddressedSectorsMax sectors and
-    ///     AddressedPartitionsMax per epoch until the queue is empty.
-    ///
-    /// The sectors are immediately ignored for Window PoSt proofs, and should be
-    /// masked in the same way as faulty sectors. A miner may not terminate sectors in the
-    /// current deadline or the next deadline to be proven.
-    ///
-    /// This function may be invoked with no new sectors to explicitly process the
-    /// next batch of sectors.
-    fn terminate_sectors(
-        rt: &impl Runtime,
-        params: TerminateSectorsParams,
-    ) -> Result<TerminateSectorsReturn, ActorError> {
-        // Note: this cannot terminate pre-committed but un-proven sectors.
-        // They must be allowed to expire (and deposit burnt).
-
-        let mut to_process = DeadlineSectorMap::new();
-
-        for term in params.terminations {
-            let deadline = term.deadline;
-            let partition = term.partition;
+        for term in params.terminations {
+            let deadline = term.deadline;
+            let partition = term.partition;
 
             to_process.add(rt.policy(), deadline, partition, term.sectors).map_err(|e| {
                 actor_error!(
@@ -2609,6 +2980,8 @@ impl Actor {
     /// The addressed partitions are removed from the deadline, and new ones appended.
     /// The final partition in the deadline is always included in the compaction, whether or not explicitly requested.
     /// Removed sectors are removed from state entirely.
+    /// If no partitions are specified, the deadline's under-filled partitions without faulty or
+    /// unproven sectors are selected automatically (up to the partition limit) and merged.
     /// May not be invoked if the deadline has any un-processed early terminations.
     fn compact_partitions(
         rt: &impl Runtime,
@@ -2671,6 +3044,26 @@ impl Actor {
 
             let mut deadline = deadlines.load_deadline(store, params_deadline)?;
 
+            // If no partitions are specified, merge the deadline's under-filled partitions.
+            let underfilled;
+            let partitions = if partition_count == 0 {
+                underfilled = deadline
+                    .underfilled_partitions(
+                        store,
+                        info.window_post_partition_sectors,
+                        submission_partition_limit,
+                    )
+                    .map_err(|e| {
+                        e.downcast_default(
+                            ExitCode::USR_ILLEGAL_STATE,
+                            format!("failed to select partitions in deadline {}", params_deadline),
+                        )
+                    })?;
+                &underfilled
+            } else {
+                partitions
+            };
+
             let daily_fee_before = deadline.daily_fee.clone();
 
             let mut sectors = Sectors::load(store, &state.sectors).map_err(|e| {
@@ -2696,6 +3089,8 @@ impl Actor {
             sectors.delete_sectors(&dead).map_err(|e| {
                 e.wrap("failed to delete sectors removed during partition compaction")
             })?;
+            state.delete_sector_claims(store, &dead)?;
+            state.delete_proof_refreshes(store, &dead)?;
             state.sectors = sectors.amt.flush().with_context_code(
                 ExitCode::USR_ILLEGAL_STATE,
                 || "failed to save sectors after compaction",
@@ -2777,6 +3172,221 @@ impl Actor {
         Ok(())
     }
 
+    /// Reverts the replica updates of sectors, restoring their original sealed CIDs.
+    /// The data of the sectors is dropped: their deals are terminated, their verified registry
+    /// claims within their minimum term are removed, and they lose the power of the data.
+    /// The miner is penalised the termination fee for each sector, as if it had been
+    /// terminated, and the initial pledge is not reduced.
+    /// A reverted sector cannot be updated again.
+    fn revert_replica_update(
+        rt: &impl Runtime,
+        params: RevertReplicaUpdateParams,
+    ) -> Result<(), ActorError> {
+        let state: State = rt.state()?;
+        let info = get_miner_info(rt.store(), &state)?;
+        rt.validate_immediate_caller_is(
+            info.control_addresses.iter().chain(&[info.worker, info.owner]),
+        )?;
+
+        let curr_epoch = rt.curr_epoch();
+        {
+            let policy = rt.policy();
+            let count = params.sectors.len();
+            if count == 0 {
+                return Err(actor_error!(illegal_argument, "no sectors to revert"));
+            }
+            if count > policy.addressed_sectors_max {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "too many sectors to revert {}, max {}",
+                    count,
+                    policy.addressed_sectors_max
+                ));
+            }
+        }
+
+        let reward_stats = request_current_epoch_block_reward(rt)?;
+        let power_stats = request_current_total_power(rt)?;
+
+        let (power_delta, pledge_delta, to_burn, with_data, with_claims) =
+            rt.transaction(|state: &mut State, rt| {
+                let policy = rt.policy();
+                let store = rt.store();
+                let mut sectors = Sectors::load(store, &state.sectors).map_err(|e| {
+                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors")
+                })?;
+                let mut deadlines = state.load_deadlines(store)?;
+                let proving_period_start = state.current_proving_period_start(policy, curr_epoch);
+
+                // Old and new infos of the reverted sectors, by deadline and partition.
+                let mut updates = BTreeMap::<
+                    u64,
+                    BTreeMap<u64, Vec<(SectorOnChainInfo, SectorOnChainInfo)>>,
+                >::new();
+                let mut with_data = Vec::new();
+                let mut with_claims = Vec::new();
+                let mut penalty = TokenAmount::zero();
+                for sector_number in params.sectors.iter() {
+                    let sector = sectors.must_get(sector_number)?;
+                    let Some(sector_key_cid) = sector.sector_key_cid else {
+                        return Err(actor_error!(
+                            illegal_argument,
+                            "sector {} has no replica update to revert",
+                            sector_number
+                        ));
+                    };
+                    let (dl_idx, p_idx) = state.find_sector(store, sector_number).map_err(|e| {
+                        e.downcast_default(
+                            ExitCode::USR_ILLEGAL_STATE,
+                            format!("failed to find sector {}", sector_number),
+                        )
+                    })?;
+                    if !deadline_is_mutable(policy, proving_period_start, dl_idx, curr_epoch) {
+                        return Err(actor_error!(
+                            illegal_argument,
+                            "cannot revert sector {} in immutable deadline {}",
+                            sector_number,
+                            dl_idx
+                        ));
+                    }
+                    if !state.check_sector_active(store, dl_idx, p_idx, sector_number, true)? {
+                        return Err(actor_error!(
+                            illegal_argument,
+                            "sector {} isn't active",
+                            sector_number
+                        ));
+                    }
+
+                    let new_sector =
+                        revert_sector_info(info.sector_size, &sector, sector_key_cid, curr_epoch);
+                    let fault_fee = pledge_penalty_for_continued_fault(
+                        &reward_stats.this_epoch_reward_smoothed,
+                        &power_stats.quality_adj_power_smoothed,
+                        &qa_power_for_sector(info.sector_size, &sector),
+                    );
+                    penalty += pledge_penalty_for_termination(
+                        &sector.initial_pledge,
+                        curr_epoch - sector.activation,
+                        &fault_fee,
+                    );
+                    if sector.deal_weight.is_positive() || sector.verified_deal_weight.is_positive()
+                    {
+                        with_data.push(sector_number);
+                    }
+                    if sector.verified_deal_weight.is_positive() {
+                        with_claims.push(sector_number);
+                    }
+                    updates
+                        .entry(dl_idx)
+                        .or_default()
+                        .entry(p_idx)
+                        .or_default()
+                        .push((sector, new_sector));
+                }
+
+                let mut power_delta = PowerPair::zero();
+                let mut new_sectors = Vec::new();
+                for (dl_idx, partition_updates) in updates {
+                    let mut deadline = deadlines.load_deadline(store, dl_idx)?;
+                    let mut partitions = deadline
+                        .partitions_amt(store)
+                        .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                            format!("failed to load partitions for deadline {}", dl_idx)
+                        })?;
+                    let quant = state.quant_spec_for_deadline(policy, dl_idx);
+
+                    for (p_idx, sector_updates) in partition_updates {
+                        let mut partition = partitions
+                            .get(p_idx)
+                            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                                format!("failed to load deadline {} partition {}", dl_idx, p_idx)
+                            })?
+                            .cloned()
+                            .ok_or_else(|| {
+                                actor_error!(
+                                    illegal_state,
+                                    "no such deadline {} partition {}",
+                                    dl_idx,
+                                    p_idx
+                                )
+                            })?;
+
+                        let (old_infos, new_infos): (Vec<_>, Vec<_>) =
+                            sector_updates.into_iter().unzip();
+                        let (partition_power_delta, _, partition_daily_fee_delta) = partition
+                            .replace_sectors(store, &old_infos, &new_infos, info.sector_size, quant)
+                            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                                format!(
+                                    "failed to replace sectors at deadline {} partition {}",
+                                    dl_idx, p_idx
+                                )
+                            })?;
+
+                        deadline.live_power += &partition_power_delta;
+                        deadline.daily_fee += &partition_daily_fee_delta;
+                        power_delta += &partition_power_delta;
+
+                        partitions
+                            .set(p_idx, partition)
+                            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                                format!("failed to save deadline {} partition {}", dl_idx, p_idx)
+                            })?;
+                        new_sectors.extend(new_infos);
+                    }
+
+                    deadline.partitions = partitions
+                        .flush()
+                        .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                            format!("failed to save partitions for deadline {}", dl_idx)
+                        })?;
+                    deadlines
+                        .update_deadline(policy, store, dl_idx, &deadline)
+                        .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                            format!("failed to save deadline {}", dl_idx)
+                        })?;
+                }
+
+                sectors.store(new_sectors).map_err(|e| {
+                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to update sector infos")
+                })?;
+                state.sectors = sectors.amt.flush().map_err(|e| {
+                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save sectors")
+                })?;
+                state.save_deadlines(store, deadlines).map_err(|e| {
+                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
+                })?;
+                state.delete_sector_claims(store, &params.sectors)?;
+
+                state
+                    .apply_penalty(&penalty)
+                    .map_err(|e| actor_error!(illegal_state, "failed to apply penalty: {}", e))?;
+                let (to_burn, total_unlocked) = state
+                    .repay_partial_debt_in_priority_order(store, curr_epoch, &rt.current_balance())
+                    .map_err(|e| {
+                        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to repay penalty")
+                    })?;
+                Ok((power_delta, total_unlocked.neg(), to_burn, with_data, with_claims))
+            })?;
+
+        request_update_power(rt, power_delta)?;
+        if !with_data.is_empty() {
+            let terminated_data = BitField::try_from_bits(with_data.iter().copied())
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "invalid sector number")?;
+            request_terminate_deals(rt, curr_epoch, &terminated_data)?;
+        }
+        request_remove_sector_claims(rt, with_claims)?;
+        burn_funds(rt, to_burn)?;
+        notify_pledge_changed(rt, &pledge_delta)?;
+
+        for sector in params.sectors.iter() {
+            emit::sector_update_reverted(rt, sector)?;
+        }
+
+        let state: State = rt.state()?;
+        state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
+        Ok(())
+    }
+
     /// Locks up some amount of a the miner's unlocked balance (including funds received alongside the invoking message).
     fn apply_rewards(rt: &impl Runtime, params: ApplyRewardParams) -> Result<(), ActorError> {
         if params.reward.is_negative() {
@@ -3051,6 +3661,15 @@ impl Actor {
                 None,
                 amount_withdrawn.clone(),
             ))?;
+            if info.beneficiary != info.owner {
+                emit::beneficiary_withdrawal(
+                    rt,
+                    info.beneficiary.id().unwrap(),
+                    &amount_withdrawn,
+                    &info.beneficiary_term.available(rt.curr_epoch()),
+                    info.beneficiary_term.expiration,
+                )?;
+            }
         }
 
         burn_funds(rt, fee_to_burn)?;
@@ -3208,32 +3827,62 @@ impl Actor {
         })
     }
 
+    /// Repays fee debt. When called by the owner or a control address, as much debt as possible
+    /// is repaid from the miner's vesting funds and available balance, including any value sent
+    /// with the message. Any other actor may call this method to fund the repayment, which then
+    /// repays only from the value it sent, refunding any value in excess of the debt.
     fn repay_debt(rt: &impl Runtime) -> Result<(), ActorError> {
-        let (burn_amount, total_unlocked, state) = rt.transaction(|state: &mut State, rt| {
-            let info = get_miner_info(rt.store(), state)?;
-            rt.validate_immediate_caller_is(
-                info.control_addresses.iter().chain(&[info.worker, info.owner]),
-            )?;
-
-            // Repay as much fee debt as possible.
-            let (burn_amount, total_unlocked) = state
-                .repay_partial_debt_in_priority_order(
-                    rt.store(),
-                    rt.curr_epoch(),
-                    &rt.current_balance(),
-                )
-                .map_err(|e| {
-                    e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to unlock fee debt")
-                })?;
+        rt.validate_immediate_caller_accept_any()?;
+        let (burn_amount, total_unlocked, refund, state) =
+            rt.transaction(|state: &mut State, rt| {
+                let info = get_miner_info(rt.store(), state)?;
+                let caller = rt.message().caller();
+                let (burn_amount, total_unlocked, refund) = if info
+                    .control_addresses
+                    .iter()
+                    .chain(&[info.worker, info.owner])
+                    .any(|a| *a == caller)
+                {
+                    // Repay as much fee debt as possible.
+                    let (burn_amount, total_unlocked) = state
+                        .repay_partial_debt_in_priority_order(
+                            rt.store(),
+                            rt.curr_epoch(),
+                            &rt.current_balance(),
+                        )
+                        .map_err(|e| {
+                            e.downcast_default(
+                                ExitCode::USR_ILLEGAL_STATE,
+                                "failed to unlock fee debt",
+                            )
+                        })?;
+                    (burn_amount, total_unlocked, TokenAmount::zero())
+                } else {
+                    // Repay only from the value sent, leaving the miner's own funds untouched,
+                    // and refund the value in excess of the debt.
+                    let value = rt.message().value_received();
+                    let to_burn = cmp::min(&value, &state.fee_debt).clone();
+                    state.fee_debt -= &to_burn;
+                    let refund = value - &to_burn;
+                    (to_burn, TokenAmount::zero(), refund)
+                };
 
-            Ok((burn_amount, total_unlocked, state.clone()))
-        })?;
+                Ok((burn_amount, total_unlocked, refund, state.clone()))
+            })?;
 
         notify_pledge_changed(rt, &total_unlocked.neg())?;
-        burn_funds(rt, burn_amount)?;
+        burn_funds(rt, burn_amount.clone())?;
+        if refund.is_positive() {
+            extract_send_result(rt.send_simple(&rt.message().caller(), METHOD_SEND, None, refund))
+                .map_err(|e| e.wrap("failed to refund value in excess of fee debt"))?;
+        }
 
         state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
-        Ok(())
+        if burn_amount.is_zero() {
+            return Ok(());
+        }
+        let payer = rt.message().caller().id().unwrap();
+        emit::fee_debt_repaid(rt, payer, &burn_amount)
     }
 
     fn on_deferred_cron_event(
@@ -3306,6 +3955,11 @@ struct ExtendExpirationsInner {
     // `maintain` is the space of claims to maintain
     // maintain <= check with equality in the case no claims are dropped
     claims: Option<BTreeMap<SectorNumber, (u64, u64)>>,
+    // Map from sector being extended to the IDs of the claims it drops
+    dropped_claims: BTreeMap<SectorNumber, Vec<ext::verifreg::ClaimID>>,
+    // Map from sector being extended whose claims were not recorded to the IDs of the
+    // claims it maintains, to be recorded
+    unrecorded_claims: BTreeMap<SectorNumber, Vec<ext::verifreg::ClaimID>>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -3333,84 +3987,443 @@ impl From<ExpirationExtension2> for ValidatedExpirationExtension {
     }
 }
 
-fn validate_extension_declarations(
-    rt: &impl Runtime,
-    extensions: Vec<ExpirationExtension2>,
-) -> Result<ExtendExpirationsInner, ActorError> {
-    let mut claim_space_by_sector = BTreeMap::<SectorNumber, (u64, u64)>::new();
+fn validate_extension_declarations(
+    rt: &impl Runtime,
+    extensions: Vec<ExpirationExtension2>,
+) -> Result<ExtendExpirationsInner, ActorError> {
+    let mut claim_space_by_sector = BTreeMap::<SectorNumber, (u64, u64)>::new();
+    let mut dropped_claims = BTreeMap::<SectorNumber, Vec<ext::verifreg::ClaimID>>::new();
+
+    for decl in &extensions {
+        let policy = rt.policy();
+        if decl.deadline >= policy.wpost_period_deadlines {
+            return Err(actor_error!(
+                illegal_argument,
+                "deadline {} not in range 0..{}",
+                decl.deadline,
+                policy.wpost_period_deadlines
+            ));
+        }
+
+        for sc in &decl.sectors_with_claims {
+            if !sc.drop_claims.is_empty() {
+                dropped_claims
+                    .entry(sc.sector_number)
+                    .or_default()
+                    .extend_from_slice(&sc.drop_claims);
+            }
+            let mut drop_claims = sc.drop_claims.clone();
+            let mut all_claim_ids = sc.maintain_claims.clone();
+            all_claim_ids.append(&mut drop_claims);
+            let claims = get_claims(rt, &all_claim_ids)
+                .with_context(|| format!("failed to get claims for sector {}", sc.sector_number))?;
+            let first_drop = sc.maintain_claims.len();
+
+            for (i, claim) in claims.iter().enumerate() {
+                // check provider and sector matches
+                if claim.provider != rt.message().receiver().id().unwrap() {
+                    return Err(actor_error!(
+                        illegal_argument,
+                        "failed to validate declaration sector={}, claim={}, expected claim provider to be {} but found {} ",
+                        sc.sector_number,
+                        all_claim_ids[i],
+                        rt.message().receiver().id().unwrap(),
+                        claim.provider
+                    ));
+                }
+                if claim.sector != sc.sector_number {
+                    return Err(actor_error!(
+                        illegal_argument,
+                        "failed to validate declaration sector={}, claim={} expected claim sector number to be {} but found {} ",
+                        sc.sector_number,
+                        all_claim_ids[i],
+                        sc.sector_number,
+                        claim.sector
+                    ));
+                }
+
+                // If we are not dropping check expiration does not exceed term max
+                let mut maintain_delta: u64 = 0;
+                if i < first_drop {
+                    if decl.new_expiration > claim.term_start + claim.term_max {
+                        return Err(actor_error!(
+                            forbidden,
+                            "failed to validate declaration sector={}, claim={} claim only allows extension to {} but declared new expiration is {}",
+                            sc.sector_number,
+                            sc.maintain_claims[i],
+                            claim.term_start + claim.term_max,
+                            decl.new_expiration
+                        ));
+                    }
+                    maintain_delta = claim.size.0
+                }
+
+                claim_space_by_sector
+                    .entry(sc.sector_number)
+                    .and_modify(|(check, maintain)| {
+                        *check += claim.size.0;
+                        *maintain += maintain_delta;
+                    })
+                    .or_insert((claim.size.0, maintain_delta));
+            }
+        }
+    }
+    Ok(ExtendExpirationsInner {
+        extensions: extensions.into_iter().map(|e2| e2.into()).collect(),
+        claims: Some(claim_space_by_sector),
+        dropped_claims,
+        unrecorded_claims: BTreeMap::new(),
+    })
+}
+
+fn validate_claim_extension_declarations(
+    rt: &impl Runtime,
+    extensions: Vec<ExpirationExtensionByClaims>,
+) -> Result<ExtendExpirationsInner, ActorError> {
+    let policy = rt.policy();
+    let state: State = rt.state()?;
+    let provider = rt.message().receiver().id().unwrap();
+
+    // The recorded claims of each declared sector, with whether each is dropped.
+    let mut sector_claims = Vec::<(usize, SectorNumber, ext::verifreg::ClaimID, bool)>::new();
+    let mut dropped_claims = BTreeMap::<SectorNumber, Vec<ext::verifreg::ClaimID>>::new();
+    // The declared claims of sectors whose claims are not recorded, with whether each is dropped.
+    // Their sectors are known only once the claims are loaded.
+    let mut supplied_claims = Vec::<(usize, ext::verifreg::ClaimID, bool)>::new();
+    let mut unrecorded_sectors = Vec::<BTreeSet<SectorNumber>>::with_capacity(extensions.len());
+    let mut supplied_ids = BTreeSet::<ext::verifreg::ClaimID>::new();
+    for (i, decl) in extensions.iter().enumerate() {
+        if decl.deadline >= policy.wpost_period_deadlines {
+            return Err(actor_error!(
+                illegal_argument,
+                "deadline {} not in range 0..{}",
+                decl.deadline,
+                policy.wpost_period_deadlines
+            ));
+        }
+        let sectors = decl.sectors.validate().map_err(|e| {
+            actor_error!(illegal_argument, "failed to validate sectors bitfield: {}", e)
+        })?;
+
+        let mut undeclared_drops: BTreeSet<_> = decl.drop_claims.iter().copied().collect();
+        let mut unrecorded = BTreeSet::new();
+        for sector_number in sectors.iter() {
+            let claim_ids = state.get_sector_claims(rt.store(), sector_number)?;
+            if claim_ids.is_empty() {
+                unrecorded.insert(sector_number);
+            }
+            for claim_id in claim_ids {
+                let drop = undeclared_drops.remove(&claim_id);
+                if drop {
+                    dropped_claims.entry(sector_number).or_default().push(claim_id);
+                }
+                sector_claims.push((i, sector_number, claim_id, drop));
+            }
+        }
+        // Any remaining claims must belong to sectors whose claims are not recorded,
+        // and each may be declared only once so that its space is counted once.
+        let supplied = decl
+            .maintain_claims
+            .iter()
+            .map(|id| (*id, false))
+            .chain(undeclared_drops.into_iter().map(|id| (id, true)));
+        for (claim_id, drop) in supplied {
+            if !supplied_ids.insert(claim_id) {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "claim {} declared more than once",
+                    claim_id
+                ));
+            }
+            supplied_claims.push((i, claim_id, drop));
+        }
+        unrecorded_sectors.push(unrecorded);
+    }
+
+    let mut unrecorded_claims = BTreeMap::<SectorNumber, Vec<ext::verifreg::ClaimID>>::new();
+    let mut claim_space_by_sector = BTreeMap::<SectorNumber, (u64, u64)>::new();
+    if !sector_claims.is_empty() || !supplied_claims.is_empty() {
+        let claim_ids: Vec<_> = sector_claims
+            .iter()
+            .map(|(_, _, id, _)| *id)
+            .chain(supplied_claims.iter().map(|(_, id, _)| *id))
+            .collect();
+        let claims = get_claims(rt, &claim_ids).context("failed to get claims for sectors")?;
+        let (recorded, supplied) = claims.split_at(sector_claims.len());
+        for ((_, sector_number, claim_id, _), claim) in sector_claims.iter().zip(recorded) {
+            if claim.provider != provider || claim.sector != *sector_number {
+                return Err(actor_error!(
+                    illegal_state,
+                    "claim {} of provider {} sector {} is recorded for sector {}",
+                    claim_id,
+                    claim.provider,
+                    claim.sector,
+                    sector_number
+                ));
+            }
+        }
+        for ((i, claim_id, drop), claim) in supplied_claims.iter().zip(supplied) {
+            if claim.provider != provider || !unrecorded_sectors[*i].contains(&claim.sector) {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "claim {} of provider {} sector {} is not a claim of a sector without recorded claims in the declaration",
+                    claim_id,
+                    claim.provider,
+                    claim.sector
+                ));
+            }
+            if *drop {
+                dropped_claims.entry(claim.sector).or_default().push(*claim_id);
+            } else {
+                unrecorded_claims.entry(claim.sector).or_default().push(*claim_id);
+            }
+            sector_claims.push((*i, claim.sector, *claim_id, *drop));
+        }
+
+        for ((i, sector_number, claim_id, drop), claim) in sector_claims.iter().zip(&claims) {
+            // If we are not dropping check expiration does not exceed term max
+            let mut maintain_delta: u64 = 0;
+            if !drop {
+                let new_expiration = extensions[*i].new_expiration;
+                if new_expiration > claim.term_start + claim.term_max {
+                    return Err(actor_error!(
+                        forbidden,
+                        "failed to validate declaration sector={}, claim={} claim only allows extension to {} but declared new expiration is {}",
+                        sector_number,
+                        claim_id,
+                        claim.term_start + claim.term_max,
+                        new_expiration
+                    ));
+                }
+                maintain_delta = claim.size.0;
+            }
+
+            let (check, maintain) = claim_space_by_sector.entry(*sector_number).or_default();
+            *check += claim.size.0;
+            *maintain += maintain_delta;
+        }
+    }
+
+    Ok(ExtendExpirationsInner {
+        extensions: extensions
+            .into_iter()
+            .map(|e| ValidatedExpirationExtension {
+                deadline: e.deadline,
+                partition: e.partition,
+                sectors: e.sectors,
+                new_expiration: e.new_expiration,
+            })
+            .collect(),
+        claims: Some(claim_space_by_sector),
+        dropped_claims,
+        unrecorded_claims,
+    })
+}
+
+/// Extends the expirations of the sectors in validated declarations, returning the resulting
+/// power and pledge deltas.
+fn apply_expiration_extensions(
+    rt: &impl Runtime,
+    state: &mut State,
+    info: &MinerInfo,
+    inner: &ExtendExpirationsInner,
+) -> Result<(PowerPair, TokenAmount), ActorError> {
+    let curr_epoch = rt.curr_epoch();
+    let circulating_supply = rt.total_fil_circ_supply();
+
+    /* Loop over sectors and do extension */
+    let mut deadlines =
+        state.load_deadlines(rt.store()).map_err(|e| e.wrap("failed to load deadlines"))?;
+
+    // Group declarations by deadline, and remember iteration order.
+    //
+    let mut decls_by_deadline: Vec<_> = std::iter::repeat_with(Vec::new)
+        .take(rt.policy().wpost_period_deadlines as usize)
+        .collect();
+    let mut deadlines_to_load = Vec::<u64>::new();
+    for decl in &inner.extensions {
+        // the deadline indices are already checked.
+        let decls = &mut decls_by_deadline[decl.deadline as usize];
+        if decls.is_empty() {
+            deadlines_to_load.push(decl.deadline);
+        }
+        decls.push(decl);
+    }
+
+    let mut sectors = Sectors::load(rt.store(), &state.sectors).map_err(|e| {
+        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors array")
+    })?;
+
+    let mut power_delta = PowerPair::zero();
+    let mut pledge_delta = TokenAmount::zero();
+
+    for deadline_idx in deadlines_to_load {
+        let policy = rt.policy();
+        let mut deadline = deadlines.load_deadline(rt.store(), deadline_idx)?;
+
+        let mut partitions = deadline.partitions_amt(rt.store()).map_err(|e| {
+            e.downcast_default(
+                ExitCode::USR_ILLEGAL_STATE,
+                format!("failed to load partitions for deadline {}", deadline_idx),
+            )
+        })?;
+
+        let quant = state.quant_spec_for_deadline(policy, deadline_idx);
+
+        let mut deadline_power_delta = PowerPair::zero();
+        let mut deadline_pledge_delta = TokenAmount::zero();
+        let mut deadline_daily_fee_delta = TokenAmount::zero();
+
+        // Group modified partitions by epoch to which they are extended. Duplicates are ok.
+        let mut partitions_by_new_epoch = BTreeMap::<ChainEpoch, Vec<u64>>::new();
+        let mut epochs_to_reschedule = Vec::<ChainEpoch>::new();
+
+        for decl in &mut decls_by_deadline[deadline_idx as usize] {
+            let key = PartitionKey { deadline: deadline_idx, partition: decl.partition };
+
+            let mut partition = partitions
+                .get(decl.partition)
+                .map_err(|e| {
+                    e.downcast_default(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        format!("failed to load partition {:?}", key),
+                    )
+                })?
+                .cloned()
+                .ok_or_else(|| actor_error!(not_found, "no such partition {:?}", key))?;
+
+            let old_sectors = sectors
+                .load_sectors(&decl.sectors)
+                .map_err(|e| e.wrap("failed to load sectors"))?;
+            let new_sectors: Vec<SectorOnChainInfo> = old_sectors
+                .iter()
+                .map(|sector| match &inner.claims {
+                    None => Err(actor_error!(
+                        unspecified,
+                        "extend2 always specifies (potentially empty) claim mapping"
+                    )),
+                    Some(claim_space_by_sector) => extend_sector_committment(
+                        rt.policy(),
+                        rt.network_version(),
+                        curr_epoch,
+                        &circulating_supply,
+                        decl.new_expiration,
+                        sector,
+                        info.sector_size,
+                        claim_space_by_sector,
+                    ),
+                })
+                .collect::<Result<_, _>>()?;
+
+            // Overwrite sector infos.
+            sectors.store(new_sectors.clone()).map_err(|e| {
+                e.downcast_default(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    format!("failed to update sectors {:?}", decl.sectors),
+                )
+            })?;
+
+            // Remove old sectors from partition and assign new sectors.
+            let (partition_power_delta, partition_pledge_delta, partition_daily_fee_delta) =
+                partition
+                    .replace_sectors(
+                        rt.store(),
+                        &old_sectors,
+                        &new_sectors,
+                        info.sector_size,
+                        quant,
+                    )
+                    .map_err(|e| {
+                        e.downcast_default(
+                            ExitCode::USR_ILLEGAL_STATE,
+                            format!("failed to replace sector expirations at {:?}", key),
+                        )
+                    })?;
+
+            deadline_power_delta += &partition_power_delta;
+            // expected to be zero, see note below.
+            deadline_pledge_delta += &partition_pledge_delta;
+            // non-zero when extending sectors that previously paid no fees (e.g., because
+            // they were sealed before we started charging fees).
+            deadline_daily_fee_delta += &partition_daily_fee_delta;
+
+            partitions.set(decl.partition, partition).map_err(|e| {
+                e.downcast_default(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    format!("failed to save partition {:?}", key),
+                )
+            })?;
+
+            // Record the new partition expiration epoch for setting outside this loop
+            // over declarations.
+            let prev_epoch_partitions = partitions_by_new_epoch.entry(decl.new_expiration);
+            let not_exists = matches!(prev_epoch_partitions, Entry::Vacant(_));
 
-    for decl in &extensions {
-        let policy = rt.policy();
-        if decl.deadline >= policy.wpost_period_deadlines {
-            return Err(actor_error!(
-                illegal_argument,
-                "deadline {} not in range 0..{}",
-                decl.deadline,
-                policy.wpost_period_deadlines
-            ));
+            // Add declaration partition
+            prev_epoch_partitions.or_default().push(decl.partition);
+            if not_exists {
+                // reschedule epoch if the partition for new epoch didn't already exist
+                epochs_to_reschedule.push(decl.new_expiration);
+            }
         }
 
-        for sc in &decl.sectors_with_claims {
-            let mut drop_claims = sc.drop_claims.clone();
-            let mut all_claim_ids = sc.maintain_claims.clone();
-            all_claim_ids.append(&mut drop_claims);
-            let claims = get_claims(rt, &all_claim_ids)
-                .with_context(|| format!("failed to get claims for sector {}", sc.sector_number))?;
-            let first_drop = sc.maintain_claims.len();
+        deadline.live_power += &deadline_power_delta;
+        deadline.daily_fee += &deadline_daily_fee_delta;
 
-            for (i, claim) in claims.iter().enumerate() {
-                // check provider and sector matches
-                if claim.provider != rt.message().receiver().id().unwrap() {
-                    return Err(actor_error!(
-                        illegal_argument,
-                        "failed to validate declaration sector={}, claim={}, expected claim provider to be {} but found {} ",
-                        sc.sector_number,
-                        all_claim_ids[i],
-                        rt.message().receiver().id().unwrap(),
-                        claim.provider
-                    ));
-                }
-                if claim.sector != sc.sector_number {
-                    return Err(actor_error!(
-                        illegal_argument,
-                        "failed to validate declaration sector={}, claim={} expected claim sector number to be {} but found {} ",
-                        sc.sector_number,
-                        all_claim_ids[i],
-                        sc.sector_number,
-                        claim.sector
-                    ));
-                }
+        power_delta += &deadline_power_delta;
+        pledge_delta += &deadline_pledge_delta;
 
-                // If we are not dropping check expiration does not exceed term max
-                let mut maintain_delta: u64 = 0;
-                if i < first_drop {
-                    if decl.new_expiration > claim.term_start + claim.term_max {
-                        return Err(actor_error!(
-                            forbidden,
-                            "failed to validate declaration sector={}, claim={} claim only allows extension to {} but declared new expiration is {}",
-                            sc.sector_number,
-                            sc.maintain_claims[i],
-                            claim.term_start + claim.term_max,
-                            decl.new_expiration
-                        ));
-                    }
-                    maintain_delta = claim.size.0
-                }
+        deadline.partitions = partitions.flush().map_err(|e| {
+            e.downcast_default(
+                ExitCode::USR_ILLEGAL_STATE,
+                format!("failed to save partitions for deadline {}", deadline_idx),
+            )
+        })?;
 
-                claim_space_by_sector
-                    .entry(sc.sector_number)
-                    .and_modify(|(check, maintain)| {
-                        *check += claim.size.0;
-                        *maintain += maintain_delta;
-                    })
-                    .or_insert((claim.size.0, maintain_delta));
-            }
+        // Record partitions in deadline expiration queue
+        for epoch in epochs_to_reschedule {
+            let p_idxs = partitions_by_new_epoch.get(&epoch).unwrap();
+            deadline.add_expiration_partitions(rt.store(), epoch, p_idxs, quant).map_err(
+                |e| {
+                    e.downcast_default(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        format!(
+                            "failed to add expiration partitions to \\n                                        deadline {} epoch {}",
+                            deadline_idx, epoch
+                        ),
+                    )
+                },
+            )?;
         }
+
+        deadlines.update_deadline(policy, rt.store(), deadline_idx, &deadline).map_err(|e| {
+            e.downcast_default(
+                ExitCode::USR_ILLEGAL_STATE,
+                format!("failed to save deadline {}", deadline_idx),
+            )
+        })?;
     }
-    Ok(ExtendExpirationsInner {
-        extensions: extensions.into_iter().map(|e2| e2.into()).collect(),
-        claims: Some(claim_space_by_sector),
-    })
+
+    state.sectors = sectors
+        .amt
+        .flush()
+        .map_err(|e| e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save sectors"))?;
+    state
+        .save_deadlines(rt.store(), deadlines)
+        .map_err(|e| e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines"))?;
+
+    // Dropped claims no longer contribute to the sectors' verified deal weight.
+    let mut remaining_claims = Vec::with_capacity(inner.dropped_claims.len());
+    for (sector_number, dropped) in &inner.dropped_claims {
+        let mut claim_ids = state.get_sector_claims(rt.store(), *sector_number)?;
+        claim_ids.retain(|id| !dropped.contains(id));
+        remaining_claims.push((*sector_number, claim_ids));
+    }
+    state.put_sector_claims(rt.store(), remaining_claims)?;
+    // Claims declared for sectors whose claims were not recorded are recorded from now on.
+    state.put_sector_claims(rt.store(), inner.unrecorded_claims.clone())?;
+
+    Ok((power_delta, pledge_delta))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -3688,6 +4701,14 @@ where
             ));
         }
 
+        if sector_info.flags.contains(SectorOnChainInfoFlags::REPLICA_UPDATE_REVERTED) {
+            return Err(actor_error!(
+                illegal_argument,
+                "cannot update sector with a reverted replica update, skipping sector {}",
+                update.sector_number
+            ));
+        }
+
         if (&sector_info.deal_weight + &sector_info.verified_deal_weight) != DealWeight::zero() {
             return Err(actor_error!(
                 illegal_argument,
@@ -3740,7 +4761,7 @@ fn update_replica_states<BS>(
     expected_count: usize,
     sectors: &mut Sectors<BS>,
     sector_size: SectorSize,
-) -> Result<(PowerPair, TokenAmount), ActorError>
+) -> Result<(PowerPair, TokenAmount, Vec<SectorActivationSummary>), ActorError>
 where
     BS: Blockstore,
 {
@@ -3757,10 +4778,12 @@ where
     };
     let mut power_delta = PowerPair::zero();
     let mut pledge_delta = TokenAmount::zero();
+    let mut summaries = Vec::with_capacity(expected_count);
 
     rt.transaction(|state: &mut State, rt| {
         let mut deadlines = state.load_deadlines(rt.store())?;
         let mut new_sectors = Vec::with_capacity(expected_count);
+        let mut new_sector_claims = Vec::with_capacity(expected_count);
         // Process updates grouped by deadline.
         for (&dl_idx, updates) in updates_by_deadline {
             let mut deadline = deadlines.load_deadline(rt.store(), dl_idx)?;
@@ -3837,6 +4860,13 @@ where
                     },
                 )?;
 
+                summaries.push(summarize_sector(
+                    sector_size,
+                    &new_sector_info,
+                    update.activated_data.claim_ids.len(),
+                ));
+                new_sector_claims
+                    .push((new_sector_info.sector_number, update.activated_data.claim_ids.clone()));
                 new_sectors.push(new_sector_info);
             } // End loop over declarations in one deadline.
 
@@ -3867,6 +4897,19 @@ where
             ));
         }
 
+        // Updating a sector re-seals it, so its proof is refreshed.
+        if rt.policy().proof_refresh_enabled {
+            let refresh = ProofRefresh {
+                proven_epoch: rt.curr_epoch(),
+                refresh_epoch: rt.curr_epoch() + rt.policy().proof_refresh_period,
+            };
+            state.schedule_proof_refreshes(
+                rt.policy(),
+                rt.store(),
+                new_sectors.iter().map(|s| (s.sector_number, refresh)),
+            )?;
+        }
+
         // Overwrite sector infos.
         sectors.store(new_sectors).map_err(|e| {
             e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to update sector infos")
@@ -3878,6 +4921,7 @@ where
         state.save_deadlines(rt.store(), deadlines).map_err(|e| {
             e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to save deadlines")
         })?;
+        state.put_sector_claims(rt.store(), new_sector_claims)?;
 
         // Update pledge.
         let current_balance = rt.current_balance();
@@ -3902,7 +4946,7 @@ where
         state.check_balance_invariants(&current_balance).map_err(balance_invariants_broken)?;
         Ok(())
     })?;
-    Ok((power_delta, pledge_delta))
+    Ok((power_delta, pledge_delta, summaries))
 }
 
 // Builds a new sector info representing newly activated data in an existing sector.
@@ -3966,6 +5010,35 @@ fn update_existing_sector_info(
     new_sector_info
 }
 
+// Builds a new sector info for a sector with its replica update reverted, restoring its
+// original sealed CID and dropping the weight of its data.
+fn revert_sector_info(
+    sector_size: SectorSize,
+    sector_info: &SectorOnChainInfo,
+    sector_key_cid: Cid,
+    curr_epoch: ChainEpoch,
+) -> SectorOnChainInfo {
+    let mut new_sector_info = sector_info.clone();
+    new_sector_info.sealed_cid = sector_key_cid;
+    new_sector_info.sector_key_cid = None;
+    new_sector_info.power_base_epoch = curr_epoch;
+    new_sector_info.deal_weight = DealWeight::zero();
+    new_sector_info.verified_deal_weight = DealWeight::zero();
+    new_sector_info.expected_day_reward = None;
+    new_sector_info.replaced_day_reward = None;
+    new_sector_info.expected_storage_pledge = None;
+    new_sector_info.flags.set(SectorOnChainInfoFlags::REPLICA_UPDATE_REVERTED, true);
+
+    let old_qa_power = qa_power_for_sector(sector_size, sector_info);
+    let new_qa_power = qa_power_for_sector(sector_size, &new_sector_info);
+    if !new_sector_info.daily_fee.is_zero() && old_qa_power != new_qa_power {
+        // adjust the daily_fee by the same proportion as the power changed
+        new_sector_info.daily_fee =
+            daily_proof_fee_adjust(&new_sector_info.daily_fee, &old_qa_power, &new_qa_power)
+    }
+    new_sector_info
+}
+
 // Note: We're using the current power+epoch reward, rather than at time of termination.
 fn process_early_terminations(
     rt: &impl Runtime,
@@ -3977,6 +5050,7 @@ fn process_early_terminations(
 > {
     let mut terminated_sector_nums = vec![];
     let mut sectors_with_data = vec![];
+    let mut sectors_with_claims = vec![];
     let (result, more, penalty, pledge_delta) = rt.transaction(|state: &mut State, rt| {
         let store = rt.store();
         let policy = rt.policy();
@@ -4026,6 +5100,9 @@ fn process_early_terminations(
                 if sector.deal_weight.is_positive() || sector.verified_deal_weight.is_positive() {
                     sectors_with_data.push(sector.sector_number);
                 }
+                if sector.verified_deal_weight.is_positive() {
+                    sectors_with_claims.push(sector.sector_number);
+                }
             }
         }
 
@@ -4077,6 +5154,7 @@ fn process_early_terminations(
     let terminated_data = BitField::try_from_bits(sectors_with_data)
         .context_code(ExitCode::USR_ILLEGAL_STATE, "invalid sector number")?;
     request_terminate_deals(rt, rt.curr_epoch(), &terminated_data)?;
+    request_remove_sector_claims(rt, sectors_with_claims)?;
 
     for sector in terminated_sector_nums {
         emit::sector_terminated(rt, sector)?;
@@ -4108,7 +5186,7 @@ fn handle_proving_deadline(
         let mut info = get_miner_info(rt.store(), state)?;
         process_pending_worker(&mut info, rt, state)?;
 
-        let deposit_to_burn = state
+        let (deposit_to_burn, expired_precommits) = state
             .cleanup_expired_pre_commits(policy, rt.store(), rt.curr_epoch())
             .map_err(|e| {
                 e.downcast_default(
@@ -4125,6 +5203,18 @@ fn handle_proving_deadline(
             rt.message().receiver(),
             deposit_to_burn
         );
+        for (sector, deposit) in &expired_precommits {
+            emit::precommit_expired(rt, *sector, deposit)?;
+        }
+
+        // Report sectors whose proofs were not refreshed by their scheduled epoch.
+        if policy.proof_refresh_enabled {
+            for (sector, refresh_epoch) in
+                state.pop_expired_proofs(policy, rt.store(), curr_epoch)?
+            {
+                emit::sector_proof_expired(rt, sector, refresh_epoch)?;
+            }
+        }
 
         // Record whether or not we _had_ early terminations in the queue before this method.
         // That way, don't re-schedule a cron callback if one is already scheduled.
@@ -4373,6 +5463,28 @@ fn request_terminate_deals(
     Ok(())
 }
 
+/// Requests the verified registry remove the claims for data in terminated sectors
+/// whose minimum term has not been met.
+/// A failure is logged rather than aborting the termination.
+fn request_remove_sector_claims(
+    rt: &impl Runtime,
+    sectors: Vec<SectorNumber>,
+) -> Result<(), ActorError> {
+    if sectors.is_empty() {
+        return Ok(());
+    }
+    let res = extract_send_result(rt.send_simple(
+        &VERIFIED_REGISTRY_ACTOR_ADDR,
+        ext::verifreg::REMOVE_SECTOR_CLAIMS_METHOD,
+        IpldBlock::serialize_cbor(&ext::verifreg::RemoveSectorClaimsParams { sectors })?,
+        TokenAmount::zero(),
+    ));
+    if let Err(e) = res {
+        error!("failed to remove claims for terminated sectors: {}", e);
+    }
+    Ok(())
+}
+
 fn schedule_early_termination_work(rt: &impl Runtime) -> Result<(), ActorError> {
     info!("scheduling early terminations with cron...");
     enroll_cron_event(
@@ -4387,6 +5499,246 @@ fn have_pending_early_terminations(state: &State) -> bool {
     !no_early_terminations
 }
 
+/// Validates the parameters of a Window PoSt submission that don't depend on the miner's state.
+fn validate_windowed_post_params(
+    policy: &Policy,
+    params: &SubmitWindowedPoStParams,
+) -> Result<(), ActorError> {
+    if params.proofs.len() != 1 {
+        return Err(actor_error!(
+            illegal_argument,
+            "expected exactly one proof, got {}",
+            params.proofs.len()
+        ));
+    }
+
+    if check_valid_post_proof_type(policy, params.proofs[0].post_proof).is_err() {
+        return Err(actor_error!(
+            illegal_argument,
+            "proof type {:?} not allowed",
+            params.proofs[0].post_proof
+        ));
+    }
+
+    if params.deadline >= policy.wpost_period_deadlines {
+        return Err(actor_error!(
+            illegal_argument,
+            "invalid deadline {} of {}",
+            params.deadline,
+            policy.wpost_period_deadlines
+        ));
+    }
+
+    if params.chain_commit_rand.0.len() > RANDOMNESS_LENGTH {
+        return Err(actor_error!(
+            illegal_argument,
+            "expected at most {} bytes of randomness, got {}",
+            RANDOMNESS_LENGTH,
+            params.chain_commit_rand.0.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Validates a Window PoSt submission against the miner's state and the chain,
+/// returning the current deadline. The proof itself is not verified.
+fn validate_windowed_post_state(
+    rt: &impl Runtime,
+    state: &State,
+    info: &MinerInfo,
+    params: &SubmitWindowedPoStParams,
+) -> Result<DeadlineInfo, ActorError> {
+    let current_epoch = rt.curr_epoch();
+    let max_proof_size = info.window_post_proof_type.proof_size().map_err(|e| {
+        actor_error!(illegal_state, "failed to determine max window post proof size: {}", e)
+    })?;
+
+    // Make sure the miner is using the correct proof type.
+    if params.proofs[0].post_proof != info.window_post_proof_type {
+        return Err(actor_error!(
+            illegal_argument,
+            "expected proof of type {:?}, got {:?}",
+            info.window_post_proof_type,
+            params.proofs[0].post_proof
+        ));
+    }
+
+    // Make sure the proof size doesn't exceed the max. We could probably check for an exact match, but this is safer.
+    let max_size = max_proof_size * params.partitions.len();
+    if params.proofs[0].proof_bytes.len() > max_size {
+        return Err(actor_error!(
+            illegal_argument,
+            "expected proof to be smaller than {} bytes",
+            max_size
+        ));
+    }
+
+    // Validate that the miner didn't try to prove too many partitions at once.
+    let submission_partition_limit = cmp::min(
+        load_partitions_sectors_max(rt.policy(), info.window_post_partition_sectors),
+        rt.policy().posted_partitions_max,
+    );
+
+    if params.partitions.len() as u64 > submission_partition_limit {
+        return Err(actor_error!(
+            illegal_argument,
+            "too many partitions {}, limit {}",
+            params.partitions.len(),
+            submission_partition_limit
+        ));
+    }
+    let current_deadline = state.deadline_info(rt.policy(), current_epoch);
+
+    // Check that the miner state indicates that the current proving deadline has started.
+    // This should only fail if the cron actor wasn't invoked, and matters only in case that it hasn't been
+    // invoked for a whole proving period, and hence the missed PoSt submissions from the prior occurrence
+    // of this deadline haven't been processed yet.
+    if !current_deadline.is_open() {
+        return Err(actor_error!(
+            illegal_state,
+            "proving period {} not yet open at {}",
+            current_deadline.period_start,
+            current_epoch
+        ));
+    }
+
+    // The miner may only submit a proof for the current deadline.
+    if params.deadline != current_deadline.index {
+        return Err(actor_error!(
+            illegal_argument,
+            "invalid deadline {} at epoch {}, expected {}",
+            params.deadline,
+            current_epoch,
+            current_deadline.index
+        ));
+    }
+
+    // Verify that the PoSt was committed to the chain at most
+    // WPoStChallengeLookback+WPoStChallengeWindow in the past.
+    if params.chain_commit_epoch < current_deadline.challenge {
+        return Err(actor_error!(
+            illegal_argument,
+            "expected chain commit epoch {} to be after {}",
+            params.chain_commit_epoch,
+            current_deadline.challenge
+        ));
+    }
+
+    if params.chain_commit_epoch >= current_epoch {
+        return Err(actor_error!(
+            illegal_argument,
+            "chain commit epoch {} must be less than the current epoch {}",
+            params.chain_commit_epoch,
+            current_epoch
+        ));
+    }
+
+    // Verify the chain commit randomness
+    let comm_rand = rt.get_randomness_from_tickets(
+        DomainSeparationTag::PoStChainCommit,
+        params.chain_commit_epoch,
+        &[],
+    )?;
+    if Randomness(comm_rand.into()) != params.chain_commit_rand {
+        return Err(actor_error!(illegal_argument, "post commit randomness mismatched"));
+    }
+    Ok(current_deadline)
+}
+
+/// Returns the info for the most recent challenge window of a deadline, failing if
+/// that window's proofs are not currently open to dispute.
+fn disputed_deadline_info(
+    policy: &Policy,
+    st: &State,
+    deadline: u64,
+    current_epoch: ChainEpoch,
+) -> Result<DeadlineInfo, ActorError> {
+    let dl_info = st.deadline_info(policy, current_epoch);
+    if !deadline_available_for_optimistic_post_dispute(
+        policy,
+        dl_info.period_start,
+        deadline,
+        current_epoch,
+    ) {
+        return Err(actor_error!(
+            forbidden,
+            "can only dispute window posts during the dispute window \
+            ({} epochs after the challenge window closes)",
+            policy.wpost_dispute_window
+        ));
+    }
+
+    // Find the proving period start for the deadline in question.
+    let mut pp_start = dl_info.period_start;
+    if dl_info.index < deadline {
+        pp_start -= policy.wpost_proving_period
+    }
+    Ok(new_deadline_info(policy, pp_start, deadline, current_epoch))
+}
+
+/// Verifies an optimistically accepted window PoSt of some partitions of a deadline, and if
+/// the proof is invalid records faults for the sectors it proved.
+/// Returns None if the proof is valid, or otherwise the power delta from the new faults
+/// and the power to be penalised for the invalid proof.
+fn dispute_post(
+    rt: &impl Runtime,
+    info: &MinerInfo,
+    deadline: &mut Deadline,
+    target_deadline: &DeadlineInfo,
+    partitions: BitField,
+    proofs: Vec<PoStProof>,
+) -> Result<Option<(PowerPair, PowerPair)>, ActorError> {
+    let policy = rt.policy();
+
+    // Load the partition info we need for the dispute.
+    let mut dispute_info =
+        deadline.load_partitions_for_dispute(rt.store(), partitions).map_err(|e| {
+            e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load partition for dispute")
+        })?;
+
+    // This includes power that is no longer active (e.g., due to sector terminations).
+    // It must only be used for penalty calculations, not power adjustments.
+    let penalised_power = dispute_info.disputed_power.clone();
+
+    // Load sectors for the dispute.
+    let sectors = Sectors::load(rt.store(), &deadline.sectors_snapshot).map_err(|e| {
+        e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load sectors array")
+    })?;
+    let sector_infos = sectors
+        .load_for_proof(&dispute_info.all_sector_nos, &dispute_info.ignored_sector_nos)
+        .map_err(|e| {
+            e.downcast_default(
+                ExitCode::USR_ILLEGAL_STATE,
+                "failed to load sectors to dispute window post",
+            )
+        })?;
+
+    // Check proof, the dispute fails if validation succeeds.
+    if verify_windowed_post(rt, target_deadline.challenge, &sector_infos, proofs)? {
+        return Ok(None);
+    }
+    info!("Successfully disputed post- window post was invalid");
+
+    // Ok, now we record faults. This always works because
+    // we don't allow compaction/moving sectors during the
+    // challenge window.
+    //
+    // However, some of these sectors may have been
+    // terminated. That's fine, we'll skip them.
+    let fault_expiration_epoch = target_deadline.last() + policy.fault_max_age;
+    let power_delta = deadline
+        .record_faults(
+            rt.store(),
+            &sectors,
+            info.sector_size,
+            quant_spec_for_deadline(policy, target_deadline),
+            fault_expiration_epoch,
+            &mut dispute_info.disputed_sectors,
+        )
+        .map_err(|e| e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to declare faults"))?;
+    Ok(Some((power_delta, penalised_power)))
+}
+
 // returns true if valid, false if invalid, error if failed to validate either way!
 fn verify_windowed_post(
     rt: &impl Runtime,
@@ -5014,6 +6366,22 @@ pub fn power_for_sector(sector_size: SectorSize, sector: &SectorOnChainInfo) ->
     }
 }
 
+/// Summarizes the power, pledge and fee of a newly activated or updated sector,
+/// with the number of claims recorded for it.
+fn summarize_sector(
+    sector_size: SectorSize,
+    sector: &SectorOnChainInfo,
+    claims: usize,
+) -> SectorActivationSummary {
+    SectorActivationSummary {
+        sector: sector.sector_number,
+        power: power_for_sector(sector_size, sector),
+        initial_pledge: sector.initial_pledge.clone(),
+        daily_fee: sector.daily_fee.clone(),
+        claims: claims as u64,
+    }
+}
+
 /// Returns the sum of the raw byte and quality-adjusted power for sectors.
 pub fn power_for_sectors(sector_size: SectorSize, sectors: &[SectorOnChainInfo]) -> PowerPair {
     let qa = sectors.iter().map(|s| qa_power_for_sector(sector_size, s)).sum();
@@ -5140,16 +6508,18 @@ fn activate_new_sector_infos(
     data_activations: Vec<DataActivationOutput>,
     pledge_inputs: &NetworkPledgeInputs,
     info: &MinerInfo,
-) -> Result<(), ActorError> {
+) -> Result<Vec<SectorActivationSummary>, ActorError> {
     let activation_epoch = rt.curr_epoch();
 
-    let (total_pledge, newly_vested) = rt.transaction(|state: &mut State, rt| {
+    let (total_pledge, newly_vested, summaries) = rt.transaction(|state: &mut State, rt| {
         let policy = rt.policy();
         let store = rt.store();
 
         let mut new_sector_numbers = Vec::<SectorNumber>::with_capacity(data_activations.len());
         let mut deposit_to_unlock = TokenAmount::zero();
         let mut new_sectors = Vec::<SectorOnChainInfo>::new();
+        let mut new_sector_claims = Vec::with_capacity(data_activations.len());
+        let mut summaries = Vec::with_capacity(data_activations.len());
         let mut total_pledge = TokenAmount::zero();
 
         for (pci, deal_spaces) in precommits.iter().zip(data_activations) {
@@ -5205,12 +6575,30 @@ fn activate_new_sector_infos(
             };
 
             new_sector_numbers.push(new_sector_info.sector_number);
+            summaries.push(summarize_sector(
+                info.sector_size,
+                &new_sector_info,
+                deal_spaces.claim_ids.len(),
+            ));
+            new_sector_claims.push((new_sector_info.sector_number, deal_spaces.claim_ids));
             new_sectors.push(new_sector_info);
         }
 
         state.put_sectors(store, new_sectors.clone()).map_err(|e| {
             e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to put new sectors")
         })?;
+        state.put_sector_claims(store, new_sector_claims)?;
+        if policy.proof_refresh_enabled {
+            let refresh = ProofRefresh {
+                proven_epoch: activation_epoch,
+                refresh_epoch: activation_epoch + policy.proof_refresh_period,
+            };
+            state.schedule_proof_refreshes(
+                policy,
+                store,
+                new_sector_numbers.iter().map(|&sno| (sno, refresh)),
+            )?;
+        }
         state.delete_precommitted_sectors(store, &new_sector_numbers)?;
         state
             .assign_sectors_to_deadlines(
@@ -5252,13 +6640,13 @@ fn activate_new_sector_infos(
 
         state.check_balance_invariants(&rt.current_balance()).map_err(balance_invariants_broken)?;
 
-        Ok((total_pledge, newly_vested))
+        Ok((total_pledge, newly_vested, summaries))
     })?;
     // Request pledge update for activated sectors.
     // Power is not activated until first Window poST.
     notify_pledge_changed(rt, &(total_pledge - newly_vested))?;
 
-    Ok(())
+    Ok(summaries)
 }
 
 /// Calculate create miner deposit by MINIMUM_CONSENSUS_POWER x StateMinerInitialPledgeCollateral / 10
@@ -5323,6 +6711,7 @@ struct DataActivationOutput {
     pub unverified_space: BigInt,
     pub verified_space: BigInt,
     pub pieces: Vec<(Cid, u64)>,
+    pub claim_ids: Vec<ext::verifreg::ClaimID>,
 }
 
 // Track information needed to update a sector info's data during ProveReplicaUpdate
@@ -5345,6 +6734,7 @@ struct ReplicaUpdateActivatedData {
     seal_cid: Cid,
     unverified_space: BigInt,
     verified_space: BigInt,
+    claim_ids: Vec<ext::verifreg::ClaimID>,
 }
 
 // Activates data pieces by claiming allocations with the verified registry.
@@ -5417,9 +6807,12 @@ fn activate_sectors_pieces(
         .map(|(sector_claim, sector_pieces)| {
             let mut unverified_space = BigInt::zero();
             let mut pieces = Vec::new();
+            let mut claim_ids = Vec::new();
             for piece in *sector_pieces {
-                if piece.verified_allocation_key.is_none() {
-                    unverified_space += piece.size.0;
+                match &piece.verified_allocation_key {
+                    // A claim takes the ID of the allocation it is made from.
+                    Some(alloc_key) => claim_ids.push(alloc_key.id),
+                    None => unverified_space += piece.size.0,
                 }
                 pieces.push((piece.cid, piece.size.0));
             }
@@ -5427,6 +6820,7 @@ fn activate_sectors_pieces(
                 unverified_space: unverified_space.clone(),
                 verified_space: sector_claim.claimed_space.clone(),
                 pieces,
+                claim_ids,
             }
         })
         .collect();
@@ -5527,16 +6921,20 @@ fn activate_sectors_deals(
         .map(|(sector_deals, sector_claim)| {
             let mut sector_pieces = Vec::new();
             let mut unverified_deal_space = BigInt::zero();
+            let mut claim_ids = Vec::new();
             for info in &sector_deals.activated {
                 sector_pieces.push((info.data, info.size.0));
                 if info.allocation_id == NO_ALLOCATION_ID {
                     unverified_deal_space += info.size.0;
+                } else {
+                    claim_ids.push(info.allocation_id);
                 }
             }
             DataActivationOutput {
                 unverified_space: unverified_deal_space,
                 verified_space: sector_claim.claimed_space,
                 pieces: sector_pieces,
+                claim_ids,
             }
         })
         .collect();
@@ -5555,10 +6953,14 @@ fn batch_claim_allocations(
         // but otherwise send a group for each sector (even if empty) to ease association of results.
         true => ext::verifreg::ClaimAllocationsReturn {
             sector_results: BatchReturn::ok(verified_claims.len() as u32),
-            sector_claims: vec![
-                ext::verifreg::SectorClaimSummary { claimed_space: BigInt::zero() };
-                verified_claims.len()
-            ],
+            sector_claims: verified_claims
+                .iter()
+                .map(|sector| ext::verifreg::SectorClaimSummary {
+                    claimed_space: BigInt::zero(),
+                    term_start: rt.curr_epoch(),
+                    sector_expiry: sector.expiry,
+                })
+                .collect(),
         },
         false => {
             let claim_raw = extract_send_result(rt.send_simple(
@@ -5645,6 +7047,8 @@ impl ActorCode for Actor {
         RepayDebt|RepayDebtExported => repay_debt,
         ChangeOwnerAddress|ChangeOwnerAddressExported => change_owner_address,
         DisputeWindowedPoSt => dispute_windowed_post,
+        DisputeWindowedPoStPartitions => dispute_windowed_post_partitions,
+        RevertReplicaUpdate => revert_replica_update,
         PreCommitSectorBatch2 => pre_commit_sector_batch2,
         ChangeBeneficiary|ChangeBeneficiaryExported => change_beneficiary,
         GetBeneficiary|GetBeneficiaryExported => get_beneficiary,
@@ -5659,8 +7063,27 @@ impl ActorCode for Actor {
         ProveCommitSectors3 => prove_commit_sectors3,
         ProveReplicaUpdates3 => prove_replica_updates3,
         ProveCommitSectorsNI => prove_commit_sectors_ni,
+        ProveCommitSectors3WithSummaries => prove_commit_sectors3_with_summaries,
+        ProveReplicaUpdates3WithSummaries => prove_replica_updates3_with_summaries,
+        ProveCommitSectorsNIWithSummaries => prove_commit_sectors_ni_with_summaries,
         MaxTerminationFeeExported => max_termination_fee,
         InitialPledgeExported => initial_pledge,
+        RetryNotificationsExported => retry_notifications,
+        FindSectorExported => find_sector,
+        GetSectorClaimsExported => get_sector_claims,
+        CheckWindowedPoStExported => check_windowed_post,
+        GetDisputableProofsExported => get_disputable_proofs,
+        GetMinerInfoExported => get_miner_info_exported,
+        GetProofRefreshEpochExported => get_proof_refresh_epoch,
+        ExtendProofRefreshExported => extend_proof_refresh,
+        GetPartitionReportExported => get_partition_report,
+        ExtendSectorExpirationsByClaimsExported => extend_sector_expirations_by_claims,
+        GetSectorInfoExported => get_sector_info,
+        GetSectorsInfoExported => get_sectors_info,
+        GetSectorCountExported => get_sector_count,
+        GetDeadlineInfoExported => get_deadline_info,
+        GetPartitionSectorsExported => get_partition_sectors,
+        GetLockedFundsExported => get_locked_funds,
     }
 }
 
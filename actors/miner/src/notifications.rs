@@ -1,6 +1,6 @@
 use crate::{
-    PieceActivationManifest, PieceChange, SECTOR_CONTENT_CHANGED, SectorChanges,
-    SectorContentChangedParams, SectorContentChangedReturn,
+    FailedNotification, PieceActivationManifest, PieceChange, SECTOR_CONTENT_CHANGED,
+    SectorChanges, SectorContentChangedParams, SectorContentChangedReturn, State,
 };
 use fil_actors_runtime::runtime::Runtime;
 use fil_actors_runtime::{
@@ -28,6 +28,7 @@ pub struct ActivationNotifications<'a> {
 /// Notifications are fire-and-forget, so there is no return value.
 /// If require_success is true, this method will return an error if any of the notifications fail
 /// or are rejected by the receiving actor.
+/// Otherwise, notifications which fail to be delivered are recorded in state to be retried.
 pub fn notify_data_consumers(
     rt: &impl Runtime,
     activations: &[ActivationNotifications],
@@ -57,6 +58,7 @@ pub fn notify_data_consumers(
         }
     }
 
+    let mut failures: Vec<FailedNotification> = vec![];
     for (notifee, payloads) in activations_by_notifee {
         // Reject notifications to any actor other than the built-in market.
         if notifee != STORAGE_MARKET_ACTOR_ADDR {
@@ -81,20 +83,62 @@ pub fn notify_data_consumers(
             &notifee,
             SectorContentChangedParams { sectors: sectors_changes.clone() },
         );
-        if require_success {
-            match response {
-                Ok(response) => {
+        match response {
+            Ok(response) => {
+                if require_success {
                     validate_notification_response(&notifee, &sectors_changes, &response)?;
                 }
-                Err(e) => {
+            }
+            Err(e) => {
+                if require_success {
                     return Err(e);
                 }
+                failures.extend(
+                    sectors_changes
+                        .into_iter()
+                        .map(|changes| FailedNotification { receiver: notifee, changes }),
+                );
             }
         }
     }
+
+    if !failures.is_empty() {
+        rt.transaction(|st: &mut State, rt| st.record_failed_notifications(rt.store(), failures))?;
+    }
     Ok(())
 }
 
+/// Re-sends notifications which previously failed to be delivered,
+/// sending a single notification per receiving actor.
+/// Returns the number of sector notifications delivered, and those which failed again.
+/// Responses are not validated, as the notifications have already been committed to.
+pub fn resend_failed_notifications(
+    rt: &impl Runtime,
+    failed: Vec<FailedNotification>,
+) -> (u64, Vec<FailedNotification>) {
+    let mut changes_by_receiver = BTreeMap::<Address, Vec<SectorChanges>>::new();
+    for failure in failed {
+        changes_by_receiver.entry(failure.receiver).or_default().push(failure.changes);
+    }
+
+    let mut delivered = 0;
+    let mut failures: Vec<FailedNotification> = vec![];
+    for (receiver, sectors_changes) in changes_by_receiver {
+        let response = send_notification(
+            rt,
+            &receiver,
+            SectorContentChangedParams { sectors: sectors_changes.clone() },
+        );
+        match response {
+            Ok(_) => delivered += sectors_changes.len() as u64,
+            Err(_) => failures.extend(
+                sectors_changes.into_iter().map(|changes| FailedNotification { receiver, changes }),
+            ),
+        }
+    }
+    (delivered, failures)
+}
+
 // Sends a notification to one receiver and handles errors and syntactically invalid responses.
 fn send_notification(
     rt: &impl Runtime,
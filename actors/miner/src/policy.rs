@@ -29,6 +29,9 @@ lazy_static! {
     pub static ref VERIFIED_DEAL_WEIGHT_MULTIPLIER: BigInt = BigInt::from(100);
 }
 
+/// Maximum number of sectors returned by a single call to list sector information.
+pub const GET_SECTORS_INFO_MAX_PAGE_SIZE: u64 = 1000;
+
 /// The maximum number of partitions that may be required to be loaded in a single invocation,
 /// when all the sector infos for the partitions will be loaded.
 pub fn load_partitions_sectors_max(policy: &Policy, partition_sector_count: u64) -> u64 {
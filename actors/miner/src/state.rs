@@ -30,6 +30,7 @@ use fil_actors_runtime::{
 
 use super::beneficiary::*;
 use super::deadlines::new_deadline_info;
+use super::ext::verifreg::ClaimID;
 use super::policy::*;
 use super::types::*;
 use super::{
@@ -43,6 +44,10 @@ pub const PRECOMMIT_CONFIG: Config = Config { bit_width: HAMT_BIT_WIDTH, ..DEFAU
 
 const PRECOMMIT_EXPIRY_AMT_BITWIDTH: u32 = 6;
 pub const SECTORS_AMT_BITWIDTH: u32 = 5;
+pub const FAILED_NOTIFICATIONS_AMT_BITWIDTH: u32 = 5;
+pub const SECTOR_CLAIMS_AMT_BITWIDTH: u32 = 5;
+pub const PROOF_REFRESH_EPOCHS_AMT_BITWIDTH: u32 = 5;
+pub const PROOF_REFRESH_QUEUE_AMT_BITWIDTH: u32 = 6;
 
 /// Balance of Miner Actor should be greater than or equal to
 /// the sum of PreCommitDeposits and LockedFunds.
@@ -111,6 +116,21 @@ pub struct State {
 
     // True when miner cron is active, false otherwise
     pub deadline_cron_active: bool,
+
+    /// Sector content change notifications which could not be delivered, pending retry.
+    pub failed_notifications: Cid, // Array, AMT[SectorNumber]Vec<FailedNotification> (sparse)
+
+    /// The verified registry claims contributing to each sector's verified deal weight,
+    /// recorded when data is activated.
+    pub sector_claims: Cid, // Array, AMT[SectorNumber]Vec<ClaimID> (sparse)
+
+    /// The epochs at which each sector's proof was last refreshed and by which it is scheduled
+    /// to be refreshed again, if proof refresh is enabled by policy.
+    pub proof_refresh_epochs: Cid, // Array, AMT[SectorNumber]ProofRefresh (sparse)
+
+    /// The sectors whose proofs are scheduled to be refreshed by each epoch, quantized to
+    /// deadline boundaries. Entries are not removed when a sector's schedule is extended.
+    pub proof_refresh_queue: Cid, // BitFieldQueue (AMT[Epoch]*BitField)
 }
 
 #[derive(PartialEq, Eq)]
@@ -149,6 +169,37 @@ impl State {
                         "failed to construct sectors array",
                     )
                 })?;
+        let empty_failed_notifications_array =
+            Array::<Vec<FailedNotification>, BS>::new_with_bit_width(
+                store,
+                FAILED_NOTIFICATIONS_AMT_BITWIDTH,
+            )
+            .flush()
+            .context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "failed to construct empty failed notifications array",
+            )?;
+        let empty_sector_claims_array =
+            Array::<Vec<ClaimID>, BS>::new_with_bit_width(store, SECTOR_CLAIMS_AMT_BITWIDTH)
+                .flush()
+                .context_code(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    "failed to construct empty sector claims array",
+                )?;
+        let empty_proof_refresh_epochs_array =
+            Array::<ProofRefresh, BS>::new_with_bit_width(store, PROOF_REFRESH_EPOCHS_AMT_BITWIDTH)
+                .flush()
+                .context_code(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    "failed to construct empty proof refresh epochs array",
+                )?;
+        let empty_proof_refresh_queue =
+            Array::<BitField, BS>::new_with_bit_width(store, PROOF_REFRESH_QUEUE_AMT_BITWIDTH)
+                .flush()
+                .context_code(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    "failed to construct empty proof refresh queue",
+                )?;
         let empty_bitfield = store.put_cbor(&BitField::new(), Code::Blake2b256).map_err(|e| {
             e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to construct empty bitfield")
         })?;
@@ -183,6 +234,10 @@ impl State {
             early_terminations: BitField::new(),
             deadline_cron_active: false,
             pre_committed_sectors_cleanup: empty_precommits_cleanup_array,
+            failed_notifications: empty_failed_notifications_array,
+            sector_claims: empty_sector_claims_array,
+            proof_refresh_epochs: empty_proof_refresh_epochs_array,
+            proof_refresh_queue: empty_proof_refresh_queue,
         })
     }
 
@@ -388,6 +443,41 @@ impl State {
         sectors.get(sector_num)
     }
 
+    /// Lists sectors in ascending order of sector number, starting from a sector number
+    /// (inclusive), or from the first sector if none.
+    /// Returns at most `limit` sectors, and the sector number from which to resume listing if
+    /// more remain.
+    pub fn list_sectors<BS: Blockstore>(
+        &self,
+        store: &BS,
+        start: Option<SectorNumber>,
+        limit: usize,
+    ) -> Result<(Vec<SectorOnChainInfo>, Option<SectorNumber>), ActorError> {
+        let sectors = Sectors::load(store, &self.sectors)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "loading sectors")?;
+        let mut listed = Vec::new();
+        let mut next = None;
+        sectors
+            .amt
+            .for_each_while_ranged(start, None, |sector_number, sector| {
+                if listed.len() == limit {
+                    next = Some(sector_number);
+                    return Ok(false);
+                }
+                listed.push(sector.clone());
+                Ok(true)
+            })
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to iterate sectors")?;
+        Ok((listed, next))
+    }
+
+    /// Returns the number of sectors in state, including faulty sectors.
+    pub fn sector_count<BS: Blockstore>(&self, store: &BS) -> Result<u64, ActorError> {
+        let sectors = Sectors::load(store, &self.sectors)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "loading sectors")?;
+        Ok(sectors.amt.count())
+    }
+
     pub fn for_each_sector<BS: Blockstore, F>(&self, store: &BS, mut f: F) -> anyhow::Result<()>
     where
         F: FnMut(&SectorOnChainInfo) -> anyhow::Result<()>,
@@ -687,6 +777,266 @@ impl State {
         Ok(true)
     }
 
+    /// Returns whether a sector is committed and has not been terminated or expired.
+    /// Faulty sectors remain committed.
+    pub fn is_sector_committed<BS: Blockstore>(
+        &self,
+        store: &BS,
+        sector_number: SectorNumber,
+    ) -> Result<bool, ActorError> {
+        if self.get_sector(store, sector_number)?.is_none() {
+            return Ok(false);
+        }
+        let (deadline_idx, partition_idx) = self
+            .find_sector(store, sector_number)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to find sector")?;
+        let deadline = self.load_deadlines(store)?.load_deadline(store, deadline_idx)?;
+        let partition = deadline.load_partition(store, partition_idx)?;
+        Ok(!partition.terminated.get(sector_number))
+    }
+
+    /// Records sector content change notifications which could not be delivered,
+    /// so that they may be retried.
+    pub fn record_failed_notifications<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        failures: Vec<FailedNotification>,
+    ) -> Result<(), ActorError> {
+        let mut pending =
+            Array::<Vec<FailedNotification>, BS>::load(&self.failed_notifications, store)
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load failed notifications")?;
+        for failure in failures {
+            let sector_number = failure.changes.sector;
+            let mut sector_failures = pending
+                .get(sector_number)
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load failed notifications")?
+                .cloned()
+                .unwrap_or_default();
+            sector_failures.push(failure);
+            pending.set(sector_number, sector_failures).context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                "failed to record failed notification",
+            )?;
+        }
+        self.failed_notifications = pending
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush failed notifications")?;
+        Ok(())
+    }
+
+    /// Removes and returns the failed notifications for up to max_sectors sectors,
+    /// in order of sector number.
+    pub fn take_failed_notifications<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        max_sectors: u64,
+    ) -> Result<Vec<FailedNotification>, ActorError> {
+        let mut pending =
+            Array::<Vec<FailedNotification>, BS>::load(&self.failed_notifications, store)
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load failed notifications")?;
+        let mut sector_numbers = Vec::new();
+        pending
+            .for_each_while(|sector_number, _| {
+                sector_numbers.push(sector_number);
+                Ok((sector_numbers.len() as u64) < max_sectors)
+            })
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to iterate failed notifications")?;
+
+        let mut taken = Vec::new();
+        for sector_number in sector_numbers {
+            if let Some(sector_failures) = pending
+                .delete(sector_number)
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to remove failed notification")?
+            {
+                taken.extend(sector_failures);
+            }
+        }
+        self.failed_notifications = pending
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush failed notifications")?;
+        Ok(taken)
+    }
+
+    /// Returns the IDs of the verified registry claims recorded for a sector.
+    pub fn get_sector_claims<BS: Blockstore>(
+        &self,
+        store: &BS,
+        sector_number: SectorNumber,
+    ) -> Result<Vec<ClaimID>, ActorError> {
+        let sector_claims = Array::<Vec<ClaimID>, BS>::load(&self.sector_claims, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load sector claims")?;
+        Ok(sector_claims
+            .get(sector_number)
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to load claims for sector {}", sector_number)
+            })?
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Records the verified registry claims for sectors, replacing any claims previously
+    /// recorded for them. A sector with no claims has its entry removed.
+    pub fn put_sector_claims<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        claims: impl IntoIterator<Item = (SectorNumber, Vec<ClaimID>)>,
+    ) -> Result<(), ActorError> {
+        let mut sector_claims = Array::<Vec<ClaimID>, BS>::load(&self.sector_claims, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load sector claims")?;
+        for (sector_number, claim_ids) in claims {
+            if claim_ids.is_empty() {
+                sector_claims
+                    .delete(sector_number)
+                    .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                        format!("failed to remove claims for sector {}", sector_number)
+                    })?;
+            } else {
+                sector_claims
+                    .set(sector_number, claim_ids)
+                    .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                        format!("failed to record claims for sector {}", sector_number)
+                    })?;
+            }
+        }
+        self.sector_claims = sector_claims
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush sector claims")?;
+        Ok(())
+    }
+
+    /// Removes the recorded verified registry claims for sectors.
+    pub fn delete_sector_claims<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        sector_numbers: &BitField,
+    ) -> Result<(), ActorError> {
+        self.put_sector_claims(store, sector_numbers.iter().map(|sno| (sno, vec![])))
+    }
+
+    /// Returns a sector's scheduled proof refresh, if any.
+    pub fn get_proof_refresh<BS: Blockstore>(
+        &self,
+        store: &BS,
+        sector_number: SectorNumber,
+    ) -> Result<Option<ProofRefresh>, ActorError> {
+        let epochs = Array::<ProofRefresh, BS>::load(&self.proof_refresh_epochs, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load proof refresh epochs")?;
+        Ok(epochs
+            .get(sector_number)
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to load proof refresh epoch for sector {}", sector_number)
+            })?
+            .copied())
+    }
+
+    /// Schedules sectors' proofs to be refreshed by some epochs, replacing any refreshes
+    /// previously scheduled for them.
+    pub fn schedule_proof_refreshes<BS: Blockstore>(
+        &mut self,
+        policy: &Policy,
+        store: &BS,
+        refreshes: impl IntoIterator<Item = (SectorNumber, ProofRefresh)>,
+    ) -> Result<(), ActorError> {
+        let mut epochs = Array::<ProofRefresh, BS>::load(&self.proof_refresh_epochs, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load proof refresh epochs")?;
+        let mut queue = BitFieldQueue::new(
+            store,
+            &self.proof_refresh_queue,
+            self.quant_spec_every_deadline(policy),
+        )
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load proof refresh queue")?;
+        let mut queued = Vec::new();
+        for (sector_number, refresh) in refreshes {
+            epochs
+                .set(sector_number, refresh)
+                .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("failed to schedule proof refresh for sector {}", sector_number)
+                })?;
+            queued.push((refresh.refresh_epoch, sector_number));
+        }
+        queue
+            .add_many_to_queue_values(queued)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to queue proof refreshes")?;
+        self.proof_refresh_epochs = epochs
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush proof refresh epochs")?;
+        self.proof_refresh_queue = queue
+            .amt
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush proof refresh queue")?;
+        Ok(())
+    }
+
+    /// Removes the scheduled proof refreshes for sectors.
+    pub fn delete_proof_refreshes<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        sector_numbers: &BitField,
+    ) -> Result<(), ActorError> {
+        let mut epochs = Array::<ProofRefresh, BS>::load(&self.proof_refresh_epochs, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load proof refresh epochs")?;
+        for sector_number in sector_numbers.iter() {
+            epochs.delete(sector_number).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to remove proof refresh for sector {}", sector_number)
+            })?;
+        }
+        self.proof_refresh_epochs = epochs
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush proof refresh epochs")?;
+        Ok(())
+    }
+
+    /// Removes the scheduled proof refreshes which have passed, returning the number and
+    /// refresh epoch of each committed sector whose proof has expired.
+    /// Sectors whose schedule has since been extended are skipped, as are those which have
+    /// been terminated or have expired, whose schedule is simply removed.
+    pub fn pop_expired_proofs<BS: Blockstore>(
+        &mut self,
+        policy: &Policy,
+        store: &BS,
+        current_epoch: ChainEpoch,
+    ) -> Result<Vec<(SectorNumber, ChainEpoch)>, ActorError> {
+        let mut queue = BitFieldQueue::new(
+            store,
+            &self.proof_refresh_queue,
+            self.quant_spec_every_deadline(policy),
+        )
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load proof refresh queue")?;
+        let (due, modified) = queue
+            .pop_until(current_epoch)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to pop proof refresh queue")?;
+        if !modified {
+            return Ok(vec![]);
+        }
+        self.proof_refresh_queue = queue
+            .amt
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush proof refresh queue")?;
+
+        let epochs = Array::<ProofRefresh, BS>::load(&self.proof_refresh_epochs, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load proof refresh epochs")?;
+        let mut removed = BitField::new();
+        let mut expired = Vec::new();
+        for sector_number in due.iter() {
+            let scheduled =
+                epochs.get(sector_number).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("failed to load proof refresh epoch for sector {}", sector_number)
+                })?;
+            match scheduled {
+                Some(refresh) if refresh.refresh_epoch <= current_epoch => {
+                    removed.set(sector_number);
+                    if self.is_sector_committed(store, sector_number)? {
+                        expired.push((sector_number, refresh.refresh_epoch));
+                    }
+                }
+                // Removed, or rescheduled to a later epoch.
+                _ => {}
+            }
+        }
+        self.delete_proof_refreshes(store, &removed)?;
+        Ok(expired)
+    }
+
     /// Returns an error if the target sector cannot be found and/or is faulty/terminated.
     pub fn check_sector_health<BS: Blockstore>(
         &self,
@@ -1014,12 +1364,14 @@ impl State {
         Ok(())
     }
 
+    /// Removes pre-committed sectors whose clean up epoch has passed, returning the total deposit
+    /// to burn, and the number and deposit of each sector removed.
     pub fn cleanup_expired_pre_commits<BS: Blockstore>(
         &mut self,
         policy: &Policy,
         store: &BS,
         current_epoch: ChainEpoch,
-    ) -> anyhow::Result<TokenAmount> {
+    ) -> anyhow::Result<(TokenAmount, Vec<(SectorNumber, TokenAmount)>)> {
         let mut deposit_to_burn = TokenAmount::zero();
 
         // cleanup expired pre-committed sectors
@@ -1036,6 +1388,7 @@ impl State {
         }
 
         let mut precommits_to_delete = Vec::new();
+        let mut expired = Vec::new();
         let precommitted =
             PreCommitMap::load(store, &self.pre_committed_sectors, PRECOMMIT_CONFIG, "precommits")?;
 
@@ -1052,7 +1405,8 @@ impl State {
             precommits_to_delete.push(sector_number);
 
             // increment deposit to burn
-            deposit_to_burn += sector.pre_commit_deposit;
+            deposit_to_burn += &sector.pre_commit_deposit;
+            expired.push((sector_number, sector.pre_commit_deposit));
         }
 
         // Actually delete it.
@@ -1068,7 +1422,7 @@ impl State {
             ));
         }
 
-        Ok(deposit_to_burn)
+        Ok((deposit_to_burn, expired))
     }
 
     pub fn advance_deadline<BS: Blockstore>(
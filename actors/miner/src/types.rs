@@ -20,8 +20,10 @@ use fvm_shared::sector::{
 use serde::{Deserialize, Serialize};
 
 use fil_actors_runtime::reward::FilterEstimate;
-use fil_actors_runtime::{BatchReturn, DealWeight};
+use fil_actors_runtime::runtime::policy_constants::{ADDRESSED_SECTORS_MAX, MAX_MULTIADDRS};
+use fil_actors_runtime::{BatchReturn, BoundedVec, Cursor, DealWeight};
 
+use crate::PowerPair;
 use crate::commd::CompactCommD;
 use crate::ext::verifreg::AllocationID;
 use crate::ext::verifreg::ClaimID;
@@ -30,6 +32,12 @@ use super::beneficiary::*;
 
 pub type CronEvent = i64;
 
+/// Multiaddresses of a miner's peer, bounded when decoded from method parameters.
+pub type Multiaddrs = BoundedVec<BytesDe, MAX_MULTIADDRS>;
+
+/// Individual proofs for a batch of sectors, bounded when decoded from method parameters.
+pub type SectorProofs = BoundedVec<RawBytes, { ADDRESSED_SECTORS_MAX as usize }>;
+
 pub const CRON_EVENT_WORKER_KEY_CHANGE: CronEvent = 0;
 pub const CRON_EVENT_PROVING_DEADLINE: CronEvent = 1;
 pub const CRON_EVENT_PROCESS_EARLY_TERMINATIONS: CronEvent = 2;
@@ -44,7 +52,7 @@ pub struct MinerConstructorParams {
     pub window_post_proof_type: RegisteredPoStProof,
     #[serde(with = "strict_bytes")]
     pub peer_id: Vec<u8>,
-    pub multi_addresses: Vec<BytesDe>,
+    pub multi_addresses: Multiaddrs,
 }
 
 #[derive(Serialize_tuple, Deserialize_tuple)]
@@ -85,7 +93,7 @@ pub struct ChangePeerIDParams {
 
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct ChangeMultiaddrsParams {
-    pub new_multi_addrs: Vec<BytesDe>,
+    pub new_multi_addrs: Multiaddrs,
 }
 
 #[derive(Serialize_tuple, Deserialize_tuple)]
@@ -163,7 +171,7 @@ pub struct ProveCommitSectors3Params {
     pub sector_activations: Vec<SectorActivationManifest>,
     // Proofs for each sector, parallel to activation manifests.
     // Exactly one of sector_proofs or aggregate_proof must be non-empty.
-    pub sector_proofs: Vec<RawBytes>,
+    pub sector_proofs: SectorProofs,
     // Aggregate proof for all sectors.
     // Exactly one of sector_proofs or aggregate_proof must be non-empty.
     pub aggregate_proof: RawBytes,
@@ -222,12 +230,38 @@ pub struct ProveCommitSectors3Return {
     pub activation_results: BatchReturn,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize_tuple, Deserialize_tuple)]
+pub struct ProveCommitSectors3WithSummariesReturn {
+    pub activation_results: BatchReturn,
+    /// Summaries of the sectors activated, in the order of the successful activation results.
+    pub sectors: Vec<SectorActivationSummary>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize_tuple, Deserialize_tuple)]
 #[serde(transparent)]
 pub struct ProveCommitSectorsNIReturn {
     pub activation_results: BatchReturn,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize_tuple, Deserialize_tuple)]
+pub struct ProveCommitSectorsNIWithSummariesReturn {
+    pub activation_results: BatchReturn,
+    /// Summaries of the sectors activated, in the order of the successful activation results.
+    pub sectors: Vec<SectorActivationSummary>,
+}
+
+/// Summary of a sector's state immediately after it was activated or updated.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize_tuple, Deserialize_tuple)]
+pub struct SectorActivationSummary {
+    pub sector: SectorNumber,
+    /// The raw byte and quality-adjusted power of the sector.
+    pub power: PowerPair,
+    pub initial_pledge: TokenAmount,
+    pub daily_fee: TokenAmount,
+    /// The number of verified registry claims recorded for the sector.
+    pub claims: u64,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct CheckSectorProvenParams {
     pub sector_number: SectorNumber,
@@ -255,6 +289,25 @@ pub struct ExpirationExtension2 {
     pub new_expiration: ChainEpoch,
 }
 
+#[derive(Clone, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct ExtendSectorExpirationsByClaimsParams {
+    pub extensions: Vec<ExpirationExtensionByClaims>,
+}
+
+#[derive(Clone, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct ExpirationExtensionByClaims {
+    pub deadline: u64,
+    pub partition: u64,
+    // IDs of sectors to extend, with or without FIL+ claims
+    pub sectors: BitField,
+    // IDs of claims maintained by sectors whose claims are not recorded, such as sectors
+    // activated before claims were recorded. Recorded claims are maintained unless dropped.
+    pub maintain_claims: Vec<ClaimID>,
+    // IDs of claims of the sectors that are dropped rather than maintained
+    pub drop_claims: Vec<ClaimID>,
+    pub new_expiration: ChainEpoch,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct TerminateSectorsParams {
     pub terminations: Vec<TerminationDeclaration>,
@@ -453,6 +506,8 @@ bitflags::bitflags! {
     pub struct SectorOnChainInfoFlags: u32 {
         /// QA power mechanism introduced in FIP-0045
         const SIMPLE_QA_POWER = 0x1;
+        /// The sector's replica update has been reverted, so it may not be updated again
+        const REPLICA_UPDATE_REVERTED = 0x2;
     }
 }
 
@@ -475,12 +530,24 @@ pub struct DisputeWindowedPoStParams {
     pub post_index: u64, // only one is allowed at a time to avoid loading too many sector infos.
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct RevertReplicaUpdateParams {
+    pub sectors: BitField,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct DisputeWindowedPoStPartitionsParams {
+    pub deadline: u64,
+    pub partitions: BitField,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct ProveReplicaUpdates3Params {
     pub sector_updates: Vec<SectorUpdateManifest>,
     // Proofs for each sector, parallel to activation manifests.
     // Exactly one of sector_proofs or aggregate_proof must be non-empty.
-    pub sector_proofs: Vec<RawBytes>,
+    pub sector_proofs: SectorProofs,
     // Aggregate proof for all sectors.
     // Exactly one of sector_proofs or aggregate_proof must be non-empty.
     pub aggregate_proof: RawBytes,
@@ -513,6 +580,13 @@ pub struct ProveReplicaUpdates3Return {
     pub activation_results: BatchReturn,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize_tuple, Deserialize_tuple)]
+pub struct ProveReplicaUpdates3WithSummariesReturn {
+    pub activation_results: BatchReturn,
+    /// Summaries of the sectors updated, in the order of the successful activation results.
+    pub sectors: Vec<SectorActivationSummary>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct ChangeBeneficiaryParams {
     pub new_beneficiary: Address,
@@ -577,6 +651,13 @@ pub struct GetVestingFundsReturn {
     pub vesting_funds: Vec<(ChainEpoch, TokenAmount)>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct GetLockedFundsReturn {
+    /// The total of the funds vesting in the miner.
+    pub locked_funds: TokenAmount,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct GetPeerIDReturn {
     #[serde(with = "strict_bytes")]
@@ -588,6 +669,20 @@ pub struct GetMultiaddrsReturn {
     pub multi_addrs: Vec<BytesDe>,
 }
 
+#[derive(Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct GetMinerInfoReturn {
+    pub owner: Address,
+    pub worker: Address,
+    /// The pending change of worker key, if any.
+    pub pending_worker_key: Option<WorkerKeyChange>,
+    pub control_addresses: Vec<Address>,
+    #[serde(with = "strict_bytes")]
+    pub peer_id: Vec<u8>,
+    pub multi_addrs: Vec<BytesDe>,
+    pub window_post_proof_type: RegisteredPoStProof,
+    pub sector_size: SectorSize,
+}
+
 // Notification of change committed to one or more sectors.
 // The relevant state must be already committed so the receiver can observe any impacts
 // at the sending miner actor.
@@ -648,6 +743,30 @@ pub struct PieceReturn {
     pub accepted: bool,
 }
 
+// A sector content change notification which could not be delivered to its receiver,
+// retained in state to be retried.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct FailedNotification {
+    pub receiver: Address,
+    pub changes: SectorChanges,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct RetryNotificationsParams {
+    // Maximum number of sectors for which to retry failed notifications.
+    pub max_sectors: u64,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct RetryNotificationsReturn {
+    // Number of sector notifications delivered.
+    pub delivered: u64,
+    // Number of sector notifications which failed again and remain pending.
+    pub failed: u64,
+    // Number of sector notifications discarded because the sector is no longer committed.
+    pub dropped: u64,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 pub struct MaxTerminationFeeParams {
     #[serde(with = "bigint_ser")]
@@ -666,3 +785,233 @@ pub struct MaxTerminationFeeReturn {
 pub struct InitialPledgeReturn {
     pub initial_pledge: TokenAmount,
 }
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct FindSectorParams {
+    pub sector_number: SectorNumber,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct FindSectorReturn {
+    pub deadline: u64,
+    pub partition: u64,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetSectorClaimsParams {
+    pub sector_number: SectorNumber,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetSectorClaimsReturn {
+    // Whether the sector's claims are known. The claims of a sector with verified deal weight
+    // activated before claims were recorded are unknown, and none are returned.
+    pub known: bool,
+    pub claim_ids: Vec<ClaimID>,
+}
+
+/// Summary of the on-chain information of a sector, omitting deprecated fields.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct SectorSummary {
+    pub sector_number: SectorNumber,
+    pub seal_proof: RegisteredSealProof,
+    pub sealed_cid: Cid,
+    pub activation: ChainEpoch,
+    pub expiration: ChainEpoch,
+    #[serde(with = "bigint_ser")]
+    pub deal_weight: DealWeight,
+    #[serde(with = "bigint_ser")]
+    pub verified_deal_weight: DealWeight,
+    pub initial_pledge: TokenAmount,
+    pub power_base_epoch: ChainEpoch,
+    pub daily_fee: TokenAmount,
+    pub flags: SectorOnChainInfoFlags,
+}
+
+impl From<&SectorOnChainInfo> for SectorSummary {
+    fn from(sector: &SectorOnChainInfo) -> Self {
+        Self {
+            sector_number: sector.sector_number,
+            seal_proof: sector.seal_proof,
+            sealed_cid: sector.sealed_cid,
+            activation: sector.activation,
+            expiration: sector.expiration,
+            deal_weight: sector.deal_weight.clone(),
+            verified_deal_weight: sector.verified_deal_weight.clone(),
+            initial_pledge: sector.initial_pledge.clone(),
+            power_base_epoch: sector.power_base_epoch,
+            daily_fee: sector.daily_fee.clone(),
+            flags: sector.flags,
+        }
+    }
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetSectorInfoParams {
+    pub sector_number: SectorNumber,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetSectorInfoReturn {
+    pub sector: SectorSummary,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetSectorsInfoParams {
+    /// The start cursor to list from the beginning; otherwise the next_cursor of a previous call.
+    pub cursor: Cursor,
+    /// Maximum number of sectors to return, capped at GET_SECTORS_INFO_MAX_PAGE_SIZE.
+    pub limit: u64,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetSectorsInfoReturn {
+    /// Summaries of the sectors, in ascending order of sector number.
+    pub sectors: Vec<SectorSummary>,
+    /// Cursor from which to continue the listing, if more sectors remain.
+    pub next_cursor: Option<Cursor>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetSectorCountReturn {
+    pub count: u64,
+}
+
+/// A sector's scheduled proof refresh.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ProofRefresh {
+    /// The epoch at which the sector's proof was last refreshed, by sealing or updating it.
+    pub proven_epoch: ChainEpoch,
+    /// The epoch by which the sector's proof is to be refreshed.
+    pub refresh_epoch: ChainEpoch,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetProofRefreshEpochParams {
+    pub sector_number: SectorNumber,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetProofRefreshEpochReturn {
+    /// The epoch by which the sector's proof must be refreshed, or None if not scheduled.
+    pub refresh_epoch: Option<ChainEpoch>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ExtendProofRefreshParams {
+    pub sectors: BitField,
+    pub new_refresh_epoch: ChainEpoch,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetDisputableProofsParams {
+    pub deadline: u64,
+}
+
+/// A Window PoSt that was optimistically accepted and may be disputed.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct DisputableProof {
+    /// The index of the proof, to pass to DisputeWindowedPoSt.
+    pub post_index: u64,
+    pub partitions: BitField,
+    pub proofs: Vec<PoStProof>,
+    /// The address (ID) of the account that submitted the proof, if recorded.
+    pub prover: Option<Address>,
+    /// The epoch at which the proof was accepted, if recorded.
+    pub epoch: Option<ChainEpoch>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetDisputableProofsReturn {
+    /// Whether the deadline's proofs may currently be disputed.
+    pub dispute_open: bool,
+    /// Snapshot of the miner's sectors AMT at the end of the deadline's last challenge window.
+    pub sectors_snapshot: Cid,
+    /// Snapshot of the deadline's partitions AMT at the end of its last challenge window.
+    pub partitions_snapshot: Cid,
+    /// The undisputed proofs accepted during the deadline's last challenge window.
+    pub proofs: Vec<DisputableProof>,
+}
+
+/// Summary of the fill of a deadline's partitions, for assessing whether to compact them.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct DeadlinePartitionReport {
+    /// The number of live (including faulty) sectors in each partition, in partition order.
+    pub partition_live_sectors: Vec<u64>,
+    /// The number of terminated sectors remaining in the partitions, which compaction removes.
+    pub dead_sectors: u64,
+    /// The number of partitions by which compacting all of the deadline's partitions would
+    /// reduce it. Each is one less partition to load and update when proving the deadline.
+    pub removable_partitions: u64,
+    /// Whether the deadline may currently be compacted.
+    pub compactable: bool,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetPartitionReportReturn {
+    /// The maximum number of sectors in a partition.
+    pub partition_sectors: u64,
+    /// Reports for each deadline, in deadline order.
+    pub deadlines: Vec<DeadlinePartitionReport>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetDeadlineInfoParams {
+    pub deadline: u64,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetDeadlineInfoReturn {
+    /// First epoch from which a proof may be submitted for the deadline's current or next
+    /// challenge window (whichever has not yet elapsed).
+    pub open: ChainEpoch,
+    /// First epoch from which a proof may no longer be submitted for that window.
+    pub close: ChainEpoch,
+    /// Epoch at which the window's challenge randomness is drawn.
+    pub challenge: ChainEpoch,
+    /// The number of partitions in the deadline.
+    pub partitions: u64,
+    /// Partitions for which a proof has been accepted in the current proving period.
+    pub partitions_posted: BitField,
+    /// The number of non-terminated sectors in the deadline (including faulty).
+    pub live_sectors: u64,
+    /// The total number of sectors in the deadline (including terminated).
+    pub total_sectors: u64,
+    /// Power of the non-terminated sectors (including faulty and unproven).
+    pub live_power: PowerPair,
+    /// Power of the faulty sectors.
+    pub faulty_power: PowerPair,
+    /// The daily fee payable for the deadline's sectors.
+    pub daily_fee: TokenAmount,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetPartitionSectorsParams {
+    pub deadline: u64,
+    pub partition: u64,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetPartitionSectorsReturn {
+    /// All sectors in the partition, including terminated sectors not yet removed.
+    pub all: BitField,
+    /// Non-terminated sectors (including faulty and unproven).
+    pub live: BitField,
+    /// Sectors that have not yet been proven.
+    pub unproven: BitField,
+    /// Sectors detected or declared faulty, and not yet recovered.
+    pub faulty: BitField,
+    /// Faulty sectors declared as recovering, expected to be proven at the next proof.
+    pub recovering: BitField,
+    /// Terminated sectors not yet removed from the partition.
+    pub terminated: BitField,
+}
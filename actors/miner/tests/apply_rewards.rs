@@ -78,6 +78,10 @@ fn funds_vest() {
     let st = h.get_state(&rt);
     let (locked_amt, _) = locked_reward_from_reward(amt);
     assert_eq!(locked_amt, st.locked_funds);
+    // The total locked is the sum of the vesting schedule.
+    let vesting_sum = vesting_funds.iter().fold(TokenAmount::zero(), |sum, vf| sum + &vf.amount);
+    assert_eq!(locked_amt, vesting_sum);
+    assert_eq!(locked_amt, h.query_locked_funds(&rt).unwrap());
     // technically applying rewards without first activating cron is an impossible state but convenient for testing
     let (_, acc) = check_state_invariants(rt.policy(), &st, rt.store(), &rt.get_balance());
     assert_eq!(1, acc.len());
@@ -102,6 +102,59 @@ fn compacting_a_partition_with_both_live_and_dead_sectors_removes_dead_sectors_r
     h.check_state(&rt);
 }
 
+#[test]
+fn partition_report_counts_dead_sectors_until_compaction() {
+    let (mut h, rt) = setup();
+    rt.set_epoch(200);
+
+    let sectors_info = h.commit_and_prove_sectors(&rt, 4, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+    h.advance_and_submit_posts(&rt, &sectors_info);
+    let (deadline_id, _) =
+        rt.get_state::<State>().find_sector(rt.store(), sectors_info[0].sector_number).unwrap();
+
+    let report = h.get_partition_report(&rt);
+    assert_eq!(rt.policy().wpost_period_deadlines, report.deadlines.len() as u64);
+    let dl = &report.deadlines[deadline_id as usize];
+    assert_eq!(vec![4], dl.partition_live_sectors);
+    assert_eq!((0, 0), (dl.dead_sectors, dl.removable_partitions));
+
+    // terminate a sector, leaving it in the partition until compaction
+    let epoch = *rt.epoch.borrow();
+    rt.set_epoch(epoch + 100);
+    h.apply_rewards(&rt, BIG_REWARDS.clone(), TokenAmount::zero());
+    let terminated_sector = &sectors_info[0];
+    let sector_size = terminated_sector.seal_proof.sector_size().unwrap();
+    let sector_age = *rt.epoch.borrow() - terminated_sector.activation;
+    let fault_fee = pledge_penalty_for_continued_fault(
+        &h.epoch_reward_smooth,
+        &h.epoch_qa_power_smooth,
+        &qa_power_for_sector(sector_size, terminated_sector),
+    );
+    let expected_fee =
+        pledge_penalty_for_termination(&terminated_sector.initial_pledge, sector_age, &fault_fee);
+    h.terminate_sectors(
+        &rt,
+        &bitfield_from_slice(&[terminated_sector.sector_number]),
+        expected_fee,
+    );
+
+    let target_epoch = *rt.epoch.borrow() + rt.policy().wpost_dispute_window;
+    h.advance_to_epoch_with_cron(&rt, target_epoch);
+    let report = h.get_partition_report(&rt);
+    let dl = &report.deadlines[deadline_id as usize];
+    assert_eq!(vec![3], dl.partition_live_sectors);
+    assert_eq!(1, dl.dead_sectors);
+    assert!(dl.compactable);
+
+    h.compact_partitions(&rt, deadline_id, bitfield_from_slice(&[0])).unwrap();
+    let report = h.get_partition_report(&rt);
+    let dl = &report.deadlines[deadline_id as usize];
+    assert_eq!(vec![3], dl.partition_live_sectors);
+    assert_eq!(0, dl.dead_sectors);
+
+    h.check_state(&rt);
+}
+
 #[test]
 fn fail_to_compact_partitions_with_faults() {
     let (mut h, rt) = setup();
@@ -452,6 +452,47 @@ fn removing_no_partitions_does_nothing() {
         .assert(rt.store(), &sectors, &deadline);
 }
 
+#[test]
+fn selects_and_merges_underfilled_partitions() {
+    let (_, rt) = setup();
+    let mut deadline = Deadline::new(rt.store()).unwrap();
+
+    let (deadline_state, sectors) = add_then_terminate_then_pop_early(&rt, &mut deadline);
+    let store = rt.store();
+
+    // Selection is bounded by the partition limit.
+    let limited = deadline.underfilled_partitions(store, PARTITION_SIZE, 2).unwrap();
+    assert_bitfield_equals(&limited, &[0, 1]);
+
+    // All three partitions have fewer than PARTITION_SIZE live sectors.
+    let underfilled = deadline.underfilled_partitions(store, PARTITION_SIZE, 10).unwrap();
+    assert_bitfield_equals(&underfilled, &[0, 1, 2]);
+
+    let mut sectors_array = sectors_arr(store, sectors.to_owned());
+    let dead = deadline
+        .compact_partitions(
+            store,
+            &mut sectors_array,
+            SECTOR_SIZE,
+            PARTITION_SIZE,
+            &underfilled,
+            QUANT_SPEC,
+        )
+        .expect("should have merged partitions");
+
+    assert_bitfield_equals(&dead, &[1, 3, 6]);
+
+    // The six live sectors are packed into two partitions.
+    deadline_state
+        .with_terminations(&[])
+        .with_partitions(vec![bitfield_from_slice(&[2, 4, 5, 7]), bitfield_from_slice(&[8, 9])])
+        .assert(store, &sectors, &deadline);
+
+    // Nothing further to merge: only the final partition is under-filled.
+    let underfilled = deadline.underfilled_partitions(store, PARTITION_SIZE, 10).unwrap();
+    assert!(underfilled.is_empty());
+}
+
 #[test]
 fn fails_to_remove_partitions_with_faulty_sectors() {
     let (_, rt) = setup();
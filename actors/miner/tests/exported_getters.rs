@@ -1,12 +1,16 @@
 use fil_actor_miner::{
-    Actor, GetAvailableBalanceReturn, GetOwnerReturn, GetSectorSizeReturn,
-    IsControllingAddressParam, IsControllingAddressReturn, Method,
+    Actor, FindSectorParams, FindSectorReturn, GetAvailableBalanceReturn, GetDeadlineInfoParams,
+    GetDeadlineInfoReturn, GetMinerInfoReturn, GetOwnerReturn, GetPartitionSectorsParams,
+    GetPartitionSectorsReturn, GetSectorCountReturn, GetSectorInfoParams, GetSectorInfoReturn,
+    GetSectorSizeReturn, GetSectorsInfoParams, GetSectorsInfoReturn, IsControllingAddressParam,
+    IsControllingAddressReturn, Method, SectorSummary, WorkerKeyChange, new_deadline_info,
 };
-use fil_actors_runtime::INIT_ACTOR_ADDR;
 use fil_actors_runtime::runtime::policy_constants::MAX_SECTOR_NUMBER;
-use fil_actors_runtime::test_utils::EVM_ACTOR_CODE_ID;
+use fil_actors_runtime::test_utils::{EVM_ACTOR_CODE_ID, expect_abort};
+use fil_actors_runtime::{Cursor, INIT_ACTOR_ADDR};
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
+use fvm_shared::error::ExitCode;
 use fvm_shared::{clock::ChainEpoch, econ::TokenAmount};
 use std::ops::Sub;
 
@@ -94,6 +98,49 @@ fn info_getters() {
     h.check_state(&rt);
 }
 
+#[test]
+fn miner_info_getter() {
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+
+    // request a worker change so that it is pending
+    let new_worker = Address::new_id(999);
+    rt.set_epoch(PERIOD_OFFSET + 1);
+    h.change_worker_address(&rt, new_worker, h.control_addrs.clone()).unwrap();
+    let effective_at = PERIOD_OFFSET + 1 + rt.policy.worker_key_change_delay;
+
+    // set caller to not-builtin
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+
+    rt.expect_validate_caller_any();
+    let ret: GetMinerInfoReturn = rt
+        .call::<Actor>(Method::GetMinerInfoExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+
+    let info = h.get_info(&rt);
+    assert_eq!(
+        GetMinerInfoReturn {
+            owner: h.owner,
+            worker: h.worker,
+            pending_worker_key: Some(WorkerKeyChange { new_worker, effective_at }),
+            control_addresses: h.control_addrs.clone(),
+            peer_id: info.peer_id,
+            multi_addrs: info.multi_address,
+            window_post_proof_type: h.window_post_proof_type,
+            sector_size: h.sector_size,
+        },
+        ret
+    );
+
+    h.check_state(&rt);
+}
+
 #[test]
 fn collateral_getters() {
     let h = ActorHarness::new(PERIOD_OFFSET);
@@ -144,3 +191,263 @@ fn collateral_getters() {
 
     h.check_state(&rt);
 }
+
+#[test]
+fn find_sector_getter() {
+    let mut h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+
+    let sectors =
+        h.commit_and_prove_sectors(&rt, 2, DEFAULT_SECTOR_EXPIRATION as u64, vec![], true);
+
+    // set caller to not-builtin
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+
+    for sector in &sectors {
+        rt.expect_validate_caller_any();
+        let ret: FindSectorReturn = rt
+            .call::<Actor>(
+                Method::FindSectorExported as u64,
+                IpldBlock::serialize_cbor(&FindSectorParams {
+                    sector_number: sector.sector_number,
+                })
+                .unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+
+        let (deadline, partition) =
+            h.get_state(&rt).find_sector(&rt.store, sector.sector_number).unwrap();
+        assert_eq!(FindSectorReturn { deadline, partition }, ret);
+    }
+
+    // a sector that was never committed is not found
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_NOT_FOUND,
+        rt.call::<Actor>(
+            Method::FindSectorExported as u64,
+            IpldBlock::serialize_cbor(&FindSectorParams { sector_number: 1000 }).unwrap(),
+        ),
+    );
+    rt.verify();
+
+    h.check_state(&rt);
+}
+
+#[test]
+fn sector_info_getters() {
+    let mut h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+
+    let sectors =
+        h.commit_and_prove_sectors(&rt, 3, DEFAULT_SECTOR_EXPIRATION as u64, vec![], true);
+    let summaries: Vec<SectorSummary> = sectors.iter().map(SectorSummary::from).collect();
+
+    // set caller to not-builtin
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+
+    rt.expect_validate_caller_any();
+    let count: GetSectorCountReturn = rt
+        .call::<Actor>(Method::GetSectorCountExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    assert_eq!(3, count.count);
+
+    for summary in &summaries {
+        rt.expect_validate_caller_any();
+        let ret: GetSectorInfoReturn = rt
+            .call::<Actor>(
+                Method::GetSectorInfoExported as u64,
+                IpldBlock::serialize_cbor(&GetSectorInfoParams {
+                    sector_number: summary.sector_number,
+                })
+                .unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        assert_eq!(summary, &ret.sector);
+    }
+
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_NOT_FOUND,
+        rt.call::<Actor>(
+            Method::GetSectorInfoExported as u64,
+            IpldBlock::serialize_cbor(&GetSectorInfoParams { sector_number: 1000 }).unwrap(),
+        ),
+    );
+    rt.verify();
+
+    // list the sectors in pages of two
+    let get_sectors_info = |cursor: Cursor| -> GetSectorsInfoReturn {
+        rt.expect_validate_caller_any();
+        let ret = rt
+            .call::<Actor>(
+                Method::GetSectorsInfoExported as u64,
+                IpldBlock::serialize_cbor(&GetSectorsInfoParams { cursor, limit: 2 }).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret
+    };
+    let first = get_sectors_info(Cursor::start());
+    assert_eq!(summaries[..2], first.sectors);
+    let second = get_sectors_info(first.next_cursor.unwrap());
+    assert_eq!(summaries[2..], second.sectors);
+    assert!(second.next_cursor.is_none());
+
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        rt.call::<Actor>(
+            Method::GetSectorsInfoExported as u64,
+            IpldBlock::serialize_cbor(&GetSectorsInfoParams { cursor: Cursor::start(), limit: 0 })
+                .unwrap(),
+        ),
+    );
+    rt.verify();
+
+    h.check_state(&rt);
+}
+
+#[test]
+fn deadline_and_partition_getters() {
+    let mut h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    rt.set_balance(BIG_BALANCE.clone());
+    h.construct_and_verify(&rt);
+
+    let sectors =
+        h.commit_and_prove_sectors(&rt, 3, DEFAULT_SECTOR_EXPIRATION as u64, vec![], true);
+    h.advance_and_submit_posts(&rt, &sectors);
+    let state = h.get_state(&rt);
+    let (dlidx, pidx) = state.find_sector(&rt.store, sectors[0].sector_number).unwrap();
+
+    // fault two of the sectors, and declare one of them recovering
+    h.declare_faults(&rt, &sectors[..2]);
+    h.declare_recoveries(
+        &rt,
+        dlidx,
+        pidx,
+        bitfield_from_slice(&[sectors[1].sector_number]),
+        TokenAmount::zero(),
+    )
+    .unwrap();
+
+    // set caller to not-builtin
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+
+    rt.expect_validate_caller_any();
+    let info: GetDeadlineInfoReturn = rt
+        .call::<Actor>(
+            Method::GetDeadlineInfoExported as u64,
+            IpldBlock::serialize_cbor(&GetDeadlineInfoParams { deadline: dlidx }).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+
+    let epoch = *rt.epoch.borrow();
+    let dl_info = new_deadline_info(
+        &rt.policy,
+        state.current_proving_period_start(&rt.policy, epoch),
+        dlidx,
+        epoch,
+    )
+    .next_not_elapsed();
+    let deadline = h.get_deadline(&rt, dlidx);
+    assert_eq!(
+        GetDeadlineInfoReturn {
+            open: dl_info.open,
+            close: dl_info.close,
+            challenge: dl_info.challenge,
+            partitions: 1,
+            partitions_posted: deadline.partitions_posted.clone(),
+            live_sectors: 3,
+            total_sectors: 3,
+            live_power: deadline.live_power.clone(),
+            faulty_power: deadline.faulty_power.clone(),
+            daily_fee: deadline.daily_fee.clone(),
+        },
+        info
+    );
+    assert!(!info.faulty_power.is_zero());
+    assert!(info.daily_fee.is_positive());
+
+    rt.expect_validate_caller_any();
+    let partition: GetPartitionSectorsReturn = rt
+        .call::<Actor>(
+            Method::GetPartitionSectorsExported as u64,
+            IpldBlock::serialize_cbor(&GetPartitionSectorsParams {
+                deadline: dlidx,
+                partition: pidx,
+            })
+            .unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+
+    let all: Vec<u64> = sectors.iter().map(|s| s.sector_number).collect();
+    assert_eq!(
+        GetPartitionSectorsReturn {
+            all: bitfield_from_slice(&all),
+            live: bitfield_from_slice(&all),
+            unproven: bitfield_from_slice(&[]),
+            faulty: bitfield_from_slice(&all[..2]),
+            recovering: bitfield_from_slice(&all[1..2]),
+            terminated: bitfield_from_slice(&[]),
+        },
+        partition
+    );
+
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        rt.call::<Actor>(
+            Method::GetDeadlineInfoExported as u64,
+            IpldBlock::serialize_cbor(&GetDeadlineInfoParams {
+                deadline: rt.policy.wpost_period_deadlines,
+            })
+            .unwrap(),
+        ),
+    );
+    rt.verify();
+
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_NOT_FOUND,
+        rt.call::<Actor>(
+            Method::GetPartitionSectorsExported as u64,
+            IpldBlock::serialize_cbor(&GetPartitionSectorsParams {
+                deadline: dlidx,
+                partition: pidx + 1,
+            })
+            .unwrap(),
+        ),
+    );
+    rt.verify();
+
+    h.check_state(&rt);
+}
@@ -1,9 +1,9 @@
 use fil_actor_market::ActivatedDeal;
 use fil_actor_miner::ext::verifreg::Claim as FILPlusClaim;
 use fil_actor_miner::{
-    ExpirationExtension2, ExtendSectorExpiration2Params, PoStPartition, SectorClaim,
-    SectorOnChainInfo, State, daily_proof_fee, power_for_sector,
-    seal_proof_sector_maximum_lifetime,
+    ExpirationExtension2, ExpirationExtensionByClaims, ExtendSectorExpiration2Params,
+    ExtendSectorExpirationsByClaimsParams, PoStPartition, SectorClaim, SectorOnChainInfo, State,
+    daily_proof_fee, power_for_sector, seal_proof_sector_maximum_lifetime,
 };
 use fil_actors_runtime::DealWeight;
 use fil_actors_runtime::{
@@ -1052,6 +1052,182 @@ fn update_expiration2_drop_claims_failure_cases() {
     claim1.sector = old_sector.sector_number;
 }
 
+#[test]
+fn extend_expirations_by_recorded_claims() {
+    let (mut h, rt) = setup();
+    let verified_deals = vec![
+        test_activated_deal(h.sector_size as u64 / 2, 1),
+        test_activated_deal(h.sector_size as u64 / 2, 2),
+    ];
+    let policy = Policy::default();
+    let old_sector = commit_sector_verified_deals(&verified_deals, &mut h, &rt);
+    h.advance_and_submit_posts(&rt, &vec![old_sector.clone()]);
+    let state: State = rt.get_state();
+    let (deadline_index, partition_index) =
+        state.find_sector(rt.store(), old_sector.sector_number).unwrap();
+
+    let new_expiration = old_sector.expiration + 42 * rt.policy().wpost_proving_period;
+    let second_expiration = new_expiration + 42 * EPOCHS_IN_DAY;
+    let client = Address::new_id(3000).id().unwrap();
+    let claim_ids = h.get_sector_claims(&rt, old_sector.sector_number);
+    assert_eq!(2, claim_ids.len());
+    let claims: HashMap<_, _> = [
+        (
+            claim_ids[0],
+            make_claim(
+                claim_ids[0],
+                &old_sector,
+                client,
+                h.receiver.id().unwrap(),
+                second_expiration,
+                &verified_deals[0],
+                rt.policy.minimum_verified_allocation_term,
+            ),
+        ),
+        (
+            claim_ids[1],
+            make_claim(
+                claim_ids[1],
+                &old_sector,
+                client,
+                h.receiver.id().unwrap(),
+                new_expiration,
+                &verified_deals[1],
+                rt.policy.minimum_verified_allocation_term,
+            ),
+        ),
+    ]
+    .into();
+    let make_params = |new_expiration, drop_claims| ExtendSectorExpirationsByClaimsParams {
+        extensions: vec![ExpirationExtensionByClaims {
+            deadline: deadline_index,
+            partition: partition_index,
+            sectors: bitfield_from_slice(&[old_sector.sector_number]),
+            maintain_claims: vec![],
+            drop_claims,
+            new_expiration,
+        }],
+    };
+
+    // maintained claims must allow the new expiration
+    expect_abort_contains_message(
+        ExitCode::USR_FORBIDDEN,
+        "claim only allows extension to",
+        h.extend_sectors_by_claims(&rt, make_params(second_expiration, vec![]), claims.clone()),
+    );
+    rt.reset();
+
+    // dropped claims that are not recorded must belong to declared sectors without recorded claims
+    let mut other_claims = claims.clone();
+    let mut other_claim = claims[&claim_ids[1]].clone();
+    other_claim.sector = old_sector.sector_number + 1;
+    other_claims.insert(999, other_claim);
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "is not a claim of a sector without recorded claims",
+        h.extend_sectors_by_claims(&rt, make_params(new_expiration, vec![999]), other_claims),
+    );
+    rt.reset();
+
+    rt.set_epoch(old_sector.expiration - policy.end_of_life_claim_drop_period);
+    h.extend_sectors_by_claims(&rt, make_params(new_expiration, vec![claim_ids[1]]), claims)
+        .unwrap();
+    check_for_expiration(
+        &mut h,
+        &rt,
+        new_expiration,
+        old_sector.sector_number,
+        deadline_index,
+        partition_index,
+    );
+    assert_sector_verified_space(&mut h, &rt, old_sector.sector_number, verified_deals[0].size.0);
+    assert_eq!(vec![claim_ids[0]], h.get_sector_claims(&rt, old_sector.sector_number));
+}
+
+#[test]
+fn extend_expirations_by_declared_claims_of_unrecorded_sector() {
+    let (mut h, rt) = setup();
+    let verified_deals = vec![
+        test_activated_deal(h.sector_size as u64 / 2, 1),
+        test_activated_deal(h.sector_size as u64 / 2, 2),
+    ];
+    let policy = Policy::default();
+    let old_sector = commit_sector_verified_deals(&verified_deals, &mut h, &rt);
+    h.advance_and_submit_posts(&rt, &vec![old_sector.clone()]);
+    let (deadline_index, partition_index) =
+        h.get_state(&rt).find_sector(rt.store(), old_sector.sector_number).unwrap();
+
+    // Forget the sector's claims, as for a sector activated before claims were recorded.
+    let claim_ids = h.get_sector_claims(&rt, old_sector.sector_number);
+    let mut st = h.get_state(&rt);
+    st.delete_sector_claims(rt.store(), &bitfield_from_slice(&[old_sector.sector_number])).unwrap();
+    rt.replace_state(&st);
+
+    let new_expiration = old_sector.expiration + 42 * rt.policy().wpost_proving_period;
+    let client = Address::new_id(3000).id().unwrap();
+    let claims: HashMap<_, _> = claim_ids
+        .iter()
+        .zip(&verified_deals)
+        .map(|(id, deal)| {
+            let claim = make_claim(
+                *id,
+                &old_sector,
+                client,
+                h.receiver.id().unwrap(),
+                new_expiration,
+                deal,
+                rt.policy.minimum_verified_allocation_term,
+            );
+            (*id, claim)
+        })
+        .collect();
+    let make_params = |maintain_claims, drop_claims| ExtendSectorExpirationsByClaimsParams {
+        extensions: vec![ExpirationExtensionByClaims {
+            deadline: deadline_index,
+            partition: partition_index,
+            sectors: bitfield_from_slice(&[old_sector.sector_number]),
+            maintain_claims,
+            drop_claims,
+            new_expiration,
+        }],
+    };
+
+    // the declared claims must account for all of the sector's verified deal space
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "does not match verified deal space",
+        h.extend_sectors_by_claims(&rt, make_params(vec![claim_ids[0]], vec![]), claims.clone()),
+    );
+    rt.reset();
+
+    // a claim may be declared only once
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "declared more than once",
+        h.extend_sectors_by_claims(
+            &rt,
+            make_params(vec![claim_ids[0]], vec![claim_ids[0]]),
+            claims.clone(),
+        ),
+    );
+    rt.reset();
+
+    rt.set_epoch(old_sector.expiration - policy.end_of_life_claim_drop_period);
+    h.extend_sectors_by_claims(&rt, make_params(vec![claim_ids[0]], vec![claim_ids[1]]), claims)
+        .unwrap();
+    check_for_expiration(
+        &mut h,
+        &rt,
+        new_expiration,
+        old_sector.sector_number,
+        deadline_index,
+        partition_index,
+    );
+    assert_sector_verified_space(&mut h, &rt, old_sector.sector_number, verified_deals[0].size.0);
+    // The maintained claim is recorded from now on.
+    assert_eq!(vec![claim_ids[0]], h.get_sector_claims(&rt, old_sector.sector_number));
+}
+
 fn commit_sector_verified_deals(
     verified_deals: &[ActivatedDeal],
     h: &mut ActorHarness,
@@ -80,7 +80,7 @@ fn constructor_params(env: &TestEnv) -> ConstructorParams {
         control_addresses: env.control_addrs.clone(),
         window_post_proof_type: RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
         peer_id: env.peer_id.clone(),
-        multi_addresses: env.multiaddrs.clone(),
+        multi_addresses: env.multiaddrs.clone().into(),
     }
 }
 
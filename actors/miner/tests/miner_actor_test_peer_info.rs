@@ -123,7 +123,7 @@ fn get_and_change_multiaddrs_restricted_correctly() {
     let new_multiaddrs = vec![BytesDe(vec![1, 3, 3, 7])];
 
     let params = IpldBlock::serialize_cbor(&ChangeMultiaddrsParams {
-        new_multi_addrs: new_multiaddrs.clone(),
+        new_multi_addrs: new_multiaddrs.clone().into(),
     })
     .unwrap();
 
@@ -96,6 +96,157 @@ fn basic_post_and_dispute() {
     h.dispute_window_post(&rt, &dlinfo, 0, &dispute_sectors, Some(expected_result));
 }
 
+#[test]
+fn dispute_post_by_partition() {
+    let period_offset = ChainEpoch::from(100);
+    let precommit_epoch = ChainEpoch::from(1);
+
+    let mut h = ActorHarness::new(period_offset);
+    h.set_proof_type(RegisteredSealProof::StackedDRG2KiBV1P1);
+
+    let rt = h.new_runtime();
+    rt.epoch.replace(precommit_epoch);
+    rt.balance.replace(BIG_BALANCE.clone());
+
+    h.construct_and_verify(&rt);
+
+    let sectors = h.commit_and_prove_sectors(&rt, 1, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+    let sector = sectors[0].clone();
+    let pwr = miner::power_for_sector(h.sector_size, &sector);
+
+    let state = h.get_state(&rt);
+    let (dlidx, pidx) = state.find_sector(&rt.store, sector.sector_number).unwrap();
+    let dlinfo = h.advance_to_deadline(&rt, dlidx);
+    h.submit_window_post(
+        &rt,
+        &dlinfo,
+        vec![miner::PoStPartition { index: pidx, skipped: make_empty_bitfield() }],
+        vec![sector.clone()],
+        PoStConfig::with_expected_power_delta(&pwr),
+    );
+    let burnt_funds = miner::daily_fee_for_sectors(&sectors);
+    h.advance_deadline(&rt, CronConfig { burnt_funds, ..Default::default() });
+
+    // A partition proven by a valid proof can't be disputed, and one without a proof isn't found.
+    let partitions = [pidx, pidx + 1];
+    let ret = h.dispute_window_post_partitions(&rt, &dlinfo, &partitions, 0, &sectors, None);
+    assert_eq!(vec![ExitCode::USR_ILLEGAL_ARGUMENT, ExitCode::USR_NOT_FOUND], ret.codes());
+    assert_eq!(1, h.get_disputable_proofs(&rt, dlidx).proofs.len());
+
+    // An invalid proof is disputed and penalised as if disputed alone.
+    let expected_result = PoStDisputeResult {
+        expected_power_delta: Some(-pwr.clone()),
+        expected_penalty: Some(miner::pledge_penalty_for_invalid_windowpost(
+            &h.epoch_reward_smooth,
+            &h.epoch_qa_power_smooth,
+            &pwr.qa,
+        )),
+        expected_reward: Some(miner::BASE_REWARD_FOR_DISPUTED_WINDOW_POST.clone()),
+        expected_pledge_delta: None,
+    };
+    let ret = h.dispute_window_post_partitions(
+        &rt,
+        &dlinfo,
+        &partitions,
+        0,
+        &sectors,
+        Some(expected_result),
+    );
+    assert_eq!(vec![ExitCode::OK, ExitCode::USR_NOT_FOUND], ret.codes());
+    assert!(h.get_disputable_proofs(&rt, dlidx).proofs.is_empty());
+    let (_, partition) = h.find_sector(&rt, sector.sector_number);
+    assert!(partition.faults.get(sector.sector_number));
+}
+
+#[test]
+fn disputable_proofs_are_listed() {
+    let period_offset = ChainEpoch::from(100);
+    let precommit_epoch = ChainEpoch::from(1);
+
+    let mut h = ActorHarness::new(period_offset);
+    h.set_proof_type(RegisteredSealProof::StackedDRG2KiBV1P1);
+
+    let rt = h.new_runtime();
+    rt.epoch.replace(precommit_epoch);
+    rt.balance.replace(BIG_BALANCE.clone());
+
+    h.construct_and_verify(&rt);
+
+    let sectors = h.commit_and_prove_sectors(&rt, 1, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+    let sector = sectors[0].clone();
+    let pwr = miner::power_for_sector(h.sector_size, &sector);
+
+    let state = h.get_state(&rt);
+    let (dlidx, pidx) = state.find_sector(&rt.store, sector.sector_number).unwrap();
+    let dlinfo = h.advance_to_deadline(&rt, dlidx);
+
+    let post_epoch = *rt.epoch.borrow();
+    h.submit_window_post(
+        &rt,
+        &dlinfo,
+        vec![miner::PoStPartition { index: pidx, skipped: make_empty_bitfield() }],
+        vec![sector.clone()],
+        PoStConfig::with_expected_power_delta(&pwr),
+    );
+
+    // The proof is recorded with its prover and epoch, but can't be disputed while the
+    // challenge window is open.
+    let posts = amt_to_vec::<miner::WindowedPoSt>(
+        &rt,
+        &h.get_deadline(&rt, dlidx).optimistic_post_submissions,
+    );
+    assert_eq!(posts.len(), 1);
+    assert_eq!(Some(h.worker), posts[0].prover);
+    assert_eq!(Some(post_epoch), posts[0].epoch);
+    let ret = h.get_disputable_proofs(&rt, dlidx);
+    assert!(!ret.dispute_open);
+    assert!(ret.proofs.is_empty());
+
+    // Once the challenge window closes the proof is disputable.
+    let burnt_funds = miner::daily_fee_for_sectors(&sectors);
+    h.advance_deadline(&rt, CronConfig { burnt_funds, ..Default::default() });
+    let deadline = h.get_deadline(&rt, dlidx);
+    let ret = h.get_disputable_proofs(&rt, dlidx);
+    assert!(ret.dispute_open);
+    assert_eq!(deadline.sectors_snapshot, ret.sectors_snapshot);
+    assert_eq!(deadline.partitions_snapshot, ret.partitions_snapshot);
+    assert_eq!(1, ret.proofs.len());
+    let proof = &ret.proofs[0];
+    assert_eq!(0, proof.post_index);
+    assert_bitfield_equals(&proof.partitions, &[pidx]);
+    assert_eq!(make_post_proofs(h.window_post_proof_type), proof.proofs);
+    assert_eq!(Some(h.worker), proof.prover);
+    assert_eq!(Some(post_epoch), proof.epoch);
+
+    // A successfully disputed proof is no longer listed.
+    let expected_result = PoStDisputeResult {
+        expected_power_delta: Some(-pwr.clone()),
+        expected_penalty: Some(miner::pledge_penalty_for_invalid_windowpost(
+            &h.epoch_reward_smooth,
+            &h.epoch_qa_power_smooth,
+            &pwr.qa,
+        )),
+        expected_reward: Some(miner::BASE_REWARD_FOR_DISPUTED_WINDOW_POST.clone()),
+        expected_pledge_delta: None,
+    };
+    h.dispute_window_post(&rt, &dlinfo, 0, &sectors, Some(expected_result));
+    assert!(h.get_disputable_proofs(&rt, dlidx).proofs.is_empty());
+
+    // An invalid deadline is rejected.
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        rt.call::<miner::Actor>(
+            miner::Method::GetDisputableProofsExported as u64,
+            IpldBlock::serialize_cbor(&miner::GetDisputableProofsParams {
+                deadline: rt.policy.wpost_period_deadlines,
+            })
+            .unwrap(),
+        ),
+    );
+    rt.reset();
+}
+
 #[test]
 fn invalid_submissions() {
     let period_offset = ChainEpoch::from(100);
@@ -1332,3 +1483,68 @@ fn bad_post_fails_when_verified() {
     rt.reset();
     h.check_state(&rt);
 }
+
+#[test]
+fn check_post_without_submitting() {
+    let period_offset = ChainEpoch::from(100);
+    let precommit_epoch = ChainEpoch::from(1);
+
+    let mut h = ActorHarness::new(period_offset);
+    let rt = h.new_runtime();
+    rt.epoch.replace(precommit_epoch);
+    rt.balance.replace(BIG_BALANCE.clone());
+
+    h.construct_and_verify(&rt);
+
+    let sectors = h.commit_and_prove_sectors(&rt, 1, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+    let sector = sectors[0].clone();
+    let pwr = miner::power_for_sector(h.sector_size, &sector);
+
+    let state = h.get_state(&rt);
+    let (dlidx, pidx) = state.find_sector(&rt.store, sector.sector_number).unwrap();
+    let dlinfo = h.advance_to_deadline(&rt, dlidx);
+
+    let make_params = |deadline| miner::SubmitWindowedPoStParams {
+        deadline,
+        partitions: vec![miner::PoStPartition { index: pidx, skipped: make_empty_bitfield() }],
+        proofs: make_post_proofs(h.window_post_proof_type),
+        chain_commit_epoch: dlinfo.challenge,
+        chain_commit_rand: Randomness(TEST_RANDOMNESS_ARRAY_FROM_ONE.into()),
+    };
+
+    // A valid proof checks out, without changing state.
+    let state_before = *rt.state.borrow();
+    h.check_window_post(&rt, &dlinfo, &sectors, make_params(dlidx), ExitCode::OK).unwrap();
+    assert_eq!(state_before, *rt.state.borrow());
+    assert!(h.get_deadline(&rt, dlidx).partitions_posted.is_empty());
+
+    // An invalid proof is rejected.
+    let result = h.check_window_post(
+        &rt,
+        &dlinfo,
+        &sectors,
+        make_params(dlidx),
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+    );
+    expect_abort_contains_message(ExitCode::USR_ILLEGAL_ARGUMENT, "invalid post", result);
+    rt.reset();
+
+    // Parameters are validated as for submission.
+    rt.expect_validate_caller_any();
+    let result = rt.call::<miner::Actor>(
+        miner::Method::CheckWindowedPoStExported as u64,
+        IpldBlock::serialize_cbor(&make_params(rt.policy.wpost_period_deadlines)).unwrap(),
+    );
+    expect_abort_contains_message(ExitCode::USR_ILLEGAL_ARGUMENT, "invalid deadline", result);
+    rt.reset();
+
+    // The proof can still be submitted.
+    h.submit_window_post(
+        &rt,
+        &dlinfo,
+        make_params(dlidx).partitions,
+        vec![sector],
+        PoStConfig::with_expected_power_delta(&pwr),
+    );
+    h.check_state(&rt);
+}
@@ -0,0 +1,53 @@
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::RegisteredSealProof;
+use num_traits::Zero;
+
+mod util;
+
+use util::*;
+
+const PERIOD_OFFSET: ChainEpoch = 100;
+const DEFAULT_SECTOR_EXPIRATION: u64 = 220;
+
+#[test]
+fn miners_have_independent_state() {
+    let mut h = MultiMinerHarness::new(2, PERIOD_OFFSET);
+    h.set_proof_type(RegisteredSealProof::StackedDRG2KiBV1P1);
+    let mut rt = h.new_runtime();
+    rt.set_epoch(1);
+    h.construct_and_verify(&mut rt, &BIG_BALANCE);
+
+    // Each miner has its own actor and owner.
+    assert_ne!(h.miners[0].receiver, h.miners[1].receiver);
+    for idx in 0..2 {
+        let (owner, info) = h.with_miner(&mut rt, idx, |h, rt| (h.owner, h.get_info(rt)));
+        assert_eq!(owner, info.owner);
+    }
+    assert_ne!(h.miners[0].owner, h.miners[1].owner);
+
+    // Sectors committed by one miner are not seen by the other.
+    let sectors = h.with_miner(&mut rt, 1, |h, rt| {
+        h.commit_and_prove_sectors(rt, 2, DEFAULT_SECTOR_EXPIRATION, vec![], true)
+    });
+    h.with_miner(&mut rt, 0, |h, rt| {
+        let st = h.get_state(rt);
+        assert!(st.get_sector(&rt.store, sectors[0].sector_number).unwrap().is_none());
+    });
+
+    // A withdrawal draws on the active miner's balance only.
+    let other_balance = h.with_miner(&mut rt, 1, |_, rt| rt.get_balance());
+    h.with_miner(&mut rt, 0, |h, rt| {
+        h.withdraw_funds(
+            rt,
+            h.owner,
+            &ONE_PERCENT_BALANCE,
+            &ONE_PERCENT_BALANCE,
+            &TokenAmount::zero(),
+        )
+        .unwrap()
+    });
+    assert_eq!(other_balance, h.with_miner(&mut rt, 1, |_, rt| rt.get_balance()));
+
+    h.check_state(&mut rt);
+}
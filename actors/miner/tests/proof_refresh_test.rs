@@ -0,0 +1,96 @@
+use fil_actors_runtime::EPOCHS_IN_DAY;
+use fil_actors_runtime::test_utils::*;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::error::ExitCode;
+
+mod util;
+use util::*;
+
+const PERIOD_OFFSET: ChainEpoch = 100;
+
+fn setup(refresh_period: ChainEpoch) -> (ActorHarness, MockRuntime) {
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let mut rt = h.new_runtime();
+    rt.policy.proof_refresh_enabled = true;
+    rt.policy.proof_refresh_period = refresh_period;
+    h.construct_and_verify(&rt);
+    rt.set_balance(BIG_BALANCE.clone());
+    (h, rt)
+}
+
+#[test]
+fn reports_proof_not_refreshed_by_scheduled_epoch() {
+    let (mut h, rt) = setup(1);
+    let sectors = h.commit_and_prove_sectors(&rt, 1, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+    let sector = &sectors[0];
+    let refresh_epoch = sector.activation + 1;
+    assert_eq!(Some(refresh_epoch), h.get_proof_refresh_epoch(&rt, sector.sector_number));
+
+    // The refresh is due at the start of the next deadline, and reported by its cron.
+    h.advance_deadline(&rt, CronConfig::empty());
+    h.advance_deadline(
+        &rt,
+        CronConfig {
+            expired_proofs: vec![(sector.sector_number, refresh_epoch)],
+            ..CronConfig::empty()
+        },
+    );
+    assert_eq!(None, h.get_proof_refresh_epoch(&rt, sector.sector_number));
+    h.check_state(&rt);
+}
+
+#[test]
+fn does_not_report_expired_proofs_when_disabled() {
+    let (mut h, mut rt) = setup(1);
+    let sectors = h.commit_and_prove_sectors(&rt, 1, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+    let sector = &sectors[0];
+
+    rt.policy.proof_refresh_enabled = false;
+    h.advance_deadline(&rt, CronConfig::empty());
+    h.advance_deadline(&rt, CronConfig::empty());
+    assert_eq!(Some(sector.activation + 1), h.get_proof_refresh_epoch(&rt, sector.sector_number));
+    h.check_state(&rt);
+}
+
+#[test]
+fn extends_proof_refresh_epoch() {
+    let (mut h, mut rt) = setup(100 * EPOCHS_IN_DAY);
+    let sectors = h.commit_and_prove_sectors(&rt, 2, DEFAULT_SECTOR_EXPIRATION, vec![], true);
+    let numbers: Vec<_> = sectors.iter().map(|s| s.sector_number).collect();
+    let scheduled = sectors[0].activation + rt.policy.proof_refresh_period;
+
+    // The new epoch must be later than the scheduled one, but within the refresh period.
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.extend_proof_refresh(&rt, &numbers, scheduled));
+    rt.reset();
+    rt.set_epoch(*rt.epoch.borrow() + 10);
+    let max_epoch = *rt.epoch.borrow() + rt.policy.proof_refresh_period;
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        h.extend_proof_refresh(&rt, &numbers, max_epoch + 1),
+    );
+    rt.reset();
+    expect_abort(ExitCode::USR_NOT_FOUND, h.extend_proof_refresh(&rt, &[999], max_epoch));
+    rt.reset();
+
+    h.extend_proof_refresh(&rt, &numbers, max_epoch).unwrap();
+    for number in &numbers {
+        assert_eq!(Some(max_epoch), h.get_proof_refresh_epoch(&rt, *number));
+    }
+
+    // The refresh can't be postponed past two refresh periods after the sector was proven.
+    let latest_epoch = sectors[0].activation + 2 * rt.policy.proof_refresh_period;
+    let epoch = *rt.epoch.borrow();
+    rt.set_epoch(latest_epoch - 10);
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        h.extend_proof_refresh(&rt, &numbers, latest_epoch + 1),
+    );
+    rt.reset();
+    h.extend_proof_refresh(&rt, &numbers, latest_epoch).unwrap();
+    rt.set_epoch(epoch);
+
+    rt.policy.proof_refresh_enabled = false;
+    expect_abort(ExitCode::USR_FORBIDDEN, h.extend_proof_refresh(&rt, &numbers, latest_epoch));
+    rt.reset();
+    h.check_state(&rt);
+}
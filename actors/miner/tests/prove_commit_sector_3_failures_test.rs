@@ -47,7 +47,7 @@ fn reject_no_proof_types() {
     let (h, rt, activations) = setup_precommits(&[(0, 0, 0)]);
     let cfg = ProveCommitSectors3Config {
         param_twiddle: Some(Box::new(|p: &mut ProveCommitSectors3Params| {
-            p.sector_proofs = vec![];
+            p.sector_proofs = vec![].into();
             p.aggregate_proof = RawBytes::default();
         })),
         ..Default::default()
@@ -65,7 +65,7 @@ fn reject_both_proof_types() {
     let (h, rt, activations) = setup_precommits(&[(0, 0, 0)]);
     let cfg = ProveCommitSectors3Config {
         param_twiddle: Some(Box::new(|p: &mut ProveCommitSectors3Params| {
-            p.sector_proofs = vec![RawBytes::new(vec![1, 2, 3, 4])];
+            p.sector_proofs = vec![RawBytes::new(vec![1, 2, 3, 4])].into();
             p.aggregate_proof = RawBytes::new(vec![1, 2, 3, 4])
         })),
         ..Default::default()
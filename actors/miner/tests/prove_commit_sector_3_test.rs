@@ -1,15 +1,18 @@
+use fvm_ipld_bitfield::BitField;
 use fvm_ipld_encoding::RawBytes;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::error::ExitCode;
 use fvm_shared::sector::SectorNumber;
 use fvm_shared::{ActorID, clock::ChainEpoch};
 
 use fil_actor_miner::ext::verifreg::{AllocationClaim, SectorAllocationClaims};
 use fil_actor_miner::{
-    DataActivationNotification, PieceChange, ProveCommitSectors3Return, SectorChanges,
-    SectorOnChainInfo, SectorPreCommitInfo,
+    Actor, DataActivationNotification, GetSectorClaimsParams, Method, PieceChange,
+    ProveCommitSectors3Return, RetryNotificationsReturn, SectorActivationSummary, SectorChanges,
+    SectorOnChainInfo, SectorPreCommitInfo, State, power_for_sector,
 };
 use fil_actors_runtime::cbor::serialize;
-use fil_actors_runtime::test_utils::MockRuntime;
+use fil_actors_runtime::test_utils::{MockRuntime, expect_abort};
 use fil_actors_runtime::{BatchReturn, EPOCHS_IN_DAY, STORAGE_MARKET_ACTOR_ADDR};
 use util::*;
 
@@ -42,8 +45,8 @@ fn commit_batch() {
 
     let cfg = ProveCommitSectors3Config::default();
     let (result, claims, notifications) =
-        h.prove_commit_sectors3(&rt, &manifests, true, true, false, cfg).unwrap();
-    assert_commit_result(&[ExitCode::OK; 4], &result);
+        h.prove_commit_sectors3_with_summaries(&rt, &manifests, true, true, false, cfg).unwrap();
+    assert_eq!(BatchReturn::of(&[ExitCode::OK; 4]), result.activation_results);
     let sectors: Vec<SectorOnChainInfo> = snos.iter().map(|sno| h.get_sector(&rt, *sno)).collect();
 
     // Explicitly verify claims match what we expect.
@@ -116,6 +119,20 @@ fn commit_batch() {
     verify_weights(&rt, &h, snos[2], piece_size, 0);
     // Sector 3: Deal doesn't make a difference to verified weight only set.
     verify_weights(&rt, &h, snos[3], 0, piece_size);
+
+    // The returned summaries reflect the activated sectors, with a claim for each allocation.
+    let expected_summaries: Vec<SectorActivationSummary> = sectors
+        .iter()
+        .zip([0, 1, 0, 1])
+        .map(|(s, claims)| SectorActivationSummary {
+            sector: s.sector_number,
+            power: power_for_sector(h.sector_size, s),
+            initial_pledge: s.initial_pledge.clone(),
+            daily_fee: s.daily_fee.clone(),
+            claims,
+        })
+        .collect();
+    assert_eq!(expected_summaries, result.sectors);
     h.check_state(&rt);
 }
 
@@ -218,6 +235,63 @@ fn multiple_pieces_in_sector() {
     h.check_state(&rt);
 }
 
+#[test]
+fn sector_claims_recorded() {
+    let (h, mut rt) = setup_basic();
+    let piece_size = h.sector_size as u64 / 2;
+    let precommits = precommit_sectors(
+        &mut rt,
+        &h,
+        &[&[piece_size, piece_size], &[piece_size, piece_size], &[piece_size, piece_size]],
+    );
+    let snos: Vec<SectorNumber> =
+        precommits.iter().map(|pci: &SectorPreCommitInfo| pci.sector_number).collect();
+
+    let manifests = vec![
+        make_activation_manifest(
+            snos[0],
+            &[(piece_size, CLIENT_ID, 1000, 2000), (piece_size, CLIENT_ID, 1001, 0)],
+        ),
+        make_activation_manifest(
+            snos[1],
+            &[(piece_size, CLIENT_ID, 1002, 0), (piece_size, 0, 0, 0)],
+        ),
+        make_activation_manifest(snos[2], &[(piece_size, 0, 0, 2002), (piece_size, 0, 0, 0)]),
+    ];
+
+    let cfg = ProveCommitSectors3Config::default();
+    let (result, _, _) = h.prove_commit_sectors3(&rt, &manifests, true, true, false, cfg).unwrap();
+    assert_commit_result(&[ExitCode::OK; 3], &result);
+
+    // Each sector's claims are those of its verified pieces.
+    assert_eq!(vec![1000, 1001], h.get_sector_claims(&rt, snos[0]));
+    assert_eq!(vec![1002], h.get_sector_claims(&rt, snos[1]));
+    assert!(h.get_sector_claims(&rt, snos[2]).is_empty());
+
+    // The claims of a sector with verified deal weight but none recorded,
+    // as for sectors activated before claims were recorded, are unknown.
+    let mut st: State = h.get_state(&rt);
+    st.delete_sector_claims(rt.store(), &BitField::try_from_bits([snos[1]]).unwrap()).unwrap();
+    rt.replace_state(&st);
+    let ret = h.get_sector_claims_return(&rt, snos[1]);
+    assert!(!ret.known);
+    assert!(ret.claim_ids.is_empty());
+    st.put_sector_claims(rt.store(), [(snos[1], vec![1002])]).unwrap();
+    rt.replace_state(&st);
+
+    // A sector that is not committed is not found.
+    rt.expect_validate_caller_any();
+    expect_abort(
+        ExitCode::USR_NOT_FOUND,
+        rt.call::<Actor>(
+            Method::GetSectorClaimsExported as u64,
+            IpldBlock::serialize_cbor(&GetSectorClaimsParams { sector_number: 1 }).unwrap(),
+        ),
+    );
+    rt.reset();
+    h.check_state(&rt);
+}
+
 #[test]
 fn multiple_notifs_for_piece() {
     let (h, mut rt) = setup_basic();
@@ -465,6 +539,43 @@ fn aborted_notification_dropped() {
     verify_weights(&rt, &h, snos[1], 0, piece_size);
 }
 
+#[test]
+fn aborted_notification_retried() {
+    let (h, mut rt) = setup_basic();
+    let piece_size = h.sector_size as u64;
+    let precommits = precommit_sectors(&mut rt, &h, &[&[piece_size], &[piece_size]]);
+    let snos: Vec<SectorNumber> =
+        precommits.iter().map(|pci: &SectorPreCommitInfo| pci.sector_number).collect();
+
+    let manifests = vec![
+        make_activation_manifest(snos[0], &[(piece_size, CLIENT_ID, 1000, 2000)]),
+        make_activation_manifest(snos[1], &[(piece_size, CLIENT_ID, 1001, 2001)]),
+    ];
+
+    let cfg = ProveCommitSectors3Config {
+        notification_result: Some(ExitCode::USR_UNSPECIFIED),
+        ..Default::default()
+    };
+    let (result, _, notifications) =
+        h.prove_commit_sectors3(&rt, &manifests, false, false, false, cfg).unwrap();
+    assert_commit_result(&[ExitCode::OK; 2], &result);
+
+    // The receiver aborts again, so the notifications remain pending.
+    let ret = h.retry_notifications(&rt, 10, &notifications, ExitCode::USR_UNSPECIFIED).unwrap();
+    assert_eq!(RetryNotificationsReturn { delivered: 0, failed: 2, dropped: 0 }, ret);
+
+    // Retry is bounded by the number of sectors requested.
+    let ret = h.retry_notifications(&rt, 1, &notifications[..1], ExitCode::OK).unwrap();
+    assert_eq!(RetryNotificationsReturn { delivered: 1, failed: 0, dropped: 0 }, ret);
+    let ret = h.retry_notifications(&rt, 10, &notifications[1..], ExitCode::OK).unwrap();
+    assert_eq!(RetryNotificationsReturn { delivered: 1, failed: 0, dropped: 0 }, ret);
+
+    // Nothing remains to be retried.
+    let ret = h.retry_notifications(&rt, 10, &[], ExitCode::OK).unwrap();
+    assert_eq!(RetryNotificationsReturn { delivered: 0, failed: 0, dropped: 0 }, ret);
+    h.check_state(&rt);
+}
+
 #[test]
 fn rejected_notification_dropped() {
     let (h, mut rt) = setup_basic();
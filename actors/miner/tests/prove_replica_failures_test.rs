@@ -45,7 +45,7 @@ fn reject_no_proof_types() {
     let (h, rt, sector_updates) = setup(1, 0, 0, 0);
     let cfg = ProveReplicaUpdatesConfig {
         param_twiddle: Some(Box::new(|p: &mut ProveReplicaUpdates3Params| {
-            p.sector_proofs = vec![];
+            p.sector_proofs = vec![].into();
             p.aggregate_proof = RawBytes::default();
         })),
         ..Default::default()
@@ -63,7 +63,7 @@ fn reject_both_proof_types() {
     let (h, rt, sector_updates) = setup(1, 0, 0, 0);
     let cfg = ProveReplicaUpdatesConfig {
         param_twiddle: Some(Box::new(|p: &mut ProveReplicaUpdates3Params| {
-            p.sector_proofs = vec![RawBytes::new(vec![1, 2, 3, 4])];
+            p.sector_proofs = vec![RawBytes::new(vec![1, 2, 3, 4])].into();
             p.aggregate_proof = RawBytes::new(vec![1, 2, 3, 4])
         })),
         ..Default::default()
@@ -98,7 +98,7 @@ fn reject_aggregate_proof() {
     let (h, rt, sector_updates) = setup(1, 0, 0, 0);
     let cfg = ProveReplicaUpdatesConfig {
         param_twiddle: Some(Box::new(|p: &mut ProveReplicaUpdates3Params| {
-            p.sector_proofs = vec![];
+            p.sector_proofs = vec![].into();
             p.aggregate_proof = RawBytes::new(vec![1, 2, 3, 4]);
             p.aggregate_proof_type = Some(SnarkPackV2);
         })),
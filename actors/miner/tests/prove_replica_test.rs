@@ -7,13 +7,15 @@ use fvm_shared::{ActorID, clock::ChainEpoch};
 
 use fil_actor_miner::ext::verifreg::{AllocationClaim, SectorAllocationClaims};
 use fil_actor_miner::{
-    DataActivationNotification, PieceChange, SectorChanges, State, daily_proof_fee,
+    DataActivationNotification, PieceChange, SectorActivationSummary, SectorChanges, State,
+    daily_proof_fee, pledge_penalty_for_continued_fault, pledge_penalty_for_termination,
+    power_for_sector, qa_power_for_sector,
 };
 use fil_actor_miner::{ProveReplicaUpdates3Return, SectorOnChainInfo};
 use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::test_utils::{MockRuntime, expect_abort_contains_message};
 use fil_actors_runtime::{BatchReturn, EPOCHS_IN_DAY, STORAGE_MARKET_ACTOR_ADDR, runtime::Runtime};
-use num_traits::Zero;
+use num_traits::{Signed, Zero};
 use util::*;
 
 mod util;
@@ -43,9 +45,10 @@ fn update_batch() {
     ];
 
     let cfg = ProveReplicaUpdatesConfig::default();
-    let (result, claims, notifications) =
-        h.prove_replica_updates3_batch(&rt, &sector_updates, true, true, cfg).unwrap();
-    assert_update_result(&vec![ExitCode::OK; sectors.len()], &result);
+    let (result, claims, notifications) = h
+        .prove_replica_updates3_batch_with_summaries(&rt, &sector_updates, true, true, cfg)
+        .unwrap();
+    assert_eq!(BatchReturn::of(&vec![ExitCode::OK; sectors.len()]), result.activation_results);
 
     // Explicitly verify claims match what we expect.
     assert_eq!(
@@ -132,6 +135,18 @@ fn update_batch() {
         );
 
         total_fees += &after.daily_fee;
+
+        // The returned summary reflects the updated sector.
+        assert_eq!(
+            SectorActivationSummary {
+                sector: after.sector_number,
+                power: power_for_sector(h.sector_size, after),
+                initial_pledge: after.initial_pledge.clone(),
+                daily_fee: after.daily_fee.clone(),
+                claims: if has_verified { 1 } else { 0 },
+            },
+            result.sectors[i]
+        );
     }
 
     let (deadline_index, partition_index) = st.find_sector(rt.store(), snos[0]).unwrap();
@@ -689,6 +704,70 @@ fn update_to_empty() {
     h.check_state(&rt);
 }
 
+#[test]
+fn revert_update() {
+    let (h, rt, sectors) = setup_empty_sectors(2);
+    let snos = sectors.iter().map(|s| s.sector_number).collect::<Vec<_>>();
+    let st: State = h.get_state(&rt);
+    let store = rt.store();
+    let piece_size = h.sector_size as u64;
+
+    let sector_updates =
+        vec![make_update_manifest(&st, store, snos[0], &[(piece_size, CLIENT_ID, 1000, 2000)])];
+    let cfg = ProveReplicaUpdatesConfig::default();
+    h.prove_replica_updates3_batch(&rt, &sector_updates, true, true, cfg).unwrap();
+    let updated = h.get_sector(&rt, snos[0]);
+
+    // A sector that hasn't been updated can't be reverted.
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "has no replica update to revert",
+        h.revert_replica_update(&rt, &[snos[1]], &TokenAmount::zero()),
+    );
+    rt.reset();
+
+    // Reverting drops the power of the verified data, for the sector's termination fee.
+    let fault_fee = pledge_penalty_for_continued_fault(
+        &h.epoch_reward_smooth,
+        &h.epoch_qa_power_smooth,
+        &qa_power_for_sector(h.sector_size, &updated),
+    );
+    let fee = pledge_penalty_for_termination(
+        &updated.initial_pledge,
+        *rt.epoch.borrow() - updated.activation,
+        &fault_fee,
+    );
+    assert!(fee.is_positive());
+    h.revert_replica_update(&rt, &[snos[0]], &fee).unwrap();
+
+    let reverted = h.get_sector(&rt, snos[0]);
+    assert_eq!(sectors[0].sealed_cid, reverted.sealed_cid);
+    assert_eq!(None, reverted.sector_key_cid);
+    assert_eq!(updated.initial_pledge, reverted.initial_pledge);
+    assert!(reverted.daily_fee < updated.daily_fee);
+    assert!(reverted.deal_weight.is_zero());
+    assert!(reverted.verified_deal_weight.is_zero());
+    assert!(h.get_state(&rt).get_sector_claims(rt.store(), snos[0]).unwrap().is_empty());
+
+    // The sector can be neither reverted nor updated again.
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "has no replica update to revert",
+        h.revert_replica_update(&rt, &[snos[0]], &TokenAmount::zero()),
+    );
+    rt.reset();
+    let st: State = h.get_state(&rt);
+    let sector_updates =
+        vec![make_update_manifest(&st, store, snos[0], &[(piece_size, CLIENT_ID, 1001, 2001)])];
+    let cfg = ProveReplicaUpdatesConfig::default();
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "cannot update sector with a reverted replica update",
+        h.prove_replica_updates3_batch(&rt, &sector_updates, true, true, cfg),
+    );
+    h.check_state(&rt);
+}
+
 fn setup_basic() -> (ActorHarness, MockRuntime) {
     let h = ActorHarness::new_with_options(HarnessOptions::default());
     let rt = h.new_runtime();
@@ -2,6 +2,7 @@ use fil_actor_miner::{Actor, Method, locked_reward_from_reward};
 use fil_actors_runtime::BURNT_FUNDS_ACTOR_ADDR;
 use fil_actors_runtime::test_utils::{EVM_ACTOR_CODE_ID, expect_abort_contains_message};
 use fvm_shared::METHOD_SEND;
+use fvm_shared::address::Address;
 use fvm_shared::bigint::Zero;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
@@ -75,12 +76,20 @@ fn repay_debt_restricted_correctly() {
 
     // can call the exported method
 
-    rt.expect_validate_caller_addr(h.caller_addrs());
+    rt.expect_validate_caller_any();
 
     rt.add_balance(fee_debt.clone());
     rt.set_received(fee_debt.clone());
 
-    rt.expect_send_simple(BURNT_FUNDS_ACTOR_ADDR, METHOD_SEND, None, fee_debt, None, ExitCode::OK);
+    rt.expect_send_simple(
+        BURNT_FUNDS_ACTOR_ADDR,
+        METHOD_SEND,
+        None,
+        fee_debt.clone(),
+        None,
+        ExitCode::OK,
+    );
+    expect_fee_debt_repaid_event(&rt, h.owner, &fee_debt);
 
     rt.call::<Actor>(Method::RepayDebtExported as u64, None).unwrap();
 
@@ -91,6 +100,36 @@ fn repay_debt_restricted_correctly() {
     h.check_state(&rt);
 }
 
+#[test]
+fn third_party_can_repay_debt() {
+    let h = ActorHarness::new(PERIOD_OFFSET);
+    let rt = h.new_runtime();
+    h.construct_and_verify(&rt);
+
+    // introduce fee debt
+    let mut st = h.get_state(&rt);
+    let fee_debt: TokenAmount = 4 * &*BIG_BALANCE;
+    st.fee_debt = fee_debt.clone();
+    rt.replace_state(&st);
+
+    // an actor with no role in the miner repays only from the value it sends,
+    // though the miner's balance could cover the debt
+    rt.add_balance(fee_debt.clone());
+    let financier = Address::new_id(5000);
+    let value = BIG_BALANCE.clone();
+    h.repay_debts_from(&rt, financier, &value, &TokenAmount::zero(), &value).unwrap();
+    assert_eq!(&fee_debt - &value, h.get_state(&rt).fee_debt);
+
+    // it then pays off the rest, and is refunded the value in excess of the debt
+    let remaining = &fee_debt - &value;
+    let value = &remaining + &*BIG_BALANCE;
+    h.repay_debts_from(&rt, financier, &value, &TokenAmount::zero(), &remaining).unwrap();
+    assert!(h.get_state(&rt).fee_debt.is_zero());
+    // nothing is repaid once there is no debt, and all the value is refunded
+    h.repay_debts_from(&rt, financier, &value, &TokenAmount::zero(), &TokenAmount::zero()).unwrap();
+    h.check_state(&rt);
+}
+
 #[test]
 fn partially_repay_debt() {
     let h = ActorHarness::new(PERIOD_OFFSET);
@@ -9,11 +9,12 @@ mod serialization {
 
     use fil_actor_miner::{
         Deadline, ExpirationSet, PowerPair, ProveCommitSectorsNIParams, SectorNIActivationInfo,
-        SectorOnChainInfo, SectorOnChainInfoFlags,
+        SectorOnChainInfo, SectorOnChainInfoFlags, WindowedPoSt,
     };
     use fvm_ipld_bitfield::BitField;
     use fvm_ipld_bitfield::iter::Ranges;
     use fvm_ipld_encoding::ipld_block::IpldBlock;
+    use fvm_shared::address::Address;
     use fvm_shared::bigint::BigInt;
     use fvm_shared::econ::TokenAmount;
     use fvm_shared::sector::{RegisteredAggregateProof, RegisteredSealProof};
@@ -267,6 +268,46 @@ mod serialization {
         }
     }
 
+    #[test]
+    fn windowed_post() {
+        let test_cases = vec![
+            (
+                WindowedPoSt {
+                    partitions: BitField::new(),
+                    proofs: vec![],
+                    prover: None,
+                    epoch: None,
+                },
+                // [[],[]]
+                &hex!("824080")[..],
+                // [[],[],null,null]
+                &hex!("844080f6f6")[..],
+            ),
+            (
+                WindowedPoSt {
+                    partitions: BitField::new(),
+                    proofs: vec![],
+                    prover: Some(Address::new_id(1)),
+                    epoch: Some(2),
+                },
+                // [[],[],[AAE],2]
+                &hex!("84408042000102")[..],
+                // same on write as read
+                &hex!("84408042000102")[..],
+            ),
+        ];
+
+        for (idx, (params, read_bytes, write_bytes)) in test_cases.into_iter().enumerate() {
+            let encoded = IpldBlock::serialize_cbor(&params).unwrap().unwrap();
+            assert_eq!(encoded.data, write_bytes, "Test case {} encoding failed", idx);
+
+            let decoded: WindowedPoSt =
+                IpldBlock::deserialize(&IpldBlock { codec: 0x71, data: read_bytes.to_vec() })
+                    .unwrap();
+            assert_eq!(params, decoded, "Test case {} decoding failed", idx);
+        }
+    }
+
     #[test]
     fn deadline() {
         let test_cases = vec![
@@ -54,15 +54,20 @@ use fil_actor_miner::{
     CompactPartitionsParams, CompactSectorNumbersParams, CronEventPayload,
     DataActivationNotification, Deadline, DeadlineInfo, Deadlines, DeclareFaultsParams,
     DeclareFaultsRecoveredParams, DeferredCronEventParams, DisputeWindowedPoStParams,
-    ExpirationQueue, ExpirationSet, ExtendSectorExpiration2Params, FaultDeclaration,
+    DisputeWindowedPoStPartitionsParams, ExpirationQueue, ExpirationSet, ExtendProofRefreshParams,
+    ExtendSectorExpiration2Params, ExtendSectorExpirationsByClaimsParams, FaultDeclaration,
     GetAvailableBalanceReturn, GetBeneficiaryReturn, GetControlAddressesReturn,
-    GetMultiaddrsReturn, GetPeerIDReturn, Method, Method as MinerMethod,
-    MinerConstructorParams as ConstructorParams, MinerInfo, NO_QUANTIZATION, Partition,
-    PendingBeneficiaryChange, PieceActivationManifest, PieceChange, PieceReturn, PoStPartition,
-    PowerPair, PreCommitSectorBatchParams, PreCommitSectorBatchParams2, PreCommitSectorParams,
-    ProveCommitSectorParams, ProveCommitSectors3Params, ProveCommitSectors3Return, QuantSpec,
-    RecoveryDeclaration, ReportConsensusFaultParams, SECTOR_CONTENT_CHANGED, SECTORS_AMT_BITWIDTH,
-    SectorActivationManifest, SectorChanges, SectorContentChangedParams,
+    GetDisputableProofsParams, GetDisputableProofsReturn, GetLockedFundsReturn,
+    GetMultiaddrsReturn, GetPartitionReportReturn, GetPeerIDReturn, GetProofRefreshEpochParams,
+    GetProofRefreshEpochReturn, GetSectorClaimsParams, GetSectorClaimsReturn, Method,
+    Method as MinerMethod, MinerConstructorParams as ConstructorParams, MinerInfo, NO_QUANTIZATION,
+    Partition, PendingBeneficiaryChange, PieceActivationManifest, PieceChange, PieceReturn,
+    PoStPartition, PowerPair, PreCommitSectorBatchParams, PreCommitSectorBatchParams2,
+    PreCommitSectorParams, ProveCommitSectorParams, ProveCommitSectors3Params,
+    ProveCommitSectors3Return, ProveCommitSectors3WithSummariesReturn, QuantSpec,
+    RecoveryDeclaration, ReportConsensusFaultParams, RetryNotificationsParams,
+    RetryNotificationsReturn, RevertReplicaUpdateParams, SECTOR_CONTENT_CHANGED,
+    SECTORS_AMT_BITWIDTH, SectorActivationManifest, SectorChanges, SectorContentChangedParams,
     SectorContentChangedReturn, SectorOnChainInfo, SectorPreCommitInfo, SectorPreCommitOnChainInfo,
     SectorReturn, SectorUpdateManifest, Sectors, State, SubmitWindowedPoStParams,
     TerminateSectorsParams, TerminationDeclaration, VerifiedAllocationKey, WindowedPoSt,
@@ -71,7 +76,8 @@ use fil_actor_miner::{
     ext::power::UPDATE_CLAIMED_POWER_METHOD,
     ext::verifreg::{
         AllocationClaim, AllocationID, CLAIM_ALLOCATIONS_METHOD, ClaimAllocationsParams,
-        ClaimAllocationsReturn, SectorAllocationClaims, SectorClaimSummary,
+        ClaimAllocationsReturn, REMOVE_SECTOR_CLAIMS_METHOD, RemoveSectorClaimsParams,
+        SectorAllocationClaims, SectorClaimSummary,
     },
     ext::verifreg::{Claim as FILPlusClaim, ClaimID, GetClaimsParams, GetClaimsReturn},
     initial_pledge_for_power, locked_reward_from_reward, max_prove_commit_duration,
@@ -81,7 +87,8 @@ use fil_actor_miner::{
 };
 use fil_actor_miner::{
     ProveCommitSectorsNIParams, ProveCommitSectorsNIReturn, ProveReplicaUpdates3Params,
-    ProveReplicaUpdates3Return, SectorNIActivationInfo, raw_power_for_sector,
+    ProveReplicaUpdates3Return, ProveReplicaUpdates3WithSummariesReturn, SectorNIActivationInfo,
+    raw_power_for_sector,
 };
 use fil_actor_power::{
     CurrentTotalPowerReturn, EnrollCronEventParams, Method as PowerMethod, UpdateClaimedPowerParams,
@@ -188,6 +195,115 @@ impl Default for HarnessOptions {
     }
 }
 
+/// A harness for several miners sharing one MockRuntime, for tests of interactions between miners.
+/// The runtime acts as one miner at a time: switching to another miner saves the state root and
+/// balance of the active miner and restores those of the other.
+/// All miners share the network power and reward parameters, proof type, and proving period offset.
+pub struct MultiMinerHarness {
+    pub miners: Vec<ActorHarness>,
+    active: usize,
+    // The state root and balance of each miner, as of when it was last active.
+    saved: Vec<(Option<Cid>, TokenAmount)>,
+}
+
+#[allow(dead_code)]
+impl MultiMinerHarness {
+    /// Creates a harness for `count` miners. The first miner has the same addresses as a
+    /// single-miner harness; each other miner has its own actor, owner, worker and control addresses.
+    pub fn new(count: u64, proving_period_offset: ChainEpoch) -> MultiMinerHarness {
+        assert!(count > 0, "at least one miner is required");
+        let miners = (0..count)
+            .map(|i| {
+                let mut h = ActorHarness::new(proving_period_offset);
+                if i > 0 {
+                    let base = 100 + 10 * i;
+                    h.receiver = Address::new_id(RECEIVER_ID + i);
+                    h.owner = Address::new_id(base);
+                    h.worker = Address::new_id(base + 1);
+                    h.control_addrs = (2..5).map(|j| Address::new_id(base + j)).collect();
+                    h.worker_key = new_bls_addr(i as u8);
+                    h.beneficiary = h.owner;
+                }
+                h
+            })
+            .collect();
+        MultiMinerHarness {
+            miners,
+            active: 0,
+            saved: vec![(None, TokenAmount::zero()); count as usize],
+        }
+    }
+
+    pub fn set_proof_type(&mut self, proof_type: RegisteredSealProof) {
+        for h in self.miners.iter_mut() {
+            h.set_proof_type(proof_type);
+        }
+    }
+
+    /// Creates a runtime acting as the first miner, which knows the accounts of all miners,
+    /// and knows each miner actor as such.
+    pub fn new_runtime(&self) -> MockRuntime {
+        let rt = self.miners[0].new_runtime();
+        for h in self.miners.iter() {
+            let mut code_cids = rt.actor_code_cids.borrow_mut();
+            code_cids.insert(h.receiver, *MINER_ACTOR_CODE_ID);
+            for addr in h.control_addrs.iter().chain(&[h.owner, h.worker]) {
+                code_cids.insert(*addr, *ACCOUNT_ACTOR_CODE_ID);
+            }
+        }
+        rt
+    }
+
+    /// The index of the miner the runtime is acting as.
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Switches the runtime to act as the miner at `idx`.
+    pub fn switch_to(&mut self, rt: &mut MockRuntime, idx: usize) {
+        if idx == self.active {
+            return;
+        }
+        self.saved[self.active] = (*rt.state.borrow(), rt.get_balance());
+        let (state, balance) = self.saved[idx].clone();
+        rt.state.replace(state);
+        rt.balance.replace(balance);
+        rt.receiver = self.miners[idx].receiver;
+        self.active = idx;
+    }
+
+    /// Runs `f` with the runtime acting as the miner at `idx`, and leaves it acting as that miner.
+    pub fn with_miner<R>(
+        &mut self,
+        rt: &mut MockRuntime,
+        idx: usize,
+        f: impl FnOnce(&mut ActorHarness, &MockRuntime) -> R,
+    ) -> R {
+        self.switch_to(rt, idx);
+        f(&mut self.miners[idx], rt)
+    }
+
+    /// Constructs every miner, each with the given balance in addition to its creation deposit.
+    pub fn construct_and_verify(&mut self, rt: &mut MockRuntime, balance: &TokenAmount) {
+        for idx in 0..self.miners.len() {
+            self.with_miner(rt, idx, |h, rt| {
+                rt.set_balance(balance.clone());
+                h.construct_and_verify(rt);
+            });
+        }
+        self.switch_to(rt, 0);
+    }
+
+    /// Checks the state invariants of every miner, leaving the runtime acting as the same miner.
+    pub fn check_state(&mut self, rt: &mut MockRuntime) {
+        let active = self.active;
+        for idx in 0..self.miners.len() {
+            self.with_miner(rt, idx, |h, rt| h.check_state(rt));
+        }
+        self.switch_to(rt, active);
+    }
+}
+
 #[allow(dead_code)]
 impl ActorHarness {
     pub fn new_with_options(options: HarnessOptions) -> ActorHarness {
@@ -302,7 +418,7 @@ impl ActorHarness {
             control_addresses: self.control_addrs.clone(),
             window_post_proof_type: self.window_post_proof_type,
             peer_id: vec![0],
-            multi_addresses: vec![],
+            multi_addresses: vec![].into(),
         };
 
         rt.actor_code_cids.borrow_mut().insert(self.owner, *ACCOUNT_ACTOR_CODE_ID);
@@ -394,7 +510,7 @@ impl ActorHarness {
     }
 
     pub fn set_multiaddr(&self, rt: &MockRuntime, new_multiaddrs: Vec<BytesDe>) {
-        let params = ChangeMultiaddrsParams { new_multi_addrs: new_multiaddrs.clone() };
+        let params = ChangeMultiaddrsParams { new_multi_addrs: new_multiaddrs.clone().into() };
 
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
         rt.expect_validate_caller_addr(self.caller_addrs());
@@ -421,7 +537,7 @@ impl ActorHarness {
     }
 
     pub fn set_multiaddr_fail(&self, rt: &MockRuntime, new_multiaddrs: Vec<BytesDe>) {
-        let params = ChangeMultiaddrsParams { new_multi_addrs: new_multiaddrs };
+        let params = ChangeMultiaddrsParams { new_multi_addrs: new_multiaddrs.into() };
 
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
 
@@ -1151,6 +1267,8 @@ impl ActorHarness {
                         claimed_space: BigInt::from(
                             sector.claims.iter().map(|c| c.size.0).sum::<u64>(),
                         ),
+                        term_start: *rt.epoch.borrow(),
+                        sector_expiry: sector.expiry,
                     })
                     .collect(),
             };
@@ -1200,6 +1318,62 @@ impl ActorHarness {
         (ProveCommitSectors3Return, Vec<SectorAllocationClaims>, Vec<SectorChanges>),
         ActorError,
     > {
+        let (ret, claims, notifications) = self
+            .prove_commit_sectors3_method::<ProveCommitSectors3Return>(
+                rt,
+                MinerMethod::ProveCommitSectors3 as u64,
+                sector_activations,
+                require_activation_success,
+                require_notification_success,
+                aggregate,
+                cfg,
+            )?;
+        assert_eq!(sector_activations.len(), ret.activation_results.size());
+        Ok((ret, claims, notifications))
+    }
+
+    pub fn prove_commit_sectors3_with_summaries(
+        &self,
+        rt: &MockRuntime,
+        sector_activations: &[SectorActivationManifest],
+        require_activation_success: bool,
+        require_notification_success: bool,
+        aggregate: bool,
+        cfg: ProveCommitSectors3Config,
+    ) -> Result<
+        (ProveCommitSectors3WithSummariesReturn, Vec<SectorAllocationClaims>, Vec<SectorChanges>),
+        ActorError,
+    > {
+        let (ret, claims, notifications) = self
+            .prove_commit_sectors3_method::<ProveCommitSectors3WithSummariesReturn>(
+                rt,
+                MinerMethod::ProveCommitSectors3WithSummaries as u64,
+                sector_activations,
+                require_activation_success,
+                require_notification_success,
+                aggregate,
+                cfg,
+            )?;
+        assert_eq!(sector_activations.len(), ret.activation_results.size());
+        Ok((ret, claims, notifications))
+    }
+
+    // Invokes a prove commit sectors 3 method, and sets and checks mock expectations for
+    // the expected interactions.
+    #[allow(clippy::too_many_arguments)]
+    fn prove_commit_sectors3_method<R>(
+        &self,
+        rt: &MockRuntime,
+        method: u64,
+        sector_activations: &[SectorActivationManifest],
+        require_activation_success: bool,
+        require_notification_success: bool,
+        aggregate: bool,
+        cfg: ProveCommitSectors3Config,
+    ) -> Result<(R, Vec<SectorAllocationClaims>, Vec<SectorChanges>), ActorError>
+    where
+        R: for<'a> Deserialize<'a>,
+    {
         fn make_proof(i: u8) -> RawBytes {
             RawBytes::new(vec![i, i, i, i])
         }
@@ -1210,7 +1384,7 @@ impl ActorHarness {
             ProveCommitSectors3Params {
                 sector_activations: sector_activations.into(),
                 aggregate_proof: make_proof(0),
-                sector_proofs: vec![],
+                sector_proofs: vec![].into(),
                 aggregate_proof_type: Some(RegisteredAggregateProof::SnarkPackV2),
                 require_activation_success,
                 require_notification_success,
@@ -1322,7 +1496,11 @@ impl ActorHarness {
                 .iter()
                 .map(|c| c.size.0)
                 .sum::<u64>();
-            sector_claimed_space.push(SectorClaimSummary { claimed_space: claimed_space.into() });
+            sector_claimed_space.push(SectorClaimSummary {
+                claimed_space: claimed_space.into(),
+                term_start: *rt.epoch.borrow(),
+                sector_expiry: precommit.info.expiration,
+            });
 
             let notifications = notifications_from_pieces(&sa.pieces);
             if !notifications.is_empty() {
@@ -1425,20 +1603,11 @@ impl ActorHarness {
             )
         }
 
-        let result = rt.call::<Actor>(
-            MinerMethod::ProveCommitSectors3 as u64,
-            IpldBlock::serialize_cbor(&params).unwrap(),
-        );
-        let result = result
-            .map(|r| {
-                let ret: ProveCommitSectors3Return = r.unwrap().deserialize().unwrap();
-                assert_eq!(sector_activations.len(), ret.activation_results.size());
-                ret
-            })
-            .or_else(|e| {
-                rt.reset();
-                Err(e)
-            })?;
+        let result = rt.call::<Actor>(method, IpldBlock::serialize_cbor(&params).unwrap());
+        let result = result.map(|r| r.unwrap().deserialize().unwrap()).or_else(|e| {
+            rt.reset();
+            Err(e)
+        })?;
         rt.verify();
         Ok((result, sector_allocation_claims, expected_sector_notifications))
     }
@@ -1458,6 +1627,56 @@ impl ActorHarness {
         (ProveReplicaUpdates3Return, Vec<SectorAllocationClaims>, Vec<SectorChanges>),
         ActorError,
     > {
+        let (ret, claims, notifications) = self
+            .prove_replica_updates3_method::<ProveReplicaUpdates3Return>(
+                rt,
+                MinerMethod::ProveReplicaUpdates3 as u64,
+                sector_updates,
+                require_activation_success,
+                require_notification_success,
+                cfg,
+            )?;
+        assert_eq!(sector_updates.len(), ret.activation_results.size());
+        Ok((ret, claims, notifications))
+    }
+
+    // As for prove_replica_updates3_batch, invoking the method that returns sector summaries.
+    pub fn prove_replica_updates3_batch_with_summaries(
+        &self,
+        rt: &MockRuntime,
+        sector_updates: &[SectorUpdateManifest],
+        require_activation_success: bool,
+        require_notification_success: bool,
+        cfg: ProveReplicaUpdatesConfig,
+    ) -> Result<
+        (ProveReplicaUpdates3WithSummariesReturn, Vec<SectorAllocationClaims>, Vec<SectorChanges>),
+        ActorError,
+    > {
+        let (ret, claims, notifications) = self
+            .prove_replica_updates3_method::<ProveReplicaUpdates3WithSummariesReturn>(
+                rt,
+                MinerMethod::ProveReplicaUpdates3WithSummaries as u64,
+                sector_updates,
+                require_activation_success,
+                require_notification_success,
+                cfg,
+            )?;
+        assert_eq!(sector_updates.len(), ret.activation_results.size());
+        Ok((ret, claims, notifications))
+    }
+
+    fn prove_replica_updates3_method<R>(
+        &self,
+        rt: &MockRuntime,
+        method: u64,
+        sector_updates: &[SectorUpdateManifest],
+        require_activation_success: bool,
+        require_notification_success: bool,
+        cfg: ProveReplicaUpdatesConfig,
+    ) -> Result<(R, Vec<SectorAllocationClaims>, Vec<SectorChanges>), ActorError>
+    where
+        R: for<'a> Deserialize<'a>,
+    {
         fn make_proof(i: u8) -> RawBytes {
             RawBytes::new(vec![i, i, i, i])
         }
@@ -1519,7 +1738,11 @@ impl ActorHarness {
             }
             let claimed_space =
                 expected_sector_claims.last().unwrap().claims.iter().map(|c| c.size.0).sum::<u64>();
-            sector_claimed_space.push(SectorClaimSummary { claimed_space: claimed_space.into() });
+            sector_claimed_space.push(SectorClaimSummary {
+                claimed_space: claimed_space.into(),
+                term_start: *rt.epoch.borrow(),
+                sector_expiry: sector.expiration,
+            });
 
             let notifications = notifications_from_pieces(&sup.pieces);
             if !notifications.is_empty() {
@@ -1631,20 +1854,11 @@ impl ActorHarness {
             )
         }
 
-        let result = rt.call::<Actor>(
-            MinerMethod::ProveReplicaUpdates3 as u64,
-            IpldBlock::serialize_cbor(&params).unwrap(),
-        );
-        let result = result
-            .map(|r| {
-                let ret: ProveReplicaUpdates3Return = r.unwrap().deserialize().unwrap();
-                assert_eq!(sector_updates.len(), ret.activation_results.size());
-                ret
-            })
-            .or_else(|e| {
-                rt.reset();
-                Err(e)
-            })?;
+        let result = rt.call::<Actor>(method, IpldBlock::serialize_cbor(&params).unwrap());
+        let result = result.map(|r| r.unwrap().deserialize().unwrap()).or_else(|e| {
+            rt.reset();
+            Err(e)
+        })?;
         rt.verify();
         Ok((result, expected_sector_claims, expected_sector_notifications))
     }
@@ -1654,6 +1868,152 @@ impl ActorHarness {
         state.get_sector(&rt.store, sector_number).unwrap().unwrap()
     }
 
+    pub fn get_sector_claims(&self, rt: &MockRuntime, sector_number: SectorNumber) -> Vec<ClaimID> {
+        let ret = self.get_sector_claims_return(rt, sector_number);
+        assert!(ret.known);
+        ret.claim_ids
+    }
+
+    pub fn get_sector_claims_return(
+        &self,
+        rt: &MockRuntime,
+        sector_number: SectorNumber,
+    ) -> GetSectorClaimsReturn {
+        rt.expect_validate_caller_any();
+        let ret: GetSectorClaimsReturn = rt
+            .call::<Actor>(
+                Method::GetSectorClaimsExported as u64,
+                IpldBlock::serialize_cbor(&GetSectorClaimsParams { sector_number }).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret
+    }
+
+    pub fn get_proof_refresh_epoch(
+        &self,
+        rt: &MockRuntime,
+        sector_number: SectorNumber,
+    ) -> Option<ChainEpoch> {
+        rt.expect_validate_caller_any();
+        let ret: GetProofRefreshEpochReturn = rt
+            .call::<Actor>(
+                Method::GetProofRefreshEpochExported as u64,
+                IpldBlock::serialize_cbor(&GetProofRefreshEpochParams { sector_number }).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret.refresh_epoch
+    }
+
+    pub fn extend_proof_refresh(
+        &self,
+        rt: &MockRuntime,
+        sectors: &[SectorNumber],
+        new_refresh_epoch: ChainEpoch,
+    ) -> Result<(), ActorError> {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
+        rt.expect_validate_caller_addr(self.caller_addrs());
+        for sector in sectors {
+            expect_proof_refresh_event(rt, "proof-refresh-extended", *sector, new_refresh_epoch);
+        }
+        let params = ExtendProofRefreshParams {
+            sectors: BitField::try_from_bits(sectors.iter().copied()).unwrap(),
+            new_refresh_epoch,
+        };
+        rt.call::<Actor>(
+            Method::ExtendProofRefreshExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )?;
+        rt.verify();
+        Ok(())
+    }
+
+    /// Reverts the replica updates of active sectors, expecting a penalty.
+    pub fn revert_replica_update(
+        &self,
+        rt: &MockRuntime,
+        sectors: &[SectorNumber],
+        expected_fee: &TokenAmount,
+    ) -> Result<(), ActorError> {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
+        rt.expect_validate_caller_addr(self.caller_addrs());
+        self.expect_query_network_info(rt);
+
+        let mut power_delta = PowerPair::zero();
+        let mut with_data = vec![];
+        let mut with_claims = vec![];
+        for sector_number in sectors {
+            let sector = self.get_sector(rt, *sector_number);
+            power_delta.qa += BigInt::from(self.sector_size as u64)
+                - qa_power_for_sector(self.sector_size, &sector);
+            if sector.deal_weight.is_positive() || sector.verified_deal_weight.is_positive() {
+                with_data.push(*sector_number);
+            }
+            if sector.verified_deal_weight.is_positive() {
+                with_claims.push(*sector_number);
+            }
+        }
+        expect_update_power(rt, power_delta);
+        if !with_data.is_empty() {
+            rt.expect_send_simple(
+                STORAGE_MARKET_ACTOR_ADDR,
+                ON_MINER_SECTORS_TERMINATE_METHOD,
+                IpldBlock::serialize_cbor(&OnMinerSectorsTerminateParams {
+                    epoch: *rt.epoch.borrow(),
+                    sectors: bitfield_from_slice(&with_data),
+                })
+                .unwrap(),
+                TokenAmount::zero(),
+                None,
+                ExitCode::OK,
+            );
+        }
+        if !with_claims.is_empty() {
+            rt.expect_send_simple(
+                VERIFIED_REGISTRY_ACTOR_ADDR,
+                REMOVE_SECTOR_CLAIMS_METHOD,
+                IpldBlock::serialize_cbor(&RemoveSectorClaimsParams { sectors: with_claims })
+                    .unwrap(),
+                TokenAmount::zero(),
+                None,
+                ExitCode::OK,
+            );
+        }
+
+        // The penalty is repaid as it would be from the current state.
+        let mut st = self.get_state(rt);
+        st.apply_penalty(expected_fee).unwrap();
+        let (to_burn, unlocked) = st
+            .repay_partial_debt_in_priority_order(
+                &rt.store,
+                *rt.epoch.borrow(),
+                &rt.balance.borrow(),
+            )
+            .unwrap();
+        expect_burn(rt, to_burn);
+        expect_update_pledge(rt, &unlocked.neg());
+        for sector_number in sectors {
+            expect_event(rt, "sector-update-reverted", sector_number);
+        }
+
+        rt.call::<Actor>(
+            Method::RevertReplicaUpdate as u64,
+            IpldBlock::serialize_cbor(&RevertReplicaUpdateParams {
+                sectors: bitfield_from_slice(sectors),
+            })
+            .unwrap(),
+        )?;
+        rt.verify();
+        Ok(())
+    }
+
     pub fn advance_to_epoch_with_cron(&self, rt: &MockRuntime, epoch: ChainEpoch) {
         let mut deadline = self.get_deadline_info(rt);
         while deadline.last() < epoch {
@@ -1702,6 +2062,12 @@ impl ActorHarness {
         expect_update_power(rt, cfg.power_delta.unwrap_or_else(PowerPair::zero));
         expect_burn(rt, cfg.burnt_funds);
         expect_update_pledge(rt, &cfg.pledge_delta);
+        for (sector, deposit) in &cfg.expired_precommits {
+            expect_precommit_expired_event(rt, *sector, deposit);
+        }
+        for (sector, refresh_epoch) in &cfg.expired_proofs {
+            expect_proof_refresh_event(rt, "sector-proof-expired", *sector, *refresh_epoch);
+        }
 
         // Re-enrollment for next period.
         if !cfg.no_enrollment {
@@ -1830,6 +2196,75 @@ impl ActorHarness {
         )
     }
 
+    /// Checks a Window PoSt of non-faulty sectors, expecting the proof to be verified with the
+    /// given result.
+    pub fn check_window_post(
+        &self,
+        rt: &MockRuntime,
+        deadline: &DeadlineInfo,
+        infos: &[SectorOnChainInfo],
+        params: SubmitWindowedPoStParams,
+        verification_exit: ExitCode,
+    ) -> Result<(), ActorError> {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
+        rt.expect_validate_caller_any();
+        rt.expect_get_randomness_from_tickets(
+            DomainSeparationTag::PoStChainCommit,
+            params.chain_commit_epoch,
+            Vec::new(),
+            TEST_RANDOMNESS_ARRAY_FROM_ONE.into(),
+        );
+        let challenge_rand = TEST_RANDOMNESS_ARRAY_FROM_TWO;
+        rt.expect_get_randomness_from_beacon(
+            DomainSeparationTag::WindowedPoStChallengeSeed,
+            deadline.challenge,
+            RawBytes::serialize(self.receiver).unwrap().to_vec(),
+            challenge_rand,
+        );
+        let vi = self.make_window_post_verify_info(
+            infos,
+            &BitField::new(),
+            infos[0].clone(),
+            Randomness(challenge_rand.into()),
+            params.proofs.clone(),
+        );
+        rt.expect_verify_post(vi, verification_exit);
+
+        let ret = rt.call::<Actor>(
+            Method::CheckWindowedPoStExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        ret.map(|_| ())
+    }
+
+    pub fn get_disputable_proofs(&self, rt: &MockRuntime, dlidx: u64) -> GetDisputableProofsReturn {
+        rt.expect_validate_caller_any();
+        let ret = rt
+            .call::<Actor>(
+                Method::GetDisputableProofsExported as u64,
+                IpldBlock::serialize_cbor(&GetDisputableProofsParams { deadline: dlidx }).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret
+    }
+
+    pub fn get_partition_report(&self, rt: &MockRuntime) -> GetPartitionReportReturn {
+        rt.expect_validate_caller_any();
+        let ret = rt
+            .call::<Actor>(Method::GetPartitionReportExported as u64, None)
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        ret
+    }
+
     fn make_window_post_verify_info(
         &self,
         infos: &[SectorOnChainInfo],
@@ -1856,7 +2291,7 @@ impl ActorHarness {
             randomness: challenge_rand,
             proofs,
             challenged_sectors: proof_infos,
-            prover: RECEIVER_ID,
+            prover: self.receiver.id().unwrap(),
         }
     }
 
@@ -1867,6 +2302,63 @@ impl ActorHarness {
         proof_index: u64,
         infos: &[SectorOnChainInfo],
         expect_success: Option<PoStDisputeResult>,
+    ) {
+        self.expect_dispute_window_post(rt, deadline, proof_index, infos, &expect_success);
+        let params =
+            DisputeWindowedPoStParams { deadline: deadline.index, post_index: proof_index };
+        let result = rt.call::<Actor>(
+            Method::DisputeWindowedPoSt as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+
+        if expect_success.is_some() {
+            result.unwrap();
+        } else {
+            expect_abort_contains_message(
+                ExitCode::USR_ILLEGAL_ARGUMENT,
+                "failed to dispute valid post",
+                result,
+            );
+        }
+        rt.verify();
+    }
+
+    /// Disputes the proofs of some partitions of a deadline, expecting them to be
+    /// proven by the proof at some index.
+    pub fn dispute_window_post_partitions(
+        &self,
+        rt: &MockRuntime,
+        deadline: &DeadlineInfo,
+        partitions: &[u64],
+        proof_index: u64,
+        infos: &[SectorOnChainInfo],
+        expect_success: Option<PoStDisputeResult>,
+    ) -> BatchReturn {
+        self.expect_dispute_window_post(rt, deadline, proof_index, infos, &expect_success);
+        let params = DisputeWindowedPoStPartitionsParams {
+            deadline: deadline.index,
+            partitions: make_bitfield(partitions),
+        };
+        let result = rt
+            .call::<Actor>(
+                Method::DisputeWindowedPoStPartitions as u64,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        result
+    }
+
+    fn expect_dispute_window_post(
+        &self,
+        rt: &MockRuntime,
+        deadline: &DeadlineInfo,
+        proof_index: u64,
+        infos: &[SectorOnChainInfo],
+        expect_success: &Option<PoStDisputeResult>,
     ) {
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
         rt.expect_validate_caller_any();
@@ -1944,24 +2436,6 @@ impl ActorHarness {
                 expect_update_pledge(rt, &expected_pledge_delta);
             }
         }
-
-        let params =
-            DisputeWindowedPoStParams { deadline: deadline.index, post_index: proof_index };
-        let result = rt.call::<Actor>(
-            Method::DisputeWindowedPoSt as u64,
-            IpldBlock::serialize_cbor(&params).unwrap(),
-        );
-
-        if expect_success.is_some() {
-            result.unwrap();
-        } else {
-            expect_abort_contains_message(
-                ExitCode::USR_ILLEGAL_ARGUMENT,
-                "failed to dispute valid post",
-                result,
-            );
-        }
-        rt.verify();
     }
 
     fn get_submitted_proof(&self, rt: &MockRuntime, deadline: &Deadline, idx: u64) -> WindowedPoSt {
@@ -2377,6 +2851,7 @@ impl ActorHarness {
 
         // notify change to initial pledge
         let mut sectors_with_data = vec![];
+        let mut sectors_with_claims = vec![];
         for sector_info in &sector_infos {
             pledge_delta -= sector_info.initial_pledge.to_owned();
             if sector_info.deal_weight.is_positive()
@@ -2384,6 +2859,9 @@ impl ActorHarness {
             {
                 sectors_with_data.push(sector_info.sector_number);
             }
+            if sector_info.verified_deal_weight.is_positive() {
+                sectors_with_claims.push(sector_info.sector_number);
+            }
         }
 
         expect_update_pledge(rt, &pledge_delta);
@@ -2402,6 +2880,19 @@ impl ActorHarness {
                 ExitCode::OK,
             );
         }
+        if !sectors_with_claims.is_empty() {
+            rt.expect_send_simple(
+                VERIFIED_REGISTRY_ACTOR_ADDR,
+                REMOVE_SECTOR_CLAIMS_METHOD,
+                IpldBlock::serialize_cbor(&RemoveSectorClaimsParams {
+                    sectors: sectors_with_claims,
+                })
+                .unwrap(),
+                TokenAmount::zero(),
+                None,
+                ExitCode::OK,
+            );
+        }
 
         let sector_power = power_for_sectors(self.sector_size, &sector_infos);
         if has_active_sector {
@@ -2470,8 +2961,25 @@ impl ActorHarness {
         expected_repaid_from_vest: &TokenAmount,
         expected_repaid_from_balance: &TokenAmount,
     ) -> Result<(), ActorError> {
-        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
-        rt.expect_validate_caller_addr(self.caller_addrs());
+        self.repay_debts_from(
+            rt,
+            self.worker,
+            value,
+            expected_repaid_from_vest,
+            expected_repaid_from_balance,
+        )
+    }
+
+    pub fn repay_debts_from(
+        &self,
+        rt: &MockRuntime,
+        payer: Address,
+        value: &TokenAmount,
+        expected_repaid_from_vest: &TokenAmount,
+        expected_repaid_from_balance: &TokenAmount,
+    ) -> Result<(), ActorError> {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, payer);
+        rt.expect_validate_caller_any();
 
         rt.add_balance(value.clone());
         rt.set_received(value.clone());
@@ -2484,8 +2992,19 @@ impl ActorHarness {
         if total_repaid.is_positive() {
             expect_burn(rt, total_repaid.clone());
         }
+        // An actor with no role in the miner is refunded the value not applied to the debt.
+        let refund = value - &total_repaid;
+        let is_controlling =
+            self.control_addrs.iter().chain(&[self.worker, self.owner]).any(|a| *a == payer);
+        if !is_controlling && refund.is_positive() {
+            rt.expect_send_simple(payer, METHOD_SEND, None, refund, None, ExitCode::OK);
+        }
+        if total_repaid.is_positive() {
+            expect_fee_debt_repaid_event(rt, payer, &total_repaid);
+        }
         let result = rt.call::<Actor>(Method::RepayDebt as u64, None)?;
         expect_empty(result);
+        rt.verify();
         Ok(())
     }
 
@@ -2497,6 +3016,7 @@ impl ActorHarness {
         expected_withdrawn: &TokenAmount,
         expected_debt_repaid: &TokenAmount,
     ) -> Result<(), ActorError> {
+        let info = self.get_info(rt);
         rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, from_address);
         rt.expect_validate_caller_addr(vec![self.owner, self.beneficiary]);
 
@@ -2510,6 +3030,17 @@ impl ActorHarness {
                 None,
                 ExitCode::OK,
             );
+            if info.beneficiary != info.owner {
+                let mut term = info.beneficiary_term.clone();
+                term.used_quota += expected_withdrawn;
+                expect_beneficiary_withdrawal_event(
+                    rt,
+                    info.beneficiary,
+                    expected_withdrawn,
+                    &term.available(*rt.epoch.borrow()),
+                    term.expiration,
+                );
+            }
         }
 
         if expected_debt_repaid.is_positive() {
@@ -2772,6 +3303,92 @@ impl ActorHarness {
         Ok(ret)
     }
 
+    // Expects the claims recorded for the extended sectors to be fetched in a single batch
+    // and returned from `expected_claims`.
+    pub fn extend_sectors_by_claims(
+        &self,
+        rt: &MockRuntime,
+        params: ExtendSectorExpirationsByClaimsParams,
+        expected_claims: HashMap<ClaimID, FILPlusClaim>,
+    ) -> Result<Option<IpldBlock>, ActorError> {
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, self.worker);
+        rt.expect_validate_caller_addr(self.caller_addrs());
+
+        let state = self.get_state(rt);
+        let mut claim_ids = Vec::new();
+        let mut supplied_ids = Vec::new();
+        let mut qa_delta = BigInt::zero();
+        for extension in &params.extensions {
+            let mut recorded_ids = BTreeSet::new();
+            for sector_nr in extension.sectors.iter() {
+                let mut sector_claims = state.get_sector_claims(&rt.store, sector_nr).unwrap();
+                recorded_ids.extend(sector_claims.iter().copied());
+                claim_ids.extend(sector_claims.iter().copied());
+                if sector_claims.is_empty() {
+                    // The claims of sectors without recorded claims are declared.
+                    sector_claims = extension
+                        .maintain_claims
+                        .iter()
+                        .chain(&extension.drop_claims)
+                        .filter(|id| expected_claims[id].sector == sector_nr)
+                        .copied()
+                        .collect();
+                }
+                let dropped_space: u64 = sector_claims
+                    .iter()
+                    .filter(|id| extension.drop_claims.contains(id))
+                    .map(|id| expected_claims[id].size.0)
+                    .sum();
+
+                let sector = self.get_sector(rt, sector_nr);
+                let old_duration = sector.expiration - sector.power_base_epoch;
+                let old_verified_deal_space = &sector.verified_deal_weight / old_duration;
+                let mut new_sector = sector.clone();
+                new_sector.expiration = extension.new_expiration;
+                new_sector.power_base_epoch = *rt.epoch.borrow();
+                new_sector.verified_deal_weight = (old_verified_deal_space
+                    - BigInt::from(dropped_space))
+                    * (new_sector.expiration - new_sector.power_base_epoch);
+                qa_delta += qa_power_for_sector(self.sector_size, &new_sector)
+                    - qa_power_for_sector(self.sector_size, &sector);
+            }
+            supplied_ids.extend(extension.maintain_claims.iter().copied());
+            let unrecorded_drops: BTreeSet<_> =
+                extension.drop_claims.iter().filter(|id| !recorded_ids.contains(id)).collect();
+            supplied_ids.extend(unrecorded_drops);
+        }
+        claim_ids.extend(supplied_ids);
+
+        if !claim_ids.is_empty() {
+            let claims = claim_ids.iter().map(|id| expected_claims[id].clone()).collect();
+            rt.expect_send_simple(
+                VERIFIED_REGISTRY_ACTOR_ADDR,
+                fil_actor_miner::ext::verifreg::GET_CLAIMS_METHOD as u64,
+                IpldBlock::serialize_cbor(&GetClaimsParams {
+                    provider: self.receiver.id().unwrap(),
+                    claim_ids: claim_ids.clone(),
+                })
+                .unwrap(),
+                TokenAmount::zero(),
+                IpldBlock::serialize_cbor(&GetClaimsReturn {
+                    batch_info: BatchReturn::ok(claim_ids.len() as u32),
+                    claims,
+                })
+                .unwrap(),
+                ExitCode::OK,
+            );
+        }
+        expect_update_power(rt, PowerPair::new(BigInt::zero(), qa_delta));
+
+        let ret = rt.call::<Actor>(
+            Method::ExtendSectorExpirationsByClaimsExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )?;
+
+        rt.verify();
+        Ok(ret)
+    }
+
     pub fn compact_partitions(
         &self,
         rt: &MockRuntime,
@@ -2835,6 +3452,122 @@ impl ActorHarness {
         rt.verify();
         Ok(available_balance_ret.available_balance)
     }
+
+    pub fn query_locked_funds(&self, rt: &MockRuntime) -> Result<TokenAmount, ActorError> {
+        // set caller to non-builtin
+        rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+        rt.expect_validate_caller_any();
+        let locked_funds_ret: GetLockedFundsReturn = rt
+            .call::<Actor>(Method::GetLockedFundsExported as u64, None)?
+            .unwrap()
+            .deserialize()?;
+        rt.verify();
+        Ok(locked_funds_ret.locked_funds)
+    }
+
+    pub fn retry_notifications(
+        &self,
+        rt: &MockRuntime,
+        max_sectors: u64,
+        expected_notifications: &[SectorChanges],
+        notification_result: ExitCode,
+    ) -> Result<RetryNotificationsReturn, ActorError> {
+        // set caller to non-builtin
+        rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+        rt.expect_validate_caller_any();
+        if !expected_notifications.is_empty() {
+            let sector_notification_resps = expected_notifications
+                .iter()
+                .map(|sn| SectorReturn {
+                    added: vec![PieceReturn { accepted: true }; sn.added.len()],
+                })
+                .collect();
+            rt.expect_send_simple(
+                STORAGE_MARKET_ACTOR_ADDR,
+                SECTOR_CONTENT_CHANGED,
+                IpldBlock::serialize_cbor(&SectorContentChangedParams {
+                    sectors: expected_notifications.to_vec(),
+                })
+                .unwrap(),
+                TokenAmount::zero(),
+                IpldBlock::serialize_cbor(&SectorContentChangedReturn {
+                    sectors: sector_notification_resps,
+                })
+                .unwrap(),
+                notification_result,
+            );
+        }
+        let ret: RetryNotificationsReturn = rt
+            .call::<Actor>(
+                Method::RetryNotificationsExported as u64,
+                IpldBlock::serialize_cbor(&RetryNotificationsParams { max_sectors }).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()?;
+        rt.verify();
+        Ok(ret)
+    }
+}
+
+pub fn expect_fee_debt_repaid_event(rt: &MockRuntime, payer: Address, repaid: &TokenAmount) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("fee-debt-repaid")
+            .field_indexed("payer", &payer.id().unwrap())
+            .field("repaid", repaid)
+            .build()
+            .unwrap(),
+    );
+}
+
+pub fn expect_beneficiary_withdrawal_event(
+    rt: &MockRuntime,
+    beneficiary: Address,
+    amount: &TokenAmount,
+    remaining_quota: &TokenAmount,
+    expiration: ChainEpoch,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("beneficiary-withdrawal")
+            .field_indexed("beneficiary", &beneficiary.id().unwrap())
+            .field("amount", amount)
+            .field("remaining-quota", remaining_quota)
+            .field("expiration", &expiration)
+            .build()
+            .unwrap(),
+    );
+}
+
+pub fn expect_precommit_expired_event(
+    rt: &MockRuntime,
+    sector: SectorNumber,
+    deposit_burned: &TokenAmount,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("precommit-expired")
+            .field_indexed("sector", &sector)
+            .field("deposit-burned", deposit_burned)
+            .build()
+            .unwrap(),
+    );
+}
+
+pub fn expect_proof_refresh_event(
+    rt: &MockRuntime,
+    typ: &str,
+    sector: SectorNumber,
+    refresh_epoch: ChainEpoch,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ(typ)
+            .field_indexed("sector", &sector)
+            .field("refresh-epoch", &refresh_epoch)
+            .build()
+            .unwrap(),
+    );
 }
 
 pub fn expect_sector_event(
@@ -3002,6 +3735,8 @@ pub struct CronConfig {
     pub power_delta: Option<PowerPair>,
     pub pledge_delta: TokenAmount, // Expected change in miner's pledge
     pub burnt_funds: TokenAmount, // Expected burnt funds, through penalties, fee debt repayments and daily fees
+    pub expired_precommits: Vec<(SectorNumber, TokenAmount)>, // Expected pre-commits cleaned up, with deposit burnt
+    pub expired_proofs: Vec<(SectorNumber, ChainEpoch)>, // Expected sectors whose proofs expired, with refresh epoch
 }
 
 #[allow(dead_code)]
@@ -3013,6 +3748,8 @@ impl CronConfig {
             power_delta: None,
             pledge_delta: TokenAmount::zero(),
             burnt_funds: TokenAmount::zero(),
+            expired_precommits: vec![],
+            expired_proofs: vec![],
         }
     }
 }
@@ -3862,6 +4599,7 @@ impl CronControl {
             rt,
             CronConfig {
                 no_enrollment: true,
+                expired_precommits: vec![(self.pre_commit_num - 1, st.pre_commit_deposits.clone())],
                 burnt_funds: st.pre_commit_deposits,
                 ..CronConfig::empty()
             },
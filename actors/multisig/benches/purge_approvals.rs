@@ -0,0 +1,115 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Compares the cost of purging a removed signer's approvals using the index of approvals by
+//! signer with that of scanning every pending transaction, as `purge_approvals` did before the
+//! index existed.
+//!
+//! Run with `cargo bench -p fil_actor_multisig`. Each row is a wallet with the given number of
+//! pending transactions, one of which was approved by the removed signer.
+
+use std::time::{Duration, Instant};
+
+use fil_actor_multisig::{
+    PENDING_TXN_CONFIG, PendingTxnMap, SIGNER_APPROVALS_CONFIG, SignerApprovalsMap, State,
+    Transaction, TxnID,
+};
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::METHOD_SEND;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+
+const PENDING_COUNTS: &[i64] = &[10, 100, 1_000, 10_000];
+const ITERATIONS: u32 = 10;
+
+fn main() {
+    println!(
+        "{:>8} {:>14} {:>14} {:>14} {:>14}",
+        "pending", "indexed reads", "scan reads", "indexed time", "scan time"
+    );
+    for &pending in PENDING_COUNTS {
+        let (store, st, removed) = setup(pending);
+        let (indexed_reads, indexed_time) = measure(&store, || {
+            let mut st = st.clone();
+            st.purge_approvals(&store, &removed).unwrap();
+        });
+        let (scan_reads, scan_time) = measure(&store, || {
+            let mut st = st.clone();
+            purge_by_scan(&store, &mut st, &removed);
+        });
+        println!(
+            "{:>8} {:>14} {:>14} {:>14?} {:>14?}",
+            pending, indexed_reads, scan_reads, indexed_time, scan_time
+        );
+    }
+}
+
+// Builds a wallet with `pending` transactions, the last of which was proposed by the signer to
+// be removed and the rest by another signer.
+fn setup(pending: i64) -> (MemoryBlockstore, State, Address) {
+    let store = MemoryBlockstore::new();
+    let removed = Address::new_id(101);
+    let other = Address::new_id(102);
+    let mut txns = PendingTxnMap::empty(&store, PENDING_TXN_CONFIG, "pending txns");
+    let mut approvals =
+        SignerApprovalsMap::empty(&store, SIGNER_APPROVALS_CONFIG, "signer approvals");
+    for id in 0..pending {
+        let proposer = if id == pending - 1 { removed } else { other };
+        let txn = Transaction {
+            to: Address::new_id(103),
+            value: TokenAmount::from_atto(id),
+            method: METHOD_SEND,
+            params: RawBytes::default(),
+            approved: vec![proposer],
+        };
+        txns.set(&TxnID(id), txn).unwrap();
+        approvals.put(&proposer, TxnID(id)).unwrap();
+    }
+    let st = State {
+        signers: vec![removed, other],
+        num_approvals_threshold: 2,
+        next_tx_id: TxnID(pending),
+        initial_balance: TokenAmount::default(),
+        start_epoch: 0,
+        unlock_duration: 0,
+        pending_txs: txns.flush().unwrap(),
+        signer_approvals: approvals.flush().unwrap(),
+    };
+    (store, st, removed)
+}
+
+// Returns the blockstore reads of one run of `f`, and its mean time over ITERATIONS runs.
+fn measure(store: &MemoryBlockstore, mut f: impl FnMut()) -> (usize, Duration) {
+    let before = store.snapshot_stats();
+    f();
+    let reads = store.snapshot_stats().since(&before).r;
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    (reads, start.elapsed() / ITERATIONS)
+}
+
+// Removes a signer's approvals by visiting every pending transaction.
+fn purge_by_scan(store: &MemoryBlockstore, st: &mut State, addr: &Address) {
+    let mut txns =
+        PendingTxnMap::load(store, &st.pending_txs, PENDING_TXN_CONFIG, "pending txns").unwrap();
+    let mut to_update = Vec::new();
+    txns.for_each(|id, txn| {
+        if txn.approved.contains(addr) {
+            to_update.push((id, txn.clone()));
+        }
+        Ok(())
+    })
+    .unwrap();
+    for (id, mut txn) in to_update {
+        txn.approved.retain(|approver| approver != addr);
+        if txn.approved.is_empty() {
+            txns.delete(&id).unwrap();
+        } else {
+            txns.set(&id, txn).unwrap();
+        }
+    }
+    st.pending_txs = txns.flush().unwrap();
+}
@@ -9,7 +9,7 @@ use fvm_ipld_encoding::RawBytes;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::METHOD_CONSTRUCTOR;
 use fvm_shared::MethodNum;
-use fvm_shared::address::Address;
+use fvm_shared::address::{Address, Protocol};
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use num_derive::FromPrimitive;
@@ -48,6 +48,12 @@ pub enum Method {
     LockBalance = 9,
     // Method numbers derived from FRC-0042 standards
     UniversalReceiverHook = frc42_dispatch::method_hash!("Receive"),
+    GetTxnExported = frc42_dispatch::method_hash!("GetTxn"),
+    SimulateProposalExported = frc42_dispatch::method_hash!("SimulateProposal"),
+    ProposeExported = frc42_dispatch::method_hash!("Propose"),
+    ApproveExported = frc42_dispatch::method_hash!("Approve"),
+    CancelExported = frc42_dispatch::method_hash!("Cancel"),
+    ApproveByHashExported = frc42_dispatch::method_hash!("ApproveByHash"),
 }
 
 /// Multisig Actor
@@ -98,11 +104,14 @@ impl Actor {
         }
 
         let empty_root = PendingTxnMap::empty(rt.store(), PENDING_TXN_CONFIG, "empty").flush()?;
+        let empty_approvals_root =
+            SignerApprovalsMap::empty(rt.store(), SIGNER_APPROVALS_CONFIG, "empty").flush()?;
 
         let mut st: State = State {
             signers: resolved_signers,
             num_approvals_threshold: params.num_approvals_threshold,
             pending_txs: empty_root,
+            signer_approvals: empty_approvals_root,
             initial_balance: TokenAmount::zero(),
             next_tx_id: Default::default(),
             start_epoch: Default::default(),
@@ -200,6 +209,60 @@ impl Actor {
         }
     }
 
+    /// Approves the pending transaction with a proposal hash, as for Approve.
+    /// The hash identifies the transaction independently of its ID, which may change under
+    /// chain re-orgs, so that a signer that is itself a multisig (or other contract) can
+    /// propose its approval before the ID of the transaction to approve is final.
+    /// Only transactions with IDs near the expected ID are searched, which bounds the cost
+    /// while tolerating an ID shifted by a re-org. If several of them have the hash,
+    /// the earliest proposed is approved.
+    pub fn approve_by_hash(
+        rt: &impl Runtime,
+        params: ApproveByHashParams,
+    ) -> Result<ApproveByHashReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let approver: Address = rt.message().caller();
+
+        if params.proposal_hash.is_empty() {
+            return Err(actor_error!(illegal_argument, "proposal hash must not be empty"));
+        }
+
+        let st: State = rt.state()?;
+        if !st.is_signer(&approver) {
+            return Err(actor_error!(forbidden; "{} is not a signer", approver));
+        }
+        let ptx =
+            PendingTxnMap::load(rt.store(), &st.pending_txs, PENDING_TXN_CONFIG, "pending txns")?;
+        let first = params.txn_id.0.saturating_sub(APPROVE_BY_HASH_SEARCH_RADIUS).max(0);
+        let last =
+            params.txn_id.0.saturating_add(APPROVE_BY_HASH_SEARCH_RADIUS).min(st.next_tx_id.0 - 1);
+        let mut found: Option<(TxnID, Transaction)> = None;
+        for id in (first..=last).map(TxnID) {
+            let Some(txn) = ptx.get(&id)? else {
+                continue;
+            };
+            let hash = compute_proposal_hash(txn, rt)
+                .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("failed to compute proposal hash for (tx: {:?})", id)
+                })?;
+            if params.proposal_hash == hash {
+                found = Some((id, txn.clone()));
+                break;
+            }
+        }
+        let (txn_id, txn) = found.ok_or_else(|| {
+            actor_error!(not_found, "no pending transaction with the proposal hash for approval")
+        })?;
+
+        let (applied, ret, code) = execute_transaction_if_approved(rt, &st, txn_id, &txn)?;
+        let (applied, ret, code) = if applied {
+            (applied, ret, code)
+        } else {
+            Self::approve_transaction(rt, txn_id, txn)?
+        };
+        Ok(ApproveByHashReturn { txn_id, applied, code, ret })
+    }
+
     /// Multisig actor cancel function
     pub fn cancel(rt: &impl Runtime, params: TxnIDParams) -> Result<(), ActorError> {
         rt.validate_immediate_caller_accept_any()?;
@@ -236,6 +299,7 @@ impl Actor {
             }
 
             st.pending_txs = ptx.flush()?;
+            st.remove_signer_approvals(rt.store(), params.id, &tx.approved)?;
             Ok(())
         })
     }
@@ -390,6 +454,72 @@ impl Actor {
         Ok(())
     }
 
+    /// Returns a pending transaction along with its proposal hash and approval status.
+    pub fn get_txn(rt: &impl Runtime, params: GetTxnParams) -> Result<GetTxnReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        let ptx =
+            PendingTxnMap::load(rt.store(), &st.pending_txs, PENDING_TXN_CONFIG, "pending txns")?;
+        let txn = ptx
+            .get(&params.id)?
+            .ok_or_else(|| actor_error!(not_found, "no such transaction {:?}", params.id))?;
+        let proposal_hash = compute_proposal_hash(txn, rt)
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("failed to compute proposal hash for (tx: {:?})", params.id)
+            })?;
+
+        Ok(GetTxnReturn {
+            to: txn.to,
+            value: txn.value.clone(),
+            method: txn.method,
+            params: txn.params.clone(),
+            approved: txn.approved.clone(),
+            proposal_hash: proposal_hash.to_vec(),
+            remaining_approvals: st
+                .num_approvals_threshold
+                .saturating_sub(txn.approved.len() as u64),
+        })
+    }
+
+    /// Checks that a transaction proposed by the caller could be executed now, without recording
+    /// a proposal, so that signers may be warned of a proposal that would fail on execution.
+    /// Fails with the error that proposal or execution would abort with, for lack of funds
+    /// (including locked funds) or of an actor at the recipient address.
+    /// The recipient's handling of the message is not simulated.
+    pub fn simulate_proposal(
+        rt: &impl Runtime,
+        params: SimulateProposalParams,
+    ) -> Result<SimulateProposalReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let proposer: Address = rt.message().caller();
+
+        if params.value.is_negative() {
+            return Err(actor_error!(
+                illegal_argument,
+                "proposed value must be non-negative, was {}",
+                params.value
+            ));
+        }
+
+        let st: State = rt.state()?;
+        if !st.is_signer(&proposer) {
+            return Err(actor_error!(forbidden, "{} is not a signer", proposer));
+        }
+        st.check_available(rt.current_balance(), &params.value, rt.curr_epoch())?;
+
+        // A send to an unknown key address creates an account actor,
+        // but there is no way to create an actor at an unknown ID or actor address.
+        let recipient_exists =
+            rt.resolve_address(&params.to).and_then(|id| rt.get_actor_code_cid(&id)).is_some();
+        if !recipient_exists && matches!(params.to.protocol(), Protocol::ID | Protocol::Actor) {
+            return Err(actor_error!(not_found, "no actor at recipient address {}", params.to));
+        }
+
+        Ok(SimulateProposalReturn {
+            remaining_approvals: st.num_approvals_threshold.saturating_sub(1),
+        })
+    }
+
     fn approve_transaction(
         rt: &impl Runtime,
         tx_id: TxnID,
@@ -418,6 +548,7 @@ impl Actor {
 
             ptx.set(&tx_id, txn.clone())?;
             st.pending_txs = ptx.flush()?;
+            st.add_signer_approval(rt.store(), &rt.message().caller(), tx_id)?;
 
             // Go implementation holds reference to state after transaction so this must be cloned
             // to match to handle possible exit code inconsistency
@@ -472,6 +603,7 @@ fn execute_transaction_if_approved(
             )?;
             ptx.delete(&txn_id)?;
             st.pending_txs = ptx.flush()?;
+            st.remove_signer_approvals(rt.store(), txn_id, &txn.approved)?;
             Ok(())
         })?;
 
@@ -553,15 +685,18 @@ impl ActorCode for Actor {
 
     actor_dispatch! {
       Constructor => constructor,
-      Propose => propose,
-      Approve => approve,
-      Cancel => cancel,
+      Propose|ProposeExported => propose,
+      Approve|ApproveExported => approve,
+      Cancel|CancelExported => cancel,
       AddSigner => add_signer,
       RemoveSigner => remove_signer,
       SwapSigner => swap_signer,
       ChangeNumApprovalsThreshold => change_num_approvals_threshold,
       LockBalance => lock_balance,
       UniversalReceiverHook => universal_receiver_hook,
+      GetTxnExported => get_txn,
+      SimulateProposalExported => simulate_proposal,
+      ApproveByHashExported => approve_by_hash,
       _ => fallback,
     }
 }
@@ -9,10 +9,11 @@ use fvm_shared::bigint::BigInt;
 use fvm_shared::bigint::Integer;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
-use indexmap::IndexMap;
 use num_traits::Zero;
 
-use fil_actors_runtime::{ActorError, Config, DEFAULT_HAMT_CONFIG, Map2, actor_error};
+use fil_actors_runtime::{
+    ActorError, Config, DEFAULT_HAMT_CONFIG, Map2, SetMultimap, SetMultimapConfig, actor_error,
+};
 
 use super::TxnID;
 use super::types::Transaction;
@@ -20,6 +21,11 @@ use super::types::Transaction;
 pub type PendingTxnMap<BS> = Map2<BS, TxnID, Transaction>;
 pub const PENDING_TXN_CONFIG: Config = DEFAULT_HAMT_CONFIG;
 
+/// Index from each signer to the IDs of the pending transactions it has approved.
+pub type SignerApprovalsMap<BS> = SetMultimap<BS, Address, TxnID>;
+pub const SIGNER_APPROVALS_CONFIG: SetMultimapConfig =
+    SetMultimapConfig { outer: DEFAULT_HAMT_CONFIG, inner: DEFAULT_HAMT_CONFIG };
+
 /// Multisig actor state
 #[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug)]
 pub struct State {
@@ -33,6 +39,8 @@ pub struct State {
     pub unlock_duration: ChainEpoch,
 
     pub pending_txs: Cid,
+    /// SetMultimap<Address, TxnID> of the pending transactions approved by each signer.
+    pub signer_approvals: Cid,
 }
 
 impl State {
@@ -71,8 +79,68 @@ impl State {
         TokenAmount::from_atto(numerator.atto().div_ceil(&denominator))
     }
 
-    /// Iterates all pending transactions and removes an address from each list of approvals,
-    /// if present.  If an approval list becomes empty, the pending transaction is deleted.
+    /// Records that `signer` has approved the pending transaction `txn_id`.
+    pub fn add_signer_approval<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        signer: &Address,
+        txn_id: TxnID,
+    ) -> Result<(), ActorError> {
+        let mut approvals = SignerApprovalsMap::load(
+            store,
+            &self.signer_approvals,
+            SIGNER_APPROVALS_CONFIG,
+            "signer approvals",
+        )?;
+        approvals.put(signer, txn_id)?;
+        self.signer_approvals = approvals.flush()?;
+        Ok(())
+    }
+
+    /// Rebuilds the index of approvals by signer from the pending transactions, replacing any
+    /// existing index. This migrates state written before the index was maintained.
+    pub fn rebuild_signer_approvals<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+    ) -> Result<(), ActorError> {
+        let txns =
+            PendingTxnMap::load(store, &self.pending_txs, PENDING_TXN_CONFIG, "pending txns")?;
+        let mut approvals =
+            SignerApprovalsMap::empty(store, SIGNER_APPROVALS_CONFIG, "signer approvals");
+        txns.for_each(|txn_id, txn| {
+            for approver in &txn.approved {
+                approvals.put(approver, txn_id)?;
+            }
+            Ok(())
+        })?;
+        self.signer_approvals = approvals.flush()?;
+        Ok(())
+    }
+
+    /// Removes the index entries for a pending transaction that has been cancelled or executed.
+    pub fn remove_signer_approvals<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        txn_id: TxnID,
+        approvers: &[Address],
+    ) -> Result<(), ActorError> {
+        let mut approvals = SignerApprovalsMap::load(
+            store,
+            &self.signer_approvals,
+            SIGNER_APPROVALS_CONFIG,
+            "signer approvals",
+        )?;
+        for approver in approvers {
+            approvals.remove(approver, txn_id)?;
+        }
+        self.signer_approvals = approvals.flush()?;
+        Ok(())
+    }
+
+    /// Removes an address from the list of approvals of each pending transaction it approved.
+    /// If an approval list becomes empty, the pending transaction is deleted.
+    /// Only the transactions indexed under `addr` are visited, so the cost is proportional
+    /// to the number of approvals by `addr` rather than the number of pending transactions.
     pub fn purge_approvals<BS: Blockstore>(
         &mut self,
         store: &BS,
@@ -80,20 +148,23 @@ impl State {
     ) -> Result<(), ActorError> {
         let mut txns =
             PendingTxnMap::load(store, &self.pending_txs, PENDING_TXN_CONFIG, "pending txns")?;
-
-        // Identify transactions that need updating
-        let mut txn_ids_to_purge = IndexMap::new();
-        txns.for_each(|tx_id, txn: &Transaction| {
-            for approver in txn.approved.iter() {
-                if approver == addr {
-                    txn_ids_to_purge.insert(tx_id, txn.clone());
-                }
-            }
+        let mut approvals = SignerApprovalsMap::load(
+            store,
+            &self.signer_approvals,
+            SIGNER_APPROVALS_CONFIG,
+            "signer approvals",
+        )?;
+
+        let mut txn_ids_to_purge = Vec::new();
+        approvals.for_each_in(addr, |tx_id| {
+            txn_ids_to_purge.push(tx_id);
             Ok(())
         })?;
 
-        // Update or remove those transactions.
-        for (tx_id, mut txn) in txn_ids_to_purge {
+        for tx_id in txn_ids_to_purge {
+            let mut txn = txns.get(&tx_id)?.cloned().ok_or_else(|| {
+                actor_error!(illegal_state, "indexed approval for missing transaction {}", tx_id)
+            })?;
             txn.approved.retain(|approver| approver != addr);
 
             if !txn.approved.is_empty() {
@@ -102,8 +173,10 @@ impl State {
                 txns.delete(&tx_id)?;
             }
         }
+        approvals.remove_all(addr)?;
 
         self.pending_txs = txns.flush()?;
+        self.signer_approvals = approvals.flush()?;
         Ok(())
     }
 
@@ -5,7 +5,10 @@ use fvm_shared::address::Address;
 
 use fil_actors_runtime::MessageAccumulator;
 
-use crate::{PENDING_TXN_CONFIG, PendingTxnMap, SIGNERS_MAX, State, TxnID};
+use crate::{
+    PENDING_TXN_CONFIG, PendingTxnMap, SIGNER_APPROVALS_CONFIG, SIGNERS_MAX, SignerApprovalsMap,
+    State, TxnID,
+};
 
 pub struct StateSummary {
     pub pending_tx_count: u64,
@@ -52,6 +55,7 @@ pub fn check_state_invariants<BS: Blockstore>(
     // test pending transactions
     let mut max_tx_id = TxnID(-1);
     let mut pending_tx_count = 0u64;
+    let mut expected_approvals = HashSet::<(Address, TxnID)>::new();
 
     match PendingTxnMap::load(store, &state.pending_txs, PENDING_TXN_CONFIG, "pending txns") {
         Ok(transactions) => {
@@ -74,6 +78,7 @@ pub fn check_state_invariants<BS: Blockstore>(
                         format!("duplicate approval {approval} for transaction {tx_id}"),
                     );
                     seen_approvals.insert(approval);
+                    expected_approvals.insert((*approval, tx_id));
                 });
                 acc.require((seen_approvals.len() as u64) < state.num_approvals_threshold,
                     format!("number of approvals ({}) meets the approvals threshold ({}), transaction should not be pending",
@@ -89,6 +94,33 @@ pub fn check_state_invariants<BS: Blockstore>(
         Err(e) => acc.add(format!("error loading transactions: {e}")),
     };
 
+    // test the signer approvals index matches the pending transaction approvals
+    match SignerApprovalsMap::load(
+        store,
+        &state.signer_approvals,
+        SIGNER_APPROVALS_CONFIG,
+        "signer approvals",
+    ) {
+        Ok(approvals) => {
+            let mut indexed_approvals = HashSet::<(Address, TxnID)>::new();
+            let ret = approvals.for_each(|signer, _| {
+                approvals.for_each_in(&signer, |tx_id| {
+                    acc.require(
+                        expected_approvals.contains(&(signer, tx_id)),
+                        format!("indexed approval by {signer} for transaction {tx_id} not found"),
+                    );
+                    indexed_approvals.insert((signer, tx_id));
+                    Ok(())
+                })
+            });
+            acc.require_no_error(ret, "error iterating signer approvals");
+            for (signer, tx_id) in expected_approvals.difference(&indexed_approvals) {
+                acc.add(format!("approval by {signer} for transaction {tx_id} is not indexed"));
+            }
+        }
+        Err(e) => acc.add(format!("error loading signer approvals: {e}")),
+    };
+
     acc.require(
         state.next_tx_id > max_tx_id,
         format!("next transaction id {} is not greater than pending ids", state.next_tx_id),
@@ -18,6 +18,10 @@ use fil_actors_runtime::MapKey;
 /// are required, please use a combining tree of multisigs.
 pub const SIGNERS_MAX: usize = 256;
 
+/// The number of transaction IDs either side of the expected ID that are searched for a
+/// transaction to approve by its proposal hash.
+pub const APPROVE_BY_HASH_SEARCH_RADIUS: i64 = 16;
+
 /// Transaction ID type
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Hash, Eq, PartialEq, PartialOrd)]
 #[serde(transparent)]
@@ -122,6 +126,73 @@ pub struct ApproveReturn {
     pub ret: RawBytes,
 }
 
+/// Parameters for approving a pending transaction identified by its proposal hash.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct ApproveByHashParams {
+    #[serde(with = "strict_bytes")]
+    pub proposal_hash: Vec<u8>,
+    /// The ID the transaction is expected to have. Only transactions with IDs within
+    /// APPROVE_BY_HASH_SEARCH_RADIUS of it are searched.
+    pub txn_id: TxnID,
+}
+
+/// Approve by hash method call return.
+#[derive(Serialize_tuple, Deserialize_tuple)]
+pub struct ApproveByHashReturn {
+    /// TxnID is the ID of the approved transaction.
+    pub txn_id: TxnID,
+    /// Applied indicates if the transaction was applied as opposed to approved but not applied
+    /// due to lack of approvals.
+    pub applied: bool,
+    /// Code is the exitcode of the transaction, if Applied is false this field should be ignored.
+    pub code: ExitCode,
+    /// Ret is the return value of the transaction, if Applied is false this field should
+    /// be ignored.
+    pub ret: RawBytes,
+}
+
+/// Parameters for querying a pending transaction.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct GetTxnParams {
+    pub id: TxnID,
+}
+
+/// A pending transaction and its approval status.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct GetTxnReturn {
+    pub to: Address,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: RawBytes,
+    /// Signers that have approved the transaction, starting with the proposer.
+    pub approved: Vec<Address>,
+    /// Hash of the proposal, to be passed to Approve or Cancel.
+    #[serde(with = "strict_bytes")]
+    pub proposal_hash: Vec<u8>,
+    /// Number of further approvals needed to meet the current threshold.
+    /// This is zero if the threshold was lowered after the last approval, in which case
+    /// the transaction is executed upon the next approval by a signer.
+    pub remaining_approvals: u64,
+}
+
+/// Parameters for simulating a proposal, as for Propose.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct SimulateProposalParams {
+    pub to: Address,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: RawBytes,
+}
+
+/// The outcome of a simulated proposal which could be executed.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct SimulateProposalReturn {
+    /// Number of approvals needed after the proposer's own for the transaction to be executed.
+    /// If zero, the transaction would be executed immediately upon proposal.
+    pub remaining_approvals: u64,
+}
+
 /// Add signer params.
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct AddSignerParams {
@@ -1,7 +1,8 @@
 use fil_actor_multisig::testing::check_state_invariants;
 use fil_actor_multisig::{
-    Actor as MultisigActor, ConstructorParams, Method, ProposeReturn, SIGNERS_MAX, State,
-    Transaction, TxnID, TxnIDParams, compute_proposal_hash,
+    APPROVE_BY_HASH_SEARCH_RADIUS, Actor as MultisigActor, ConstructorParams, Method,
+    PENDING_TXN_CONFIG, PendingTxnMap, ProposeReturn, SIGNER_APPROVALS_CONFIG, SIGNERS_MAX,
+    SignerApprovalsMap, State, Transaction, TxnID, TxnIDParams, compute_proposal_hash,
 };
 use fil_actors_runtime::FIRST_EXPORTED_METHOD_NUMBER;
 use fil_actors_runtime::cbor::serialize;
@@ -1273,6 +1274,80 @@ fn remove_signer_deletes_solo_proposals() {
     check_state(&rt);
 }
 
+// Counts the blockstore reads made while removing a signer that approved a single transaction,
+// with `unrelated` other transactions pending that were proposed by another signer.
+fn remove_signer_reads(unrelated: i64) -> usize {
+    let msig = Address::new_id(100);
+    let anne = Address::new_id(101);
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob, chuck]);
+
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+    for _ in 0..unrelated {
+        h.propose_ok(&rt, chuck, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
+    }
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    h.propose_ok(&rt, chuck, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
+
+    rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+    let before = rt.store.snapshot_stats();
+    h.remove_signer(&rt, anne, false).unwrap();
+    let reads = rt.store.snapshot_stats().since(&before).r;
+
+    let st: State = rt.get_state();
+    let ptx =
+        PendingTxnMap::load(&rt.store, &st.pending_txs, PENDING_TXN_CONFIG, "pending").unwrap();
+    let mut remaining = 0;
+    ptx.for_each(|_, _| {
+        remaining += 1;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(unrelated, remaining);
+    check_state(&rt);
+    reads
+}
+
+#[test]
+fn remove_signer_reads_scale_with_signer_approvals() {
+    let few = remove_signer_reads(10);
+    let many = remove_signer_reads(1000);
+    // Only the removed signer's transactions are visited, so the thousand unrelated
+    // transactions cost no more than the extra HAMT levels needed to hold them.
+    assert!(many <= few + 4, "removing signer read {many} blocks, expected at most {}", few + 4);
+}
+
+#[test]
+fn rebuild_signer_approvals() {
+    let msig = Address::new_id(100);
+    let anne = Address::new_id(101);
+    let bob = Address::new_id(102);
+    let chuck = Address::new_id(103);
+
+    let rt = construct_runtime(msig);
+    let h = util::ActorHarness::new();
+    h.construct_and_verify(&rt, 3, 0, 0, vec![anne, bob, chuck]);
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+    h.propose_ok(&rt, chuck, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
+    h.propose_ok(&rt, chuck, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+    h.propose_ok(&rt, chuck, TokenAmount::zero(), METHOD_SEND, RawBytes::default());
+    let indexed: State = rt.get_state();
+
+    // Rebuild the index from the pending transactions, as when migrating state without it.
+    let mut st = indexed.clone();
+    st.signer_approvals =
+        SignerApprovalsMap::empty(&rt.store, SIGNER_APPROVALS_CONFIG, "empty").flush().unwrap();
+    st.rebuild_signer_approvals(&rt.store).unwrap();
+    assert_eq!(indexed.signer_approvals, st.signer_approvals);
+    rt.replace_state(&st);
+    check_state(&rt);
+}
+
 // Approve
 mod approval_tests {
     use super::*;
@@ -1325,6 +1400,203 @@ mod approval_tests {
         check_state(&rt);
     }
 
+    #[test]
+    fn nested_multisig_signer_approves_by_proposal_hash() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let chuck = Address::new_id(103);
+        let inner_msig = Address::new_id(110);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 2, 0, 0, vec![anne, inner_msig]);
+
+        let fake_params = RawBytes::from(vec![1, 2, 3, 4]);
+        let fake_method = 42;
+        let send_value = TokenAmount::from_atto(10u8);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        h.propose_ok(&rt, chuck, send_value.clone(), fake_method, fake_params.clone());
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        let proposal_hash =
+            h.propose_ok(&rt, chuck, send_value.clone(), fake_method, fake_params.clone());
+
+        // The hash identifies the transaction, so the inner multisig need not know its ID.
+        // Identical proposals have the same hash, and the earliest is approved.
+        rt.set_balance(send_value.clone());
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, inner_msig);
+        rt.expect_send_simple(
+            chuck,
+            fake_method,
+            to_ipld_block(fake_params.clone()),
+            send_value.clone(),
+            None,
+            ExitCode::OK,
+        );
+        let ret = h.approve_by_hash(&rt, TxnID(1), proposal_hash).unwrap();
+        assert_eq!(TxnID(0), ret.txn_id);
+        assert!(ret.applied);
+        assert_eq!(ExitCode::OK, ret.code);
+        h.assert_transactions(
+            &rt,
+            vec![(
+                TxnID(1),
+                Transaction {
+                    to: chuck,
+                    value: send_value,
+                    method: fake_method,
+                    params: fake_params,
+                    approved: vec![anne],
+                },
+            )],
+        );
+
+        // The inner multisig may not approve with a hash matching no transaction,
+        // and an empty hash is not accepted.
+        expect_abort(ExitCode::USR_NOT_FOUND, h.approve_by_hash(&rt, TxnID(1), [0u8; 32]));
+        rt.reset();
+
+        // Transactions far from the expected ID are not searched.
+        expect_abort(
+            ExitCode::USR_NOT_FOUND,
+            h.approve_by_hash(&rt, TxnID(2 + APPROVE_BY_HASH_SEARCH_RADIUS), proposal_hash),
+        );
+        rt.reset();
+        rt.expect_validate_caller_any();
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            rt.call::<MultisigActor>(
+                Method::ApproveByHashExported as u64,
+                IpldBlock::serialize_cbor(&fil_actor_multisig::ApproveByHashParams {
+                    proposal_hash: vec![],
+                    txn_id: TxnID(1),
+                })
+                .unwrap(),
+            ),
+        );
+        rt.reset();
+
+        // Nor may a non-signer approve.
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, Address::new_id(111));
+        expect_abort(ExitCode::USR_FORBIDDEN, h.approve_by_hash(&rt, TxnID(1), proposal_hash));
+        rt.reset();
+        check_state(&rt);
+    }
+
+    #[test]
+    fn contract_signer_uses_exported_methods() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let chuck = Address::new_id(103);
+        let contract = Address::new_id(110);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 2, 0, 0, vec![anne, contract]);
+
+        let fake_params = RawBytes::from(vec![1, 2, 3, 4]);
+        let fake_method = 42;
+        let send_value = TokenAmount::from_atto(10u8);
+        let propose_params = fil_actor_multisig::ProposeParams {
+            to: chuck,
+            value: send_value.clone(),
+            method: fake_method,
+            params: fake_params.clone(),
+        };
+
+        // A contract may not call the unexported method numbers.
+        rt.set_caller(*EVM_ACTOR_CODE_ID, contract);
+        expect_abort_contains_message(
+            ExitCode::USR_FORBIDDEN,
+            "must be built-in",
+            rt.call::<MultisigActor>(
+                Method::Propose as u64,
+                IpldBlock::serialize_cbor(&propose_params).unwrap(),
+            ),
+        );
+        rt.reset();
+
+        rt.expect_validate_caller_any();
+        let ret: ProposeReturn = rt
+            .call::<MultisigActor>(
+                Method::ProposeExported as u64,
+                IpldBlock::serialize_cbor(&propose_params).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        assert_eq!(TxnID(0), ret.txn_id);
+        assert!(!ret.applied);
+
+        rt.set_balance(send_value.clone());
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        rt.expect_send_simple(
+            chuck,
+            fake_method,
+            to_ipld_block(fake_params),
+            send_value,
+            None,
+            ExitCode::OK,
+        );
+        rt.expect_validate_caller_any();
+        let ret: ApproveReturn = rt
+            .call::<MultisigActor>(
+                Method::ApproveExported as u64,
+                IpldBlock::serialize_cbor(&TxnIDParams { id: TxnID(0), proposal_hash: vec![] })
+                    .unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        assert!(ret.applied);
+        h.assert_transactions(&rt, vec![]);
+        check_state(&rt);
+    }
+
+    #[test]
+    fn get_txn_reports_approval_status() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let bob = Address::new_id(102);
+        let chuck = Address::new_id(103);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        h.construct_and_verify(&rt, 3, 0, 0, vec![anne, bob, chuck]);
+
+        let fake_params = RawBytes::from(vec![1, 2, 3, 4]);
+        let fake_method = 42;
+        let send_value = TokenAmount::from_atto(10u8);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        let proposal_hash =
+            h.propose_ok(&rt, chuck, send_value.clone(), fake_method, fake_params.clone());
+
+        // Anyone may inspect the transaction.
+        rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1000));
+        let txn = h.get_txn(&rt, TxnID(0)).unwrap();
+        assert_eq!(chuck, txn.to);
+        assert_eq!(send_value, txn.value);
+        assert_eq!(fake_method, txn.method);
+        assert_eq!(fake_params, txn.params);
+        assert_eq!(vec![anne], txn.approved);
+        assert_eq!(proposal_hash.to_vec(), txn.proposal_hash);
+        assert_eq!(2, txn.remaining_approvals);
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, bob);
+        h.approve_ok(&rt, TxnID(0), proposal_hash);
+        let txn = h.get_txn(&rt, TxnID(0)).unwrap();
+        assert_eq!(vec![anne, bob], txn.approved);
+        assert_eq!(1, txn.remaining_approvals);
+
+        // Lowering the threshold below the approval count leaves nothing remaining.
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, msig);
+        h.change_num_approvals_threshold(&rt, 1).unwrap();
+        assert_eq!(0, h.get_txn(&rt, TxnID(0)).unwrap().remaining_approvals);
+
+        expect_abort(ExitCode::USR_NOT_FOUND, h.get_txn(&rt, TxnID(1)));
+        check_state(&rt);
+    }
+
     #[test]
     fn test_approve_with_non_empty_ret_value() {
         let msig = Address::new_id(100);
@@ -2428,6 +2700,76 @@ mod lock_balance_tests {
     }
 }
 
+mod simulate_proposal_tests {
+    use super::*;
+
+    #[test]
+    fn simulate_proposal_reports_remaining_approvals_without_recording() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let bob = Address::new_id(102);
+        let chuck = Address::new_id(103);
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        rt.set_balance(TokenAmount::from_atto(10u8));
+        h.construct_and_verify(&rt, 2, 0, 0, vec![anne, bob]);
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        let ret = h
+            .simulate_proposal(&rt, chuck, TokenAmount::from_atto(10u8), 42, RawBytes::default())
+            .unwrap();
+        assert_eq!(1, ret.remaining_approvals);
+
+        // A send to an unknown key address would create an account.
+        let bls = Address::new_bls(&[1u8; BLS_PUB_LEN]).unwrap();
+        let ret = h
+            .simulate_proposal(&rt, bls, TokenAmount::zero(), METHOD_SEND, RawBytes::default())
+            .unwrap();
+        assert_eq!(1, ret.remaining_approvals);
+
+        h.assert_transactions(&rt, vec![]);
+        check_state(&rt);
+    }
+
+    #[test]
+    fn simulate_proposal_fails_as_execution_would() {
+        let msig = Address::new_id(100);
+        let anne = Address::new_id(101);
+        let chuck = Address::new_id(103);
+        let send_value = TokenAmount::from_atto(20u8);
+        let unlock_duration = 20;
+        let start_epoch = 10;
+        let rt = construct_runtime(msig);
+        let h = util::ActorHarness::new();
+        rt.set_balance(send_value.clone());
+        rt.set_received(send_value.clone());
+        h.construct_and_verify(&rt, 1, unlock_duration, start_epoch, vec![anne]);
+        rt.set_epoch(start_epoch + unlock_duration / 2);
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, anne);
+        let simulate = |to: Address, value: u8| {
+            h.simulate_proposal(&rt, to, TokenAmount::from_atto(value), 0, RawBytes::default())
+        };
+        // Half the balance is still locked.
+        assert_eq!(0, simulate(chuck, 10).unwrap().remaining_approvals);
+        expect_abort(ExitCode::USR_INSUFFICIENT_FUNDS, simulate(chuck, 11));
+        expect_abort(ExitCode::USR_INSUFFICIENT_FUNDS, simulate(chuck, 21));
+        // There is no actor at the recipient ID address.
+        expect_abort(ExitCode::USR_NOT_FOUND, simulate(Address::new_id(999), 0));
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            h.simulate_proposal(&rt, chuck, TokenAmount::from_atto(-1), 0, RawBytes::default()),
+        );
+
+        // Only a signer may propose.
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, chuck);
+        expect_abort(ExitCode::USR_FORBIDDEN, simulate(chuck, 0));
+
+        h.assert_transactions(&rt, vec![]);
+        check_state(&rt);
+    }
+}
+
 #[test]
 fn token_receiver() {
     let msig = Address::new_id(1000);
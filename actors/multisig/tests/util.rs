@@ -1,7 +1,9 @@
 use fil_actor_multisig::{
-    Actor, AddSignerParams, ApproveReturn, ConstructorParams, Method, PENDING_TXN_CONFIG,
-    PendingTxnMap, ProposeParams, ProposeReturn, RemoveSignerParams, State, SwapSignerParams,
-    Transaction, TxnID, TxnIDParams, compute_proposal_hash,
+    Actor, AddSignerParams, ApproveByHashParams, ApproveByHashReturn, ApproveReturn,
+    ConstructorParams, GetTxnParams, GetTxnReturn, Method, PENDING_TXN_CONFIG, PendingTxnMap,
+    ProposeParams, ProposeReturn, RemoveSignerParams, SimulateProposalParams,
+    SimulateProposalReturn, State, SwapSignerParams, Transaction, TxnID, TxnIDParams,
+    compute_proposal_hash,
 };
 use fil_actor_multisig::{ChangeNumApprovalsThresholdParams, LockBalanceParams};
 use fil_actors_runtime::ActorError;
@@ -150,6 +152,22 @@ impl ActorHarness {
         ret
     }
 
+    pub fn approve_by_hash(
+        &self,
+        rt: &MockRuntime,
+        txn_id: TxnID,
+        proposal_hash: [u8; 32],
+    ) -> Result<ApproveByHashReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = ApproveByHashParams { proposal_hash: Vec::<u8>::from(proposal_hash), txn_id };
+        let ret = rt.call::<Actor>(
+            Method::ApproveByHashExported as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
     pub fn cancel(
         &self,
         rt: &MockRuntime,
@@ -167,6 +185,34 @@ impl ActorHarness {
         ret
     }
 
+    pub fn get_txn(&self, rt: &MockRuntime, txn_id: TxnID) -> Result<GetTxnReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<Actor>(
+            Method::GetTxnExported as u64,
+            IpldBlock::serialize_cbor(&GetTxnParams { id: txn_id }).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn simulate_proposal(
+        &self,
+        rt: &MockRuntime,
+        to: Address,
+        value: TokenAmount,
+        method: MethodNum,
+        params: RawBytes,
+    ) -> Result<SimulateProposalReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<Actor>(
+            Method::SimulateProposalExported as u64,
+            IpldBlock::serialize_cbor(&SimulateProposalParams { to, value, method, params })
+                .unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
     pub fn lock_balance(
         &self,
         rt: &MockRuntime,
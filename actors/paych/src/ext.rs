@@ -1,5 +1,7 @@
-use fvm_ipld_encoding::strict_bytes;
+use fil_actors_runtime::reward::FilterEstimate;
 use fvm_ipld_encoding::tuple::*;
+use fvm_ipld_encoding::{RawBytes, strict_bytes};
+use fvm_shared::clock::ChainEpoch;
 
 pub mod account {
     use super::*;
@@ -15,3 +17,23 @@ pub mod account {
         pub message: Vec<u8>,
     }
 }
+
+pub mod power {
+    use super::*;
+
+    pub const ENROLL_CRON_EVENT_METHOD: u64 = 4;
+
+    #[derive(Serialize_tuple, Deserialize_tuple)]
+    pub struct EnrollCronEventParams {
+        pub event_epoch: ChainEpoch,
+        pub payload: RawBytes,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple)]
+    pub struct DeferredCronEventParams {
+        #[serde(with = "strict_bytes")]
+        pub event_payload: Vec<u8>,
+        pub reward_smoothed: FilterEstimate,
+        pub quality_adj_power_smoothed: FilterEstimate,
+    }
+}
@@ -4,12 +4,14 @@
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    ActorContext, ActorDowncast, ActorError, Array, actor_dispatch, actor_error, deserialize_block,
-    extract_send_result, resolve_to_actor_id,
+    ActorContext, ActorDowncast, ActorError, Array, STORAGE_POWER_ACTOR_ADDR, actor_dispatch,
+    actor_error, deserialize_block, extract_send_result, resolve_to_actor_id,
 };
 use fvm_ipld_blockstore::Blockstore;
-use fvm_ipld_encoding::CBOR;
+use fvm_ipld_encoding::{CBOR, RawBytes};
 use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::crypto::hash::SupportedHashes;
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::econ::TokenAmount;
@@ -40,6 +42,8 @@ pub enum Method {
     UpdateChannelState = 2,
     Settle = 3,
     Collect = 4,
+    ScheduleCollect = 5,
+    OnDeferredCronEvent = 12,
 }
 
 pub const ERR_CHANNEL_STATE_UPDATE_AFTER_SETTLED: ExitCode = ExitCode::new(32);
@@ -149,10 +153,7 @@ impl Actor {
         }
 
         if !sv.secret_pre_image.is_empty() {
-            let hashed_secret: &[u8] = &rt.hash_blake2b(&params.secret);
-            if hashed_secret != sv.secret_pre_image.as_slice() {
-                return Err(actor_error!(illegal_argument; "incorrect secret"));
-            }
+            verify_secret(rt, &sv.secret_pre_image, &params.secret)?;
         }
 
         if let Some(extra) = &sv.extra {
@@ -289,18 +290,106 @@ impl Actor {
             return Err(actor_error!(forbidden; "payment channel not settling or settled"));
         }
 
-        // send ToSend to `to`
-        extract_send_result(rt.send_simple(&st.to, METHOD_SEND, None, st.to_send))
-            .map_err(|e| e.wrap("Failed to send funds to `to` address"))?;
+        pay_out(rt, st)
+    }
 
-        // return remaining balance back to the "from" address.
-        extract_send_result(rt.send_simple(&st.from, METHOD_SEND, None, rt.current_balance()))
-            .map_err(|e| e.wrap("Failed to send funds to `from` address"))?;
+    /// Schedules the channel to be collected automatically once settled, by enrolling a cron
+    /// event with the power actor for the settlement epoch. The channel must be settling.
+    pub fn schedule_collect(rt: &impl Runtime) -> Result<(), ActorError> {
+        let st: State = rt.state()?;
+        rt.validate_immediate_caller_is(&[st.from, st.to])?;
 
-        rt.delete_actor()?;
+        if st.settling_at == 0 {
+            return Err(actor_error!(forbidden; "payment channel not settling"));
+        }
+        if rt.curr_epoch() >= st.settling_at {
+            return Err(
+                actor_error!(forbidden; "payment channel already settled, collect it instead"),
+            );
+        }
 
-        Ok(())
+        enroll_collect_event(rt, st.settling_at)
+    }
+
+    /// Invoked by the power actor's cron at the epoch enrolled by ScheduleCollect, to collect the
+    /// channel. If the settlement epoch has since been pushed back by a voucher's minimum settle
+    /// height, the collection is rescheduled instead.
+    pub fn on_deferred_cron_event(
+        rt: &impl Runtime,
+        _params: ext::power::DeferredCronEventParams,
+    ) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_is(std::iter::once(&STORAGE_POWER_ACTOR_ADDR))?;
+        let st: State = rt.state()?;
+
+        if st.settling_at == 0 {
+            return Err(actor_error!(forbidden; "payment channel not settling"));
+        }
+        if rt.curr_epoch() < st.settling_at {
+            return enroll_collect_event(rt, st.settling_at);
+        }
+
+        pay_out(rt, st)
+    }
+}
+
+// Pays out the redeemed amount to the recipient and the remaining balance to the owner,
+// then deletes the channel.
+fn pay_out(rt: &impl Runtime, st: State) -> Result<(), ActorError> {
+    // send ToSend to `to`
+    extract_send_result(rt.send_simple(&st.to, METHOD_SEND, None, st.to_send))
+        .map_err(|e| e.wrap("Failed to send funds to `to` address"))?;
+
+    // return remaining balance back to the "from" address.
+    extract_send_result(rt.send_simple(&st.from, METHOD_SEND, None, rt.current_balance()))
+        .map_err(|e| e.wrap("Failed to send funds to `from` address"))?;
+
+    rt.delete_actor()?;
+
+    Ok(())
+}
+
+// Enrolls a cron event with the power actor to collect the channel at an epoch.
+fn enroll_collect_event(rt: &impl Runtime, epoch: ChainEpoch) -> Result<(), ActorError> {
+    let params =
+        ext::power::EnrollCronEventParams { event_epoch: epoch, payload: RawBytes::default() };
+    extract_send_result(rt.send_simple(
+        &STORAGE_POWER_ACTOR_ADDR,
+        ext::power::ENROLL_CRON_EVENT_METHOD,
+        IpldBlock::serialize_cbor(&params)?,
+        TokenAmount::zero(),
+    ))
+    .map_err(|e| e.wrap("failed to enroll cron event"))?;
+    Ok(())
+}
+
+// Checks that a secret revealed at redemption hashes to the value committed in a voucher.
+// A raw BLAKE2b-256 digest may commit to an empty secret, as it always could.
+fn verify_secret(rt: &impl Runtime, secret_hash: &[u8], secret: &[u8]) -> Result<(), ActorError> {
+    let matches = if secret_hash.len() == 32 {
+        rt.hash_blake2b(secret).as_slice() == secret_hash
+    } else {
+        if secret.is_empty() {
+            return Err(actor_error!(illegal_argument; "voucher requires a secret"));
+        }
+        // A multihash with single-byte code and digest length prefixes.
+        let (hasher, digest) = match secret_hash {
+            [SECRET_HASH_SHA2_256, 32, digest @ ..] => (SupportedHashes::Sha2_256, digest),
+            [SECRET_HASH_KECCAK_256, 32, digest @ ..] => (SupportedHashes::Keccak256, digest),
+            _ => {
+                return Err(actor_error!(illegal_argument;
+                    "unsupported voucher secret hash {:?}", secret_hash));
+            }
+        };
+        if digest.len() != 32 {
+            return Err(actor_error!(illegal_argument;
+                "invalid voucher secret hash length {}", digest.len()));
+        }
+        rt.hash(hasher, secret).as_slice() == digest
+    };
+    if !matches {
+        return Err(actor_error!(illegal_argument; "incorrect secret"));
     }
+    Ok(())
 }
 
 #[inline]
@@ -332,5 +421,7 @@ impl ActorCode for Actor {
         UpdateChannelState => update_channel_state,
         Settle => settle,
         Collect => collect,
+        ScheduleCollect => schedule_collect,
+        OnDeferredCronEvent => on_deferred_cron_event,
     }
 }
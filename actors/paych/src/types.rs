@@ -22,6 +22,11 @@ pub const MAX_SECRET_SIZE: usize = 256;
 
 pub const LANE_STATES_AMT_BITWIDTH: u32 = 3;
 
+// Multihash codes of the hash functions supported for voucher secrets,
+// in addition to a raw BLAKE2b-256 digest.
+pub const SECRET_HASH_SHA2_256: u8 = 0x12;
+pub const SECRET_HASH_KECCAK_256: u8 = 0x1b;
+
 /// Constructor parameters for payment channel actor
 #[derive(Serialize_tuple, Deserialize_tuple)]
 pub struct ConstructorParams {
@@ -40,7 +45,10 @@ pub struct SignedVoucher {
     /// Max epoch beyond which the voucher cannot be redeemed
     /// set to 0 means no timeout
     pub time_lock_max: ChainEpoch,
-    /// (optional) Used by `to` to validate
+    /// (optional) Hash of a secret which must be revealed to redeem the voucher.
+    /// A 32-byte value is a raw BLAKE2b-256 digest. Otherwise the value must be a
+    /// SHA2-256 or Keccak-256 multihash, enabling hash-locked payments which interoperate
+    /// with contracts on other chains.
     #[serde(with = "strict_bytes")]
     pub secret_pre_image: Vec<u8>,
     /// (optional) Specified by `from` to add a verification method to the voucher
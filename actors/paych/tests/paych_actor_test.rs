@@ -10,7 +10,8 @@ use fil_actor_paych::ext::account::{AUTHENTICATE_MESSAGE_METHOD, AuthenticateMes
 use fil_actor_paych::testing::check_state_invariants;
 use fil_actor_paych::{
     Actor as PaychActor, ConstructorParams, LaneState, MAX_LANE, Merge, Method, ModVerifyParams,
-    SETTLE_DELAY, SignedVoucher, State as PState, UpdateChannelStateParams,
+    SECRET_HASH_KECCAK_256, SECRET_HASH_SHA2_256, SETTLE_DELAY, SignedVoucher, State as PState,
+    UpdateChannelStateParams,
 };
 
 use fil_actors_runtime::INIT_ACTOR_ADDR;
@@ -23,6 +24,7 @@ use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::METHOD_CONSTRUCTOR;
 use fvm_shared::address::Address;
 use fvm_shared::clock::ChainEpoch;
+use fvm_shared::crypto::hash::SupportedHashes;
 use fvm_shared::crypto::signature::Signature;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
@@ -824,6 +826,90 @@ mod secret_preimage {
         rt.verify();
         check_state(&rt);
     }
+
+    // Redeems a voucher committing to the given secret hash, revealing the given secret.
+    fn redeem_with_secret(secret_hash: Vec<u8>, secret: &[u8], exp: ExitCode) {
+        let (rt, sv) = require_create_channel_with_lanes(1);
+        let state: PState = rt.get_state();
+
+        let mut ucp = UpdateChannelStateParams { secret: secret.to_vec(), sv };
+        ucp.sv.secret_pre_image = secret_hash;
+
+        rt.expect_validate_caller_addr(vec![state.from, state.to]);
+        expect_authenticate_message(&rt, state.to, ucp.sv.clone(), ExitCode::OK);
+
+        if exp.is_success() {
+            call(&rt, Method::UpdateChannelState as u64, IpldBlock::serialize_cbor(&ucp).unwrap());
+        } else {
+            expect_abort(
+                &rt,
+                Method::UpdateChannelState as u64,
+                IpldBlock::serialize_cbor(&ucp).unwrap(),
+                exp,
+            );
+        }
+
+        rt.verify();
+        check_state(&rt);
+    }
+
+    fn multihash(code: u8, hasher: SupportedHashes, data: &[u8]) -> Vec<u8> {
+        let (digest, len) = hash(hasher, data);
+        [&[code, len as u8], &digest[..len]].concat()
+    }
+
+    #[test]
+    fn succeed_blake2b_secret() {
+        let secret = b"Professor X";
+        redeem_with_secret(blake2b_256(secret).to_vec(), secret, ExitCode::OK);
+    }
+
+    #[test]
+    fn succeed_sha256_secret() {
+        let secret = b"Professor X";
+        let secret_hash = multihash(SECRET_HASH_SHA2_256, SupportedHashes::Sha2_256, secret);
+        redeem_with_secret(secret_hash, secret, ExitCode::OK);
+    }
+
+    #[test]
+    fn succeed_keccak256_secret() {
+        let secret = b"Professor X";
+        let secret_hash = multihash(SECRET_HASH_KECCAK_256, SupportedHashes::Keccak256, secret);
+        redeem_with_secret(secret_hash, secret, ExitCode::OK);
+    }
+
+    #[test]
+    fn incorrect_sha256_secret() {
+        let secret_hash =
+            multihash(SECRET_HASH_SHA2_256, SupportedHashes::Sha2_256, b"Professor X");
+        redeem_with_secret(secret_hash, b"Magneto", ExitCode::USR_ILLEGAL_ARGUMENT);
+    }
+
+    #[test]
+    fn succeed_empty_blake2b_secret() {
+        redeem_with_secret(blake2b_256(b"").to_vec(), b"", ExitCode::OK);
+    }
+
+    #[test]
+    fn missing_secret() {
+        let secret_hash = blake2b_256(b"Professor X").to_vec();
+        redeem_with_secret(secret_hash, b"", ExitCode::USR_ILLEGAL_ARGUMENT);
+    }
+
+    #[test]
+    fn missing_sha256_secret() {
+        let secret_hash =
+            multihash(SECRET_HASH_SHA2_256, SupportedHashes::Sha2_256, b"Professor X");
+        redeem_with_secret(secret_hash, b"", ExitCode::USR_ILLEGAL_ARGUMENT);
+    }
+
+    #[test]
+    fn unsupported_secret_hash() {
+        let secret = b"Professor X";
+        // A SHA2-512 multihash is not supported.
+        let secret_hash = multihash(0x13, SupportedHashes::Sha2_512, secret);
+        redeem_with_secret(secret_hash, secret, ExitCode::USR_ILLEGAL_ARGUMENT);
+    }
 }
 
 mod actor_settle {
@@ -1022,6 +1108,126 @@ mod actor_collect {
     }
 }
 
+mod actor_schedule_collect {
+    use fil_actor_paych::ext::power::{
+        DeferredCronEventParams, ENROLL_CRON_EVENT_METHOD, EnrollCronEventParams,
+    };
+    use fil_actors_runtime::{ActorError, STORAGE_POWER_ACTOR_ADDR};
+    use fvm_shared::METHOD_SEND;
+
+    use super::*;
+
+    fn settle(rt: &MockRuntime) -> PState {
+        let st: PState = rt.get_state();
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, st.from);
+        rt.expect_validate_caller_addr(vec![st.from, st.to]);
+        call(rt, Method::Settle as u64, None);
+        rt.get_state()
+    }
+
+    fn expect_enroll(rt: &MockRuntime, epoch: ChainEpoch) {
+        rt.expect_send_simple(
+            STORAGE_POWER_ACTOR_ADDR,
+            ENROLL_CRON_EVENT_METHOD,
+            IpldBlock::serialize_cbor(&EnrollCronEventParams {
+                event_epoch: epoch,
+                payload: RawBytes::default(),
+            })
+            .unwrap(),
+            TokenAmount::zero(),
+            None,
+            ExitCode::OK,
+        );
+    }
+
+    fn cron(rt: &MockRuntime) -> Result<Option<IpldBlock>, ActorError> {
+        rt.set_caller(*POWER_ACTOR_CODE_ID, STORAGE_POWER_ACTOR_ADDR);
+        rt.expect_validate_caller_addr(vec![STORAGE_POWER_ACTOR_ADDR]);
+        let params = DeferredCronEventParams {
+            event_payload: vec![],
+            reward_smoothed: Default::default(),
+            quality_adj_power_smoothed: Default::default(),
+        };
+        rt.call::<PaychActor>(
+            Method::OnDeferredCronEvent as u64,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )
+    }
+
+    #[test]
+    fn collects_at_settlement() {
+        let (rt, _sv) = require_create_channel_with_lanes(1);
+        rt.epoch.replace(10);
+        let st = settle(&rt);
+
+        // The recipient schedules collection.
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, st.to);
+        rt.expect_validate_caller_addr(vec![st.from, st.to]);
+        expect_enroll(&rt, st.settling_at);
+        call(&rt, Method::ScheduleCollect as u64, None);
+        rt.verify();
+
+        rt.epoch.replace(st.settling_at);
+        rt.expect_send_simple(
+            st.to,
+            METHOD_SEND,
+            Default::default(),
+            st.to_send.clone(),
+            Default::default(),
+            ExitCode::OK,
+        );
+        rt.expect_send_simple(
+            st.from,
+            METHOD_SEND,
+            Default::default(),
+            &*rt.balance.borrow() - &st.to_send,
+            Default::default(),
+            ExitCode::OK,
+        );
+        rt.expect_delete_actor();
+        assert!(cron(&rt).unwrap().is_none());
+        rt.verify();
+        assert!(rt.is_deleted());
+    }
+
+    #[test]
+    fn reschedules_when_settlement_is_deferred() {
+        let (rt, _sv) = require_create_channel_with_lanes(1);
+        rt.epoch.replace(10);
+        let st = settle(&rt);
+
+        // The cron event fires before the settlement epoch, as if a voucher had pushed it back.
+        rt.epoch.replace(st.settling_at - 1);
+        expect_enroll(&rt, st.settling_at);
+        assert!(cron(&rt).unwrap().is_none());
+        rt.verify();
+        assert!(!rt.is_deleted());
+    }
+
+    #[test]
+    fn fails_if_not_settling() {
+        let (rt, _sv) = require_create_channel_with_lanes(1);
+        let st: PState = rt.get_state();
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, st.from);
+        rt.expect_validate_caller_addr(vec![st.from, st.to]);
+        expect_abort(&rt, Method::ScheduleCollect as u64, None, ExitCode::USR_FORBIDDEN);
+
+        expect_abort_contains_message(ExitCode::USR_FORBIDDEN, "not settling", cron(&rt));
+        rt.reset();
+    }
+
+    #[test]
+    fn fails_if_caller_is_not_a_party() {
+        let (rt, _sv) = require_create_channel_with_lanes(1);
+        let st = settle(&rt);
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(1000));
+        rt.expect_validate_caller_addr(vec![st.from, st.to]);
+        expect_abort(&rt, Method::ScheduleCollect as u64, None, ExitCode::USR_FORBIDDEN);
+    }
+}
+
 fn require_create_channel_with_lanes(num_lanes: u64) -> (MockRuntime, SignedVoucher) {
     let paych_addr = Address::new_id(100);
     let payer_addr = Address::new_id(PAYER_ID);
@@ -0,0 +1,43 @@
+// A namespace for helpers that build and emit power actor events.
+
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::{ActorError, EventBuilder};
+use fvm_shared::ActorID;
+use fvm_shared::bigint::bigint_ser::BigIntSer;
+use fvm_shared::sector::StoragePower;
+
+/// Indicates a new miner has been created, with a claim of no power.
+pub fn miner_created(rt: &impl Runtime, miner: ActorID) -> Result<(), ActorError> {
+    EventBuilder::new().typ("miner-created").field_indexed("miner", &miner).emit(rt)
+}
+
+/// Indicates a miner's claimed raw and quality-adjusted power have changed by some deltas.
+pub fn power_updated(
+    rt: &impl Runtime,
+    miner: ActorID,
+    raw_byte_delta: &StoragePower,
+    quality_adj_delta: &StoragePower,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("power-updated")
+        .field_indexed("miner", &miner)
+        .field("raw-power-delta", &BigIntSer(raw_byte_delta))
+        .field("qa-power-delta", &BigIntSer(quality_adj_delta))
+        .emit(rt)
+}
+
+/// Indicates a miner's claim has been deleted, removing all its power from the network,
+/// after the miner failed to process a cron callback.
+pub fn claim_deleted(rt: &impl Runtime, miner: ActorID) -> Result<(), ActorError> {
+    EventBuilder::new().typ("claim-deleted").field_indexed("miner", &miner).emit(rt)
+}
+
+/// Indicates a miner has been suspended by governance, and its consensus power removed.
+pub fn miner_suspended(rt: &impl Runtime, miner: ActorID) -> Result<(), ActorError> {
+    EventBuilder::new().typ("miner-suspended").field_indexed("miner", &miner).emit(rt)
+}
+
+/// Indicates a suspended miner has been reinstated, and its consensus power restored.
+pub fn miner_reinstated(rt: &impl Runtime, miner: ActorID) -> Result<(), ActorError> {
+    EventBuilder::new().typ("miner-reinstated").field_indexed("miner", &miner).emit(rt)
+}
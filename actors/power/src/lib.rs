@@ -1,38 +1,44 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
-use fil_actors_runtime::reward::ThisEpochRewardReturn;
+use fil_actors_runtime::reward::{ThisEpochRewardReturn, pledge_ramp_gamma};
 use fvm_ipld_encoding::RawBytes;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::Address;
 use fvm_shared::bigint::bigint_ser::BigIntSer;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
-use fvm_shared::{METHOD_CONSTRUCTOR, MethodNum};
+use fvm_shared::{ActorID, METHOD_CONSTRUCTOR, MethodNum};
 use log::{debug, error};
 use num_derive::FromPrimitive;
 use num_traits::Zero;
+use std::marker::PhantomData;
 
 use ext::init;
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
-    ActorDowncast, ActorError, CRON_ACTOR_ADDR, INIT_ACTOR_ADDR, Multimap, REWARD_ACTOR_ADDR,
-    SYSTEM_ACTOR_ADDR, actor_dispatch, actor_error, deserialize_block, extract_send_result,
+    ActorDowncast, ActorError, BatchReturnGen, CRON_ACTOR_ADDR, Cursor, INIT_ACTOR_ADDR, Multimap,
+    REWARD_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, actor_dispatch, actor_error, deserialize_block,
+    extract_send_result, state_getters,
 };
 
 pub use self::policy::*;
 pub use self::state::*;
 pub use self::types::*;
+pub use self::weight::*;
 
 #[cfg(feature = "fil-actor")]
 fil_actors_runtime::wasm_trampoline!(Actor);
 
+mod emit;
 #[doc(hidden)]
 pub mod ext;
 mod policy;
 mod state;
 pub mod testing;
 mod types;
+mod weight;
 
 // * Updated to specs-actors commit: 999e57a151cc7ada020ca2844b651499ab8c0dec (v3.0.1)
 
@@ -50,21 +56,32 @@ pub enum Method {
     // OnConsensusFault = 7, // Deprecated v2
     // SubmitPoRepForBulkVerify = 8, // Deprecated
     CurrentTotalPower = 9,
+    SuspendMiner = 10,
+    ReinstateMiner = 11,
     // Method numbers derived from FRC-0042 standards
     CreateMinerExported = frc42_dispatch::method_hash!("CreateMiner"),
     NetworkRawPowerExported = frc42_dispatch::method_hash!("NetworkRawPower"),
+    NetworkQAPowerExported = frc42_dispatch::method_hash!("NetworkQAPower"),
+    NetworkPledgeExported = frc42_dispatch::method_hash!("NetworkPledge"),
     MinerRawPowerExported = frc42_dispatch::method_hash!("MinerRawPower"),
     MinerCountExported = frc42_dispatch::method_hash!("MinerCount"),
     MinerConsensusCountExported = frc42_dispatch::method_hash!("MinerConsensusCount"),
     MinerPowerExported = frc42_dispatch::method_hash!("MinerPower"),
+    TotalPowerAtExported = frc42_dispatch::method_hash!("TotalPowerAt"),
+    PledgeRampStatusExported = frc42_dispatch::method_hash!("PledgeRampStatus"),
+    ListMinerClaimsExported = frc42_dispatch::method_hash!("ListMinerClaims"),
+    MinerPowersExported = frc42_dispatch::method_hash!("MinerPowers"),
+    GetCronEventsExported = frc42_dispatch::method_hash!("GetCronEvents"),
+    MinerPledgeExported = frc42_dispatch::method_hash!("MinerPledge"),
+    HistoricalPowerExported = frc42_dispatch::method_hash!("HistoricalPower"),
 }
 
 pub const ERR_TOO_MANY_PROVE_COMMITS: ExitCode = ExitCode::new(32);
 
-/// Storage Power Actor
-pub struct Actor;
+/// Storage Power Actor, weighing miners' claims in consensus with `W`.
+pub struct Actor<W = QualityAdjustedWeight>(PhantomData<W>);
 
-impl Actor {
+impl<W: ConsensusWeight + Default> Actor<W> {
     /// Constructor for StoragePower actor
     fn constructor(rt: &impl Runtime) -> Result<(), ActorError> {
         rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
@@ -88,8 +105,8 @@ impl Actor {
             worker: params.worker,
             window_post_proof_type: params.window_post_proof_type,
             peer_id: params.peer,
-            multi_addresses: params.multiaddrs,
-            control_addresses: Default::default(),
+            multi_addresses: params.multiaddrs.into_inner(),
+            control_addresses: params.control_addresses,
         })?;
 
         let miner_actor_code_cid = rt.get_code_cid_for_type(Type::Miner);
@@ -130,6 +147,7 @@ impl Actor {
             st.save_claims(&mut claims)?;
             Ok(())
         })?;
+        emit::miner_created(rt, id_address.id().unwrap())?;
         Ok(CreateMinerReturn { id_address, robust_address })
     }
 
@@ -147,6 +165,7 @@ impl Actor {
 
             st.add_to_claim(
                 rt.policy(),
+                &W::default(),
                 &mut claims,
                 &miner_addr,
                 &params.raw_byte_delta,
@@ -155,18 +174,24 @@ impl Actor {
 
             st.save_claims(&mut claims)?;
             Ok(())
-        })
+        })?;
+        emit::power_updated(
+            rt,
+            miner_addr.id().unwrap(),
+            &params.raw_byte_delta,
+            &params.quality_adjusted_delta,
+        )
     }
 
     fn enroll_cron_event(
         rt: &impl Runtime,
         params: EnrollCronEventParams,
     ) -> Result<(), ActorError> {
-        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
-        let miner_event = CronEvent {
-            miner_addr: rt.message().caller(),
-            callback_payload: params.payload.clone(),
-        };
+        rt.validate_immediate_caller_type(&[Type::Miner, Type::PaymentChannel])?;
+        let caller = rt.message().caller();
+        let is_channel = is_payment_channel(rt, &caller);
+        let miner_event =
+            CronEvent { miner_addr: caller, callback_payload: params.payload.clone() };
 
         // Ensure it is not possible to enter a large negative number which would cause
         // problems in cron processing.
@@ -176,6 +201,10 @@ impl Actor {
         }
 
         rt.transaction(|st: &mut State, rt| {
+            if is_channel {
+                return st.add_channel_cron_event(rt.store(), params.event_epoch, miner_event);
+            }
+
             let mut events = Multimap::from_root(
                 rt.store(),
                 &st.cron_event_queue,
@@ -186,9 +215,8 @@ impl Actor {
                 e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to load cron events")
             })?;
 
-            st.append_cron_event(&mut events, params.event_epoch, miner_event).map_err(|e| {
-                e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to enroll cron event")
-            })?;
+            // Re-enrolling an event that is already queued is a no-op.
+            st.append_cron_event(rt.store(), &mut events, params.event_epoch, miner_event)?;
 
             st.cron_event_queue = events.root().map_err(|e| {
                 e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to flush cron events")
@@ -213,13 +241,14 @@ impl Actor {
 
         Self::process_deferred_cron_events(rt, rewret)?;
 
-        let this_epoch_raw_byte_power = rt.transaction(|st: &mut State, _| {
+        let this_epoch_raw_byte_power = rt.transaction(|st: &mut State, rt| {
             let (raw_byte_power, qa_power) = st.current_total_power();
             st.this_epoch_pledge_collateral = st.total_pledge_collateral.clone();
             st.this_epoch_quality_adj_power = qa_power;
             st.this_epoch_raw_byte_power = raw_byte_power;
             // Can assume delta is one since cron is invoked every epoch.
             st.update_smoothed_estimate(1);
+            st.record_power_history(rt.store(), rt.curr_epoch())?;
 
             Ok(IpldBlock::serialize_cbor(&BigIntSer(&st.this_epoch_raw_byte_power))?)
         })?;
@@ -242,7 +271,9 @@ impl Actor {
     ) -> Result<(), ActorError> {
         rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
         rt.transaction(|st: &mut State, rt| {
-            st.validate_miner_has_claim(rt.store(), &rt.message().caller())?;
+            let miner_addr = rt.message().caller();
+            st.validate_miner_has_claim(rt.store(), &miner_addr)?;
+            st.add_miner_pledge(rt.store(), &miner_addr, &params.pledge_delta)?;
             st.add_pledge_total(params.pledge_delta);
             if st.total_pledge_collateral.is_negative() {
                 return Err(actor_error!(
@@ -255,34 +286,52 @@ impl Actor {
         })
     }
 
-    /// Returns the total power and pledge recorded by the power actor.
-    /// The returned values are frozen during the cron tick before this epoch
-    /// so that this method returns consistent values while processing all messages
-    /// of an epoch.
-    fn current_total_power(rt: &impl Runtime) -> Result<CurrentTotalPowerReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-        let st: State = rt.state()?;
+    state_getters! {
+        State;
+
+        /// Returns the total power and pledge recorded by the power actor.
+        /// The returned values are frozen during the cron tick before this epoch
+        /// so that this method returns consistent values while processing all messages
+        /// of an epoch.
+        fn current_total_power() -> CurrentTotalPowerReturn {
+            raw_byte_power: this_epoch_raw_byte_power,
+            quality_adj_power: this_epoch_quality_adj_power,
+            pledge_collateral: this_epoch_pledge_collateral,
+            quality_adj_power_smoothed: this_epoch_qa_power_smoothed,
+            ramp_start_epoch,
+            ramp_duration_epochs,
+        }
 
-        Ok(CurrentTotalPowerReturn {
-            raw_byte_power: st.this_epoch_raw_byte_power,
-            quality_adj_power: st.this_epoch_quality_adj_power,
-            pledge_collateral: st.this_epoch_pledge_collateral,
-            quality_adj_power_smoothed: st.this_epoch_qa_power_smoothed,
-            ramp_start_epoch: st.ramp_start_epoch,
-            ramp_duration_epochs: st.ramp_duration_epochs,
-        })
-    }
+        /// Returns the total raw power of the network.
+        /// This is defined as the sum of the active (i.e. non-faulty) byte commitments
+        /// of all miners that have more than the consensus minimum amount of storage active.
+        /// This value is static over an epoch, and does NOT get updated as messages are executed.
+        /// It is recalculated after all messages at an epoch have been executed.
+        fn network_raw_power() -> NetworkRawPowerReturn {
+            raw_byte_power: this_epoch_raw_byte_power,
+        }
 
-    /// Returns the total raw power of the network.
-    /// This is defined as the sum of the active (i.e. non-faulty) byte commitments
-    /// of all miners that have more than the consensus minimum amount of storage active.
-    /// This value is static over an epoch, and does NOT get updated as messages are executed.
-    /// It is recalculated after all messages at an epoch have been executed.
-    fn network_raw_power(rt: &impl Runtime) -> Result<NetworkRawPowerReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-        let st: State = rt.state()?;
+        /// Returns the total quality-adjusted power of the network, counted as for the raw power.
+        /// This value is static over an epoch, like the raw power.
+        fn network_qa_power() -> NetworkQAPowerReturn {
+            quality_adj_power: this_epoch_quality_adj_power,
+        }
 
-        Ok(NetworkRawPowerReturn { raw_byte_power: st.this_epoch_raw_byte_power })
+        /// Returns the total pledge collateral locked by miners.
+        /// This value is static over an epoch, like the network power.
+        fn network_pledge() -> NetworkPledgeReturn {
+            pledge_collateral: this_epoch_pledge_collateral,
+        }
+
+        /// Returns the total number of miners created, regardless of whether or not
+        /// they have any pledged storage.
+        fn miner_count() -> MinerCountReturn { miner_count }
+
+        /// Returns the total number of miners that have more than the consensus minimum amount of storage active.
+        /// Active means that the storage must not be faulty.
+        fn miner_consensus_count() -> MinerConsensusCountReturn {
+            miner_consensus_count: miner_above_min_power_count,
+        }
     }
 
     /// Returns the raw power claimed by the specified miner,
@@ -301,25 +350,7 @@ impl Actor {
         Ok(MinerRawPowerReturn { raw_byte_power, meets_consensus_minimum })
     }
 
-    /// Returns the total number of miners created, regardless of whether or not
-    /// they have any pledged storage.
-    fn miner_count(rt: &impl Runtime) -> Result<MinerCountReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-        let st: State = rt.state()?;
-
-        Ok(MinerCountReturn { miner_count: st.miner_count })
-    }
-
-    /// Returns the total number of miners that have more than the consensus minimum amount of storage active.
-    /// Active means that the storage must not be faulty.
-    fn miner_consensus_count(rt: &impl Runtime) -> Result<MinerConsensusCountReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-        let st: State = rt.state()?;
-
-        Ok(MinerConsensusCountReturn { miner_consensus_count: st.miner_above_min_power_count })
-    }
-
-    /// Returns the miner's quality-adjusted and raw power
+    /// Returns the miner's raw power, and quality-adjusted power as weighed in consensus.
     fn miner_power(
         rt: &impl Runtime,
         params: MinerPowerParams,
@@ -332,19 +363,227 @@ impl Actor {
 
         if let Some(claim) = claim {
             Ok(MinerPowerReturn {
+                quality_adj_power: W::default().weight(&claim),
                 raw_byte_power: claim.raw_byte_power,
-                quality_adj_power: claim.quality_adj_power,
             })
         } else {
             Err(actor_error!(not_found, "miner not found"))
         }
     }
 
+    /// Returns the pledge collateral locked by a miner, as reported by its pledge updates.
+    fn miner_pledge(
+        rt: &impl Runtime,
+        params: MinerPledgeParams,
+    ) -> Result<MinerPledgeReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+
+        let miner_address = &Address::new_id(params.miner);
+        if st.miner_power(rt.store(), miner_address)?.is_none() {
+            return Err(actor_error!(not_found, "miner not found"));
+        }
+        let pledge_collateral = st.miner_pledge(rt.store(), miner_address)?;
+        Ok(MinerPledgeReturn { pledge_collateral })
+    }
+
+    /// Returns the raw power, and quality-adjusted power as weighed in consensus, of each of the
+    /// given miners.
+    /// Miners without a claim fail in the batch with USR_NOT_FOUND.
+    fn miner_powers(
+        rt: &impl Runtime,
+        params: MinerPowersParams,
+    ) -> Result<MinerPowersReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        let claims = st.load_claims(rt.store())?;
+        let weight = W::default();
+
+        let mut batch_gen = BatchReturnGen::new(params.miners.len());
+        let mut powers = Vec::new();
+        for miner in params.miners {
+            match claims.get(&Address::new_id(miner))? {
+                Some(claim) => {
+                    batch_gen.add_success();
+                    powers.push(MinerPowerReturn {
+                        raw_byte_power: claim.raw_byte_power.clone(),
+                        quality_adj_power: weight.weight(claim),
+                    });
+                }
+                None => {
+                    batch_gen.add_fail(ExitCode::USR_NOT_FOUND);
+                    debug!("no claim for miner {}", miner);
+                }
+            }
+        }
+        Ok(MinerPowersReturn { batch_info: batch_gen.generate(), powers })
+    }
+
+    /// Lists miners' claimed power, including that of suspended miners.
+    /// Results are paginated; a call returns at most the requested number of claims,
+    /// up to LIST_MINER_CLAIMS_MAX_PAGE_SIZE.
+    fn list_miner_claims(
+        rt: &impl Runtime,
+        params: ListMinerClaimsParams,
+    ) -> Result<ListMinerClaimsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.limit == 0 {
+            return Err(actor_error!(illegal_argument, "limit must be positive"));
+        }
+        let limit = params.limit.min(LIST_MINER_CLAIMS_MAX_PAGE_SIZE) as usize;
+
+        // The cursor's position is the first miner to list.
+        let start: Option<ActorID> = params.cursor.decode()?;
+        let st: State = rt.state()?;
+        let (claims, next) = st.list_claims(rt.store(), start, limit)?;
+        let next_cursor = next.map(|miner| Cursor::encode(&miner)).transpose()?;
+        Ok(ListMinerClaimsReturn { claims, next_cursor })
+    }
+
+    /// Returns the events enrolled in the cron queue for a range of epochs, so that miners
+    /// can verify that their callbacks are scheduled.
+    /// The range may span at most GET_CRON_EVENTS_MAX_EPOCH_RANGE epochs.
+    fn get_cron_events(
+        rt: &impl Runtime,
+        params: GetCronEventsParams,
+    ) -> Result<GetCronEventsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.end_epoch < params.start_epoch {
+            return Err(actor_error!(
+                illegal_argument,
+                "end epoch {} before start epoch {}",
+                params.end_epoch,
+                params.start_epoch
+            ));
+        }
+        if params.end_epoch.saturating_sub(params.start_epoch) >= GET_CRON_EVENTS_MAX_EPOCH_RANGE {
+            return Err(actor_error!(
+                illegal_argument,
+                "epoch range {}..={} exceeds maximum of {} epochs",
+                params.start_epoch,
+                params.end_epoch,
+                GET_CRON_EVENTS_MAX_EPOCH_RANGE
+            ));
+        }
+
+        let st: State = rt.state()?;
+        let events = st
+            .cron_events_in(rt.store(), params.start_epoch, params.end_epoch)?
+            .into_iter()
+            .map(|(epoch, event)| CronEventSummary {
+                epoch,
+                miner_addr: event.miner_addr,
+                payload_digest: rt.hash_blake2b(&event.callback_payload),
+            })
+            .collect();
+        Ok(GetCronEventsReturn { events })
+    }
+
+    /// Returns the total power and pledge frozen by the cron tick at the end of the given epoch,
+    /// i.e. the values returned by CurrentTotalPower during the following epoch.
+    /// Only the most recent POWER_HISTORY_LENGTH epochs are retained, and no totals
+    /// are recorded for null rounds.
+    fn total_power_at(
+        rt: &impl Runtime,
+        params: TotalPowerAtParams,
+    ) -> Result<TotalPowerAtReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+
+        let entry = st.power_history_at(rt.store(), params.epoch)?.ok_or_else(|| {
+            actor_error!(not_found, "no power totals recorded for epoch {}", params.epoch)
+        })?;
+        Ok(TotalPowerAtReturn {
+            raw_byte_power: entry.raw_byte_power,
+            quality_adj_power: entry.quality_adj_power,
+            pledge_collateral: entry.pledge_collateral,
+        })
+    }
+
+    /// Returns the network totals frozen by the cron tick at the end of each epoch in a range,
+    /// for callers that need a series of historical totals rather than a single epoch's.
+    /// The range may span at most POWER_HISTORY_LENGTH epochs. Epochs for which no totals
+    /// are retained, such as null rounds, are omitted.
+    fn historical_power(
+        rt: &impl Runtime,
+        params: HistoricalPowerParams,
+    ) -> Result<HistoricalPowerReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.end_epoch < params.start_epoch {
+            return Err(actor_error!(
+                illegal_argument,
+                "end epoch {} before start epoch {}",
+                params.end_epoch,
+                params.start_epoch
+            ));
+        }
+        if params.end_epoch.saturating_sub(params.start_epoch) >= POWER_HISTORY_LENGTH as i64 {
+            return Err(actor_error!(
+                illegal_argument,
+                "epoch range {}..={} exceeds maximum of {} epochs",
+                params.start_epoch,
+                params.end_epoch,
+                POWER_HISTORY_LENGTH
+            ));
+        }
+
+        let st: State = rt.state()?;
+        let entries = st.power_history_in(rt.store(), params.start_epoch, params.end_epoch)?;
+        Ok(HistoricalPowerReturn { entries })
+    }
+
+    /// Returns the progress of the FIP-0081 pledge ramp at the current epoch,
+    /// computed as by the miner actor's initial pledge calculation.
+    fn pledge_ramp_status(rt: &impl Runtime) -> Result<PledgeRampStatusReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        Ok(PledgeRampStatusReturn {
+            ramp_start_epoch: st.ramp_start_epoch,
+            ramp_duration_epochs: st.ramp_duration_epochs,
+            ramp_multiplier: pledge_ramp_gamma(
+                rt.curr_epoch() - st.ramp_start_epoch,
+                st.ramp_duration_epochs,
+            ),
+        })
+    }
+
+    /// Suspends a miner, removing its claimed power from the network's consensus power.
+    /// The miner's claim is retained and continues to track its sectors,
+    /// and its cron callbacks continue to be invoked. Holding them would also suspend the
+    /// miner's deadline processing, sparing it the penalties for faults and missed proofs.
+    /// May only be invoked by governance, as an emergency measure against a malicious miner.
+    fn suspend_miner(rt: &impl Runtime, params: SuspendMinerParams) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
+        let miner_addr = Address::new_id(params.miner);
+
+        rt.transaction(|st: &mut State, rt| {
+            st.suspend_miner(rt.policy(), &W::default(), rt.store(), &miner_addr, rt.curr_epoch())
+        })?;
+        emit::miner_suspended(rt, params.miner)
+    }
+
+    /// Reinstates a suspended miner, restoring its claimed power to the network's consensus power.
+    /// May only be invoked by governance.
+    fn reinstate_miner(rt: &impl Runtime, params: ReinstateMinerParams) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
+        let miner_addr = Address::new_id(params.miner);
+
+        rt.transaction(|st: &mut State, rt| {
+            st.reinstate_miner(rt.policy(), &W::default(), rt.store(), &miner_addr)
+        })?;
+        emit::miner_reinstated(rt, params.miner)
+    }
+
+    /// Invokes the callbacks of events enrolled in the cron queue up to the current epoch.
+    /// The events enrolled by payment channels up to the current epoch are then processed,
+    /// at most the policy's max_cron_events_per_tick of them, with the remainder carried over
+    /// to following ticks in order of epoch and then of enrollment.
     fn process_deferred_cron_events(
         rt: &impl Runtime,
         rewret: ThisEpochRewardReturn,
     ) -> Result<(), ActorError> {
         let rt_epoch = rt.curr_epoch();
+        let max_events = rt.policy().max_cron_events_per_tick;
         let mut cron_events = Vec::new();
         let st: State = rt.state()?;
         rt.transaction(|st: &mut State, rt| {
@@ -359,6 +598,7 @@ impl Actor {
             })?;
 
             let claims = st.load_claims(rt.store())?;
+            let mut keys = st.load_cron_event_keys(rt.store())?;
             for epoch in st.first_cron_epoch..=rt_epoch {
                 let epoch_events = load_cron_events(&events, epoch).map_err(|e| {
                     e.downcast_default(
@@ -370,14 +610,17 @@ impl Actor {
                 if epoch_events.is_empty() {
                     continue;
                 }
+                // The count may trail the queue for events enrolled before it was tracked.
+                st.cron_event_count = st.cron_event_count.saturating_sub(epoch_events.len() as u64);
 
                 for evt in epoch_events.into_iter() {
+                    keys.delete(&cron_event_key(epoch, &evt)?)?;
                     let miner_has_claim = claims.contains_key(&evt.miner_addr)?;
-                    if !miner_has_claim {
+                    if miner_has_claim {
+                        cron_events.push((evt, true));
+                    } else {
                         debug!("skipping cron event for unknown miner: {}", evt.miner_addr);
-                        continue;
                     }
-                    cron_events.push(evt);
                 }
 
                 events.remove_all(&epoch_key(epoch)).map_err(|e| {
@@ -392,12 +635,23 @@ impl Actor {
             st.cron_event_queue = events.root().map_err(|e| {
                 e.downcast_default(ExitCode::USR_ILLEGAL_STATE, "failed to flush events")
             })?;
+            st.cron_event_keys = keys.flush()?;
+
+            // Payment channels' events follow the miners'. A channel collected since enrolling
+            // is not called back.
+            for evt in st.take_channel_cron_events(rt.store(), rt_epoch, max_events)? {
+                if is_payment_channel(rt, &evt.miner_addr) {
+                    cron_events.push((evt, false));
+                } else {
+                    debug!("skipping cron event for collected payment channel: {}", evt.miner_addr);
+                }
+            }
 
             Ok(())
         })?;
 
         let mut failed_miner_crons = Vec::new();
-        for event in cron_events {
+        for (event, is_miner) in cron_events {
             let params = IpldBlock::serialize_cbor(&ext::miner::DeferredCronEventParams {
                 event_payload: event.callback_payload.bytes().to_owned(),
                 reward_smoothed: rewret.this_epoch_reward_smoothed.clone(),
@@ -414,18 +668,24 @@ impl Actor {
             // Failures are unexpected here but will result in removal of miner power
             // A log message would really help here.
             if let Err(e) = res {
-                error!("OnDeferredCronEvent failed for miner {}: res {}", event.miner_addr, e);
-                failed_miner_crons.push(event.miner_addr)
+                error!("OnDeferredCronEvent failed for {}: res {}", event.miner_addr, e);
+                // A payment channel has no power to remove.
+                if is_miner {
+                    failed_miner_crons.push(event.miner_addr)
+                }
             }
         }
 
         if !failed_miner_crons.is_empty() {
+            let mut deleted = Vec::new();
             rt.transaction(|st: &mut State, rt| {
                 let mut claims = st.load_claims(rt.store())?;
 
                 // Remove power and leave miner frozen
                 for miner_addr in failed_miner_crons {
-                    if let Err(e) = st.delete_claim(rt.policy(), &mut claims, &miner_addr) {
+                    if let Err(e) =
+                        st.delete_claim(rt.policy(), &W::default(), &mut claims, &miner_addr)
+                    {
                         error!(
                             "failed to delete claim for miner {} after\
                             failing on deferred cron event: {}",
@@ -433,17 +693,31 @@ impl Actor {
                         );
                         continue;
                     }
-                    st.miner_count -= 1
+                    st.miner_count -= 1;
+                    deleted.push(miner_addr);
                 }
                 st.save_claims(&mut claims)?;
                 Ok(())
             })?;
+            for miner_addr in deleted {
+                emit::claim_deleted(rt, miner_addr.id().unwrap())?;
+            }
         }
         Ok(())
     }
 }
 
-impl ActorCode for Actor {
+/// Whether an address is that of a payment channel actor, which may enroll cron events
+/// (to collect the channel once settled) without holding a claim.
+fn is_payment_channel(rt: &impl Runtime, addr: &Address) -> bool {
+    addr.id()
+        .ok()
+        .and_then(|id| rt.get_actor_code_cid(&id))
+        .and_then(|code| rt.resolve_builtin_actor_type(&code))
+        == Some(Type::PaymentChannel)
+}
+
+impl<W: ConsensusWeight + Default> ActorCode for Actor<W> {
     type Methods = Method;
 
     fn name() -> &'static str {
@@ -459,9 +733,20 @@ impl ActorCode for Actor {
         UpdatePledgeTotal => update_pledge_total,
         CurrentTotalPower => current_total_power,
         NetworkRawPowerExported => network_raw_power,
+        NetworkQAPowerExported => network_qa_power,
+        NetworkPledgeExported => network_pledge,
         MinerRawPowerExported => miner_raw_power,
         MinerCountExported => miner_count,
         MinerConsensusCountExported => miner_consensus_count,
         MinerPowerExported => miner_power,
+        TotalPowerAtExported => total_power_at,
+        PledgeRampStatusExported => pledge_ramp_status,
+        ListMinerClaimsExported => list_miner_claims,
+        MinerPowersExported => miner_powers,
+        GetCronEventsExported => get_cron_events,
+        MinerPledgeExported => miner_pledge,
+        HistoricalPowerExported => historical_power,
+        SuspendMiner => suspend_miner,
+        ReinstateMiner => reinstate_miner,
     }
 }
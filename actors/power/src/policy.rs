@@ -11,3 +11,14 @@ pub const CONSENSUS_MINER_MIN_MINERS: i64 = 4;
 ///
 /// To support onboarding 1EiB/year, we need to allow at least 32 prove commits per epoch.
 pub const MAX_MINER_PROVE_COMMITS_PER_EPOCH: u64 = 200;
+
+/// Number of most recent epochs for which network power totals are retained for historical
+/// queries. This covers one day of epochs, comfortably more than the finality lookback.
+pub const POWER_HISTORY_LENGTH: u64 = 2880;
+
+/// Maximum number of claims returned by a single call to list miners' claims.
+pub const LIST_MINER_CLAIMS_MAX_PAGE_SIZE: u64 = 1000;
+
+/// Maximum number of epochs in the range of a single query of the cron event queue.
+/// This covers a full proving period, in which a miner enrolls a callback for each deadline.
+pub const GET_CRON_EVENTS_MAX_EPOCH_RANGE: i64 = 2880;
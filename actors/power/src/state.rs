@@ -18,18 +18,19 @@ use fvm_shared::error::ExitCode;
 use fvm_shared::sector::{RegisteredPoStProof, StoragePower};
 use integer_encoding::VarInt;
 use lazy_static::lazy_static;
-use num_traits::Signed;
+use num_traits::{Signed, Zero};
 
 use fil_actors_runtime::builtin::reward::smooth::{
     AlphaBetaFilter, DEFAULT_ALPHA, DEFAULT_BETA, FilterEstimate,
 };
+use fil_actors_runtime::cbor::serialize_vec;
 use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::{
-    ActorContext, ActorDowncast, ActorError, AsActorError, Config, DEFAULT_HAMT_CONFIG, Map2,
-    Multimap, actor_error,
+    ActorContext, ActorError, Array, AsActorError, Config, DEFAULT_HAMT_CONFIG, Map2, Multimap,
+    Set, actor_error,
 };
 
-use super::CONSENSUS_MINER_MIN_MINERS;
+use super::{CONSENSUS_MINER_MIN_MINERS, ConsensusWeight, POWER_HISTORY_LENGTH};
 
 lazy_static! {
     /// genesis power in bytes = 750,000 GiB
@@ -40,10 +41,19 @@ lazy_static! {
 
 pub const CRON_QUEUE_HAMT_BITWIDTH: u32 = 6;
 pub const CRON_QUEUE_AMT_BITWIDTH: u32 = 6;
+pub const POWER_HISTORY_AMT_BITWIDTH: u32 = 5;
 
 pub type ClaimsMap<BS> = Map2<BS, Address, Claim>;
 pub const CLAIMS_CONFIG: Config = DEFAULT_HAMT_CONFIG;
 
+pub type SuspendedMinersMap<BS> = Map2<BS, Address, SuspendedMiner>;
+
+pub type MinerPledgesMap<BS> = Map2<BS, Address, TokenAmount>;
+
+pub type ChannelCronEventsMap<BS> = Map2<BS, Address, ChainEpoch>;
+
+pub type CronEventKeysSet<BS> = Set<BS, Vec<u8>>;
+
 /// Storage power actor state
 #[derive(Default, Serialize_tuple, Deserialize_tuple, Clone, Debug)]
 pub struct State {
@@ -89,17 +99,66 @@ pub struct State {
 
     // Deprecated as of FIP 0084
     pub proof_validation_batch: Option<Cid>,
+
+    /// Network totals recorded at the end of each of the most recent epochs,
+    /// indexed by epoch modulo POWER_HISTORY_LENGTH.
+    pub power_history: Cid, // AMT[ChainEpoch % POWER_HISTORY_LENGTH]PowerHistoryEntry
+
+    /// Number of events in the cron event queue.
+    pub cron_event_count: u64,
+
+    /// Miners suspended by governance, whose claims contribute no power to the network totals.
+    pub suspended_miners: Cid, // Map, HAMT[address]SuspendedMiner
+
+    /// Pledge collateral locked by each miner, as reported by the miner's pledge updates.
+    /// Miners with no pledge locked have no entry.
+    pub miner_pledges: Cid, // Map, HAMT[address]TokenAmount
+
+    /// Epoch of the cron event enrolled by each payment channel with one pending.
+    /// A payment channel may have at most one cron event pending.
+    pub channel_cron_events: Cid, // Map, HAMT[address]ChainEpoch
+
+    /// A queue of events enrolled by payment channels, indexed by epoch.
+    /// These are kept apart from the miners' events, which they cannot delay.
+    pub channel_cron_queue: Cid, // Multimap, (HAMT[ChainEpoch]AMT[CronEvent]
+
+    /// First epoch of the payment channel queue yet to be processed by cron.
+    /// If cron stops short of the current epoch because it reached the limit of events it may
+    /// process in a tick, this is the epoch from which it resumes.
+    pub first_channel_cron_epoch: ChainEpoch,
+
+    /// Keys of the distinct events in the cron event queue, by epoch, miner and payload.
+    /// Enrolling an event identical to one already queued is a no-op.
+    pub cron_event_keys: Cid, // Set, HAMT[cron_event_key]
 }
 
 impl State {
     pub fn new<BS: Blockstore>(store: &BS) -> anyhow::Result<State> {
         let empty_claims = ClaimsMap::empty(store, CLAIMS_CONFIG, "empty").flush()?;
+        let empty_suspended = SuspendedMinersMap::empty(store, CLAIMS_CONFIG, "empty").flush()?;
+        let empty_pledges = MinerPledgesMap::empty(store, CLAIMS_CONFIG, "empty").flush()?;
+        let empty_channel_events =
+            ChannelCronEventsMap::empty(store, CLAIMS_CONFIG, "empty").flush()?;
+        let empty_event_keys = CronEventKeysSet::empty(store, CLAIMS_CONFIG, "empty").flush()?;
         let empty_mmap = Multimap::new(store, CRON_QUEUE_HAMT_BITWIDTH, CRON_QUEUE_AMT_BITWIDTH)
             .root()
             .context_code(ExitCode::USR_ILLEGAL_STATE, "Failed to get empty multimap cid")?;
+        let empty_history =
+            Array::<PowerHistoryEntry, BS>::new_with_bit_width(store, POWER_HISTORY_AMT_BITWIDTH)
+                .flush()
+                .context_code(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    "Failed to create empty power history",
+                )?;
         Ok(State {
             cron_event_queue: empty_mmap,
+            channel_cron_queue: empty_mmap,
             claims: empty_claims,
+            power_history: empty_history,
+            suspended_miners: empty_suspended,
+            miner_pledges: empty_pledges,
+            channel_cron_events: empty_channel_events,
+            cron_event_keys: empty_event_keys,
             this_epoch_qa_power_smoothed: FilterEstimate::new(
                 INITIAL_QA_POWER_ESTIMATE_POSITION.clone(),
                 INITIAL_QA_POWER_ESTIMATE_VELOCITY.clone(),
@@ -126,6 +185,10 @@ impl State {
         })?;
 
         let miner_nominal_power = claim.raw_byte_power.clone();
+        if self.is_suspended(s, a)? {
+            // A suspended miner has no consensus power, whatever its claim.
+            return Ok((miner_nominal_power, false));
+        }
         let miner_min_power = consensus_miner_min_power(policy, claim.window_post_proof_type)
             .context_code(
                 ExitCode::USR_ILLEGAL_STATE,
@@ -156,6 +219,7 @@ impl State {
     pub(super) fn add_to_claim<BS: Blockstore>(
         &mut self,
         policy: &Policy,
+        weight: &dyn ConsensusWeight,
         claims: &mut ClaimsMap<BS>,
         miner: &Address,
         power: &StoragePower,
@@ -163,10 +227,8 @@ impl State {
     ) -> Result<(), ActorError> {
         let old_claim = claims
             .get(miner)?
-            .ok_or_else(|| actor_error!(not_found, "no claim for actor {}", miner))?;
-
-        self.total_qa_bytes_committed += qa_power;
-        self.total_bytes_committed += power;
+            .ok_or_else(|| actor_error!(not_found, "no claim for actor {}", miner))?
+            .clone();
 
         let new_claim = Claim {
             raw_byte_power: old_claim.raw_byte_power.clone() + power,
@@ -174,6 +236,51 @@ impl State {
             window_post_proof_type: old_claim.window_post_proof_type,
         };
 
+        // A suspended miner's claim is updated and counts as committed power, but contributes
+        // nothing to consensus power until the miner is reinstated.
+        self.update_committed_totals(&old_claim, &new_claim);
+        if !self.is_suspended(claims.store(), miner)? {
+            self.update_consensus_totals(policy, weight, &old_claim, &new_claim)?;
+        }
+
+        if new_claim.raw_byte_power.is_negative() {
+            return Err(actor_error!(
+                illegal_state,
+                "negative claimed raw byte power: {}",
+                new_claim.raw_byte_power
+            ));
+        }
+        if new_claim.quality_adj_power.is_negative() {
+            return Err(actor_error!(
+                illegal_state,
+                "negative claimed quality adjusted power: {}",
+                new_claim.quality_adj_power
+            ));
+        }
+        set_claim(claims, miner, new_claim)
+    }
+
+    /// Updates the network's committed power totals for a change in a miner's claim.
+    fn update_committed_totals(&mut self, old_claim: &Claim, new_claim: &Claim) {
+        self.total_qa_bytes_committed +=
+            &new_claim.quality_adj_power - &old_claim.quality_adj_power;
+        self.total_bytes_committed += &new_claim.raw_byte_power - &old_claim.raw_byte_power;
+    }
+
+    /// Updates the network's consensus power totals for a change in a miner's claim.
+    fn update_consensus_totals(
+        &mut self,
+        policy: &Policy,
+        weight: &dyn ConsensusWeight,
+        old_claim: &Claim,
+        new_claim: &Claim,
+    ) -> Result<(), ActorError> {
+        let power = &new_claim.raw_byte_power - &old_claim.raw_byte_power;
+
+        // The totals of claims meeting the minimum count their weight in consensus.
+        let old_weight = weight.weight(old_claim);
+        let new_weight = weight.weight(new_claim);
+
         let min_power: StoragePower =
             consensus_miner_min_power(policy, old_claim.window_post_proof_type)
                 .exit_code(ExitCode::USR_ILLEGAL_STATE)?;
@@ -183,14 +290,14 @@ impl State {
         if prev_below && !still_below {
             // Just passed min miner size
             self.miner_above_min_power_count += 1;
-            self.total_quality_adj_power += &new_claim.quality_adj_power;
+            self.total_quality_adj_power += new_weight;
             self.total_raw_byte_power += &new_claim.raw_byte_power;
         } else if !prev_below && still_below {
             // just went below min miner size
             self.miner_above_min_power_count -= 1;
             self.total_quality_adj_power = self
                 .total_quality_adj_power
-                .checked_sub(&old_claim.quality_adj_power)
+                .checked_sub(&old_weight)
                 .expect("Negative nominal power");
             self.total_raw_byte_power = self
                 .total_raw_byte_power
@@ -198,24 +305,10 @@ impl State {
                 .expect("Negative raw byte power");
         } else if !prev_below && !still_below {
             // Was above the threshold, still above
-            self.total_quality_adj_power += qa_power;
+            self.total_quality_adj_power += new_weight - old_weight;
             self.total_raw_byte_power += power;
         }
 
-        if new_claim.raw_byte_power.is_negative() {
-            return Err(actor_error!(
-                illegal_state,
-                "negative claimed raw byte power: {}",
-                new_claim.raw_byte_power
-            ));
-        }
-        if new_claim.quality_adj_power.is_negative() {
-            return Err(actor_error!(
-                illegal_state,
-                "negative claimed quality adjusted power: {}",
-                new_claim.quality_adj_power
-            ));
-        }
         if self.miner_above_min_power_count < 0 {
             return Err(actor_error!(
                 illegal_state,
@@ -223,8 +316,70 @@ impl State {
                 self.miner_above_min_power_count
             ));
         }
+        Ok(())
+    }
 
-        set_claim(claims, miner, new_claim)
+    pub fn load_suspended_miners<BS: Blockstore>(
+        &self,
+        s: BS,
+    ) -> Result<SuspendedMinersMap<BS>, ActorError> {
+        SuspendedMinersMap::load(s, &self.suspended_miners, CLAIMS_CONFIG, "suspended miners")
+    }
+
+    /// Whether a miner is suspended by governance.
+    pub fn is_suspended<BS: Blockstore>(
+        &self,
+        s: &BS,
+        miner: &Address,
+    ) -> Result<bool, ActorError> {
+        self.load_suspended_miners(s)?.contains_key(miner)
+    }
+
+    /// Suspends a miner, removing its claimed power from the network's consensus power totals.
+    /// The claim itself is retained, and continues to be updated and to count as committed power
+    /// while the miner is suspended.
+    pub(super) fn suspend_miner<BS: Blockstore>(
+        &mut self,
+        policy: &Policy,
+        weight: &dyn ConsensusWeight,
+        store: &BS,
+        miner: &Address,
+        epoch: ChainEpoch,
+    ) -> Result<(), ActorError> {
+        let claim = self
+            .get_claim(store, miner)
+            .exit_code(ExitCode::USR_ILLEGAL_STATE)?
+            .ok_or_else(|| actor_error!(not_found, "no claim for actor {}", miner))?;
+        let mut suspended = self.load_suspended_miners(store)?;
+        if suspended.contains_key(miner)? {
+            return Err(actor_error!(illegal_argument, "miner {} is already suspended", miner));
+        }
+        self.update_consensus_totals(policy, weight, &claim, &claim.zeroed())?;
+        suspended.set(miner, SuspendedMiner { epoch })?;
+        self.suspended_miners = suspended.flush()?;
+        Ok(())
+    }
+
+    /// Reinstates a suspended miner, restoring its claimed power to the network's consensus
+    /// power totals.
+    pub(super) fn reinstate_miner<BS: Blockstore>(
+        &mut self,
+        policy: &Policy,
+        weight: &dyn ConsensusWeight,
+        store: &BS,
+        miner: &Address,
+    ) -> Result<(), ActorError> {
+        let mut suspended = self.load_suspended_miners(store)?;
+        suspended
+            .delete(miner)?
+            .ok_or_else(|| actor_error!(illegal_argument, "miner {} is not suspended", miner))?;
+        let claim = self
+            .get_claim(store, miner)
+            .exit_code(ExitCode::USR_ILLEGAL_STATE)?
+            .ok_or_else(|| actor_error!(illegal_state, "no claim for suspended actor {}", miner))?;
+        self.update_consensus_totals(policy, weight, &claim.zeroed(), &claim)?;
+        self.suspended_miners = suspended.flush()?;
+        Ok(())
     }
 
     pub fn load_claims<BS: Blockstore>(&self, s: BS) -> Result<ClaimsMap<BS>, ActorError> {
@@ -243,19 +398,217 @@ impl State {
         self.total_pledge_collateral += amount;
     }
 
+    pub fn load_miner_pledges<BS: Blockstore>(
+        &self,
+        s: BS,
+    ) -> Result<MinerPledgesMap<BS>, ActorError> {
+        MinerPledgesMap::load(s, &self.miner_pledges, CLAIMS_CONFIG, "miner pledges")
+    }
+
+    /// Returns the pledge collateral locked by a miner.
+    pub fn miner_pledge<BS: Blockstore>(
+        &self,
+        s: &BS,
+        miner: &Address,
+    ) -> Result<TokenAmount, ActorError> {
+        let pledges = self.load_miner_pledges(s)?;
+        Ok(pledges.get(miner)?.cloned().unwrap_or_default())
+    }
+
+    /// Adds to the pledge collateral locked by a miner.
+    /// The result is clamped at zero, since pledge locked before the miner's pledge was
+    /// tracked may be released without having been recorded.
+    pub(super) fn add_miner_pledge<BS: Blockstore>(
+        &mut self,
+        s: &BS,
+        miner: &Address,
+        amount: &TokenAmount,
+    ) -> Result<(), ActorError> {
+        let mut pledges = self.load_miner_pledges(s)?;
+        let pledge = pledges.get(miner)?.cloned().unwrap_or_default() + amount;
+        if !pledge.is_positive() {
+            pledges.delete(miner)?;
+        } else {
+            pledges.set(miner, pledge)?;
+        }
+        self.miner_pledges = pledges.flush()?;
+        Ok(())
+    }
+
+    pub fn load_channel_cron_events<BS: Blockstore>(
+        &self,
+        s: BS,
+    ) -> Result<ChannelCronEventsMap<BS>, ActorError> {
+        ChannelCronEventsMap::load(s, &self.channel_cron_events, CLAIMS_CONFIG, "channel events")
+    }
+
+    fn load_channel_cron_queue<'bs, BS: Blockstore>(
+        &self,
+        s: &'bs BS,
+    ) -> Result<Multimap<'bs, BS>, ActorError> {
+        Multimap::from_root(
+            s,
+            &self.channel_cron_queue,
+            CRON_QUEUE_HAMT_BITWIDTH,
+            CRON_QUEUE_AMT_BITWIDTH,
+        )
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load channel cron events")
+    }
+
+    /// Queues a payment channel's cron event, failing if it already has one pending.
+    /// An event for an epoch already processed is queued at the next epoch to be processed.
+    pub(super) fn add_channel_cron_event<BS: Blockstore>(
+        &mut self,
+        s: &BS,
+        epoch: ChainEpoch,
+        event: CronEvent,
+    ) -> Result<(), ActorError> {
+        let channel = event.miner_addr;
+        let mut index = self.load_channel_cron_events(s)?;
+        if let Some(pending) = index.get(&channel)? {
+            return Err(actor_error!(
+                forbidden,
+                "payment channel {} already has a cron event pending at {}",
+                channel,
+                pending
+            ));
+        }
+        let epoch = epoch.max(self.first_channel_cron_epoch);
+        index.set(&channel, epoch)?;
+        self.channel_cron_events = index.flush()?;
+
+        let mut queue = self.load_channel_cron_queue(s)?;
+        queue.add(epoch_key(epoch), event).context_code(
+            ExitCode::USR_ILLEGAL_STATE,
+            format!("failed to store channel cron event at epoch {}", epoch),
+        )?;
+        self.channel_cron_queue = queue
+            .root()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush channel cron events")?;
+        Ok(())
+    }
+
+    /// Removes and returns at most `max` of the payment channels' events queued up to and
+    /// including `epoch`, in order of epoch and then of enrollment. Processing resumes from
+    /// the events remaining at the next call.
+    /// Each channel may enroll another event once its pending one is taken, whether or not
+    /// the channel still exists to be called back.
+    pub(super) fn take_channel_cron_events<BS: Blockstore>(
+        &mut self,
+        s: &BS,
+        epoch: ChainEpoch,
+        max: u64,
+    ) -> Result<Vec<CronEvent>, ActorError> {
+        let mut queue = self.load_channel_cron_queue(s)?;
+        let mut taken = Vec::new();
+        let mut resume_epoch = self.first_channel_cron_epoch.max(epoch + 1);
+        for e in self.first_channel_cron_epoch..=epoch {
+            if taken.len() as u64 >= max {
+                resume_epoch = e;
+                break;
+            }
+            let mut events = load_cron_events(&queue, e).context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                format!("failed to load channel cron events at {}", e),
+            )?;
+            if events.is_empty() {
+                continue;
+            }
+            let carried = events.split_off(events.len().min((max - taken.len() as u64) as usize));
+            queue.remove_all(&epoch_key(e)).context_code(
+                ExitCode::USR_ILLEGAL_STATE,
+                format!("failed to clear channel cron events at {}", e),
+            )?;
+            taken.extend(events);
+            if !carried.is_empty() {
+                for event in carried {
+                    queue.add(epoch_key(e), event).context_code(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        format!("failed to carry over channel cron events at {}", e),
+                    )?;
+                }
+                resume_epoch = e;
+                break;
+            }
+        }
+        if !taken.is_empty() {
+            let mut index = self.load_channel_cron_events(s)?;
+            for event in &taken {
+                index.delete(&event.miner_addr)?;
+            }
+            self.channel_cron_events = index.flush()?;
+            self.channel_cron_queue = queue
+                .root()
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush channel cron events")?;
+        }
+        self.first_channel_cron_epoch = resume_epoch;
+        Ok(taken)
+    }
+
+    pub fn load_cron_event_keys<BS: Blockstore>(
+        &self,
+        s: BS,
+    ) -> Result<CronEventKeysSet<BS>, ActorError> {
+        CronEventKeysSet::load(s, &self.cron_event_keys, CLAIMS_CONFIG, "cron event keys")
+    }
+
+    /// Appends an event to the cron queue at an epoch, unless an identical event
+    /// (same miner and payload) is already queued at that epoch.
+    /// Returns whether the event was appended.
     pub(super) fn append_cron_event<BS: Blockstore>(
         &mut self,
+        s: &BS,
         events: &mut Multimap<BS>,
         epoch: ChainEpoch,
         event: CronEvent,
-    ) -> anyhow::Result<()> {
+    ) -> Result<bool, ActorError> {
+        let mut keys = self.load_cron_event_keys(s)?;
+        if keys.put(&cron_event_key(epoch, &event)?)?.is_some() {
+            return Ok(false);
+        }
+        self.cron_event_keys = keys.flush()?;
+
         if epoch < self.first_cron_epoch {
             self.first_cron_epoch = epoch;
         }
 
-        events.add(epoch_key(epoch), event).map_err(|e| {
-            e.downcast_wrap(format!("failed to store cron event at epoch {}", epoch))
-        })?;
+        events.add(epoch_key(epoch), event).context_code(
+            ExitCode::USR_ILLEGAL_STATE,
+            format!("failed to store cron event at epoch {}", epoch),
+        )?;
+        self.cron_event_count += 1;
+        Ok(true)
+    }
+
+    /// Counts and indexes the events in the cron queue, replacing the recorded count and keys.
+    /// This migrates state written before these were maintained.
+    pub fn rebuild_cron_event_index<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+    ) -> Result<(), ActorError> {
+        let events = Multimap::from_root(
+            store,
+            &self.cron_event_queue,
+            CRON_QUEUE_HAMT_BITWIDTH,
+            CRON_QUEUE_AMT_BITWIDTH,
+        )
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load cron events")?;
+        let mut count = 0;
+        let mut keys = CronEventKeysSet::empty(store, CLAIMS_CONFIG, "cron event keys");
+        events
+            .for_all::<_, CronEvent>(|key, epoch_events| {
+                let (epoch, _) = ChainEpoch::decode_var(key)
+                    .ok_or_else(|| anyhow!("invalid cron event epoch key {:?}", key))?;
+                epoch_events.for_each(|_, event| {
+                    count += 1;
+                    keys.put(&cron_event_key(epoch, event)?)?;
+                    Ok(())
+                })?;
+                Ok(())
+            })
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to index cron events")?;
+        self.cron_event_count = count;
+        self.cron_event_keys = keys.flush()?;
         Ok(())
     }
 
@@ -267,6 +620,72 @@ impl State {
         }
     }
 
+    /// Records this epoch's frozen network totals in the power history,
+    /// overwriting the entry from POWER_HISTORY_LENGTH epochs earlier.
+    pub(super) fn record_power_history<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        epoch: ChainEpoch,
+    ) -> Result<(), ActorError> {
+        let mut history = Array::<PowerHistoryEntry, BS>::load(&self.power_history, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load power history")?;
+        history
+            .set(
+                epoch as u64 % POWER_HISTORY_LENGTH,
+                PowerHistoryEntry {
+                    epoch,
+                    raw_byte_power: self.this_epoch_raw_byte_power.clone(),
+                    quality_adj_power: self.this_epoch_quality_adj_power.clone(),
+                    pledge_collateral: self.this_epoch_pledge_collateral.clone(),
+                },
+            )
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to record power history")?;
+        self.power_history = history
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush power history")?;
+        Ok(())
+    }
+
+    /// Returns the network totals recorded at the end of the given epoch,
+    /// or None if no totals were recorded for that epoch or they have since been overwritten.
+    pub fn power_history_at<BS: Blockstore>(
+        &self,
+        store: &BS,
+        epoch: ChainEpoch,
+    ) -> Result<Option<PowerHistoryEntry>, ActorError> {
+        if epoch < 0 {
+            return Ok(None);
+        }
+        let history = Array::<PowerHistoryEntry, BS>::load(&self.power_history, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load power history")?;
+        let entry = history
+            .get(epoch as u64 % POWER_HISTORY_LENGTH)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load power history entry")?;
+        Ok(entry.filter(|e| e.epoch == epoch).cloned())
+    }
+
+    /// Returns the network totals recorded at the end of each epoch in the inclusive range,
+    /// in epoch order, omitting epochs for which no totals are retained.
+    pub fn power_history_in<BS: Blockstore>(
+        &self,
+        store: &BS,
+        start: ChainEpoch,
+        end: ChainEpoch,
+    ) -> Result<Vec<PowerHistoryEntry>, ActorError> {
+        let history = Array::<PowerHistoryEntry, BS>::load(&self.power_history, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load power history")?;
+        let mut entries = Vec::new();
+        for epoch in start.max(0)..=end {
+            let entry = history
+                .get(epoch as u64 % POWER_HISTORY_LENGTH)
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load power history entry")?;
+            if let Some(entry) = entry.filter(|e| e.epoch == epoch) {
+                entries.push(entry.clone());
+            }
+        }
+        Ok(entries)
+    }
+
     pub(super) fn update_smoothed_estimate(&mut self, delta: ChainEpoch) {
         let filter_qa_power = AlphaBetaFilter::load(
             &self.this_epoch_qa_power_smoothed,
@@ -322,9 +741,64 @@ impl State {
         Ok(claim.cloned())
     }
 
+    /// Lists miners' claims in the order of the claims HAMT, starting from a miner (inclusive),
+    /// or from the beginning if none is given.
+    /// Returns at most `limit` claims, and the miner from which to resume listing if more remain.
+    pub fn list_claims<BS: Blockstore>(
+        &self,
+        store: &BS,
+        start: Option<ActorID>,
+        limit: usize,
+    ) -> Result<(Vec<(ActorID, Claim)>, Option<ActorID>), ActorError> {
+        let claims = self.load_claims(store)?;
+        let mut listed = Vec::new();
+        let next =
+            claims.for_each_from(start.map(Address::new_id).as_ref(), limit, |miner, claim| {
+                let id =
+                    miner.id().context_code(ExitCode::USR_ILLEGAL_STATE, "non-ID claim key")?;
+                listed.push((id, claim.clone()));
+                Ok(())
+            })?;
+        let next = next
+            .map(|miner| miner.id().context_code(ExitCode::USR_ILLEGAL_STATE, "non-ID claim key"))
+            .transpose()?;
+        Ok((listed, next))
+    }
+
+    /// Returns the events enrolled in the cron queue at each epoch in a range (inclusive),
+    /// in order of epoch and then of enrollment.
+    pub fn cron_events_in<BS: Blockstore>(
+        &self,
+        store: &BS,
+        start: ChainEpoch,
+        end: ChainEpoch,
+    ) -> Result<Vec<(ChainEpoch, CronEvent)>, ActorError> {
+        let events = Multimap::from_root(
+            store,
+            &self.cron_event_queue,
+            CRON_QUEUE_HAMT_BITWIDTH,
+            CRON_QUEUE_AMT_BITWIDTH,
+        )
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load cron events")?;
+
+        let mut listed = Vec::new();
+        events
+            .for_each_in(
+                start..=end,
+                |epoch| epoch_key(*epoch),
+                |epoch, _, event: &CronEvent| {
+                    listed.push((*epoch, event.clone()));
+                    Ok(())
+                },
+            )
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to iterate cron events")?;
+        Ok(listed)
+    }
+
     pub(super) fn delete_claim<BS: Blockstore>(
         &mut self,
         policy: &Policy,
+        weight: &dyn ConsensusWeight,
         claims: &mut ClaimsMap<BS>,
         miner: &Address,
     ) -> anyhow::Result<()> {
@@ -336,11 +810,20 @@ impl State {
         };
 
         // Subtract from stats to remove power
-        self.add_to_claim(policy, claims, miner, &rbp.neg(), &qap.neg())
+        self.add_to_claim(policy, weight, claims, miner, &rbp.neg(), &qap.neg())
             .context("subtract miner power before deleting claim")?;
         claims
             .delete(miner)?
             .ok_or_else(|| anyhow!("failed to delete claim for {miner}: doesn't exist"))?;
+
+        let mut suspended = self.load_suspended_miners(claims.store())?;
+        if suspended.delete(miner)?.is_some() {
+            self.suspended_miners = suspended.flush()?;
+        }
+        let mut pledges = self.load_miner_pledges(claims.store())?;
+        if pledges.delete(miner)?.is_some() {
+            self.miner_pledges = pledges.flush()?;
+        }
         Ok(())
     }
 }
@@ -388,6 +871,11 @@ pub fn epoch_key(e: ChainEpoch) -> BytesKey {
     bz.into()
 }
 
+/// Key of a cron event in the set of those queued, distinct for each epoch, miner and payload.
+pub fn cron_event_key(epoch: ChainEpoch, event: &CronEvent) -> Result<Vec<u8>, ActorError> {
+    serialize_vec(&(epoch, &event.miner_addr, &event.callback_payload), "cron event key")
+}
+
 #[derive(Debug, Serialize_tuple, Deserialize_tuple, Clone, PartialEq, Eq)]
 pub struct Claim {
     /// Miner's proof type used to determine minimum miner size
@@ -400,7 +888,36 @@ pub struct Claim {
     pub quality_adj_power: StoragePower,
 }
 
-#[derive(Clone, Debug, Serialize_tuple, Deserialize_tuple)]
+impl Claim {
+    /// A claim of no power, with the same proof type.
+    fn zeroed(&self) -> Claim {
+        Claim {
+            window_post_proof_type: self.window_post_proof_type,
+            raw_byte_power: StoragePower::zero(),
+            quality_adj_power: StoragePower::zero(),
+        }
+    }
+}
+
+/// A miner suspended by governance.
+#[derive(Debug, Serialize_tuple, Deserialize_tuple, Clone, PartialEq, Eq)]
+pub struct SuspendedMiner {
+    /// The epoch at which the miner was suspended.
+    pub epoch: ChainEpoch,
+}
+
+/// Network totals frozen by the cron tick at the end of an epoch.
+#[derive(Debug, Serialize_tuple, Deserialize_tuple, Clone, PartialEq, Eq)]
+pub struct PowerHistoryEntry {
+    pub epoch: ChainEpoch,
+    #[serde(with = "bigint_ser")]
+    pub raw_byte_power: StoragePower,
+    #[serde(with = "bigint_ser")]
+    pub quality_adj_power: StoragePower,
+    pub pledge_collateral: TokenAmount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct CronEvent {
     pub miner_addr: Address,
     pub callback_payload: RawBytes,
@@ -1,15 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::RawBytes;
 use fvm_shared::{address::Address, clock::ChainEpoch, sector::StoragePower};
 use num_traits::{Signed, Zero};
 
-use fil_actors_runtime::{MessageAccumulator, Multimap, parse_uint_key, runtime::Policy};
+use fil_actors_runtime::{MessageAccumulator, Multimap, runtime::Policy};
+use integer_encoding::VarInt;
 
 use crate::{
-    CLAIMS_CONFIG, CRON_QUEUE_AMT_BITWIDTH, CRON_QUEUE_HAMT_BITWIDTH, Claim, ClaimsMap, CronEvent,
-    State, consensus_miner_min_power,
+    CLAIMS_CONFIG, CRON_QUEUE_AMT_BITWIDTH, CRON_QUEUE_HAMT_BITWIDTH, ChannelCronEventsMap, Claim,
+    ClaimsMap, ConsensusWeight, CronEvent, MinerPledgesMap, QualityAdjustedWeight, State,
+    SuspendedMinersMap, consensus_miner_min_power, cron_event_key,
 };
 
 pub struct MinerCronEvent {
@@ -30,6 +32,34 @@ pub fn check_state_invariants<BS: Blockstore>(
     policy: &Policy,
     state: &State,
     store: &BS,
+) -> (StateSummary, MessageAccumulator) {
+    let (summary, acc) =
+        check_state_invariants_with_weight(policy, state, store, &QualityAdjustedWeight);
+
+    // Claims weighing their quality-adjusted power bound the totals of consensus power.
+    acc.require(
+        state.total_raw_byte_power <= state.total_quality_adj_power,
+        format!(
+            "total raw power {} is greater than total quality adjusted power {}",
+            state.total_raw_byte_power, state.total_quality_adj_power
+        ),
+    );
+    acc.require(
+        state.total_quality_adj_power <= state.total_qa_bytes_committed,
+        format!(
+            "total qa power {} is greater than qa power committed {}",
+            state.total_quality_adj_power, state.total_qa_bytes_committed
+        ),
+    );
+    (summary, acc)
+}
+
+/// Checks internal invariants of the state of a power actor weighing claims with `weight`.
+pub fn check_state_invariants_with_weight<BS: Blockstore>(
+    policy: &Policy,
+    state: &State,
+    store: &BS,
+    weight: &dyn ConsensusWeight,
 ) -> (StateSummary, MessageAccumulator) {
     let acc = MessageAccumulator::default();
 
@@ -51,13 +81,6 @@ pub fn check_state_invariants<BS: Blockstore>(
         format!("total qa power committed is negative {}", state.total_qa_bytes_committed),
     );
 
-    acc.require(
-        state.total_raw_byte_power <= state.total_quality_adj_power,
-        format!(
-            "total raw power {} is greater than total quality adjusted power {}",
-            state.total_raw_byte_power, state.total_quality_adj_power
-        ),
-    );
     acc.require(
         state.total_bytes_committed <= state.total_qa_bytes_committed,
         format!(
@@ -72,27 +95,94 @@ pub fn check_state_invariants<BS: Blockstore>(
             state.total_raw_byte_power, state.total_bytes_committed
         ),
     );
-    acc.require(
-        state.total_quality_adj_power <= state.total_qa_bytes_committed,
-        format!(
-            "total qa power {} is greater than qa power committed {}",
-            state.total_quality_adj_power, state.total_qa_bytes_committed
-        ),
-    );
 
     let crons = check_cron_invariants(state, store, &acc);
-    let claims = check_claims_invariants(policy, state, store, &acc);
+    check_channel_cron_invariants(state, store, &crons, &acc);
+    let claims = check_claims_invariants(policy, state, store, weight, &acc);
     check_proofs_invariants(state, &acc);
 
     (StateSummary { crons, claims }, acc)
 }
 
+fn check_channel_cron_invariants<BS: Blockstore>(
+    state: &State,
+    store: &BS,
+    crons: &CronEventsByAddress,
+    acc: &MessageAccumulator,
+) {
+    let mut queued = CronEventsByAddress::new();
+    let mut queued_count = 0;
+    match Multimap::from_root(
+        store,
+        &state.channel_cron_queue,
+        CRON_QUEUE_HAMT_BITWIDTH,
+        CRON_QUEUE_AMT_BITWIDTH,
+    ) {
+        Ok(queue) => {
+            let ret = queue.for_all::<_, CronEvent>(|key, events| {
+                let (epoch, _) = ChainEpoch::decode_var(key)
+                    .ok_or_else(|| anyhow::anyhow!("invalid epoch key {key:?}"))?;
+                acc.require(
+                    epoch >= state.first_channel_cron_epoch,
+                    format!(
+                        "channel cron event at epoch {epoch} before first_channel_cron_epoch {}",
+                        state.first_channel_cron_epoch
+                    ),
+                );
+                events.for_each(|_, event| {
+                    queued_count += 1;
+                    queued
+                        .entry(event.miner_addr)
+                        .or_default()
+                        .push(MinerCronEvent { epoch, payload: event.callback_payload.clone() });
+                    Ok(())
+                })?;
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating channel cron events");
+        }
+        Err(e) => acc.add(format!("error loading channel cron queue: {e}")),
+    }
+
+    for address in queued.keys() {
+        acc.require(
+            !crons.contains_key(address),
+            format!("payment channel {address} has cron events in the miners' queue"),
+        );
+    }
+
+    match ChannelCronEventsMap::load(store, &state.channel_cron_events, CLAIMS_CONFIG, "channels") {
+        Ok(channel_events) => {
+            let mut indexed_count = 0;
+            let ret = channel_events.for_each(|address, epoch| {
+                indexed_count += 1;
+                let events = queued.get(&address).map(Vec::as_slice).unwrap_or_default();
+                acc.require(
+                    events.len() == 1 && events[0].epoch == *epoch,
+                    format!("payment channel {address} has no single cron event queued at {epoch}"),
+                );
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating channel cron event index");
+            acc.require(
+                indexed_count == queued_count,
+                format!(
+                    "{indexed_count} payment channels indexed for {queued_count} queued cron events"
+                ),
+            );
+        }
+        Err(e) => acc.add(format!("error loading channel cron events: {e}")),
+    }
+}
+
 fn check_cron_invariants<BS: Blockstore>(
     state: &State,
     store: &BS,
     acc: &MessageAccumulator,
 ) -> CronEventsByAddress {
     let mut cron_events_by_address = CronEventsByAddress::new();
+    let mut event_count = 0u64;
+    let mut event_keys = HashSet::new();
 
     match Multimap::from_root(
         store,
@@ -102,13 +192,13 @@ fn check_cron_invariants<BS: Blockstore>(
     ) {
         Ok(queue) => {
             let ret = queue.for_all::<_, CronEvent>(|key, events| {
-                let epoch = match parse_uint_key(key) {
-                    Ok(key) => key,
-                    Err(e) => {
-                        acc.add(format!("non-int key in cron array: {e}"));
+                let epoch = match ChainEpoch::decode_var(key) {
+                    Some((epoch, _)) => epoch,
+                    None => {
+                        acc.add(format!("invalid epoch key in cron array: {key:?}"));
                         return Ok(());
                     }
-                } as i64;
+                };
 
                 acc.require(
                     epoch >= state.first_cron_epoch,
@@ -119,6 +209,8 @@ fn check_cron_invariants<BS: Blockstore>(
                 );
                 events
                     .for_each(|_, event| {
+                        event_count += 1;
+                        event_keys.insert(cron_event_key(epoch, event)?);
                         cron_events_by_address.entry(event.miner_addr).or_default().push(
                             MinerCronEvent { epoch, payload: event.callback_payload.clone() },
                         );
@@ -129,16 +221,42 @@ fn check_cron_invariants<BS: Blockstore>(
                     })
             });
             acc.require_no_error(ret, "error iterating cron tasks");
+            acc.require(
+                event_count == state.cron_event_count,
+                format!(
+                    "cron event count {} does not match {event_count} events in queue",
+                    state.cron_event_count
+                ),
+            );
         }
         Err(e) => acc.add(format!("error loading cron event queue: {e}")),
     }
 
+    // Events queued before the keys were maintained may be duplicated, sharing a key.
+    match state.load_cron_event_keys(store) {
+        Ok(keys) => {
+            let mut key_count = 0;
+            let ret = keys.for_each(|key| {
+                key_count += 1;
+                acc.require(event_keys.contains(&key), "cron event key has no event queued");
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating cron event keys");
+            acc.require(
+                key_count == event_keys.len(),
+                format!("{key_count} cron event keys for {} distinct events", event_keys.len()),
+            );
+        }
+        Err(e) => acc.add(format!("error loading cron event keys: {e}")),
+    }
+
     cron_events_by_address
 }
 fn check_claims_invariants<BS: Blockstore>(
     policy: &Policy,
     state: &State,
     store: &BS,
+    weight: &dyn ConsensusWeight,
     acc: &MessageAccumulator,
 ) -> ClaimsByAddress {
     let mut claims_by_address = ClaimsByAddress::new();
@@ -149,6 +267,18 @@ fn check_claims_invariants<BS: Blockstore>(
     let mut qa_power = StoragePower::zero();
     let mut claims_with_sufficient_power_count = 0;
 
+    let mut suspended = HashSet::new();
+    match SuspendedMinersMap::load(store, &state.suspended_miners, CLAIMS_CONFIG, "suspended") {
+        Ok(suspended_miners) => {
+            let ret = suspended_miners.for_each(|address, _| {
+                suspended.insert(address);
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating suspended miners");
+        }
+        Err(e) => acc.add(format!("error loading suspended miners: {e}")),
+    };
+
     match ClaimsMap::load(store, &state.claims, CLAIMS_CONFIG, "claims") {
         Ok(claims) => {
             let ret = claims.for_each(|address, claim| {
@@ -156,6 +286,17 @@ fn check_claims_invariants<BS: Blockstore>(
                 committed_raw_power += &claim.raw_byte_power;
                 committed_qa_power += &claim.quality_adj_power;
 
+                // A suspended miner's claim contributes no consensus power.
+                let claim = &if suspended.contains(&address) {
+                    Claim {
+                        window_post_proof_type: claim.window_post_proof_type,
+                        raw_byte_power: StoragePower::zero(),
+                        quality_adj_power: StoragePower::zero(),
+                    }
+                } else {
+                    claim.clone()
+                };
+
                 let min_power =
                     match consensus_miner_min_power(policy, claim.window_post_proof_type) {
                         Ok(power) => power,
@@ -171,7 +312,7 @@ fn check_claims_invariants<BS: Blockstore>(
                 if claim.raw_byte_power >= min_power {
                     claims_with_sufficient_power_count += 1;
                     raw_power += &claim.raw_byte_power;
-                    qa_power += &claim.quality_adj_power;
+                    qa_power += weight.weight(claim);
                 }
 
                 Ok(())
@@ -181,6 +322,31 @@ fn check_claims_invariants<BS: Blockstore>(
         Err(e) => acc.add(format!("error loading power claims: {e}")),
     };
 
+    for address in &suspended {
+        acc.require(
+            claims_by_address.contains_key(address),
+            format!("suspended miner {address} has no claim"),
+        );
+    }
+
+    match MinerPledgesMap::load(store, &state.miner_pledges, CLAIMS_CONFIG, "pledges") {
+        Ok(pledges) => {
+            let ret = pledges.for_each(|address, pledge| {
+                acc.require(
+                    claims_by_address.contains_key(&address),
+                    format!("miner {address} with pledge has no claim"),
+                );
+                acc.require(
+                    pledge.is_positive(),
+                    format!("miner {address} has non-positive pledge {pledge}"),
+                );
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating miner pledges");
+        }
+        Err(e) => acc.add(format!("error loading miner pledges: {e}")),
+    };
+
     acc.require(committed_raw_power == state.total_bytes_committed, format!("sum of raw power in claims {committed_raw_power} does not match recorded bytes committed {}", state.total_bytes_committed));
     acc.require(committed_qa_power == state.total_qa_bytes_committed, format!("sum of qa power in claims {committed_qa_power} does not match recorded qa power committed {}", state.total_qa_bytes_committed));
     acc.require(claims_with_sufficient_power_count == state.miner_above_min_power_count, format!("claims with sufficient power {claims_with_sufficient_power_count} does not match miner_above_min_power_count {}", state.miner_above_min_power_count));
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use fil_actors_runtime::reward::FilterEstimate;
+use fil_actors_runtime::runtime::policy_constants::MAX_MULTIADDRS;
+use fil_actors_runtime::{BatchReturn, BoundedVec, Cursor};
 use fvm_ipld_encoding::tuple::*;
 use fvm_ipld_encoding::{BytesDe, RawBytes, strict_bytes};
 use fvm_shared::ActorID;
@@ -13,6 +15,8 @@ use fvm_shared::sector::{RegisteredPoStProof, StoragePower};
 
 use serde::{Deserialize, Serialize};
 
+use crate::{Claim, PowerHistoryEntry};
+
 pub type SectorTermination = i64;
 
 /// Implicit termination after all deals expire
@@ -29,7 +33,11 @@ pub struct CreateMinerParams {
     pub window_post_proof_type: RegisteredPoStProof,
     #[serde(with = "strict_bytes")]
     pub peer: Vec<u8>,
-    pub multiaddrs: Vec<BytesDe>,
+    pub multiaddrs: BoundedVec<BytesDe, MAX_MULTIADDRS>,
+    /// Control addresses with which the miner is created, if any.
+    /// This field may be omitted, in which case the miner has no control addresses.
+    #[serde(default)]
+    pub control_addresses: Vec<Address>,
 }
 
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
@@ -72,6 +80,46 @@ pub struct CurrentTotalPowerReturn {
     pub ramp_duration_epochs: u64,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct TotalPowerAtParams {
+    pub epoch: ChainEpoch,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct TotalPowerAtReturn {
+    #[serde(with = "bigint_ser")]
+    pub raw_byte_power: StoragePower,
+    #[serde(with = "bigint_ser")]
+    pub quality_adj_power: StoragePower,
+    pub pledge_collateral: TokenAmount,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct HistoricalPowerParams {
+    /// First epoch of the range to query.
+    pub start_epoch: ChainEpoch,
+    /// Last epoch of the range to query (inclusive).
+    pub end_epoch: ChainEpoch,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct HistoricalPowerReturn {
+    /// Recorded totals, in epoch order.
+    pub entries: Vec<PowerHistoryEntry>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct PledgeRampStatusReturn {
+    pub ramp_start_epoch: ChainEpoch,
+    pub ramp_duration_epochs: u64,
+    /// The pledge ramp multiplier (gamma) at the current epoch, scaled by
+    /// GAMMA_FIXED_POINT_FACTOR: the share of additional pledge coming from the
+    /// baseline formulation rather than simple pledge.
+    pub ramp_multiplier: u64,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 #[serde(transparent)]
 pub struct NetworkRawPowerReturn {
@@ -79,6 +127,19 @@ pub struct NetworkRawPowerReturn {
     pub raw_byte_power: StoragePower,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct NetworkQAPowerReturn {
+    #[serde(with = "bigint_ser")]
+    pub quality_adj_power: StoragePower,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct NetworkPledgeReturn {
+    pub pledge_collateral: TokenAmount,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 #[serde(transparent)]
 pub struct MinerRawPowerParams {
@@ -106,6 +167,72 @@ pub struct MinerPowerReturn {
     pub quality_adj_power: StoragePower,
 }
 
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct MinerPledgeParams {
+    pub miner: ActorID,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct MinerPledgeReturn {
+    pub pledge_collateral: TokenAmount,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct MinerPowersParams {
+    pub miners: Vec<ActorID>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct MinerPowersReturn {
+    pub batch_info: BatchReturn,
+    /// Power of the miners found, in the order requested.
+    pub powers: Vec<MinerPowerReturn>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ListMinerClaimsParams {
+    /// The start cursor to list from the beginning; otherwise the next_cursor of a previous call.
+    pub cursor: Cursor,
+    /// Maximum number of claims to return, capped at LIST_MINER_CLAIMS_MAX_PAGE_SIZE.
+    pub limit: u64,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct ListMinerClaimsReturn {
+    /// Miners' claims, in no particular order.
+    pub claims: Vec<(ActorID, Claim)>,
+    /// Cursor from which to continue the listing, if more claims remain.
+    pub next_cursor: Option<Cursor>,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct GetCronEventsParams {
+    /// First epoch of the range to query.
+    pub start_epoch: ChainEpoch,
+    /// Last epoch of the range to query (inclusive).
+    pub end_epoch: ChainEpoch,
+}
+
+/// An event enrolled in the cron queue, identified by its miner and a digest of its payload.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+pub struct CronEventSummary {
+    pub epoch: ChainEpoch,
+    pub miner_addr: Address,
+    /// Blake2b-256 digest of the callback payload.
+    #[serde(with = "strict_bytes")]
+    pub payload_digest: [u8; 32],
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct GetCronEventsReturn {
+    /// Enrolled events, in order of epoch and then of enrollment.
+    pub events: Vec<CronEventSummary>,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
 #[serde(transparent)]
 pub struct MinerCountReturn {
@@ -117,3 +244,15 @@ pub struct MinerCountReturn {
 pub struct MinerConsensusCountReturn {
     pub miner_consensus_count: i64,
 }
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct SuspendMinerParams {
+    pub miner: ActorID,
+}
+
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct ReinstateMinerParams {
+    pub miner: ActorID,
+}
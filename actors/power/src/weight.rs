@@ -0,0 +1,46 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! The weight in consensus of the power claimed by miners.
+//!
+//! Claims record the power reported by miners, and the network's total quality-adjusted power
+//! sums the weight of the claims that meet the consensus minimum. By default a claim weighs
+//! its quality-adjusted power. Research networks may build the actor with the
+//! `custom-consensus-weight` feature, implement [`ConsensusWeight`] with their own formula and
+//! deploy [`Actor`](crate::Actor) parameterized by it, without changing the bookkeeping of claims.
+
+use fvm_shared::sector::StoragePower;
+
+use crate::Claim;
+
+/// A formula for the weight in consensus of a miner's claim.
+/// The weight of a claim must not be negative.
+/// Only [`QualityAdjustedWeight`] implements it unless the `custom-consensus-weight` feature
+/// is enabled.
+pub trait ConsensusWeight: sealed::Sealed {
+    fn weight(&self, claim: &Claim) -> StoragePower;
+}
+
+/// Weighs a claim by its quality-adjusted power.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QualityAdjustedWeight;
+
+impl ConsensusWeight for QualityAdjustedWeight {
+    fn weight(&self, claim: &Claim) -> StoragePower {
+        claim.quality_adj_power.clone()
+    }
+}
+
+#[cfg(not(feature = "custom-consensus-weight"))]
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::QualityAdjustedWeight {}
+}
+
+#[cfg(feature = "custom-consensus-weight")]
+mod sealed {
+    pub trait Sealed {}
+
+    impl<T> Sealed for T {}
+}
@@ -4,7 +4,6 @@ use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_encoding::{BytesDe, RawBytes};
-use fvm_shared::MethodNum;
 use fvm_shared::address::Address;
 use fvm_shared::bigint::BigInt;
 use fvm_shared::bigint::bigint_ser::BigIntSer;
@@ -12,6 +11,7 @@ use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::sector::{RegisteredPoStProof, RegisteredSealProof, StoragePower};
+use fvm_shared::{ActorID, MethodNum};
 use lazy_static::lazy_static;
 use num_traits::Zero;
 use serde::Serialize;
@@ -26,8 +26,11 @@ use fil_actor_power::ext::reward::Method::ThisEpochReward;
 use fil_actor_power::ext::reward::UPDATE_NETWORK_KPI;
 use fil_actor_power::testing::check_state_invariants;
 use fil_actor_power::{
-    Claim, CreateMinerParams, CreateMinerReturn, CurrentTotalPowerReturn, Method, State,
-    UpdateClaimedPowerParams, ext,
+    Claim, CreateMinerParams, CreateMinerReturn, CurrentTotalPowerReturn, GetCronEventsParams,
+    GetCronEventsReturn, HistoricalPowerParams, HistoricalPowerReturn, ListMinerClaimsParams,
+    ListMinerClaimsReturn, Method, MinerPledgeParams, MinerPledgeReturn, MinerPowersParams,
+    MinerPowersReturn, ReinstateMinerParams, State, SuspendMinerParams, TotalPowerAtParams,
+    TotalPowerAtReturn, UpdateClaimedPowerParams, ext,
 };
 use fil_actor_power::{CronEvent, MinerConsensusCountReturn};
 use fil_actor_power::{MinerCountReturn, epoch_key};
@@ -41,7 +44,7 @@ use fil_actors_runtime::test_utils::{
     ACCOUNT_ACTOR_CODE_ID, MINER_ACTOR_CODE_ID, MockRuntime, SYSTEM_ACTOR_CODE_ID,
 };
 use fil_actors_runtime::{
-    ActorError, INIT_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    ActorError, Cursor, EventBuilder, INIT_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
 };
 use fil_actors_runtime::{CRON_ACTOR_ADDR, DEFAULT_HAMT_CONFIG};
 use fil_actors_runtime::{Map2, MapKey, Multimap};
@@ -127,6 +130,7 @@ impl Harness {
         robust: &Address,
         peer: Vec<u8>,
         multiaddrs: Vec<BytesDe>,
+        control_addresses: Vec<Address>,
         window_post_proof_type: RegisteredPoStProof,
         value: &TokenAmount,
     ) -> Result<(), ActorError> {
@@ -138,7 +142,7 @@ impl Harness {
         let miner_ctor_params = MinerConstructorParams {
             owner: *owner,
             worker: *worker,
-            control_addresses: vec![],
+            control_addresses: control_addresses.clone(),
             window_post_proof_type,
             peer_id: peer.clone(),
             multi_addresses: multiaddrs.clone(),
@@ -157,12 +161,21 @@ impl Harness {
             ExitCode::OK,
         );
 
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("miner-created")
+                .field_indexed("miner", &miner.id().unwrap())
+                .build()
+                .unwrap(),
+        );
+
         let params = CreateMinerParams {
             owner: *owner,
             worker: *worker,
             window_post_proof_type,
             peer,
-            multiaddrs,
+            multiaddrs: multiaddrs.into(),
+            control_addresses,
         };
         rt.call::<PowerActor>(
             Method::CreateMiner as MethodNum,
@@ -190,6 +203,7 @@ impl Harness {
             &actr_addr,
             peer,
             vec![],
+            vec![],
             self.window_post_proof,
             &TokenAmount::zero(),
         )
@@ -224,6 +238,9 @@ impl Harness {
         let mut claims = state.load_claims(rt.store()).unwrap();
         claims.delete(miner).expect("Failed to delete claim");
         state.claims = claims.flush().unwrap();
+        let mut pledges = state.load_miner_pledges(rt.store()).unwrap();
+        pledges.delete(miner).expect("Failed to delete pledge");
+        state.miner_pledges = pledges.flush().unwrap();
 
         rt.replace_state(&state);
     }
@@ -236,7 +253,7 @@ impl Harness {
         payload: &RawBytes,
     ) -> Result<(), ActorError> {
         rt.set_caller(*MINER_ACTOR_CODE_ID, miner_address.to_owned());
-        rt.expect_validate_caller_type(vec![Type::Miner]);
+        rt.expect_validate_caller_type(vec![Type::Miner, Type::PaymentChannel]);
         let params = IpldBlock::serialize_cbor(&EnrollCronEventParams {
             event_epoch: epoch,
             payload: payload.clone(),
@@ -276,6 +293,7 @@ impl Harness {
     pub fn update_pledge_total(&self, rt: &MockRuntime, miner: Address, delta: &TokenAmount) {
         let st: State = rt.get_state();
         let prev = st.total_pledge_collateral;
+        let prev_miner = st.miner_pledge(rt.store(), &miner).unwrap();
 
         rt.set_caller(*MINER_ACTOR_CODE_ID, miner);
         rt.expect_validate_caller_type(vec![Type::Miner]);
@@ -288,6 +306,22 @@ impl Harness {
 
         let st: State = rt.get_state();
         assert_eq!(prev + delta, st.total_pledge_collateral);
+        assert_eq!(prev_miner + delta, st.miner_pledge(rt.store(), &miner).unwrap());
+    }
+
+    pub fn miner_pledge(
+        &self,
+        rt: &MockRuntime,
+        miner: ActorID,
+    ) -> Result<TokenAmount, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<PowerActor>(
+            Method::MinerPledgeExported as MethodNum,
+            IpldBlock::serialize_cbor(&MinerPledgeParams { miner }).unwrap(),
+        );
+        rt.verify();
+        let ret: MinerPledgeReturn = ret?.unwrap().deserialize().unwrap();
+        Ok(ret.pledge_collateral)
     }
 
     pub fn current_power_total(&self, rt: &MockRuntime) -> CurrentTotalPowerReturn {
@@ -302,6 +336,79 @@ impl Harness {
         ret
     }
 
+    pub fn total_power_at(
+        &self,
+        rt: &MockRuntime,
+        epoch: ChainEpoch,
+    ) -> Result<TotalPowerAtReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<PowerActor>(
+            Method::TotalPowerAtExported as MethodNum,
+            IpldBlock::serialize_cbor(&TotalPowerAtParams { epoch }).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn historical_power(
+        &self,
+        rt: &MockRuntime,
+        start_epoch: ChainEpoch,
+        end_epoch: ChainEpoch,
+    ) -> Result<HistoricalPowerReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<PowerActor>(
+            Method::HistoricalPowerExported as MethodNum,
+            IpldBlock::serialize_cbor(&HistoricalPowerParams { start_epoch, end_epoch }).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn miner_powers(
+        &self,
+        rt: &MockRuntime,
+        miners: Vec<ActorID>,
+    ) -> Result<MinerPowersReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<PowerActor>(
+            Method::MinerPowersExported as MethodNum,
+            IpldBlock::serialize_cbor(&MinerPowersParams { miners }).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn list_miner_claims(
+        &self,
+        rt: &MockRuntime,
+        cursor: Cursor,
+        limit: u64,
+    ) -> Result<ListMinerClaimsReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<PowerActor>(
+            Method::ListMinerClaimsExported as MethodNum,
+            IpldBlock::serialize_cbor(&ListMinerClaimsParams { cursor, limit }).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
+    pub fn get_cron_events(
+        &self,
+        rt: &MockRuntime,
+        start_epoch: ChainEpoch,
+        end_epoch: ChainEpoch,
+    ) -> Result<GetCronEventsReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let ret = rt.call::<PowerActor>(
+            Method::GetCronEventsExported as MethodNum,
+            IpldBlock::serialize_cbor(&GetCronEventsParams { start_epoch, end_epoch }).unwrap(),
+        );
+        rt.verify();
+        Ok(ret?.unwrap().deserialize().unwrap())
+    }
+
     pub fn update_claimed_power(
         &self,
         rt: &MockRuntime,
@@ -317,6 +424,15 @@ impl Harness {
         };
         rt.set_caller(*MINER_ACTOR_CODE_ID, miner);
         rt.expect_validate_caller_type(vec![Type::Miner]);
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("power-updated")
+                .field_indexed("miner", &miner.id().unwrap())
+                .field("raw-power-delta", &BigIntSer(raw_delta))
+                .field("qa-power-delta", &BigIntSer(qa_delta))
+                .build()
+                .unwrap(),
+        );
         rt.call::<PowerActor>(
             Method::UpdateClaimedPower as MethodNum,
             IpldBlock::serialize_cbor(&params).unwrap(),
@@ -340,6 +456,44 @@ impl Harness {
         }
     }
 
+    pub fn suspend_miner(&self, rt: &MockRuntime, miner: Address) -> Result<(), ActorError> {
+        let miner = miner.id().unwrap();
+        rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("miner-suspended")
+                .field_indexed("miner", &miner)
+                .build()
+                .unwrap(),
+        );
+        rt.call::<PowerActor>(
+            Method::SuspendMiner as MethodNum,
+            IpldBlock::serialize_cbor(&SuspendMinerParams { miner }).unwrap(),
+        )?;
+        rt.verify();
+        Ok(())
+    }
+
+    pub fn reinstate_miner(&self, rt: &MockRuntime, miner: Address) -> Result<(), ActorError> {
+        let miner = miner.id().unwrap();
+        rt.set_caller(*SYSTEM_ACTOR_CODE_ID, SYSTEM_ACTOR_ADDR);
+        rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("miner-reinstated")
+                .field_indexed("miner", &miner)
+                .build()
+                .unwrap(),
+        );
+        rt.call::<PowerActor>(
+            Method::ReinstateMiner as MethodNum,
+            IpldBlock::serialize_cbor(&ReinstateMinerParams { miner }).unwrap(),
+        )?;
+        rt.verify();
+        Ok(())
+    }
+
     pub fn expect_total_power_eager(
         &self,
         rt: &MockRuntime,
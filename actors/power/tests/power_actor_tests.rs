@@ -2,10 +2,12 @@ use fil_actor_power::ext::init::{EXEC_METHOD, ExecParams};
 use fil_actor_power::ext::miner::MinerConstructorParams;
 use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::test_utils::{
-    ACCOUNT_ACTOR_CODE_ID, EVM_ACTOR_CODE_ID, MINER_ACTOR_CODE_ID, SYSTEM_ACTOR_CODE_ID,
-    expect_abort, expect_abort_contains_message,
+    ACCOUNT_ACTOR_CODE_ID, EVM_ACTOR_CODE_ID, MINER_ACTOR_CODE_ID, PAYCH_ACTOR_CODE_ID,
+    SYSTEM_ACTOR_CODE_ID, blake2b_256, expect_abort, expect_abort_contains_message,
+};
+use fil_actors_runtime::{
+    BatchReturn, Cursor, EventBuilder, INIT_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, runtime::Policy,
 };
-use fil_actors_runtime::{INIT_ACTOR_ADDR, runtime::Policy};
 use fvm_ipld_encoding::{BytesDe, RawBytes};
 use fvm_shared::MethodNum;
 use fvm_shared::address::Address;
@@ -19,8 +21,10 @@ use std::ops::Neg;
 
 use fil_actor_power::{
     Actor as PowerActor, Actor, CONSENSUS_MINER_MIN_MINERS, CreateMinerParams, CreateMinerReturn,
-    EnrollCronEventParams, Method, MinerPowerParams, MinerPowerReturn, MinerRawPowerParams,
-    MinerRawPowerReturn, NetworkRawPowerReturn, State, UpdateClaimedPowerParams,
+    CronEventSummary, EnrollCronEventParams, GET_CRON_EVENTS_MAX_EPOCH_RANGE, Method,
+    MinerPowerParams, MinerPowerReturn, MinerRawPowerParams, MinerRawPowerReturn,
+    NetworkPledgeReturn, NetworkQAPowerReturn, NetworkRawPowerReturn, POWER_HISTORY_LENGTH,
+    PledgeRampStatusReturn, State, SuspendMinerParams, UpdateClaimedPowerParams,
     consensus_miner_min_power,
 };
 
@@ -53,6 +57,7 @@ fn create_miner() {
         &ACTOR,
         peer,
         multiaddrs,
+        vec![Address::new_id(102)],
         RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
         &TokenAmount::from_atto(10),
     )
@@ -92,7 +97,8 @@ fn create_miner_given_send_to_init_actor_fails_should_fail() {
         worker: *OWNER,
         window_post_proof_type: RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
         peer: peer.clone(),
-        multiaddrs: multiaddrs.clone(),
+        multiaddrs: multiaddrs.clone().into(),
+        control_addresses: vec![],
     };
 
     // owner send CreateMiner to Actor
@@ -212,7 +218,7 @@ fn power_and_pledge_accounted_below_threshold() {
 
     // Add power and pledge for miner2
     h.update_claimed_power(&rt, MINER2, small_power_unit, small_power_unit);
-    h.update_pledge_total(&rt, MINER1, &TokenAmount::from_atto(1_000_000));
+    h.update_pledge_total(&rt, MINER2, &TokenAmount::from_atto(1_000_000));
     h.expect_total_power_eager(&rt, small_power_unit_x2, small_power_unit_x3);
     h.expect_total_pledge_eager(&rt, &TokenAmount::from_atto(1_000_000));
 
@@ -236,6 +242,11 @@ fn power_and_pledge_accounted_below_threshold() {
     let claim2 = h.get_claim(&rt, &MINER2).unwrap();
     assert!(claim2.raw_byte_power.is_zero());
     assert!(claim2.quality_adj_power.is_zero());
+
+    // Pledge is accounted to the miner that reported it.
+    assert_eq!(TokenAmount::zero(), h.miner_pledge(&rt, MINER1.id().unwrap()).unwrap());
+    assert_eq!(TokenAmount::from_atto(900_000), h.miner_pledge(&rt, MINER2.id().unwrap()).unwrap());
+    expect_abort(ExitCode::USR_NOT_FOUND, h.miner_pledge(&rt, 999));
     h.check_state(&rt);
 }
 
@@ -273,6 +284,77 @@ fn enroll_cron_epoch_multiple_events() {
     h.check_state(&rt);
 }
 
+#[test]
+fn enroll_cron_event_is_deduplicated() {
+    let (mut h, rt) = setup();
+
+    h.create_miner_basic(&rt, *OWNER, *OWNER, *MINER).unwrap();
+
+    let payload = RawBytes::serialize(b"Cthulhu").unwrap();
+    h.enroll_cron_event(&rt, 1, &MINER, &payload).unwrap();
+    assert_eq!(1, rt.get_state::<State>().cron_event_count);
+
+    // Re-enrolling the same event at the same epoch doesn't add to the queue.
+    h.enroll_cron_event(&rt, 1, &MINER, &payload).unwrap();
+    assert_eq!(1, h.get_enrolled_cron_ticks(&rt, 1).len());
+    assert_eq!(1, rt.get_state::<State>().cron_event_count);
+
+    // The same payload at another epoch, and another payload at the same epoch, are distinct.
+    h.enroll_cron_event(&rt, 2, &MINER, &payload).unwrap();
+    h.enroll_cron_event(&rt, 1, &MINER, &RawBytes::serialize(b"Azathoth").unwrap()).unwrap();
+    assert_eq!(2, h.get_enrolled_cron_ticks(&rt, 1).len());
+    assert_eq!(1, h.get_enrolled_cron_ticks(&rt, 2).len());
+    assert_eq!(3, rt.get_state::<State>().cron_event_count);
+
+    h.check_state(&rt);
+}
+
+#[test]
+fn get_cron_events_in_range() {
+    let (mut h, rt) = setup();
+
+    h.create_miner_basic(&rt, *OWNER, *OWNER, *MINER).unwrap();
+    let miner2_address = Address::new_id(501);
+    h.create_miner_basic(&rt, *OWNER, *OWNER, miner2_address).unwrap();
+
+    let payload1 = RawBytes::serialize(b"Cthulhu").unwrap();
+    let payload2 = RawBytes::serialize(b"Azathoth").unwrap();
+    h.enroll_cron_event(&rt, 3, &MINER, &payload1).unwrap();
+    h.enroll_cron_event(&rt, 1, &miner2_address, &payload2).unwrap();
+    h.enroll_cron_event(&rt, 1, &MINER, &payload1).unwrap();
+    h.enroll_cron_event(&rt, 5, &MINER, &payload2).unwrap();
+
+    let summary = |epoch, miner_addr, payload: &RawBytes| CronEventSummary {
+        epoch,
+        miner_addr,
+        payload_digest: blake2b_256(payload),
+    };
+    // Events are listed in order of epoch, then of enrollment, and the range is inclusive.
+    let ret = h.get_cron_events(&rt, 0, 3).unwrap();
+    assert_eq!(
+        vec![
+            summary(1, miner2_address, &payload2),
+            summary(1, *MINER, &payload1),
+            summary(3, *MINER, &payload1),
+        ],
+        ret.events
+    );
+    assert_eq!(vec![summary(5, *MINER, &payload2)], h.get_cron_events(&rt, 5, 5).unwrap().events);
+    assert!(h.get_cron_events(&rt, 6, 100).unwrap().events.is_empty());
+
+    // The range must be ordered and bounded.
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.get_cron_events(&rt, 3, 2));
+    rt.reset();
+    expect_abort(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        h.get_cron_events(&rt, 0, GET_CRON_EVENTS_MAX_EPOCH_RANGE),
+    );
+    rt.reset();
+    h.get_cron_events(&rt, 0, GET_CRON_EVENTS_MAX_EPOCH_RANGE - 1).unwrap();
+
+    h.check_state(&rt);
+}
+
 #[test]
 fn enroll_cron_epoch_before_current_epoch() {
     let (mut h, rt) = setup();
@@ -418,12 +500,94 @@ fn all_of_one_miners_power_disappears_when_that_miner_dips_below_min_power_thres
     h.check_state(&rt);
 }
 
+#[test]
+fn suspended_miner_consensus_power_is_removed_until_reinstated() {
+    let power_unit = &consensus_miner_min_power(
+        &Policy::default(),
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+    )
+    .unwrap();
+
+    let (mut h, rt) = setup();
+    for miner in [MINER1, MINER2, MINER3, MINER4, MINER5] {
+        h.create_miner_basic(&rt, *OWNER, *OWNER, miner).unwrap();
+        h.update_claimed_power(&rt, miner, power_unit, power_unit);
+    }
+    h.expect_total_power_eager(&rt, &(power_unit * 5), &(power_unit * 5));
+
+    h.suspend_miner(&rt, MINER1).unwrap();
+    h.expect_total_power_eager(&rt, &(power_unit * 4), &(power_unit * 4));
+    h.expect_miners_above_min_power(&rt, 4);
+    h.check_state(&rt);
+
+    // The claim is retained, and still tracks the miner's committed power,
+    // but no longer counts in consensus.
+    h.update_claimed_power(&rt, MINER1, power_unit, &(power_unit * 2));
+    assert_eq!(power_unit * 2, h.get_claim(&rt, &MINER1).unwrap().raw_byte_power);
+    h.expect_total_power_eager(&rt, &(power_unit * 4), &(power_unit * 4));
+    let st: State = rt.get_state();
+    assert_eq!(power_unit * 6, st.total_bytes_committed);
+    assert_eq!(power_unit * 7, st.total_qa_bytes_committed);
+
+    rt.expect_validate_caller_any();
+    let ret: MinerRawPowerReturn = rt
+        .call::<PowerActor>(
+            Method::MinerRawPowerExported as MethodNum,
+            IpldBlock::serialize_cbor(&MinerRawPowerParams { miner: MINER1.id().unwrap() })
+                .unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    assert_eq!(power_unit * 2, ret.raw_byte_power);
+    assert!(!ret.meets_consensus_minimum);
+    h.check_state(&rt);
+
+    h.reinstate_miner(&rt, MINER1).unwrap();
+    h.expect_total_power_eager(&rt, &(power_unit * 6), &(power_unit * 7));
+    h.expect_miners_above_min_power(&rt, 5);
+    h.check_state(&rt);
+}
+
+#[test]
+fn suspend_and_reinstate_miner_validation() {
+    let (mut h, rt) = setup();
+    h.create_miner_basic(&rt, *OWNER, *OWNER, MINER1).unwrap();
+
+    // Only governance may suspend a miner.
+    rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, *OWNER);
+    rt.expect_validate_caller_addr(vec![SYSTEM_ACTOR_ADDR]);
+    expect_abort(
+        ExitCode::USR_FORBIDDEN,
+        rt.call::<PowerActor>(
+            Method::SuspendMiner as MethodNum,
+            IpldBlock::serialize_cbor(&SuspendMinerParams { miner: MINER1.id().unwrap() }).unwrap(),
+        ),
+    );
+    rt.reset();
+
+    expect_abort(ExitCode::USR_NOT_FOUND, h.suspend_miner(&rt, MINER2));
+    rt.reset();
+    expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.reinstate_miner(&rt, MINER1));
+    rt.reset();
+
+    h.suspend_miner(&rt, MINER1).unwrap();
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "already suspended",
+        h.suspend_miner(&rt, MINER1),
+    );
+    rt.reset();
+    h.check_state(&rt);
+}
+
 #[test]
 fn enroll_cron_epoch_given_negative_epoch_should_fail() {
     let (h, rt) = setup();
 
     rt.set_caller(*MINER_ACTOR_CODE_ID, *MINER);
-    rt.expect_validate_caller_type(vec![Type::Miner]);
+    rt.expect_validate_caller_type(vec![Type::Miner, Type::PaymentChannel]);
 
     let params = EnrollCronEventParams {
         event_epoch: -1,
@@ -580,6 +744,8 @@ fn get_network_and_miner_power() {
     // manually update state in lieu of cron running
     let mut state: State = rt.get_state();
     state.this_epoch_raw_byte_power = power_unit.clone();
+    state.this_epoch_quality_adj_power = power_unit * 3;
+    state.this_epoch_pledge_collateral = TokenAmount::from_atto(42);
     rt.replace_state(&state);
 
     // set caller to not-builtin
@@ -595,6 +761,24 @@ fn get_network_and_miner_power() {
 
     assert_eq!(power_unit, &network_power.raw_byte_power);
 
+    rt.expect_validate_caller_any();
+    let network_qa_power: NetworkQAPowerReturn = rt
+        .call::<Actor>(Method::NetworkQAPowerExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    assert_eq!(power_unit * 3, network_qa_power.quality_adj_power);
+
+    rt.expect_validate_caller_any();
+    let network_pledge: NetworkPledgeReturn = rt
+        .call::<Actor>(Method::NetworkPledgeExported as u64, None)
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    assert_eq!(TokenAmount::from_atto(42), network_pledge.pledge_collateral);
+
     rt.expect_validate_caller_any();
     let miner_power: MinerRawPowerReturn = rt
         .call::<Actor>(
@@ -628,6 +812,133 @@ fn get_network_and_miner_power() {
     h.check_state(&rt);
 }
 
+#[test]
+fn get_powers_of_many_miners() {
+    let power_unit = &consensus_miner_min_power(
+        &Policy::default(),
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+    )
+    .unwrap();
+
+    let (mut h, rt) = setup();
+    h.create_miner_basic(&rt, *OWNER, *OWNER, MINER1).unwrap();
+    h.create_miner_basic(&rt, *OWNER, *OWNER, MINER2).unwrap();
+    h.update_claimed_power(&rt, MINER1, power_unit, power_unit);
+    h.update_claimed_power(&rt, MINER2, power_unit, &(power_unit * 2));
+
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+    let ret = h
+        .miner_powers(&rt, vec![MINER2.id().unwrap(), MINER3.id().unwrap(), MINER1.id().unwrap()])
+        .unwrap();
+    assert_eq!(
+        BatchReturn::of(&[ExitCode::OK, ExitCode::USR_NOT_FOUND, ExitCode::OK]),
+        ret.batch_info
+    );
+    assert_eq!(
+        vec![
+            MinerPowerReturn {
+                raw_byte_power: power_unit.clone(),
+                quality_adj_power: power_unit * 2
+            },
+            MinerPowerReturn {
+                raw_byte_power: power_unit.clone(),
+                quality_adj_power: power_unit.clone()
+            },
+        ],
+        ret.powers
+    );
+
+    let ret = h.miner_powers(&rt, vec![]).unwrap();
+    assert_eq!(BatchReturn::empty(), ret.batch_info);
+    assert!(ret.powers.is_empty());
+    h.check_state(&rt);
+}
+
+#[test]
+fn list_miner_claims_paginates() {
+    let power_unit = &consensus_miner_min_power(
+        &Policy::default(),
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+    )
+    .unwrap();
+
+    let (mut h, rt) = setup();
+    for (i, miner) in [MINER1, MINER2, MINER3].into_iter().enumerate() {
+        h.create_miner_basic(&rt, *OWNER, *OWNER, miner).unwrap();
+        h.update_claimed_power(&rt, miner, power_unit, &(power_unit * (i + 1)));
+    }
+
+    rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(1234));
+    let first = h.list_miner_claims(&rt, Cursor::start(), 2).unwrap();
+    assert_eq!(2, first.claims.len());
+    let next_cursor = first.next_cursor.expect("expected more claims");
+    let second = h.list_miner_claims(&rt, next_cursor.clone(), 2).unwrap();
+    assert_eq!(1, second.claims.len());
+    assert_eq!(None, second.next_cursor);
+
+    // Every claim is listed exactly once.
+    let mut listed: Vec<_> = first.claims.into_iter().chain(second.claims).collect();
+    listed.sort_by_key(|(miner, _)| *miner);
+    let expected: Vec<_> = [MINER1, MINER2, MINER3]
+        .iter()
+        .map(|miner| (miner.id().unwrap(), h.get_claim(&rt, miner).unwrap()))
+        .collect();
+    assert_eq!(expected, listed);
+
+    // A large limit lists all claims at once.
+    let all = h.list_miner_claims(&rt, Cursor::start(), 100).unwrap();
+    assert_eq!(3, all.claims.len());
+    assert_eq!(None, all.next_cursor);
+
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "limit must be positive",
+        h.list_miner_claims(&rt, next_cursor, 0),
+    );
+    rt.reset();
+    expect_abort_contains_message(
+        ExitCode::USR_ILLEGAL_ARGUMENT,
+        "malformed cursor",
+        h.list_miner_claims(&rt, Cursor::encode(&"nonsense").unwrap(), 2),
+    );
+    rt.reset();
+    h.check_state(&rt);
+}
+
+#[test]
+fn pledge_ramp_status_tracks_ramp_progress() {
+    let (h, rt) = setup();
+
+    let mut st: State = rt.get_state();
+    st.ramp_start_epoch = 100;
+    st.ramp_duration_epochs = 1000;
+    rt.replace_state(&st);
+
+    rt.set_caller(*EVM_ACTOR_CODE_ID, *OWNER);
+    for (epoch, ramp_multiplier) in [(50, 1000), (100, 1000), (600, 850), (1100, 700), (5000, 700)]
+    {
+        rt.set_epoch(epoch);
+        rt.expect_validate_caller_any();
+        let status: PledgeRampStatusReturn = rt
+            .call::<Actor>(Method::PledgeRampStatusExported as u64, None)
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        assert_eq!(
+            PledgeRampStatusReturn {
+                ramp_start_epoch: 100,
+                ramp_duration_epochs: 1000,
+                ramp_multiplier
+            },
+            status
+        );
+    }
+
+    h.check_state(&rt);
+}
+
 #[test]
 fn given_no_miner_claim_update_pledge_total_should_abort() {
     let (mut h, rt) = setup();
@@ -732,6 +1043,99 @@ mod cron_tests {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn total_power_at_past_epochs() {
+        let (mut h, rt) = setup();
+        let power_unit = consensus_miner_min_power(
+            &Policy::default(),
+            RegisteredPoStProof::StackedDRGWindow2KiBV1P1,
+        )
+        .unwrap();
+
+        let miners: Vec<Address> = (101..105).map(Address::new_id).collect();
+        for miner in &miners {
+            h.create_miner_basic(&rt, OWNER, OWNER, *miner).unwrap();
+            h.update_claimed_power(&rt, *miner, &power_unit, &power_unit);
+        }
+        let pledge = TokenAmount::from_atto(5u8);
+        h.update_pledge_total(&rt, miners[0], &pledge);
+        let first_power: BigInt = &power_unit * 4u8;
+        h.on_epoch_tick_end(&rt, 1, &first_power);
+
+        // Power grows, and epoch 2 is a null round.
+        h.update_claimed_power(&rt, miners[0], &power_unit, &power_unit);
+        let second_power: BigInt = &power_unit * 5u8;
+        h.on_epoch_tick_end(&rt, 3, &second_power);
+
+        let at_first = h.total_power_at(&rt, 1).unwrap();
+        assert_eq!(first_power, at_first.raw_byte_power);
+        assert_eq!(first_power, at_first.quality_adj_power);
+        assert_eq!(pledge, at_first.pledge_collateral);
+
+        let at_second = h.total_power_at(&rt, 3).unwrap();
+        assert_eq!(second_power, at_second.raw_byte_power);
+        assert_eq!(second_power, at_second.quality_adj_power);
+
+        // No totals for null rounds or epochs that have not yet ended.
+        expect_abort(ExitCode::USR_NOT_FOUND, h.total_power_at(&rt, 2));
+        expect_abort(ExitCode::USR_NOT_FOUND, h.total_power_at(&rt, 4));
+
+        // Totals are overwritten once they fall out of the history window.
+        let later = 1 + POWER_HISTORY_LENGTH as ChainEpoch;
+        h.on_epoch_tick_end(&rt, later, &second_power);
+        expect_abort(ExitCode::USR_NOT_FOUND, h.total_power_at(&rt, 1));
+        assert_eq!(second_power, h.total_power_at(&rt, later).unwrap().raw_byte_power);
+        assert_eq!(second_power, h.total_power_at(&rt, 3).unwrap().raw_byte_power);
+
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn historical_power_over_range() {
+        let (mut h, rt) = setup();
+        let power_unit = consensus_miner_min_power(
+            &Policy::default(),
+            RegisteredPoStProof::StackedDRGWindow2KiBV1P1,
+        )
+        .unwrap();
+
+        let miners: Vec<Address> = (101..105).map(Address::new_id).collect();
+        for miner in &miners {
+            h.create_miner_basic(&rt, OWNER, OWNER, *miner).unwrap();
+            h.update_claimed_power(&rt, *miner, &power_unit, &power_unit);
+        }
+        let first_power: BigInt = &power_unit * 4u8;
+        h.on_epoch_tick_end(&rt, 1, &first_power);
+        // Epoch 2 is a null round.
+        h.update_claimed_power(&rt, miners[0], &power_unit, &power_unit);
+        let second_power: BigInt = &power_unit * 5u8;
+        h.on_epoch_tick_end(&rt, 3, &second_power);
+
+        let history = h.historical_power(&rt, 0, 4).unwrap().entries;
+        assert_eq!(vec![1, 3], history.iter().map(|e| e.epoch).collect::<Vec<_>>());
+        assert_eq!(first_power, history[0].raw_byte_power);
+        assert_eq!(first_power, history[0].quality_adj_power);
+        assert_eq!(second_power, history[1].raw_byte_power);
+        assert_eq!(second_power, history[1].quality_adj_power);
+        assert!(h.historical_power(&rt, 2, 2).unwrap().entries.is_empty());
+
+        expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.historical_power(&rt, 3, 1));
+        rt.reset();
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            h.historical_power(&rt, 0, POWER_HISTORY_LENGTH as ChainEpoch),
+        );
+        rt.reset();
+
+        // Entries that have been overwritten are omitted.
+        let later = 1 + POWER_HISTORY_LENGTH as ChainEpoch;
+        h.on_epoch_tick_end(&rt, later, &second_power);
+        let history = h.historical_power(&rt, 1, later - 1).unwrap().entries;
+        assert_eq!(vec![3], history.iter().map(|e| e.epoch).collect::<Vec<_>>());
+
+        h.check_state(&rt);
+    }
+
     #[test]
     fn event_scheduled_in_null_round_called_next_round() {
         let (mut h, rt) = setup();
@@ -801,6 +1205,230 @@ mod cron_tests {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn miner_cron_events_over_limit_are_not_delayed() {
+        let (mut h, mut rt) = setup();
+        rt.policy.max_cron_events_per_tick = 3;
+
+        let miner1 = Address::new_id(101);
+        let miner2 = Address::new_id(102);
+        h.create_miner_basic(&rt, OWNER, OWNER, miner1).unwrap();
+        h.create_miner_basic(&rt, OWNER, OWNER, miner2).unwrap();
+
+        rt.set_epoch(1);
+        h.enroll_cron_event(&rt, 2, &miner1, &RawBytes::from(vec![1])).unwrap();
+        h.enroll_cron_event(&rt, 2, &miner2, &RawBytes::from(vec![2])).unwrap();
+        h.enroll_cron_event(&rt, 3, &miner1, &RawBytes::from(vec![3])).unwrap();
+        h.enroll_cron_event(&rt, 3, &miner2, &RawBytes::from(vec![4])).unwrap();
+
+        // After a stall, all the miners' events are processed in a single tick despite
+        // exceeding the limit.
+        rt.set_epoch(3);
+        rt.expect_validate_caller_addr(vec![CRON_ACTOR_ADDR]);
+        h.expect_query_network_info(&rt);
+        let state: State = rt.get_state();
+        for (miner, payload) in [(miner1, 1), (miner2, 2), (miner1, 3), (miner2, 4)] {
+            let params = DeferredCronEventParams {
+                event_payload: vec![payload],
+                reward_smoothed: h.this_epoch_reward_smoothed.clone(),
+                quality_adj_power_smoothed: state.this_epoch_qa_power_smoothed.clone(),
+            };
+            rt.expect_send_simple(
+                miner,
+                ON_DEFERRED_CRON_EVENT_METHOD,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+                TokenAmount::zero(),
+                None,
+                ExitCode::OK,
+            );
+        }
+        rt.expect_send_simple(
+            REWARD_ACTOR_ADDR,
+            UPDATE_NETWORK_KPI,
+            IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
+            TokenAmount::zero(),
+            None,
+            ExitCode::OK,
+        );
+        rt.set_caller(*CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR);
+        rt.call::<PowerActor>(Method::OnEpochTickEnd as u64, None).unwrap();
+        rt.verify();
+
+        let state: State = rt.get_state();
+        assert_eq!(4, state.first_cron_epoch);
+        assert_eq!(0, state.cron_event_count);
+        assert!(h.get_enrolled_cron_ticks(&rt, 2).is_empty());
+        assert!(h.get_enrolled_cron_ticks(&rt, 3).is_empty());
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn channel_cron_events_over_limit_are_carried_over() {
+        let (mut h, mut rt) = setup();
+        rt.policy.max_cron_events_per_tick = 2;
+
+        let miner = Address::new_id(101);
+        h.create_miner_basic(&rt, OWNER, OWNER, miner).unwrap();
+        let channels = [Address::new_id(201), Address::new_id(202), Address::new_id(203)];
+
+        rt.set_epoch(1);
+        h.enroll_cron_event(&rt, 2, &miner, &RawBytes::from(vec![1])).unwrap();
+        h.enroll_cron_event(&rt, 3, &miner, &RawBytes::from(vec![2])).unwrap();
+        for (channel, epoch) in channels.iter().zip([2, 2, 3]) {
+            rt.set_caller(*PAYCH_ACTOR_CODE_ID, *channel);
+            rt.expect_validate_caller_type(vec![Type::Miner, Type::PaymentChannel]);
+            let params = EnrollCronEventParams { event_epoch: epoch, payload: RawBytes::default() };
+            rt.call::<PowerActor>(
+                Method::EnrollCronEvent as u64,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )
+            .unwrap();
+            rt.verify();
+        }
+
+        let tick = |epoch, expected: Vec<(Address, Vec<u8>)>| {
+            rt.set_epoch(epoch);
+            rt.expect_validate_caller_addr(vec![CRON_ACTOR_ADDR]);
+            h.expect_query_network_info(&rt);
+            let state: State = rt.get_state();
+            for (actor, payload) in expected {
+                let params = DeferredCronEventParams {
+                    event_payload: payload,
+                    reward_smoothed: h.this_epoch_reward_smoothed.clone(),
+                    quality_adj_power_smoothed: state.this_epoch_qa_power_smoothed.clone(),
+                };
+                rt.expect_send_simple(
+                    actor,
+                    ON_DEFERRED_CRON_EVENT_METHOD,
+                    IpldBlock::serialize_cbor(&params).unwrap(),
+                    TokenAmount::zero(),
+                    None,
+                    ExitCode::OK,
+                );
+            }
+            rt.expect_send_simple(
+                REWARD_ACTOR_ADDR,
+                UPDATE_NETWORK_KPI,
+                IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
+                TokenAmount::zero(),
+                None,
+                ExitCode::OK,
+            );
+            rt.set_caller(*CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR);
+            rt.call::<PowerActor>(Method::OnEpochTickEnd as u64, None).unwrap();
+            rt.verify();
+        };
+
+        // After a stall, the miners' events are all processed first, then the channels' up
+        // to the limit, stopping at epoch 3 and resuming from there at the next tick.
+        tick(
+            3,
+            vec![(miner, vec![1]), (miner, vec![2]), (channels[0], vec![]), (channels[1], vec![])],
+        );
+        let state: State = rt.get_state();
+        assert_eq!(4, state.first_cron_epoch);
+        assert_eq!(3, state.first_channel_cron_epoch);
+        let pending = state.load_channel_cron_events(&rt.store).unwrap();
+        assert_eq!(Some(&3), pending.get(&channels[2]).unwrap());
+        assert!(pending.get(&channels[0]).unwrap().is_none());
+        h.check_state(&rt);
+
+        tick(4, vec![(channels[2], vec![])]);
+        let state: State = rt.get_state();
+        assert_eq!(5, state.first_channel_cron_epoch);
+        assert!(state.load_channel_cron_events(&rt.store).unwrap().is_empty());
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn payment_channel_cron_event() {
+        let (h, rt) = setup();
+        let paych = Address::new_id(201);
+
+        rt.set_epoch(1);
+        let enroll = |epoch: ChainEpoch| {
+            rt.set_caller(*PAYCH_ACTOR_CODE_ID, paych);
+            rt.expect_validate_caller_type(vec![Type::Miner, Type::PaymentChannel]);
+            let params = EnrollCronEventParams { event_epoch: epoch, payload: RawBytes::default() };
+            let ret = rt.call::<PowerActor>(
+                Method::EnrollCronEvent as u64,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            );
+            rt.verify();
+            ret
+        };
+        enroll(2).unwrap();
+        // The channel's event is queued apart from the miners'.
+        assert!(h.get_enrolled_cron_ticks(&rt, 2).is_empty());
+        let state: State = rt.get_state();
+        assert_eq!(0, state.cron_event_count);
+
+        // The channel may not enroll another event while one is pending.
+        expect_abort(ExitCode::USR_FORBIDDEN, enroll(3));
+        rt.reset();
+
+        // The payment channel is called back despite having no claim, and its failure
+        // doesn't affect the miner count.
+        rt.set_epoch(2);
+        rt.expect_validate_caller_addr(vec![CRON_ACTOR_ADDR]);
+        h.expect_query_network_info(&rt);
+        let state: State = rt.get_state();
+        let params = DeferredCronEventParams {
+            event_payload: vec![],
+            reward_smoothed: h.this_epoch_reward_smoothed.clone(),
+            quality_adj_power_smoothed: state.this_epoch_qa_power_smoothed.clone(),
+        };
+        rt.expect_send_simple(
+            paych,
+            ON_DEFERRED_CRON_EVENT_METHOD,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+            TokenAmount::zero(),
+            None,
+            ExitCode::USR_FORBIDDEN,
+        );
+        rt.expect_send_simple(
+            REWARD_ACTOR_ADDR,
+            UPDATE_NETWORK_KPI,
+            IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
+            TokenAmount::zero(),
+            None,
+            ExitCode::OK,
+        );
+        rt.set_caller(*CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR);
+        rt.call::<PowerActor>(Method::OnEpochTickEnd as u64, None).unwrap();
+        rt.verify();
+
+        let state: State = rt.get_state();
+        assert_eq!(0, state.miner_count);
+
+        // Once its event is processed, the channel may enroll another.
+        enroll(3).unwrap();
+        h.check_state(&rt);
+
+        // A channel collected before its event is due is not called back, and its event is
+        // cleared all the same.
+        rt.actor_code_cids.borrow_mut().remove(&paych);
+        rt.set_epoch(3);
+        rt.expect_validate_caller_addr(vec![CRON_ACTOR_ADDR]);
+        h.expect_query_network_info(&rt);
+        rt.expect_send_simple(
+            REWARD_ACTOR_ADDR,
+            UPDATE_NETWORK_KPI,
+            IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
+            TokenAmount::zero(),
+            None,
+            ExitCode::OK,
+        );
+        rt.set_caller(*CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR);
+        rt.call::<PowerActor>(Method::OnEpochTickEnd as u64, None).unwrap();
+        rt.verify();
+
+        let state: State = rt.get_state();
+        assert!(state.load_channel_cron_events(&rt.store).unwrap().is_empty());
+        assert_eq!(4, state.first_channel_cron_epoch);
+        h.check_state(&rt);
+    }
+
     #[test]
     fn event_scheduled_in_past_called_next_round() {
         let (mut h, rt) = setup();
@@ -997,6 +1625,14 @@ mod cron_tests {
             None,
             ExitCode::OK,
         );
+        // the failed miner's claim is deleted
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("claim-deleted")
+                .field_indexed("miner", &miner1.id().unwrap())
+                .build()
+                .unwrap(),
+        );
         // reward actor is still invoked
         rt.set_caller(*CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR);
         rt.expect_send_simple(
@@ -1040,6 +1676,58 @@ mod cron_tests {
         rt.verify();
         h.check_state(&rt);
     }
+
+    #[test]
+    fn calls_back_suspended_miner() {
+        let (mut h, rt) = setup();
+        rt.set_epoch(1);
+
+        let miner1 = Address::new_id(101);
+        let miner2 = Address::new_id(102);
+        h.create_miner_basic(&rt, OWNER, OWNER, miner1).unwrap();
+        h.create_miner_basic(&rt, OWNER, OWNER, miner2).unwrap();
+
+        let payload = RawBytes::from(vec![0x01]);
+        h.enroll_cron_event(&rt, 2, &miner1, &payload).unwrap();
+        h.enroll_cron_event(&rt, 3, &miner2, &payload).unwrap();
+        h.suspend_miner(&rt, miner1).unwrap();
+
+        let tick = |epoch: ChainEpoch, miner: Address| {
+            rt.set_epoch(epoch);
+            rt.expect_validate_caller_addr(vec![CRON_ACTOR_ADDR]);
+            h.expect_query_network_info(&rt);
+            let state: State = rt.get_state();
+            rt.expect_send_simple(
+                miner,
+                ON_DEFERRED_CRON_EVENT_METHOD,
+                IpldBlock::serialize_cbor(&DeferredCronEventParams {
+                    event_payload: payload.bytes().to_vec(),
+                    reward_smoothed: h.this_epoch_reward_smoothed.clone(),
+                    quality_adj_power_smoothed: state.this_epoch_qa_power_smoothed,
+                })
+                .unwrap(),
+                TokenAmount::zero(),
+                None,
+                ExitCode::OK,
+            );
+            rt.expect_send_simple(
+                REWARD_ACTOR_ADDR,
+                UPDATE_NETWORK_KPI,
+                IpldBlock::serialize_cbor(&BigIntSer(&BigInt::zero())).unwrap(),
+                TokenAmount::zero(),
+                None,
+                ExitCode::OK,
+            );
+            rt.set_caller(*CRON_ACTOR_CODE_ID, CRON_ACTOR_ADDR);
+            rt.call::<PowerActor>(Method::OnEpochTickEnd as u64, None).unwrap();
+            rt.verify();
+        };
+
+        // Suspension doesn't hold a miner's callbacks.
+        tick(2, miner1);
+        tick(3, miner2);
+        h.check_state(&rt);
+    }
 }
 
 #[test]
@@ -1054,7 +1742,8 @@ fn create_miner_restricted_correctly() {
         worker: *OWNER,
         window_post_proof_type: RegisteredPoStProof::StackedDRGWinning2KiBV1,
         peer: peer.clone(),
-        multiaddrs: multiaddrs.clone(),
+        multiaddrs: multiaddrs.clone().into(),
+        control_addresses: vec![],
     })
     .unwrap();
 
@@ -1095,6 +1784,13 @@ fn create_miner_restricted_correctly() {
         ExitCode::OK,
     );
 
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("miner-created")
+            .field_indexed("miner", &MINER.id().unwrap())
+            .build()
+            .unwrap(),
+    );
     let ret: CreateMinerReturn = rt
         .call::<PowerActor>(Method::CreateMinerExported as MethodNum, params)
         .unwrap()
@@ -1108,3 +1804,81 @@ fn create_miner_restricted_correctly() {
 
     h.check_state(&rt);
 }
+
+#[cfg(feature = "custom-consensus-weight")]
+mod custom_consensus_weight_tests {
+    use super::*;
+
+    use fil_actor_power::testing::check_state_invariants_with_weight;
+    use fil_actor_power::{Claim, ConsensusWeight};
+
+    /// Weighs a claim by its raw byte power, whatever the quality of its sectors.
+    #[derive(Default)]
+    struct RawByteWeight;
+
+    impl ConsensusWeight for RawByteWeight {
+        fn weight(&self, claim: &Claim) -> StoragePower {
+            claim.raw_byte_power.clone()
+        }
+    }
+
+    type WeightedActor = Actor<RawByteWeight>;
+
+    #[test]
+    fn claims_are_weighed_by_custom_formula() {
+        let (mut h, rt) = setup();
+        let power = consensus_miner_min_power(&Policy::default(), h.window_post_proof).unwrap();
+        let qa_power = &power * 10;
+        h.create_miner_basic(&rt, *OWNER, *OWNER, MINER1).unwrap();
+
+        rt.set_caller(*MINER_ACTOR_CODE_ID, MINER1);
+        rt.expect_validate_caller_type(vec![Type::Miner]);
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("power-updated")
+                .field_indexed("miner", &MINER1.id().unwrap())
+                .field("raw-power-delta", &BigIntSer(&power))
+                .field("qa-power-delta", &BigIntSer(&qa_power))
+                .build()
+                .unwrap(),
+        );
+        rt.call::<WeightedActor>(
+            Method::UpdateClaimedPower as MethodNum,
+            IpldBlock::serialize_cbor(&UpdateClaimedPowerParams {
+                raw_byte_delta: power.clone(),
+                quality_adjusted_delta: qa_power.clone(),
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        rt.verify();
+
+        // The claim and committed power keep the quality-adjusted power, while consensus power
+        // and the miner's reported power weigh the raw power.
+        let st: State = rt.get_state();
+        assert_eq!(qa_power, h.get_claim(&rt, &MINER1).unwrap().quality_adj_power);
+        assert_eq!(qa_power, st.total_qa_bytes_committed);
+        assert_eq!(power, st.total_quality_adj_power);
+
+        rt.expect_validate_caller_any();
+        let miner_power: MinerPowerReturn = rt
+            .call::<WeightedActor>(
+                Method::MinerPowerExported as MethodNum,
+                IpldBlock::serialize_cbor(&MinerPowerParams { miner: MINER1.id().unwrap() })
+                    .unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+        assert_eq!(
+            MinerPowerReturn { raw_byte_power: power.clone(), quality_adj_power: power },
+            miner_power
+        );
+
+        let (_, acc) =
+            check_state_invariants_with_weight(&Policy::default(), &st, &rt.store, &RawByteWeight);
+        acc.assert_empty();
+    }
+}
@@ -0,0 +1,17 @@
+include!("../../runtime/networks.rs");
+
+// The network feature which includes the TestingMint faucet method.
+const TESTING_MINT_FEATURE: &str = "testing-mint";
+const NETWORK_ENV: &str = "BUILD_FIL_NETWORK";
+
+fn main() {
+    let network = std::env::var(NETWORK_ENV).ok();
+    println!("cargo:rerun-if-env-changed={}", NETWORK_ENV);
+    println!("cargo:rerun-if-changed=../../runtime/networks.rs");
+
+    let network = network.as_deref().unwrap_or("mainnet");
+    let features = NETWORKS.iter().find(|(k, _)| k == &network).expect("unknown network").1;
+    if features.contains(&TESTING_MINT_FEATURE) {
+        println!("cargo:rustc-cfg=feature=\"{}\"", TESTING_MINT_FEATURE);
+    }
+}
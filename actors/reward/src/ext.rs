@@ -1,5 +1,8 @@
+use fil_actors_runtime::reward::FilterEstimate;
 use fvm_ipld_encoding::tuple::*;
+use fvm_shared::bigint::bigint_ser;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::StoragePower;
 
 pub mod miner {
     use super::*;
@@ -12,3 +15,21 @@ pub mod miner {
         pub penalty: TokenAmount,
     }
 }
+
+pub mod power {
+    use super::*;
+
+    pub const CURRENT_TOTAL_POWER_METHOD: u64 = 9;
+
+    #[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+    pub struct CurrentTotalPowerReturn {
+        #[serde(with = "bigint_ser")]
+        pub raw_byte_power: StoragePower,
+        #[serde(with = "bigint_ser")]
+        pub quality_adj_power: StoragePower,
+        pub pledge_collateral: TokenAmount,
+        pub quality_adj_power_smoothed: FilterEstimate,
+        pub ramp_start_epoch: i64,
+        pub ramp_duration_epochs: u64,
+    }
+}
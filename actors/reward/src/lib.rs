@@ -1,21 +1,30 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+use fil_actors_runtime::reward::pledge::{
+    initial_pledge_for_power, pledge_penalty_for_continued_fault, pledge_penalty_for_termination,
+};
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
 use fil_actors_runtime::{
     ActorError, BURNT_FUNDS_ACTOR_ADDR, EXPECTED_LEADERS_PER_EPOCH, STORAGE_POWER_ACTOR_ADDR,
-    SYSTEM_ACTOR_ADDR, actor_dispatch, actor_error, extract_send_result,
+    SYSTEM_ACTOR_ADDR, actor_dispatch, actor_error, deserialize_block, extract_send_result,
+    state_getters,
 };
 
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::sys::SendFlags;
 use fvm_shared::{METHOD_CONSTRUCTOR, METHOD_SEND};
 use log::{error, warn};
 use num_derive::FromPrimitive;
+use num_traits::{Signed, Zero};
 
 pub use self::logic::*;
-pub use self::state::{Reward, State, VestingFunction};
+pub use self::state::{
+    BASE_FEE_HISTORY_AMT_BITWIDTH, BASE_FEE_HISTORY_LENGTH, BaseFeeRecord, Reward, State,
+    VestingFunction,
+};
 pub use self::types::*;
 
 #[cfg(feature = "fil-actor")]
@@ -44,6 +53,12 @@ pub enum Method {
     AwardBlockReward = 2,
     ThisEpochReward = 3,
     UpdateNetworkKPI = 4,
+    // Method numbers derived from FRC-0042 standards
+    InitialPledgeForPowerExported = frc42_dispatch::method_hash!("InitialPledgeForPower"),
+    SectorPenaltiesExported = frc42_dispatch::method_hash!("SectorPenalties"),
+    BaseFeeHistoryExported = frc42_dispatch::method_hash!("BaseFeeHistory"),
+    #[cfg(feature = "testing-mint")]
+    TestingMintExported = frc42_dispatch::method_hash!("TestingMint"),
 }
 
 /// Reward Actor
@@ -55,7 +70,7 @@ impl Actor {
         rt.validate_immediate_caller_is(std::iter::once(&SYSTEM_ACTOR_ADDR))?;
 
         if let Some(power) = params.power.map(|v| v.0) {
-            rt.create(&State::new(power))?;
+            rt.create(&State::new(rt.store(), power)?)?;
             Ok(())
         } else {
             Err(actor_error!(illegal_argument, "argument should not be nil"))
@@ -173,16 +188,15 @@ impl Actor {
         Ok(())
     }
 
-    /// The award value used for the current epoch, updated at the end of an epoch
-    /// through cron tick.  In the case previous epochs were null blocks this
-    /// is the reward value as calculated at the last non-null epoch.
-    fn this_epoch_reward(rt: &impl Runtime) -> Result<ThisEpochRewardReturn, ActorError> {
-        rt.validate_immediate_caller_accept_any()?;
-        let st: State = rt.state()?;
-        Ok(ThisEpochRewardReturn {
-            this_epoch_baseline_power: st.this_epoch_baseline_power,
-            this_epoch_reward_smoothed: st.this_epoch_reward_smoothed,
-        })
+    state_getters! {
+        State;
+        /// The award value used for the current epoch, updated at the end of an epoch
+        /// through cron tick.  In the case previous epochs were null blocks this
+        /// is the reward value as calculated at the last non-null epoch.
+        fn this_epoch_reward() -> ThisEpochRewardReturn {
+            this_epoch_baseline_power,
+            this_epoch_reward_smoothed,
+        }
     }
 
     /// Called at the end of each epoch by the power actor (in turn by its cron hook).
@@ -209,10 +223,109 @@ impl Actor {
 
             st.update_to_next_epoch_with_reward(&curr_realized_power);
             st.update_smoothed_estimates(st.epoch - prev);
-            Ok(())
+            st.record_base_fee(rt.store(), rt.curr_epoch(), rt.base_fee())
         })?;
         Ok(())
     }
+
+    /// Returns the initial pledge the miner actor would require for committing a sector with
+    /// the given quality-adjusted power in the current epoch.
+    /// The pledge depends on the circulating supply, and is the base of the sector's termination fee.
+    fn initial_pledge_for_power(
+        rt: &impl Runtime,
+        params: InitialPledgeForPowerParams,
+    ) -> Result<InitialPledgeForPowerReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.qa_power.is_negative() {
+            return Err(actor_error!(illegal_argument, "negative power {}", params.qa_power));
+        }
+        let st: State = rt.state()?;
+        let pwr = request_current_total_power(rt)?;
+        let initial_pledge = initial_pledge_for_power(
+            &params.qa_power,
+            &st.this_epoch_baseline_power,
+            &st.this_epoch_reward_smoothed,
+            &pwr.quality_adj_power_smoothed,
+            &rt.total_fil_circ_supply(),
+            rt.curr_epoch() - pwr.ramp_start_epoch,
+            pwr.ramp_duration_epochs,
+        );
+        Ok(InitialPledgeForPowerReturn { initial_pledge })
+    }
+
+    /// Returns the penalties the miner actor would apply in the current epoch to a sector with
+    /// the given quality-adjusted power and initial pledge, if it continued faulty or was
+    /// terminated at the given age.
+    fn sector_penalties(
+        rt: &impl Runtime,
+        params: SectorPenaltiesParams,
+    ) -> Result<SectorPenaltiesReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.qa_power.is_negative() {
+            return Err(actor_error!(illegal_argument, "negative power {}", params.qa_power));
+        }
+        if params.initial_pledge.is_negative() {
+            return Err(actor_error!(
+                illegal_argument,
+                "negative initial pledge {}",
+                params.initial_pledge
+            ));
+        }
+        if params.sector_age < 0 {
+            return Err(actor_error!(
+                illegal_argument,
+                "negative sector age {}",
+                params.sector_age
+            ));
+        }
+        let st: State = rt.state()?;
+        let pwr = request_current_total_power(rt)?;
+        let continued_fault_penalty = pledge_penalty_for_continued_fault(
+            &st.this_epoch_reward_smoothed,
+            &pwr.quality_adj_power_smoothed,
+            &params.qa_power,
+        );
+        let termination_fee = pledge_penalty_for_termination(
+            &params.initial_pledge,
+            params.sector_age,
+            &continued_fault_penalty,
+        );
+        Ok(SectorPenaltiesReturn { continued_fault_penalty, termination_fee })
+    }
+
+    /// Returns the base fees of up to `limit` of the most recent non-null epochs,
+    /// most recent first. Base fees are recorded by the network KPI update at each
+    /// non-null epoch, and only those of the last BASE_FEE_HISTORY_LENGTH epochs are retained.
+    fn base_fee_history(
+        rt: &impl Runtime,
+        params: BaseFeeHistoryParams,
+    ) -> Result<BaseFeeHistoryReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        let base_fees = st.recent_base_fees(rt.store(), rt.curr_epoch(), params.limit)?;
+        Ok(BaseFeeHistoryReturn { base_fees })
+    }
+
+    /// Transfers funds from the reward actor's balance of not-yet-minted FIL to an address,
+    /// as a faucet. Only present in devnet and testing builds.
+    #[cfg(feature = "testing-mint")]
+    fn testing_mint(rt: &impl Runtime, params: TestingMintParams) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.amount.is_negative() {
+            return Err(actor_error!(illegal_argument, "negative amount {}", params.amount));
+        }
+        let balance = rt.current_balance();
+        if params.amount > balance {
+            return Err(actor_error!(
+                insufficient_funds,
+                "cannot mint {}, reward actor balance is {}",
+                params.amount,
+                balance
+            ));
+        }
+        extract_send_result(rt.send_simple(&params.to, METHOD_SEND, None, params.amount))?;
+        Ok(())
+    }
 }
 
 impl ActorCode for Actor {
@@ -227,5 +340,27 @@ impl ActorCode for Actor {
         AwardBlockReward => award_block_reward,
         ThisEpochReward => this_epoch_reward,
         UpdateNetworkKPI => update_network_kpi,
+        InitialPledgeForPowerExported => initial_pledge_for_power,
+        SectorPenaltiesExported => sector_penalties,
+        BaseFeeHistoryExported => base_fee_history,
+        #[cfg(feature = "testing-mint")]
+        TestingMintExported => testing_mint,
     }
 }
+
+/// Requests the network's current power totals and smoothed estimate from the power actor.
+fn request_current_total_power(
+    rt: &impl Runtime,
+) -> Result<ext::power::CurrentTotalPowerReturn, ActorError> {
+    deserialize_block(
+        extract_send_result(rt.send(
+            &STORAGE_POWER_ACTOR_ADDR,
+            ext::power::CURRENT_TOTAL_POWER_METHOD,
+            None,
+            TokenAmount::zero(),
+            None,
+            SendFlags::READ_ONLY,
+        ))
+        .map_err(|e| e.wrap("failed to check current power"))?,
+    )
+}
@@ -1,12 +1,15 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+use cid::Cid;
+use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::repr::*;
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::bigint::BigInt;
 use fvm_shared::bigint::bigint_ser;
 use fvm_shared::clock::{ChainEpoch, EPOCH_UNDEFINED};
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
 use fvm_shared::sector::StoragePower;
 use lazy_static::lazy_static;
 use num_derive::FromPrimitive;
@@ -14,6 +17,7 @@ use num_derive::FromPrimitive;
 use fil_actors_runtime::builtin::reward::smooth::{
     AlphaBetaFilter, DEFAULT_ALPHA, DEFAULT_BETA, FilterEstimate,
 };
+use fil_actors_runtime::{ActorError, Array, AsActorError};
 
 /// The unit of spacetime committed to the network
 pub type Spacetime = BigInt;
@@ -27,6 +31,10 @@ lazy_static! {
     pub static ref INITIAL_REWARD_VELOCITY_ESTIMATE: TokenAmount = TokenAmount::from_atto(-109897758509i64);
 }
 
+/// Number of most recent epochs for which the base fee is retained in state.
+pub const BASE_FEE_HISTORY_LENGTH: u64 = 256;
+pub const BASE_FEE_HISTORY_AMT_BITWIDTH: u32 = 5;
+
 /// Reward actor state
 #[derive(Serialize_tuple, Deserialize_tuple, Default, Debug, Clone)]
 pub struct State {
@@ -74,10 +82,24 @@ pub struct State {
     // into a code constant in a subsequent upgrade.
     pub simple_total: TokenAmount,
     pub baseline_total: TokenAmount,
+
+    /// Base fees recorded at the non-null epochs among the most recent epochs,
+    /// indexed by epoch modulo BASE_FEE_HISTORY_LENGTH.
+    pub base_fee_history: Cid, // AMT[ChainEpoch % BASE_FEE_HISTORY_LENGTH]BaseFeeRecord
 }
 
 impl State {
-    pub fn new(curr_realized_power: StoragePower) -> Self {
+    pub fn new<BS: Blockstore>(
+        store: &BS,
+        curr_realized_power: StoragePower,
+    ) -> Result<Self, ActorError> {
+        let empty_history =
+            Array::<BaseFeeRecord, BS>::new_with_bit_width(store, BASE_FEE_HISTORY_AMT_BITWIDTH)
+                .flush()
+                .context_code(
+                    ExitCode::USR_ILLEGAL_STATE,
+                    "failed to create empty base fee history",
+                )?;
         let mut st = Self {
             effective_baseline_power: BASELINE_INITIAL_VALUE.clone(),
             this_epoch_baseline_power: INIT_BASELINE_POWER.clone(),
@@ -88,11 +110,12 @@ impl State {
             ),
             simple_total: SIMPLE_TOTAL.clone(),
             baseline_total: BASELINE_TOTAL.clone(),
+            base_fee_history: empty_history,
             ..Default::default()
         };
         st.update_to_next_epoch_with_reward(&curr_realized_power);
 
-        st
+        Ok(st)
     }
 
     /// Takes in current realized power and updates internal state
@@ -145,6 +168,51 @@ impl State {
             filter_reward.next_estimate(self.this_epoch_reward.atto(), delta);
     }
 
+    /// Records the base fee of an epoch in the history,
+    /// overwriting the entry from BASE_FEE_HISTORY_LENGTH epochs earlier.
+    pub(super) fn record_base_fee<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        epoch: ChainEpoch,
+        base_fee: TokenAmount,
+    ) -> Result<(), ActorError> {
+        let mut history = Array::<BaseFeeRecord, BS>::load(&self.base_fee_history, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load base fee history")?;
+        history
+            .set(epoch as u64 % BASE_FEE_HISTORY_LENGTH, BaseFeeRecord { epoch, base_fee })
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to record base fee")?;
+        self.base_fee_history = history
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush base fee history")?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` of the base fees recorded within the BASE_FEE_HISTORY_LENGTH
+    /// epochs ending at the given epoch, most recent first.
+    pub fn recent_base_fees<BS: Blockstore>(
+        &self,
+        store: &BS,
+        epoch: ChainEpoch,
+        limit: u64,
+    ) -> Result<Vec<BaseFeeRecord>, ActorError> {
+        let history = Array::<BaseFeeRecord, BS>::load(&self.base_fee_history, store)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load base fee history")?;
+        let horizon = epoch - BASE_FEE_HISTORY_LENGTH as ChainEpoch;
+        let mut records = Vec::new();
+        history
+            .for_each(|_, record| {
+                // Entries last written before a run of null rounds may be older than the horizon.
+                if record.epoch > horizon && record.epoch <= epoch {
+                    records.push(record.clone());
+                }
+                Ok(())
+            })
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to iterate base fee history")?;
+        records.sort_by(|a, b| b.epoch.cmp(&a.epoch));
+        records.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+        Ok(records)
+    }
+
     pub fn into_total_storage_power_reward(self) -> TokenAmount {
         self.total_storage_power_reward
     }
@@ -158,6 +226,13 @@ pub enum VestingFunction {
     Linear = 1,
 }
 
+/// The base fee of a non-null epoch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct BaseFeeRecord {
+    pub epoch: ChainEpoch,
+    pub base_fee: TokenAmount,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct Reward {
     pub vesting_function: VestingFunction,
@@ -1,13 +1,15 @@
-use crate::State;
-use fil_actors_runtime::MessageAccumulator;
+use crate::{BASE_FEE_HISTORY_LENGTH, BaseFeeRecord, State};
+use fil_actors_runtime::{Array, MessageAccumulator};
+use fvm_ipld_blockstore::Blockstore;
 use fvm_shared::{clock::ChainEpoch, econ::TokenAmount};
 use num_traits::Signed;
 
 #[derive(Default)]
 pub struct StateSummary {}
 
-pub fn check_state_invariants(
+pub fn check_state_invariants<BS: Blockstore>(
     state: &State,
+    store: &BS,
     prior_epoch: ChainEpoch,
     balance: &TokenAmount,
 ) -> (StateSummary, MessageAccumulator) {
@@ -52,5 +54,26 @@ pub fn check_state_invariants(
         format!("cumsum realized negative ({})", state.cumsum_realized),
     );
 
+    match Array::<BaseFeeRecord, BS>::load(&state.base_fee_history, store) {
+        Ok(history) => {
+            let ret = history.for_each(|index, record| {
+                acc.require(
+                    record.epoch >= 0 && record.epoch as u64 % BASE_FEE_HISTORY_LENGTH == index,
+                    format!("base fee for epoch {} recorded at index {}", record.epoch, index),
+                );
+                acc.require(
+                    record.epoch <= prior_epoch,
+                    format!(
+                        "base fee recorded at {} after prior epoch {}",
+                        record.epoch, prior_epoch
+                    ),
+                );
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating base fee history");
+        }
+        Err(e) => acc.add(format!("error loading base fee history: {e}")),
+    }
+
     (StateSummary::default(), acc)
 }
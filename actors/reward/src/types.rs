@@ -3,8 +3,13 @@
 
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::address::Address;
+use fvm_shared::bigint::bigint_ser;
 use fvm_shared::bigint::bigint_ser::BigIntDe;
+use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::StoragePower;
+
+use crate::BaseFeeRecord;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 #[serde(transparent)]
@@ -27,3 +32,55 @@ pub use fil_actors_runtime::builtin::reward::ThisEpochRewardReturn;
 pub struct UpdateNetworkKPIParams {
     pub curr_realized_power: Option<BigIntDe>,
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct InitialPledgeForPowerParams {
+    #[serde(with = "bigint_ser")]
+    pub qa_power: StoragePower,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct InitialPledgeForPowerReturn {
+    pub initial_pledge: TokenAmount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct SectorPenaltiesParams {
+    /// Quality-adjusted power of the sector.
+    #[serde(with = "bigint_ser")]
+    pub qa_power: StoragePower,
+    /// Initial pledge of the sector, from which its termination fee is computed.
+    pub initial_pledge: TokenAmount,
+    /// Number of epochs since the sector's activation, at termination.
+    pub sector_age: ChainEpoch,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct SectorPenaltiesReturn {
+    /// Penalty for the sector continuing faulty for another proving period.
+    pub continued_fault_penalty: TokenAmount,
+    /// Fee for terminating the sector.
+    pub termination_fee: TokenAmount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct BaseFeeHistoryParams {
+    /// Maximum number of records to return.
+    pub limit: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct BaseFeeHistoryReturn {
+    /// Base fees of the most recent non-null epochs, most recent first.
+    pub base_fees: Vec<BaseFeeRecord>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct TestingMintParams {
+    pub to: Address,
+    pub amount: TokenAmount,
+}
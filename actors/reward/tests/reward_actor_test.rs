@@ -4,8 +4,9 @@
 use std::cell::RefCell;
 
 use fil_actor_reward::{
-    Actor as RewardActor, AwardBlockRewardParams, BASELINE_INITIAL_VALUE, Method,
-    PENALTY_MULTIPLIER, State, ThisEpochRewardReturn, ext,
+    Actor as RewardActor, AwardBlockRewardParams, BASE_FEE_HISTORY_LENGTH, BASELINE_INITIAL_VALUE,
+    BaseFeeHistoryParams, BaseFeeHistoryReturn, BaseFeeRecord, Method, PENALTY_MULTIPLIER, State,
+    ThisEpochRewardReturn, ext,
 };
 use fil_actors_runtime::EXPECTED_LEADERS_PER_EPOCH;
 use fil_actors_runtime::test_utils::*;
@@ -316,6 +317,136 @@ mod test_this_epoch_reward {
     }
 }
 
+mod test_pledge_and_penalties {
+    use fil_actor_reward::{
+        InitialPledgeForPowerParams, InitialPledgeForPowerReturn, SectorPenaltiesParams,
+        SectorPenaltiesReturn,
+    };
+    use fil_actors_runtime::EPOCHS_IN_DAY;
+    use fil_actors_runtime::reward::FilterEstimate;
+    use fil_actors_runtime::reward::pledge::{
+        initial_pledge_for_power, pledge_penalty_for_continued_fault,
+        pledge_penalty_for_termination,
+    };
+    use fvm_shared::sys::SendFlags;
+    use num::Zero;
+
+    use super::*;
+
+    const RAMP_DURATION: u64 = 100;
+
+    fn network_qa_power_estimate() -> FilterEstimate {
+        FilterEstimate::new(StoragePower::from(1u64 << 50), Zero::zero())
+    }
+
+    fn expect_current_total_power(rt: &MockRuntime) {
+        rt.expect_send(
+            STORAGE_POWER_ACTOR_ADDR,
+            ext::power::CURRENT_TOTAL_POWER_METHOD,
+            None,
+            TokenAmount::zero(),
+            None,
+            SendFlags::READ_ONLY,
+            IpldBlock::serialize_cbor(&ext::power::CurrentTotalPowerReturn {
+                raw_byte_power: StoragePower::from(1u64 << 50),
+                quality_adj_power: StoragePower::from(1u64 << 50),
+                pledge_collateral: TokenAmount::zero(),
+                quality_adj_power_smoothed: network_qa_power_estimate(),
+                ramp_start_epoch: 0,
+                ramp_duration_epochs: RAMP_DURATION,
+            })
+            .unwrap(),
+            ExitCode::OK,
+            None,
+        );
+    }
+
+    #[test]
+    fn initial_pledge_for_power_as_computed_by_miner() {
+        let rt = construct_and_verify(&StoragePower::from(1u64 << 50));
+        let circulating_supply = TokenAmount::from_whole(1_000_000);
+        rt.set_circulating_supply(circulating_supply.clone());
+        rt.set_epoch(50);
+        let st: State = rt.get_state();
+        let qa_power = StoragePower::from(32u64 << 30);
+
+        rt.expect_validate_caller_any();
+        expect_current_total_power(&rt);
+        let ret: InitialPledgeForPowerReturn = rt
+            .call::<RewardActor>(
+                Method::InitialPledgeForPowerExported as u64,
+                IpldBlock::serialize_cbor(&InitialPledgeForPowerParams {
+                    qa_power: qa_power.clone(),
+                })
+                .unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+
+        let expected = initial_pledge_for_power(
+            &qa_power,
+            &st.this_epoch_baseline_power,
+            &st.this_epoch_reward_smoothed,
+            &network_qa_power_estimate(),
+            &circulating_supply,
+            50,
+            RAMP_DURATION,
+        );
+        assert!(expected.is_positive());
+        assert_eq!(expected, ret.initial_pledge);
+    }
+
+    #[test]
+    fn sector_penalties_as_computed_by_miner() {
+        let rt = construct_and_verify(&StoragePower::from(1u64 << 50));
+        let st: State = rt.get_state();
+        let params = SectorPenaltiesParams {
+            qa_power: StoragePower::from(32u64 << 30),
+            initial_pledge: TokenAmount::from_whole(10),
+            sector_age: 30 * EPOCHS_IN_DAY,
+        };
+
+        rt.expect_validate_caller_any();
+        expect_current_total_power(&rt);
+        let ret: SectorPenaltiesReturn = rt
+            .call::<RewardActor>(
+                Method::SectorPenaltiesExported as u64,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+
+        let fault_fee = pledge_penalty_for_continued_fault(
+            &st.this_epoch_reward_smoothed,
+            &network_qa_power_estimate(),
+            &params.qa_power,
+        );
+        assert_eq!(fault_fee, ret.continued_fault_penalty);
+        assert_eq!(
+            pledge_penalty_for_termination(&params.initial_pledge, params.sector_age, &fault_fee),
+            ret.termination_fee
+        );
+
+        // Negative inputs are rejected.
+        rt.expect_validate_caller_any();
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            rt.call::<RewardActor>(
+                Method::SectorPenaltiesExported as u64,
+                IpldBlock::serialize_cbor(&SectorPenaltiesParams { sector_age: -1, ..params })
+                    .unwrap(),
+            ),
+        );
+        rt.verify();
+    }
+}
+
 #[test]
 fn test_successive_kpi_updates() {
     let power = StoragePower::from_i128(1 << 50).unwrap();
@@ -327,6 +458,37 @@ fn test_successive_kpi_updates() {
     }
 }
 
+#[test]
+fn base_fee_history_retains_most_recent_epochs() {
+    let power = StoragePower::from_i128(1 << 50).unwrap();
+    let rt = construct_and_verify(&power);
+    assert!(base_fee_history(&rt, 10).base_fees.is_empty());
+
+    let last_epoch = BASE_FEE_HISTORY_LENGTH as ChainEpoch + 2;
+    for epoch in 1..=last_epoch {
+        // Null rounds have no base fee recorded.
+        if epoch == last_epoch - 1 {
+            continue;
+        }
+        rt.epoch.replace(epoch);
+        rt.set_base_fee(TokenAmount::from_atto(100 + epoch));
+        update_network_kpi(&rt, &power);
+    }
+
+    let record =
+        |epoch: ChainEpoch| BaseFeeRecord { epoch, base_fee: TokenAmount::from_atto(100 + epoch) };
+    assert_eq!(
+        vec![record(last_epoch), record(last_epoch - 2), record(last_epoch - 3)],
+        base_fee_history(&rt, 3).base_fees
+    );
+
+    // Only epochs within the retained window are returned, so the null round
+    // leaves one fewer record than the window's length.
+    let all = base_fee_history(&rt, u64::MAX).base_fees;
+    assert_eq!(BASE_FEE_HISTORY_LENGTH as usize - 1, all.len());
+    assert_eq!(record(3), *all.last().unwrap());
+}
+
 fn construct_and_verify(curr_power: &StoragePower) -> MockRuntime {
     let rt = MockRuntime {
         receiver: REWARD_ACTOR_ADDR,
@@ -404,6 +566,21 @@ fn this_epoch_reward(rt: &MockRuntime) -> ThisEpochRewardReturn {
     resp
 }
 
+fn base_fee_history(rt: &MockRuntime, limit: u64) -> BaseFeeHistoryReturn {
+    rt.expect_validate_caller_any();
+    let ret = rt
+        .call::<RewardActor>(
+            Method::BaseFeeHistoryExported as u64,
+            IpldBlock::serialize_cbor(&BaseFeeHistoryParams { limit }).unwrap(),
+        )
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    rt.verify();
+    ret
+}
+
 fn update_network_kpi(rt: &MockRuntime, curr_raw_power: &StoragePower) {
     rt.set_caller(*POWER_ACTOR_CODE_ID, STORAGE_POWER_ACTOR_ADDR);
     rt.expect_validate_caller_addr(vec![STORAGE_POWER_ACTOR_ADDR]);
@@ -5,7 +5,10 @@ use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::CborStore;
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::METHOD_CONSTRUCTOR;
+use fvm_shared::bigint::bigint_ser;
+use fvm_shared::clock::ChainEpoch;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sector::StoragePower;
 use num_derive::FromPrimitive;
 
 use fil_actors_runtime::runtime::{ActorCode, Runtime};
@@ -21,6 +24,7 @@ fil_actors_runtime::wasm_trampoline!(Actor);
 #[repr(u64)]
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
+    GetPolicyExported = frc42_dispatch::method_hash!("GetPolicy"),
 }
 
 /// System actor state.
@@ -50,6 +54,34 @@ impl State {
     }
 }
 
+/// Active network version and policy values, for callers that must adapt to the network
+/// they run on rather than assume mainnet parameters.
+#[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone, PartialEq, Eq)]
+pub struct GetPolicyReturn {
+    pub network_version: u32,
+    /// Minimum size of a verified allocation.
+    #[serde(with = "bigint_ser")]
+    pub minimum_verified_allocation_size: StoragePower,
+    /// Minimum term of a verified allocation.
+    pub minimum_verified_allocation_term: ChainEpoch,
+    /// Maximum term of a verified allocation.
+    pub maximum_verified_allocation_term: ChainEpoch,
+    /// Maximum delay between making a verified allocation and claiming it.
+    pub maximum_verified_allocation_expiration: ChainEpoch,
+    /// Minimum period for which a sector may be committed.
+    pub min_sector_expiration: ChainEpoch,
+    /// Maximum period by which a sector's expiration may be extended beyond the current epoch.
+    pub max_sector_expiration_extension: ChainEpoch,
+    /// Minimum number of sectors in an aggregated proof.
+    pub min_aggregated_sectors: u64,
+    /// Maximum number of sectors in an aggregated proof.
+    pub max_aggregated_sectors: u64,
+    /// Maximum number of partitions that may be addressed in a single message.
+    pub addressed_partitions_max: u64,
+    /// Maximum number of sectors that may be addressed in a single message.
+    pub addressed_sectors_max: u64,
+}
+
 /// System actor.
 pub struct Actor;
 
@@ -62,6 +94,25 @@ impl Actor {
         rt.create(&state)?;
         Ok(())
     }
+
+    /// Returns the active network version and policy values.
+    pub fn get_policy(rt: &impl Runtime) -> Result<GetPolicyReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let policy = rt.policy();
+        Ok(GetPolicyReturn {
+            network_version: rt.network_version().into(),
+            minimum_verified_allocation_size: policy.minimum_verified_allocation_size.clone(),
+            minimum_verified_allocation_term: policy.minimum_verified_allocation_term,
+            maximum_verified_allocation_term: policy.maximum_verified_allocation_term,
+            maximum_verified_allocation_expiration: policy.maximum_verified_allocation_expiration,
+            min_sector_expiration: policy.min_sector_expiration,
+            max_sector_expiration_extension: policy.max_sector_expiration_extension,
+            min_aggregated_sectors: policy.min_aggregated_sectors,
+            max_aggregated_sectors: policy.max_aggregated_sectors,
+            addressed_partitions_max: policy.addressed_partitions_max,
+            addressed_sectors_max: policy.addressed_sectors_max,
+        })
+    }
 }
 
 impl ActorCode for Actor {
@@ -73,6 +124,7 @@ impl ActorCode for Actor {
 
     actor_dispatch! {
         Constructor => constructor,
+        GetPolicyExported => get_policy,
     }
 }
 
@@ -81,11 +133,16 @@ mod tests {
     use std::cell::RefCell;
 
     use fvm_shared::MethodNum;
+    use fvm_shared::address::Address;
+    use fvm_shared::version::NetworkVersion;
 
     use fil_actors_runtime::SYSTEM_ACTOR_ADDR;
-    use fil_actors_runtime::test_utils::{MockRuntime, SYSTEM_ACTOR_CODE_ID};
+    use fil_actors_runtime::runtime::Policy;
+    use fil_actors_runtime::test_utils::{
+        ACCOUNT_ACTOR_CODE_ID, MockRuntime, SYSTEM_ACTOR_CODE_ID,
+    };
 
-    use crate::{Actor, Method, State};
+    use crate::{Actor, GetPolicyReturn, Method, State};
 
     pub fn new_runtime() -> MockRuntime {
         MockRuntime {
@@ -107,4 +164,30 @@ mod tests {
         let builtin_actors = state.get_builtin_actors(&rt.store).unwrap();
         assert!(builtin_actors.is_empty());
     }
+
+    #[test]
+    fn get_policy() {
+        let mut rt = new_runtime();
+        let mut policy = Policy::default();
+        policy.maximum_verified_allocation_term = 1234;
+        policy.addressed_sectors_max = 7;
+        rt.set_policy(policy.clone());
+        rt.set_network_version(NetworkVersion::V21);
+
+        rt.expect_validate_caller_any();
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(1000));
+        let ret: GetPolicyReturn = rt
+            .call::<Actor>(Method::GetPolicyExported as MethodNum, None)
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        rt.verify();
+
+        assert_eq!(21, ret.network_version);
+        assert_eq!(1234, ret.maximum_verified_allocation_term);
+        assert_eq!(7, ret.addressed_sectors_max);
+        assert_eq!(policy.minimum_verified_allocation_size, ret.minimum_verified_allocation_size);
+        assert_eq!(policy.max_aggregated_sectors, ret.max_aggregated_sectors);
+    }
 }
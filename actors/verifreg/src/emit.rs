@@ -1,7 +1,7 @@
 // A namespace for helpers that build and emit verified registry events.
 
 use crate::{ActorError, Allocation, AllocationID, Claim};
-use crate::{ClaimID, DataCap};
+use crate::{ClaimID, DataCap, VerifierActivity};
 use cid::Cid;
 use fil_actors_runtime::EventBuilder;
 use fil_actors_runtime::runtime::Runtime;
@@ -25,7 +25,27 @@ pub fn verifier_balance(
     if let Some(client) = client {
         event = event.field_indexed("client", &client);
     }
-    rt.emit_event(&event.build()?)
+    event.emit(rt)
+}
+
+/// Indicates a verifier has granted datacap to a client, with the verifier's granting
+/// activity in the current window after the grant.
+pub fn verifier_activity(
+    rt: &impl Runtime,
+    verifier: ActorID,
+    client: ActorID,
+    amount: &DataCap,
+    activity: &VerifierActivity,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("verifier-activity")
+        .field_indexed("verifier", &verifier)
+        .field_indexed("client", &client)
+        .field("amount", &BigIntSer(amount))
+        .field("window-start", &activity.window_start)
+        .field("window-granted", &BigIntSer(&activity.window_granted))
+        .field("window-grants", &activity.window_grants)
+        .emit(rt)
 }
 
 /// Indicates a new allocation has been made.
@@ -34,15 +54,13 @@ pub fn allocation(
     id: AllocationID,
     alloc: &Allocation,
 ) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("allocation")
-            .with_parties(id, alloc.client, alloc.provider)
-            .with_piece(&alloc.data, alloc.size.0)
-            .with_term(alloc.term_min, alloc.term_max)
-            .field("expiration", &alloc.expiration)
-            .build()?,
-    )
+    EventBuilder::new()
+        .typ("allocation")
+        .with_parties(id, alloc.client, alloc.provider)
+        .with_piece(&alloc.data, alloc.size.0)
+        .with_term(alloc.term_min, alloc.term_max)
+        .field("expiration", &alloc.expiration)
+        .emit(rt)
 }
 
 /// Indicates an expired allocation has been removed.
@@ -51,57 +69,88 @@ pub fn allocation_removed(
     id: AllocationID,
     alloc: &Allocation,
 ) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("allocation-removed")
-            .with_parties(id, alloc.client, alloc.provider)
-            .with_piece(&alloc.data, alloc.size.0)
-            .with_term(alloc.term_min, alloc.term_max)
-            .field("expiration", &alloc.expiration)
-            .build()?,
-    )
+    EventBuilder::new()
+        .typ("allocation-removed")
+        .with_parties(id, alloc.client, alloc.provider)
+        .with_piece(&alloc.data, alloc.size.0)
+        .with_term(alloc.term_min, alloc.term_max)
+        .field("expiration", &alloc.expiration)
+        .emit(rt)
+}
+
+/// Indicates datacap recovered from expired allocations has been returned to their client.
+/// The amount is in datacap whole units (not TokenAmount).
+pub fn datacap_refunded(
+    rt: &impl Runtime,
+    client: ActorID,
+    amount: &DataCap,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("datacap-refunded")
+        .field_indexed("client", &client)
+        .field("amount", &BigIntSer(amount))
+        .emit(rt)
 }
 
-/// Indicates an allocation has been claimed.
-pub fn claim(rt: &impl Runtime, id: ClaimID, claim: &Claim) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("claim")
-            .with_parties(id, claim.client, claim.provider)
-            .with_piece(&claim.data, claim.size.0)
-            .with_term(claim.term_min, claim.term_max)
-            .field("term-start", &claim.term_start)
-            .field_indexed("sector", &claim.sector)
-            .build()?,
-    )
+/// Indicates an allocation has been claimed, by a sector committed until the given expiry.
+pub fn claim(
+    rt: &impl Runtime,
+    id: ClaimID,
+    claim: &Claim,
+    sector_expiry: ChainEpoch,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("claim")
+        .with_parties(id, claim.client, claim.provider)
+        .with_piece(&claim.data, claim.size.0)
+        .with_term(claim.term_min, claim.term_max)
+        .field("term-start", &claim.term_start)
+        .field_indexed("sector", &claim.sector)
+        .field("sector-expiry", &sector_expiry)
+        .emit(rt)
 }
 
 /// Indicates an existing claim has been updated (e.g. with a longer term).
 pub fn claim_updated(rt: &impl Runtime, id: ClaimID, claim: &Claim) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("claim-updated")
-            .with_parties(id, claim.client, claim.provider)
-            .with_piece(&claim.data, claim.size.0)
-            .with_term(claim.term_min, claim.term_max)
-            .field("term-start", &claim.term_start)
-            .field_indexed("sector", &claim.sector)
-            .build()?,
-    )
+    EventBuilder::new()
+        .typ("claim-updated")
+        .with_parties(id, claim.client, claim.provider)
+        .with_piece(&claim.data, claim.size.0)
+        .with_term(claim.term_min, claim.term_max)
+        .field("term-start", &claim.term_start)
+        .field_indexed("sector", &claim.sector)
+        .emit(rt)
 }
 
 /// Indicates an expired claim has been removed.
 pub fn claim_removed(rt: &impl Runtime, id: ClaimID, claim: &Claim) -> Result<(), ActorError> {
-    rt.emit_event(
-        &EventBuilder::new()
-            .typ("claim-removed")
-            .with_parties(id, claim.client, claim.provider)
-            .with_piece(&claim.data, claim.size.0)
-            .with_term(claim.term_min, claim.term_max)
-            .field("term-start", &claim.term_start)
-            .field_indexed("sector", &claim.sector)
-            .build()?,
-    )
+    EventBuilder::new()
+        .typ("claim-removed")
+        .with_parties(id, claim.client, claim.provider)
+        .with_piece(&claim.data, claim.size.0)
+        .with_term(claim.term_min, claim.term_max)
+        .field("term-start", &claim.term_start)
+        .field_indexed("sector", &claim.sector)
+        .emit(rt)
+}
+
+/// Indicates a claim has been removed because its sector was terminated before the claim's
+/// minimum term was met. The number of epochs of the minimum term not served is reported.
+pub fn claim_terminated(
+    rt: &impl Runtime,
+    id: ClaimID,
+    claim: &Claim,
+    term_lost: ChainEpoch,
+) -> Result<(), ActorError> {
+    EventBuilder::new()
+        .typ("claim-terminated")
+        .with_parties(id, claim.client, claim.provider)
+        .with_piece(&claim.data, claim.size.0)
+        .with_term(claim.term_min, claim.term_max)
+        .field("term-start", &claim.term_start)
+        .field_indexed("sector", &claim.sector)
+        .field("term-lost", &term_lost)
+        .emit(rt)
 }
 
 // Private helpers //
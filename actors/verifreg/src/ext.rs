@@ -18,6 +18,47 @@ pub mod account {
     }
 }
 
+pub mod miner {
+    use super::*;
+    use cid::Cid;
+    use fil_actors_runtime::DealWeight;
+    use fvm_shared::bigint::bigint_ser;
+    use fvm_shared::clock::ChainEpoch;
+    use fvm_shared::econ::TokenAmount;
+    use fvm_shared::sector::{RegisteredSealProof, SectorNumber};
+
+    pub const GET_SECTOR_INFO_EXPORTED: u64 = frc42_dispatch::method_hash!("GetSectorInfo");
+
+    #[derive(Serialize_tuple, Deserialize_tuple)]
+    #[serde(transparent)]
+    pub struct GetSectorInfoParams {
+        pub sector_number: SectorNumber,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple)]
+    #[serde(transparent)]
+    pub struct GetSectorInfoReturn {
+        pub sector: SectorSummary,
+    }
+
+    #[derive(Serialize_tuple, Deserialize_tuple)]
+    pub struct SectorSummary {
+        pub sector_number: SectorNumber,
+        pub seal_proof: RegisteredSealProof,
+        pub sealed_cid: Cid,
+        pub activation: ChainEpoch,
+        pub expiration: ChainEpoch,
+        #[serde(with = "bigint_ser")]
+        pub deal_weight: DealWeight,
+        #[serde(with = "bigint_ser")]
+        pub verified_deal_weight: DealWeight,
+        pub initial_pledge: TokenAmount,
+        pub power_base_epoch: ChainEpoch,
+        pub daily_fee: TokenAmount,
+        pub flags: u32,
+    }
+}
+
 pub mod datacap {
     use super::*;
     use fvm_shared::econ::TokenAmount;
@@ -1,6 +1,8 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+use std::collections::BTreeSet;
+
 use frc46_token::receiver::{FRC46_TOKEN_TYPE, FRC46TokenReceived};
 use frc46_token::token::TOKEN_PRECISION;
 use frc46_token::token::types::{BurnParams, TransferParams};
@@ -13,6 +15,7 @@ use fvm_shared::bigint::BigInt;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sector::SectorNumber;
 use fvm_shared::sys::SendFlags;
 use fvm_shared::{ActorID, METHOD_CONSTRUCTOR};
 use log::info;
@@ -24,8 +27,9 @@ use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{ActorCode, Policy, Runtime};
 use fil_actors_runtime::{ActorContext, AsActorError, BatchReturnGen};
 use fil_actors_runtime::{
-    ActorError, BatchReturn, DATACAP_TOKEN_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR,
-    SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR, actor_dispatch, actor_error,
+    ActorError, BatchReturn, Cursor, DATACAP_TOKEN_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ID,
+    OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS, STORAGE_MARKET_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR, VersionedOperatorData, actor_dispatch, actor_error,
     deserialize_block, extract_send_result, resolve_to_actor_id,
 };
 
@@ -36,7 +40,10 @@ use crate::state::{
 
 pub use self::state::Allocation;
 pub use self::state::Claim;
+pub use self::state::RegistryStats;
 pub use self::state::State;
+pub use self::state::TermReductionApproval;
+pub use self::state::VerifierActivity;
 pub use self::types::*;
 
 #[cfg(feature = "fil-actor")]
@@ -66,12 +73,21 @@ pub enum Method {
     GetClaims = 10,
     ExtendClaimTerms = 11,
     RemoveExpiredClaims = 12,
+    RemoveSectorClaims = 13,
+    AllocateFromGovernance = 14,
+    SetAllocationGovernor = 15,
     // Method numbers derived from FRC-0042 standards
     AddVerifiedClientExported = frc42_dispatch::method_hash!("AddVerifiedClient"),
     RemoveExpiredAllocationsExported = frc42_dispatch::method_hash!("RemoveExpiredAllocations"),
     GetClaimsExported = frc42_dispatch::method_hash!("GetClaims"),
     ExtendClaimTermsExported = frc42_dispatch::method_hash!("ExtendClaimTerms"),
     RemoveExpiredClaimsExported = frc42_dispatch::method_hash!("RemoveExpiredClaims"),
+    AllocateFromGovernanceExported = frc42_dispatch::method_hash!("AllocateFromGovernance"),
+    RegistryStatsExported = frc42_dispatch::method_hash!("RegistryStats"),
+    ReduceClaimTermsExported = frc42_dispatch::method_hash!("ReduceClaimTerms"),
+    GetVerifierActivityExported = frc42_dispatch::method_hash!("GetVerifierActivity"),
+    ValidateAllocationRequestsExported = frc42_dispatch::method_hash!("ValidateAllocationRequests"),
+    ListAllocationsExported = frc42_dispatch::method_hash!("ListAllocations"),
     UniversalReceiverHook = frc42_dispatch::method_hash!("Receive"),
 }
 
@@ -126,9 +142,12 @@ impl Actor {
         }
 
         // Store the new verifier and allowance (over-writing).
+        // A verifier's activity is kept when its allowance is over-written.
         rt.transaction(|st: &mut State, rt| {
             st.put_verifier(rt.store(), &verifier_addr, &params.allowance)
-                .context("failed to add verifier")
+                .context("failed to add verifier")?;
+            st.start_verifier_activity(rt.store(), verifier, rt.curr_epoch())
+                .context("failed to start verifier activity")
         })?;
 
         emit::verifier_balance(rt, verifier, &params.allowance, None)
@@ -204,12 +223,16 @@ impl Actor {
             st.put_verifier(rt.store(), &verifier_addr, &new_verifier_cap)
                 .context("failed to update verifier allowance")?;
 
-            emit::verifier_balance(
-                rt,
-                verifier_addr.id().unwrap(),
-                &new_verifier_cap,
-                Some(client.id().unwrap()),
-            )
+            let verifier_id = verifier_addr.id().unwrap();
+            let activity = st.record_verifier_grant(
+                rt.store(),
+                verifier_id,
+                rt.curr_epoch(),
+                &params.allowance,
+            )?;
+
+            emit::verifier_balance(rt, verifier_id, &new_verifier_cap, Some(client_id))?;
+            emit::verifier_activity(rt, verifier_id, client_id, &params.allowance, &activity)
         })?;
 
         // Credit client token allowance.
@@ -351,6 +374,7 @@ impl Actor {
                         .unwrap(); // Unwrapping here as both paths to here should ensure the allocation exists.
 
                     emit::allocation_removed(rt, *id, &existing)?;
+                    st.stats.remove_allocation(&existing);
 
                     // Unwrapping here as both paths to here should ensure the allocation exists.
                     recovered_datacap += existing.size.0;
@@ -368,6 +392,9 @@ impl Actor {
                 &recovered_datacap, params.client
             )
         })?;
+        if !recovered_datacap.is_zero() {
+            emit::datacap_refunded(rt, params.client, &recovered_datacap)?;
+        }
 
         Ok(RemoveExpiredAllocationsReturn {
             considered,
@@ -399,6 +426,7 @@ impl Actor {
 
         rt.transaction(|st: &mut State, rt| {
             let mut claims = st.load_claims(rt.store())?;
+            let mut sector_claims = st.load_sector_claims(rt.store())?;
             let mut allocs = st.load_allocs(rt.store())?;
 
             // Note: this doesn't prevent being called with the same sector number twice.
@@ -452,22 +480,29 @@ impl Actor {
                     if !inserted {
                         return Err(actor_error!(illegal_argument, "claim {} already exists", id));
                     }
+                    state::index_sector_claim(&mut sector_claims, &new_claim, id)?;
 
                     // Emit a claim event below
-                    emit::claim(rt, id, &new_claim)?;
+                    emit::claim(rt, id, &new_claim, sector.expiry)?;
 
                     allocs.remove(new_claim.client, id).context_code(
                         ExitCode::USR_ILLEGAL_STATE,
                         format!("failed to remove allocation {}", id),
                     )?;
+                    st.stats.claim_allocation(&new_claim);
                     sector_claimed_space += DataCap::from(new_claim.size.0);
                 }
                 total_claimed_space += &sector_claimed_space;
-                sector_results.push(SectorClaimSummary { claimed_space: sector_claimed_space });
+                sector_results.push(SectorClaimSummary {
+                    claimed_space: sector_claimed_space,
+                    term_start: rt.curr_epoch(),
+                    sector_expiry: sector.expiry,
+                });
                 batch_gen.add_success();
             }
             st.save_allocs(&mut allocs)?;
             st.save_claims(&mut claims)?;
+            st.save_sector_claims(&mut sector_claims)?;
             Ok(())
         })
         .context("state transaction failed")?;
@@ -515,6 +550,14 @@ impl Actor {
         Ok(GetClaimsReturn { batch_info: batch_gen.generate(), claims })
     }
 
+    // Returns aggregate statistics of the registry: the number of verifiers, and the total
+    // size of unclaimed allocations and of active claims.
+    pub fn registry_stats(rt: &impl Runtime) -> Result<RegistryStatsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        Ok(st.stats)
+    }
+
     /// Extends the maximum term of some claims up to the largest value they could have been
     /// originally allocated.
     /// Callable only by the claims' client.
@@ -584,6 +627,172 @@ impl Actor {
         Ok(batch_gen.generate())
     }
 
+    // Reduces the maximum term of claims, with the approval of both each claim's client
+    // and its provider.
+    // Each party calls with the same reduced term: the first call records the caller's
+    // approval, replacing any earlier pending approval for the claim, and the second,
+    // by the other party, applies the reduction.
+    // The term may not be reduced below the term already elapsed plus the claim's minimum term,
+    // nor end before the expiration of the claim's sector.
+    pub fn reduce_claim_terms(
+        rt: &impl Runtime,
+        params: ReduceClaimTermsParams,
+    ) -> Result<ReduceClaimTermsReturn, ActorError> {
+        // Permissions are checked per-claim.
+        rt.validate_immediate_caller_accept_any()?;
+        let caller_id = rt.message().caller().id().unwrap();
+        let curr_epoch = rt.curr_epoch();
+        let mut batch_gen = BatchReturnGen::new(params.terms.len());
+
+        // Query the expiration of the sector of each claim to which the caller is a party.
+        let st: State = rt.state()?;
+        let mut st_claims = st.load_claims(rt.store())?;
+        let mut sector_expirations = Vec::with_capacity(params.terms.len());
+        for term in params.terms.iter() {
+            let sector = state::get_claim(&mut st_claims, term.provider, term.claim_id)?
+                .filter(|claim| caller_id == claim.client || caller_id == claim.provider)
+                .map(|claim| claim.sector);
+            let expiration = match sector {
+                Some(sector) => get_sector_expiration(rt, term.provider, sector)?,
+                None => None,
+            };
+            sector_expirations.push(expiration);
+        }
+
+        rt.transaction(|st: &mut State, rt| {
+            let mut st_claims = st.load_claims(rt.store())?;
+            let mut reductions = st.load_claim_term_reductions(rt.store())?;
+            for (term, sector_expiration) in params.terms.into_iter().zip(sector_expirations) {
+                let maybe_claim = state::get_claim(&mut st_claims, term.provider, term.claim_id)?;
+                let Some(claim) = maybe_claim else {
+                    batch_gen.add_fail(ExitCode::USR_NOT_FOUND);
+                    info!("no claim {} for provider {}", term.claim_id, term.provider);
+                    continue;
+                };
+                // Confirm the caller is the claim's client or provider.
+                if caller_id != claim.client && caller_id != claim.provider {
+                    batch_gen.add_fail(ExitCode::USR_FORBIDDEN);
+                    info!(
+                        "caller {} is neither client {} nor provider {} of claim {}",
+                        caller_id, claim.client, claim.provider, term.claim_id,
+                    );
+                    continue;
+                }
+                // Confirm the new term limit is less than the old one.
+                if term.term_max >= claim.term_max {
+                    batch_gen.add_fail(ExitCode::USR_ILLEGAL_ARGUMENT);
+                    info!(
+                        "term_max {} for claim {} is not less than current {}",
+                        term.term_max, term.claim_id, claim.term_max,
+                    );
+                    continue;
+                }
+                // Confirm the new term limit is no less than the elapsed plus minimum term.
+                let term_floor = curr_epoch - claim.term_start + claim.term_min;
+                if term.term_max < term_floor {
+                    batch_gen.add_fail(ExitCode::USR_ILLEGAL_ARGUMENT);
+                    info!(
+                        "term_max {} for claim {} is less than elapsed plus minimum term {}",
+                        term.term_max, term.claim_id, term_floor,
+                    );
+                    continue;
+                }
+                // Confirm the new term limit doesn't end before the claim's sector expires.
+                if let Some(expiration) = sector_expiration {
+                    if claim.term_start + term.term_max < expiration {
+                        batch_gen.add_fail(ExitCode::USR_ILLEGAL_ARGUMENT);
+                        info!(
+                            "term_max {} for claim {} ends before its sector's expiration {}",
+                            term.term_max, term.claim_id, expiration,
+                        );
+                        continue;
+                    }
+                }
+
+                let approved_by_other = reductions
+                    .get(&term.claim_id)?
+                    .is_some_and(|a| a.term_max == term.term_max && a.approver != caller_id);
+                if approved_by_other {
+                    let new_claim = Claim { term_max: term.term_max, ..*claim };
+                    st_claims.put(term.provider, term.claim_id, new_claim.clone()).context_code(
+                        ExitCode::USR_ILLEGAL_STATE,
+                        "HAMT put failure storing new claims",
+                    )?;
+                    reductions.delete(&term.claim_id)?;
+                    emit::claim_updated(rt, term.claim_id, &new_claim)?;
+                } else {
+                    reductions.set(
+                        &term.claim_id,
+                        TermReductionApproval { term_max: term.term_max, approver: caller_id },
+                    )?;
+                }
+                batch_gen.add_success();
+            }
+            st.save_claims(&mut st_claims)?;
+            st.claim_term_reductions = reductions.flush()?;
+            Ok(())
+        })
+        .context("state transaction failed")?;
+        Ok(batch_gen.generate())
+    }
+
+    // Returns a verifier's record of DataCap granted to clients since the epoch it was added
+    // (or activity was first recorded), in total and within the window of its most recent grant.
+    // Fails with not found if the actor is not a verifier.
+    pub fn get_verifier_activity(
+        rt: &impl Runtime,
+        params: GetVerifierActivityParams,
+    ) -> Result<GetVerifierActivityReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let st: State = rt.state()?;
+        st.get_verifier_activity(rt.store(), params.verifier)?
+            .ok_or_else(|| actor_error!(not_found, "{} is not a verifier", params.verifier))
+    }
+
+    // Lists a client's allocations.
+    // Results are paginated; a call returns at most the requested number of allocations,
+    // up to LIST_ALLOCATIONS_MAX_PAGE_SIZE.
+    pub fn list_allocations(
+        rt: &impl Runtime,
+        params: ListAllocationsParams,
+    ) -> Result<ListAllocationsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        if params.limit == 0 {
+            return Err(actor_error!(illegal_argument, "limit must be positive"));
+        }
+        let limit = params.limit.min(LIST_ALLOCATIONS_MAX_PAGE_SIZE) as usize;
+
+        // The cursor's position is the first allocation to list.
+        let start: Option<AllocationID> = params.cursor.decode()?;
+        let st: State = rt.state()?;
+        let (allocations, next) = st.list_allocations(rt.store(), params.client, start, limit)?;
+        let next_cursor = next.map(|id| Cursor::encode(&id)).transpose()?;
+        Ok(ListAllocationsReturn { allocations, next_cursor })
+    }
+
+    // Checks allocation requests against the rules applied when allocations are made,
+    // without making them, so that callers can drop unacceptable requests beforehand.
+    pub fn validate_allocation_requests(
+        rt: &impl Runtime,
+        params: ValidateAllocationRequestsParams,
+    ) -> Result<ValidateAllocationRequestsReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let curr_epoch = rt.curr_epoch();
+        let mut batch_gen = BatchReturnGen::new(params.requests.len());
+        for req in &params.requests {
+            let validated = validate_new_allocation(req, rt.policy(), curr_epoch)
+                .and_then(|_| check_miner_id(rt, req.provider));
+            match validated {
+                Ok(()) => batch_gen.add_success(),
+                Err(e) => {
+                    info!("invalid allocation request for piece {}: {}", req.data, e);
+                    batch_gen.add_fail(e.exit_code());
+                }
+            }
+        }
+        Ok(batch_gen.generate())
+    }
+
     // A claim may be removed after its maximum term has elapsed (by anyone).
     // If no claims are specified, all eligible claims are removed.
     pub fn remove_expired_claims(
@@ -597,6 +806,8 @@ impl Actor {
         let mut considered = Vec::<ClaimID>::new();
         rt.transaction(|st: &mut State, rt| {
             let mut claims = st.load_claims(rt.store())?;
+            let mut sector_claims = st.load_sector_claims(rt.store())?;
+            let mut reductions = st.load_claim_term_reductions(rt.store())?;
             let to_remove: Vec<&ClaimID>;
             if params.claim_ids.is_empty() {
                 // Find all expired claims for the provider.
@@ -604,7 +815,7 @@ impl Actor {
                 batch_ret = BatchReturn::ok(considered.len() as u32);
                 to_remove = considered.iter().collect();
             } else {
-                considered = params.claim_ids.clone();
+                considered = params.claim_ids.to_vec();
                 batch_ret = expiration::check_expired(
                     &mut claims,
                     &params.claim_ids,
@@ -615,18 +826,19 @@ impl Actor {
             }
 
             for id in to_remove {
-                let removed = claims
-                    .remove(params.provider, *id)
-                    .context_code(
-                        ExitCode::USR_ILLEGAL_STATE,
-                        format!("failed to remove claim {}", id),
-                    )?
-                    .unwrap();
-
+                let removed = st.remove_claim(
+                    &mut claims,
+                    &mut sector_claims,
+                    &mut reductions,
+                    params.provider,
+                    *id,
+                )?;
                 emit::claim_removed(rt, *id, &removed)?;
             }
 
             st.save_claims(&mut claims)?;
+            st.save_sector_claims(&mut sector_claims)?;
+            st.claim_term_reductions = reductions.flush()?;
             Ok(())
         })
         .context("state transaction failed")?;
@@ -634,6 +846,137 @@ impl Actor {
         Ok(RemoveExpiredClaimsReturn { considered, results: batch_ret })
     }
 
+    // Removes the calling provider's claims for data committed in the given sectors, in one
+    // batch, when the provider terminates those sectors or reverts their data.
+    // Only claims whose minimum term has not been met are removed, each reported with the
+    // portion of its minimum term that was lost. Claims that have served their minimum term
+    // remain until they expire.
+    // Callable only by a miner actor, for its own claims.
+    pub fn remove_sector_claims(
+        rt: &impl Runtime,
+        params: RemoveSectorClaimsParams,
+    ) -> Result<RemoveSectorClaimsReturn, ActorError> {
+        rt.validate_immediate_caller_type(std::iter::once(&Type::Miner))?;
+        let provider = rt.message().caller().id().unwrap();
+        let max_sectors = rt.policy().addressed_sectors_max;
+        if params.sectors.len() as u64 > max_sectors {
+            return Err(actor_error!(
+                illegal_argument,
+                "too many sectors {}, max {}",
+                params.sectors.len(),
+                max_sectors
+            ));
+        }
+        let sectors: BTreeSet<SectorNumber> = params.sectors.into_iter().collect();
+        let curr_epoch = rt.curr_epoch();
+
+        let removed = rt
+            .transaction(|st: &mut State, rt| {
+                let mut claims = st.load_claims(rt.store())?;
+                let mut sector_claims = st.load_sector_claims(rt.store())?;
+                let mut reductions = st.load_claim_term_reductions(rt.store())?;
+                let removed = state::find_unmet_sector_claims(
+                    &mut claims,
+                    &mut sector_claims,
+                    provider,
+                    &sectors,
+                    curr_epoch,
+                )?;
+                for id in &removed {
+                    let claim = st.remove_claim(
+                        &mut claims,
+                        &mut sector_claims,
+                        &mut reductions,
+                        provider,
+                        *id,
+                    )?;
+                    let term_lost = claim.term_start + claim.term_min - curr_epoch;
+                    emit::claim_terminated(rt, *id, &claim, term_lost)?;
+                }
+
+                st.save_claims(&mut claims)?;
+                st.save_sector_claims(&mut sector_claims)?;
+                st.claim_term_reductions = reductions.flush()?;
+                Ok(removed)
+            })
+            .context("state transaction failed")?;
+
+        Ok(RemoveSectorClaimsReturn { removed })
+    }
+
+    // Creates allocations directly, without a transfer of datacap from a client.
+    // Only the root key, or the allocation governor it designates, may call this method,
+    // and only if the policy enables governance allocations.
+    // The caller is the client of the new allocations. Datacap for the total allocation size
+    // is minted to this actor, so the token supply accounts for the allocations just as
+    // if they had been funded by a client's transfer.
+    pub fn allocate_from_governance(
+        rt: &impl Runtime,
+        params: AllocateFromGovernanceParams,
+    ) -> Result<AllocateFromGovernanceReturn, ActorError> {
+        let st: State = rt.state()?;
+        rt.validate_immediate_caller_is(
+            std::iter::once(&st.root_key).chain(st.allocation_governor.iter()),
+        )?;
+        if !rt.policy().governance_allocations_enabled {
+            return Err(actor_error!(forbidden, "governance allocations are disabled"));
+        }
+        if params.allocations.is_empty() {
+            return Err(actor_error!(illegal_argument, "no allocations requested"));
+        }
+
+        let client = rt.message().caller().id().unwrap();
+        let curr_epoch = rt.curr_epoch();
+        let mut datacap_total = DataCap::zero();
+        let mut new_allocs = Vec::with_capacity(params.allocations.len());
+        for req in &params.allocations {
+            validate_new_allocation(req, rt.policy(), curr_epoch)?;
+            check_miner_id(rt, req.provider)?;
+            new_allocs.push(Allocation {
+                client,
+                provider: req.provider,
+                data: req.data,
+                size: req.size,
+                term_min: req.term_min,
+                term_max: req.term_max,
+                expiration: req.expiration,
+            });
+            datacap_total += DataCap::from(req.size.0);
+        }
+
+        // Mint the datacap backing the allocations to this actor.
+        // It will be burnt when the allocations are claimed, or returned to the client if they expire.
+        let my_addr = rt.message().receiver();
+        mint(rt, &my_addr, &datacap_total, vec![])
+            .context("failed to mint datacap for governance allocations")?;
+
+        let new_allocations = rt.transaction(|st: &mut State, rt| {
+            let ids = st.insert_allocations(rt.store(), client, new_allocs.clone())?;
+            for (id, alloc) in ids.iter().zip(new_allocs.iter()) {
+                emit::allocation(rt, *id, alloc)?;
+            }
+            Ok(ids)
+        })?;
+
+        Ok(AllocateFromGovernanceReturn { new_allocations })
+    }
+
+    // Sets the actor permitted to create allocations from governance alongside the root key.
+    pub fn set_allocation_governor(
+        rt: &impl Runtime,
+        params: SetAllocationGovernorParams,
+    ) -> Result<(), ActorError> {
+        let governor = params
+            .governor
+            .map(|addr| resolve_to_actor_id(rt, &addr, false).map(Address::new_id))
+            .transpose()?;
+        rt.transaction(|st: &mut State, rt| {
+            rt.validate_immediate_caller_is(std::iter::once(&st.root_key))?;
+            st.allocation_governor = governor;
+            Ok(())
+        })
+    }
+
     // Receives data cap tokens (only) and creates allocations according to one or more
     // allocation requests specified in the transfer's operator data.
     // The token amount received must exactly correspond to the sum of the requested allocation sizes.
@@ -654,9 +997,36 @@ impl Actor {
         let tokens_received = validate_tokens_received(&params, my_id)?;
         let client = tokens_received.from;
 
+        // Accept tokens minted to this actor by itself, which back allocations made by governance.
+        // The token actor reports itself as the sender of minted tokens.
+        if client == DATACAP_TOKEN_ACTOR_ID
+            && tokens_received.operator == my_id
+            && tokens_received.operator_data.is_empty()
+        {
+            return Ok(AllocationsResponse {
+                allocation_results: BatchReturn::empty(),
+                extension_results: BatchReturn::empty(),
+                new_allocations: vec![],
+            });
+        }
+
         // Extract and validate allocation request from the operator data.
         let reqs: AllocationRequests =
-            deserialize(&tokens_received.operator_data, "allocation requests")?;
+            match VersionedOperatorData::decode(&tokens_received.operator_data)? {
+                Some(VersionedOperatorData { version, payload }) => match version {
+                    OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS => {
+                        deserialize(&payload, "allocation requests")?
+                    }
+                    _ => {
+                        return Err(actor_error!(
+                            illegal_argument,
+                            "unhandled operator data version {}",
+                            version
+                        ));
+                    }
+                },
+                None => deserialize(&tokens_received.operator_data, "allocation requests")?,
+            };
         let mut datacap_total = DataCap::zero();
 
         // Construct new allocation records.
@@ -1074,6 +1444,30 @@ fn check_miner_id(rt: &impl Runtime, id: ActorID) -> Result<(), ActorError> {
     Ok(())
 }
 
+// Returns the expiration of a miner's sector, or None if the miner has no such sector.
+fn get_sector_expiration(
+    rt: &impl Runtime,
+    provider: ActorID,
+    sector_number: SectorNumber,
+) -> Result<Option<ChainEpoch>, ActorError> {
+    let res = extract_send_result(rt.send_simple(
+        &Address::new_id(provider),
+        ext::miner::GET_SECTOR_INFO_EXPORTED,
+        IpldBlock::serialize_cbor(&ext::miner::GetSectorInfoParams { sector_number })?,
+        TokenAmount::zero(),
+    ));
+    match res {
+        Ok(ret) => {
+            let ret: ext::miner::GetSectorInfoReturn = deserialize_block(ret)?;
+            Ok(Some(ret.sector.expiration))
+        }
+        Err(e) if e.exit_code() == ExitCode::USR_NOT_FOUND => Ok(None),
+        Err(e) => {
+            Err(e.wrap(format!("failed to get sector {} of provider {}", sector_number, provider)))
+        }
+    }
+}
+
 fn can_claim_alloc(
     claim_alloc: &AllocationClaim,
     provider: ActorID,
@@ -1109,6 +1503,14 @@ impl ActorCode for Actor {
         GetClaims|GetClaimsExported => get_claims,
         ExtendClaimTerms|ExtendClaimTermsExported => extend_claim_terms,
         RemoveExpiredClaims|RemoveExpiredClaimsExported => remove_expired_claims,
+        RemoveSectorClaims => remove_sector_claims,
+        AllocateFromGovernance|AllocateFromGovernanceExported => allocate_from_governance,
+        SetAllocationGovernor => set_allocation_governor,
         UniversalReceiverHook => universal_receiver_hook,
+        RegistryStatsExported => registry_stats,
+        ReduceClaimTermsExported => reduce_claim_terms,
+        GetVerifierActivityExported => get_verifier_activity,
+        ValidateAllocationRequestsExported => validate_allocation_requests,
+        ListAllocationsExported => list_allocations,
     }
 }
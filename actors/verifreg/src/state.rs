@@ -1,19 +1,24 @@
 // Copyright 2019-2022 ChainSafe Systems
 // SPDX-License-Identifier: Apache-2.0, MIT
 
+use std::collections::BTreeSet;
+
 use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::address::Address;
+use fvm_shared::bigint::bigint_ser;
 use fvm_shared::bigint::bigint_ser::BigIntDe;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::error::ExitCode;
 use fvm_shared::piece::PaddedPieceSize;
 use fvm_shared::sector::SectorNumber;
 use fvm_shared::{ActorID, HAMT_BIT_WIDTH};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 
 use fil_actors_runtime::{
-    ActorError, AsActorError, Config, DEFAULT_HAMT_CONFIG, Map2, MapMap, actor_error,
+    ActorError, AsActorError, Config, DEFAULT_HAMT_CONFIG, EPOCHS_IN_DAY, Map2, MapMap, actor_error,
 };
 
 use crate::{AddrPairKey, AllocationID, ClaimID};
@@ -25,6 +30,15 @@ pub const DATACAP_MAP_CONFIG: Config = DEFAULT_HAMT_CONFIG;
 pub type RemoveDataCapProposalMap<BS> = Map2<BS, AddrPairKey, RemoveDataCapProposalID>;
 pub const REMOVE_DATACAP_PROPOSALS_CONFIG: Config = DEFAULT_HAMT_CONFIG;
 
+pub type ClaimTermReductionMap<BS> = Map2<BS, ClaimID, TermReductionApproval>;
+pub const CLAIM_TERM_REDUCTIONS_CONFIG: Config = DEFAULT_HAMT_CONFIG;
+
+pub type VerifierActivityMap<BS> = Map2<BS, ActorID, VerifierActivity>;
+pub const VERIFIER_ACTIVITY_CONFIG: Config = DEFAULT_HAMT_CONFIG;
+
+/// Length of the epoch windows in which verifiers' grants of DataCap are counted.
+pub const VERIFIER_ACTIVITY_WINDOW: ChainEpoch = EPOCHS_IN_DAY;
+
 #[derive(Serialize_tuple, Deserialize_tuple, Debug, Clone)]
 pub struct State {
     pub root_key: Address,
@@ -38,6 +52,17 @@ pub struct State {
     pub next_allocation_id: u64,
     // Maps provider IDs to allocations claimed by that provider.
     pub claims: Cid, // HAMT[ActorID]HAMT[ClaimID]Claim
+    // An actor designated by the root key to also create allocations directly, if any.
+    pub allocation_governor: Option<Address>,
+    // Aggregate statistics, maintained as verifiers, allocations and claims are added and removed.
+    pub stats: RegistryStats,
+    // Maps claim IDs to a pending approval, by the claim's client or provider,
+    // of a reduction of the claim's maximum term.
+    pub claim_term_reductions: Cid, // HAMT[ClaimID]TermReductionApproval
+    // Maps verifier IDs to counters of the DataCap they have granted to clients.
+    pub verifier_activity: Cid, // HAMT[ActorID]VerifierActivity
+    // Maps provider IDs and sector numbers to the claims for data committed in each sector.
+    pub sector_claims: Cid, // HAMT[ActorID]HAMT[SectorNumber]Vec<ClaimID>
 }
 
 impl State {
@@ -49,6 +74,10 @@ impl State {
                 .map_err(|e| {
                     actor_error!(illegal_state, "failed to create empty multi map: {}", e)
                 })?;
+        let empty_reductions =
+            ClaimTermReductionMap::empty(store, CLAIM_TERM_REDUCTIONS_CONFIG, "empty").flush()?;
+        let empty_activity =
+            VerifierActivityMap::empty(store, VERIFIER_ACTIVITY_CONFIG, "empty").flush()?;
 
         Ok(State {
             root_key,
@@ -57,6 +86,11 @@ impl State {
             allocations: empty_allocs_claims,
             next_allocation_id: 1,
             claims: empty_allocs_claims,
+            allocation_governor: None,
+            stats: RegistryStats::default(),
+            claim_term_reductions: empty_reductions,
+            verifier_activity: empty_activity,
+            sector_claims: empty_allocs_claims,
         })
     }
 
@@ -68,7 +102,9 @@ impl State {
         cap: &DataCap,
     ) -> Result<(), ActorError> {
         let mut verifiers = self.load_verifiers(store)?;
-        verifiers.set(verifier, BigIntDe(cap.clone()))?;
+        if verifiers.set(verifier, BigIntDe(cap.clone()))?.is_none() {
+            self.stats.verifier_count += 1;
+        }
         self.verifiers = verifiers.flush()?;
         Ok(())
     }
@@ -83,6 +119,15 @@ impl State {
             .delete(verifier)?
             .context_code(ExitCode::USR_ILLEGAL_ARGUMENT, "verifier not found")?;
         self.verifiers = verifiers.flush()?;
+        // Saturates rather than panicking if the stats were not backfilled.
+        self.stats.verifier_count = self.stats.verifier_count.saturating_sub(1);
+
+        let verifier_id = verifier
+            .id()
+            .context_code(ExitCode::USR_ILLEGAL_ARGUMENT, "verifier should be an ID address")?;
+        let mut activity = self.load_verifier_activity(store)?;
+        activity.delete(&verifier_id)?;
+        self.verifier_activity = activity.flush()?;
         Ok(())
     }
 
@@ -100,6 +145,40 @@ impl State {
         DataCapMap::load(store, &self.verifiers, DATACAP_MAP_CONFIG, "verifiers")
     }
 
+    /// Lists a client's allocations in the order of its allocations HAMT, starting from an
+    /// allocation (inclusive), or from the beginning if none is given.
+    /// Returns at most `limit` allocations, and the allocation from which to resume listing
+    /// if more remain.
+    pub fn list_allocations<BS: Blockstore>(
+        &self,
+        store: &BS,
+        client: ActorID,
+        start: Option<AllocationID>,
+        limit: usize,
+    ) -> Result<(Vec<(AllocationID, Allocation)>, Option<AllocationID>), ActorError> {
+        let clients = Map2::<&BS, ActorID, Cid>::load(
+            store,
+            &self.allocations,
+            DEFAULT_HAMT_CONFIG,
+            "allocations",
+        )?;
+        let Some(root) = clients.get(&client)? else {
+            return Ok((vec![], None));
+        };
+        let allocs = Map2::<&BS, AllocationID, Allocation>::load(
+            store,
+            root,
+            DEFAULT_HAMT_CONFIG,
+            "client allocations",
+        )?;
+        let mut listed = Vec::new();
+        let next = allocs.for_each_from(start.as_ref(), limit, |id, alloc| {
+            listed.push((id, alloc.clone()));
+            Ok(())
+        })?;
+        Ok((listed, next))
+    }
+
     pub fn load_allocs<'a, BS: Blockstore>(
         &self,
         store: &'a BS,
@@ -140,12 +219,14 @@ impl State {
         let first_id = self.next_allocation_id;
         let mut count = 0;
         let count_ref = &mut count;
+        let allocated_size = &mut self.stats.allocated_size;
         allocs
             .put_many(
                 client,
                 new_allocs.into_iter().map(move |a| {
                     let id = first_id + *count_ref;
                     *count_ref += 1;
+                    *allocated_size += a.size.0;
                     (id, a)
                 }),
             )
@@ -196,7 +277,251 @@ impl State {
         self.save_claims(&mut st_claims)?;
         Ok(())
     }
+
+    pub fn load_sector_claims<'a, BS: Blockstore>(
+        &self,
+        store: &'a BS,
+    ) -> Result<MapMap<'a, BS, Vec<ClaimID>, ActorID, SectorNumber>, ActorError> {
+        MapMap::<BS, Vec<ClaimID>, ActorID, SectorNumber>::from_root(
+            store,
+            &self.sector_claims,
+            HAMT_BIT_WIDTH,
+            HAMT_BIT_WIDTH,
+        )
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load sector claims index")
+    }
+
+    pub fn save_sector_claims<BS: Blockstore>(
+        &mut self,
+        sector_claims: &mut MapMap<'_, BS, Vec<ClaimID>, ActorID, SectorNumber>,
+    ) -> Result<(), ActorError> {
+        self.sector_claims = sector_claims
+            .flush()
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to flush sector claims index")?;
+        Ok(())
+    }
+
+    // Removes a claim, with its entry in the index of claims by sector and any pending
+    // approval of a reduction of its term, returning the removed claim.
+    pub fn remove_claim<BS: Blockstore>(
+        &mut self,
+        claims: &mut MapMap<'_, BS, Claim, ActorID, ClaimID>,
+        sector_claims: &mut MapMap<'_, BS, Vec<ClaimID>, ActorID, SectorNumber>,
+        reductions: &mut ClaimTermReductionMap<&BS>,
+        provider: ActorID,
+        id: ClaimID,
+    ) -> Result<Claim, ActorError> {
+        let claim = claims
+            .remove(provider, id)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, format!("failed to remove claim {}", id))?
+            .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("claim {} not found for provider {}", id, provider)
+            })?;
+        unindex_sector_claim(sector_claims, &claim, id)?;
+        reductions.delete(&id)?;
+        self.stats.remove_claim(&claim);
+        Ok(claim)
+    }
+
+    // Rebuilds the index of claims by sector from the claims, replacing any existing index.
+    // This migrates state written before the index was maintained.
+    pub fn rebuild_sector_claims<BS: Blockstore>(&mut self, store: &BS) -> Result<(), ActorError> {
+        let mut claimed = Vec::new();
+        for_each_nested(store, &self.claims, "claims", |_, id, claim: &Claim| {
+            claimed.push((id, claim.clone()));
+            Ok(())
+        })?;
+        // Index claims in the order of their IDs, as they were made.
+        claimed.sort_unstable_by_key(|(id, _)| *id);
+        let mut sector_claims = MapMap::<BS, Vec<ClaimID>, ActorID, SectorNumber>::new(
+            store,
+            HAMT_BIT_WIDTH,
+            HAMT_BIT_WIDTH,
+        );
+        for (id, claim) in claimed {
+            index_sector_claim(&mut sector_claims, &claim, id)?;
+        }
+        self.save_sector_claims(&mut sector_claims)
+    }
+
+    // Recomputes the aggregate statistics from the verifiers, allocations and claims.
+    // This migrates state written before the statistics were maintained.
+    pub fn rebuild_stats<BS: Blockstore>(&mut self, store: &BS) -> Result<(), ActorError> {
+        let mut stats = RegistryStats::default();
+        self.load_verifiers(store)?.for_each(|_, _| {
+            stats.verifier_count += 1;
+            Ok(())
+        })?;
+        for_each_nested(store, &self.allocations, "allocations", |_, _, alloc: &Allocation| {
+            stats.allocated_size += alloc.size.0;
+            Ok(())
+        })?;
+        for_each_nested(store, &self.claims, "claims", |_, _, claim: &Claim| {
+            stats.claimed_size += claim.size.0;
+            Ok(())
+        })?;
+        self.stats = stats;
+        Ok(())
+    }
+
+    pub fn load_claim_term_reductions<BS: Blockstore>(
+        &self,
+        store: BS,
+    ) -> Result<ClaimTermReductionMap<BS>, ActorError> {
+        ClaimTermReductionMap::load(
+            store,
+            &self.claim_term_reductions,
+            CLAIM_TERM_REDUCTIONS_CONFIG,
+            "claim term reductions",
+        )
+    }
+
+    pub fn load_verifier_activity<BS: Blockstore>(
+        &self,
+        store: BS,
+    ) -> Result<VerifierActivityMap<BS>, ActorError> {
+        VerifierActivityMap::load(
+            store,
+            &self.verifier_activity,
+            VERIFIER_ACTIVITY_CONFIG,
+            "verifier activity",
+        )
+    }
+
+    pub fn get_verifier_activity<BS: Blockstore>(
+        &self,
+        store: &BS,
+        verifier: ActorID,
+    ) -> Result<Option<VerifierActivity>, ActorError> {
+        let activity = self.load_verifier_activity(store)?;
+        Ok(activity.get(&verifier)?.cloned())
+    }
+
+    // Starts counting a verifier's grants from an epoch, unless they are already counted.
+    pub fn start_verifier_activity<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        verifier: ActorID,
+        epoch: ChainEpoch,
+    ) -> Result<(), ActorError> {
+        let mut activity = self.load_verifier_activity(store)?;
+        if activity.set_if_absent(&verifier, VerifierActivity::new(epoch))? {
+            self.verifier_activity = activity.flush()?;
+        }
+        Ok(())
+    }
+
+    // Starts counting grants from an epoch for every verifier whose grants are not yet counted,
+    // such as verifiers added before activity was recorded.
+    pub fn init_verifier_activity<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        epoch: ChainEpoch,
+    ) -> Result<(), ActorError> {
+        let verifiers = self.load_verifiers(store)?;
+        let mut activity = self.load_verifier_activity(store)?;
+        verifiers.for_each(|verifier, _| {
+            let verifier_id = verifier
+                .id()
+                .context_code(ExitCode::USR_ILLEGAL_STATE, "verifier should be an ID address")?;
+            activity.set_if_absent(&verifier_id, VerifierActivity::new(epoch))?;
+            Ok(())
+        })?;
+        self.verifier_activity = activity.flush()?;
+        Ok(())
+    }
+
+    // Records a verifier's grant of DataCap to a client at an epoch,
+    // returning the verifier's updated activity.
+    pub fn record_verifier_grant<BS: Blockstore>(
+        &mut self,
+        store: &BS,
+        verifier: ActorID,
+        epoch: ChainEpoch,
+        amount: &DataCap,
+    ) -> Result<VerifierActivity, ActorError> {
+        let mut activity = self.load_verifier_activity(store)?;
+        let mut verifier_activity =
+            activity.get(&verifier)?.cloned().unwrap_or_else(|| VerifierActivity::new(epoch));
+        verifier_activity.record_grant(epoch, amount);
+        activity.set(&verifier, verifier_activity.clone())?;
+        self.verifier_activity = activity.flush()?;
+        Ok(verifier_activity)
+    }
 }
+
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegistryStats {
+    // The number of verifiers.
+    pub verifier_count: u64,
+    // The total (padded) size of allocations not yet claimed or removed.
+    #[serde(with = "bigint_ser")]
+    pub allocated_size: DataCap,
+    // The total (padded) size of claims not yet removed.
+    #[serde(with = "bigint_ser")]
+    pub claimed_size: DataCap,
+}
+
+impl RegistryStats {
+    // Records the removal of an allocation, without a claim.
+    pub fn remove_allocation(&mut self, alloc: &Allocation) {
+        self.allocated_size -= alloc.size.0;
+    }
+
+    // Records the claim of an allocation, which is removed.
+    pub fn claim_allocation(&mut self, claim: &Claim) {
+        self.allocated_size -= claim.size.0;
+        self.claimed_size += claim.size.0;
+    }
+
+    // Records the removal of a claim.
+    pub fn remove_claim(&mut self, claim: &Claim) {
+        self.claimed_size -= claim.size.0;
+    }
+}
+
+// Counters of a verifier's grants of DataCap to clients since an epoch, in total and within
+// the window of its most recent grant (see VERIFIER_ACTIVITY_WINDOW).
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerifierActivity {
+    // The epoch from which grants are counted: when the verifier was added, or when
+    // activity was first recorded for verifiers added before then.
+    pub since: ChainEpoch,
+    // The total DataCap granted.
+    #[serde(with = "bigint_ser")]
+    pub total_granted: DataCap,
+    // The total number of grants.
+    pub total_grants: u64,
+    // The first epoch of the window of the most recent grant.
+    pub window_start: ChainEpoch,
+    // The DataCap granted within that window.
+    #[serde(with = "bigint_ser")]
+    pub window_granted: DataCap,
+    // The number of grants within that window.
+    pub window_grants: u64,
+}
+
+impl VerifierActivity {
+    pub fn new(since: ChainEpoch) -> Self {
+        Self { since, ..Default::default() }
+    }
+
+    // Records a grant at an epoch, restarting the window counters if the epoch is
+    // in a later window than the most recent grant.
+    pub fn record_grant(&mut self, epoch: ChainEpoch, amount: &DataCap) {
+        let window_start = epoch - epoch.rem_euclid(VERIFIER_ACTIVITY_WINDOW);
+        if window_start != self.window_start {
+            self.window_start = window_start;
+            self.window_granted = DataCap::default();
+            self.window_grants = 0;
+        }
+        self.total_granted += amount;
+        self.total_grants += 1;
+        self.window_granted += amount;
+        self.window_grants += 1;
+    }
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq)]
 pub struct Claim {
     // The provider storing the data (from allocation).
@@ -217,6 +542,15 @@ pub struct Claim {
     pub sector: SectorNumber,
 }
 
+// One party's approval of a reduction of a claim's maximum term, awaiting the other's.
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq)]
+pub struct TermReductionApproval {
+    // The reduced maximum term approved.
+    pub term_max: ChainEpoch,
+    // The claim's client or provider which approved the reduction.
+    pub approver: ActorID,
+}
+
 #[derive(Serialize_tuple, Deserialize_tuple, Clone, Debug, PartialEq, Eq)]
 pub struct Allocation {
     // The verified client which allocated the DataCap.
@@ -262,3 +596,105 @@ where
         .get(provider, id)
         .context_code(ExitCode::USR_ILLEGAL_STATE, "HAMT lookup failure getting claim")
 }
+
+// Visits each value in a map of maps keyed by actor ID and then by a numeric ID,
+// such as the allocations by client or the claims by provider.
+fn for_each_nested<BS, V, F>(
+    store: &BS,
+    root: &Cid,
+    name: &'static str,
+    mut f: F,
+) -> Result<(), ActorError>
+where
+    BS: Blockstore,
+    V: Serialize + DeserializeOwned,
+    F: FnMut(ActorID, u64, &V) -> Result<(), ActorError>,
+{
+    let outer = Map2::<&BS, ActorID, Cid>::load(store, root, DEFAULT_HAMT_CONFIG, name)?;
+    outer.for_each(|actor, inner_root| {
+        let inner = Map2::<&BS, u64, V>::load(store, inner_root, DEFAULT_HAMT_CONFIG, name)?;
+        inner.for_each(|id, value| f(actor, id, value))
+    })
+}
+
+// Records a claim in the index of claims by sector.
+pub fn index_sector_claim<BS>(
+    sector_claims: &mut MapMap<BS, Vec<ClaimID>, ActorID, SectorNumber>,
+    claim: &Claim,
+    id: ClaimID,
+) -> Result<(), ActorError>
+where
+    BS: Blockstore,
+{
+    let mut ids = sector_claims
+        .get(claim.provider, claim.sector)
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load sector claims")?
+        .cloned()
+        .unwrap_or_default();
+    ids.push(id);
+    sector_claims
+        .put(claim.provider, claim.sector, ids)
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to put sector claims")?;
+    Ok(())
+}
+
+// Removes a claim from the index of claims by sector.
+pub fn unindex_sector_claim<BS>(
+    sector_claims: &mut MapMap<BS, Vec<ClaimID>, ActorID, SectorNumber>,
+    claim: &Claim,
+    id: ClaimID,
+) -> Result<(), ActorError>
+where
+    BS: Blockstore,
+{
+    let mut ids = sector_claims
+        .get(claim.provider, claim.sector)
+        .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load sector claims")?
+        .cloned()
+        .unwrap_or_default();
+    ids.retain(|i| *i != id);
+    if ids.is_empty() {
+        sector_claims
+            .remove(claim.provider, claim.sector)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to remove sector claims")?;
+    } else {
+        sector_claims
+            .put(claim.provider, claim.sector, ids)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to put sector claims")?;
+    }
+    Ok(())
+}
+
+// Finds a provider's claims for data committed in any of the given sectors, using the
+// index of claims by sector, whose minimum term has not been met at the current epoch.
+// Returns those claims' IDs, in ascending order.
+pub fn find_unmet_sector_claims<BS>(
+    claims: &mut MapMap<BS, Claim, ActorID, ClaimID>,
+    sector_claims: &mut MapMap<BS, Vec<ClaimID>, ActorID, SectorNumber>,
+    provider: ActorID,
+    sectors: &BTreeSet<SectorNumber>,
+    curr_epoch: ChainEpoch,
+) -> Result<Vec<ClaimID>, ActorError>
+where
+    BS: Blockstore,
+{
+    let mut found_ids = Vec::<ClaimID>::new();
+    for sector in sectors {
+        let ids = sector_claims
+            .get(provider, *sector)
+            .context_code(ExitCode::USR_ILLEGAL_STATE, "failed to load sector claims")?
+            .cloned()
+            .unwrap_or_default();
+        for id in ids {
+            let claim = get_claim(claims, provider, id)?
+                .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("indexed claim {} not found for provider {}", id, provider)
+                })?;
+            if curr_epoch < claim.term_start + claim.term_min {
+                found_ids.push(id);
+            }
+        }
+    }
+    found_ids.sort_unstable();
+    Ok(found_ids)
+}
@@ -5,6 +5,7 @@ use fvm_ipld_blockstore::Blockstore;
 use fvm_shared::ActorID;
 use fvm_shared::address::{Address, Protocol};
 use fvm_shared::clock::ChainEpoch;
+use fvm_shared::sector::SectorNumber;
 use num_traits::Signed;
 
 use fil_actors_runtime::runtime::policy_constants::{
@@ -51,6 +52,34 @@ pub fn check_state_invariants<BS: Blockstore>(
         Err(e) => acc.add(format!("error loading verifiers {e}")),
     }
 
+    // Check verifier activity is recorded for exactly the verifiers
+    match state.load_verifier_activity(&store) {
+        Ok(activity) => {
+            let mut active_verifiers = 0;
+            let ret = activity.for_each(|verifier, activity| {
+                acc.require(
+                    all_verifiers.contains_key(&Address::new_id(verifier)),
+                    format!("activity recorded for {verifier} which is not a verifier"),
+                );
+                acc.require(
+                    activity.window_grants <= activity.total_grants,
+                    format!("verifier {verifier} window grants exceed total grants"),
+                );
+                active_verifiers += 1;
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating verifier activity");
+            acc.require(
+                active_verifiers == all_verifiers.len(),
+                format!(
+                    "activity recorded for {active_verifiers} verifiers, expected {}",
+                    all_verifiers.len()
+                ),
+            );
+        }
+        Err(e) => acc.add(format!("error loading verifier activity {e}")),
+    }
+
     // Load and check allocations
     let mut all_allocations = HashMap::new();
     match state.load_allocs(&store) {
@@ -133,6 +162,100 @@ pub fn check_state_invariants<BS: Blockstore>(
         Err(e) => acc.add(format!("error loading claims {e}")),
     }
 
+    // Check the index of claims by sector
+    let mut indexed_claims = 0;
+    match state.load_sector_claims(&store) {
+        Ok(sector_claims) => {
+            let ret = sector_claims.for_each(|provider_key, inner_root| {
+                let provider_id = decode_actor_id(provider_key).unwrap();
+                let inner = Map2::<&BS, SectorNumber, Vec<ClaimID>>::load(
+                    &store,
+                    inner_root,
+                    DEFAULT_HAMT_CONFIG,
+                    "sector claims inner",
+                );
+                match inner {
+                    Ok(sectors) => {
+                        let ret = sectors.for_each(|sector, ids: &Vec<ClaimID>| {
+                            acc.require(
+                                !ids.is_empty(),
+                                format!("provider {provider_id} sector {sector} has empty claims"),
+                            );
+                            for id in ids {
+                                let indexed = all_claims.get(id).is_some_and(|c: &Claim| {
+                                    c.provider == provider_id && c.sector == sector
+                                });
+                                acc.require(
+                                    indexed,
+                                    format!(
+                                        "provider {provider_id} sector {sector} indexes claim {id} not in that sector"
+                                    ),
+                                );
+                            }
+                            indexed_claims += ids.len();
+                            Ok(())
+                        });
+                        acc.require_no_error(
+                            ret,
+                            format!("error iterating sector claims inner for {provider_id}"),
+                        );
+                    }
+                    Err(e) => acc.add(format!("error loading sector claims {e}")),
+                }
+                Ok(())
+            });
+
+            acc.require_no_error(ret, "error iterating sector claims outer");
+        }
+        Err(e) => acc.add(format!("error loading sector claims {e}")),
+    }
+    acc.require(
+        indexed_claims == all_claims.len(),
+        format!(
+            "sector claims index has {} entries for {} claims",
+            indexed_claims,
+            all_claims.len()
+        ),
+    );
+
+    // Check pending term reductions are for existing claims
+    match state.load_claim_term_reductions(&store) {
+        Ok(reductions) => {
+            let ret = reductions.for_each(|claim_id, _| {
+                acc.require(
+                    all_claims.contains_key(&claim_id),
+                    format!("term reduction for missing claim {claim_id}"),
+                );
+                Ok(())
+            });
+            acc.require_no_error(ret, "error iterating claim term reductions");
+        }
+        Err(e) => acc.add(format!("error loading claim term reductions {e}")),
+    }
+
+    // Check aggregate statistics
+    acc.require(
+        state.stats.verifier_count == all_verifiers.len() as u64,
+        format!(
+            "verifier count {} doesn't match verifiers {}",
+            state.stats.verifier_count,
+            all_verifiers.len()
+        ),
+    );
+    let allocated_size: u64 = all_allocations.values().map(|a| a.size.0).sum();
+    acc.require(
+        state.stats.allocated_size == DataCap::from(allocated_size),
+        format!(
+            "allocated size {} doesn't match allocations {}",
+            state.stats.allocated_size, allocated_size
+        ),
+    );
+    let claimed_size: u64 = all_claims.values().map(|c| c.size.0).sum();
+    acc.require(
+        state.stats.claimed_size == DataCap::from(claimed_size),
+        format!("claimed size {} doesn't match claims {}", state.stats.claimed_size, claimed_size),
+    );
+
     (
         StateSummary { verifiers: all_verifiers, allocations: all_allocations, claims: all_claims },
         acc,
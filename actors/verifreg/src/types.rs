@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 use cid::Cid;
-use fil_actors_runtime::{BatchReturn, MapKey};
+use fil_actors_runtime::runtime::policy_constants::MAX_CLAIMS_PER_REQUEST;
+use fil_actors_runtime::{BatchReturn, BoundedVec, Cursor, MapKey};
 use fvm_ipld_encoding::tuple::*;
 use fvm_shared::ActorID;
 use fvm_shared::address::Address;
@@ -14,7 +15,7 @@ use fvm_shared::sector::SectorNumber;
 use fvm_shared::sector::StoragePower;
 use std::fmt::{Debug, Formatter};
 
-use crate::Claim;
+use crate::{Allocation, Claim, RegistryStats, VerifierActivity};
 
 pub type AllocationID = u64;
 pub type ClaimID = u64;
@@ -159,10 +160,13 @@ pub struct ClaimAllocationsParams {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Default, Serialize_tuple, Deserialize_tuple)]
-#[serde(transparent)]
 pub struct SectorClaimSummary {
     #[serde(with = "bigint_ser")]
     pub claimed_space: BigInt,
+    /// The term start of the sector's new claims, which is the epoch at which they were made.
+    pub term_start: ChainEpoch,
+    /// The expiration to which the sector is committed.
+    pub sector_expiry: ChainEpoch,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
@@ -173,6 +177,26 @@ pub struct ClaimAllocationsReturn {
     pub sector_claims: Vec<SectorClaimSummary>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct AllocateFromGovernanceParams {
+    pub allocations: Vec<AllocationRequest>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct AllocateFromGovernanceReturn {
+    // IDs of new allocations created.
+    pub new_allocations: Vec<AllocationID>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct SetAllocationGovernorParams {
+    // The actor permitted to create allocations from governance, or None to permit only the root.
+    pub governor: Option<Address>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct ClaimTerm {
     pub provider: ActorID,
@@ -182,11 +206,57 @@ pub struct ClaimTerm {
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct ExtendClaimTermsParams {
-    pub terms: Vec<ClaimTerm>,
+    pub terms: BoundedVec<ClaimTerm, MAX_CLAIMS_PER_REQUEST>,
 }
 
 pub type ExtendClaimTermsReturn = BatchReturn;
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ReduceClaimTermsParams {
+    pub terms: BoundedVec<ClaimTerm, MAX_CLAIMS_PER_REQUEST>,
+}
+
+pub type ReduceClaimTermsReturn = BatchReturn;
+
+pub type RegistryStatsReturn = RegistryStats;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct GetVerifierActivityParams {
+    pub verifier: ActorID,
+}
+
+pub type GetVerifierActivityReturn = VerifierActivity;
+
+/// The maximum number of allocations returned by a call to ListAllocations.
+pub const LIST_ALLOCATIONS_MAX_PAGE_SIZE: u64 = 1000;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ListAllocationsParams {
+    /// The client whose allocations to list.
+    pub client: ActorID,
+    /// The start cursor to list from the beginning; otherwise the next_cursor of a previous call.
+    pub cursor: Cursor,
+    /// Maximum number of allocations to return, capped at LIST_ALLOCATIONS_MAX_PAGE_SIZE.
+    pub limit: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ListAllocationsReturn {
+    /// The client's allocations with their IDs, in no particular order.
+    pub allocations: Vec<(AllocationID, Allocation)>,
+    /// Cursor from which to continue the listing, if more allocations remain.
+    pub next_cursor: Option<Cursor>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ValidateAllocationRequestsParams {
+    pub requests: Vec<AllocationRequest>,
+}
+
+pub type ValidateAllocationRequestsReturn = BatchReturn;
+
 //
 // Receiver hook payload
 //
@@ -233,7 +303,7 @@ pub struct AllocationsResponse {
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
 pub struct GetClaimsParams {
     pub provider: ActorID,
-    pub claim_ids: Vec<ClaimID>,
+    pub claim_ids: BoundedVec<ClaimID, MAX_CLAIMS_PER_REQUEST>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
@@ -248,7 +318,7 @@ pub struct RemoveExpiredClaimsParams {
     pub provider: ActorID,
     // Optional list of claim IDs to attempt to remove.
     // Empty means remove all eligible expired claims.
-    pub claim_ids: Vec<ClaimID>,
+    pub claim_ids: BoundedVec<ClaimID, MAX_CLAIMS_PER_REQUEST>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
@@ -258,3 +328,15 @@ pub struct RemoveExpiredClaimsReturn {
     // Results for each processed claim.
     pub results: BatchReturn,
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct RemoveSectorClaimsParams {
+    // Sectors of the calling provider for which to remove claims within their minimum term.
+    pub sectors: Vec<SectorNumber>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct RemoveSectorClaimsReturn {
+    // Ids of the claims removed.
+    pub removed: Vec<ClaimID>,
+}
@@ -3,32 +3,38 @@ use std::collections::HashMap;
 
 use cid::Cid;
 
-use frc46_token::receiver::{FRC46_TOKEN_TYPE, FRC46TokenReceived};
+use frc46_token::receiver::FRC46TokenReceived;
 use frc46_token::token::TOKEN_PRECISION;
-use frc46_token::token::types::{BurnParams, BurnReturn, TransferParams};
-use fvm_actor_utils::receiver::UniversalReceiverParams;
+use frc46_token::token::types::TransferParams;
 use fvm_ipld_encoding::RawBytes;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
+use fvm_shared::bigint::BigInt;
 use fvm_shared::bigint::bigint_ser::BigIntSer;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 use fvm_shared::piece::PaddedPieceSize;
-use fvm_shared::sector::SectorNumber;
+use fvm_shared::sector::{RegisteredSealProof, SectorNumber};
 use fvm_shared::sys::SendFlags;
 use fvm_shared::{ActorID, MethodNum};
 use num_traits::{ToPrimitive, Zero};
 
+use fil_actor_verifreg::state;
 use fil_actor_verifreg::state::{DATACAP_MAP_CONFIG, DataCapMap};
 use fil_actor_verifreg::testing::check_state_invariants;
 use fil_actor_verifreg::{
-    Actor as VerifregActor, AddVerifiedClientParams, AddVerifierParams, Allocation,
-    AllocationClaim, AllocationID, AllocationRequest, AllocationRequests, AllocationsResponse,
-    Claim, ClaimAllocationsParams, ClaimAllocationsReturn, ClaimExtensionRequest, ClaimID, DataCap,
-    ExtendClaimTermsParams, ExtendClaimTermsReturn, GetClaimsParams, GetClaimsReturn, Method,
-    RemoveExpiredAllocationsParams, RemoveExpiredAllocationsReturn, RemoveExpiredClaimsParams,
-    RemoveExpiredClaimsReturn, SectorAllocationClaims, State, ext,
+    Actor as VerifregActor, AddVerifiedClientParams, AddVerifierParams,
+    AllocateFromGovernanceParams, AllocateFromGovernanceReturn, Allocation, AllocationClaim,
+    AllocationID, AllocationRequest, AllocationRequests, AllocationsResponse, Claim,
+    ClaimAllocationsParams, ClaimAllocationsReturn, ClaimExtensionRequest, ClaimID, DataCap,
+    ExtendClaimTermsParams, ExtendClaimTermsReturn, GetClaimsParams, GetClaimsReturn,
+    GetVerifierActivityParams, GetVerifierActivityReturn, ListAllocationsParams,
+    ListAllocationsReturn, Method, ReduceClaimTermsParams, ReduceClaimTermsReturn,
+    RegistryStatsReturn, RemoveExpiredAllocationsParams, RemoveExpiredAllocationsReturn,
+    RemoveExpiredClaimsParams, RemoveExpiredClaimsReturn, RemoveSectorClaimsParams,
+    RemoveSectorClaimsReturn, SectorAllocationClaims, SetAllocationGovernorParams, State,
+    ValidateAllocationRequestsParams, ValidateAllocationRequestsReturn, VerifierActivity, ext,
 };
 use fil_actors_runtime::cbor::serialize;
 use fil_actors_runtime::runtime::Runtime;
@@ -38,8 +44,9 @@ use fil_actors_runtime::runtime::policy_constants::{
 };
 use fil_actors_runtime::test_utils::*;
 use fil_actors_runtime::{
-    ActorError, AsActorError, BatchReturn, DATACAP_TOKEN_ACTOR_ADDR, EventBuilder,
+    ActorError, AsActorError, BatchReturn, Cursor, DATACAP_TOKEN_ACTOR_ADDR, EventBuilder,
     STORAGE_MARKET_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    VersionedOperatorData,
 };
 
 pub const ROOT_ADDR: Address = Address::new_id(101);
@@ -194,6 +201,13 @@ impl Harness {
         assert_eq!(*allowance, self.get_verifier_allowance(rt, &verifier_id_addr));
     }
 
+    pub fn get_verifier_activity(&self, rt: &MockRuntime, verifier: ActorID) -> VerifierActivity {
+        rt.get_state::<State>()
+            .get_verifier_activity(&rt.store, verifier)
+            .unwrap()
+            .unwrap_or_default()
+    }
+
     pub fn get_verifier_allowance(&self, rt: &MockRuntime, verifier: &Address) -> DataCap {
         let verifiers = rt.get_state::<State>().load_verifiers(&rt.store).unwrap();
         verifiers.get(verifier).unwrap().unwrap().clone().0
@@ -218,18 +232,10 @@ impl Harness {
         let client_resolved = rt.get_id_address(client).unwrap_or(*client);
 
         // Expect tokens to be minted.
-        let mint_params = ext::datacap::MintParams {
-            to: client_resolved,
-            amount: TokenAmount::from_whole(allowance.to_i64().unwrap()),
-            operators: vec![STORAGE_MARKET_ACTOR_ADDR],
-        };
-        rt.expect_send_simple(
-            DATACAP_TOKEN_ACTOR_ADDR,
-            ext::datacap::Method::Mint as MethodNum,
-            IpldBlock::serialize_cbor(&mint_params).unwrap(),
-            TokenAmount::zero(),
-            None,
-            ExitCode::OK,
+        rt.expect_datacap_mint(
+            client_resolved,
+            TokenAmount::from_whole(allowance.to_i64().unwrap()),
+            vec![STORAGE_MARKET_ACTOR_ADDR],
         );
 
         let params = AddVerifiedClientParams { address: *client, allowance: allowance.clone() };
@@ -243,6 +249,19 @@ impl Harness {
                     .field_indexed("client", &client_resolved.id().unwrap())
                     .build()?,
             );
+            let mut activity = self.get_verifier_activity(rt, verifier.id().unwrap());
+            activity.record_grant(*rt.epoch.borrow(), allowance);
+            rt.expect_emitted_event(
+                EventBuilder::new()
+                    .typ("verifier-activity")
+                    .field_indexed("verifier", &verifier.id().unwrap())
+                    .field_indexed("client", &client_resolved.id().unwrap())
+                    .field("amount", &BigIntSer(allowance))
+                    .field("window-start", &activity.window_start)
+                    .field("window-granted", &BigIntSer(&activity.window_granted))
+                    .field("window-grants", &activity.window_grants)
+                    .build()?,
+            );
         }
         let ret = rt.call::<VerifregActor>(
             Method::AddVerifiedClient as MethodNum,
@@ -274,11 +293,33 @@ impl Harness {
                 .context_code(ExitCode::USR_ILLEGAL_STATE, "faild to put")?
         );
         st.next_allocation_id += 1;
+        st.stats.allocated_size += alloc.size.0;
         st.allocations = allocs.flush().expect("failed flushing allocation table");
         rt.replace_state(&st);
         Ok(alloc_id)
     }
 
+    pub fn list_allocations(
+        &self,
+        rt: &MockRuntime,
+        client: ActorID,
+        cursor: Cursor,
+        limit: u64,
+    ) -> Result<ListAllocationsReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = ListAllocationsParams { client, cursor, limit };
+        let ret = rt
+            .call::<VerifregActor>(
+                Method::ListAllocationsExported as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize list allocations return");
+        rt.verify();
+        Ok(ret)
+    }
+
     pub fn load_alloc(
         &self,
         rt: &MockRuntime,
@@ -304,32 +345,15 @@ impl Harness {
         rt.set_caller(*MINER_ACTOR_CODE_ID, Address::new_id(provider));
 
         for (id, alloc, sector) in expect_claimed.iter() {
-            expect_claim_emitted(
-                rt,
-                "claim",
-                *id,
-                alloc.client,
-                alloc.provider,
-                &alloc.data,
-                alloc.size.0,
-                *sector,
-                alloc.term_min,
-                alloc.term_max,
-                0,
-            )
+            let sector_expiry =
+                claim_allocs.iter().find(|claims| claims.sector == *sector).unwrap().expiry;
+            expect_new_claim_emitted(rt, *id, alloc, *sector, 0, sector_expiry)
         }
 
         if datacap_burnt > 0 {
-            rt.expect_send_simple(
-                DATACAP_TOKEN_ACTOR_ADDR,
-                ext::datacap::Method::Burn as MethodNum,
-                IpldBlock::serialize_cbor(&BurnParams {
-                    amount: TokenAmount::from_whole(datacap_burnt.to_i64().unwrap()),
-                })
-                .unwrap(),
+            rt.expect_datacap_burn(
+                TokenAmount::from_whole(datacap_burnt.to_i64().unwrap()),
                 TokenAmount::zero(),
-                IpldBlock::serialize_cbor(&BurnReturn { balance: TokenAmount::zero() }).unwrap(),
-                ExitCode::OK,
             );
         }
 
@@ -372,19 +396,21 @@ impl Harness {
                 alloc.expiration,
             )
         }
-        rt.expect_send_simple(
-            DATACAP_TOKEN_ACTOR_ADDR,
-            ext::datacap::Method::Transfer as MethodNum,
-            IpldBlock::serialize_cbor(&TransferParams {
-                to: Address::new_id(client),
-                amount: TokenAmount::from_whole(expected_datacap.to_i64().unwrap()),
-                operator_data: RawBytes::default(),
-            })
-            .unwrap(),
-            TokenAmount::zero(),
-            None,
-            ExitCode::OK,
-        );
+        rt.expect_datacap_transfer(TransferParams {
+            to: Address::new_id(client),
+            amount: TokenAmount::from_whole(expected_datacap.to_i64().unwrap()),
+            operator_data: RawBytes::default(),
+        });
+        if expected_datacap > 0 {
+            rt.expect_emitted_event(
+                EventBuilder::new()
+                    .typ("datacap-refunded")
+                    .field_indexed("client", &client)
+                    .field("amount", &BigIntSer(&DataCap::from(expected_datacap)))
+                    .build()
+                    .unwrap(),
+            );
+        }
 
         let params = RemoveExpiredAllocationsParams { client, allocation_ids };
         let ret = rt
@@ -425,7 +451,7 @@ impl Harness {
             )
         }
 
-        let params = RemoveExpiredClaimsParams { provider, claim_ids };
+        let params = RemoveExpiredClaimsParams { provider, claim_ids: claim_ids.into() };
         let ret = rt
             .call::<VerifregActor>(
                 Method::RemoveExpiredClaims as MethodNum,
@@ -438,6 +464,35 @@ impl Harness {
         Ok(ret)
     }
 
+    // Invokes the RemoveSectorClaims actor method from the provider.
+    pub fn remove_sector_claims(
+        &self,
+        rt: &MockRuntime,
+        provider: ActorID,
+        sectors: Vec<SectorNumber>,
+        expect_removed: Vec<(ClaimID, Claim)>,
+    ) -> Result<RemoveSectorClaimsReturn, ActorError> {
+        rt.set_caller(*MINER_ACTOR_CODE_ID, Address::new_id(provider));
+        rt.expect_validate_caller_type(vec![Type::Miner]);
+
+        let curr_epoch = *rt.epoch.borrow();
+        for (id, claim) in expect_removed {
+            expect_claim_terminated_emitted(rt, id, &claim, curr_epoch);
+        }
+
+        let params = RemoveSectorClaimsParams { sectors };
+        let ret = rt
+            .call::<VerifregActor>(
+                Method::RemoveSectorClaims as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize remove sector claims return");
+        rt.verify();
+        Ok(ret)
+    }
+
     pub fn load_claim(&self, rt: &MockRuntime, provider: ActorID, id: ClaimID) -> Option<Claim> {
         let st: State = rt.get_state();
         let mut claims = st.load_claims(rt.store()).unwrap();
@@ -454,26 +509,17 @@ impl Harness {
         expected_burn: u64,
     ) -> Result<(), ActorError> {
         rt.set_caller(*DATACAP_TOKEN_ACTOR_CODE_ID, DATACAP_TOKEN_ACTOR_ADDR);
-        let params = UniversalReceiverParams {
-            type_: FRC46_TOKEN_TYPE,
-            payload: serialize(&payload, "payload").unwrap(),
-        };
+        let params = make_frc46_receiver_hook_params(&payload);
 
         if !expected_burn.is_zero() {
-            rt.expect_send_simple(
-                DATACAP_TOKEN_ACTOR_ADDR,
-                ext::datacap::Method::Burn as MethodNum,
-                IpldBlock::serialize_cbor(&BurnParams {
-                    amount: TokenAmount::from_whole(expected_burn),
-                })
-                .unwrap(),
-                TokenAmount::zero(),
-                IpldBlock::serialize_cbor(&BurnReturn { balance: TokenAmount::zero() }).unwrap(),
-                ExitCode::OK,
-            );
+            rt.expect_datacap_burn(TokenAmount::from_whole(expected_burn), TokenAmount::zero());
         }
 
-        let allocs_req: AllocationRequests = payload.operator_data.deserialize().unwrap();
+        let operator_data = match VersionedOperatorData::decode(&payload.operator_data).unwrap() {
+            Some(versioned) => versioned.payload,
+            None => payload.operator_data.clone(),
+        };
+        let allocs_req: AllocationRequests = operator_data.deserialize().unwrap();
         for (alloc, id) in allocs_req.allocations.iter().zip(expected_alloc_ids.iter()) {
             expect_allocation_emitted(
                 rt,
@@ -524,6 +570,72 @@ impl Harness {
         Ok(())
     }
 
+    pub fn allocate_from_governance(
+        &self,
+        rt: &MockRuntime,
+        caller: &Address,
+        reqs: Vec<AllocationRequest>,
+        expected_alloc_ids: Vec<AllocationID>,
+    ) -> Result<(), ActorError> {
+        let st: State = rt.get_state();
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, *caller);
+        rt.expect_validate_caller_addr(
+            std::iter::once(st.root_key).chain(st.allocation_governor).collect(),
+        );
+        let total_size: u64 = reqs.iter().map(|r| r.size.0).sum();
+        rt.expect_datacap_mint(
+            VERIFIED_REGISTRY_ACTOR_ADDR,
+            TokenAmount::from_whole(total_size),
+            vec![],
+        );
+        let client = caller.id().unwrap();
+        for (alloc, id) in reqs.iter().zip(expected_alloc_ids.iter()) {
+            expect_allocation_emitted(
+                rt,
+                "allocation",
+                *id,
+                client,
+                alloc.provider,
+                &alloc.data,
+                alloc.size.0,
+                alloc.term_min,
+                alloc.term_max,
+                alloc.expiration,
+            )
+        }
+
+        let params = AllocateFromGovernanceParams { allocations: reqs };
+        let ret: AllocateFromGovernanceReturn = rt
+            .call::<VerifregActor>(
+                Method::AllocateFromGovernance as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize allocate from governance return");
+        assert_eq!(expected_alloc_ids, ret.new_allocations);
+        rt.verify();
+        Ok(())
+    }
+
+    pub fn set_allocation_governor(
+        &self,
+        rt: &MockRuntime,
+        governor: Option<Address>,
+    ) -> Result<(), ActorError> {
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, self.root);
+        rt.expect_validate_caller_addr(vec![self.root]);
+        let params = SetAllocationGovernorParams { governor };
+        rt.call::<VerifregActor>(
+            Method::SetAllocationGovernor as MethodNum,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+        )?;
+        rt.verify();
+        let st: State = rt.get_state();
+        assert_eq!(governor, st.allocation_governor);
+        Ok(())
+    }
+
     // Creates a claim directly in state.
     pub fn create_claim(&self, rt: &MockRuntime, claim: &Claim) -> Result<ClaimID, ActorError> {
         let mut st: State = rt.get_state();
@@ -534,8 +646,12 @@ impl Harness {
                 .put_if_absent(claim.provider, id, claim.clone())
                 .context_code(ExitCode::USR_ILLEGAL_STATE, "faild to put")?
         );
+        let mut sector_claims = st.load_sector_claims(rt.store()).unwrap();
+        state::index_sector_claim(&mut sector_claims, claim, id)?;
         st.next_allocation_id += 1;
+        st.stats.claimed_size += claim.size.0;
         st.claims = claims.flush().expect("failed flushing allocation table");
+        st.sector_claims = sector_claims.flush().expect("failed flushing sector claims index");
         rt.replace_state(&st);
         Ok(id)
     }
@@ -547,7 +663,7 @@ impl Harness {
         claim_ids: Vec<ClaimID>,
     ) -> Result<GetClaimsReturn, ActorError> {
         rt.expect_validate_caller_any();
-        let params = GetClaimsParams { claim_ids, provider };
+        let params = GetClaimsParams { claim_ids: claim_ids.into(), provider };
         let ret = rt
             .call::<VerifregActor>(
                 Method::GetClaims as MethodNum,
@@ -560,6 +676,57 @@ impl Harness {
         Ok(ret)
     }
 
+    pub fn registry_stats(&self, rt: &MockRuntime) -> RegistryStatsReturn {
+        rt.expect_validate_caller_any();
+        let ret = rt
+            .call::<VerifregActor>(Method::RegistryStatsExported as MethodNum, None)
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize registry stats return");
+        rt.verify();
+        ret
+    }
+
+    pub fn query_verifier_activity(
+        &self,
+        rt: &MockRuntime,
+        verifier: ActorID,
+    ) -> Result<GetVerifierActivityReturn, ActorError> {
+        rt.expect_validate_caller_any();
+        let params = GetVerifierActivityParams { verifier };
+        let ret = rt
+            .call::<VerifregActor>(
+                Method::GetVerifierActivityExported as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize verifier activity return");
+        rt.verify();
+        Ok(ret)
+    }
+
+    pub fn validate_allocation_requests(
+        &self,
+        rt: &MockRuntime,
+        requests: Vec<AllocationRequest>,
+    ) -> ValidateAllocationRequestsReturn {
+        rt.expect_validate_caller_any();
+        let params = ValidateAllocationRequestsParams { requests };
+        let ret = rt
+            .call::<VerifregActor>(
+                Method::ValidateAllocationRequestsExported as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )
+            .unwrap()
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize validate allocation requests return");
+        rt.verify();
+        ret
+    }
+
     pub fn extend_claim_terms(
         &self,
         rt: &MockRuntime,
@@ -596,6 +763,80 @@ impl Harness {
         rt.verify();
         Ok(ret)
     }
+
+    // Expects the sector of each claim to which the caller is a party to be queried from its
+    // provider, expiring at sector_expiration (or not found if None), and the claims listed in
+    // expected to be updated with the reduced terms.
+    pub fn reduce_claim_terms(
+        &self,
+        rt: &MockRuntime,
+        params: &ReduceClaimTermsParams,
+        sector_expiration: Option<ChainEpoch>,
+        expected: Vec<(ClaimID, Claim)>,
+    ) -> Result<ReduceClaimTermsReturn, ActorError> {
+        let caller = rt.caller.borrow().id().unwrap();
+        let st: State = rt.get_state();
+        let mut claims = st.load_claims(rt.store()).unwrap();
+        for term in params.terms.iter() {
+            let Some(claim) = state::get_claim(&mut claims, term.provider, term.claim_id).unwrap()
+            else {
+                continue;
+            };
+            if caller != claim.client && caller != claim.provider {
+                continue;
+            }
+            let (ret, code) = match sector_expiration {
+                Some(expiration) => (
+                    IpldBlock::serialize_cbor(&ext::miner::GetSectorInfoReturn {
+                        sector: make_sector_summary(claim.sector, expiration),
+                    })
+                    .unwrap(),
+                    ExitCode::OK,
+                ),
+                None => (None, ExitCode::USR_NOT_FOUND),
+            };
+            rt.expect_send_simple(
+                Address::new_id(term.provider),
+                ext::miner::GET_SECTOR_INFO_EXPORTED,
+                IpldBlock::serialize_cbor(&ext::miner::GetSectorInfoParams {
+                    sector_number: claim.sector,
+                })
+                .unwrap(),
+                TokenAmount::zero(),
+                ret,
+                code,
+            );
+        }
+        for (id, mut new_claim) in expected {
+            let red = params.terms.iter().find(|c| c.claim_id == id).unwrap();
+            new_claim.term_max = red.term_max;
+            expect_claim_emitted(
+                rt,
+                "claim-updated",
+                id,
+                new_claim.client,
+                new_claim.provider,
+                &new_claim.data,
+                new_claim.size.0,
+                new_claim.sector,
+                new_claim.term_min,
+                new_claim.term_max,
+                new_claim.term_start,
+            )
+        }
+
+        rt.expect_validate_caller_any();
+        let ret = rt
+            .call::<VerifregActor>(
+                Method::ReduceClaimTermsExported as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            )?
+            .unwrap()
+            .deserialize()
+            .expect("failed to deserialize reduce claim terms return");
+        rt.verify();
+        Ok(ret)
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -658,6 +899,56 @@ pub fn expect_claim_emitted(
     );
 }
 
+pub fn expect_claim_terminated_emitted(
+    rt: &MockRuntime,
+    id: ClaimID,
+    claim: &Claim,
+    curr_epoch: ChainEpoch,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("claim-terminated")
+            .field_indexed("id", &id)
+            .field_indexed("client", &claim.client)
+            .field_indexed("provider", &claim.provider)
+            .field_indexed("piece-cid", &claim.data)
+            .field("piece-size", &claim.size.0)
+            .field("term-min", &claim.term_min)
+            .field("term-max", &claim.term_max)
+            .field("term-start", &claim.term_start)
+            .field_indexed("sector", &claim.sector)
+            .field("term-lost", &(claim.term_start + claim.term_min - curr_epoch))
+            .build()
+            .unwrap(),
+    );
+}
+
+pub fn expect_new_claim_emitted(
+    rt: &MockRuntime,
+    id: u64,
+    alloc: &Allocation,
+    sector: SectorNumber,
+    term_start: ChainEpoch,
+    sector_expiry: ChainEpoch,
+) {
+    rt.expect_emitted_event(
+        EventBuilder::new()
+            .typ("claim")
+            .field_indexed("id", &id)
+            .field_indexed("client", &alloc.client)
+            .field_indexed("provider", &alloc.provider)
+            .field_indexed("piece-cid", &alloc.data)
+            .field("piece-size", &alloc.size.0)
+            .field("term-min", &alloc.term_min)
+            .field("term-max", &alloc.term_max)
+            .field("term-start", &term_start)
+            .field_indexed("sector", &sector)
+            .field("sector-expiry", &sector_expiry)
+            .build()
+            .unwrap(),
+    );
+}
+
 pub fn make_alloc(data_id: &str, client: ActorID, provider: ActorID, size: u64) -> Allocation {
     Allocation {
         client,
@@ -724,6 +1015,25 @@ pub fn make_claim_reqs(
 }
 
 #[allow(clippy::too_many_arguments)]
+pub fn make_sector_summary(
+    sector_number: SectorNumber,
+    expiration: ChainEpoch,
+) -> ext::miner::SectorSummary {
+    ext::miner::SectorSummary {
+        sector_number,
+        seal_proof: RegisteredSealProof::StackedDRG32GiBV1P1,
+        sealed_cid: make_sealed_cid(b"sealed"),
+        activation: 0,
+        expiration,
+        deal_weight: BigInt::zero(),
+        verified_deal_weight: BigInt::zero(),
+        initial_pledge: TokenAmount::zero(),
+        power_base_epoch: 0,
+        daily_fee: TokenAmount::zero(),
+        flags: 0,
+    }
+}
+
 pub fn make_claim(
     data_id: &str,
     client: ActorID,
@@ -768,14 +1078,13 @@ pub fn make_receiver_hook_token_payload(
     // let total_size: u64 = alloc_requests.iter().map(|r| r.size.0).sum();
     let payload =
         AllocationRequests { allocations: alloc_requests, extensions: extension_requests };
-    FRC46TokenReceived {
-        from: client,
-        to: VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap(),
-        operator: client,
-        amount: TokenAmount::from_whole(datacap_received as i64),
-        operator_data: serialize(&payload, "operator data").unwrap(),
-        token_data: Default::default(),
-    }
+    make_frc46_token_received(
+        client,
+        VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap(),
+        client,
+        TokenAmount::from_whole(datacap_received as i64),
+        serialize(&payload, "operator data").unwrap(),
+    )
 }
 
 pub fn assert_allocation(
@@ -86,6 +86,7 @@ mod verifiers {
     use fvm_shared::{METHOD_SEND, MethodNum};
 
     use fil_actor_verifreg::{Actor as VerifregActor, AddVerifierParams, DataCap, Method};
+    use fil_actors_runtime::DATACAP_TOKEN_ACTOR_ADDR;
     use fil_actors_runtime::test_utils::*;
     use harness::*;
     use util::*;
@@ -132,6 +133,28 @@ mod verifiers {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn add_verifier_side_effects_recorded_per_call() {
+        let (h, rt) = new_harness();
+        let allowance = verifier_allowance(&rt);
+        for verifier in [*VERIFIER, *VERIFIER2] {
+            h.add_verifier(&rt, &verifier, &allowance).unwrap();
+            let record = rt.call_record.take();
+            assert_eq!(1, record.events.len());
+            assert_eq!(1, record.sends.len());
+            assert_eq!(DATACAP_TOKEN_ACTOR_ADDR, record.sends[0].to);
+            assert!(record.state_changed());
+        }
+
+        // An aborted call leaves the state unchanged and its events are discarded.
+        expect_abort(ExitCode::USR_ILLEGAL_ARGUMENT, h.add_verifier(&rt, &ROOT_ADDR, &allowance));
+        rt.reset();
+        let record = rt.call_record.take();
+        assert!(record.events.is_empty());
+        assert!(!record.state_changed());
+        h.check_state(&rt);
+    }
+
     #[test]
     fn add_verifier_rejects_client() {
         let (h, rt) = new_harness();
@@ -225,6 +248,24 @@ mod verifiers {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn verifier_count() {
+        let (h, rt) = new_harness();
+        let allowance = verifier_allowance(&rt);
+        assert_eq!(0, h.registry_stats(&rt).verifier_count);
+        h.add_verifier(&rt, &VERIFIER, &allowance).unwrap();
+        h.add_verifier(&rt, &VERIFIER2, &allowance).unwrap();
+        assert_eq!(2, h.registry_stats(&rt).verifier_count);
+
+        // Overwriting an existing verifier's allowance doesn't change the count.
+        h.add_verifier(&rt, &VERIFIER, &(allowance.clone() + 1)).unwrap();
+        assert_eq!(2, h.registry_stats(&rt).verifier_count);
+
+        h.remove_verifier(&rt, &VERIFIER).unwrap();
+        assert_eq!(1, h.registry_stats(&rt).verifier_count);
+        h.check_state(&rt);
+    }
+
     #[test]
     fn remove_verifier_id_address() {
         let (h, rt) = new_harness();
@@ -248,11 +289,12 @@ mod clients {
     use num_traits::ToPrimitive;
     use num_traits::Zero;
 
+    use fil_actor_verifreg::state::VERIFIER_ACTIVITY_WINDOW;
     use fil_actor_verifreg::{
-        Actor as VerifregActor, AddVerifiedClientParams, DataCap, Method, ext,
+        Actor as VerifregActor, AddVerifiedClientParams, DataCap, Method, State, VerifierActivity,
     };
     use fil_actors_runtime::test_utils::*;
-    use fil_actors_runtime::{DATACAP_TOKEN_ACTOR_ADDR, EventBuilder, STORAGE_MARKET_ACTOR_ADDR};
+    use fil_actors_runtime::{EventBuilder, STORAGE_MARKET_ACTOR_ADDR};
     use harness::*;
     use util::*;
 
@@ -293,6 +335,121 @@ mod clients {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn verifier_activity_counts_grants_by_window() {
+        let (h, rt) = new_harness();
+        let allowance_client = client_allowance(&rt);
+        let allowance_verifier = &allowance_client * 3;
+        let since = *rt.epoch.borrow();
+        h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
+        let verifier = VERIFIER.id().unwrap();
+        assert_eq!(VerifierActivity::new(since), h.query_verifier_activity(&rt, verifier).unwrap());
+
+        let window_start = VERIFIER_ACTIVITY_WINDOW;
+        rt.set_epoch(window_start + 10);
+        h.add_client(&rt, &VERIFIER, &CLIENT, &allowance_client, &allowance_verifier).unwrap();
+        h.add_client(
+            &rt,
+            &VERIFIER,
+            &CLIENT2,
+            &allowance_client,
+            &(&allowance_verifier - &allowance_client),
+        )
+        .unwrap();
+        assert_eq!(
+            VerifierActivity {
+                since,
+                total_granted: &allowance_client * 2,
+                total_grants: 2,
+                window_start,
+                window_granted: &allowance_client * 2,
+                window_grants: 2,
+            },
+            h.query_verifier_activity(&rt, verifier).unwrap()
+        );
+
+        // A grant in the next window restarts the window counters.
+        rt.set_epoch(window_start + VERIFIER_ACTIVITY_WINDOW);
+        h.add_client(&rt, &VERIFIER, &CLIENT3, &allowance_client, &allowance_client).unwrap();
+        assert_eq!(
+            VerifierActivity {
+                since,
+                total_granted: &allowance_client * 3,
+                total_grants: 3,
+                window_start: window_start + VERIFIER_ACTIVITY_WINDOW,
+                window_granted: allowance_client.clone(),
+                window_grants: 1,
+            },
+            h.query_verifier_activity(&rt, verifier).unwrap()
+        );
+
+        // Actors that are not verifiers have no activity.
+        expect_abort(
+            ExitCode::USR_NOT_FOUND,
+            h.query_verifier_activity(&rt, VERIFIER2.id().unwrap()),
+        );
+        rt.reset();
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn verifier_activity_follows_verifier() {
+        let (h, rt) = new_harness();
+        let allowance_client = client_allowance(&rt);
+        let allowance_verifier = &allowance_client * 2;
+        let verifier = VERIFIER.id().unwrap();
+        rt.set_epoch(10);
+        h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
+        h.add_client(&rt, &VERIFIER, &CLIENT, &allowance_client, &allowance_verifier).unwrap();
+        let activity = h.query_verifier_activity(&rt, verifier).unwrap();
+        assert_eq!(10, activity.since);
+        assert_eq!(1, activity.total_grants);
+
+        // Overwriting the verifier's allowance keeps its activity.
+        rt.set_epoch(20);
+        h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
+        assert_eq!(activity, h.query_verifier_activity(&rt, verifier).unwrap());
+
+        // Removing the verifier removes its activity.
+        h.remove_verifier(&rt, &VERIFIER).unwrap();
+        expect_abort(ExitCode::USR_NOT_FOUND, h.query_verifier_activity(&rt, verifier));
+        rt.reset();
+        h.check_state(&rt);
+
+        // A verifier added again counts its grants afresh.
+        rt.set_epoch(30);
+        h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
+        assert_eq!(VerifierActivity::new(30), h.query_verifier_activity(&rt, verifier).unwrap());
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn init_verifier_activity_for_existing_verifiers() {
+        let (h, rt) = new_harness();
+        let allowance_client = client_allowance(&rt);
+        let allowance_verifier = &allowance_client * 2;
+        rt.set_epoch(10);
+        h.add_verifier(&rt, &VERIFIER, &allowance_verifier).unwrap();
+        h.add_verifier(&rt, &VERIFIER2, &allowance_verifier).unwrap();
+        h.add_client(&rt, &VERIFIER, &CLIENT, &allowance_client, &allowance_verifier).unwrap();
+        let activity = h.query_verifier_activity(&rt, VERIFIER.id().unwrap()).unwrap();
+
+        // Drop the second verifier's activity, as for a verifier added before it was recorded.
+        let mut st: State = rt.get_state();
+        let mut activities = st.load_verifier_activity(&rt.store).unwrap();
+        activities.delete(&VERIFIER2.id().unwrap()).unwrap();
+        st.verifier_activity = activities.flush().unwrap();
+
+        st.init_verifier_activity(&rt.store, 50).unwrap();
+        rt.replace_state(&st);
+        assert_eq!(activity, h.query_verifier_activity(&rt, VERIFIER.id().unwrap()).unwrap());
+        assert_eq!(
+            VerifierActivity::new(50),
+            h.query_verifier_activity(&rt, VERIFIER2.id().unwrap()).unwrap()
+        );
+        h.check_state(&rt);
+    }
+
     #[test]
     fn verifier_allowance_exhausted() {
         let (h, rt) = new_harness();
@@ -430,26 +587,32 @@ mod clients {
 
         // can call the exported method num
 
-        let mint_params = ext::datacap::MintParams {
-            to: *CLIENT,
-            amount: TokenAmount::from_whole(allowance_client.to_i64().unwrap()),
-            operators: vec![STORAGE_MARKET_ACTOR_ADDR],
-        };
-        rt.expect_send_simple(
-            DATACAP_TOKEN_ACTOR_ADDR,
-            ext::datacap::Method::Mint as MethodNum,
-            IpldBlock::serialize_cbor(&mint_params).unwrap(),
-            TokenAmount::zero(),
-            None,
-            ExitCode::OK,
+        rt.expect_datacap_mint(
+            *CLIENT,
+            TokenAmount::from_whole(allowance_client.to_i64().unwrap()),
+            vec![STORAGE_MARKET_ACTOR_ADDR],
         );
 
         rt.expect_emitted_event(
             EventBuilder::new()
                 .typ("verifier-balance")
                 .field_indexed("verifier", &VERIFIER.id().unwrap())
-                .field("balance", &BigIntSer(&(allowance_verifier - allowance_client)))
+                .field("balance", &BigIntSer(&(&allowance_verifier - &allowance_client)))
+                .field_indexed("client", &CLIENT.id().unwrap())
+                .build()
+                .unwrap(),
+        );
+        let mut activity = VerifierActivity::default();
+        activity.record_grant(*rt.epoch.borrow(), &allowance_client);
+        rt.expect_emitted_event(
+            EventBuilder::new()
+                .typ("verifier-activity")
+                .field_indexed("verifier", &VERIFIER.id().unwrap())
                 .field_indexed("client", &CLIENT.id().unwrap())
+                .field("amount", &BigIntSer(&allowance_client))
+                .field("window-start", &activity.window_start)
+                .field("window-granted", &BigIntSer(&activity.window_granted))
+                .field("window-grants", &activity.window_grants)
                 .build()
                 .unwrap(),
         );
@@ -530,17 +693,19 @@ mod allocs_claims {
 
     use fil_actor_verifreg::{
         Actor, AllocationID, ClaimTerm, DataCap, ExtendClaimTermsParams, GetClaimsParams, Method,
-        State,
+        ReduceClaimTermsParams, RegistryStats, RemoveSectorClaimsParams, State,
     };
-    use fil_actor_verifreg::{Claim, ExtendClaimTermsReturn};
-    use fil_actors_runtime::FailCode;
+    use fil_actor_verifreg::{Claim, ExtendClaimTermsReturn, SectorClaimSummary};
+    use fil_actors_runtime::runtime::builtins::Type;
     use fil_actors_runtime::runtime::policy_constants::{
         MAXIMUM_VERIFIED_ALLOCATION_TERM, MINIMUM_VERIFIED_ALLOCATION_SIZE,
         MINIMUM_VERIFIED_ALLOCATION_TERM,
     };
     use fil_actors_runtime::test_utils::{
-        ACCOUNT_ACTOR_CODE_ID, EVM_ACTOR_CODE_ID, expect_abort, expect_abort_contains_message,
+        ACCOUNT_ACTOR_CODE_ID, EVM_ACTOR_CODE_ID, MINER_ACTOR_CODE_ID, expect_abort,
+        expect_abort_contains_message,
     };
+    use fil_actors_runtime::{Cursor, FailCode};
     use harness::*;
 
     use crate::*;
@@ -651,6 +816,49 @@ mod allocs_claims {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn list_allocations() {
+        let (h, rt) = new_harness();
+        let alloc1 = make_alloc("1", CLIENT1, PROVIDER1, ALLOC_SIZE);
+        let alloc2 = make_alloc("2", CLIENT1, PROVIDER2, ALLOC_SIZE);
+        let alloc3 = make_alloc("3", CLIENT1, PROVIDER1, ALLOC_SIZE * 2);
+        let alloc4 = make_alloc("4", CLIENT2, PROVIDER1, ALLOC_SIZE);
+        let id1 = h.create_alloc(&rt, &alloc1).unwrap();
+        let id2 = h.create_alloc(&rt, &alloc2).unwrap();
+        let id3 = h.create_alloc(&rt, &alloc3).unwrap();
+        let id4 = h.create_alloc(&rt, &alloc4).unwrap();
+
+        // Page through the first client's allocations.
+        let first = h.list_allocations(&rt, CLIENT1, Cursor::start(), 2).unwrap();
+        assert_eq!(2, first.allocations.len());
+        let cursor = first.next_cursor.expect("more allocations remain");
+        let rest = h.list_allocations(&rt, CLIENT1, cursor, 2).unwrap();
+        assert_eq!(1, rest.allocations.len());
+        assert_eq!(None, rest.next_cursor);
+        let mut listed: Vec<_> = first.allocations.into_iter().chain(rest.allocations).collect();
+        listed.sort_by_key(|(id, _)| *id);
+        assert_eq!(vec![(id1, alloc1), (id2, alloc2), (id3, alloc3)], listed);
+
+        // Allocations of other clients are listed separately.
+        let other = h.list_allocations(&rt, CLIENT2, Cursor::start(), 10).unwrap();
+        assert_eq!(vec![(id4, alloc4)], other.allocations);
+        assert_eq!(None, other.next_cursor);
+        let none = h.list_allocations(&rt, PROVIDER1, Cursor::start(), 10).unwrap();
+        assert!(none.allocations.is_empty());
+
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            h.list_allocations(&rt, CLIENT1, Cursor::start(), 0),
+        );
+        rt.reset();
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            h.list_allocations(&rt, CLIENT1, Cursor::encode(&"nonsense").unwrap(), 2),
+        );
+        rt.reset();
+        h.check_state(&rt);
+    }
+
     #[test]
     fn claim_allocs() {
         let (h, rt) = new_harness();
@@ -686,7 +894,14 @@ mod allocs_claims {
                 .unwrap();
 
             assert_eq!(ret.sector_results.codes(), vec![ExitCode::OK]);
-            assert_eq!(ret.sector_claims[0].claimed_space, BigInt::from(2 * size));
+            assert_eq!(
+                ret.sector_claims[0],
+                SectorClaimSummary {
+                    claimed_space: BigInt::from(2 * size),
+                    term_start: 0,
+                    sector_expiry: expiry,
+                }
+            );
             assert_alloc_claimed(&rt, CLIENT1, PROVIDER1, id1, &alloc1, 0, sector);
             assert_alloc_claimed(&rt, CLIENT2, PROVIDER1, id2, &alloc2, 0, sector);
             h.check_state(&rt);
@@ -866,6 +1081,51 @@ mod allocs_claims {
         }
     }
 
+    #[test]
+    fn registry_stats_track_allocations_and_claims() {
+        let (h, rt) = new_harness();
+        let size = MINIMUM_VERIFIED_ALLOCATION_SIZE as u64;
+        assert_eq!(RegistryStats::default(), h.registry_stats(&rt));
+
+        let alloc1 = make_alloc("1", CLIENT1, PROVIDER1, size);
+        let alloc2 = make_alloc("2", CLIENT1, PROVIDER1, 2 * size);
+        let id1 = h.create_alloc(&rt, &alloc1).unwrap();
+        let id2 = h.create_alloc(&rt, &alloc2).unwrap();
+        let stats = h.registry_stats(&rt);
+        assert_eq!(DataCap::from(3 * size), stats.allocated_size);
+        assert!(stats.claimed_size.is_zero());
+
+        // Claiming an allocation moves its size from allocated to claimed.
+        let sector = 1000;
+        let expiry = MINIMUM_VERIFIED_ALLOCATION_TERM;
+        let reqs = vec![make_claim_reqs(sector, expiry, &[(id2, &alloc2)])];
+        h.claim_allocations(
+            &rt,
+            PROVIDER1,
+            reqs,
+            2 * size,
+            false,
+            vec![(id2, alloc2.clone(), sector)],
+        )
+        .unwrap();
+        let stats = h.registry_stats(&rt);
+        assert_eq!(DataCap::from(size), stats.allocated_size);
+        assert_eq!(DataCap::from(2 * size), stats.claimed_size);
+
+        // Removing the claim's sector removes its size.
+        let claim = claim_from_alloc(&alloc2, 0, sector);
+        h.remove_sector_claims(&rt, PROVIDER1, vec![sector], vec![(id2, claim)]).unwrap();
+        let stats = h.registry_stats(&rt);
+        assert_eq!(DataCap::from(size), stats.allocated_size);
+        assert!(stats.claimed_size.is_zero());
+
+        // Removing an expired allocation removes its size.
+        rt.set_epoch(alloc1.expiration + 1);
+        h.remove_expired_allocations(&rt, CLIENT1, vec![id1], vec![(id1, alloc1)]).unwrap();
+        assert_eq!(RegistryStats::default(), h.registry_stats(&rt));
+        h.check_state(&rt);
+    }
+
     #[test]
     fn get_claims() {
         let (h, rt) = new_harness();
@@ -908,6 +1168,65 @@ mod allocs_claims {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn remove_sector_claims() {
+        let (h, rt) = new_harness();
+        let size = MINIMUM_VERIFIED_ALLOCATION_SIZE as u64;
+        let start = 100;
+        let min_term = MINIMUM_VERIFIED_ALLOCATION_TERM;
+        let max_term = min_term + 1000;
+
+        let claim1 = make_claim("1", CLIENT1, PROVIDER1, size, min_term, max_term, start, 1);
+        let claim2 = make_claim("2", CLIENT2, PROVIDER1, size, min_term, max_term, start, 1);
+        let claim3 = make_claim("3", CLIENT1, PROVIDER1, size, min_term, max_term, start, 2);
+        let claim4 = make_claim("4", CLIENT1, PROVIDER1, size, min_term, max_term, start, 3);
+        let claim5 = make_claim("5", CLIENT1, PROVIDER2, size, min_term, max_term, start, 1);
+        let claim6 = make_claim("6", CLIENT1, PROVIDER1, size, min_term, max_term, 0, 2);
+        let id1 = h.create_claim(&rt, &claim1).unwrap();
+        let id2 = h.create_claim(&rt, &claim2).unwrap();
+        let id3 = h.create_claim(&rt, &claim3).unwrap();
+        let id4 = h.create_claim(&rt, &claim4).unwrap();
+        let id5 = h.create_claim(&rt, &claim5).unwrap();
+        let id6 = h.create_claim(&rt, &claim6).unwrap();
+        rt.set_epoch(min_term + 10);
+
+        // Only the calling provider's claims in the given sectors are removed, and only
+        // those within their minimum term. Claim 6 has already served its minimum term.
+        let ret = h
+            .remove_sector_claims(
+                &rt,
+                PROVIDER1,
+                vec![1, 2, 4],
+                vec![(id1, claim1), (id2, claim2), (id3, claim3)],
+            )
+            .unwrap();
+        assert_eq!(vec![id1, id2, id3], ret.removed);
+        assert!(h.load_claim(&rt, PROVIDER1, id1).is_none());
+        assert!(h.load_claim(&rt, PROVIDER1, id2).is_none());
+        assert!(h.load_claim(&rt, PROVIDER1, id3).is_none());
+        assert_claim(&rt, PROVIDER1, id4, &claim4);
+        assert_claim(&rt, PROVIDER2, id5, &claim5);
+        assert_claim(&rt, PROVIDER1, id6, &claim6);
+
+        // Sectors with no claims are ignored.
+        let ret = h.remove_sector_claims(&rt, PROVIDER1, vec![1, 2], vec![]).unwrap();
+        assert!(ret.removed.is_empty());
+
+        // Only callable by a miner.
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(PROVIDER1));
+        rt.expect_validate_caller_type(vec![Type::Miner]);
+        expect_abort(
+            ExitCode::USR_FORBIDDEN,
+            rt.call::<Actor>(
+                Method::RemoveSectorClaims as MethodNum,
+                IpldBlock::serialize_cbor(&RemoveSectorClaimsParams { sectors: vec![3] }).unwrap(),
+            ),
+        );
+        rt.reset();
+        assert_claim(&rt, PROVIDER1, id4, &claim4);
+        h.check_state(&rt);
+    }
+
     #[test]
     fn extend_claims_basic() {
         let (h, rt) = new_harness();
@@ -931,7 +1250,8 @@ mod allocs_claims {
                 ClaimTerm { provider: PROVIDER1, claim_id: id1, term_max: max_term + 1 },
                 ClaimTerm { provider: PROVIDER1, claim_id: id2, term_max: max_term + 2 },
                 ClaimTerm { provider: PROVIDER2, claim_id: id3, term_max: max_term + 3 },
-            ],
+            ]
+            .into(),
         };
 
         let expected_claims =
@@ -963,7 +1283,7 @@ mod allocs_claims {
         {
             let claim_id = h.create_claim(&rt, &claim).unwrap();
             let params = ExtendClaimTermsParams {
-                terms: vec![ClaimTerm { provider: PROVIDER1, claim_id, term_max: max_term }],
+                terms: vec![ClaimTerm { provider: PROVIDER1, claim_id, term_max: max_term }].into(),
             };
             rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
             let ret = h.extend_claim_terms(&rt, &params, vec![(claim_id, claim.clone())]).unwrap();
@@ -974,7 +1294,7 @@ mod allocs_claims {
         {
             let claim_id = h.create_claim(&rt, &claim).unwrap();
             let params = ExtendClaimTermsParams {
-                terms: vec![ClaimTerm { provider: PROVIDER1, claim_id, term_max: max_term }],
+                terms: vec![ClaimTerm { provider: PROVIDER1, claim_id, term_max: max_term }].into(),
             };
             rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT2));
             let ret = h.extend_claim_terms(&rt, &params, vec![]).unwrap();
@@ -985,7 +1305,7 @@ mod allocs_claims {
         {
             let claim_id = h.create_claim(&rt, &claim).unwrap();
             let params = ExtendClaimTermsParams {
-                terms: vec![ClaimTerm { provider: PROVIDER2, claim_id, term_max: max_term }],
+                terms: vec![ClaimTerm { provider: PROVIDER2, claim_id, term_max: max_term }].into(),
             };
             rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
             let ret = h.extend_claim_terms(&rt, &params, vec![]).unwrap();
@@ -1000,7 +1320,8 @@ mod allocs_claims {
                     provider: PROVIDER1,
                     claim_id,
                     term_max: MAXIMUM_VERIFIED_ALLOCATION_TERM + 1,
-                }],
+                }]
+                .into(),
             };
             rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
             let ret = h.extend_claim_terms(&rt, &params, vec![]).unwrap();
@@ -1011,7 +1332,8 @@ mod allocs_claims {
         {
             let claim_id = h.create_claim(&rt, &claim).unwrap();
             let params = ExtendClaimTermsParams {
-                terms: vec![ClaimTerm { provider: PROVIDER1, claim_id, term_max: max_term - 1 }],
+                terms: vec![ClaimTerm { provider: PROVIDER1, claim_id, term_max: max_term - 1 }]
+                    .into(),
             };
             rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
             let ret = h.extend_claim_terms(&rt, &params, vec![]).unwrap();
@@ -1026,7 +1348,8 @@ mod allocs_claims {
                     provider: PROVIDER1,
                     claim_id,
                     term_max: MAXIMUM_VERIFIED_ALLOCATION_TERM,
-                }],
+                }]
+                .into(),
             };
             rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
             rt.set_epoch(max_term + 1);
@@ -1037,6 +1360,131 @@ mod allocs_claims {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn reduce_claims_with_client_and_provider_approval() {
+        let (h, rt) = new_harness();
+        let min_term = MINIMUM_VERIFIED_ALLOCATION_TERM;
+        let max_term = min_term + 1000;
+        let claim = make_claim("1", CLIENT1, PROVIDER1, ALLOC_SIZE, min_term, max_term, 0, 0);
+        let id = h.create_claim(&rt, &claim).unwrap();
+        rt.set_epoch(100);
+        let sector_expiration = Some(min_term + 200);
+        let reduce = |term_max| ReduceClaimTermsParams {
+            terms: vec![ClaimTerm { provider: PROVIDER1, claim_id: id, term_max }].into(),
+        };
+
+        // Only the client and provider may approve, and only a reduction no lower than
+        // the elapsed plus minimum term.
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT2));
+        let ret =
+            h.reduce_claim_terms(&rt, &reduce(min_term + 500), sector_expiration, vec![]).unwrap();
+        assert_eq!(ret.codes(), vec![ExitCode::USR_FORBIDDEN]);
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
+        let params = ReduceClaimTermsParams {
+            terms: vec![
+                ClaimTerm { provider: PROVIDER1, claim_id: id, term_max: max_term },
+                ClaimTerm { provider: PROVIDER1, claim_id: id, term_max: min_term + 99 },
+                ClaimTerm { provider: PROVIDER2, claim_id: id, term_max: min_term + 500 },
+            ]
+            .into(),
+        };
+        let ret = h.reduce_claim_terms(&rt, &params, sector_expiration, vec![]).unwrap();
+        assert_eq!(
+            ret.codes(),
+            vec![
+                ExitCode::USR_ILLEGAL_ARGUMENT,
+                ExitCode::USR_ILLEGAL_ARGUMENT,
+                ExitCode::USR_NOT_FOUND
+            ]
+        );
+
+        // A party's repeated approval does not apply the reduction,
+        // and a differing approval replaces the pending one.
+        let ret =
+            h.reduce_claim_terms(&rt, &reduce(min_term + 500), sector_expiration, vec![]).unwrap();
+        assert_eq!(ret.codes(), vec![ExitCode::OK]);
+        h.reduce_claim_terms(&rt, &reduce(min_term + 500), sector_expiration, vec![]).unwrap();
+        rt.set_caller(*MINER_ACTOR_CODE_ID, Address::new_id(PROVIDER1));
+        h.reduce_claim_terms(&rt, &reduce(min_term + 400), sector_expiration, vec![]).unwrap();
+        assert_claim(&rt, PROVIDER1, id, &claim);
+
+        // The other party's matching approval applies it.
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
+        let ret = h
+            .reduce_claim_terms(
+                &rt,
+                &reduce(min_term + 400),
+                sector_expiration,
+                vec![(id, claim.clone())],
+            )
+            .unwrap();
+        assert_eq!(ret.codes(), vec![ExitCode::OK]);
+        assert_claim(&rt, PROVIDER1, id, &Claim { term_max: min_term + 400, ..claim });
+        let st: State = rt.get_state();
+        assert!(st.load_claim_term_reductions(rt.store()).unwrap().get(&id).unwrap().is_none());
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn reduced_claim_term_may_not_end_before_sector_expiration() {
+        let (h, rt) = new_harness();
+        let min_term = MINIMUM_VERIFIED_ALLOCATION_TERM;
+        let max_term = min_term + 1000;
+        let claim = make_claim("1", CLIENT1, PROVIDER1, ALLOC_SIZE, min_term, max_term, 0, 0);
+        let id = h.create_claim(&rt, &claim).unwrap();
+        rt.set_epoch(100);
+        let reduce = |term_max| ReduceClaimTermsParams {
+            terms: vec![ClaimTerm { provider: PROVIDER1, claim_id: id, term_max }].into(),
+        };
+
+        // A reduction ending before the sector's expiration is rejected.
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
+        let sector_expiration = Some(min_term + 450);
+        let ret = h.reduce_claim_terms(&rt, &reduce(min_term + 400), sector_expiration, vec![]);
+        assert_eq!(ret.unwrap().codes(), vec![ExitCode::USR_ILLEGAL_ARGUMENT]);
+
+        // The sector's expiration is checked again when the other party approves,
+        // since it may have been extended since the first approval.
+        let ret = h.reduce_claim_terms(&rt, &reduce(min_term + 500), sector_expiration, vec![]);
+        assert_eq!(ret.unwrap().codes(), vec![ExitCode::OK]);
+        rt.set_caller(*MINER_ACTOR_CODE_ID, Address::new_id(PROVIDER1));
+        let ret = h.reduce_claim_terms(&rt, &reduce(min_term + 500), Some(min_term + 600), vec![]);
+        assert_eq!(ret.unwrap().codes(), vec![ExitCode::USR_ILLEGAL_ARGUMENT]);
+        assert_claim(&rt, PROVIDER1, id, &claim);
+
+        // A claim whose sector is not found has no such floor.
+        let ret =
+            h.reduce_claim_terms(&rt, &reduce(min_term + 500), None, vec![(id, claim.clone())]);
+        assert_eq!(ret.unwrap().codes(), vec![ExitCode::OK]);
+        assert_claim(&rt, PROVIDER1, id, &Claim { term_max: min_term + 500, ..claim });
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn removing_claim_drops_pending_term_reduction() {
+        let (h, rt) = new_harness();
+        let min_term = MINIMUM_VERIFIED_ALLOCATION_TERM;
+        let max_term = min_term + 1000;
+        let claim = make_claim("1", CLIENT1, PROVIDER1, ALLOC_SIZE, min_term, max_term, 0, 7);
+        let id = h.create_claim(&rt, &claim).unwrap();
+        rt.set_epoch(100);
+
+        rt.set_caller(*ACCOUNT_ACTOR_CODE_ID, Address::new_id(CLIENT1));
+        let params = ReduceClaimTermsParams {
+            terms: vec![ClaimTerm { provider: PROVIDER1, claim_id: id, term_max: min_term + 500 }]
+                .into(),
+        };
+        h.reduce_claim_terms(&rt, &params, Some(min_term + 200), vec![]).unwrap();
+        let st: State = rt.get_state();
+        assert!(st.load_claim_term_reductions(rt.store()).unwrap().get(&id).unwrap().is_some());
+
+        // The pending approval is removed with the claim.
+        h.remove_sector_claims(&rt, PROVIDER1, vec![7], vec![(id, claim)]).unwrap();
+        let st: State = rt.get_state();
+        assert!(st.load_claim_term_reductions(rt.store()).unwrap().get(&id).unwrap().is_none());
+        h.check_state(&rt);
+    }
+
     #[test]
     fn expire_claims() {
         let (h, rt) = new_harness();
@@ -1121,7 +1569,7 @@ mod allocs_claims {
 
         // First, let's extend some claims
         // Empty request to avoid setting expectations for events etc.
-        let params = ExtendClaimTermsParams { terms: vec![] };
+        let params = ExtendClaimTermsParams { terms: vec![].into() };
 
         // set caller to not-builtin
         rt.set_caller(*EVM_ACTOR_CODE_ID, Address::new_id(CLIENT1));
@@ -1153,7 +1601,7 @@ mod allocs_claims {
 
         // Now let's Get those Claims, and check them
 
-        let params = GetClaimsParams { claim_ids: vec![], provider: PROVIDER1 };
+        let params = GetClaimsParams { claim_ids: vec![].into(), provider: PROVIDER1 };
         // cannot call the unexported extend method num
         expect_abort_contains_message(
             ExitCode::USR_FORBIDDEN,
@@ -1179,8 +1627,6 @@ mod allocs_claims {
 }
 
 mod datacap {
-    use frc46_token::receiver::FRC46_TOKEN_TYPE;
-    use fvm_actor_utils::receiver::UniversalReceiverParams;
     use fvm_ipld_encoding::ipld_block::IpldBlock;
     use fvm_shared::address::Address;
     use fvm_shared::econ::TokenAmount;
@@ -1188,15 +1634,17 @@ mod datacap {
     use fvm_shared::{ActorID, MethodNum};
 
     use fil_actor_verifreg::{Actor as VerifregActor, Claim, Method, State};
-    use fil_actors_runtime::cbor::serialize;
     use fil_actors_runtime::runtime::policy_constants::{
         MAXIMUM_VERIFIED_ALLOCATION_EXPIRATION, MAXIMUM_VERIFIED_ALLOCATION_TERM,
         MINIMUM_VERIFIED_ALLOCATION_SIZE, MINIMUM_VERIFIED_ALLOCATION_TERM,
     };
     use fil_actors_runtime::test_utils::*;
     use fil_actors_runtime::{
-        BatchReturn, DATACAP_TOKEN_ACTOR_ADDR, EPOCHS_IN_YEAR, STORAGE_MARKET_ACTOR_ADDR,
+        BatchReturn, DATACAP_TOKEN_ACTOR_ADDR, EPOCHS_IN_YEAR,
+        OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS, STORAGE_MARKET_ACTOR_ADDR,
+        VersionedOperatorData,
     };
+    use fvm_ipld_encoding::RawBytes;
     use harness::*;
 
     use crate::*;
@@ -1254,6 +1702,42 @@ mod datacap {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn receive_tokens_versioned_operator_data() {
+        let (h, rt) = new_harness();
+        add_miner(&rt, PROVIDER1);
+
+        let reqs = vec![make_alloc_req(&rt, PROVIDER1, SIZE)];
+        let mut payload = make_receiver_hook_token_payload(CLIENT1, reqs.clone(), vec![], SIZE);
+        payload.operator_data = RawBytes::serialize(VersionedOperatorData {
+            version: OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS,
+            payload: payload.operator_data,
+        })
+        .unwrap();
+        h.receive_tokens(&rt, payload, BatchReturn::ok(1), BATCH_EMPTY, vec![1], 0).unwrap();
+        assert_allocation(&rt, CLIENT1, 1, &alloc_from_req(CLIENT1, &reqs[0]));
+
+        // An unsupported version is rejected.
+        let mut payload = make_receiver_hook_token_payload(CLIENT1, reqs, vec![], SIZE);
+        payload.operator_data = RawBytes::serialize(VersionedOperatorData {
+            version: 99,
+            payload: RawBytes::default(),
+        })
+        .unwrap();
+        rt.set_caller(*DATACAP_TOKEN_ACTOR_CODE_ID, DATACAP_TOKEN_ACTOR_ADDR);
+        rt.expect_validate_caller_addr(vec![DATACAP_TOKEN_ACTOR_ADDR]);
+        expect_abort_contains_message(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            "unsupported operator data version 99",
+            rt.call::<VerifregActor>(
+                Method::UniversalReceiverHook as MethodNum,
+                IpldBlock::serialize_cbor(&make_frc46_receiver_hook_params(&payload)).unwrap(),
+            ),
+        );
+        rt.verify();
+        h.check_state(&rt);
+    }
+
     #[test]
     fn receive_tokens_extend_claims() {
         let (h, rt) = new_harness();
@@ -1340,19 +1824,12 @@ mod datacap {
         let (h, rt) = new_harness();
         add_miner(&rt, PROVIDER1);
 
-        let params = UniversalReceiverParams {
-            type_: FRC46_TOKEN_TYPE,
-            payload: serialize(
-                &make_receiver_hook_token_payload(
-                    CLIENT1,
-                    vec![make_alloc_req(&rt, PROVIDER1, SIZE)],
-                    vec![],
-                    SIZE,
-                ),
-                "payload",
-            )
-            .unwrap(),
-        };
+        let params = make_frc46_receiver_hook_params(&make_receiver_hook_token_payload(
+            CLIENT1,
+            vec![make_alloc_req(&rt, PROVIDER1, SIZE)],
+            vec![],
+            SIZE,
+        ));
 
         rt.set_caller(*MARKET_ACTOR_CODE_ID, STORAGE_MARKET_ACTOR_ADDR); // Wrong caller
         rt.expect_validate_caller_addr(vec![DATACAP_TOKEN_ACTOR_ADDR]);
@@ -1381,10 +1858,7 @@ mod datacap {
         );
         // Set invalid receiver hook "to" address (should be the verified registry itself).
         payload.to = PROVIDER1;
-        let params = UniversalReceiverParams {
-            type_: FRC46_TOKEN_TYPE,
-            payload: serialize(&payload, "payload").unwrap(),
-        };
+        let params = make_frc46_receiver_hook_params(&payload);
 
         rt.set_caller(*DATACAP_TOKEN_ACTOR_CODE_ID, DATACAP_TOKEN_ACTOR_ADDR);
         rt.expect_validate_caller_addr(vec![DATACAP_TOKEN_ACTOR_ADDR]);
@@ -1507,6 +1981,38 @@ mod datacap {
         h.check_state(&rt);
     }
 
+    #[test]
+    fn validate_alloc_reqs() {
+        let (h, rt) = new_harness();
+        add_miner(&rt, PROVIDER1);
+        rt.set_epoch(100);
+
+        let mut expired = make_alloc_req(&rt, PROVIDER1, SIZE);
+        expired.expiration = 99;
+        let mut short_term = make_alloc_req(&rt, PROVIDER1, SIZE);
+        short_term.term_min = MINIMUM_VERIFIED_ALLOCATION_TERM - 1;
+        let reqs = vec![
+            make_alloc_req(&rt, PROVIDER1, SIZE),
+            make_alloc_req(&rt, PROVIDER1, SIZE - 1),
+            expired,
+            short_term,
+            // Not a miner.
+            make_alloc_req(&rt, PROVIDER2, SIZE),
+        ];
+        let ret = h.validate_allocation_requests(&rt, reqs);
+        assert_eq!(
+            BatchReturn::of(&[
+                ExitCode::OK,
+                ExitCode::USR_ILLEGAL_ARGUMENT,
+                ExitCode::USR_ILLEGAL_ARGUMENT,
+                ExitCode::USR_ILLEGAL_ARGUMENT,
+                ExitCode::USR_ILLEGAL_ARGUMENT,
+            ]),
+            ret
+        );
+        h.check_state(&rt);
+    }
+
     #[test]
     fn receive_invalid_extension_reqs() {
         let (h, rt) = new_harness();
@@ -1597,6 +2103,180 @@ mod datacap {
     }
 }
 
+mod governance {
+    use fvm_ipld_encoding::RawBytes;
+    use fvm_ipld_encoding::ipld_block::IpldBlock;
+    use fvm_shared::address::Address;
+    use fvm_shared::econ::TokenAmount;
+    use fvm_shared::error::ExitCode;
+    use fvm_shared::{ActorID, MethodNum};
+
+    use fil_actor_verifreg::{
+        Actor as VerifregActor, AllocationsResponse, Method, SetAllocationGovernorParams, State,
+    };
+    use fil_actors_runtime::runtime::policy_constants::MINIMUM_VERIFIED_ALLOCATION_SIZE;
+    use fil_actors_runtime::test_utils::*;
+    use fil_actors_runtime::{BatchReturn, DATACAP_TOKEN_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR};
+    use harness::*;
+
+    use crate::*;
+
+    const PROVIDER1: ActorID = 301;
+    const PROVIDER2: ActorID = 302;
+    const GOVERNOR: Address = Address::new_id(501);
+    const SIZE: u64 = MINIMUM_VERIFIED_ALLOCATION_SIZE as u64;
+
+    fn new_enabled_harness() -> (Harness, MockRuntime) {
+        let (h, mut rt) = new_harness();
+        rt.policy.governance_allocations_enabled = true;
+        add_miner(&rt, PROVIDER1);
+        add_miner(&rt, PROVIDER2);
+        (h, rt)
+    }
+
+    #[test]
+    fn root_makes_allocations() {
+        let (h, rt) = new_enabled_harness();
+        let root = h.root.id().unwrap();
+        let reqs =
+            vec![make_alloc_req(&rt, PROVIDER1, SIZE), make_alloc_req(&rt, PROVIDER2, SIZE * 2)];
+        h.allocate_from_governance(&rt, &h.root, reqs.clone(), vec![1, 2]).unwrap();
+
+        assert_allocation(&rt, root, 1, &alloc_from_req(root, &reqs[0]));
+        assert_allocation(&rt, root, 2, &alloc_from_req(root, &reqs[1]));
+        let st: State = rt.get_state();
+        assert_eq!(3, st.next_allocation_id);
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn designated_governor_makes_allocations() {
+        let (h, rt) = new_enabled_harness();
+        h.set_allocation_governor(&rt, Some(GOVERNOR)).unwrap();
+
+        let reqs = vec![make_alloc_req(&rt, PROVIDER1, SIZE)];
+        h.allocate_from_governance(&rt, &GOVERNOR, reqs.clone(), vec![1]).unwrap();
+        let governor = GOVERNOR.id().unwrap();
+        assert_allocation(&rt, governor, 1, &alloc_from_req(governor, &reqs[0]));
+
+        // The root may still make allocations.
+        h.allocate_from_governance(&rt, &h.root, reqs.clone(), vec![2]).unwrap();
+
+        // Once the designation is removed, the former governor may not.
+        h.set_allocation_governor(&rt, None).unwrap();
+        expect_abort(
+            ExitCode::USR_FORBIDDEN,
+            h.allocate_from_governance(&rt, &GOVERNOR, reqs, vec![3]),
+        );
+        rt.reset();
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn only_root_sets_governor() {
+        let (h, rt) = new_enabled_harness();
+        rt.set_caller(*MULTISIG_ACTOR_CODE_ID, GOVERNOR);
+        rt.expect_validate_caller_addr(vec![h.root]);
+        let params = SetAllocationGovernorParams { governor: Some(GOVERNOR) };
+        expect_abort(
+            ExitCode::USR_FORBIDDEN,
+            rt.call::<VerifregActor>(
+                Method::SetAllocationGovernor as MethodNum,
+                IpldBlock::serialize_cbor(&params).unwrap(),
+            ),
+        );
+        rt.reset();
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn rejects_unauthorized_or_invalid_requests() {
+        let (h, rt) = new_enabled_harness();
+        // Other callers are forbidden.
+        let reqs = vec![make_alloc_req(&rt, PROVIDER1, SIZE)];
+        expect_abort(
+            ExitCode::USR_FORBIDDEN,
+            h.allocate_from_governance(&rt, &GOVERNOR, reqs.clone(), vec![1]),
+        );
+        rt.reset();
+
+        // Requests are validated as for allocations funded by a client.
+        let small = vec![make_alloc_req(&rt, PROVIDER1, SIZE - 1)];
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            h.allocate_from_governance(&rt, &h.root, small, vec![1]),
+        );
+        rt.reset();
+        let not_miner = vec![make_alloc_req(&rt, 999, SIZE)];
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            h.allocate_from_governance(&rt, &h.root, not_miner, vec![1]),
+        );
+        rt.reset();
+        expect_abort(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            h.allocate_from_governance(&rt, &h.root, vec![], vec![]),
+        );
+        rt.reset();
+
+        let st: State = rt.get_state();
+        assert_eq!(1, st.next_allocation_id);
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let (h, rt) = new_harness();
+        add_miner(&rt, PROVIDER1);
+        let reqs = vec![make_alloc_req(&rt, PROVIDER1, SIZE)];
+        expect_abort(
+            ExitCode::USR_FORBIDDEN,
+            h.allocate_from_governance(&rt, &h.root, reqs, vec![1]),
+        );
+        rt.reset();
+        h.check_state(&rt);
+    }
+
+    #[test]
+    fn receiver_hook_accepts_self_mint() {
+        let (h, rt) = new_enabled_harness();
+        let my_id = VERIFIED_REGISTRY_ACTOR_ADDR.id().unwrap();
+        let datacap_id = DATACAP_TOKEN_ACTOR_ADDR.id().unwrap();
+        let receive = |from: ActorID| {
+            let payload = make_frc46_token_received(
+                from,
+                my_id,
+                my_id,
+                TokenAmount::from_whole(SIZE),
+                RawBytes::default(),
+            );
+            rt.set_caller(*DATACAP_TOKEN_ACTOR_CODE_ID, DATACAP_TOKEN_ACTOR_ADDR);
+            rt.expect_validate_caller_addr(vec![DATACAP_TOKEN_ACTOR_ADDR]);
+            rt.call::<VerifregActor>(
+                Method::UniversalReceiverHook as MethodNum,
+                IpldBlock::serialize_cbor(&make_frc46_receiver_hook_params(&payload)).unwrap(),
+            )
+        };
+
+        // The token actor reports itself as the sender of minted tokens.
+        let ret: AllocationsResponse = receive(datacap_id).unwrap().unwrap().deserialize().unwrap();
+        assert_eq!(
+            AllocationsResponse {
+                allocation_results: BatchReturn::empty(),
+                extension_results: BatchReturn::empty(),
+                new_allocations: vec![],
+            },
+            ret
+        );
+        rt.verify();
+
+        // A transfer of this actor's own tokens to itself must request allocations.
+        expect_abort(ExitCode::USR_SERIALIZATION, receive(my_id));
+        rt.reset();
+        h.check_state(&rt);
+    }
+}
+
 // Tests to match with Go github.com/filecoin-project/go-state-types/builtin/*/verifreg
 mod serialization {
     use std::str::FromStr;
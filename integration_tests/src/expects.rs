@@ -6,6 +6,8 @@ use fvm_ipld_bitfield::BitField;
 use fvm_ipld_encoding::RawBytes;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_shared::address::Address;
+use fvm_shared::bigint::BigInt;
+use fvm_shared::bigint::bigint_ser::BigIntSer;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::deal::DealID;
 use fvm_shared::econ::TokenAmount;
@@ -22,10 +24,10 @@ use fil_actor_miner::ext::verifreg::ClaimID;
 use fil_actor_miner::{IsControllingAddressParam, PowerPair};
 use fil_actor_miner::{PieceChange, SectorChanges, SectorContentChangedParams};
 use fil_actor_power::{UpdateClaimedPowerParams, UpdatePledgeTotalParams};
-use fil_actor_verifreg::GetClaimsParams;
+use fil_actor_verifreg::{Claim, GetClaimsParams, RemoveSectorClaimsParams};
 use fil_actors_runtime::{
     BURNT_FUNDS_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ADDR, DATACAP_TOKEN_ACTOR_ID, EventBuilder,
-    REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ID,
+    INIT_ACTOR_ID, REWARD_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ID,
     STORAGE_POWER_ACTOR_ADDR, STORAGE_POWER_ACTOR_ID, VERIFIED_REGISTRY_ACTOR_ADDR,
     VERIFIED_REGISTRY_ACTOR_ID,
 };
@@ -266,7 +268,7 @@ impl Expect {
         ids: Vec<ClaimID>,
     ) -> ExpectInvocation {
         let params =
-            IpldBlock::serialize_cbor(&GetClaimsParams { provider: miner, claim_ids: ids })
+            IpldBlock::serialize_cbor(&GetClaimsParams { provider: miner, claim_ids: ids.into() })
                 .unwrap();
         ExpectInvocation {
             from,
@@ -278,6 +280,23 @@ impl Expect {
             ..Default::default()
         }
     }
+    pub fn verifreg_remove_sector_claims(
+        from: ActorID,
+        sectors: Vec<SectorNumber>,
+        events: Vec<EmittedEvent>,
+    ) -> ExpectInvocation {
+        let params = IpldBlock::serialize_cbor(&RemoveSectorClaimsParams { sectors }).unwrap();
+        ExpectInvocation {
+            from,
+            to: VERIFIED_REGISTRY_ACTOR_ADDR,
+            method: fil_actor_verifreg::Method::RemoveSectorClaims as u64,
+            params: Some(params),
+            value: Some(TokenAmount::zero()),
+            subinvocs: Some(vec![]),
+            events: Some(events),
+            ..Default::default()
+        }
+    }
     pub fn frc42_balance(from: ActorID, to: Address, address: Address) -> ExpectInvocation {
         let params = Some(IpldBlock::serialize_cbor(&BalanceParams { address }).unwrap());
         ExpectInvocation {
@@ -413,6 +432,72 @@ impl Expect {
                 .unwrap(),
         }
     }
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_verifreg_new_claim_event(
+        id: u64,
+        client: ActorID,
+        provider: ActorID,
+        piece_cid: &Cid,
+        piece_size: u64,
+        term_min: ChainEpoch,
+        term_max: ChainEpoch,
+        term_start: ChainEpoch,
+        sector: SectorNumber,
+        sector_expiry: ChainEpoch,
+    ) -> EmittedEvent {
+        EmittedEvent {
+            emitter: VERIFIED_REGISTRY_ACTOR_ID,
+            event: EventBuilder::new()
+                .typ("claim")
+                .field_indexed("id", &id)
+                .field_indexed("client", &client)
+                .field_indexed("provider", &provider)
+                .field_indexed("piece-cid", piece_cid)
+                .field("piece-size", &piece_size)
+                .field("term-min", &term_min)
+                .field("term-max", &term_max)
+                .field("term-start", &term_start)
+                .field_indexed("sector", &sector)
+                .field("sector-expiry", &sector_expiry)
+                .build()
+                .unwrap(),
+        }
+    }
+    pub fn build_verifreg_claim_terminated_event(
+        id: ClaimID,
+        claim: &Claim,
+        term_lost: ChainEpoch,
+    ) -> EmittedEvent {
+        EmittedEvent {
+            emitter: VERIFIED_REGISTRY_ACTOR_ID,
+            event: EventBuilder::new()
+                .typ("claim-terminated")
+                .field_indexed("id", &id)
+                .field_indexed("client", &claim.client)
+                .field_indexed("provider", &claim.provider)
+                .field_indexed("piece-cid", &claim.data)
+                .field("piece-size", &claim.size.0)
+                .field("term-min", &claim.term_min)
+                .field("term-max", &claim.term_max)
+                .field("term-start", &claim.term_start)
+                .field_indexed("sector", &claim.sector)
+                .field("term-lost", &term_lost)
+                .build()
+                .unwrap(),
+        }
+    }
+    pub fn build_verifreg_datacap_refunded_event(client: ActorID, amount: u64) -> EmittedEvent {
+        EmittedEvent {
+            emitter: VERIFIED_REGISTRY_ACTOR_ID,
+            event: EventBuilder::new()
+                .typ("datacap-refunded")
+                .field_indexed("client", &client)
+                .field("amount", &BigIntSer(&BigInt::from(amount)))
+                .build()
+                .unwrap(),
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn build_market_event(
         typ: &str,
@@ -432,6 +517,27 @@ impl Expect {
         }
     }
 
+    pub fn build_actor_created_event(
+        id: ActorID,
+        code: Cid,
+        robust_address: Address,
+        delegated_address: Option<Address>,
+        creator: ActorID,
+    ) -> EmittedEvent {
+        EmittedEvent {
+            emitter: INIT_ACTOR_ID,
+            event: EventBuilder::new()
+                .typ("actor-created")
+                .field_indexed("id", &id)
+                .field_indexed("code", &code)
+                .field("robust", &robust_address)
+                .field("delegated", &delegated_address)
+                .field_indexed("creator", &creator)
+                .build()
+                .unwrap(),
+        }
+    }
+
     pub fn build_miner_event(
         typ: &str,
         miner_id: ActorID,
@@ -447,6 +553,22 @@ impl Expect {
         }
     }
 
+    pub fn build_precommit_expired_event(
+        miner_id: ActorID,
+        sector_number: SectorNumber,
+        deposit_burned: &TokenAmount,
+    ) -> EmittedEvent {
+        EmittedEvent {
+            emitter: miner_id,
+            event: EventBuilder::new()
+                .typ("precommit-expired")
+                .field_indexed("sector", &sector_number)
+                .field("deposit-burned", deposit_burned)
+                .build()
+                .unwrap(),
+        }
+    }
+
     pub fn build_sector_activation_event(
         typ: &str,
         miner_id: ActorID,
@@ -2,13 +2,16 @@ use export_macro::vm_test;
 use fil_actor_account::Method::AuthenticateMessageExported;
 use fil_actor_account::types::AuthenticateMessageParams;
 use fvm_ipld_encoding::RawBytes;
+use fvm_shared::METHOD_SEND;
+use fvm_shared::address::Address;
 use fvm_shared::bigint::Zero;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
 
 use crate::util::{create_accounts, generate_deal_proposal};
+use fil_actors_runtime::STORAGE_MARKET_ACTOR_ADDR;
 use vm_api::VM;
-use vm_api::util::{apply_code, apply_ok};
+use vm_api::util::{apply_code, apply_ok, apply_signed_code, sign_message};
 
 /// Using a deal proposal as a serialized message, we confirm that:
 /// - calls to Account::authenticate_message with valid signatures succeed
@@ -49,3 +52,47 @@ pub fn account_authenticate_message_test(v: &dyn VM) {
         ExitCode::USR_ILLEGAL_ARGUMENT,
     );
 }
+
+/// Messages executed with sender authentication are applied only if signed by the sender's
+/// account actor over the message at the sender's current sequence.
+#[vm_test]
+pub fn signed_message_test(v: &dyn VM) {
+    let addrs = create_accounts(v, 2, &TokenAmount::from_whole(10_000));
+    let (sender, receiver) = (addrs[0], addrs[1]);
+    let value = TokenAmount::from_whole(1);
+    let sequence = |v: &dyn VM, addr: &Address| {
+        v.actor(&v.resolve_id_address(addr).unwrap()).unwrap().sequence
+    };
+
+    // A correctly signed message is applied.
+    let signature = sign_message(v, &sender, &receiver, &value, METHOD_SEND, &None);
+    apply_signed_code(v, &sender, &receiver, &value, METHOD_SEND, None::<RawBytes>, ExitCode::OK);
+    assert_eq!(1, sequence(v, &sender));
+    assert_eq!(TokenAmount::from_whole(10_001), v.balance(&receiver));
+
+    // A signature over the message at a prior sequence is rejected.
+    assert!(
+        v.execute_signed_message(&sender, &receiver, &value, METHOD_SEND, None, &signature)
+            .is_err()
+    );
+
+    // An invalid signature is rejected.
+    assert!(v.execute_signed_message(&sender, &receiver, &value, METHOD_SEND, None, &[]).is_err());
+    assert_eq!(1, sequence(v, &sender));
+    assert_eq!(TokenAmount::from_whole(10_001), v.balance(&receiver));
+
+    // A sender which is not an account actor cannot sign messages.
+    let signature =
+        sign_message(v, &STORAGE_MARKET_ACTOR_ADDR, &receiver, &value, METHOD_SEND, &None);
+    assert!(
+        v.execute_signed_message(
+            &STORAGE_MARKET_ACTOR_ADDR,
+            &receiver,
+            &value,
+            METHOD_SEND,
+            None,
+            &signature
+        )
+        .is_err()
+    );
+}
@@ -13,7 +13,7 @@ use vm_api::util::{DynBlockstore, get_state};
 use crate::util::{
     advance_to_proving_deadline, create_accounts, create_miner, cron_tick, expect_invariants,
     get_network_stats, invariant_failure_patterns, miner_balance, precommit_sectors_v2,
-    prove_commit_sectors, submit_windowed_post,
+    prove_commit_sectors, report_state_size, submit_windowed_post,
 };
 
 struct Onboarding {
@@ -141,6 +141,7 @@ pub fn batch_onboarding_test(v: &dyn VM) {
         BigInt::from(sector_size * proven_count as u64)
     );
     assert!(network_stats.total_pledge_collateral.is_positive());
+    report_state_size(v, "batch_onboarding");
 
     expect_invariants(
         v,
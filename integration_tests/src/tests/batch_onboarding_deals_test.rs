@@ -254,7 +254,7 @@ pub fn prove_commit_aggregate(
 
     let params = ProveCommitSectors3Params {
         sector_activations,
-        sector_proofs: vec![],
+        sector_proofs: vec![].into(),
         aggregate_proof: RawBytes::new(vec![0; 192]),
         aggregate_proof_type: Some(RegisteredAggregateProof::SnarkPackV2),
         require_activation_success: true,
@@ -12,7 +12,8 @@ use crate::expects::Expect;
 use crate::util::{
     advance_by_deadline_to_epoch, advance_to_proving_deadline, assert_invariants, create_accounts,
     create_miner, cron_tick, expect_invariants, get_network_stats, invariant_failure_patterns,
-    miner_balance, miner_prove_sector, precommit_sectors_v2, submit_windowed_post,
+    miner_balance, miner_dline_info, miner_prove_sector, precommit_sectors_v2,
+    submit_windowed_post,
 };
 use fil_actor_cron::Method as CronMethod;
 use fil_actor_market::Method as MarketMethod;
@@ -242,6 +243,103 @@ pub fn skip_sector_test(v: &dyn VM) {
     assert_invariants(v, &Policy::default(), None)
 }
 
+#[vm_test]
+pub fn deferred_cron_failure_removes_claim_test(v: &dyn VM) {
+    let (miner_info, sector_info) = setup(v);
+    let p_st: PowerState = get_state(v, &STORAGE_POWER_ACTOR_ADDR).unwrap();
+    let miner_count = p_st.miner_count;
+
+    // The miner's deadline cron aborts at the end of the sector's proving deadline.
+    v.set_invocation_failure(
+        &miner_info.miner_id,
+        MinerMethod::OnDeferredCronEvent as u64,
+        Some(ExitCode::USR_ILLEGAL_STATE),
+    )
+    .unwrap();
+    v.set_epoch(sector_info.deadline_info.last());
+    cron_tick(v);
+
+    ExpectInvocation {
+        to: CRON_ACTOR_ADDR,
+        method: CronMethod::EpochTick as u64,
+        params: None,
+        subinvocs: Some(vec![
+            ExpectInvocation {
+                from: CRON_ACTOR_ID,
+                to: STORAGE_POWER_ACTOR_ADDR,
+                method: PowerMethod::OnEpochTickEnd as u64,
+                subinvocs: Some(vec![
+                    Expect::reward_this_epoch(STORAGE_POWER_ACTOR_ID),
+                    ExpectInvocation {
+                        from: STORAGE_POWER_ACTOR_ID,
+                        to: miner_info.miner_id,
+                        method: MinerMethod::OnDeferredCronEvent as u64,
+                        exit_code: ExitCode::USR_ILLEGAL_STATE,
+                        subinvocs: Some(vec![]),
+                        ..Default::default()
+                    },
+                    Expect::reward_update_kpi(),
+                ]),
+                ..Default::default()
+            },
+            ExpectInvocation {
+                from: CRON_ACTOR_ID,
+                to: STORAGE_MARKET_ACTOR_ADDR,
+                method: MarketMethod::CronTick as u64,
+                ..Default::default()
+            },
+        ]),
+        ..Default::default()
+    }
+    .matches(v.take_invocations().last().unwrap());
+
+    // The power actor removes the miner's claim, and the miner's cron is not re-enrolled.
+    let p_st: PowerState = get_state(v, &STORAGE_POWER_ACTOR_ADDR).unwrap();
+    let store = DynBlockstore::wrap(v.blockstore());
+    assert!(p_st.get_claim(&store, &miner_info.miner_id).unwrap().is_none());
+    assert_eq!(miner_count - 1, p_st.miner_count);
+    assert!(p_st.total_bytes_committed.is_zero());
+    let expected_failures = [
+        invariant_failure_patterns::REWARD_STATE_EPOCH_MISMATCH.to_owned(),
+        invariant_failure_patterns::MINER_NO_POWER_CLAIM.to_owned(),
+        invariant_failure_patterns::MINER_DEADLINE_CRON_NOT_ENROLLED.to_owned(),
+    ];
+    expect_invariants(v, &Policy::default(), &expected_failures, None);
+
+    // Without a claim, the miner's next PoSt cannot activate the sector's power.
+    v.set_invocation_failure(&miner_info.miner_id, MinerMethod::OnDeferredCronEvent as u64, None)
+        .unwrap();
+    v.set_epoch(sector_info.deadline_info.open + Policy::default().wpost_proving_period);
+    let deadline_info = miner_dline_info(v, &miner_info.miner_id);
+    assert_eq!(sector_info.deadline_info.index, deadline_info.index);
+    let params = SubmitWindowedPoStParams {
+        deadline: deadline_info.index,
+        partitions: vec![PoStPartition {
+            index: sector_info.partition_index,
+            skipped: BitField::new(),
+        }],
+        proofs: vec![PoStProof {
+            post_proof: miner_info.seal_proof.registered_window_post_proof().unwrap(),
+            proof_bytes: vec![],
+        }],
+        chain_commit_epoch: deadline_info.challenge,
+        chain_commit_rand: Randomness(TEST_VM_RAND_ARRAY.into()),
+    };
+    apply_code(
+        v,
+        &miner_info.worker,
+        &miner_info.miner_id,
+        &TokenAmount::zero(),
+        MinerMethod::SubmitWindowedPoSt as u64,
+        Some(params),
+        ExitCode::USR_NOT_FOUND,
+    );
+
+    let p_st: PowerState = get_state(v, &STORAGE_POWER_ACTOR_ADDR).unwrap();
+    assert!(p_st.get_claim(&store, &miner_info.miner_id).unwrap().is_none());
+    expect_invariants(v, &Policy::default(), &expected_failures, None);
+}
+
 #[vm_test]
 pub fn missed_first_post_deadline_test(v: &dyn VM) {
     let (miner_info, sector_info) = setup(v);
@@ -377,6 +475,11 @@ pub fn overdue_precommit_test(v: &dyn VM) {
                             Expect::burn(id_addr.id().unwrap(), Some(precommit.pre_commit_deposit)),
                             // No re-enrollment of cron because burning of PCD discontinues miner cron scheduling
                         ]),
+                        events: Some(vec![Expect::build_precommit_expired_event(
+                            id_addr.id().unwrap(),
+                            sector_number,
+                            &precommit.pre_commit_deposit,
+                        )]),
                         ..Default::default()
                     },
                     Expect::reward_update_kpi(),
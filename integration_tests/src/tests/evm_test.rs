@@ -2,6 +2,7 @@ use alloy_core::sol_types::{SolCall, SolInterface, decode_revert_reason};
 use alloy_core::{primitives::Address as EthAddress, sol};
 
 use export_macro::vm_test;
+use fil_actor_evm::revert::RevertReason;
 use fil_actors_evm_shared::uints::U256;
 use fil_actors_runtime::{
     EAM_ACTOR_ADDR, EAM_ACTOR_ID, test_utils::ETHACCOUNT_ACTOR_CODE_ID,
@@ -295,6 +296,19 @@ pub fn evm_create_test(v: &dyn VM) {
             "expected contract revert {}",
             call_result.message
         );
+        assert!(
+            call_result.message.ends_with("\"create failed\""),
+            "unexpected message: {}",
+            call_result.message
+        );
+        assert_eq!(
+            Some(RevertReason::Error("create failed".to_string())),
+            RevertReason::from_error_data(call_result.ret.as_ref())
+        );
+        // The revert payload is also recorded in the invocation trace.
+        let trace = v.take_invocations().pop().unwrap();
+        assert_eq!(call_result.ret, trace.return_value);
+
         let BytesDe(return_value) =
             call_result.ret.unwrap().deserialize().expect("failed to deserialize results");
         let revert_msg: String = decode_revert_reason(&return_value).expect("expected a revert");
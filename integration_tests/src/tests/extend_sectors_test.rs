@@ -621,7 +621,7 @@ pub fn extend_updated_sector_with_claims_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -663,8 +663,7 @@ pub fn extend_updated_sector_with_claims_test(v: &dyn VM) {
                 from: miner_id,
                 to: VERIFIED_REGISTRY_ACTOR_ADDR,
                 method: VerifregMethod::ClaimAllocations as u64,
-                events: Some(vec![Expect::build_verifreg_claim_event(
-                    "claim",
+                events: Some(vec![Expect::build_verifreg_new_claim_event(
                     claim_id,
                     verified_client.id().unwrap(),
                     miner_id,
@@ -674,6 +673,7 @@ pub fn extend_updated_sector_with_claims_test(v: &dyn VM) {
                     claim_term + MARKET_DEFAULT_ALLOCATION_TERM_BUFFER,
                     v.epoch(),
                     sector_number,
+                    initial_sector_info.expiration,
                 )]),
                 ..Default::default()
             },
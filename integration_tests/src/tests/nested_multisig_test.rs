@@ -1,8 +1,8 @@
 use export_macro::vm_test;
 use fil_actor_init::ExecReturn;
 use fil_actor_multisig::{
-    Method as MsigMethod, PENDING_TXN_CONFIG, PendingTxnMap, ProposeParams, State as MsigState,
-    Transaction, TxnID, TxnIDParams,
+    ApproveByHashParams, Method as MsigMethod, PENDING_TXN_CONFIG, PendingTxnMap, ProposeParams,
+    State as MsigState, Transaction, TxnID, TxnIDParams, compute_proposal_hash,
 };
 use fil_actors_runtime::INIT_ACTOR_ADDR;
 use fil_actors_runtime::cbor::serialize;
@@ -262,3 +262,81 @@ pub fn nested_multisig_direct_proposal_test(v: &dyn VM) {
 
     assert_invariants(v, &Policy::default(), None);
 }
+
+#[vm_test]
+pub fn nested_multisig_approve_by_hash_test(v: &dyn VM) {
+    // The inner multisig approves a proposal to the main multisig by its hash,
+    // without depending on the proposal's transaction ID.
+    let addrs = create_accounts(v, 4, &TokenAmount::from_whole(10_000));
+    let (alice, bob, charlie, recipient) = (addrs[0], addrs[1], addrs[2], addrs[3]);
+
+    let inner_msig_addr = create_msig(v, &[alice, bob], 2);
+    let main_msig_addr = create_msig(v, &[inner_msig_addr, charlie], 2);
+    apply_ok(
+        v,
+        &alice,
+        &main_msig_addr,
+        &TokenAmount::from_whole(1000),
+        METHOD_SEND,
+        None::<RawBytes>,
+    );
+
+    // Charlie proposes in the main multisig.
+    let send_amount = TokenAmount::from_whole(50);
+    apply_ok(
+        v,
+        &charlie,
+        &main_msig_addr,
+        &TokenAmount::zero(),
+        MsigMethod::Propose as u64,
+        Some(ProposeParams {
+            to: recipient,
+            value: send_amount.clone(),
+            method: METHOD_SEND,
+            params: RawBytes::default(),
+        }),
+    );
+    let proposal = Transaction {
+        to: recipient,
+        value: send_amount.clone(),
+        method: METHOD_SEND,
+        params: RawBytes::default(),
+        approved: vec![charlie],
+    };
+    let proposal_hash = compute_proposal_hash(&proposal, v.primitives()).unwrap();
+    check_txs(v, main_msig_addr, vec![(TxnID(0), proposal)]);
+
+    // Alice and Bob approve it through the inner multisig, by hash.
+    apply_ok(
+        v,
+        &alice,
+        &inner_msig_addr,
+        &TokenAmount::zero(),
+        MsigMethod::Propose as u64,
+        Some(ProposeParams {
+            to: main_msig_addr,
+            value: TokenAmount::zero(),
+            method: MsigMethod::ApproveByHashExported as u64,
+            params: serialize(
+                &ApproveByHashParams { proposal_hash: proposal_hash.to_vec(), txn_id: TxnID(0) },
+                "approve by hash params",
+            )
+            .unwrap(),
+        }),
+    );
+    let recipient_balance_before = v.actor(&recipient).unwrap().balance;
+    apply_ok(
+        v,
+        &bob,
+        &inner_msig_addr,
+        &TokenAmount::zero(),
+        MsigMethod::Approve as u64,
+        Some(TxnIDParams { id: TxnID(0), proposal_hash: vec![] }),
+    );
+
+    assert_eq!(recipient_balance_before + send_amount, v.actor(&recipient).unwrap().balance);
+    check_txs(v, main_msig_addr, vec![]);
+    check_txs(v, inner_msig_addr, vec![]);
+
+    assert_invariants(v, &Policy::default(), None);
+}
@@ -3,7 +3,7 @@ use fil_actor_init::Method as InitMethod;
 use fil_actor_miner::{
     MIN_SECTOR_EXPIRATION, Method as MinerMethod, MinerConstructorParams, max_prove_commit_duration,
 };
-use fil_actor_power::{CreateMinerParams, Method as PowerMethod};
+use fil_actor_power::{CreateMinerParams, CreateMinerReturn, Method as PowerMethod};
 use fil_actors_runtime::runtime::Policy;
 
 use fil_actors_runtime::{
@@ -42,11 +42,13 @@ pub fn power_create_miner_test(v: &dyn VM) {
         worker: owner,
         window_post_proof_type,
         peer: peer_id.clone(),
-        multiaddrs: multiaddrs.clone(),
+        multiaddrs: multiaddrs.clone().into(),
+        control_addresses: vec![],
     };
     let res = create_miner_internal(v, &params, &value);
 
     let owner_id = v.resolve_id_address(&owner).unwrap().id().unwrap();
+    let miner_ret: CreateMinerReturn = res.ret.as_ref().unwrap().deserialize().unwrap();
     let expect = ExpectInvocation {
         // send to power actor
         from: owner_id,
@@ -72,12 +74,19 @@ pub fn power_create_miner_test(v: &dyn VM) {
                             window_post_proof_type,
                             peer_id,
                             control_addresses: vec![],
-                            multi_addresses: multiaddrs,
+                            multi_addresses: multiaddrs.into(),
                         })
                         .unwrap(),
                     ),
                     ..Default::default()
                 }]),
+                events: Some(vec![Expect::build_actor_created_event(
+                    miner_ret.id_address.id().unwrap(),
+                    v.actor(&miner_ret.id_address).unwrap().code,
+                    miner_ret.robust_address,
+                    None,
+                    STORAGE_POWER_ACTOR_ID,
+                )]),
                 ..Default::default()
             },
         ]),
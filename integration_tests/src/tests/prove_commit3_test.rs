@@ -208,7 +208,7 @@ pub fn prove_commit_sectors3_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveCommitSectors3Params {
         sector_activations: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         aggregate_proof_type: None,
         require_activation_success: true,
@@ -240,8 +240,7 @@ pub fn prove_commit_sectors3_test(v: &dyn VM) {
         })
         .collect();
 
-    let claim_event_1 = Expect::build_verifreg_claim_event(
-        "claim",
+    let claim_event_1 = Expect::build_verifreg_new_claim_event(
         alloc_ids_s2[0],
         client_id,
         miner_id,
@@ -251,10 +250,10 @@ pub fn prove_commit_sectors3_test(v: &dyn VM) {
         claim_term_max,
         v.epoch(),
         first_sector_number + 2,
+        sector_expiry,
     );
 
-    let claim_event_2 = Expect::build_verifreg_claim_event(
-        "claim",
+    let claim_event_2 = Expect::build_verifreg_new_claim_event(
         alloc_ids_s2[1],
         client_id,
         miner_id,
@@ -264,10 +263,10 @@ pub fn prove_commit_sectors3_test(v: &dyn VM) {
         claim_term_max,
         v.epoch(),
         first_sector_number + 2,
+        sector_expiry,
     );
 
-    let claim_event_3 = Expect::build_verifreg_claim_event(
-        "claim",
+    let claim_event_3 = Expect::build_verifreg_new_claim_event(
         alloc_ids_s4[0],
         client_id,
         miner_id,
@@ -277,6 +276,7 @@ pub fn prove_commit_sectors3_test(v: &dyn VM) {
         claim_term_max,
         v.epoch(),
         first_sector_number + 4,
+        sector_expiry,
     );
 
     ExpectInvocation {
@@ -32,10 +32,11 @@ use fil_actors_runtime::runtime::Policy;
 use fil_actors_runtime::runtime::policy_constants::MARKET_DEFAULT_ALLOCATION_TERM_BUFFER;
 use fil_actors_runtime::test_utils::{make_piece_cid, make_sealed_cid};
 use fil_actors_runtime::{
-    EPOCHS_IN_DAY, EPOCHS_IN_YEAR, STORAGE_MARKET_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR,
+    EPOCHS_IN_DAY, EPOCHS_IN_YEAR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ID,
+    VERIFIED_REGISTRY_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ID,
 };
 use vm_api::VM;
-use vm_api::trace::{EmittedEvent, ExpectInvocation};
+use vm_api::trace::{EmittedEvent, ExpectEvent, ExpectInvocation};
 use vm_api::util::{DynBlockstore, apply_code, apply_ok, get_state, mutate_state};
 
 use crate::deals::{DealBatcher, DealOptions};
@@ -272,7 +273,7 @@ pub fn prove_replica_update_multi_dline_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -355,7 +356,7 @@ pub fn immutable_deadline_failure_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -421,7 +422,7 @@ pub fn unhealthy_sector_failure_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -503,7 +504,7 @@ pub fn terminated_sector_failure_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests,
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -613,7 +614,7 @@ pub fn bad_post_upgrade_dispute_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests,
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -790,7 +791,7 @@ pub fn wrong_deadline_index_failure_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -856,7 +857,7 @@ pub fn wrong_partition_index_failure_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -976,7 +977,7 @@ pub fn deal_included_in_multiple_sectors_failure_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -1084,7 +1085,7 @@ pub fn replica_update_verified_deal_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -1106,8 +1107,7 @@ pub fn replica_update_verified_deal_test(v: &dyn VM) {
     let claim_id = 1_u64;
     let deal_term = proposal.end_epoch - proposal.start_epoch;
     let term_max = deal_term + MARKET_DEFAULT_ALLOCATION_TERM_BUFFER;
-    let claim_event = Expect::build_verifreg_claim_event(
-        "claim",
+    let claim_event = Expect::build_verifreg_new_claim_event(
         claim_id,
         client.id().unwrap(),
         maddr.id().unwrap(),
@@ -1117,6 +1117,7 @@ pub fn replica_update_verified_deal_test(v: &dyn VM) {
         term_max,
         v.epoch(),
         sector_number,
+        old_sector_info.expiration,
     );
     let old_power = power_for_sector(seal_proof.sector_size().unwrap(), &old_sector_info);
 
@@ -1238,7 +1239,7 @@ pub fn replica_update_verified_deal_max_term_violated_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -1433,7 +1434,7 @@ pub fn create_miner_and_upgrade_sector(
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -1515,7 +1516,7 @@ pub fn prove_replica_update2_test(v: &dyn VM) {
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); activations.len()];
     let params = ProveCommitSectors3Params {
         sector_activations: activations,
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         aggregate_proof_type: None,
         require_activation_success: true,
@@ -1670,49 +1671,26 @@ pub fn prove_replica_update2_test(v: &dyn VM) {
         },
     ];
 
-    let claim_event_1 = Expect::build_verifreg_claim_event(
-        "claim",
-        alloc_ids_s2[0],
-        client_id,
-        miner_id,
-        &allocs[0].data,
-        allocs[0].size.0,
-        claim_term_min,
-        claim_term_max,
-        v.epoch(),
-        first_sector_number + 2,
-    );
-    let claim_event_2 = Expect::build_verifreg_claim_event(
-        "claim",
-        alloc_ids_s2[1],
-        client_id,
-        miner_id,
-        &allocs[1].data,
-        allocs[1].size.0,
-        claim_term_min,
-        claim_term_max,
-        v.epoch(),
-        first_sector_number + 2,
-    );
-    let claim_event_3 = Expect::build_verifreg_claim_event(
-        "claim",
-        alloc_ids_s4[0],
-        client_id,
-        miner_id,
-        &manifests[4].pieces[0].cid,
-        manifests[4].pieces[0].size.0,
-        claim_term_min,
-        claim_term_max,
-        v.epoch(),
-        first_sector_number + 4,
-    );
+    // The claims' terms and parties are common, so only their identity is checked.
+    let claim_event = |id: u64, data: Cid, sector: SectorNumber| {
+        ExpectEvent::new(VERIFIED_REGISTRY_ACTOR_ID, "claim")
+            .field("id", id)
+            .field("piece-cid", data)
+            .field("term-start", v.epoch())
+            .field("sector", sector)
+    };
+    let claim_events = vec![
+        claim_event(alloc_ids_s2[0], allocs[0].data, first_sector_number + 2),
+        claim_event(alloc_ids_s2[1], allocs[1].data, first_sector_number + 2),
+        claim_event(alloc_ids_s4[0], manifests[4].pieces[0].cid, first_sector_number + 4),
+    ];
 
     // Replica update
     let update_proof = seal_proof.registered_update_proof().unwrap();
     let proofs = vec![RawBytes::new(vec![1, 2, 3, 4]); manifests.len()];
     let params = ProveReplicaUpdates3Params {
         sector_updates: manifests.clone(),
-        sector_proofs: proofs,
+        sector_proofs: proofs.into(),
         aggregate_proof: RawBytes::default(),
         update_proofs_type: update_proof,
         aggregate_proof_type: None,
@@ -1809,7 +1787,7 @@ pub fn prove_replica_update2_test(v: &dyn VM) {
                     })
                     .unwrap(),
                 ),
-                events: Some(vec![claim_event_1, claim_event_2, claim_event_3]),
+                typed_events: Some(claim_events),
                 ..Default::default()
             },
             Expect::reward_this_epoch(miner_id),
@@ -1840,21 +1818,16 @@ pub fn prove_replica_update2_test(v: &dyn VM) {
                 ),
                 value: Some(TokenAmount::zero()),
                 subinvocs: Some(vec![]),
-                events: Some(
+                typed_events: Some(
                     deal_ids_s3
                         .iter()
                         .chain(deal_ids_s4.iter())
                         .map(|deal_id| {
-                            Expect::build_market_event(
-                                "deal-activated",
-                                *deal_id,
-                                client_id,
-                                miner_id,
-                            )
+                            ExpectEvent::new(STORAGE_MARKET_ACTOR_ID, "deal-activated")
+                                .field("id", *deal_id)
                         })
-                        .collect::<Vec<_>>(),
+                        .collect(),
                 ),
-
                 ..Default::default()
             },
         ]),
@@ -34,7 +34,8 @@ use crate::util::{
     advance_to_proving_deadline, assert_invariants, create_accounts, create_miner, cron_tick,
     deal_cid_for_testing, make_bitfield, make_piece_manifests_from_deal_ids, market_publish_deal,
     miner_balance, miner_precommit_one_sector_v2, miner_prove_sector,
-    precommit_meta_data_from_deals, submit_windowed_post, verifreg_add_verifier,
+    precommit_meta_data_from_deals, report_state_size, submit_windowed_post, verifreg_add_verifier,
+    verifreg_list_claims,
 };
 
 #[vm_test]
@@ -221,6 +222,22 @@ pub fn terminate_sectors_test(v: &dyn VM) {
         (deal_ids[2], unverified_client.id().unwrap()),
     ];
 
+    // The verified deals' claims have not met their minimum term, so are removed
+    // with the epochs of the term lost.
+    let mut claims: Vec<_> = verifreg_list_claims(v, miner_id).into_iter().collect();
+    claims.sort_by_key(|(id, _)| *id);
+    assert_eq!(2, claims.len());
+    let claim_events = claims
+        .iter()
+        .map(|(id, claim)| {
+            Expect::build_verifreg_claim_terminated_event(
+                *id,
+                claim,
+                claim.term_start + claim.term_min - epoch,
+            )
+        })
+        .collect();
+
     // Terminate Sector
     apply_ok(
         v,
@@ -251,6 +268,7 @@ pub fn terminate_sectors_test(v: &dyn VM) {
                 [sector_number].to_vec(),
                 deal_clients,
             ),
+            Expect::verifreg_remove_sector_claims(miner_id, vec![sector_number], claim_events),
             Expect::power_update_claim(miner_id, sector_power.neg()),
         ]),
         events: Some(vec![expect_event]),
@@ -270,6 +288,9 @@ pub fn terminate_sectors_test(v: &dyn VM) {
     assert!(pow_st.total_qa_bytes_committed.is_zero());
     assert!(pow_st.total_pledge_collateral.is_zero());
 
+    // the verified deals' claims were removed
+    assert!(verifreg_list_claims(v, miner_id).is_empty());
+
     // termination synchronously deletes deal state
     let st: MarketState = get_state(v, &STORAGE_MARKET_ACTOR_ADDR).unwrap();
     let store = DynBlockstore::wrap(v.blockstore());
@@ -323,5 +344,6 @@ pub fn terminate_sectors_test(v: &dyn VM) {
     assert!(TokenAmount::from_whole(58) < value_withdrawn);
     assert!(TokenAmount::from_whole(59) > value_withdrawn);
 
+    report_state_size(v, "terminate_sectors");
     assert_invariants(v, &Policy::default(), None);
 }
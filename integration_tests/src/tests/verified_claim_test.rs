@@ -351,8 +351,10 @@ pub fn verified_claim_scenario_test(v: &dyn VM) {
     // And advance vm past the claim's max term (no more sector exists to prove)
     v.set_epoch(new_claim_expiry_epoch);
     // Expired claim can now be cleaned up
-    let cleanup_claims =
-        RemoveExpiredClaimsParams { provider: miner_id.id().unwrap(), claim_ids: vec![claim_id] };
+    let cleanup_claims = RemoveExpiredClaimsParams {
+        provider: miner_id.id().unwrap(),
+        claim_ids: vec![claim_id].into(),
+    };
 
     let ret_raw = apply_ok(
         v,
@@ -596,7 +598,7 @@ pub fn deal_passes_claim_fails_test(v: &dyn VM) {
                 pieces: make_piece_manifests_from_deal_ids(v, vec![deal]),
             },
         ],
-        sector_proofs: vec![vec![].into(), vec![].into()],
+        sector_proofs: vec![vec![].into(), vec![].into()].into(),
         aggregate_proof: RawBytes::default(),
         aggregate_proof_type: None,
         require_activation_success: true, //
@@ -5,7 +5,7 @@ use fil_actor_datacap::{
 use fil_actor_verifreg::state::{REMOVE_DATACAP_PROPOSALS_CONFIG, RemoveDataCapProposalMap};
 use fil_actor_verifreg::{
     AddVerifiedClientParams, DataCap, RemoveDataCapParams, RemoveDataCapRequest,
-    RemoveDataCapReturn, SIGNATURE_DOMAIN_SEPARATION_REMOVE_DATA_CAP,
+    RemoveDataCapReturn, SIGNATURE_DOMAIN_SEPARATION_REMOVE_DATA_CAP, VerifierActivity,
 };
 use fil_actor_verifreg::{AddrPairKey, Method as VerifregMethod};
 use fil_actor_verifreg::{RemoveDataCapProposal, RemoveDataCapProposalID, State as VerifregState};
@@ -32,7 +32,8 @@ use vm_api::util::{DynBlockstore, apply_code, apply_ok, get_state};
 use crate::expects::Expect;
 
 use crate::util::{
-    assert_invariants, create_accounts, verifier_balance_event_with_client, verifreg_add_verifier,
+    assert_invariants, create_accounts, verifier_activity_event,
+    verifier_balance_event_with_client, verifreg_add_verifier,
 };
 use crate::{TEST_VERIFREG_ROOT_ADDR, TEST_VERIFREG_ROOT_ID};
 
@@ -70,6 +71,8 @@ pub fn remove_datacap_simple_successful_path_test(v: &dyn VM) {
     );
 
     let verifier_datacap = DataCap::from(0);
+    let mut verifier_activity = VerifierActivity::default();
+    verifier_activity.record_grant(v.epoch(), &verifier_allowance);
 
     ExpectInvocation {
         from: verifier1_id,
@@ -84,11 +87,19 @@ pub fn remove_datacap_simple_successful_path_test(v: &dyn VM) {
             subinvocs: None,
             ..Default::default()
         }]),
-        events: Some(vec![verifier_balance_event_with_client(
-            verifier1.id().unwrap(),
-            verifier_datacap,
-            verified_client.id().unwrap(),
-        )]),
+        events: Some(vec![
+            verifier_balance_event_with_client(
+                verifier1.id().unwrap(),
+                verifier_datacap,
+                verified_client.id().unwrap(),
+            ),
+            verifier_activity_event(
+                verifier1.id().unwrap(),
+                verified_client.id().unwrap(),
+                &verifier_allowance,
+                &verifier_activity,
+            ),
+        ]),
         ..Default::default()
     }
     .matches(v.take_invocations().last().unwrap());
@@ -18,6 +18,7 @@ use fil_actors_runtime::{
     VERIFIED_REGISTRY_ACTOR_ADDR, parse_uint_key, runtime::Policy,
 };
 use fil_builtin_actors_state::check::check_state_invariants;
+use fil_builtin_actors_state::size::StateSizeReport;
 use fvm_ipld_bitfield::BitField;
 use fvm_ipld_encoding::{CborStore, DAG_CBOR, RawBytes};
 use fvm_shared::address::Address;
@@ -95,6 +96,24 @@ pub fn expect_invariants(
     check_invariants(v, policy, expected_balance_total).unwrap().assert_expected(expected_patterns)
 }
 
+/// Environment variable which, when set, enables the state size reports printed by tests.
+pub const STATE_SIZE_REPORT_ENV: &str = "STATE_SIZE_REPORT";
+
+pub fn state_size_report(v: &dyn VM) -> anyhow::Result<StateSizeReport> {
+    fil_builtin_actors_state::size::state_size_report(
+        &DynBlockstore::wrap(v.blockstore()),
+        &v.actor_manifest(),
+        &v.actor_states(),
+    )
+}
+
+/// Prints a report of the size of each actor's state, if enabled by the environment.
+pub fn report_state_size(v: &dyn VM, label: &str) {
+    if std::env::var_os(STATE_SIZE_REPORT_ENV).is_some() {
+        println!("{}: {}", label, state_size_report(v).unwrap());
+    }
+}
+
 pub fn miner_balance(v: &dyn VM, m: &Address) -> MinerBalances {
     let st: MinerState = get_state(v, m).unwrap();
     MinerBalances {
@@ -259,6 +278,11 @@ pub mod invariant_failure_patterns {
     lazy_static! {
         pub static ref REWARD_STATE_EPOCH_MISMATCH: Regex =
             Regex::new("^reward state epoch \\d+ does not match prior_epoch\\+1 \\d+$").unwrap();
+        pub static ref MINER_NO_POWER_CLAIM: Regex =
+            Regex::new("^miner [ft]0\\d+ has no power claim$").unwrap();
+        pub static ref MINER_DEADLINE_CRON_NOT_ENROLLED: Regex =
+            Regex::new("^miner [ft]0\\d+ has no cron events but the deadline cron is active$")
+                .unwrap();
     }
 }
 
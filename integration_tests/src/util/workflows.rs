@@ -54,7 +54,7 @@ use fil_actor_verifreg::ext::datacap::MintParams;
 use fil_actor_verifreg::{
     AddVerifiedClientParams, AllocationID, ClaimID, ClaimTerm, ExtendClaimTermsParams,
     Method as VerifregMethod, RemoveExpiredAllocationsParams, State as VerifregState,
-    VerifierParams,
+    VerifierActivity, VerifierParams,
 };
 use fil_actor_verifreg::{AllocationRequest, DataCap};
 use fil_actor_verifreg::{AllocationRequests, state};
@@ -129,7 +129,8 @@ pub fn create_miner(
         worker: *worker,
         window_post_proof_type: post_proof_type,
         peer: peer_id,
-        multiaddrs,
+        multiaddrs: multiaddrs.into(),
+        control_addresses: vec![],
     };
     let res: CreateMinerReturn =
         create_miner_internal(v, &params, balance).ret.unwrap().deserialize().unwrap();
@@ -216,7 +217,7 @@ pub fn miner_prove_sector(
 ) {
     let prove_commit_params = ProveCommitSectors3Params {
         sector_activations: vec![SectorActivationManifest { sector_number, pieces: manifests }],
-        sector_proofs: vec![vec![].into()],
+        sector_proofs: vec![vec![].into()].into(),
         aggregate_proof: RawBytes::default(),
         aggregate_proof_type: None,
         require_activation_success: true,
@@ -837,6 +838,27 @@ pub fn verifier_balance_event_with_client(
     }
 }
 
+pub fn verifier_activity_event(
+    verifier: ActorID,
+    client: ActorID,
+    amount: &DataCap,
+    activity: &VerifierActivity,
+) -> EmittedEvent {
+    EmittedEvent {
+        emitter: VERIFIED_REGISTRY_ACTOR_ID,
+        event: EventBuilder::new()
+            .typ("verifier-activity")
+            .field_indexed("verifier", &verifier)
+            .field_indexed("client", &client)
+            .field("amount", &BigIntSer(amount))
+            .field("window-start", &activity.window_start)
+            .field("window-granted", &BigIntSer(&activity.window_granted))
+            .field("window-grants", &activity.window_grants)
+            .build()
+            .unwrap(),
+    }
+}
+
 pub fn verifreg_add_verifier(v: &dyn VM, verifier: &Address, data_cap: StoragePower) {
     let add_verifier_params = VerifierParams { address: *verifier, allowance: data_cap.clone() };
     // root address is msig, send proposal from root key
@@ -891,6 +913,8 @@ pub fn verifreg_add_client(
     let updated_verifier_balance = verifier_cap - allowance.clone();
 
     let verifier_id = v.resolve_id_address(verifier).unwrap().id().unwrap();
+    let mut activity = v_st.get_verifier_activity(&store, verifier_id).unwrap().unwrap_or_default();
+    activity.record_grant(v.epoch(), &allowance);
     let add_client_params =
         AddVerifiedClientParams { address: *client, allowance: allowance.clone() };
     apply_ok(
@@ -901,7 +925,7 @@ pub fn verifreg_add_client(
         VerifregMethod::AddVerifiedClient as u64,
         Some(add_client_params),
     );
-    let allowance_tokens = TokenAmount::from_whole(allowance);
+    let allowance_tokens = TokenAmount::from_whole(allowance.clone());
     ExpectInvocation {
         from: verifier_id,
         to: VERIFIED_REGISTRY_ACTOR_ADDR,
@@ -929,11 +953,19 @@ pub fn verifreg_add_client(
             )]),
             ..Default::default()
         }]),
-        events: Some(vec![verifier_balance_event_with_client(
-            verifier.id().unwrap(),
-            updated_verifier_balance,
-            client.id().unwrap(),
-        )]),
+        events: Some(vec![
+            verifier_balance_event_with_client(
+                verifier.id().unwrap(),
+                updated_verifier_balance,
+                client.id().unwrap(),
+            ),
+            verifier_activity_event(
+                verifier.id().unwrap(),
+                client.id().unwrap(),
+                &allowance,
+                &activity,
+            ),
+        ]),
         ..Default::default()
     }
     .matches(v.take_invocations().last().unwrap());
@@ -951,7 +983,8 @@ pub fn verifreg_extend_claim_terms(
             provider: provider.id().unwrap(),
             claim_id: claim,
             term_max: new_term,
-        }],
+        }]
+        .into(),
     };
     apply_ok(
         v,
@@ -974,7 +1007,7 @@ pub fn verifreg_remove_expired_allocations(
     let v_st: VerifregState = get_state(v, &VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
     let store = DynBlockstore::wrap(v.blockstore());
     let mut allocs = v_st.load_allocs(&store).unwrap();
-    let expected_events: Vec<EmittedEvent> = expected_expirations
+    let mut expected_events: Vec<EmittedEvent> = expected_expirations
         .iter()
         .map(|id| {
             let alloc = allocs.get(client.id().unwrap(), *id).unwrap().unwrap();
@@ -991,6 +1024,12 @@ pub fn verifreg_remove_expired_allocations(
             )
         })
         .collect();
+    if datacap_refund > 0 {
+        expected_events.push(Expect::build_verifreg_datacap_refunded_event(
+            client.id().unwrap(),
+            datacap_refund,
+        ));
+    }
 
     let caller_id = v.resolve_id_address(caller).unwrap().id().unwrap();
     let params =
@@ -0,0 +1,60 @@
+// The features enabled for each network, selected by the BUILD_FIL_NETWORK environment variable.
+// Included by the build scripts of the crates whose features depend on the network.
+static NETWORKS: &[(&str, &[&str])] = &[
+    ("mainnet", &["sector-32g", "sector-64g"]),
+    (
+        "caterpillarnet",
+        &[
+            "sector-512m",
+            "sector-32g",
+            "sector-64g",
+            "small-deals",
+            "short-precommit",
+            "min-power-2k",
+        ],
+    ),
+    ("butterflynet", &["sector-512m", "sector-32g", "sector-64g", "min-power-2g"]),
+    ("calibrationnet", &["sector-32g", "sector-64g", "min-power-32g"]),
+    (
+        "devnet",
+        &[
+            "sector-2k",
+            "sector-8m",
+            "small-deals",
+            "short-precommit",
+            "min-power-2k",
+            "testing-mint",
+        ],
+    ),
+    (
+        "testing",
+        &[
+            "sector-2k",
+            "sector-8m",
+            "sector-512m",
+            "sector-32g",
+            "sector-64g",
+            "small-deals",
+            "short-precommit",
+            "min-power-2k",
+            "no-provider-deal-collateral",
+            "testing-mint",
+        ],
+    ),
+    (
+        "testing-fake-proofs",
+        &[
+            "sector-2k",
+            "sector-8m",
+            "sector-512m",
+            "sector-32g",
+            "sector-64g",
+            "small-deals",
+            "short-precommit",
+            "min-power-2k",
+            "no-provider-deal-collateral",
+            "fake-proofs",
+            "testing-mint",
+        ],
+    ),
+];
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 pub use self::network::*;
+pub use self::operator_data::*;
 pub use self::shared::*;
 pub use self::singletons::*;
+pub use self::sponsor::*;
 
 pub mod network;
+pub mod operator_data;
 pub mod reward;
 pub mod shared;
 pub mod singletons;
+pub mod sponsor;
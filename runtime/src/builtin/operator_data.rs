@@ -0,0 +1,83 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_ipld_encoding::RawBytes;
+use fvm_ipld_encoding::tuple::*;
+
+use crate::cbor::deserialize;
+use crate::{ActorError, actor_error};
+
+/// Operator data version carrying verified registry allocation requests and claim extensions.
+pub const OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS: u64 = 1;
+
+/// Registry of the operator data versions the datacap actor will forward to the verified
+/// registry. A new payload format is rolled out by adding its version here once the receiver
+/// can interpret it.
+pub const SUPPORTED_OPERATOR_DATA_VERSIONS: &[u64] = &[OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS];
+
+/// A versioned envelope for the operator data of a datacap transfer.
+///
+/// Unversioned operator data is also accepted, and treated as the original allocation request
+/// format. The two are unambiguous: the unversioned format is a CBOR array whose first element
+/// is itself an array, while the envelope's first element is an integer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct VersionedOperatorData {
+    pub version: u64,
+    pub payload: RawBytes,
+}
+
+impl VersionedOperatorData {
+    /// Decodes operator data as a versioned envelope, returning None if it is unversioned.
+    /// Fails if the data is an envelope of an unsupported version.
+    pub fn decode(operator_data: &RawBytes) -> Result<Option<Self>, ActorError> {
+        if operator_data.is_empty() {
+            return Ok(None);
+        }
+        let data: Self = match deserialize(operator_data, "versioned operator data") {
+            Ok(data) => data,
+            Err(_) => return Ok(None),
+        };
+        if !SUPPORTED_OPERATOR_DATA_VERSIONS.contains(&data.version) {
+            return Err(actor_error!(
+                illegal_argument,
+                "unsupported operator data version {}, supported versions are {:?}",
+                data.version,
+                SUPPORTED_OPERATOR_DATA_VERSIONS
+            ));
+        }
+        Ok(Some(data))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fvm_ipld_encoding::RawBytes;
+    use fvm_shared::error::ExitCode;
+
+    use super::{OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS, VersionedOperatorData};
+
+    #[test]
+    fn decode() {
+        // Empty and unversioned data pass through.
+        assert_eq!(None, VersionedOperatorData::decode(&RawBytes::default()).unwrap());
+        let unversioned = RawBytes::serialize((Vec::<u64>::new(), Vec::<u64>::new())).unwrap();
+        assert_eq!(None, VersionedOperatorData::decode(&unversioned).unwrap());
+
+        let versioned = VersionedOperatorData {
+            version: OPERATOR_DATA_VERSION_ALLOCATION_REQUESTS,
+            payload: unversioned,
+        };
+        let encoded = RawBytes::serialize(&versioned).unwrap();
+        assert_eq!(Some(versioned), VersionedOperatorData::decode(&encoded).unwrap());
+
+        let unsupported = RawBytes::serialize(VersionedOperatorData {
+            version: 99,
+            payload: RawBytes::default(),
+        })
+        .unwrap();
+        assert_eq!(
+            ExitCode::USR_ILLEGAL_ARGUMENT,
+            VersionedOperatorData::decode(&unsupported).unwrap_err().exit_code()
+        );
+    }
+}
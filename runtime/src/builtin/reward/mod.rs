@@ -5,8 +5,11 @@ use fvm_shared::bigint::bigint_ser;
 use fvm_shared::sector::StoragePower;
 
 pub mod math;
+pub mod pledge;
+pub mod ramp;
 pub mod smooth;
 
+pub use ramp::*;
 pub use smooth::FilterEstimate;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
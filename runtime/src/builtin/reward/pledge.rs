@@ -0,0 +1,210 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+//! Projections of the block reward earned by storage power, and the pledge requirements and
+//! penalties derived from them. The miner actor applies these to its sectors, and the reward
+//! actor exposes them so that they can be reproduced outside the miner.
+
+use std::cmp;
+
+use fvm_shared::bigint::Integer;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::StoragePower;
+use lazy_static::lazy_static;
+use num_traits::Zero;
+
+use super::math::PRECISION;
+use super::{FilterEstimate, GAMMA_FIXED_POINT_FACTOR, pledge_ramp_gamma, smooth};
+use crate::network::EPOCHS_IN_DAY;
+
+/// Projection period of expected sector block rewards for storage pledge required to commit a sector.
+/// This pledge is lost if a sector is terminated before its full committed lifetime.
+pub const INITIAL_PLEDGE_FACTOR: u64 = 20;
+
+pub const INITIAL_PLEDGE_PROJECTION_PERIOD: i64 =
+    (INITIAL_PLEDGE_FACTOR as ChainEpoch) * EPOCHS_IN_DAY;
+
+const LOCK_TARGET_FACTOR_NUM: u32 = 3;
+const LOCK_TARGET_FACTOR_DENOM: u32 = 10;
+
+/// Used to compute termination fees in the base case by multiplying against initial pledge.
+pub const TERM_FEE_PLEDGE_MULTIPLE_NUM: u32 = 85;
+pub const TERM_FEE_PLEDGE_MULTIPLE_DENOM: u32 = 1000;
+
+/// Used to ensure the termination fee for young sectors is not arbitrarily low.
+pub const TERM_FEE_MIN_PLEDGE_MULTIPLE_NUM: u32 = 2;
+pub const TERM_FEE_MIN_PLEDGE_MULTIPLE_DENOM: u32 = 100;
+
+/// Used to compute termination fees when the termination fee of a sector is less than the fault fee for the same sector.
+pub const TERM_FEE_MAX_FAULT_FEE_MULTIPLE_NUM: u32 = 105;
+pub const TERM_FEE_MAX_FAULT_FEE_MULTIPLE_DENOM: u32 = 100;
+
+lazy_static! {
+    /// Cap on initial pledge requirement for sectors during the Space Race network.
+    /// The target is 1 FIL (10**18 attoFIL) per 32GiB.
+    /// This does not divide evenly, so the result is fractionally smaller.
+    static ref INITIAL_PLEDGE_MAX_PER_BYTE: TokenAmount =
+        TokenAmount::from_whole(1).div_floor(32i64 << 30);
+}
+
+// Projection period of expected daily sector block reward penalised when a fault is continued after initial detection.
+// This guarantees that a miner pays back at least the expected block reward earned since the last successful PoSt.
+// The network conservatively assumes the sector was faulty since the last time it was proven.
+// This penalty is currently overly punitive for continued faults.
+// FF = BR(t, ContinuedFaultProjectionPeriod)
+const CONTINUED_FAULT_FACTOR_NUM: i64 = 351;
+const CONTINUED_FAULT_FACTOR_DENOM: i64 = 100;
+pub const CONTINUED_FAULT_PROJECTION_PERIOD: ChainEpoch =
+    (EPOCHS_IN_DAY * CONTINUED_FAULT_FACTOR_NUM) / CONTINUED_FAULT_FACTOR_DENOM;
+
+// Maximum number of lifetime days penalized when a sector is terminated.
+pub const TERMINATION_LIFETIME_CAP: ChainEpoch = 140;
+
+/// The projected block reward a sector would earn over some period.
+/// Also known as "BR(t)".
+/// BR(t) = ProjectedRewardFraction(t) * SectorQualityAdjustedPower
+/// ProjectedRewardFraction(t) is the sum of estimated reward over estimated total power
+/// over all epochs in the projection period [t t+projectionDuration]
+pub fn expected_reward_for_power(
+    reward_estimate: &FilterEstimate,
+    network_qa_power_estimate: &FilterEstimate,
+    qa_sector_power: &StoragePower,
+    projection_duration: ChainEpoch,
+) -> TokenAmount {
+    let network_qa_power_smoothed = network_qa_power_estimate.estimate();
+
+    if network_qa_power_smoothed.is_zero() {
+        return TokenAmount::from_atto(reward_estimate.estimate());
+    }
+
+    let expected_reward_for_proving_period = smooth::extrapolated_cum_sum_of_ratio(
+        projection_duration,
+        0,
+        reward_estimate,
+        network_qa_power_estimate,
+    );
+    let br128 = qa_sector_power * expected_reward_for_proving_period; // Q.0 * Q.128 => Q.128
+    TokenAmount::from_atto(std::cmp::max(br128 >> PRECISION, Default::default()))
+}
+
+// BR but zero values are clamped at 1 attofil
+// Some uses of BR (PCD, IP) require a strictly positive value for BR derived values so
+// accounting variables can be used as succinct indicators of miner activity.
+pub fn expected_reward_for_power_clamped_at_atto_fil(
+    reward_estimate: &FilterEstimate,
+    network_qa_power_estimate: &FilterEstimate,
+    qa_sector_power: &StoragePower,
+    projection_duration: ChainEpoch,
+) -> TokenAmount {
+    let br = expected_reward_for_power(
+        reward_estimate,
+        network_qa_power_estimate,
+        qa_sector_power,
+        projection_duration,
+    );
+    if br.le(&TokenAmount::zero()) { TokenAmount::from_atto(1) } else { br }
+}
+
+/// The penalty for a sector continuing faulty for another proving period.
+/// It is a projection of the expected reward earned by the sector.
+/// Also known as "FF(t)"
+pub fn pledge_penalty_for_continued_fault(
+    reward_estimate: &FilterEstimate,
+    network_qa_power_estimate: &FilterEstimate,
+    qa_sector_power: &StoragePower,
+) -> TokenAmount {
+    expected_reward_for_power(
+        reward_estimate,
+        network_qa_power_estimate,
+        qa_sector_power,
+        CONTINUED_FAULT_PROJECTION_PERIOD,
+    )
+}
+
+/// Calculates termination fee for a given sector. Normally, it's calculated as a fixed percentage
+/// of the initial pledge. However, there are some special cases outlined in the
+/// [FIP-0098](https://github.com/filecoin-project/FIPs/blob/master/FIPS/fip-0098.md).
+pub fn pledge_penalty_for_termination(
+    initial_pledge: &TokenAmount,
+    sector_age: ChainEpoch,
+    fault_fee: &TokenAmount,
+) -> TokenAmount {
+    // Use the _percentage of the initial pledge_ strategy to determine the termination fee.
+    let simple_termination_fee =
+        (initial_pledge * TERM_FEE_PLEDGE_MULTIPLE_NUM).div_floor(TERM_FEE_PLEDGE_MULTIPLE_DENOM);
+
+    let duration_termination_fee =
+        (sector_age * &simple_termination_fee).div_floor(TERMINATION_LIFETIME_CAP * EPOCHS_IN_DAY);
+
+    // Apply the age adjustment for young sectors to arrive at the base termination fee.
+    let base_termination_fee = cmp::min(simple_termination_fee, duration_termination_fee);
+
+    // Calculate the minimum allowed fee (a lower bound on the termination fee) by comparing the absolute minimum termination fee value against the fault fee. Whatever result is _larger_ sets the lower bound for the termination fee.
+    let minimum_fee_abs = (initial_pledge * TERM_FEE_MIN_PLEDGE_MULTIPLE_NUM)
+        .div_floor(TERM_FEE_MIN_PLEDGE_MULTIPLE_DENOM);
+    let minimum_fee_ff = (fault_fee * TERM_FEE_MAX_FAULT_FEE_MULTIPLE_NUM)
+        .div_floor(TERM_FEE_MAX_FAULT_FEE_MULTIPLE_DENOM);
+    let minimum_fee = cmp::max(minimum_fee_abs, minimum_fee_ff);
+
+    cmp::max(base_termination_fee, minimum_fee)
+}
+
+/// Computes the pledge requirement for committing new quality-adjusted power to the network, given
+/// the current network total and baseline power, per-epoch reward, and circulating token supply.
+/// The pledge comprises two parts:
+/// - storage pledge, aka IP base: a multiple of the reward expected to be earned by newly-committed power
+/// - consensus pledge, aka additional IP: a pro-rata fraction of the circulating money supply
+///
+/// IP = IPBase(t) + AdditionalIP(t)
+/// IPBase(t) = BR(t, InitialPledgeProjectionPeriod)
+/// AdditionalIP(t) = LockTarget(t)*PledgeShare(t)
+/// LockTarget = (LockTargetFactorNum / LockTargetFactorDenom) * FILCirculatingSupply(t)
+/// PledgeShare(t) = sectorQAPower / max(BaselinePower(t), NetworkQAPower(t))
+pub fn initial_pledge_for_power(
+    qa_power: &StoragePower,
+    baseline_power: &StoragePower,
+    reward_estimate: &FilterEstimate,
+    network_qa_power_estimate: &FilterEstimate,
+    circulating_supply: &TokenAmount,
+    epochs_since_ramp_start: i64,
+    ramp_duration_epochs: u64,
+) -> TokenAmount {
+    let ip_base = expected_reward_for_power_clamped_at_atto_fil(
+        reward_estimate,
+        network_qa_power_estimate,
+        qa_power,
+        INITIAL_PLEDGE_PROJECTION_PERIOD,
+    );
+
+    let lock_target_num = circulating_supply.atto() * LOCK_TARGET_FACTOR_NUM;
+    let lock_target_denom = LOCK_TARGET_FACTOR_DENOM;
+    let pledge_share_num = qa_power;
+    let network_qa_power = network_qa_power_estimate.estimate();
+
+    // Gamma/GAMMA_FIXED_POINT_FACTOR is the share of pledge coming from the
+    // baseline formulation, with 1-(gamma/GAMMA_FIXED_POINT_FACTOR) coming from
+    // simple pledge.
+    let gamma = pledge_ramp_gamma(epochs_since_ramp_start, ramp_duration_epochs);
+
+    let additional_ip_num = lock_target_num * pledge_share_num;
+
+    let pledge_share_denom_baseline =
+        cmp::max(cmp::max(&network_qa_power, baseline_power), qa_power);
+    let pledge_share_denom_simple = cmp::max(&network_qa_power, qa_power);
+
+    let additional_ip_denom_baseline = pledge_share_denom_baseline * lock_target_denom;
+    let additional_ip_baseline = (gamma * &additional_ip_num)
+        .div_floor(&(additional_ip_denom_baseline * GAMMA_FIXED_POINT_FACTOR));
+    let additional_ip_denom_simple = pledge_share_denom_simple * lock_target_denom;
+    let additional_ip_simple = ((GAMMA_FIXED_POINT_FACTOR - gamma) * &additional_ip_num)
+        .div_floor(&(additional_ip_denom_simple * GAMMA_FIXED_POINT_FACTOR));
+
+    // convex combination of simple and baseline pledge
+    let additional_ip = additional_ip_baseline + additional_ip_simple;
+
+    let nominal_pledge = ip_base + TokenAmount::from_atto(additional_ip);
+    let pledge_cap = TokenAmount::from_atto(INITIAL_PLEDGE_MAX_PER_BYTE.atto() * qa_power);
+
+    cmp::min(nominal_pledge, pledge_cap)
+}
@@ -0,0 +1,44 @@
+// Copyright 2021-2023 Protocol Labs
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+/// Fixed-point scale of the pledge ramp multiplier (3 decimal places).
+pub const GAMMA_FIXED_POINT_FACTOR: u64 = 1000;
+
+/// Once FIP-0081 has fully activated, additional pledge will be 70% baseline
+/// pledge + 30% simple pledge.
+pub const FIP_0081_ACTIVATION_PERMILLE: i64 = 300;
+
+/// Returns the pledge ramp multiplier, gamma, used by the initial pledge calculation.
+/// Gamma/GAMMA_FIXED_POINT_FACTOR is the share of additional pledge coming from the
+/// baseline formulation, with 1-(gamma/GAMMA_FIXED_POINT_FACTOR) coming from simple pledge.
+///
+/// gamma = 1000 - 300 * (epochs_since_ramp_start / ramp_duration_epochs).max(0).min(1)
+pub fn pledge_ramp_gamma(epochs_since_ramp_start: i64, ramp_duration_epochs: u64) -> u64 {
+    let skew = if epochs_since_ramp_start < 0 {
+        // No skew before ramp start
+        0
+    } else if ramp_duration_epochs == 0 || epochs_since_ramp_start >= ramp_duration_epochs as i64 {
+        // 100% skew after ramp end
+        FIP_0081_ACTIVATION_PERMILLE as u64
+    } else {
+        ((epochs_since_ramp_start * FIP_0081_ACTIVATION_PERMILLE) / ramp_duration_epochs as i64)
+            as u64
+    };
+    GAMMA_FIXED_POINT_FACTOR - skew
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gamma_ramps_linearly() {
+        assert_eq!(1000, pledge_ramp_gamma(-10, 100));
+        assert_eq!(1000, pledge_ramp_gamma(0, 100));
+        assert_eq!(850, pledge_ramp_gamma(50, 100));
+        assert_eq!(700, pledge_ramp_gamma(100, 100));
+        assert_eq!(700, pledge_ramp_gamma(1000, 100));
+        // A zero duration ramp is complete as soon as it starts.
+        assert_eq!(700, pledge_ramp_gamma(0, 0));
+    }
+}
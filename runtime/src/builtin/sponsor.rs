@@ -0,0 +1,60 @@
+// Copyright 2019-2022 ChainSafe Systems
+// SPDX-License-Identifier: Apache-2.0, MIT
+
+use fvm_ipld_encoding::strict_bytes;
+use fvm_ipld_encoding::tuple::*;
+use fvm_shared::MethodNum;
+use fvm_shared::address::Address;
+
+use crate::ActorError;
+use crate::cbor::serialize_vec;
+use crate::runtime::Runtime;
+
+/// Method number of the optional sponsorship hook.
+/// An actor implementing this method may act as a payer, authorizing coverage of
+/// the gas for messages sent by other accounts.
+pub const VALIDATE_SPONSOR_METHOD: MethodNum = frc42_dispatch::method_hash!("ValidateSponsor");
+
+/// Parameters to the sponsorship hook.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+pub struct ValidateSponsorParams {
+    /// The account whose message the sponsor is asked to pay for.
+    pub sponsored: Address,
+    /// The nonce of the sponsored account's message.
+    pub nonce: u64,
+    /// The sponsor's signature over the signing payload.
+    #[serde(with = "strict_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl ValidateSponsorParams {
+    /// Returns the bytes a sponsor signs to authorize paying for the message.
+    /// The payload binds the authorization to the sponsored account and the nonce of its
+    /// message, so it cannot be replayed for a different sender or message.
+    pub fn signing_payload(&self) -> Result<Vec<u8>, ActorError> {
+        serialize_vec(&(&self.sponsored, self.nonce), "sponsorship payload")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct ValidateSponsorReturn {
+    pub authorized: bool,
+}
+
+/// Checks that a sponsorship is requested for the currently executing message, returning
+/// the payload the sponsor must have signed, or None if the sponsorship is for another message.
+///
+/// A sponsorship authorizes paying for exactly one message: the one sent by the sponsored
+/// account with the given nonce. Since executing a message consumes its sender's nonce,
+/// a signed sponsorship cannot be replayed for another message.
+pub fn validate_sponsor(
+    rt: &impl Runtime,
+    params: &ValidateSponsorParams,
+) -> Result<Option<Vec<u8>>, ActorError> {
+    let sponsored = rt.resolve_address(&params.sponsored).map(Address::new_id);
+    if sponsored != Some(rt.message().origin()) || params.nonce != rt.message().nonce() {
+        return Ok(None);
+    }
+    params.signing_payload().map(Some)
+}
@@ -118,6 +118,66 @@ macro_rules! actor_dispatch_unrestricted {
     };
 }
 
+/// Implement read-only actor methods returning values computed from actor state.
+/// Each method accepts any caller and takes no parameters. It either returns a struct whose
+/// fields are moved from state fields (of the same name, unless one is given), or the value of
+/// an expression of the state.
+/// The methods must still be dispatched with `actor_dispatch!`.
+///
+/// ```ignore
+/// impl Actor {
+///     state_getters! {
+///         State;
+///         /// Returns the number of miners.
+///         fn miner_count() -> MinerCountReturn { miner_count }
+///         /// Returns the number of miners with sufficient power.
+///         fn consensus_count() -> ConsensusCountReturn { count: miner_above_min_power_count }
+///         /// Returns whether any miner is present.
+///         fn has_miners() -> bool = |st| st.miner_count > 0;
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! state_getters {
+    (@field $st:ident $out:ident) => {
+        $st.$out
+    };
+    (@field $st:ident $out:ident $field:ident) => {
+        $st.$field
+    };
+    ($state:ty;) => {};
+    (
+        $state:ty;
+        $(#[$m:meta])* $vis:vis fn $name:ident() -> $ret:ident {
+            $($out:ident $(: $field:ident)?),* $(,)?
+        }
+        $($rest:tt)*
+    ) => {
+        $crate::state_getters! {
+            $state;
+            $(#[$m])* $vis fn $name() -> $ret = |st| $ret {
+                $($out: $crate::state_getters!(@field st $out $($field)?)),*
+            };
+            $($rest)*
+        }
+    };
+    (
+        $state:ty;
+        $(#[$m:meta])* $vis:vis fn $name:ident() -> $ret:ty = |$st:ident| $body:expr;
+        $($rest:tt)*
+    ) => {
+        $(#[$m])*
+        $vis fn $name(
+            rt: &impl $crate::runtime::Runtime,
+        ) -> ::core::result::Result<$ret, $crate::ActorError> {
+            rt.validate_immediate_caller_accept_any()?;
+            let $st: $state = rt.state()?;
+            Ok($body)
+        }
+        $crate::state_getters! { $state; $($rest)* }
+    };
+}
+
 pub trait Dispatch<RT> {
     fn call(
         self,
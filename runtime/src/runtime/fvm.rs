@@ -41,7 +41,7 @@ use crate::runtime::randomness::draw_randomness;
 use crate::runtime::{
     ActorCode, DomainSeparationTag, MessageInfo, Policy, Primitives, RuntimePolicy,
 };
-use crate::{ActorError, AsActorError, Runtime, SendError, actor_error};
+use crate::{ActorError, AsActorError, Runtime, SendError, actor_error, check_emit_allowed};
 
 /// A runtime that bridges to the FVM environment through the FVM SDK.
 pub struct FvmRuntime<B = ActorBlockstore> {
@@ -392,6 +392,7 @@ where
     }
 
     fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError> {
+        check_emit_allowed(self)?;
         fvm::event::emit_event(event)
             .context_code(ExitCode::USR_ASSERTION_FAILED, "failed to emit event")
     }
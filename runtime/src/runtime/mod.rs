@@ -241,6 +241,12 @@ pub trait Runtime: Primitives + RuntimePolicy {
     /// Returns the gas base fee (cost per unit) for the current epoch.
     fn base_fee(&self) -> TokenAmount;
 
+    /// Returns the price per unit of gas paid by the current message:
+    /// the base fee plus the message's gas premium.
+    fn effective_gas_price(&self) -> TokenAmount {
+        self.base_fee() + self.message().gas_premium()
+    }
+
     /// The gas still available for computation
     fn gas_available(&self) -> u64;
 
@@ -252,6 +258,7 @@ pub trait Runtime: Primitives + RuntimePolicy {
     fn tipset_cid(&self, epoch: i64) -> Result<Cid, ActorError>;
 
     /// Emits an event denoting that something externally noteworthy has ocurred.
+    /// Fails with USR_READ_ONLY if the call is read-only.
     fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError>;
 
     /// Returns true if the call is read_only.
@@ -120,6 +120,15 @@ pub struct Policy {
     /// The maximum number of new sectors that may be staged by a miner during a single proving period.
     pub new_sectors_per_period_max: usize,
 
+    /// Whether sectors are scheduled to have their proofs refreshed, by re-sealing or updating
+    /// them, by some epoch after which the proof is reported expired.
+    pub proof_refresh_enabled: bool,
+
+    /// Maximum number of epochs past the current epoch by which a sector's proof may be scheduled
+    /// to be refreshed, both when the sector is sealed or updated and when the schedule is extended.
+    /// A schedule may not be extended past twice this period after the proof was last refreshed.
+    pub proof_refresh_period: ChainEpoch,
+
     /// Epochs after which chain state is final with overwhelming probability
     /// (hence the likelihood of two fork of this size is negligible).
     pub chain_finality: ChainEpoch,
@@ -156,6 +165,18 @@ pub struct Policy {
     pub maximum_verified_allocation_expiration: i64,
     // Period of time at the end of a sector's life during which claims can be dropped
     pub end_of_life_claim_drop_period: ChainEpoch,
+    /// Whether governance may create verified allocations directly, without a transfer of
+    /// datacap from a client.
+    pub governance_allocations_enabled: bool,
+
+    //
+    // --- datacap policy ---
+    //
+    /// Maximum total supply of datacap (bytes), beyond which minting fails.
+    /// A safety net against a compromised verified registry minting unbounded datacap.
+    pub datacap_supply_cap: StoragePower,
+    /// Maximum amount of datacap (bytes) minted by a single call.
+    pub datacap_max_mint_per_call: StoragePower,
 
     //
     //  --- market policy ---
@@ -180,6 +201,20 @@ pub struct Policy {
     //
     /// Minimum miner consensus power
     pub minimum_consensus_power: StoragePower,
+    /// Maximum number of payment channel cron events processed by the power actor in a single
+    /// epoch tick. Events beyond this limit are carried over to following ticks.
+    /// Miners' cron events are not limited, so are never delayed.
+    pub max_cron_events_per_tick: u64,
+
+    //
+    // --- evm policy ---
+    //
+    /// Maximum number of calls to other actors and contract creations a single invocation of an
+    /// EVM contract may make. Calls to precompiles that don't call out are not counted.
+    pub evm_max_subcalls: u64,
+    /// Maximum number of calls made by invocations of the same EVM contract that may be in
+    /// progress at once, i.e. how deeply a contract may re-enter itself within a message.
+    pub evm_max_call_depth: u64,
 }
 
 impl Default for Policy {
@@ -220,6 +255,8 @@ impl Default for Policy {
             consensus_fault_ineligibility_duration:
                 policy_constants::CONSENSUS_FAULT_INELIGIBILITY_DURATION,
             new_sectors_per_period_max: policy_constants::NEW_SECTORS_PER_PERIOD_MAX,
+            proof_refresh_enabled: policy_constants::PROOF_REFRESH_ENABLED,
+            proof_refresh_period: policy_constants::PROOF_REFRESH_PERIOD,
             chain_finality: policy_constants::CHAIN_FINALITY,
             daily_fee_circulating_supply_qap_multiplier_num: BigInt::from_u64(
                 policy_constants::DAILY_FEE_CIRCULATING_SUPPLY_QAP_MULTIPLIER_NUM,
@@ -242,6 +279,11 @@ impl Default for Policy {
             maximum_verified_allocation_expiration:
                 policy_constants::MAXIMUM_VERIFIED_ALLOCATION_EXPIRATION,
             end_of_life_claim_drop_period: policy_constants::END_OF_LIFE_CLAIM_DROP_PERIOD,
+            governance_allocations_enabled: policy_constants::GOVERNANCE_ALLOCATIONS_ENABLED,
+            datacap_supply_cap: StoragePower::from(policy_constants::DATACAP_SUPPLY_CAP),
+            datacap_max_mint_per_call: StoragePower::from(
+                policy_constants::DATACAP_MAX_MINT_PER_CALL,
+            ),
             deal_updates_interval: policy_constants::DEAL_UPDATES_INTERVAL,
             prov_collateral_percent_supply_num:
                 policy_constants::PROV_COLLATERAL_PERCENT_SUPPLY_NUM,
@@ -251,6 +293,10 @@ impl Default for Policy {
                 policy_constants::MARKET_DEFAULT_ALLOCATION_TERM_BUFFER,
 
             minimum_consensus_power: StoragePower::from(policy_constants::MINIMUM_CONSENSUS_POWER),
+            max_cron_events_per_tick: policy_constants::MAX_CRON_EVENTS_PER_TICK,
+
+            evm_max_subcalls: policy_constants::EVM_MAX_SUBCALLS,
+            evm_max_call_depth: policy_constants::EVM_MAX_CALL_DEPTH,
         }
     }
 }
@@ -307,6 +353,10 @@ pub mod policy_constants {
 
     pub const MAX_MULTIADDR_DATA: usize = 1024;
 
+    // The maximum number of multiaddresses a miner may advertise, as bounded when decoding
+    // method parameters. Their total size is separately limited to MAX_MULTIADDR_DATA bytes.
+    pub const MAX_MULTIADDRS: usize = 64;
+
     // With 48 deadlines (half-hour), 300 partitions per declaration permits addressing a full EiB
     // of partitions of 32GiB sectors with 1 message per epoch within a single half-hour deadline.
     // A miner can of course submit more messages.
@@ -360,6 +410,11 @@ pub mod policy_constants {
 
     pub const NEW_SECTORS_PER_PERIOD_MAX: usize = 128 << 10;
 
+    /// Proof refresh is disabled until a proof expiration policy is adopted.
+    pub const PROOF_REFRESH_ENABLED: bool = false;
+
+    pub const PROOF_REFRESH_PERIOD: ChainEpoch = 5 * 365 * EPOCHS_IN_DAY;
+
     /// This is a conservative value that is chosen via simulations of all known attacks.
     pub const CHAIN_FINALITY: ChainEpoch = 900;
 
@@ -388,6 +443,19 @@ pub mod policy_constants {
     pub const MAXIMUM_VERIFIED_ALLOCATION_TERM: i64 = 5 * EPOCHS_IN_YEAR;
     pub const MAXIMUM_VERIFIED_ALLOCATION_EXPIRATION: i64 = 60 * EPOCHS_IN_DAY;
     pub const END_OF_LIFE_CLAIM_DROP_PERIOD: ChainEpoch = 30 * EPOCHS_IN_DAY;
+    pub const GOVERNANCE_ALLOCATIONS_ENABLED: bool = false;
+    // The maximum number of claims addressed by a single request to the verified registry.
+    // A 64GiB sector holds at most 65536 claims of the minimum verified allocation size.
+    pub const MAX_CLAIMS_PER_REQUEST: usize = 1 << 17;
+
+    //
+    // --- datacap policy ---
+    //
+
+    // 64 EiB, far above any plausible total of verified data.
+    pub const DATACAP_SUPPLY_CAP: u128 = 64 << 60;
+    // 1 EiB, far above any single allocation to a verified client.
+    pub const DATACAP_MAX_MINT_PER_CALL: u128 = 1 << 60;
 
     //
     // --- market policy ---
@@ -422,6 +490,22 @@ pub mod policy_constants {
     pub const MINIMUM_CONSENSUS_POWER: i64 = 10 << 40;
 
     pub const CREATE_MINER_DEPOSIT_POWER: i64 = MINIMUM_CONSENSUS_POWER / 10;
+
+    // Bounds the work payment channels may add to a tick, while being well above the rate
+    // at which they are expected to settle.
+    pub const MAX_CRON_EVENTS_PER_TICK: u64 = 1000;
+
+    //
+    // --- evm policy ---
+    //
+
+    // Well above what a legitimate contract invocation makes within the block gas limit,
+    // so that only runaway call loops hit it.
+    pub const EVM_MAX_SUBCALLS: u64 = 10_000;
+
+    // Well below the FVM's call stack limit, so that a re-entrant contract is stopped with a
+    // dedicated exit code before the whole call stack is exhausted.
+    pub const EVM_MAX_CALL_DEPTH: u64 = 256;
 }
 
 /// A set indicating which proofs are considered valid, optimised for lookup of a small number of
@@ -22,6 +22,18 @@ pub struct BSStats {
     pub bw: usize,
 }
 
+impl BSStats {
+    /// Returns the accesses recorded since an earlier snapshot of the same stats.
+    pub fn since(&self, earlier: &BSStats) -> BSStats {
+        BSStats {
+            r: self.r - earlier.r,
+            w: self.w - earlier.w,
+            br: self.br - earlier.br,
+            bw: self.bw - earlier.bw,
+        }
+    }
+}
+
 /// Wrapper around `Blockstore` to tracking reads and writes for verification.
 /// This struct should only be used for testing.
 #[derive(Debug, Default)]
@@ -34,6 +46,16 @@ impl MemoryBlockstore {
     pub fn new() -> Self {
         Self { blocks: Default::default(), stats: Default::default() }
     }
+
+    /// Returns a snapshot of the stats recorded so far.
+    pub fn snapshot_stats(&self) -> BSStats {
+        *self.stats.borrow()
+    }
+
+    /// Resets the stats to zero, returning those recorded until now.
+    pub fn reset_stats(&self) -> BSStats {
+        self.stats.take()
+    }
 }
 
 impl Blockstore for MemoryBlockstore {
@@ -107,4 +129,24 @@ mod tests {
         );
         assert_eq!(*tr_store.stats.borrow(), BSStats { r: 4, br: total_len, w: 3, bw: total_len });
     }
+
+    #[test]
+    fn snapshot_and_reset_stats() {
+        let store = MemoryBlockstore::new();
+        let block = Block::new(0x55, &b"foobar"[..]);
+        let cid = store.put(Code::Sha2_256, &block).unwrap();
+
+        let before = store.snapshot_stats();
+        store.get(&cid).unwrap();
+        store.has(&cid).unwrap();
+        assert_eq!(
+            store.snapshot_stats().since(&before),
+            BSStats { r: 2, br: block.len(), ..Default::default() }
+        );
+
+        assert_eq!(store.reset_stats(), BSStats { r: 2, br: block.len(), w: 1, bw: block.len() });
+        assert_eq!(store.snapshot_stats(), BSStats::default());
+        // Resetting the stats leaves the blocks in place.
+        assert_eq!(store.get(&cid).unwrap().as_deref(), Some(block.data));
+    }
 }
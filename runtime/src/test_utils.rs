@@ -32,19 +32,27 @@ use fvm_shared::version::NetworkVersion;
 use fvm_shared::{ActorID, MethodNum, Response};
 use multihash_codetable::Code;
 use multihash_derive::MultihashDigest;
+use num_traits::Zero;
 
 use crate::runtime::builtins::Type;
 use crate::runtime::{
     ActorCode, DomainSeparationTag, EMPTY_ARR_CID, MessageInfo, Policy, Primitives, Runtime,
     RuntimePolicy,
 };
-use crate::{ActorError, SendError, actor_error};
+use crate::{ActorError, DATACAP_TOKEN_ACTOR_ADDR, SendError, actor_error, check_emit_allowed};
 use rand::prelude::*;
 use serde::Serialize;
 use vm_api::MockPrimitives;
 
 use crate::test_blockstores::MemoryBlockstore;
+use frc46_token::receiver::{FRC46_TOKEN_TYPE, FRC46TokenReceived};
+use frc46_token::token::types::{
+    BurnParams, BurnReturn, TransferFromParams, TransferFromReturn, TransferParams,
+};
+use fvm_actor_utils::receiver::UniversalReceiverParams;
+use fvm_ipld_encoding::RawBytes;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::tuple::*;
 use fvm_shared::chainid::ChainID;
 use fvm_shared::event::ActorEvent;
 use fvm_shared::sys::SendFlags;
@@ -146,6 +154,7 @@ pub struct MockRuntime {
     pub caller: RefCell<Address>,
     pub caller_type: RefCell<Cid>,
     pub origin: RefCell<Address>,
+    pub nonce: RefCell<u64>,
     pub value_received: RefCell<TokenAmount>,
     #[allow(clippy::type_complexity)]
     pub hash_func: Box<dyn Fn(SupportedHashes, &[u8]) -> ([u8; 64], usize)>,
@@ -166,6 +175,7 @@ pub struct MockRuntime {
     pub in_call: RefCell<bool>,
     pub store: Rc<MemoryBlockstore>,
     pub in_transaction: RefCell<bool>,
+    pub read_only: RefCell<bool>,
 
     // Expectations
     pub expectations: RefCell<Expectations>,
@@ -180,6 +190,9 @@ pub struct MockRuntime {
     pub actor_balances: HashMap<ActorID, TokenAmount>,
     pub tipset_timestamp: u64,
     pub tipset_cids: Vec<Cid>,
+
+    // Side effects of the current, or most recent, call.
+    pub call_record: RefCell<CallRecord>,
 }
 
 #[derive(Default)]
@@ -342,6 +355,7 @@ impl MockRuntime {
             caller: RefCell::new(Address::new_id(0)),
             caller_type: Default::default(),
             origin: RefCell::new(Address::new_id(0)),
+            nonce: RefCell::new(0),
             value_received: Default::default(),
             hash_func: Box::new(hash),
             recover_secp_pubkey_fn: Box::new(recover_secp_public_key),
@@ -351,6 +365,7 @@ impl MockRuntime {
             in_call: Default::default(),
             store: Rc::new(Default::default()),
             in_transaction: Default::default(),
+            read_only: Default::default(),
             expectations: Default::default(),
             policy: Default::default(),
             circulating_supply: Default::default(),
@@ -359,10 +374,36 @@ impl MockRuntime {
             actor_balances: Default::default(),
             tipset_timestamp: Default::default(),
             tipset_cids: Default::default(),
+            call_record: Default::default(),
         }
     }
 }
 
+/// A message sent by the actor during a call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SentMessage {
+    pub to: Address,
+    pub method: MethodNum,
+    pub params: Option<IpldBlock>,
+    pub value: TokenAmount,
+}
+
+/// The side effects of a single call to the actor, as returned by [`MockRuntime::call_recorded`].
+/// Events emitted by a call that aborts are discarded, as they would be by the VM.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CallRecord {
+    pub events: Vec<ActorEvent>,
+    pub sends: Vec<SentMessage>,
+    pub state_before: Option<Cid>,
+    pub state_after: Option<Cid>,
+}
+
+impl CallRecord {
+    pub fn state_changed(&self) -> bool {
+        self.state_before != self.state_after
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct ExpectCreateActor {
     pub code_id: Cid,
@@ -528,6 +569,10 @@ impl MockRuntime {
         self.origin.replace(address);
     }
 
+    pub fn set_nonce(&self, nonce: u64) {
+        self.nonce.replace(nonce);
+    }
+
     pub fn set_address_actor_type(&self, address: Address, actor_type: Cid) {
         self.actor_code_cids.borrow_mut().insert(address, actor_type);
     }
@@ -561,15 +606,30 @@ impl MockRuntime {
     ) -> Result<Option<IpldBlock>, ActorError> {
         self.in_call.replace(true);
         let prev_state = *self.state.borrow();
+        self.call_record.replace(CallRecord { state_before: prev_state, ..Default::default() });
         let res = A::invoke_method(self, method_num, params);
 
         if res.is_err() {
             self.state.replace(prev_state);
+            self.call_record.borrow_mut().events.clear();
         }
+        self.call_record.borrow_mut().state_after = *self.state.borrow();
         self.in_call.replace(false);
         res
     }
 
+    /// Calls the actor like [`MockRuntime::call`], additionally returning a record of the events
+    /// emitted, messages sent and state root change during just this call.
+    /// Expectations are checked as usual.
+    pub fn call_recorded<A: ActorCode>(
+        &self,
+        method_num: MethodNum,
+        params: Option<IpldBlock>,
+    ) -> (Result<Option<IpldBlock>, ActorError>, CallRecord) {
+        let res = self.call::<A>(method_num, params);
+        (res, self.call_record.take())
+    }
+
     /// Verifies that all mock expectations have been met (and resets the expectations).
     pub fn verify(&self) {
         self.expectations.borrow_mut().verify()
@@ -733,6 +793,10 @@ impl MockRuntime {
         self.network_version.replace(nv);
     }
 
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.replace(read_only);
+    }
+
     #[allow(dead_code)]
     pub fn set_epoch(&self, epoch: ChainEpoch) -> ChainEpoch {
         self.epoch.replace(epoch);
@@ -830,9 +894,132 @@ impl MockRuntime {
     }
 }
 
+///// FRC-46 token helpers /////
+
+/// Method number of the FRC-46 universal receiver hook.
+pub const FRC46_RECEIVER_HOOK_METHOD: MethodNum = frc42_dispatch::method_hash!("Receive");
+/// Method numbers of the datacap token actor's exported FRC-46 methods.
+pub const DATACAP_MINT_METHOD: MethodNum = frc42_dispatch::method_hash!("Mint");
+pub const DATACAP_BURN_METHOD: MethodNum = frc42_dispatch::method_hash!("Burn");
+pub const DATACAP_BALANCE_METHOD: MethodNum = frc42_dispatch::method_hash!("Balance");
+pub const DATACAP_TRANSFER_METHOD: MethodNum = frc42_dispatch::method_hash!("Transfer");
+pub const DATACAP_TRANSFER_FROM_METHOD: MethodNum = frc42_dispatch::method_hash!("TransferFrom");
+
+// Mirrors the datacap actor's mint parameters, which the runtime cannot depend on.
+#[derive(Serialize_tuple)]
+struct DatacapMintParams {
+    to: Address,
+    amount: TokenAmount,
+    operators: Vec<Address>,
+}
+
+/// Builds the payload a token actor delivers to a recipient's receiver hook.
+pub fn make_frc46_token_received(
+    from: ActorID,
+    to: ActorID,
+    operator: ActorID,
+    amount: TokenAmount,
+    operator_data: RawBytes,
+) -> FRC46TokenReceived {
+    FRC46TokenReceived {
+        from,
+        to,
+        operator,
+        amount,
+        operator_data,
+        token_data: RawBytes::default(),
+    }
+}
+
+/// Wraps an FRC-46 payload in universal receiver hook parameters.
+pub fn make_frc46_receiver_hook_params(payload: &FRC46TokenReceived) -> UniversalReceiverParams {
+    UniversalReceiverParams {
+        type_: FRC46_TOKEN_TYPE,
+        payload: RawBytes::serialize(payload).expect("failed to serialize token payload"),
+    }
+}
+
+impl MockRuntime {
+    /// Expects the recipient's receiver hook to be invoked with the given token payload.
+    pub fn expect_frc46_receiver_hook(&self, payload: &FRC46TokenReceived, exit_code: ExitCode) {
+        self.expect_send_simple(
+            Address::new_id(payload.to),
+            FRC46_RECEIVER_HOOK_METHOD,
+            IpldBlock::serialize_cbor(&make_frc46_receiver_hook_params(payload)).unwrap(),
+            TokenAmount::zero(),
+            None,
+            exit_code,
+        );
+    }
+
+    /// Expects datacap to be minted to `to`, granting unlimited allowance to `operators`.
+    pub fn expect_datacap_mint(&self, to: Address, amount: TokenAmount, operators: Vec<Address>) {
+        self.expect_send_simple(
+            DATACAP_TOKEN_ACTOR_ADDR,
+            DATACAP_MINT_METHOD,
+            IpldBlock::serialize_cbor(&DatacapMintParams { to, amount, operators }).unwrap(),
+            TokenAmount::zero(),
+            None,
+            ExitCode::OK,
+        );
+    }
+
+    /// Expects the caller's own datacap to be burnt, leaving `balance` remaining.
+    pub fn expect_datacap_burn(&self, amount: TokenAmount, balance: TokenAmount) {
+        self.expect_send_simple(
+            DATACAP_TOKEN_ACTOR_ADDR,
+            DATACAP_BURN_METHOD,
+            IpldBlock::serialize_cbor(&BurnParams { amount }).unwrap(),
+            TokenAmount::zero(),
+            IpldBlock::serialize_cbor(&BurnReturn { balance }).unwrap(),
+            ExitCode::OK,
+        );
+    }
+
+    /// Expects a query of the datacap balance of `owner`.
+    pub fn expect_datacap_balance(&self, owner: Address, balance: TokenAmount) {
+        self.expect_send_simple(
+            DATACAP_TOKEN_ACTOR_ADDR,
+            DATACAP_BALANCE_METHOD,
+            IpldBlock::serialize_cbor(&owner).unwrap(),
+            TokenAmount::zero(),
+            IpldBlock::serialize_cbor(&balance).unwrap(),
+            ExitCode::OK,
+        );
+    }
+
+    /// Expects a transfer of the caller's own datacap.
+    pub fn expect_datacap_transfer(&self, params: TransferParams) {
+        self.expect_send_simple(
+            DATACAP_TOKEN_ACTOR_ADDR,
+            DATACAP_TRANSFER_METHOD,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+            TokenAmount::zero(),
+            None,
+            ExitCode::OK,
+        );
+    }
+
+    /// Expects a transfer of datacap by the caller acting as an operator for `params.from`.
+    pub fn expect_datacap_transfer_from(
+        &self,
+        params: TransferFromParams,
+        ret: TransferFromReturn,
+    ) {
+        self.expect_send_simple(
+            DATACAP_TOKEN_ACTOR_ADDR,
+            DATACAP_TRANSFER_FROM_METHOD,
+            IpldBlock::serialize_cbor(&params).unwrap(),
+            TokenAmount::zero(),
+            IpldBlock::serialize_cbor(&ret).unwrap(),
+            ExitCode::OK,
+        );
+    }
+}
+
 impl MessageInfo for MockRuntime {
     fn nonce(&self) -> u64 {
-        0
+        *self.nonce.borrow()
     }
 
     fn caller(&self) -> Address {
@@ -1210,6 +1397,13 @@ impl Runtime for MockRuntime {
             to, method, expected_msg.send_flags, send_flags
         );
 
+        self.call_record.borrow_mut().sends.push(SentMessage {
+            to: *to,
+            method,
+            params,
+            value: value.clone(),
+        });
+
         if let Some(e) = expected_msg.send_error {
             return Err(SendError(e));
         }
@@ -1325,6 +1519,7 @@ impl Runtime for MockRuntime {
     }
 
     fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError> {
+        check_emit_allowed(self)?;
         let expected = self
             .expectations
             .borrow_mut()
@@ -1333,6 +1528,7 @@ impl Runtime for MockRuntime {
             .expect("unexpected call to emit_event");
 
         assert_eq!(*event, expected);
+        self.call_record.borrow_mut().events.push(event.clone());
 
         Ok(())
     }
@@ -1342,7 +1538,7 @@ impl Runtime for MockRuntime {
     }
 
     fn read_only(&self) -> bool {
-        false
+        *self.read_only.borrow()
     }
 }
 
@@ -0,0 +1,124 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, ser};
+
+/// A vector of at most `MAX` elements, for use in method parameters decoded from untrusted input.
+///
+/// Decoding fails as soon as the encoded sequence declares, or turns out to hold, more than `MAX`
+/// elements, so an over-long input is rejected before it is allocated. Encoding an over-long
+/// vector fails too. The bound is otherwise not enforced on a locally constructed vector.
+/// The encoding is the same as that of a plain vector.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BoundedVec<T, const MAX: usize>(Vec<T>);
+
+impl<T, const MAX: usize> BoundedVec<T, MAX> {
+    /// The maximum number of elements.
+    pub const MAX_LEN: usize = MAX;
+
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const MAX: usize> From<Vec<T>> for BoundedVec<T, MAX> {
+    fn from(items: Vec<T>) -> Self {
+        Self(items)
+    }
+}
+
+impl<T, const MAX: usize> From<BoundedVec<T, MAX>> for Vec<T> {
+    fn from(items: BoundedVec<T, MAX>) -> Self {
+        items.0
+    }
+}
+
+impl<T, const MAX: usize> FromIterator<T> for BoundedVec<T, MAX> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T, const MAX: usize> Deref for BoundedVec<T, MAX> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const MAX: usize> DerefMut for BoundedVec<T, MAX> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, const MAX: usize> IntoIterator for BoundedVec<T, MAX> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T, const MAX: usize> IntoIterator for &'a BoundedVec<T, MAX> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: Serialize, const MAX: usize> Serialize for BoundedVec<T, MAX> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.len() > MAX {
+            return Err(ser::Error::custom(format!(
+                "vector of {} elements exceeds maximum of {}",
+                self.0.len(),
+                MAX
+            )));
+        }
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const MAX: usize> Deserialize<'de> for BoundedVec<T, MAX> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BoundedVisitor<T, const MAX: usize>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>, const MAX: usize> Visitor<'de> for BoundedVisitor<T, MAX> {
+            type Value = BoundedVec<T, MAX>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a sequence of at most {} elements", MAX)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                // Reject a declared length up front, before decoding any element.
+                let declared = seq.size_hint().unwrap_or(0);
+                if declared > MAX {
+                    return Err(de::Error::invalid_length(declared, &self));
+                }
+                // The declared length isn't trusted beyond the bound, so preallocation is capped.
+                let mut items = Vec::with_capacity(declared.min(4096));
+                while let Some(item) = seq.next_element()? {
+                    if items.len() == MAX {
+                        return Err(de::Error::invalid_length(MAX + 1, &self));
+                    }
+                    items.push(item);
+                }
+                Ok(BoundedVec(items))
+            }
+        }
+
+        deserializer.deserialize_seq(BoundedVisitor(PhantomData))
+    }
+}
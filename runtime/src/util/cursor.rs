@@ -0,0 +1,44 @@
+use fvm_ipld_encoding::{from_slice, strict_bytes};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::cbor::serialize_vec;
+use crate::{ActorError, actor_error};
+
+/// An opaque position in the listing of a paginated exported method.
+///
+/// A list method takes a cursor and returns the next one alongside a page of results, for the
+/// caller to pass back unchanged to continue the listing. The empty cursor starts a listing from
+/// the beginning. Callers should make no assumptions about a cursor's content, which is the
+/// CBOR encoding of a position chosen by the listing actor.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Cursor(#[serde(with = "strict_bytes")] Vec<u8>);
+
+impl Cursor {
+    /// The cursor from which a listing starts.
+    pub fn start() -> Self {
+        Self::default()
+    }
+
+    /// Whether this cursor starts a listing from the beginning.
+    pub fn is_start(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Encodes a position in a listing as a cursor.
+    pub fn encode<T: Serialize>(position: &T) -> Result<Self, ActorError> {
+        Ok(Self(serialize_vec(position, "cursor")?))
+    }
+
+    /// Decodes the position in a listing from a cursor, or None for the start cursor.
+    /// Fails with an illegal argument error if the cursor doesn't encode such a position.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<Option<T>, ActorError> {
+        if self.is_start() {
+            return Ok(None);
+        }
+        from_slice(&self.0)
+            .map(Some)
+            .map_err(|e| actor_error!(illegal_argument, "malformed cursor: {}", e))
+    }
+}
@@ -0,0 +1,151 @@
+use fvm_shared::event::{ActorEvent, Flags};
+
+use crate::util::events::IPLD_RAW;
+use crate::{ActorError, EventBuilder, actor_error};
+
+/// The event key for the Ethereum log data.
+pub const EVM_LOG_DATA_KEY: &str = "d";
+
+/// The event keys for the Ethereum log topics, in order.
+pub const EVM_LOG_TOPIC_KEYS: &[&str] = &["t1", "t2", "t3", "t4"];
+
+/// An Ethereum log, as emitted by the EVM actor's LOG0-LOG4 instructions.
+///
+/// The log is carried in an actor event with one indexed, raw entry per topic (keys `t1` to `t4`)
+/// followed by an indexed, raw data entry (key `d`) that is omitted when the data is empty.
+/// The emitting contract is not part of the event; indexers take it from the event's emitter.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EthLog {
+    pub topics: Vec<[u8; 32]>,
+    pub data: Vec<u8>,
+}
+
+impl EthLog {
+    /// Encodes the log as an actor event.
+    pub fn to_event(&self) -> Result<ActorEvent, ActorError> {
+        if self.topics.len() > EVM_LOG_TOPIC_KEYS.len() {
+            return Err(actor_error!(
+                illegal_argument,
+                "log has {} topics, at most {} are allowed",
+                self.topics.len(),
+                EVM_LOG_TOPIC_KEYS.len()
+            ));
+        }
+        let mut builder = EventBuilder::new();
+        for (key, topic) in EVM_LOG_TOPIC_KEYS.iter().zip(&self.topics) {
+            builder = builder.field_raw_indexed(key, topic);
+        }
+        if !self.data.is_empty() {
+            builder = builder.field_raw_indexed(EVM_LOG_DATA_KEY, &self.data);
+        }
+        builder.build()
+    }
+
+    /// Decodes a log from an actor event emitted by the EVM actor.
+    /// Fails if the event's entries do not follow the log encoding exactly.
+    pub fn from_event(event: &ActorEvent) -> Result<Self, ActorError> {
+        let mut log = EthLog::default();
+        let mut entries = event.entries.iter().peekable();
+        while let Some(entry) = entries.next() {
+            if entry.codec != IPLD_RAW || entry.flags != Flags::FLAG_INDEXED_ALL {
+                return Err(actor_error!(
+                    illegal_argument,
+                    "log entry {} must be raw and fully indexed",
+                    entry.key
+                ));
+            }
+            if entry.key == EVM_LOG_DATA_KEY {
+                if entries.peek().is_some() {
+                    return Err(actor_error!(illegal_argument, "log data must be the last entry"));
+                }
+                if entry.value.is_empty() {
+                    return Err(actor_error!(illegal_argument, "empty log data must be omitted"));
+                }
+                log.data = entry.value.clone();
+                continue;
+            }
+            match EVM_LOG_TOPIC_KEYS.get(log.topics.len()) {
+                Some(expected) if *expected == entry.key => {}
+                _ => {
+                    return Err(actor_error!(
+                        illegal_argument,
+                        "unexpected log entry {} after {} topics",
+                        entry.key,
+                        log.topics.len()
+                    ));
+                }
+            }
+            let topic = entry.value.as_slice().try_into().map_err(|_| {
+                actor_error!(
+                    illegal_argument,
+                    "log topic {} must be 32 bytes, was {}",
+                    entry.key,
+                    entry.value.len()
+                )
+            })?;
+            log.topics.push(topic);
+        }
+        Ok(log)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fvm_shared::event::{ActorEvent, Entry, Flags};
+
+    use super::{EVM_LOG_DATA_KEY, EVM_LOG_TOPIC_KEYS, EthLog, IPLD_RAW};
+
+    fn raw_entry(key: &str, value: &[u8]) -> Entry {
+        Entry {
+            flags: Flags::FLAG_INDEXED_ALL,
+            key: key.to_string(),
+            codec: IPLD_RAW,
+            value: value.to_vec(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in 0..=EVM_LOG_TOPIC_KEYS.len() {
+            for data in [vec![], vec![0xca, 0xfe]] {
+                let log = EthLog { topics: (0..n as u8).map(|i| [i; 32]).collect(), data };
+                let event = log.to_event().unwrap();
+                let expected_entries = n + usize::from(!log.data.is_empty());
+                assert_eq!(expected_entries, event.entries.len());
+                assert_eq!(log, EthLog::from_event(&event).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn too_many_topics() {
+        let log = EthLog { topics: vec![[0; 32]; 5], data: vec![] };
+        assert!(log.to_event().is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_events() {
+        let topic = [1u8; 32];
+        for entries in [
+            // Topics out of order.
+            vec![raw_entry(EVM_LOG_TOPIC_KEYS[1], &topic)],
+            // Short topic.
+            vec![raw_entry(EVM_LOG_TOPIC_KEYS[0], &topic[..31])],
+            // Data before a topic.
+            vec![raw_entry(EVM_LOG_DATA_KEY, &[1]), raw_entry(EVM_LOG_TOPIC_KEYS[0], &topic)],
+            // Empty data.
+            vec![raw_entry(EVM_LOG_DATA_KEY, &[])],
+            // Unknown key.
+            vec![raw_entry("x", &topic)],
+            // Not raw.
+            vec![Entry { codec: 0x51, ..raw_entry(EVM_LOG_TOPIC_KEYS[0], &topic) }],
+            // Not indexed.
+            vec![Entry {
+                flags: Flags::FLAG_INDEXED_KEY,
+                ..raw_entry(EVM_LOG_TOPIC_KEYS[0], &topic)
+            }],
+        ] {
+            assert!(EthLog::from_event(&ActorEvent { entries }).is_err());
+        }
+    }
+}
@@ -1,10 +1,13 @@
-use crate::ActorError;
 use crate::cbor::serialize_vec;
+use crate::runtime::Runtime;
+use crate::{ActorError, actor_error};
 use fvm_shared::event::{ActorEvent, Entry, Flags};
 use serde::ser;
 
 // Codec identifier for CBOR-encoded data.
 const IPLD_CBOR: u64 = 0x51;
+// Codec identifier for raw, uninterpreted bytes.
+pub(crate) const IPLD_RAW: u64 = 0x55;
 
 const EVENT_TYPE_KEY: &str = "$type";
 
@@ -34,11 +37,29 @@ impl EventBuilder {
         self.push_entry(name, value, Flags::FLAG_INDEXED_ALL)
     }
 
+    /// Pushes an entry with an indexed key and indexed, raw (un-encoded) value.
+    pub fn field_raw_indexed(mut self, name: &str, value: &[u8]) -> Self {
+        if let Ok(ref mut entries) = self.entries {
+            entries.push(Entry {
+                flags: Flags::FLAG_INDEXED_ALL,
+                key: name.to_string(),
+                codec: IPLD_RAW,
+                value: value.to_vec(),
+            });
+        }
+        self
+    }
+
     /// Returns an actor event ready to emit (consuming self).
     pub fn build(self) -> Result<ActorEvent, ActorError> {
         Ok(ActorEvent { entries: self.entries? })
     }
 
+    /// Builds the event and emits it (consuming self).
+    pub fn emit(self, rt: &impl Runtime) -> Result<(), ActorError> {
+        rt.emit_event(&self.build()?)
+    }
+
     /// Pushes an entry with an IPLD-CBOR-serialized value.
     fn push_entry<T: ser::Serialize + ?Sized>(
         mut self,
@@ -66,11 +87,21 @@ impl Default for EventBuilder {
     }
 }
 
+/// Returns a read-only error if the call is read-only, in which the VM rejects events.
+pub fn check_emit_allowed(rt: &impl Runtime) -> Result<(), ActorError> {
+    if rt.read_only() {
+        return Err(actor_error!(read_only; "cannot emit events in a read-only call"));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use crate::EventBuilder;
     use crate::cbor::serialize_vec;
-    use crate::util::events::{EVENT_TYPE_KEY, IPLD_CBOR};
+    use crate::test_utils::MockRuntime;
+    use crate::util::events::{EVENT_TYPE_KEY, IPLD_CBOR, IPLD_RAW};
+    use fvm_shared::error::ExitCode;
     use fvm_shared::event::{ActorEvent, Entry, Flags};
 
     #[test]
@@ -124,4 +155,36 @@ mod test {
             e
         );
     }
+
+    #[test]
+    fn raw_values() {
+        let e = EventBuilder::new().field_raw_indexed("r", &[0xca, 0xfe]).build().unwrap();
+        assert_eq!(
+            ActorEvent {
+                entries: vec![Entry {
+                    flags: Flags::FLAG_INDEXED_ALL,
+                    key: "r".to_string(),
+                    codec: IPLD_RAW,
+                    value: vec![0xca, 0xfe],
+                }]
+            },
+            e
+        );
+    }
+
+    #[test]
+    fn emit_rejected_when_read_only() {
+        let rt = MockRuntime::default();
+        let event = || EventBuilder::new().typ("l1").field_indexed("v1", "abc");
+
+        rt.set_read_only(true);
+        let err = event().emit(&rt).unwrap_err();
+        assert_eq!(ExitCode::USR_READ_ONLY, err.exit_code());
+        assert!(rt.call_record.borrow().events.is_empty());
+
+        rt.set_read_only(false);
+        rt.expect_emitted_event(event().build().unwrap());
+        event().emit(&rt).unwrap();
+        rt.verify();
+    }
 }
@@ -3,6 +3,7 @@ use crate::{ActorError, AsActorError, Hasher};
 use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_hamt as hamt;
+use fvm_ipld_hamt::HashAlgorithm;
 use fvm_shared::address::Address;
 use fvm_shared::error::ExitCode;
 use integer_encoding::VarInt;
@@ -165,6 +166,70 @@ where
         }
         Ok(())
     }
+
+    /// Iterates over at most `limit` key-value pairs in the map, starting from a key
+    /// (inclusive), or from the beginning if none is given.
+    /// Iteration follows the HAMT's internal order, not that of the keys.
+    /// If the start key is no longer in the map, e.g. because it was deleted after being
+    /// returned as a resume key, iteration starts from the key that follows it in order of the
+    /// keys' hashes. No remaining key is skipped, though a few may be visited again.
+    /// Returns the key from which to resume iteration, if any pairs remain.
+    pub fn for_each_from<F>(
+        &self,
+        start: Option<&K>,
+        limit: usize,
+        mut f: F,
+    ) -> Result<Option<K>, ActorError>
+    where
+        F: FnMut(K, &V) -> Result<(), ActorError>,
+    {
+        let (iter, resume_after) = match start {
+            Some(key) => {
+                let k =
+                    key.to_bytes().context_code(ExitCode::USR_ASSERTION_FAILED, "invalid key")?;
+                if self.contains_key(key)? {
+                    let iter = self
+                        .hamt
+                        .iter_from(&k)
+                        .with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                            format!("failed to iterate HAMT '{}' from key {key:?}", self.name)
+                        })?;
+                    (iter, None)
+                } else {
+                    let k = hamt::BytesKey(k);
+                    (self.hamt.iter(), Some((Hasher::hash(&k), k)))
+                }
+            }
+            None => (self.hamt.iter(), None),
+        };
+        // Skips the keys preceding a start key that is no longer in the map.
+        let mut iter = iter.filter(|kv| match (kv, &resume_after) {
+            (Ok((k, _)), Some((start_hash, start))) => {
+                (Hasher::hash(*k), &k.0[..]) >= (*start_hash, &start.0[..])
+            }
+            _ => true,
+        });
+        let decode_key = |k: &[u8]| {
+            K::from_bytes(k).with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("invalid key in HAMT {}", self.name)
+            })
+        };
+        for kv in iter.by_ref().take(limit) {
+            let (k, v) = kv.with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                format!("error traversing HAMT {}", self.name)
+            })?;
+            f(decode_key(k)?, v)?;
+        }
+        match iter.next() {
+            Some(kv) => {
+                let (k, _) = kv.with_context_code(ExitCode::USR_ILLEGAL_STATE, || {
+                    format!("error traversing HAMT {}", self.name)
+                })?;
+                Ok(Some(decode_key(k)?))
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 impl MapKey for Vec<u8> {
@@ -254,4 +319,37 @@ mod tests {
         assert!(res.is_err());
         assert_eq!(res.unwrap_err(), ActorError::forbidden("test".to_string()));
     }
+
+    #[test]
+    fn for_each_from_resumes_after_deleted_key() {
+        let bs = MemoryBlockstore::new();
+        let mut m = Map2::<_, u64, ()>::empty(bs, DEFAULT_HAMT_CONFIG, "empty");
+        for k in 0..100 {
+            m.set(&k, ()).unwrap();
+        }
+
+        // Delete each resume key before resuming from it.
+        let mut listed = std::collections::BTreeSet::new();
+        let mut deleted = std::collections::BTreeSet::new();
+        let mut start = None;
+        loop {
+            start = m
+                .for_each_from(start.as_ref(), 7, |k, _| {
+                    listed.insert(k);
+                    Ok(())
+                })
+                .unwrap();
+            match start {
+                Some(k) => {
+                    m.delete(&k).unwrap();
+                    deleted.insert(k);
+                }
+                None => break,
+            }
+        }
+        assert!(!deleted.is_empty());
+        let expected: std::collections::BTreeSet<u64> =
+            (0..100).filter(|k| !deleted.contains(k)).collect();
+        assert_eq!(expected, listed);
+    }
 }
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0, MIT
 
 pub use self::batch_return::*;
+pub use self::bounded_vec::BoundedVec;
+pub use self::cursor::Cursor;
 pub use self::downcast::*;
+pub use self::eth_log::*;
 pub use self::events::*;
 pub use self::map::*;
 pub use self::mapmap::MapMap;
@@ -13,8 +16,11 @@ pub use self::set_multimap::SetMultimap;
 pub use self::set_multimap::SetMultimapConfig;
 
 mod batch_return;
+mod bounded_vec;
 pub mod cbor;
+mod cursor;
 mod downcast;
+mod eth_log;
 mod events;
 mod map;
 mod mapmap;
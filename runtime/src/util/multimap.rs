@@ -96,6 +96,25 @@ where
         Ok(())
     }
 
+    /// Iterates through all values at each of a sequence of keys, in the order of the keys.
+    /// Each key is encoded with `encode` to look up its values; keys without values are skipped.
+    pub fn for_each_in<K, F, V>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        encode: impl Fn(&K) -> BytesKey,
+        mut f: F,
+    ) -> Result<(), Error>
+    where
+        V: Serialize + DeserializeOwned,
+        F: FnMut(&K, u64, &V) -> anyhow::Result<()>,
+    {
+        for key in keys {
+            self.for_each(&encode(&key), |i, v| f(&key, i, v))?;
+        }
+
+        Ok(())
+    }
+
     /// Iterates through all arrays in the multimap
     pub fn for_all<F, V>(&self, mut f: F) -> Result<(), Error>
     where
@@ -64,6 +64,21 @@ where
         self.0.for_each(|s, _| f(s))
     }
 
+    /// Iterates over at most `limit` keys in the set, in the order of the underlying HAMT,
+    /// starting from a key (inclusive), or from the beginning if none is given.
+    /// Returns the key from which to resume iteration, if any keys remain.
+    pub fn for_each_from<F>(
+        &self,
+        start: Option<&K>,
+        limit: usize,
+        mut f: F,
+    ) -> Result<Option<K>, ActorError>
+    where
+        F: FnMut(K) -> Result<(), ActorError>,
+    {
+        self.0.for_each_from(start, limit, |k, _| f(k))
+    }
+
     /// Collects all keys from the set into a vector.
     pub fn collect_keys(&self) -> Result<Vec<K>, ActorError> {
         let mut ret_keys = Vec::new();
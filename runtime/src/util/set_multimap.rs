@@ -145,4 +145,23 @@ where
 
         set.for_each(f)
     }
+
+    /// Iterates over at most `limit` values for a key, in the order of the value set's HAMT,
+    /// starting from a value (inclusive), or from the beginning if none is given.
+    /// Returns the value from which to resume iteration, if any values remain.
+    pub fn for_each_in_from<F>(
+        &self,
+        key: &K,
+        start: Option<&V>,
+        limit: usize,
+        f: F,
+    ) -> Result<Option<V>, ActorError>
+    where
+        F: FnMut(V) -> Result<(), ActorError>,
+    {
+        match self.get(key)? {
+            Some(set) => set.for_each_from(start, limit, f),
+            None => Ok(None),
+        }
+    }
 }
@@ -0,0 +1,42 @@
+use fil_actors_runtime::BoundedVec;
+use fvm_ipld_encoding::RawBytes;
+
+type Bounded = BoundedVec<u64, 3>;
+
+#[test]
+fn round_trip_within_bound() {
+    for items in [vec![], vec![1], vec![1, 2, 3]] {
+        let encoded = RawBytes::serialize(&items).unwrap();
+        let decoded: Bounded = encoded.deserialize().unwrap();
+        assert_eq!(items, decoded.to_vec());
+        // The encoding is the same as that of a plain vector.
+        assert_eq!(encoded, RawBytes::serialize(&decoded).unwrap());
+    }
+}
+
+#[test]
+fn rejects_over_long_input() {
+    let encoded = RawBytes::serialize(vec![1u64, 2, 3, 4]).unwrap();
+    assert!(encoded.deserialize::<Bounded>().is_err());
+}
+
+#[test]
+fn rejects_declared_length_before_decoding_elements() {
+    // An array header declaring 2^32 elements, with no elements following.
+    let encoded = RawBytes::new(vec![0x9a, 0xff, 0xff, 0xff, 0xff]);
+    let err = encoded.deserialize::<Bounded>().unwrap_err();
+    assert!(err.to_string().contains("at most 3 elements"), "{}", err);
+}
+
+#[test]
+fn rejects_encoding_over_long_vector() {
+    let items: Bounded = vec![1, 2, 3, 4].into();
+    assert!(RawBytes::serialize(&items).is_err());
+}
+
+#[test]
+fn does_not_trust_declared_length_within_bound() {
+    // An array header declaring 2^20 elements within a larger bound, with one element following.
+    let encoded = RawBytes::new(vec![0x9a, 0x00, 0x10, 0x00, 0x00, 0x01]);
+    assert!(encoded.deserialize::<BoundedVec<u64, { 1 << 24 }>>().is_err());
+}
@@ -0,0 +1,23 @@
+use fil_actors_runtime::Cursor;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::error::ExitCode;
+
+#[test]
+fn round_trip() {
+    assert!(Cursor::start().is_start());
+    assert_eq!(None, Cursor::start().decode::<u64>().unwrap());
+
+    let cursor = Cursor::encode(&(7u64, "key".to_string())).unwrap();
+    assert!(!cursor.is_start());
+    assert_eq!(Some((7u64, "key".to_string())), cursor.decode().unwrap());
+
+    // A cursor is encoded as a byte string holding the position.
+    let encoded = RawBytes::serialize(&cursor).unwrap();
+    assert_eq!(cursor, encoded.deserialize::<Cursor>().unwrap());
+}
+
+#[test]
+fn rejects_malformed_cursor() {
+    let cursor = Cursor::encode(&"not a number").unwrap();
+    assert_eq!(ExitCode::USR_ILLEGAL_ARGUMENT, cursor.decode::<u64>().unwrap_err().exit_code());
+}
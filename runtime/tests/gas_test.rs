@@ -0,0 +1,12 @@
+use fil_actors_runtime::runtime::Runtime;
+use fil_actors_runtime::test_utils::MockRuntime;
+use fvm_shared::econ::TokenAmount;
+
+#[test]
+fn gas_price_math() {
+    let mut rt = MockRuntime::new();
+    rt.gas_premium = TokenAmount::from_atto(5);
+    rt.set_base_fee(TokenAmount::from_atto(100));
+
+    assert_eq!(TokenAmount::from_atto(105), rt.effective_gas_price());
+}
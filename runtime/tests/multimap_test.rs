@@ -46,6 +46,31 @@ fn for_each() {
     assert_eq!(&vals, &[(0, 8), (1, 2), (2, 3)])
 }
 
+#[test]
+fn for_each_in() {
+    let store = MemoryBlockstore::new();
+    let mut mm = Multimap::new(&store, HAMT_BIT_WIDTH, 3);
+
+    mm.add(u64_key(1), 10).unwrap();
+    mm.add(u64_key(3), 30).unwrap();
+    mm.add(u64_key(3), 31).unwrap();
+    mm.add(u64_key(5), 50).unwrap();
+
+    // Values are visited in the order of the keys, skipping keys without values.
+    let mut vals: Vec<(u64, u64, u64)> = Vec::new();
+    mm.for_each_in(
+        [3, 2, 1],
+        |k| u64_key(*k),
+        |k, i, v| {
+            vals.push((*k, i, *v));
+            Ok(())
+        },
+    )
+    .unwrap();
+
+    assert_eq!(&vals, &[(3, 0, 30), (3, 1, 31), (1, 0, 10)])
+}
+
 #[test]
 fn remove_all() {
     let store = MemoryBlockstore::new();
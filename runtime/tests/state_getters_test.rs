@@ -0,0 +1,45 @@
+use fil_actors_runtime::state_getters;
+use fil_actors_runtime::test_utils::MockRuntime;
+use fvm_ipld_encoding::tuple::*;
+
+#[derive(Serialize_tuple, Deserialize_tuple)]
+struct State {
+    count: u64,
+    total: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct CountReturn {
+    value: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct TotalsReturn {
+    count: u64,
+    total: u64,
+}
+
+struct Actor;
+
+impl Actor {
+    state_getters! {
+        State;
+        fn count() -> CountReturn { value: count }
+        fn totals() -> TotalsReturn { count, total }
+        fn doubled_total() -> u64 = |st| st.total * 2;
+    }
+}
+
+#[test]
+fn getters_read_state() {
+    let rt = MockRuntime::new();
+    rt.replace_state(&State { count: 3, total: 5 });
+
+    rt.expect_validate_caller_any();
+    assert_eq!(CountReturn { value: 3 }, Actor::count(&rt).unwrap());
+    rt.expect_validate_caller_any();
+    assert_eq!(TotalsReturn { count: 3, total: 5 }, Actor::totals(&rt).unwrap());
+    rt.expect_validate_caller_any();
+    assert_eq!(10, Actor::doubled_total(&rt).unwrap());
+    rt.verify();
+}
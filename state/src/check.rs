@@ -29,7 +29,7 @@ use fvm_ipld_encoding::CborStore;
 use fvm_ipld_encoding::from_slice;
 use fvm_shared::address::Address;
 use fvm_shared::address::Protocol;
-use fvm_shared::clock::ChainEpoch;
+use fvm_shared::clock::{ChainEpoch, EPOCH_UNDEFINED};
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::sector::SectorNumber;
 use num_traits::Zero;
@@ -146,7 +146,7 @@ pub fn check_state_invariants<BS: Blockstore>(
             Some(Type::Reward) => {
                 let state = get_state!(store, actor, RewardState);
                 let (summary, msgs) =
-                    reward::check_state_invariants(&state, prior_epoch, &actor.balance);
+                    reward::check_state_invariants(&state, store, prior_epoch, &actor.balance);
                 acc.with_prefix("reward: ").add_all(&msgs);
                 reward_summary = Some(summary);
             }
@@ -180,7 +180,7 @@ pub fn check_state_invariants<BS: Blockstore>(
     }
 
     if let Some(market_summary) = market_summary.clone() {
-        check_deal_states_against_sectors(&acc, &miner_summaries, &market_summary);
+        check_deal_states_against_sectors(&acc, &miner_summaries, &market_summary, prior_epoch);
     }
 
     if let Some(verifreg_summary) = verifreg_summary {
@@ -274,8 +274,12 @@ fn check_deal_states_against_sectors(
     acc: &MessageAccumulator,
     miner_summaries: &HashMap<Address, miner::StateSummary>,
     market_summary: &market::StateSummary,
+    prior_epoch: ChainEpoch,
 ) {
-    // Check that all active deals are included within a non-terminated sector.
+    // Check that all active deals are included within a non-terminated sector carrying data,
+    // which does not expire before the deal ends.
+    // Miner state does not record piece CIDs, so a deal's piece is matched to the sector only
+    // by the sector's data weight.
     // We cannot check that all deals referenced within a sector are in the market, because deals
     // can be terminated independently of the sector in which they are included.
     for (deal_id, deal) in &market_summary.deals {
@@ -284,7 +288,7 @@ fn check_deal_states_against_sectors(
             continue;
         }
 
-        let _miner_summary = if let Some(miner_summary) = miner_summaries.get(&deal.provider) {
+        let miner_summary = if let Some(miner_summary) = miner_summaries.get(&deal.provider) {
             miner_summary
         } else {
             acc.add(format!(
@@ -293,6 +297,57 @@ fn check_deal_states_against_sectors(
             ));
             continue;
         };
+
+        if deal.slash_epoch != EPOCH_UNDEFINED || deal.end_epoch <= prior_epoch {
+            // Terminated and expired deals may outlive their sector until they are cleaned up.
+            continue;
+        }
+
+        match miner_summary.live_data_sectors.get(&deal.sector_number) {
+            Some(sector) => acc.require(
+                sector.sector_expiration >= deal.end_epoch,
+                format!(
+                    "deal {} end {} is after sector {} expiration {} for miner {}",
+                    deal_id,
+                    deal.end_epoch,
+                    deal.sector_number,
+                    sector.sector_expiration,
+                    deal.provider
+                ),
+            ),
+            None => acc.add(format!(
+                "deal {} sector {} not found among data sectors of miner {}",
+                deal_id, deal.sector_number, deal.provider
+            )),
+        }
+    }
+
+    // Check that every sector for which the market recorded pieces, through activation or
+    // SectorContentChanged notification, exists in the miner as a sector carrying data.
+    for (provider, sectors) in &market_summary.provider_sector_deals {
+        let maddr = Address::new_id(*provider);
+        let miner_summary = if let Some(miner_summary) = miner_summaries.get(&maddr) {
+            miner_summary
+        } else {
+            acc.add(format!("provider {} with sector deals not found among miners", maddr));
+            continue;
+        };
+
+        for (sector_number, deal_ids) in sectors {
+            // Expired deals remain in the mapping until settled, and may outlive their sector.
+            let has_live_deal = deal_ids.iter().any(|deal_id| {
+                market_summary.deals.get(deal_id).is_some_and(|deal| deal.end_epoch > prior_epoch)
+            });
+            if has_live_deal {
+                acc.require(
+                    miner_summary.live_data_sectors.contains_key(sector_number),
+                    format!(
+                        "sector {} with deals {:?} not found among data sectors of miner {}",
+                        sector_number, deal_ids, maddr
+                    ),
+                );
+            }
+        }
     }
 }
 
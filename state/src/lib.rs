@@ -1 +1,3 @@
 pub mod check;
+pub mod migration;
+pub mod size;
@@ -0,0 +1,335 @@
+//! Migration of actor state from the layouts prior to the fields added since the last network
+//! upgrade.
+//!
+//! Each added field follows the fields of its actor's prior state, so a prior state object
+//! decodes as a prefix of the current one. The migration appends each added field's initial
+//! value, then rebuilds those fields which index or summarize the existing state.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, anyhow, bail};
+use cid::Cid;
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_market::{
+    CLIENT_DEALS_CONFIG, ClientDealsMap, DEAL_METADATA_CONFIG, DEAL_POLICIES_CONFIG,
+    DealMetadataMap, DealPoliciesMap, METADATA_DEALS_CONFIG, MetadataDealsMap, PIECE_DEALS_CONFIG,
+    PieceDealsMap, State as MarketState,
+};
+use fil_actor_miner::{
+    FAILED_NOTIFICATIONS_AMT_BITWIDTH, PROOF_REFRESH_EPOCHS_AMT_BITWIDTH,
+    PROOF_REFRESH_QUEUE_AMT_BITWIDTH, ProofRefresh, SECTOR_CLAIMS_AMT_BITWIDTH,
+    State as MinerState,
+};
+use fil_actor_multisig::{SIGNER_APPROVALS_CONFIG, SignerApprovalsMap, State as MultisigState};
+use fil_actor_power::{
+    CLAIMS_CONFIG, CRON_QUEUE_AMT_BITWIDTH, CRON_QUEUE_HAMT_BITWIDTH, ChannelCronEventsMap,
+    CronEventKeysSet, MinerPledgesMap, POWER_HISTORY_AMT_BITWIDTH, State as PowerState,
+    SuspendedMinersMap,
+};
+use fil_actor_reward::{BASE_FEE_HISTORY_AMT_BITWIDTH, State as RewardState};
+use fil_actor_verifreg::state::{
+    CLAIM_TERM_REDUCTIONS_CONFIG, ClaimTermReductionMap, VERIFIER_ACTIVITY_CONFIG,
+    VerifierActivityMap,
+};
+use fil_actor_verifreg::{ClaimID, RegistryStats, State as VerifregState};
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::{
+    Array, DEFAULT_HAMT_CONFIG, Map2, Multimap, STORAGE_POWER_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR, parse_uint_key,
+};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::{CborStore, from_slice, to_vec};
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::sector::SectorNumber;
+use ipld_core::ipld::Ipld;
+use multihash_codetable::Code;
+use num_traits::Signed;
+use serde::de::DeserializeOwned;
+use vm_api::ActorState;
+
+// The number of fields of each migrated actor's state in its prior layout.
+const POWER_PRIOR_FIELDS: usize = 17;
+const VERIFREG_PRIOR_FIELDS: usize = 6;
+const MINER_PRIOR_FIELDS: usize = 15;
+const MARKET_PRIOR_FIELDS: usize = 13;
+const MULTISIG_PRIOR_FIELDS: usize = 7;
+const REWARD_PRIOR_FIELDS: usize = 11;
+const DATACAP_PRIOR_FIELDS: usize = 2;
+
+/// Migrates the state of each actor whose state has gained fields from its prior layout,
+/// writing the migrated state to `store` and returning the migrated tree. Actor code is
+/// unchanged.
+///
+/// Indexes and aggregates of existing state are rebuilt from it, and other added fields take
+/// their initial values.
+/// The existing verifiers' activity is counted from `epoch`.
+/// The power actor records the pledge locked by each miner with a claim, and processes
+/// payment channels' cron events from `epoch`.
+/// If enabled by the policy, each sector's proof is due for refresh one refresh period after
+/// `epoch`.
+pub fn migrate_state_tree<BS: Blockstore>(
+    store: &BS,
+    manifest: &BTreeMap<Cid, Type>,
+    policy: &Policy,
+    tree: &BTreeMap<Address, ActorState>,
+    epoch: ChainEpoch,
+) -> anyhow::Result<BTreeMap<Address, ActorState>> {
+    let mut migrated = tree.clone();
+
+    // The verified registry is migrated first, since the miners' records of their claims
+    // are taken from its index of claims by sector.
+    let verifreg = migrated
+        .get_mut(&VERIFIED_REGISTRY_ACTOR_ADDR)
+        .context("verified registry actor not found")?;
+    let verifreg_state =
+        migrate_verifreg(store, &verifreg.state, epoch).context("failed to migrate verifreg")?;
+    verifreg.state = store.put_cbor(&verifreg_state, Code::Blake2b256)?;
+
+    let mut miner_pledges = Vec::new();
+    for (addr, actor) in migrated.iter_mut() {
+        let head = match manifest.get(&actor.code) {
+            Some(Type::Miner) => {
+                let state =
+                    migrate_miner(store, policy, &actor.state, addr, &verifreg_state, epoch)
+                        .with_context(|| format!("failed to migrate miner {addr}"))?;
+                miner_pledges.push((*addr, state.initial_pledge.clone()));
+                store.put_cbor(&state, Code::Blake2b256)?
+            }
+            Some(Type::Market) => {
+                let state =
+                    migrate_market(store, &actor.state).context("failed to migrate market")?;
+                store.put_cbor(&state, Code::Blake2b256)?
+            }
+            Some(Type::Multisig) => {
+                let state = migrate_multisig(store, &actor.state)
+                    .with_context(|| format!("failed to migrate multisig {addr}"))?;
+                store.put_cbor(&state, Code::Blake2b256)?
+            }
+            Some(Type::Reward) => {
+                let state =
+                    migrate_reward(store, &actor.state).context("failed to migrate reward")?;
+                store.put_cbor(&state, Code::Blake2b256)?
+            }
+            Some(Type::DataCap) => {
+                let state =
+                    migrate_datacap(store, &actor.state).context("failed to migrate datacap")?;
+                store.put_cbor(&state, Code::Blake2b256)?
+            }
+            _ => continue,
+        };
+        actor.state = head;
+    }
+
+    // The power actor is migrated last, to record the migrated miners' pledges.
+    let power = migrated.get_mut(&STORAGE_POWER_ACTOR_ADDR).context("power actor not found")?;
+    let power_state = migrate_power(store, &power.state, miner_pledges, epoch)
+        .context("failed to migrate power")?;
+    power.state = store.put_cbor(&power_state, Code::Blake2b256)?;
+
+    Ok(migrated)
+}
+
+// Decodes a state object written in a prior layout of `prior_fields` fields as the current
+// layout, whose remaining fields take the `added` values.
+fn upgrade_state<BS: Blockstore, S: DeserializeOwned>(
+    store: &BS,
+    head: &Cid,
+    prior_fields: usize,
+    added: impl IntoIterator<Item = Ipld>,
+) -> anyhow::Result<S> {
+    let mut fields: Vec<Ipld> =
+        store.get_cbor(head)?.ok_or_else(|| anyhow!("state {head} not found"))?;
+    let len = fields.len();
+    if len != prior_fields {
+        bail!("state {head} has {len} fields, expected the {prior_fields} of its prior layout");
+    }
+    fields.extend(added);
+    Ok(from_slice(&to_vec(&fields)?)?)
+}
+
+fn migrate_verifreg<BS: Blockstore>(
+    store: &BS,
+    head: &Cid,
+    epoch: ChainEpoch,
+) -> anyhow::Result<VerifregState> {
+    let empty_reductions =
+        ClaimTermReductionMap::empty(store, CLAIM_TERM_REDUCTIONS_CONFIG, "claim term reductions")
+            .flush()?;
+    let empty_activity =
+        VerifierActivityMap::empty(store, VERIFIER_ACTIVITY_CONFIG, "verifier activity").flush()?;
+    let empty_sector_claims =
+        Map2::<&BS, u64, Cid>::empty(store, DEFAULT_HAMT_CONFIG, "sector claims").flush()?;
+    let mut state: VerifregState = upgrade_state(
+        store,
+        head,
+        VERIFREG_PRIOR_FIELDS,
+        [
+            Ipld::Null,
+            ipld_core::serde::to_ipld(RegistryStats::default())?,
+            Ipld::Link(empty_reductions),
+            Ipld::Link(empty_activity),
+            Ipld::Link(empty_sector_claims),
+        ],
+    )?;
+    state.rebuild_stats(store)?;
+    state.rebuild_sector_claims(store)?;
+    state.init_verifier_activity(store, epoch)?;
+    Ok(state)
+}
+
+fn migrate_miner<BS: Blockstore>(
+    store: &BS,
+    policy: &Policy,
+    head: &Cid,
+    addr: &Address,
+    verifreg: &VerifregState,
+    epoch: ChainEpoch,
+) -> anyhow::Result<MinerState> {
+    let empty_notifications =
+        Array::<(), BS>::new_with_bit_width(store, FAILED_NOTIFICATIONS_AMT_BITWIDTH).flush()?;
+    let empty_sector_claims =
+        Array::<(), BS>::new_with_bit_width(store, SECTOR_CLAIMS_AMT_BITWIDTH).flush()?;
+    let empty_refresh_epochs =
+        Array::<(), BS>::new_with_bit_width(store, PROOF_REFRESH_EPOCHS_AMT_BITWIDTH).flush()?;
+    let empty_refresh_queue =
+        Array::<(), BS>::new_with_bit_width(store, PROOF_REFRESH_QUEUE_AMT_BITWIDTH).flush()?;
+    let mut state: MinerState = upgrade_state(
+        store,
+        head,
+        MINER_PRIOR_FIELDS,
+        [
+            Ipld::Link(empty_notifications),
+            Ipld::Link(empty_sector_claims),
+            Ipld::Link(empty_refresh_epochs),
+            Ipld::Link(empty_refresh_queue),
+        ],
+    )?;
+
+    let mut indexed_claims = BTreeMap::<SectorNumber, Vec<ClaimID>>::new();
+    verifreg.load_sector_claims(store)?.for_each_in(addr.id()?, |key, ids| {
+        indexed_claims.insert(parse_uint_key(key)?, ids.clone());
+        Ok(())
+    })?;
+
+    // Record the claims indexed for each of the miner's sectors, and schedule the sectors'
+    // proof refreshes.
+    let mut sector_claims = Vec::new();
+    let mut refreshes = Vec::new();
+    state.for_each_sector(store, |sector| {
+        if let Some(ids) = indexed_claims.remove(&sector.sector_number) {
+            sector_claims.push((sector.sector_number, ids));
+        }
+        if policy.proof_refresh_enabled {
+            // An updated sector's proof was last refreshed by its update.
+            let proven_epoch = if sector.sector_key_cid.is_some() {
+                sector.power_base_epoch
+            } else {
+                sector.activation
+            };
+            let refresh_epoch = epoch + policy.proof_refresh_period;
+            refreshes.push((sector.sector_number, ProofRefresh { proven_epoch, refresh_epoch }));
+        }
+        Ok(())
+    })?;
+    state.put_sector_claims(store, sector_claims)?;
+    if policy.proof_refresh_enabled {
+        state.schedule_proof_refreshes(policy, store, refreshes)?;
+    }
+    Ok(state)
+}
+
+fn migrate_market<BS: Blockstore>(store: &BS, head: &Cid) -> anyhow::Result<MarketState> {
+    let empty_client_deals =
+        ClientDealsMap::empty(store, CLIENT_DEALS_CONFIG, "client deals").flush()?;
+    let empty_deal_policies =
+        DealPoliciesMap::empty(store, DEAL_POLICIES_CONFIG, "deal policies").flush()?;
+    let empty_piece_deals =
+        PieceDealsMap::empty(store, PIECE_DEALS_CONFIG, "piece deals").flush()?;
+    let empty_metadata_deals =
+        MetadataDealsMap::empty(store, METADATA_DEALS_CONFIG, "metadata deals").flush()?;
+    let empty_deal_metadata =
+        DealMetadataMap::empty(store, DEAL_METADATA_CONFIG, "deal metadata").flush()?;
+    let mut state: MarketState = upgrade_state(
+        store,
+        head,
+        MARKET_PRIOR_FIELDS,
+        [
+            Ipld::Link(empty_client_deals),
+            Ipld::Bool(false),
+            Ipld::Link(empty_deal_policies),
+            Ipld::Link(empty_piece_deals),
+            Ipld::Link(empty_metadata_deals),
+            Ipld::Link(empty_deal_metadata),
+        ],
+    )?;
+    state.rebuild_deal_indexes(store)?;
+    Ok(state)
+}
+
+fn migrate_multisig<BS: Blockstore>(store: &BS, head: &Cid) -> anyhow::Result<MultisigState> {
+    let empty_approvals =
+        SignerApprovalsMap::empty(store, SIGNER_APPROVALS_CONFIG, "signer approvals").flush()?;
+    let mut state: MultisigState =
+        upgrade_state(store, head, MULTISIG_PRIOR_FIELDS, [Ipld::Link(empty_approvals)])?;
+    state.rebuild_signer_approvals(store)?;
+    Ok(state)
+}
+
+fn migrate_reward<BS: Blockstore>(store: &BS, head: &Cid) -> anyhow::Result<RewardState> {
+    let empty_history =
+        Array::<(), BS>::new_with_bit_width(store, BASE_FEE_HISTORY_AMT_BITWIDTH).flush()?;
+    upgrade_state(store, head, REWARD_PRIOR_FIELDS, [Ipld::Link(empty_history)])
+}
+
+fn migrate_datacap<BS: Blockstore>(store: &BS, head: &Cid) -> anyhow::Result<DataCapState> {
+    upgrade_state(store, head, DATACAP_PRIOR_FIELDS, [Ipld::Null, Ipld::List(vec![])])
+}
+
+fn migrate_power<BS: Blockstore>(
+    store: &BS,
+    head: &Cid,
+    miner_pledges: Vec<(Address, TokenAmount)>,
+    epoch: ChainEpoch,
+) -> anyhow::Result<PowerState> {
+    let empty_history =
+        Array::<(), BS>::new_with_bit_width(store, POWER_HISTORY_AMT_BITWIDTH).flush()?;
+    let empty_suspended =
+        SuspendedMinersMap::empty(store, CLAIMS_CONFIG, "suspended miners").flush()?;
+    let empty_pledges = MinerPledgesMap::empty(store, CLAIMS_CONFIG, "miner pledges").flush()?;
+    let empty_channel_events =
+        ChannelCronEventsMap::empty(store, CLAIMS_CONFIG, "channel cron events").flush()?;
+    let empty_channel_queue =
+        Multimap::new(store, CRON_QUEUE_HAMT_BITWIDTH, CRON_QUEUE_AMT_BITWIDTH).root()?;
+    let empty_event_keys =
+        CronEventKeysSet::empty(store, CLAIMS_CONFIG, "cron event keys").flush()?;
+    let mut state: PowerState = upgrade_state(
+        store,
+        head,
+        POWER_PRIOR_FIELDS,
+        [
+            Ipld::Link(empty_history),
+            Ipld::Integer(0),
+            Ipld::Link(empty_suspended),
+            Ipld::Link(empty_pledges),
+            Ipld::Link(empty_channel_events),
+            Ipld::Link(empty_channel_queue),
+            Ipld::Integer(epoch.into()),
+            Ipld::Link(empty_event_keys),
+        ],
+    )?;
+
+    // Only miners with a claim have their pledge recorded.
+    let claims = state.load_claims(store)?;
+    let mut pledges = state.load_miner_pledges(store)?;
+    for (miner, pledge) in miner_pledges {
+        if pledge.is_positive() && claims.contains_key(&miner)? {
+            pledges.set(&miner, pledge)?;
+        }
+    }
+    state.miner_pledges = pledges.flush()?;
+    state.rebuild_cron_event_index(store)?;
+    Ok(state)
+}
@@ -0,0 +1,160 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use cid::Cid;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::runtime::builtins::Type;
+use fvm_ipld_blockstore::{Blockstore, MemoryBlockstore};
+use fvm_shared::address::Address;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use num_traits::Zero;
+use serde::Serialize;
+use vm_api::ActorState;
+
+use crate::check::check_state_invariants;
+
+pub use self::actors::migrate_state_tree;
+
+mod actors;
+
+/// A blockstore that reads through to an underlying store but keeps all writes in memory,
+/// so that a migration can be run against a state tree without committing anything to it.
+pub struct DryRunBlockstore<'a, BS> {
+    base: &'a BS,
+    writes: MemoryBlockstore,
+}
+
+impl<'a, BS: Blockstore> DryRunBlockstore<'a, BS> {
+    pub fn new(base: &'a BS) -> Self {
+        Self { base, writes: MemoryBlockstore::new() }
+    }
+}
+
+impl<BS: Blockstore> Blockstore for DryRunBlockstore<'_, BS> {
+    fn get(&self, k: &Cid) -> anyhow::Result<Option<Vec<u8>>> {
+        match self.writes.get(k)? {
+            Some(block) => Ok(Some(block)),
+            None => self.base.get(k),
+        }
+    }
+
+    fn put_keyed(&self, k: &Cid, block: &[u8]) -> anyhow::Result<()> {
+        self.writes.put_keyed(k, block)
+    }
+}
+
+/// A summary of an actor's entry in the state tree.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ActorSummary {
+    pub code: String,
+    /// The builtin actor type of the code, if known to the manifest.
+    pub actor_type: Option<String>,
+    pub state: String,
+    pub sequence: u64,
+    /// The balance, in attoFIL.
+    pub balance: String,
+}
+
+impl ActorSummary {
+    fn new(manifest: &BTreeMap<Cid, Type>, actor: &ActorState) -> Self {
+        Self {
+            code: actor.code.to_string(),
+            actor_type: manifest.get(&actor.code).map(|t| t.name().to_string()),
+            state: actor.state.to_string(),
+            sequence: actor.sequence,
+            balance: actor.balance.atto().to_string(),
+        }
+    }
+}
+
+/// The change made by a migration to one actor.
+/// Either summary is absent if the migration created or removed the actor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ActorDiff {
+    pub address: String,
+    pub before: Option<ActorSummary>,
+    pub after: Option<ActorSummary>,
+}
+
+/// The outcome of a migration dry run: the actors it changed, and the invariant violations
+/// found in the state tree before and after migrating.
+/// The report serializes to a machine-readable form (e.g. JSON or CBOR) for comparison
+/// across runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DryRunReport {
+    /// The changed actors, ordered by address. Unchanged actors are omitted.
+    pub diffs: Vec<ActorDiff>,
+    pub invariants_before: Vec<String>,
+    pub invariants_after: Vec<String>,
+}
+
+impl DryRunReport {
+    /// Whether the migration introduced no invariant violations.
+    pub fn passed(&self) -> bool {
+        self.invariants_after.iter().all(|msg| self.invariants_before.contains(msg))
+    }
+}
+
+/// Runs a migration against a state tree without committing it, and reports the changes it
+/// makes and the invariant checks of the tree before and after.
+///
+/// The migration, such as [`migrate_state_tree`], is given a store that reads through to
+/// `store` but retains its writes, and returns the migrated tree. The migrated tree must hold
+/// the same total balance.
+///
+/// The invariants of a tree written in prior state layouts may not be checkable, in which case
+/// the failure to check them is reported as a violation before migrating.
+pub fn dry_run<BS, F>(
+    store: &BS,
+    manifest: &BTreeMap<Cid, Type>,
+    policy: &Policy,
+    tree: &BTreeMap<Address, ActorState>,
+    prior_epoch: ChainEpoch,
+    migration: F,
+) -> anyhow::Result<DryRunReport>
+where
+    BS: Blockstore,
+    F: FnOnce(
+        &DryRunBlockstore<'_, BS>,
+        &BTreeMap<Address, ActorState>,
+    ) -> anyhow::Result<BTreeMap<Address, ActorState>>,
+{
+    let total_balance =
+        tree.values().fold(TokenAmount::zero(), |total, actor| total + &actor.balance);
+    let invariants_before =
+        match check_state_invariants(store, manifest, policy, tree, None, prior_epoch) {
+            Ok(acc) => acc.messages(),
+            Err(e) => vec![format!("failed to check invariants: {e}")],
+        };
+
+    let dry_run_store = DryRunBlockstore::new(store);
+    let migrated = migration(&dry_run_store, tree)?;
+    let invariants_after = check_state_invariants(
+        &dry_run_store,
+        manifest,
+        policy,
+        &migrated,
+        Some(total_balance),
+        prior_epoch,
+    )?
+    .messages();
+
+    let addresses: BTreeSet<&Address> = tree.keys().chain(migrated.keys()).collect();
+    let diffs = addresses
+        .into_iter()
+        .filter_map(|addr| {
+            let before = tree.get(addr);
+            let after = migrated.get(addr);
+            if before == after {
+                return None;
+            }
+            Some(ActorDiff {
+                address: addr.to_string(),
+                before: before.map(|actor| ActorSummary::new(manifest, actor)),
+                after: after.map(|actor| ActorSummary::new(manifest, actor)),
+            })
+        })
+        .collect();
+
+    Ok(DryRunReport { diffs, invariants_before, invariants_after })
+}
@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use anyhow::anyhow;
+use cid::Cid;
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_init::State as InitState;
+use fil_actor_market::State as MarketState;
+use fil_actor_miner::State as MinerState;
+use fil_actor_multisig::State as MultisigState;
+use fil_actor_power::State as PowerState;
+use fil_actor_reward::State as RewardState;
+use fil_actor_verifreg::State as VerifregState;
+use fil_actors_runtime::runtime::builtins::Type;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::{CborStore, DAG_CBOR, from_slice};
+use fvm_shared::address::Address;
+use ipld_core::ipld::Ipld;
+use vm_api::ActorState;
+
+macro_rules! get_state {
+    ($store:ident, $head:ident, $state:ty) => {
+        $store
+            .get_cbor::<$state>($head)?
+            .ok_or_else(|| anyhow!("{} is empty", stringify!($state)))?
+    };
+}
+
+/// The number of blocks in a DAG and their total encoded size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DagSize {
+    pub nodes: u64,
+    pub bytes: u64,
+}
+
+impl DagSize {
+    fn add(&mut self, other: &DagSize) {
+        self.nodes += other.nodes;
+        self.bytes += other.bytes;
+    }
+}
+
+impl fmt::Display for DagSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} nodes, {} bytes", self.nodes, self.bytes)
+    }
+}
+
+/// Walks the DAG rooted at a CID, counting each distinct block once.
+/// Links are followed out of DAG-CBOR blocks only.
+pub fn dag_size<BS: Blockstore>(store: &BS, root: &Cid) -> anyhow::Result<DagSize> {
+    let mut seen = HashSet::<Cid>::new();
+    let mut size = DagSize::default();
+    let mut pending = vec![*root];
+    while let Some(cid) = pending.pop() {
+        if !seen.insert(cid) {
+            continue;
+        }
+        let block = store.get(&cid)?.ok_or_else(|| anyhow!("block {cid} not found"))?;
+        size.nodes += 1;
+        size.bytes += block.len() as u64;
+        if cid.codec() == DAG_CBOR {
+            from_slice::<Ipld>(&block)?.references(&mut pending);
+        }
+    }
+    Ok(size)
+}
+
+/// The size of an actor's state, in total and for each substructure of the state
+/// that is stored in blocks of its own (e.g. the miner's sectors AMT).
+#[derive(Clone, Debug)]
+pub struct ActorStateSize {
+    pub actor_type: Type,
+    pub total: DagSize,
+    pub substructures: BTreeMap<&'static str, DagSize>,
+}
+
+/// Measures the state of an actor of the given type.
+/// Substructures are measured independently, so blocks shared between them
+/// (such as empty collections) are counted in each.
+pub fn actor_state_size<BS: Blockstore>(
+    store: &BS,
+    actor_type: Type,
+    head: &Cid,
+) -> anyhow::Result<ActorStateSize> {
+    let substructures = substructure_roots(store, actor_type, head)?
+        .into_iter()
+        .map(|(name, root)| Ok((name, dag_size(store, &root)?)))
+        .collect::<anyhow::Result<_>>()?;
+    Ok(ActorStateSize { actor_type, total: dag_size(store, head)?, substructures })
+}
+
+// The roots of the substructures of an actor's state, by state field name.
+fn substructure_roots<BS: Blockstore>(
+    store: &BS,
+    actor_type: Type,
+    head: &Cid,
+) -> anyhow::Result<Vec<(&'static str, Cid)>> {
+    let roots = match actor_type {
+        Type::Init => {
+            let st = get_state!(store, head, InitState);
+            vec![("address_map", st.address_map)]
+        }
+        Type::Power => {
+            let st = get_state!(store, head, PowerState);
+            let mut roots = vec![
+                ("cron_event_queue", st.cron_event_queue),
+                ("claims", st.claims),
+                ("power_history", st.power_history),
+                ("suspended_miners", st.suspended_miners),
+                ("miner_pledges", st.miner_pledges),
+                ("channel_cron_events", st.channel_cron_events),
+                ("channel_cron_queue", st.channel_cron_queue),
+                ("cron_event_keys", st.cron_event_keys),
+            ];
+            if let Some(batch) = st.proof_validation_batch {
+                roots.push(("proof_validation_batch", batch));
+            }
+            roots
+        }
+        Type::Miner => {
+            let st = get_state!(store, head, MinerState);
+            vec![
+                ("info", st.info),
+                ("pre_committed_sectors", st.pre_committed_sectors),
+                ("pre_committed_sectors_cleanup", st.pre_committed_sectors_cleanup),
+                ("allocated_sectors", st.allocated_sectors),
+                ("sectors", st.sectors),
+                ("deadlines", st.deadlines),
+                ("failed_notifications", st.failed_notifications),
+                ("sector_claims", st.sector_claims),
+                ("proof_refresh_epochs", st.proof_refresh_epochs),
+                ("proof_refresh_queue", st.proof_refresh_queue),
+            ]
+        }
+        Type::Market => {
+            let st = get_state!(store, head, MarketState);
+            vec![
+                ("proposals", st.proposals),
+                ("states", st.states),
+                ("pending_proposals", st.pending_proposals),
+                ("escrow_table", st.escrow_table),
+                ("locked_table", st.locked_table),
+                ("deal_ops_by_epoch", st.deal_ops_by_epoch),
+                ("pending_deal_allocation_ids", st.pending_deal_allocation_ids),
+                ("provider_sectors", st.provider_sectors),
+                ("client_deals", st.client_deals),
+                ("deal_policies", st.deal_policies),
+                ("piece_deals", st.piece_deals),
+                ("metadata_deals", st.metadata_deals),
+                ("deal_metadata", st.deal_metadata),
+            ]
+        }
+        Type::Multisig => {
+            let st = get_state!(store, head, MultisigState);
+            vec![("pending_txs", st.pending_txs), ("signer_approvals", st.signer_approvals)]
+        }
+        Type::Reward => {
+            let st = get_state!(store, head, RewardState);
+            vec![("base_fee_history", st.base_fee_history)]
+        }
+        Type::VerifiedRegistry => {
+            let st = get_state!(store, head, VerifregState);
+            vec![
+                ("verifiers", st.verifiers),
+                ("remove_data_cap_proposal_ids", st.remove_data_cap_proposal_ids),
+                ("allocations", st.allocations),
+                ("claims", st.claims),
+                ("claim_term_reductions", st.claim_term_reductions),
+                ("verifier_activity", st.verifier_activity),
+                ("sector_claims", st.sector_claims),
+            ]
+        }
+        Type::DataCap => {
+            let st = get_state!(store, head, DataCapState);
+            vec![("balances", st.token.balances), ("allowances", st.token.allowances)]
+        }
+        _ => vec![],
+    };
+    Ok(roots)
+}
+
+/// The sizes of the states of the actors in a state tree.
+#[derive(Clone, Debug, Default)]
+pub struct StateSizeReport {
+    pub actors: BTreeMap<Address, ActorStateSize>,
+}
+
+impl StateSizeReport {
+    /// The total size of actor states, by actor type.
+    /// Blocks shared between actors are counted once for each actor.
+    pub fn totals_by_type(&self) -> BTreeMap<Type, DagSize> {
+        let mut totals = BTreeMap::<Type, DagSize>::new();
+        for size in self.actors.values() {
+            totals.entry(size.actor_type).or_default().add(&size.total);
+        }
+        totals
+    }
+
+    /// The total size of all actor states.
+    pub fn total(&self) -> DagSize {
+        let mut total = DagSize::default();
+        self.actors.values().for_each(|size| total.add(&size.total));
+        total
+    }
+}
+
+impl fmt::Display for StateSizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "state size: {}", self.total())?;
+        for (actor_type, total) in self.totals_by_type() {
+            writeln!(f, "  {}: {}", actor_type.name(), total)?;
+        }
+        for (addr, size) in &self.actors {
+            writeln!(f, "  {} ({}): {}", addr, size.actor_type.name(), size.total)?;
+            for (name, substructure) in &size.substructures {
+                writeln!(f, "    {}: {}", name, substructure)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Measures the states of all actors in a state tree.
+pub fn state_size_report<BS: Blockstore>(
+    store: &BS,
+    manifest: &BTreeMap<Cid, Type>,
+    tree: &BTreeMap<Address, ActorState>,
+) -> anyhow::Result<StateSizeReport> {
+    let mut report = StateSizeReport::default();
+    for (addr, actor) in tree {
+        let actor_type = *manifest.get(&actor.code).ok_or_else(|| {
+            anyhow!("unexpected actor code CID {} for address {}", actor.code, addr)
+        })?;
+        report.actors.insert(*addr, actor_state_size(store, actor_type, &actor.state)?);
+    }
+    Ok(report)
+}
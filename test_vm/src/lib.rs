@@ -1,11 +1,12 @@
 use cid::Cid;
-use fil_actor_account::State as AccountState;
+use fil_actor_account::types::AuthenticateMessageParams;
+use fil_actor_account::{Method as AccountMethod, State as AccountState};
 use fil_actor_cron::{Entry as CronEntry, State as CronState};
 use fil_actor_datacap::State as DataCapState;
 use fil_actor_init::{ExecReturn, State as InitState};
 use fil_actor_market::{Method as MarketMethod, State as MarketState};
 use fil_actor_power::{Method as MethodPower, State as PowerState};
-use fil_actor_reward::State as RewardState;
+use fil_actor_reward::{Method as RewardMethod, State as RewardState, TestingMintParams};
 use fil_actor_system::State as SystemState;
 use fil_actor_verifreg::State as VerifRegState;
 use fil_actors_runtime::DATACAP_TOKEN_ACTOR_ADDR;
@@ -15,7 +16,7 @@ use fil_actors_runtime::runtime::{EMPTY_ARR_CID, Policy, Primitives};
 use fil_actors_runtime::test_blockstores::MemoryBlockstore;
 use fil_actors_runtime::{
     BURNT_FUNDS_ACTOR_ADDR, CRON_ACTOR_ADDR, EAM_ACTOR_ADDR, INIT_ACTOR_ADDR, REWARD_ACTOR_ADDR,
-    STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR,
+    STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR, SYSTEM_ACTOR_ADDR, SYSTEM_ACTOR_ID,
     VERIFIED_REGISTRY_ACTOR_ADDR,
 };
 use fil_actors_runtime::{DEFAULT_HAMT_CONFIG, Map2, test_utils::*};
@@ -23,7 +24,7 @@ use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::CborStore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 use fvm_ipld_hamt::{BytesKey, Hamt, Sha256};
-use fvm_shared::address::Address;
+use fvm_shared::address::{Address, Protocol};
 use fvm_shared::bigint::Zero;
 use fvm_shared::clock::ChainEpoch;
 use fvm_shared::econ::TokenAmount;
@@ -37,7 +38,9 @@ use std::cell::{RefCell, RefMut};
 use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 use vm_api::trace::InvocationTrace;
-use vm_api::{ActorState, MessageResult, MockPrimitives, VM, VMError, new_actor};
+use vm_api::{
+    ActorState, MessageResult, MockPrimitives, UnsignedMessage, VM, VMError, new_actor, vm_err,
+};
 
 use vm_api::util::{get_state, serialize_ok};
 
@@ -60,6 +63,10 @@ pub struct TestVM {
     circulating_supply: RefCell<TokenAmount>,
     base_fee: RefCell<TokenAmount>,
     timestamp: RefCell<u64>,
+    // Exit codes with which invocations of methods on actors are made to abort.
+    invocation_failures: RefCell<HashMap<(Address, MethodNum), ExitCode>>,
+    // Policy with which actors are invoked.
+    policy: Policy,
 }
 
 impl TestVM {
@@ -83,6 +90,8 @@ impl TestVM {
             invocations: RefCell::new(vec![]),
             base_fee: RefCell::new(TokenAmount::zero()),
             timestamp: RefCell::new(0),
+            invocation_failures: RefCell::new(HashMap::new()),
+            policy: Policy::default(),
         }
     }
 
@@ -98,10 +107,9 @@ impl TestVM {
         // system
         let sys_st = SystemState::new(&store).unwrap();
         let sys_head = v.put_store(&sys_st);
-        let sys_value = faucet_total.clone(); // delegate faucet funds to system so we can construct faucet by sending to bls addr
         v.set_actor(
             &SYSTEM_ACTOR_ADDR,
-            new_actor(*SYSTEM_ACTOR_CODE_ID, sys_head, 0, sys_value, None),
+            new_actor(*SYSTEM_ACTOR_CODE_ID, sys_head, 0, TokenAmount::zero(), None),
         );
 
         // init
@@ -112,12 +120,11 @@ impl TestVM {
             new_actor(*INIT_ACTOR_CODE_ID, init_head, 0, TokenAmount::zero(), None),
         );
 
-        // reward
-
-        let reward_head = v.put_store(&RewardState::new(StoragePower::zero()));
+        // reward, also holding the faucet funds to be minted below
+        let reward_head = v.put_store(&RewardState::new(&store, StoragePower::zero()).unwrap());
         v.set_actor(
             &REWARD_ACTOR_ADDR,
-            new_actor(*REWARD_ACTOR_CODE_ID, reward_head, 0, reward_total, None),
+            new_actor(*REWARD_ACTOR_CODE_ID, reward_head, 0, &reward_total + &faucet_total, None),
         );
 
         // cron
@@ -222,19 +229,30 @@ impl TestVM {
         );
 
         // create a faucet with 1 billion FIL for setting up test accounts
-        v.execute_message(
-            &SYSTEM_ACTOR_ADDR,
-            &Address::new_bls(FAUCET_ROOT_KEY).unwrap(),
-            &faucet_total,
-            METHOD_SEND,
-            None,
-        )
-        .unwrap();
+        let ret = v
+            .execute_message(
+                &SYSTEM_ACTOR_ADDR,
+                &REWARD_ACTOR_ADDR,
+                &TokenAmount::zero(),
+                RewardMethod::TestingMintExported as u64,
+                IpldBlock::serialize_cbor(&TestingMintParams {
+                    to: Address::new_bls(FAUCET_ROOT_KEY).unwrap(),
+                    amount: faucet_total,
+                })
+                .unwrap(),
+            )
+            .unwrap();
+        assert!(ret.code.is_success(), "failed to mint faucet funds: {}", ret.message);
 
         v.checkpoint();
         v
     }
 
+    /// Sets the policy with which actors are subsequently invoked.
+    pub fn set_policy(&mut self, policy: Policy) {
+        self.policy = policy;
+    }
+
     pub fn put_store<S>(&self, obj: &S) -> Cid
     where
         S: ser::Serialize,
@@ -260,6 +278,46 @@ impl TestVM {
         *self.state_root.borrow()
     }
 
+    // Invokes AuthenticateMessage on an account actor, as the system actor, without tracing
+    // the invocation or retaining any state change.
+    fn authenticate_message(
+        &self,
+        account: &Address,
+        signature: &[u8],
+        message: &[u8],
+    ) -> Result<(), VMError> {
+        let prior_root = self.checkpoint();
+        let params =
+            AuthenticateMessageParams { signature: signature.to_vec(), message: message.to_vec() };
+        let mut ctx = InvocationCtx {
+            v: self,
+            top: TopCtx {
+                originator_stable_addr: SYSTEM_ACTOR_ADDR,
+                originator_call_seq: 0,
+                new_actor_addr_count: RefCell::new(0),
+                circ_supply: self.circulating_supply.borrow().clone(),
+            },
+            msg: InternalMessage {
+                from: SYSTEM_ACTOR_ID,
+                to: *account,
+                value: TokenAmount::zero(),
+                method: AccountMethod::AuthenticateMessageExported as u64,
+                params: IpldBlock::serialize_cbor(&params).unwrap(),
+            },
+            allow_side_effects: RefCell::new(true),
+            caller_validated: RefCell::new(false),
+            read_only: true,
+            policy: &self.policy,
+            subinvocations: RefCell::new(vec![]),
+            events: RefCell::new(vec![]),
+        };
+        let res = ctx.invoke();
+        self.rollback(prior_root);
+        res.map(|_| ()).map_err(|e| {
+            vm_err(&format!("failed to authenticate message from {}: {}", account, e.msg()))
+        })
+    }
+
     pub fn rollback(&self, root: Cid) {
         self.actors_cache.replace(HashMap::new());
         self.state_root.replace(root);
@@ -322,31 +380,56 @@ impl VM for TestVM {
             allow_side_effects: RefCell::new(true),
             caller_validated: RefCell::new(false),
             read_only: false,
-            policy: &Policy::default(),
+            policy: &self.policy,
             subinvocations: RefCell::new(vec![]),
             events: RefCell::new(vec![]),
         };
         let res = new_ctx.invoke();
 
         let invoc = new_ctx.gather_trace(res.clone());
+        let (event_count, send_count) = (invoc.event_count(), invoc.send_count());
+        // The trace holds the receiver's ID address, if it was resolved.
+        let receiver = invoc.to;
         RefMut::map(self.invocations.borrow_mut(), |invocs| {
             invocs.push(invoc);
             invocs
         });
-        match res {
+        let (code, message, ret) = match res {
             Err(mut ae) => {
                 self.rollback(prior_root);
-                Ok(MessageResult {
-                    code: ae.exit_code(),
-                    message: ae.msg().to_string(),
-                    ret: ae.take_data(),
-                })
+                (ae.exit_code(), ae.msg().to_string(), ae.take_data())
             }
             Ok(ret) => {
                 self.checkpoint();
-                Ok(MessageResult { code: ExitCode::OK, message: "OK".to_string(), ret })
+                (ExitCode::OK, "OK".to_string(), ret)
             }
+        };
+        let receiver_sequence = match receiver.protocol() {
+            Protocol::ID => self.actor(&receiver).map(|a| a.sequence),
+            _ => None,
+        };
+        Ok(MessageResult { code, message, ret, receiver_sequence, event_count, send_count })
+    }
+
+    fn execute_signed_message(
+        &self,
+        from: &Address,
+        to: &Address,
+        value: &TokenAmount,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        signature: &[u8],
+    ) -> Result<MessageResult, VMError> {
+        let from_id = self
+            .resolve_id_address(from)
+            .ok_or_else(|| vm_err(&format!("sender {} not found", from)))?;
+        let sender = self.actor(&from_id).unwrap();
+        if sender.code != *ACCOUNT_ACTOR_CODE_ID {
+            return Err(vm_err(&format!("sender {} is not an account actor", from)));
         }
+        let message = UnsignedMessage::new(from, to, sender.sequence, value, method, &params);
+        self.authenticate_message(&from_id, signature, &message.signing_bytes())?;
+        self.execute_message(from, to, value, method, params)
     }
 
     fn execute_message_implicit(
@@ -443,6 +526,23 @@ impl VM for TestVM {
         self.timestamp.replace(timestamp);
     }
 
+    fn set_invocation_failure(
+        &self,
+        receiver: &Address,
+        method: MethodNum,
+        code: Option<ExitCode>,
+    ) -> Result<(), VMError> {
+        let receiver = self
+            .resolve_id_address(receiver)
+            .ok_or_else(|| vm_err(&format!("receiver {} not found", receiver)))?;
+        let mut failures = self.invocation_failures.borrow_mut();
+        match code {
+            Some(code) => failures.insert((receiver, method), code),
+            None => failures.remove(&(receiver, method)),
+        };
+        Ok(())
+    }
+
     fn mut_primitives(&self) -> &dyn MockPrimitives {
         &self.primitives
     }
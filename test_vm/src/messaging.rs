@@ -24,7 +24,7 @@ use fil_actors_runtime::runtime::{
 };
 use fil_actors_runtime::{ActorError, INIT_ACTOR_ADDR};
 use fil_actors_runtime::{SYSTEM_ACTOR_ID, test_utils::*};
-use fil_actors_runtime::{SendError, actor_error};
+use fil_actors_runtime::{SendError, actor_error, check_emit_allowed};
 use fvm_ipld_encoding::CborStore;
 use fvm_ipld_encoding::ipld_block::IpldBlock;
 
@@ -56,6 +56,7 @@ use fvm_shared::{ActorID, IPLD_RAW, METHOD_CONSTRUCTOR, METHOD_SEND, MethodNum,
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use std::cell::{RefCell, RefMut};
+use vm_api::events::check_event;
 use vm_api::trace::{EmittedEvent, InvocationTrace};
 use vm_api::util::get_state;
 use vm_api::{ActorState, VM, new_actor};
@@ -202,7 +203,8 @@ impl<'invocation> InvocationCtx<'invocation> {
     ) -> InvocationTrace {
         let (ret, code) = match invoke_result {
             Ok(rb) => (rb, ExitCode::OK),
-            Err(ae) => (None, ae.exit_code()),
+            // An error's data is returned to the caller, as by the FVM.
+            Err(mut ae) => (ae.take_data(), ae.exit_code()),
         };
         let mut msg = self.msg.clone();
         msg.to = match self.resolve_target(&self.msg.to) {
@@ -268,6 +270,14 @@ impl<'invocation> InvocationCtx<'invocation> {
         }
         self.msg.to = to_addr;
 
+        // Abort an invocation made to fail, without executing the actor
+        let injected =
+            self.v.invocation_failures.borrow().get(&(to_addr, self.msg.method)).copied();
+        if let Some(code) = injected {
+            self.v.rollback(prior_root);
+            return Err(ActorError::unchecked(code, "injected invocation failure".to_string()));
+        }
+
         // call target actor
         let to_actor = self.v.actor(&to_addr).unwrap();
         let params = self.msg.params.clone();
@@ -281,7 +291,7 @@ impl<'invocation> InvocationCtx<'invocation> {
             Type::Multisig => MultisigActor::invoke_method(self, self.msg.method, params),
             Type::System => SystemActor::invoke_method(self, self.msg.method, params),
             Type::Reward => RewardActor::invoke_method(self, self.msg.method, params),
-            Type::Power => PowerActor::invoke_method(self, self.msg.method, params),
+            Type::Power => <PowerActor>::invoke_method(self, self.msg.method, params),
             Type::PaymentChannel => PaychActor::invoke_method(self, self.msg.method, params),
             Type::VerifiedRegistry => VerifregActor::invoke_method(self, self.msg.method, params),
             Type::DataCap => DataCapActor::invoke_method(self, self.msg.method, params),
@@ -629,7 +639,7 @@ impl Runtime for InvocationCtx<'_> {
     fn charge_gas(&self, _name: &'static str, _compute: i64) {}
 
     fn base_fee(&self) -> TokenAmount {
-        TokenAmount::zero()
+        self.v.base_fee()
     }
 
     fn actor_balance(&self, id: ActorID) -> Option<TokenAmount> {
@@ -641,7 +651,7 @@ impl Runtime for InvocationCtx<'_> {
     }
 
     fn tipset_timestamp(&self) -> u64 {
-        0
+        self.v.timestamp()
     }
 
     fn tipset_cid(&self, _epoch: i64) -> Result<Cid, ActorError> {
@@ -649,6 +659,13 @@ impl Runtime for InvocationCtx<'_> {
     }
 
     fn emit_event(&self, event: &ActorEvent) -> Result<(), ActorError> {
+        check_emit_allowed(self)?;
+        // Every event emitted in the integration suite must conform to its registered schema.
+        let code = self.v.actor(&self.to()).unwrap().code;
+        let emitter = ACTOR_TYPES.get(&code).cloned().unwrap();
+        if let Err(e) = check_event(emitter, event) {
+            panic!("event emitted by {} does not conform to its schema: {}", self.to(), e);
+        }
         self.events
             .borrow_mut()
             .push(EmittedEvent { emitter: self.msg.to.id().unwrap(), event: event.clone() });
@@ -1,4 +1,4 @@
-use fil_actors_integration_tests::tests::account_authenticate_message_test;
+use fil_actors_integration_tests::tests::{account_authenticate_message_test, signed_message_test};
 use fil_actors_runtime::test_blockstores::MemoryBlockstore;
 use test_vm::TestVM;
 
@@ -8,3 +8,10 @@ fn account_authenticate_message() {
     let v = TestVM::new_with_singletons(store);
     account_authenticate_message_test(&v);
 }
+
+#[test]
+fn signed_message() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    signed_message_test(&v);
+}
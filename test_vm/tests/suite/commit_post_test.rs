@@ -1,6 +1,6 @@
 use fil_actors_integration_tests::tests::{
-    missed_first_post_deadline_test, overdue_precommit_test, skip_sector_test,
-    submit_post_succeeds_test,
+    deferred_cron_failure_removes_claim_test, missed_first_post_deadline_test,
+    overdue_precommit_test, skip_sector_test, submit_post_succeeds_test,
 };
 use fil_actors_runtime::test_blockstores::MemoryBlockstore;
 use test_vm::TestVM;
@@ -18,6 +18,13 @@ fn skip_sector() {
     skip_sector_test(&v);
 }
 
+#[test]
+fn deferred_cron_failure_removes_claim() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    deferred_cron_failure_removes_claim_test(&v);
+}
+
 #[test]
 fn missed_first_post_deadline() {
     let store = MemoryBlockstore::new();
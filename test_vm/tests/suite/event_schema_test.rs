@@ -0,0 +1,126 @@
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::test_utils::make_piece_cid;
+use fil_actors_runtime::{EthLog, EventBuilder};
+use fvm_shared::event::Flags;
+use vm_api::events::{EVENT_SCHEMAS, FieldValue, check_event, decode_event};
+use vm_api::trace::{EmittedEvent, ExpectEvent};
+
+#[test]
+fn conforming_events() {
+    let event = EventBuilder::new()
+        .typ("sector-activated")
+        .field_indexed("sector", &1u64)
+        .field_indexed("unsealed-cid", &None::<cid::Cid>)
+        .build()
+        .unwrap();
+    check_event(Type::Miner, &event).unwrap();
+
+    let event = EventBuilder::new()
+        .typ("verifier-balance")
+        .field_indexed("verifier", &100u64)
+        .field("balance", &fvm_shared::bigint::bigint_ser::BigIntSer(&10.into()))
+        .build()
+        .unwrap();
+    check_event(Type::VerifiedRegistry, &event).unwrap();
+
+    let log = EthLog { topics: vec![[1; 32], [2; 32]], data: vec![3] };
+    check_event(Type::EVM, &log.to_event().unwrap()).unwrap();
+
+    // Each event type is registered once.
+    for (i, schema) in EVENT_SCHEMAS.iter().enumerate() {
+        assert!(EVENT_SCHEMAS[i + 1..].iter().all(|s| s.typ != schema.typ), "{}", schema.typ);
+    }
+}
+
+#[test]
+fn non_conforming_events() {
+    let cases = [
+        // Unknown event type.
+        (Type::Miner, EventBuilder::new().typ("sector-exploded").field_indexed("sector", &1u64)),
+        // Emitted by the wrong actor.
+        (Type::Market, EventBuilder::new().typ("sector-terminated").field_indexed("sector", &1u64)),
+        // Missing entry.
+        (Type::Miner, EventBuilder::new().typ("sector-terminated")),
+        // Unexpected entry.
+        (
+            Type::Miner,
+            EventBuilder::new()
+                .typ("sector-terminated")
+                .field_indexed("sector", &1u64)
+                .field("extra", &1u64),
+        ),
+        // Value not indexed.
+        (Type::Miner, EventBuilder::new().typ("sector-terminated").field("sector", &1u64)),
+        // Value of the wrong type.
+        (Type::Miner, EventBuilder::new().typ("sector-terminated").field_indexed("sector", "one")),
+        // Incomplete repeated piece.
+        (
+            Type::Miner,
+            EventBuilder::new()
+                .typ("sector-updated")
+                .field_indexed("sector", &1u64)
+                .field_indexed("unsealed-cid", &None::<cid::Cid>)
+                .field_indexed("piece-cid", &cid::Cid::default()),
+        ),
+        // No type.
+        (Type::Miner, EventBuilder::new().field_indexed("sector", &1u64)),
+    ];
+    for (emitter, builder) in cases {
+        let event = builder.build().unwrap();
+        assert!(check_event(emitter, &event).is_err(), "{:?}", event);
+    }
+
+    // A malformed log.
+    let mut event = EthLog { topics: vec![[1; 32]], data: vec![] }.to_event().unwrap();
+    event.entries[0].flags = Flags::FLAG_INDEXED_KEY;
+    assert!(check_event(Type::EVM, &event).is_err());
+}
+
+#[test]
+fn typed_event_expectations() {
+    let event = EventBuilder::new()
+        .typ("sector-updated")
+        .field_indexed("sector", &1u64)
+        .field_indexed("unsealed-cid", &None::<cid::Cid>)
+        .field_indexed("piece-cid", &make_piece_cid(b"p1"))
+        .field("piece-size", &32u64)
+        .field_indexed("piece-cid", &make_piece_cid(b"p2"))
+        .field("piece-size", &64u64)
+        .build()
+        .unwrap();
+    let decoded = decode_event(&event).unwrap();
+    assert_eq!("sector-updated", decoded.typ);
+    assert_eq!(vec![&FieldValue::Null], decoded.values("unsealed-cid").collect::<Vec<_>>());
+    assert_eq!(
+        vec![&FieldValue::Uint(32), &FieldValue::Uint(64)],
+        decoded.values("piece-size").collect::<Vec<_>>()
+    );
+
+    // Unlisted fields are ignored, and repeated keys match successive entries.
+    let emitted = EmittedEvent { emitter: 1000, event };
+    ExpectEvent::new(1000, "sector-updated").matches(&emitted);
+    ExpectEvent::new(1000, "sector-updated")
+        .field("sector", 1u64)
+        .field("unsealed-cid", None::<cid::Cid>)
+        .field("piece-size", 32u64)
+        .field("piece-size", 64u64)
+        .matches(&emitted);
+
+    let mismatches = [
+        ExpectEvent::new(1001, "sector-updated"),
+        ExpectEvent::new(1000, "sector-activated"),
+        ExpectEvent::new(1000, "sector-updated").field("sector", 2u64),
+        ExpectEvent::new(1000, "sector-updated").field("piece-size", 64u64),
+        ExpectEvent::new(1000, "sector-updated").field("piece-cid", make_piece_cid(b"p2")),
+    ];
+    for expected in mismatches {
+        let result = std::panic::catch_unwind(|| expected.matches(&emitted));
+        assert!(result.is_err(), "{:?}", expected);
+    }
+
+    // Logs and non-conforming events can't be decoded.
+    let log = EthLog { topics: vec![[1; 32]], data: vec![] }.to_event().unwrap();
+    assert!(decode_event(&log).is_err());
+    let event = EventBuilder::new().typ("sector-terminated").build().unwrap();
+    assert!(decode_event(&event).is_err());
+}
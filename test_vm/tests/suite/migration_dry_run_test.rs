@@ -0,0 +1,52 @@
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_builtin_actors_state::migration::dry_run;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::CborStore;
+use fvm_shared::econ::TokenAmount;
+use multihash_codetable::Code;
+use test_vm::{TEST_FAUCET_ADDR, TestVM};
+use vm_api::VM;
+use vm_api::util::DynBlockstore;
+
+#[test]
+fn dry_run_reports_changes_without_committing() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let tree = v.actor_states();
+    let manifest = v.actor_manifest();
+    let policy = Policy::default();
+    let bs = DynBlockstore::wrap(v.blockstore());
+
+    // A migration that bumps the faucet's sequence and writes a new state object.
+    let mut written = None;
+    let report = dry_run(&bs, &manifest, &policy, &tree, v.epoch() - 1, |store, tree| {
+        let mut migrated = tree.clone();
+        let faucet = migrated.get_mut(&TEST_FAUCET_ADDR).unwrap();
+        faucet.sequence += 1;
+        written = Some(store.put_cbor(&"migrated", Code::Blake2b256)?);
+        Ok(migrated)
+    })
+    .unwrap();
+
+    assert!(report.passed(), "{:?}", report);
+    assert_eq!(1, report.diffs.len());
+    let diff = &report.diffs[0];
+    assert_eq!(TEST_FAUCET_ADDR.to_string(), diff.address);
+    assert_eq!(Some("account"), diff.after.as_ref().unwrap().actor_type.as_deref());
+    assert_eq!(diff.before.as_ref().unwrap().sequence + 1, diff.after.as_ref().unwrap().sequence);
+
+    // Nothing was committed.
+    assert!(!v.blockstore().has(&written.unwrap()).unwrap());
+    assert_eq!(tree, v.actor_states());
+
+    // A migration that mints FIL fails the balance invariant.
+    let report = dry_run(&bs, &manifest, &policy, &tree, v.epoch() - 1, |_, tree| {
+        let mut migrated = tree.clone();
+        migrated.get_mut(&TEST_FAUCET_ADDR).unwrap().balance += TokenAmount::from_whole(1);
+        Ok(migrated)
+    })
+    .unwrap();
+    assert!(!report.passed());
+    assert!(report.invariants_after.iter().any(|msg| msg.contains("total token balance")));
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_init::ExecReturn;
+use fil_actor_market::State as MarketState;
+use fil_actor_miner::State as MinerState;
+use fil_actor_multisig::{Method as MsigMethod, ProposeParams, State as MsigState};
+use fil_actor_power::State as PowerState;
+use fil_actor_verifreg::State as VerifregState;
+use fil_actors_integration_tests::tests::prove_commit_sectors3_test;
+use fil_actors_integration_tests::util::create_accounts;
+use fil_actors_runtime::cbor::serialize;
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::runtime::builtins::Type;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::test_utils::MULTISIG_ACTOR_CODE_ID;
+use fil_actors_runtime::{
+    DATACAP_TOKEN_ACTOR_ADDR, INIT_ACTOR_ADDR, STORAGE_MARKET_ACTOR_ADDR, STORAGE_POWER_ACTOR_ADDR,
+    VERIFIED_REGISTRY_ACTOR_ADDR,
+};
+use fil_builtin_actors_state::migration::{dry_run, migrate_state_tree};
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::{CborStore, RawBytes};
+use fvm_shared::METHOD_SEND;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use ipld_core::ipld::Ipld;
+use multihash_codetable::Code;
+use num_traits::Zero;
+use serde::de::DeserializeOwned;
+use test_vm::TestVM;
+use vm_api::util::{DynBlockstore, apply_ok};
+use vm_api::{ActorState, VM};
+
+#[test]
+fn migration_rebuilds_added_state_fields() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    // Leaves a verifier, allocations claimed by a miner's sectors, deals, and cron events.
+    prove_commit_sectors3_test(&v);
+    let msig = create_msig_with_proposal(&v);
+
+    let tree = v.actor_states();
+    let manifest = v.actor_manifest();
+    let policy = Policy::default();
+    let bs = DynBlockstore::wrap(v.blockstore());
+    let prior = to_prior_layouts(&bs, &manifest, &tree);
+    let epoch = v.epoch();
+
+    let report = dry_run(&bs, &manifest, &policy, &prior, epoch - 1, |store, tree| {
+        migrate_state_tree(store, &manifest, &policy, tree, epoch)
+    })
+    .unwrap();
+    assert!(report.passed(), "{:?}", report);
+    assert!(report.invariants_after.is_empty(), "{:?}", report.invariants_after);
+
+    let migrated = migrate_state_tree(&bs, &manifest, &policy, &prior, epoch).unwrap();
+
+    // The added fields which index or summarize existing state match those the actors
+    // maintained, and the other added fields match their initial values.
+    let (before, after): (VerifregState, VerifregState) =
+        states(&bs, &tree, &migrated, &VERIFIED_REGISTRY_ACTOR_ADDR);
+    assert_eq!(before.allocation_governor, after.allocation_governor);
+    assert_eq!(before.stats, after.stats);
+    assert_eq!(before.claim_term_reductions, after.claim_term_reductions);
+    assert_eq!(before.sector_claims, after.sector_claims);
+    after
+        .load_verifier_activity(&bs)
+        .unwrap()
+        .for_each(|_, activity| {
+            assert_eq!(epoch, activity.since);
+            Ok(())
+        })
+        .unwrap();
+
+    let (before, after): (MarketState, MarketState) =
+        states(&bs, &tree, &migrated, &STORAGE_MARKET_ACTOR_ADDR);
+    assert_eq!(before.client_deals, after.client_deals);
+    assert_eq!(before.publishing_paused, after.publishing_paused);
+    assert_eq!(before.deal_policies, after.deal_policies);
+    assert_eq!(before.piece_deals, after.piece_deals);
+    assert_eq!(before.metadata_deals, after.metadata_deals);
+
+    let (before, after): (MsigState, MsigState) = states(&bs, &tree, &migrated, &msig);
+    assert_eq!(before.signer_approvals, after.signer_approvals);
+
+    let mut miners = 0;
+    for (addr, actor) in &tree {
+        if manifest.get(&actor.code) == Some(&Type::Miner) {
+            let (before, after): (MinerState, MinerState) = states(&bs, &tree, &migrated, addr);
+            assert_eq!(before.failed_notifications, after.failed_notifications);
+            assert_eq!(before.sector_claims, after.sector_claims);
+            assert_eq!(before.proof_refresh_epochs, after.proof_refresh_epochs);
+            assert_eq!(before.proof_refresh_queue, after.proof_refresh_queue);
+            miners += 1;
+        }
+    }
+    assert!(miners > 0);
+
+    let (before, after): (PowerState, PowerState) =
+        states(&bs, &tree, &migrated, &STORAGE_POWER_ACTOR_ADDR);
+    assert_eq!(before.cron_event_count, after.cron_event_count);
+    assert_eq!(before.suspended_miners, after.suspended_miners);
+    assert_eq!(before.miner_pledges, after.miner_pledges);
+    assert_eq!(before.channel_cron_events, after.channel_cron_events);
+    assert_eq!(before.channel_cron_queue, after.channel_cron_queue);
+    assert_eq!(epoch, after.first_channel_cron_epoch);
+    assert_eq!(before.cron_event_keys, after.cron_event_keys);
+
+    // Datacap's added fields take the default values it was constructed with.
+    let (before, after): (DataCapState, DataCapState) =
+        states(&bs, &tree, &migrated, &DATACAP_TOKEN_ACTOR_ADDR);
+    assert_eq!(before.mint_limits, after.mint_limits);
+    assert_eq!(before.transfer_receivers, after.transfer_receivers);
+}
+
+// Creates a multisig wallet with a transaction proposed by one of its two signers.
+fn create_msig_with_proposal(v: &dyn VM) -> Address {
+    let signers = create_accounts(v, 2, &TokenAmount::from_whole(10_000));
+    let ctor_params = serialize(
+        &fil_actor_multisig::ConstructorParams {
+            signers: signers.clone(),
+            num_approvals_threshold: 2,
+            unlock_duration: 0,
+            start_epoch: 0,
+        },
+        "multisig ctor params",
+    )
+    .unwrap();
+    let ret: ExecReturn = apply_ok(
+        v,
+        &signers[0],
+        &INIT_ACTOR_ADDR,
+        &TokenAmount::zero(),
+        fil_actor_init::Method::Exec as u64,
+        Some(fil_actor_init::ExecParams {
+            code_cid: *MULTISIG_ACTOR_CODE_ID,
+            constructor_params: ctor_params,
+        }),
+    )
+    .deserialize()
+    .unwrap();
+    let msig = ret.id_address;
+    apply_ok(
+        v,
+        &signers[0],
+        &msig,
+        &TokenAmount::zero(),
+        MsigMethod::Propose as u64,
+        Some(ProposeParams {
+            to: signers[1],
+            value: TokenAmount::zero(),
+            method: METHOD_SEND,
+            params: RawBytes::default(),
+        }),
+    );
+    msig
+}
+
+// Rewrites the state of each actor that gained fields in the layout prior to them,
+// by dropping the trailing fields added since.
+fn to_prior_layouts(
+    store: &impl Blockstore,
+    manifest: &BTreeMap<cid::Cid, Type>,
+    tree: &BTreeMap<Address, ActorState>,
+) -> BTreeMap<Address, ActorState> {
+    let mut prior = tree.clone();
+    for actor in prior.values_mut() {
+        let added = match manifest.get(&actor.code) {
+            Some(Type::Power) => 8,
+            Some(Type::VerifiedRegistry) => 5,
+            Some(Type::Miner) => 4,
+            Some(Type::Market) => 6,
+            Some(Type::Multisig) => 1,
+            Some(Type::Reward) => 1,
+            Some(Type::DataCap) => 2,
+            _ => continue,
+        };
+        let mut fields: Vec<Ipld> = store.get_cbor(&actor.state).unwrap().unwrap();
+        fields.truncate(fields.len() - added);
+        actor.state = store.put_cbor(&fields, Code::Blake2b256).unwrap();
+    }
+    prior
+}
+
+// Loads an actor's state from the original and migrated trees.
+fn states<S: DeserializeOwned>(
+    store: &impl Blockstore,
+    tree: &BTreeMap<Address, ActorState>,
+    migrated: &BTreeMap<Address, ActorState>,
+    addr: &Address,
+) -> (S, S) {
+    let before = store.get_cbor(&tree[addr].state).unwrap().unwrap();
+    let after = store.get_cbor(&migrated[addr].state).unwrap().unwrap();
+    (before, after)
+}
@@ -5,10 +5,13 @@ mod change_beneficiary_test;
 mod change_owner_test;
 mod commit_post_test;
 mod datacap_tests;
+mod event_schema_test;
 mod evm_test;
 mod extend_sectors_test;
 mod init_test;
 mod market_miner_withdrawal_test;
+mod migration_dry_run_test;
+mod migration_test;
 mod multisig_test;
 mod power_scenario_tests;
 mod prove_commit3_test;
@@ -18,6 +21,7 @@ mod replica_update3_test;
 mod terminate_test;
 mod test_vm_test;
 mod verified_claim_test;
+mod verifreg_governance_test;
 mod verifreg_multisig_root_test;
 mod verifreg_remove_datacap_test;
 mod withdraw_balance_test;
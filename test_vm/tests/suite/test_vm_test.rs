@@ -1,5 +1,10 @@
 use fil_actor_account::State as AccountState;
-use fil_actors_integration_tests::util::{assert_invariants, check_invariants};
+use fil_actor_power::CreateMinerParams;
+use fil_actors_integration_tests::util::{
+    assert_invariants, check_invariants, create_accounts, create_miner, create_miner_internal,
+    state_size_report,
+};
+use fil_actors_runtime::runtime::builtins::Type;
 use fil_actors_runtime::runtime::{EMPTY_ARR_CID, Policy};
 use fil_actors_runtime::test_blockstores::MemoryBlockstore;
 use fil_actors_runtime::test_utils::{ACCOUNT_ACTOR_CODE_ID, PAYCH_ACTOR_CODE_ID};
@@ -7,6 +12,7 @@ use fvm_shared::METHOD_SEND;
 use fvm_shared::address::Address;
 use fvm_shared::econ::TokenAmount;
 use fvm_shared::error::ExitCode;
+use fvm_shared::sector::RegisteredPoStProof;
 use num_traits::Zero;
 use test_vm::{FIRST_TEST_USER_ADDR, TEST_FAUCET_ADDR, TestVM};
 use vm_api::util::{get_state, pk_addrs_from};
@@ -108,6 +114,47 @@ fn test_sent() {
     assert_invariants(&v, &Policy::default(), None)
 }
 
+#[test]
+fn message_result_reports_receiver_sequence_and_counts() {
+    let store = MemoryBlockstore::new();
+    let v = TestVM::new_with_singletons(store);
+    let addrs = create_accounts(&v, 2, &TokenAmount::from_whole(10_000));
+    let (a, b) = (addrs[0], addrs[1]);
+
+    // a plain send between initialized accounts makes no further sends and emits no events
+    let mres = v.execute_message(&a, &b, &TokenAmount::from_atto(1u8), METHOD_SEND, None).unwrap();
+    assert_eq!(ExitCode::OK, mres.code);
+    assert_eq!(Some(0), mres.receiver_sequence);
+    assert_eq!((0, 0), (mres.event_count, mres.send_count));
+
+    // the receiver's sequence reflects the messages it has sent
+    let mres = v.execute_message(&b, &a, &TokenAmount::from_atto(1u8), METHOD_SEND, None).unwrap();
+    assert_eq!(Some(1), mres.receiver_sequence);
+
+    // no sequence is reported for a receiver that doesn't exist
+    let mres = v
+        .execute_message(&a, &Address::new_id(88), &TokenAmount::from_atto(1u8), METHOD_SEND, None)
+        .unwrap();
+    assert_eq!(ExitCode::SYS_INVALID_RECEIVER, mres.code);
+    assert_eq!(None, mres.receiver_sequence);
+    assert_eq!((0, 0), (mres.event_count, mres.send_count));
+
+    // creating a miner sends to the init actor, which constructs the miner, which resolves
+    // the worker key; init emits actor-created and power emits miner-created
+    let params = CreateMinerParams {
+        owner: a,
+        worker: a,
+        window_post_proof_type: RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        peer: vec![],
+        multiaddrs: vec![].into(),
+        control_addresses: vec![],
+    };
+    let mres = create_miner_internal(&v, &params, &TokenAmount::zero());
+    assert_eq!(ExitCode::OK, mres.code);
+    assert_eq!(Some(0), mres.receiver_sequence);
+    assert_eq!((2, 3), (mres.event_count, mres.send_count));
+}
+
 #[test]
 fn test_pk_gen() {
     let addrs = pk_addrs_from(5, 2);
@@ -116,3 +163,35 @@ fn test_pk_gen() {
     let first_addr_seeded_six = addrs[0];
     assert_ne!(second_addr_seeded_five, first_addr_seeded_six);
 }
+
+#[test]
+fn state_size_report_covers_actor_substructures() {
+    let v = TestVM::new_with_singletons(MemoryBlockstore::new());
+    let addrs = create_accounts(&v, 1, &TokenAmount::from_whole(10_000));
+    let (miner, _) = create_miner(
+        &v,
+        &addrs[0],
+        &addrs[0],
+        RegisteredPoStProof::StackedDRGWindow32GiBV1P1,
+        &TokenAmount::from_whole(1_000),
+    );
+
+    let report = state_size_report(&v).unwrap();
+    assert_eq!(v.actor_states().len(), report.actors.len());
+
+    let miner_size = &report.actors[&miner];
+    assert_eq!(Type::Miner, miner_size.actor_type);
+    for name in ["info", "sectors", "deadlines"] {
+        let substructure = miner_size.substructures[name];
+        assert!(substructure.nodes > 0 && substructure.bytes > 0, "{name} is empty");
+        assert!(substructure.nodes < miner_size.total.nodes);
+    }
+    // The account actor's state has no substructures.
+    assert!(report.actors[&addrs[0]].substructures.is_empty());
+    assert_eq!(1, report.actors[&addrs[0]].total.nodes);
+
+    let total = report.total();
+    let by_type = report.totals_by_type();
+    assert_eq!(total.nodes, by_type.values().map(|s| s.nodes).sum::<u64>());
+    assert_eq!(total.bytes, by_type.values().map(|s| s.bytes).sum::<u64>());
+}
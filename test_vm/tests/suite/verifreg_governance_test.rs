@@ -0,0 +1,87 @@
+use fil_actor_datacap::State as DataCapState;
+use fil_actor_multisig::{Method as MsigMethod, ProposeParams, ProposeReturn};
+use fil_actor_verifreg::state::get_allocation;
+use fil_actor_verifreg::{
+    AllocateFromGovernanceParams, AllocateFromGovernanceReturn, AllocationRequest,
+    Method as VerifregMethod, State as VerifregState,
+};
+use fil_actors_integration_tests::util::{create_accounts, create_miner};
+use fil_actors_runtime::cbor::{deserialize, serialize};
+use fil_actors_runtime::runtime::Policy;
+use fil_actors_runtime::runtime::policy_constants::MINIMUM_VERIFIED_ALLOCATION_SIZE;
+use fil_actors_runtime::test_blockstores::MemoryBlockstore;
+use fil_actors_runtime::test_utils::make_piece_cid;
+use fil_actors_runtime::{
+    DATACAP_TOKEN_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ADDR, VERIFIED_REGISTRY_ACTOR_ID,
+};
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use fvm_shared::piece::PaddedPieceSize;
+use fvm_shared::sector::RegisteredSealProof;
+use num_traits::Zero;
+use test_vm::{TEST_VERIFREG_ROOT_ADDR, TEST_VERIFREG_ROOT_SIGNER_ADDR, TestVM};
+use vm_api::VM;
+use vm_api::util::{DynBlockstore, apply_ok, get_state};
+
+#[test]
+fn governance_allocation_is_backed_by_minted_datacap() {
+    let store = MemoryBlockstore::new();
+    let mut v = TestVM::new_with_singletons(store);
+    let policy = Policy { governance_allocations_enabled: true, ..Policy::default() };
+    v.set_policy(policy.clone());
+
+    let addrs = create_accounts(&v, 1, &TokenAmount::from_whole(10_000));
+    let seal_proof = RegisteredSealProof::StackedDRG32GiBV1P1;
+    let (miner, _) = create_miner(
+        &v,
+        &addrs[0],
+        &addrs[0],
+        seal_proof.registered_window_post_proof().unwrap(),
+        &TokenAmount::from_whole(1_000),
+    );
+
+    let size = MINIMUM_VERIFIED_ALLOCATION_SIZE as u64;
+    let params = AllocateFromGovernanceParams {
+        allocations: vec![AllocationRequest {
+            provider: miner.id().unwrap(),
+            data: make_piece_cid(b"governance"),
+            size: PaddedPieceSize(size),
+            term_min: policy.minimum_verified_allocation_term,
+            term_max: policy.minimum_verified_allocation_term,
+            expiration: v.epoch() + policy.maximum_verified_allocation_expiration,
+        }],
+    };
+    // The root key is a multisig, whose signer proposes the allocation.
+    let ret: ProposeReturn = apply_ok(
+        &v,
+        &TEST_VERIFREG_ROOT_SIGNER_ADDR,
+        &TEST_VERIFREG_ROOT_ADDR,
+        &TokenAmount::zero(),
+        MsigMethod::Propose as u64,
+        Some(ProposeParams {
+            to: VERIFIED_REGISTRY_ACTOR_ADDR,
+            value: TokenAmount::zero(),
+            method: VerifregMethod::AllocateFromGovernance as u64,
+            params: serialize(&params, "allocate from governance params").unwrap(),
+        }),
+    )
+    .deserialize()
+    .unwrap();
+    assert!(ret.applied);
+    assert_eq!(ExitCode::OK, ret.code);
+    let ret: AllocateFromGovernanceReturn =
+        deserialize(&ret.ret, "allocate from governance return").unwrap();
+
+    // The datacap token accepted the mint to the registry, which holds it for the allocation.
+    let bs = DynBlockstore::wrap(v.blockstore());
+    let st: VerifregState = get_state(&v, &VERIFIED_REGISTRY_ACTOR_ADDR).unwrap();
+    let mut allocs = st.load_allocs(&bs).unwrap();
+    let root = TEST_VERIFREG_ROOT_ADDR.id().unwrap();
+    let alloc = get_allocation(&mut allocs, root, ret.new_allocations[0]).unwrap().unwrap();
+    assert_eq!(miner.id().unwrap(), alloc.provider);
+    let datacap: DataCapState = get_state(&v, &DATACAP_TOKEN_ACTOR_ADDR).unwrap();
+    assert_eq!(
+        TokenAmount::from_whole(size),
+        datacap.balance(&bs, VERIFIED_REGISTRY_ACTOR_ID).unwrap()
+    );
+}
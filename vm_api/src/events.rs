@@ -0,0 +1,488 @@
+use cid::Cid;
+use fvm_ipld_encoding::{CBOR, from_slice};
+use fvm_shared::IPLD_RAW;
+use fvm_shared::address::Address;
+use fvm_shared::bigint::BigInt;
+use fvm_shared::bigint::bigint_ser::BigIntDe;
+use fvm_shared::clock::ChainEpoch;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::event::{ActorEvent, Entry, Flags};
+
+use crate::builtin::Type;
+
+/// The key of the entry carrying an event's type, which is always the first entry.
+pub const EVENT_TYPE_KEY: &str = "$type";
+
+/// The type of an event field's value, which is IPLD-CBOR encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    /// An unsigned integer, such as an actor, deal or sector number.
+    Uint,
+    Epoch,
+    BigInt,
+    TokenAmount,
+    Cid,
+    Address,
+    /// A value of the inner type, or null.
+    Nullable(&'static FieldType),
+}
+
+/// A decoded event field value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldValue {
+    Uint(u64),
+    Epoch(ChainEpoch),
+    BigInt(BigInt),
+    TokenAmount(TokenAmount),
+    Cid(Cid),
+    Address(Address),
+    Null,
+}
+
+impl From<u64> for FieldValue {
+    fn from(v: u64) -> Self {
+        FieldValue::Uint(v)
+    }
+}
+
+impl From<ChainEpoch> for FieldValue {
+    fn from(v: ChainEpoch) -> Self {
+        FieldValue::Epoch(v)
+    }
+}
+
+impl From<BigInt> for FieldValue {
+    fn from(v: BigInt) -> Self {
+        FieldValue::BigInt(v)
+    }
+}
+
+impl From<TokenAmount> for FieldValue {
+    fn from(v: TokenAmount) -> Self {
+        FieldValue::TokenAmount(v)
+    }
+}
+
+impl From<Cid> for FieldValue {
+    fn from(v: Cid) -> Self {
+        FieldValue::Cid(v)
+    }
+}
+
+impl From<Address> for FieldValue {
+    fn from(v: Address) -> Self {
+        FieldValue::Address(v)
+    }
+}
+
+impl<T: Into<FieldValue>> From<Option<T>> for FieldValue {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(FieldValue::Null, Into::into)
+    }
+}
+
+/// An event decoded according to the schema of its type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedEvent {
+    pub typ: String,
+    /// The event's entries other than the type, in order.
+    pub fields: Vec<(String, FieldValue)>,
+}
+
+impl DecodedEvent {
+    /// The values of the entries with a key, in order.
+    pub fn values<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a FieldValue> + 'a {
+        self.fields.iter().filter(move |(k, _)| k == key).map(|(_, v)| v)
+    }
+}
+
+/// The schema of a single entry of an event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldSchema {
+    pub key: &'static str,
+    pub typ: FieldType,
+    /// Whether the value is indexed, as well as the key.
+    pub indexed: bool,
+    /// Whether the entry may be omitted.
+    pub optional: bool,
+}
+
+impl FieldSchema {
+    pub const fn new(key: &'static str, typ: FieldType) -> Self {
+        Self { key, typ, indexed: false, optional: false }
+    }
+
+    pub const fn indexed(key: &'static str, typ: FieldType) -> Self {
+        Self { key, typ, indexed: true, optional: false }
+    }
+
+    pub const fn optional(self) -> Self {
+        Self { optional: true, ..self }
+    }
+}
+
+/// The schema of an event type, emitted by one actor type.
+/// The event's entries follow the type entry in the order of the fields,
+/// followed by any number of repetitions of the repeated fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventSchema {
+    pub actor: Type,
+    pub typ: &'static str,
+    pub fields: &'static [FieldSchema],
+    pub repeated: &'static [FieldSchema],
+}
+
+const fn field(key: &'static str, typ: FieldType) -> FieldSchema {
+    FieldSchema::new(key, typ)
+}
+
+const fn indexed(key: &'static str, typ: FieldType) -> FieldSchema {
+    FieldSchema::indexed(key, typ)
+}
+
+const fn schema(actor: Type, typ: &'static str, fields: &'static [FieldSchema]) -> EventSchema {
+    EventSchema { actor, typ, fields, repeated: &[] }
+}
+
+const DEAL_PARTIES: [FieldSchema; 3] = [
+    indexed("id", FieldType::Uint),
+    indexed("client", FieldType::Uint),
+    indexed("provider", FieldType::Uint),
+];
+
+const SECTOR_PIECE: [FieldSchema; 2] =
+    [indexed("piece-cid", FieldType::Cid), field("piece-size", FieldType::Uint)];
+
+const ALLOCATION: [FieldSchema; 8] = [
+    indexed("id", FieldType::Uint),
+    indexed("client", FieldType::Uint),
+    indexed("provider", FieldType::Uint),
+    indexed("piece-cid", FieldType::Cid),
+    field("piece-size", FieldType::Uint),
+    field("term-min", FieldType::Epoch),
+    field("term-max", FieldType::Epoch),
+    field("expiration", FieldType::Epoch),
+];
+
+const CLAIM: [FieldSchema; 9] = [
+    indexed("id", FieldType::Uint),
+    indexed("client", FieldType::Uint),
+    indexed("provider", FieldType::Uint),
+    indexed("piece-cid", FieldType::Cid),
+    field("piece-size", FieldType::Uint),
+    field("term-min", FieldType::Epoch),
+    field("term-max", FieldType::Epoch),
+    field("term-start", FieldType::Epoch),
+    indexed("sector", FieldType::Uint),
+];
+
+const fn claim_with(extra: FieldSchema) -> [FieldSchema; 10] {
+    let mut fields = [extra; 10];
+    let mut i = 0;
+    while i < CLAIM.len() {
+        fields[i] = CLAIM[i];
+        i += 1;
+    }
+    fields
+}
+
+const CLAIM_WITH_SECTOR_EXPIRY: [FieldSchema; 10] =
+    claim_with(field("sector-expiry", FieldType::Epoch));
+
+const CLAIM_WITH_TERM_LOST: [FieldSchema; 10] = claim_with(field("term-lost", FieldType::Epoch));
+
+/// The schemas of all events emitted by builtin actors, other than the EVM actor's logs.
+pub const EVENT_SCHEMAS: &[EventSchema] = &[
+    // cron
+    schema(
+        Type::Cron,
+        "cron-tick",
+        &[field("invoked", FieldType::Uint), field("failed", FieldType::Uint)],
+    ),
+    // init
+    schema(
+        Type::Init,
+        "actor-created",
+        &[
+            indexed("id", FieldType::Uint),
+            indexed("code", FieldType::Cid),
+            field("robust", FieldType::Address),
+            field("delegated", FieldType::Nullable(&FieldType::Address)),
+            indexed("creator", FieldType::Uint),
+        ],
+    ),
+    schema(
+        Type::Init,
+        "delegated-address-remapped",
+        &[
+            indexed("delegated", FieldType::Address),
+            indexed("previous-id", FieldType::Uint),
+            indexed("id", FieldType::Uint),
+        ],
+    ),
+    // market
+    schema(Type::Market, "deal-published", &DEAL_PARTIES),
+    schema(Type::Market, "deal-activated", &DEAL_PARTIES),
+    schema(Type::Market, "deal-terminated", &DEAL_PARTIES),
+    schema(Type::Market, "deal-completed", &DEAL_PARTIES),
+    schema(
+        Type::Market,
+        "deal-settled",
+        &[
+            indexed("id", FieldType::Uint),
+            indexed("client", FieldType::Uint),
+            indexed("provider", FieldType::Uint),
+            field("amount", FieldType::TokenAmount),
+            field("from-epoch", FieldType::Epoch),
+            field("to-epoch", FieldType::Epoch),
+        ],
+    ),
+    // miner
+    schema(Type::Miner, "sector-precommitted", &[indexed("sector", FieldType::Uint)]),
+    schema(
+        Type::Miner,
+        "precommit-expired",
+        &[indexed("sector", FieldType::Uint), field("deposit-burned", FieldType::TokenAmount)],
+    ),
+    EventSchema {
+        actor: Type::Miner,
+        typ: "sector-activated",
+        fields: &[
+            indexed("sector", FieldType::Uint),
+            indexed("unsealed-cid", FieldType::Nullable(&FieldType::Cid)),
+        ],
+        repeated: &SECTOR_PIECE,
+    },
+    EventSchema {
+        actor: Type::Miner,
+        typ: "sector-updated",
+        fields: &[
+            indexed("sector", FieldType::Uint),
+            indexed("unsealed-cid", FieldType::Nullable(&FieldType::Cid)),
+        ],
+        repeated: &SECTOR_PIECE,
+    },
+    schema(Type::Miner, "sector-terminated", &[indexed("sector", FieldType::Uint)]),
+    schema(
+        Type::Miner,
+        "sector-proof-expired",
+        &[indexed("sector", FieldType::Uint), field("refresh-epoch", FieldType::Epoch)],
+    ),
+    schema(
+        Type::Miner,
+        "proof-refresh-extended",
+        &[indexed("sector", FieldType::Uint), field("refresh-epoch", FieldType::Epoch)],
+    ),
+    schema(
+        Type::Miner,
+        "fee-debt-repaid",
+        &[indexed("payer", FieldType::Uint), field("repaid", FieldType::TokenAmount)],
+    ),
+    schema(
+        Type::Miner,
+        "beneficiary-withdrawal",
+        &[
+            indexed("beneficiary", FieldType::Uint),
+            field("amount", FieldType::TokenAmount),
+            field("remaining-quota", FieldType::TokenAmount),
+            field("expiration", FieldType::Epoch),
+        ],
+    ),
+    // power
+    schema(Type::Power, "miner-suspended", &[indexed("miner", FieldType::Uint)]),
+    schema(Type::Power, "miner-reinstated", &[indexed("miner", FieldType::Uint)]),
+    schema(Type::Power, "miner-created", &[indexed("miner", FieldType::Uint)]),
+    schema(
+        Type::Power,
+        "power-updated",
+        &[
+            indexed("miner", FieldType::Uint),
+            field("raw-power-delta", FieldType::BigInt),
+            field("qa-power-delta", FieldType::BigInt),
+        ],
+    ),
+    schema(Type::Power, "claim-deleted", &[indexed("miner", FieldType::Uint)]),
+    // verified registry
+    schema(
+        Type::VerifiedRegistry,
+        "verifier-balance",
+        &[
+            indexed("verifier", FieldType::Uint),
+            field("balance", FieldType::BigInt),
+            FieldSchema::indexed("client", FieldType::Uint).optional(),
+        ],
+    ),
+    schema(Type::VerifiedRegistry, "allocation", &ALLOCATION),
+    schema(Type::VerifiedRegistry, "allocation-removed", &ALLOCATION),
+    schema(
+        Type::VerifiedRegistry,
+        "datacap-refunded",
+        &[indexed("client", FieldType::Uint), field("amount", FieldType::BigInt)],
+    ),
+    schema(Type::VerifiedRegistry, "claim", &CLAIM_WITH_SECTOR_EXPIRY),
+    schema(Type::VerifiedRegistry, "claim-updated", &CLAIM),
+    schema(Type::VerifiedRegistry, "claim-removed", &CLAIM),
+    schema(Type::VerifiedRegistry, "claim-terminated", &CLAIM_WITH_TERM_LOST),
+];
+
+/// The keys of the EVM actor's log topics, in order, and of its log data.
+const EVM_LOG_TOPIC_KEYS: &[&str] = &["t1", "t2", "t3", "t4"];
+const EVM_LOG_DATA_KEY: &str = "d";
+
+/// Looks up the schema of an event type.
+pub fn event_schema(typ: &str) -> Option<&'static EventSchema> {
+    EVENT_SCHEMAS.iter().find(|s| s.typ == typ)
+}
+
+/// Checks an event emitted by an actor of some type against the schema of its event type.
+/// Fails if the event type is unknown, or is not emitted by the actor type, or if any entry
+/// is missing, unexpected, or has the wrong value type or indexing.
+/// Events emitted by the EVM actor are checked to be well-formed Ethereum logs.
+pub fn check_event(emitter: Type, event: &ActorEvent) -> Result<(), String> {
+    if emitter == Type::EVM {
+        return check_eth_log(event);
+    }
+    let mut entries = event.entries.iter().peekable();
+    let typ = match entries.next() {
+        Some(entry) if entry.key == EVENT_TYPE_KEY => {
+            check_entry_encoding(entry, CBOR, Flags::FLAG_INDEXED_ALL)?;
+            from_slice::<String>(&entry.value)
+                .map_err(|e| format!("failed to decode event type: {}", e))?
+        }
+        _ => return Err(format!("event must begin with a {} entry", EVENT_TYPE_KEY)),
+    };
+    let schema = event_schema(&typ).ok_or_else(|| format!("unknown event type {}", typ))?;
+    if schema.actor != emitter {
+        return Err(format!(
+            "event {} emitted by {} actor, expected {} actor",
+            typ,
+            emitter.name(),
+            schema.actor.name()
+        ));
+    }
+
+    for field in schema.fields {
+        match entries.peek() {
+            Some(entry) if entry.key == field.key => {
+                check_field(field, entry).map_err(|e| format!("event {}: {}", typ, e))?;
+                entries.next();
+            }
+            _ if field.optional => {}
+            _ => return Err(format!("event {}: missing entry {}", typ, field.key)),
+        }
+    }
+    let rest: Vec<&Entry> = entries.collect();
+    if schema.repeated.is_empty() {
+        if let Some(entry) = rest.first() {
+            return Err(format!("event {}: unexpected entry {}", typ, entry.key));
+        }
+        return Ok(());
+    }
+    for group in rest.chunks(schema.repeated.len()) {
+        for (i, field) in schema.repeated.iter().enumerate() {
+            match group.get(i) {
+                Some(entry) if entry.key == field.key => {
+                    check_field(field, entry).map_err(|e| format!("event {}: {}", typ, e))?
+                }
+                Some(entry) => {
+                    return Err(format!("event {}: unexpected entry {}", typ, entry.key));
+                }
+                None => return Err(format!("event {}: missing entry {}", typ, field.key)),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_field(field: &FieldSchema, entry: &Entry) -> Result<(), String> {
+    let flags = if field.indexed { Flags::FLAG_INDEXED_ALL } else { Flags::FLAG_INDEXED_KEY };
+    check_entry_encoding(entry, CBOR, flags)?;
+    check_value(field.typ, &entry.value)
+        .map_err(|e| format!("entry {} is not a valid {:?}: {}", entry.key, field.typ, e))
+}
+
+fn check_value(typ: FieldType, value: &[u8]) -> Result<(), fvm_ipld_encoding::Error> {
+    decode_value(typ, value).map(drop)
+}
+
+/// Decodes an event field value of some type.
+pub fn decode_value(typ: FieldType, value: &[u8]) -> Result<FieldValue, fvm_ipld_encoding::Error> {
+    Ok(match typ {
+        FieldType::Uint => FieldValue::Uint(from_slice(value)?),
+        FieldType::Epoch => FieldValue::Epoch(from_slice(value)?),
+        FieldType::BigInt => FieldValue::BigInt(from_slice::<BigIntDe>(value)?.0),
+        FieldType::TokenAmount => FieldValue::TokenAmount(from_slice(value)?),
+        FieldType::Cid => FieldValue::Cid(from_slice(value)?),
+        FieldType::Address => FieldValue::Address(from_slice(value)?),
+        FieldType::Nullable(inner) => match from_slice::<Option<()>>(value) {
+            Ok(None) => FieldValue::Null,
+            _ => decode_value(*inner, value)?,
+        },
+    })
+}
+
+/// Decodes an event according to the schema of its type, after checking it against the schema.
+/// The emitter is not checked, since it's assumed to be of the actor type of the schema.
+/// Ethereum logs cannot be decoded.
+pub fn decode_event(event: &ActorEvent) -> Result<DecodedEvent, String> {
+    let typ: String = match event.entries.first() {
+        Some(entry) if entry.key == EVENT_TYPE_KEY => {
+            from_slice(&entry.value).map_err(|e| format!("failed to decode event type: {}", e))?
+        }
+        _ => return Err(format!("event must begin with a {} entry", EVENT_TYPE_KEY)),
+    };
+    let schema = event_schema(&typ).ok_or_else(|| format!("unknown event type {}", typ))?;
+    check_event(schema.actor, event)?;
+    let fields = event.entries[1..]
+        .iter()
+        .map(|entry| {
+            // Checked above, so every entry has a schema and a valid value.
+            let field =
+                schema.fields.iter().chain(schema.repeated).find(|f| f.key == entry.key).unwrap();
+            (entry.key.clone(), decode_value(field.typ, &entry.value).unwrap())
+        })
+        .collect();
+    Ok(DecodedEvent { typ, fields })
+}
+
+fn check_entry_encoding(entry: &Entry, codec: u64, flags: Flags) -> Result<(), String> {
+    if entry.codec != codec {
+        return Err(format!(
+            "entry {} has codec {:#x}, expected {:#x}",
+            entry.key, entry.codec, codec
+        ));
+    }
+    if entry.flags != flags {
+        return Err(format!(
+            "entry {} has flags {:?}, expected {:?}",
+            entry.key, entry.flags, flags
+        ));
+    }
+    Ok(())
+}
+
+// Checks an event is an Ethereum log: up to four raw 32-byte topics, in order,
+// followed by any non-empty raw data, all fully indexed.
+fn check_eth_log(event: &ActorEvent) -> Result<(), String> {
+    let mut topics = EVM_LOG_TOPIC_KEYS.iter();
+    let mut entries = event.entries.iter().peekable();
+    while let Some(entry) = entries.next() {
+        check_entry_encoding(entry, IPLD_RAW, Flags::FLAG_INDEXED_ALL)
+            .map_err(|e| format!("log: {}", e))?;
+        if entry.key == EVM_LOG_DATA_KEY {
+            if entries.peek().is_some() {
+                return Err("log: data must be the last entry".to_string());
+            }
+            if entry.value.is_empty() {
+                return Err("log: empty data must be omitted".to_string());
+            }
+        } else if topics.next() == Some(&entry.key.as_str()) {
+            if entry.value.len() != 32 {
+                return Err(format!("log: topic {} must be 32 bytes", entry.key));
+            }
+        } else {
+            return Err(format!("log: unexpected entry {}", entry.key));
+        }
+    }
+    Ok(())
+}
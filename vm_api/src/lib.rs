@@ -4,6 +4,7 @@ use anyhow::Error;
 use cid::Cid;
 use fvm_ipld_blockstore::Blockstore;
 use fvm_ipld_encoding::{
+    RawBytes,
     ipld_block::IpldBlock,
     tuple::{Deserialize_tuple, Serialize_tuple, serde_tuple},
 };
@@ -31,6 +32,7 @@ use trace::*;
 
 pub mod builtin;
 mod error;
+pub mod events;
 pub mod trace;
 #[cfg(feature = "testing")]
 pub mod util;
@@ -63,6 +65,21 @@ pub trait VM {
         params: Option<IpldBlock>,
     ) -> Result<MessageResult, VMError>;
 
+    /// Send a message signed by the sender, authenticating the signature before execution by
+    /// invoking AuthenticateMessage on the sender's account actor, rather than trusting the
+    /// sender address. The signature is over the signing bytes of the UnsignedMessage at the
+    /// sender's current sequence. The message is not applied if the sender is not an account
+    /// actor or the signature is invalid.
+    fn execute_signed_message(
+        &self,
+        from: &Address,
+        to: &Address,
+        value: &TokenAmount,
+        method: MethodNum,
+        params: Option<IpldBlock>,
+        signature: &[u8],
+    ) -> Result<MessageResult, VMError>;
+
     /// Send a message without charging gas
     fn execute_message_implicit(
         &self,
@@ -113,6 +130,18 @@ pub trait VM {
 
     /// Set the current timestamp
     fn set_timestamp(&self, timestamp: u64);
+
+    /// Causes every subsequent invocation of a method on an actor to abort with an exit code,
+    /// without executing the actor, or restores normal execution if the code is None.
+    /// VMs which don't support failure injection return an error.
+    fn set_invocation_failure(
+        &self,
+        _receiver: &Address,
+        _method: MethodNum,
+        _code: Option<ExitCode>,
+    ) -> Result<(), VMError> {
+        Err(vm_err("invocation failure injection is not supported by this VM"))
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -120,6 +149,50 @@ pub struct MessageResult {
     pub code: ExitCode,
     pub message: String,
     pub ret: Option<IpldBlock>,
+    /// The sequence of the receiving actor after the message, or None if it doesn't exist.
+    pub receiver_sequence: Option<u64>,
+    /// The number of events emitted and not reverted while executing the message.
+    pub event_count: usize,
+    /// The number of sends made while executing the message, at any depth.
+    pub send_count: usize,
+}
+
+/// The content of a message signed by its sender.
+#[derive(Serialize_tuple, Deserialize_tuple, Clone, PartialEq, Eq, Debug)]
+pub struct UnsignedMessage {
+    pub from: Address,
+    pub to: Address,
+    pub sequence: u64,
+    pub value: TokenAmount,
+    pub method: MethodNum,
+    pub params: RawBytes,
+}
+
+impl UnsignedMessage {
+    pub fn new(
+        from: &Address,
+        to: &Address,
+        sequence: u64,
+        value: &TokenAmount,
+        method: MethodNum,
+        params: &Option<IpldBlock>,
+    ) -> Self {
+        UnsignedMessage {
+            from: *from,
+            to: *to,
+            sequence,
+            value: value.clone(),
+            method,
+            params: params
+                .as_ref()
+                .map_or_else(RawBytes::default, |p| RawBytes::new(p.data.clone())),
+        }
+    }
+
+    /// The bytes over which the sender signs the message.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        fvm_ipld_encoding::to_vec(self).unwrap()
+    }
 }
 
 // Duplicates an internal FVM type (fvm::state_tree::ActorState) that cannot be depended on here
@@ -5,6 +5,8 @@ use fvm_shared::error::{ErrorNumber, ExitCode};
 use fvm_shared::event::ActorEvent;
 use fvm_shared::{ActorID, MethodNum};
 
+use crate::events::{FieldValue, decode_event};
+
 type ReturnValue = Option<IpldBlock>;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -13,6 +15,59 @@ pub struct EmittedEvent {
     pub event: ActorEvent,
 }
 
+/// An expectation for an emitted event, matched against the event decoded by the schema of
+/// its type.
+/// Only the listed fields are checked; any others, including optional fields, are ignored.
+/// A key listed more than once matches successive entries with that key, as for
+/// repeated fields.
+#[derive(Clone, Debug)]
+pub struct ExpectEvent {
+    pub emitter: ActorID,
+    pub typ: &'static str,
+    pub fields: Vec<(&'static str, FieldValue)>,
+}
+
+impl ExpectEvent {
+    pub fn new(emitter: ActorID, typ: &'static str) -> Self {
+        Self { emitter, typ, fields: vec![] }
+    }
+
+    /// Expects a field to have a value.
+    pub fn field(mut self, key: &'static str, value: impl Into<FieldValue>) -> Self {
+        self.fields.push((key, value.into()));
+        self
+    }
+
+    /// Asserts that an emitted event matches this expectation.
+    pub fn matches(&self, emitted: &EmittedEvent) {
+        assert_eq!(
+            self.emitter, emitted.emitter,
+            "event {}: unexpected emitter: expected: {}, was: {}",
+            self.typ, self.emitter, emitted.emitter
+        );
+        let decoded = decode_event(&emitted.event)
+            .unwrap_or_else(|e| panic!("event {}: invalid emitted event: {}", self.typ, e));
+        assert_eq!(
+            self.typ, decoded.typ,
+            "unexpected event type: expected: {}, was: {}",
+            self.typ, decoded.typ
+        );
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            let occurrence = self.fields[..i].iter().filter(|(k, _)| k == key).count();
+            let actual = decoded.values(key).nth(occurrence);
+            assert_eq!(
+                Some(value),
+                actual,
+                "event {}: unexpected value of {}: expected: {:?}, was: {:?}",
+                self.typ,
+                key,
+                value,
+                actual
+            );
+        }
+    }
+}
+
 /// A trace of an actor method invocation.
 #[derive(Clone, Debug)]
 pub struct InvocationTrace {
@@ -25,11 +80,28 @@ pub struct InvocationTrace {
     pub error_number: Option<ErrorNumber>,
     // no need to check return_value or exit_code if error_number is set
     pub exit_code: ExitCode,
+    // the data of the error, if the invocation failed
     pub return_value: ReturnValue,
     pub subinvocations: Vec<InvocationTrace>,
     pub events: Vec<EmittedEvent>,
 }
 
+impl InvocationTrace {
+    /// The number of events emitted by this invocation and those it made, excluding those
+    /// reverted by the failure of the invocation that emitted them or of any caller.
+    pub fn event_count(&self) -> usize {
+        if self.exit_code != ExitCode::OK || self.error_number.is_some() {
+            return 0;
+        }
+        self.events.len() + self.subinvocations.iter().map(Self::event_count).sum::<usize>()
+    }
+
+    /// The number of sends made by this invocation and those it made.
+    pub fn send_count(&self) -> usize {
+        self.subinvocations.iter().map(|s| 1 + s.send_count()).sum()
+    }
+}
+
 /// An expectation for a method invocation trace.
 /// Non-optional fields must always be specified, and are always checked against any trace.
 /// Optional fields are ignored when checking the expectation against a trace.
@@ -53,6 +125,9 @@ pub struct ExpectInvocation {
     pub return_value: Option<ReturnValue>,
     pub subinvocs: Option<Vec<ExpectInvocation>>,
     pub events: Option<Vec<EmittedEvent>>,
+    /// Expectations for the emitted events, matched by the schemas of their types.
+    /// May be used instead of, or as well as, exactly matching events.
+    pub typed_events: Option<Vec<ExpectEvent>>,
 }
 
 impl ExpectInvocation {
@@ -138,6 +213,24 @@ impl ExpectInvocation {
             }
         }
 
+        if let Some(expected_events) = &self.typed_events {
+            let emitted_events = &invoc.events;
+            assert_eq!(
+                emitted_events.len(),
+                expected_events.len(),
+                "{} {} emitted={}, expected={}, {:?}, {:?}",
+                id,
+                "length of expected and emitted events do not match",
+                emitted_events.len(),
+                expected_events.len(),
+                emitted_events,
+                expected_events
+            );
+            for (emitted, expected) in emitted_events.iter().zip(expected_events.iter()) {
+                expected.matches(emitted);
+            }
+        }
+
         if let Some(expect_subinvocs) = &self.subinvocs {
             let subinvocs = &invoc.subinvocations;
 
@@ -209,6 +302,7 @@ impl Default for ExpectInvocation {
             return_value: None,
             subinvocs: None,
             events: None,
+            typed_events: None,
         }
     }
 }
@@ -13,7 +13,7 @@ mod blockstore;
 pub use blockstore::*;
 use serde::de::DeserializeOwned;
 
-use crate::VM;
+use crate::{UnsignedMessage, VM};
 
 /// Generate count addresses by seeding an rng
 pub fn pk_addrs_from(seed: u64, count: u64) -> Vec<Address> {
@@ -58,6 +58,39 @@ pub fn apply_code<S: Serialize>(
     res.ret.map_or(RawBytes::default(), |b| RawBytes::new(b.data))
 }
 
+/// Signs a message from the sender at its current sequence with a signature that the
+/// fake signature verification of test primitives accepts (the signing bytes themselves).
+pub fn sign_message(
+    v: &dyn VM,
+    from: &Address,
+    to: &Address,
+    value: &TokenAmount,
+    method: MethodNum,
+    params: &Option<IpldBlock>,
+) -> Vec<u8> {
+    let from_id = v.resolve_id_address(from).unwrap();
+    let sequence = v.actor(&from_id).unwrap().sequence;
+    UnsignedMessage::new(from, to, sequence, value, method, params).signing_bytes()
+}
+
+/// Signs a message from the sender and applies it with authentication of the signature,
+/// asserting the exit code.
+pub fn apply_signed_code<S: Serialize>(
+    v: &dyn VM,
+    from: &Address,
+    to: &Address,
+    value: &TokenAmount,
+    method: MethodNum,
+    params: Option<S>,
+    code: ExitCode,
+) -> RawBytes {
+    let params = params.map(|p| IpldBlock::serialize_cbor(&p).unwrap().unwrap());
+    let signature = sign_message(v, from, to, value, method, &params);
+    let res = v.execute_signed_message(from, to, value, method, params, &signature).unwrap();
+    assert_eq!(code, res.code, "expected code {}, got {} ({})", code, res.code, res.message);
+    res.ret.map_or(RawBytes::default(), |b| RawBytes::new(b.data))
+}
+
 pub fn apply_ok_implicit<S: Serialize>(
     v: &dyn VM,
     from: &Address,